@@ -161,19 +161,27 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // List all users (cross-tenant view - usually restricted in production)
     let (all_users, _, _) = user_service
-        .list_users(ListUsersRequest {
-            path_prefix: None,
-            pagination: None,
-        })
+        .list_users(
+            &root_context,
+            ListUsersRequest {
+                path_prefix: None,
+                pagination: None,
+                include_descendants: false,
+            },
+        )
         .await?;
     println!("Total users across all tenants: {}", all_users.len());
 
     // Company A can only see its users (using company-a context)
     let (company_a_users, _, _) = user_service
-        .list_users(ListUsersRequest {
-            path_prefix: Some("/company-a/".to_string()),
-            pagination: None,
-        })
+        .list_users(
+            &company_a_context,
+            ListUsersRequest {
+                path_prefix: Some("/company-a/".to_string()),
+                pagination: None,
+                include_descendants: false,
+            },
+        )
         .await?;
     println!(
         "\nCompany A users (filtered by path): {}",
@@ -185,10 +193,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Company B can only see its users (using company-b context)
     let (company_b_users, _, _) = user_service
-        .list_users(ListUsersRequest {
-            path_prefix: Some("/company-b/".to_string()),
-            pagination: None,
-        })
+        .list_users(
+            &company_b_context,
+            ListUsersRequest {
+                path_prefix: Some("/company-b/".to_string()),
+                pagination: None,
+                include_descendants: false,
+            },
+        )
         .await?;
     println!(
         "\nCompany B users (filtered by path): {}",
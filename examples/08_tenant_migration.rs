@@ -190,18 +190,26 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("\n\nStep 5: Verifying migration...\n");
 
     let (old_users, _, _) = user_service
-        .list_users(ListUsersRequest {
-            path_prefix: Some("/users/".to_string()),
-            pagination: None,
-        })
+        .list_users(
+            &old_tenant_context,
+            ListUsersRequest {
+                path_prefix: Some("/users/".to_string()),
+                pagination: None,
+                include_descendants: false,
+            },
+        )
         .await?;
     println!("Users remaining in old-tenant: {}", old_users.len());
 
     let (new_users, _, _) = user_service
-        .list_users(ListUsersRequest {
-            path_prefix: Some("/users/".to_string()),
-            pagination: None,
-        })
+        .list_users(
+            &new_tenant_context,
+            ListUsersRequest {
+                path_prefix: Some("/users/".to_string()),
+                pagination: None,
+                include_descendants: false,
+            },
+        )
         .await?;
     println!("Users now in new-tenant: {}", new_users.len());
     for user in &new_users {
@@ -232,11 +232,30 @@ async fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
     // === LIST ALL USERS ===
     println!("\n\nStep 4: Unified view across hybrid environment...\n");
 
+    // Listing across tenants requires a root context
+    let root_context = wami::context::WamiContext::builder()
+        .instance_id("dc1-prod")
+        .tenant_path(wami::arn::TenantPath::single(0))
+        .caller_arn(
+            wami::arn::WamiArn::builder()
+                .service(wami::arn::Service::Iam)
+                .tenant_path(wami::arn::TenantPath::single(0))
+                .wami_instance("dc1-prod")
+                .resource("user", "root")
+                .build()?,
+        )
+        .is_root(true)
+        .build()?;
+
     let (all_users, _, _) = user_service
-        .list_users(ListUsersRequest {
-            path_prefix: None,
-            pagination: None,
-        })
+        .list_users(
+            &root_context,
+            ListUsersRequest {
+                path_prefix: None,
+                pagination: None,
+                include_descendants: false,
+            },
+        )
         .await?;
     println!(
         "✓ Total users across hybrid environment: {}",
@@ -176,11 +176,30 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // === DEMONSTRATE UNIFIED VIEW ===
     println!("\n\nStep 5: Unified view across providers...\n");
 
+    // Listing across tenants requires a root context
+    let root_context = WamiContext::builder()
+        .instance_id("123456789012")
+        .tenant_path(TenantPath::single(0))
+        .caller_arn(
+            WamiArn::builder()
+                .service(wami::arn::Service::Iam)
+                .tenant_path(TenantPath::single(0))
+                .wami_instance("123456789012")
+                .resource("user", "root")
+                .build()?,
+        )
+        .is_root(true)
+        .build()?;
+
     let (all_roles, _, _) = role_service
-        .list_roles(ListRolesRequest {
-            path_prefix: None,
-            pagination: None,
-        })
+        .list_roles(
+            &root_context,
+            ListRolesRequest {
+                path_prefix: None,
+                pagination: None,
+                include_descendants: false,
+            },
+        )
         .await?;
     println!(
         "✓ Total roles/identities across all providers: {}",
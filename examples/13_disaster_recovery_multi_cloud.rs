@@ -170,11 +170,30 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // === DEMONSTRATE STATUS ===
     println!("\n\nStep 4: Disaster recovery status...\n");
 
+    // Listing across tenants requires a root context
+    let root_context = WamiContext::builder()
+        .instance_id("111111111111")
+        .tenant_path(TenantPath::single(0))
+        .caller_arn(
+            WamiArn::builder()
+                .service(wami::arn::Service::Iam)
+                .tenant_path(TenantPath::single(0))
+                .wami_instance("111111111111")
+                .resource("user", "root")
+                .build()?,
+        )
+        .is_root(true)
+        .build()?;
+
     let (all_users, _, _) = user_service
-        .list_users(ListUsersRequest {
-            path_prefix: Some("/critical/".to_string()),
-            pagination: None,
-        })
+        .list_users(
+            &root_context,
+            ListUsersRequest {
+                path_prefix: Some("/critical/".to_string()),
+                pagination: None,
+                include_descendants: false,
+            },
+        )
         .await?;
 
     let aws_users: Vec<_> = all_users
@@ -70,6 +70,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         action_names: vec!["s3:GetObject".to_string()],
         resource_arns: Some(vec!["arn:aws:s3:::my-bucket/file.txt".to_string()]),
         context_entries: None,
+        caller_arn: None,
+        resource_owner: None,
+        resource_policy: None,
     };
 
     let result = eval_service.simulate_custom_policy(sim_req).await?;
@@ -82,6 +85,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         action_names: vec!["s3:DeleteObject".to_string()],
         resource_arns: Some(vec!["arn:aws:s3:::my-bucket/file.txt".to_string()]),
         context_entries: None,
+        caller_arn: None,
+        resource_owner: None,
+        resource_policy: None,
     };
 
     let denied_result = eval_service.simulate_custom_policy(denied_req).await?;
@@ -85,6 +85,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         duration_seconds: Some(3600),
         external_id: None,
         policy: None,
+        tags: vec![],
+        transitive_tag_keys: vec![],
     };
 
     let response = sts_service
@@ -10,10 +10,11 @@
 //! Run with: `cargo run --example 21_sso_setup_basic`
 
 use std::sync::{Arc, RwLock};
+use wami::arn::{TenantPath, WamiArn};
+use wami::context::WamiContext;
 use wami::provider::AwsProvider;
 use wami::service::{InstanceService as SsoInstanceService, PermissionSetService};
 use wami::store::memory::InMemoryWamiStore;
-use wami::wami::sso_admin::instance::SsoInstance;
 use wami::wami::sso_admin::permission_set::PermissionSet;
 
 #[tokio::main]
@@ -22,23 +23,29 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let store = Arc::new(RwLock::new(InMemoryWamiStore::default()));
     let _provider = Arc::new(AwsProvider::new());
-    let _account_id = "123456789012";
+    let account_id = "123456789012";
+
+    let context = WamiContext::builder()
+        .instance_id(account_id)
+        .tenant_path(TenantPath::single(0))
+        .caller_arn(
+            WamiArn::builder()
+                .service(wami::arn::Service::SsoAdmin)
+                .tenant_path(TenantPath::single(0))
+                .wami_instance(account_id)
+                .resource("user", "admin")
+                .build()?,
+        )
+        .is_root(false)
+        .build()?;
 
     // Create SSO instance
     println!("Step 1: Creating SSO instance...\n");
     let instance_service = SsoInstanceService::new(store.clone());
 
-    let instance = SsoInstance {
-        instance_arn: "arn:aws:sso:::instance/ssoins-1234567890abcdef".to_string(),
-        identity_store_id: "d-1234567890".to_string(),
-        name: Some("MyOrganization SSO".to_string()),
-        status: "ACTIVE".to_string(),
-        created_date: chrono::Utc::now(),
-        wami_arn: "arn:wami:sso-admin:root:wami:123456789012:instance/myorg-sso".parse()?,
-        providers: vec![],
-    };
-
-    instance_service.create_instance(instance.clone()).await?;
+    let instance = instance_service
+        .create_instance(&context, "d-1234567890", account_id)
+        .await?;
     println!("✓ Created SSO instance: {}", instance.instance_arn);
 
     // Create permission sets
@@ -51,6 +58,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         description: Some("Full admin access".to_string()),
         session_duration: Some("PT8H".to_string()),
         relay_state: None,
+        inline_policy: None,
         created_date: chrono::Utc::now(),
         instance_arn: instance.instance_arn.clone(),
         wami_arn: "arn:wami:sso-admin:root:wami:123456789012:permission-set/admin".parse()?,
@@ -240,7 +240,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         ],
     };
     let updated_auth0 = service
-        .update_oidc_thumbprints(update_thumbprint_request)
+        .update_open_id_connect_provider_thumbprint(update_thumbprint_request)
         .await?;
     println!(
         "✅ Updated thumbprints. Total: {}",
@@ -142,6 +142,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         resource_arns: Some(vec!["*".to_string()]),
         policy_input_list: Some(vec![admin_policy_doc.to_string()]),
         context_entries: None,
+        resource_owner: None,
+        resource_policy: None,
     };
 
     let results = evaluation_service
@@ -190,6 +192,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         resource_arns: Some(vec!["*".to_string()]),
         policy_input_list: Some(vec![admin_policy_doc.to_string()]),
         context_entries: None,
+        resource_owner: None,
+        resource_policy: None,
     };
 
     let results_with_boundary = evaluation_service
@@ -293,6 +297,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         resource_arns: Some(vec!["*".to_string()]),
         policy_input_list: Some(vec![admin_policy_doc.to_string()]),
         context_entries: None,
+        resource_owner: None,
+        resource_policy: None,
     };
 
     let role_results = evaluation_service
@@ -0,0 +1,143 @@
+-- Schema for `store::sql::SqlWamiStore`
+--
+-- Every resource table has the same shape: the WAMI ARN is the primary key,
+-- `name` is the natural lookup key used by the store traits (user name,
+-- access key ID, policy ARN, ...), and the full resource is kept as JSON in
+-- `data`. `user_name`, `tenant_id` and `path` are extracted for filtering.
+
+CREATE TABLE IF NOT EXISTS wami_users (
+    wami_arn  TEXT PRIMARY KEY,
+    name      TEXT NOT NULL UNIQUE,
+    user_name TEXT,
+    tenant_id TEXT NOT NULL,
+    path      TEXT,
+    data      JSONB NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS wami_groups (
+    wami_arn  TEXT PRIMARY KEY,
+    name      TEXT NOT NULL UNIQUE,
+    user_name TEXT,
+    tenant_id TEXT NOT NULL,
+    path      TEXT,
+    data      JSONB NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS wami_roles (
+    wami_arn  TEXT PRIMARY KEY,
+    name      TEXT NOT NULL UNIQUE,
+    user_name TEXT,
+    tenant_id TEXT NOT NULL,
+    path      TEXT,
+    data      JSONB NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS wami_policies (
+    wami_arn  TEXT PRIMARY KEY,
+    name      TEXT NOT NULL UNIQUE,
+    user_name TEXT,
+    tenant_id TEXT NOT NULL,
+    path      TEXT,
+    data      JSONB NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS wami_access_keys (
+    wami_arn  TEXT PRIMARY KEY,
+    name      TEXT NOT NULL UNIQUE,
+    user_name TEXT,
+    tenant_id TEXT NOT NULL,
+    path      TEXT,
+    data      JSONB NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS wami_mfa_devices (
+    wami_arn  TEXT PRIMARY KEY,
+    name      TEXT NOT NULL UNIQUE,
+    user_name TEXT,
+    tenant_id TEXT NOT NULL,
+    path      TEXT,
+    data      JSONB NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS wami_login_profiles (
+    wami_arn  TEXT PRIMARY KEY,
+    name      TEXT NOT NULL UNIQUE,
+    user_name TEXT,
+    tenant_id TEXT NOT NULL,
+    path      TEXT,
+    data      JSONB NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS wami_server_certificates (
+    wami_arn  TEXT PRIMARY KEY,
+    name      TEXT NOT NULL UNIQUE,
+    user_name TEXT,
+    tenant_id TEXT NOT NULL,
+    path      TEXT,
+    data      JSONB NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS wami_service_credentials (
+    wami_arn  TEXT PRIMARY KEY,
+    name      TEXT NOT NULL UNIQUE,
+    user_name TEXT,
+    tenant_id TEXT NOT NULL,
+    path      TEXT,
+    data      JSONB NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS wami_signing_certificates (
+    wami_arn  TEXT PRIMARY KEY,
+    name      TEXT NOT NULL UNIQUE,
+    user_name TEXT,
+    tenant_id TEXT NOT NULL,
+    path      TEXT,
+    data      JSONB NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS wami_users_tenant_idx ON wami_users (tenant_id);
+CREATE INDEX IF NOT EXISTS wami_users_path_idx ON wami_users (path);
+CREATE INDEX IF NOT EXISTS wami_groups_tenant_idx ON wami_groups (tenant_id);
+CREATE INDEX IF NOT EXISTS wami_groups_path_idx ON wami_groups (path);
+CREATE INDEX IF NOT EXISTS wami_roles_tenant_idx ON wami_roles (tenant_id);
+CREATE INDEX IF NOT EXISTS wami_roles_path_idx ON wami_roles (path);
+CREATE INDEX IF NOT EXISTS wami_policies_tenant_idx ON wami_policies (tenant_id);
+CREATE INDEX IF NOT EXISTS wami_policies_path_idx ON wami_policies (path);
+CREATE INDEX IF NOT EXISTS wami_access_keys_user_idx ON wami_access_keys (user_name);
+CREATE INDEX IF NOT EXISTS wami_access_keys_tenant_idx ON wami_access_keys (tenant_id);
+CREATE INDEX IF NOT EXISTS wami_mfa_devices_user_idx ON wami_mfa_devices (user_name);
+CREATE INDEX IF NOT EXISTS wami_server_certificates_path_idx ON wami_server_certificates (path);
+CREATE INDEX IF NOT EXISTS wami_service_credentials_user_idx ON wami_service_credentials (user_name);
+CREATE INDEX IF NOT EXISTS wami_signing_certificates_user_idx ON wami_signing_certificates (user_name);
+
+-- Account password policies, one per tenant
+CREATE TABLE IF NOT EXISTS wami_password_policies (
+    tenant_id TEXT PRIMARY KEY,
+    data      JSONB NOT NULL
+);
+
+-- Group memberships
+CREATE TABLE IF NOT EXISTS wami_group_members (
+    group_name TEXT NOT NULL,
+    user_name  TEXT NOT NULL,
+    PRIMARY KEY (group_name, user_name)
+);
+
+CREATE INDEX IF NOT EXISTS wami_group_members_user_idx ON wami_group_members (user_name);
+
+-- Managed policy attachments (principal_type is 'user', 'group' or 'role')
+CREATE TABLE IF NOT EXISTS wami_attached_policies (
+    principal_type TEXT NOT NULL,
+    principal_name TEXT NOT NULL,
+    policy_arn     TEXT NOT NULL,
+    PRIMARY KEY (principal_type, principal_name, policy_arn)
+);
+
+-- Inline policies (principal_type is 'user', 'group' or 'role')
+CREATE TABLE IF NOT EXISTS wami_inline_policies (
+    principal_type  TEXT NOT NULL,
+    principal_name  TEXT NOT NULL,
+    policy_name     TEXT NOT NULL,
+    policy_document TEXT NOT NULL,
+    PRIMARY KEY (principal_type, principal_name, policy_name)
+);
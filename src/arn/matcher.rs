@@ -0,0 +1,224 @@
+//! Wildcard matching of WAMI ARNs against ARN patterns.
+//!
+//! A pattern has the shape of a WAMI ARN where any segment may contain `*`
+//! (any run of characters) and `?` (any single character):
+//!
+//! ```text
+//! arn:wami:{service}:{tenant_path}:wami:{instance}[:{provider}:{account}[:{region}]]:{resource_type}/{resource_id}
+//! ```
+//!
+//! Segments are compared one by one, so a `*` never spans a `:` boundary.
+//! The tenant path is compared per tenant; a tenant path of just `*` matches
+//! any depth. A pattern without a provider section matches both native and
+//! cloud-synced ARNs, while a pattern with one only matches ARNs synced to a
+//! matching provider and account (and region, when the pattern has one).
+//! A resource of just `*` matches any resource.
+
+use super::types::WamiArn;
+use crate::wami::policies::evaluation::condition::wildcard_match;
+
+/// Returns true if `arn` matches the wildcard ARN `pattern`.
+///
+/// Malformed patterns never match.
+///
+/// # Examples
+///
+/// ```
+/// use wami::arn::{matcher::matches_pattern, WamiArn};
+///
+/// let arn: WamiArn = "arn:wami:iam:12345678/87654321:wami:999888777:user/77557755"
+///     .parse()
+///     .unwrap();
+///
+/// assert!(matches_pattern(&arn, "arn:wami:iam:*:wami:*:user/*"));
+/// assert!(matches_pattern(&arn, "arn:wami:iam:12345678/*:wami:999888777:*"));
+/// assert!(!matches_pattern(&arn, "arn:wami:iam:12345678:wami:999888777:user/*"));
+/// ```
+pub fn matches_pattern(arn: &WamiArn, pattern: &str) -> bool {
+    let parts: Vec<&str> = pattern.split(':').collect();
+    if parts.len() < 7 || parts[0] != "arn" || parts[1] != "wami" || parts[4] != "wami" {
+        return false;
+    }
+
+    if !wildcard_match(parts[2], arn.service.as_str())
+        || !tenant_path_matches(arn, parts[3])
+        || !wildcard_match(parts[5], &arn.wami_instance_id)
+    {
+        return false;
+    }
+
+    // Same layout detection as the parser: provider segments never contain '/'
+    let has_segments = |range: std::ops::Range<usize>| {
+        parts.len() > range.end && parts[range].iter().all(|part| !part.contains('/'))
+    };
+    let (provider, region, resource) = if has_segments(6..9) {
+        (
+            Some((parts[6], parts[7])),
+            Some(parts[8]),
+            parts[9..].join(":"),
+        )
+    } else if has_segments(6..8) {
+        (Some((parts[6], parts[7])), None, parts[8..].join(":"))
+    } else {
+        (None, None, parts[6..].join(":"))
+    };
+
+    if let Some((provider, account_id)) = provider {
+        let Some(mapping) = &arn.cloud_mapping else {
+            return false;
+        };
+        if !wildcard_match(provider, &mapping.provider)
+            || !wildcard_match(account_id, &mapping.account_id)
+            || region.is_some_and(|region| !wildcard_match(region, mapping.region_or_global()))
+        {
+            return false;
+        }
+    }
+
+    resource_matches(arn, &resource)
+}
+
+/// Compare the tenant path tenant by tenant; a lone `*` matches any depth.
+fn tenant_path_matches(arn: &WamiArn, pattern: &str) -> bool {
+    if pattern == "*" {
+        return true;
+    }
+
+    let segments: Vec<&str> = pattern.split('/').collect();
+    segments.len() == arn.tenant_path.segments.len()
+        && segments
+            .iter()
+            .zip(&arn.tenant_path.segments)
+            .all(|(pattern, tenant)| wildcard_match(pattern, &tenant.to_string()))
+}
+
+/// Compare the resource type and ID; a lone `*` matches any resource.
+fn resource_matches(arn: &WamiArn, pattern: &str) -> bool {
+    if pattern == "*" {
+        return true;
+    }
+
+    match pattern.split_once('/') {
+        Some((resource_type, resource_id)) => {
+            wildcard_match(resource_type, &arn.resource.resource_type)
+                && wildcard_match(resource_id, &arn.resource.resource_id)
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn arn(s: &str) -> WamiArn {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_same_tenant_matches() {
+        let user = arn("arn:wami:iam:12345678:wami:999888777:user/77557755");
+
+        assert!(matches_pattern(
+            &user,
+            "arn:wami:iam:12345678:wami:999888777:user/77557755"
+        ));
+        assert!(matches_pattern(
+            &user,
+            "arn:wami:iam:12345678:wami:999888777:user/*"
+        ));
+        assert!(matches_pattern(&user, "arn:wami:*:1234*:wami:*:*"));
+        assert!(!matches_pattern(
+            &user,
+            "arn:wami:iam:87654321:wami:999888777:user/*"
+        ));
+        assert!(!matches_pattern(
+            &user,
+            "arn:wami:sts:12345678:wami:999888777:user/*"
+        ));
+    }
+
+    #[test]
+    fn test_tenant_path_wildcards() {
+        let nested = arn("arn:wami:iam:12345678/87654321/99999999:wami:999888777:user/1");
+
+        // A lone '*' matches any depth
+        assert!(matches_pattern(
+            &nested,
+            "arn:wami:iam:*:wami:999888777:user/1"
+        ));
+
+        // Otherwise each '*' stands for exactly one tenant
+        assert!(matches_pattern(
+            &nested,
+            "arn:wami:iam:12345678/*/*:wami:999888777:user/1"
+        ));
+        assert!(!matches_pattern(
+            &nested,
+            "arn:wami:iam:12345678/*:wami:999888777:user/1"
+        ));
+    }
+
+    #[test]
+    fn test_cross_provider_mismatch() {
+        let aws = arn("arn:wami:iam:12345678:wami:999888777:aws:223344556677:user/1");
+        let gcp = arn("arn:wami:iam:12345678:wami:999888777:gcp:554433221:user/1");
+        let native = arn("arn:wami:iam:12345678:wami:999888777:user/1");
+        let pattern = "arn:wami:iam:12345678:wami:999888777:aws:*:user/*";
+
+        assert!(matches_pattern(&aws, pattern));
+        assert!(!matches_pattern(&gcp, pattern));
+        assert!(!matches_pattern(&native, pattern));
+
+        // Without a provider section any provider matches
+        let any_provider = "arn:wami:iam:12345678:wami:999888777:user/1";
+        assert!(matches_pattern(&aws, any_provider));
+        assert!(matches_pattern(&gcp, any_provider));
+
+        // Regions are compared when the pattern has one
+        let regional =
+            arn("arn:wami:iam:12345678:wami:999888777:aws:223344556677:us-east-1:user/1");
+        assert!(matches_pattern(
+            &regional,
+            "arn:wami:iam:12345678:wami:999888777:aws:*:us-*:user/1"
+        ));
+        assert!(!matches_pattern(
+            &regional,
+            "arn:wami:iam:12345678:wami:999888777:aws:*:global:user/1"
+        ));
+    }
+
+    #[test]
+    fn test_resource_type_wildcards() {
+        let role = arn("arn:wami:iam:12345678:wami:999888777:role/42");
+        let group = arn("arn:wami:iam:12345678:wami:999888777:group/42");
+
+        assert!(matches_pattern(
+            &role,
+            "arn:wami:iam:12345678:wami:999888777:*/42"
+        ));
+        assert!(matches_pattern(
+            &group,
+            "arn:wami:iam:12345678:wami:999888777:*/42"
+        ));
+        assert!(matches_pattern(
+            &role,
+            "arn:wami:iam:12345678:wami:999888777:r*/*"
+        ));
+        assert!(!matches_pattern(
+            &group,
+            "arn:wami:iam:12345678:wami:999888777:r*/*"
+        ));
+    }
+
+    #[test]
+    fn test_malformed_pattern_never_matches() {
+        let user = arn("arn:wami:iam:12345678:wami:999888777:user/1");
+        assert!(!matches_pattern(&user, "*"));
+        assert!(!matches_pattern(&user, "arn:aws:iam::123456789012:user/1"));
+        assert!(!matches_pattern(
+            &user,
+            "arn:wami:iam:12345678:wami:999888777:user"
+        ));
+    }
+}
@@ -89,15 +89,17 @@
 //! ```
 
 pub mod builder;
+pub mod matcher;
 pub mod parser;
 pub mod transformer;
 pub mod types;
 
 // Re-export key types and functions
 pub use builder::ArnBuilder;
+pub use matcher::matches_pattern;
 pub use parser::{parse_arn, ArnParseError};
 pub use transformer::{
-    get_transformer, ArnTransformer, AwsArnTransformer, AzureArnTransformer, GcpArnTransformer,
-    ProviderArnInfo, ScalewayArnTransformer,
+    get_transformer, wami_labels, ArnTransformer, AwsArnTransformer, AzureArnTransformer,
+    GcpArnTransformer, ProviderArnInfo, ScalewayArnTransformer,
 };
 pub use types::{CloudMapping, Resource, Service, TenantPath, WamiArn};
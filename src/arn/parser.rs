@@ -96,9 +96,10 @@ impl FromStr for WamiArn {
         // Parse service
         let service = Service::from(parts[2]);
 
-        // Parse tenant path (numeric segments separated by '/')
+        // Parse tenant path (numeric segments separated by '/', empty ones ignored)
         let tenant_segments: std::result::Result<Vec<u64>, ArnParseError> = parts[3]
             .split('/')
+            .filter(|s| !s.is_empty())
             .map(|s| {
                 s.parse::<u64>().map_err(|_| {
                     ArnParseError::InvalidComponent(format!(
@@ -135,71 +136,50 @@ impl FromStr for WamiArn {
             ));
         }
 
-        // Now we need to determine if this is a cloud-synced ARN
-        // Cloud-synced: arn:wami:service:tenant:wami:instance:provider:account:region:resource
+        // The resource starts at the first part holding a '/' (its type/id
+        // delimiter); any colons or slashes after that belong to the resource
+        // ID. The parts between the instance ID and the resource, if any, are
+        // the cloud mapping:
+        // Cloud-synced: arn:wami:service:tenant:wami:instance:provider:account[:region]:resource
         // Native:       arn:wami:service:tenant:wami:instance:resource
+        let resource_start = (6..parts.len())
+            .find(|&i| parts[i].contains('/'))
+            .unwrap_or(6);
 
-        let (cloud_mapping, resource_part) = if parts.len() >= 10 {
-            // Potentially cloud-synced with region (provider:account:region:resource)
-            // Check if parts[6], [7], [8] look like provider/account/region (not containing '/')
-            if !parts[6].contains('/') && !parts[7].contains('/') && !parts[8].contains('/') {
-                // Cloud-synced format with region
-                let provider = parts[6].to_string();
-                let account_id = parts[7].to_string();
-                let region = parts[8].to_string();
-
-                if provider.is_empty() || account_id.is_empty() || region.is_empty() {
+        let cloud_mapping = match &parts[6..resource_start] {
+            [] => None,
+            [provider, account_id] => {
+                // Legacy cloud-synced format without region
+                if provider.is_empty() || account_id.is_empty() {
                     return Err(ArnParseError::InvalidComponent(
-                        "Provider, account ID, and region cannot be empty".to_string(),
+                        "Provider and account ID cannot be empty".to_string(),
                     ));
                 }
-
-                let cloud_mapping = if region == "global" {
-                    Some(CloudMapping::new(provider, account_id))
-                } else {
-                    Some(CloudMapping::with_region(provider, account_id, region))
-                };
-
-                // Resource is everything after region, joined back with ':'
-                let resource_part = parts[9..].join(":");
-
-                (cloud_mapping, resource_part)
-            } else {
-                // Native format (resource contains ':')
-                let resource_part = parts[6..].join(":");
-                (None, resource_part)
+                Some(CloudMapping::new(*provider, *account_id))
             }
-        } else if parts.len() >= 9 {
-            // Legacy cloud-synced without region (provider:account:resource)
-            // Check if parts[6] and [7] look like provider/account (not containing '/')
-            if !parts[6].contains('/') && !parts[7].contains('/') {
-                // Legacy cloud-synced format without region
-                let provider = parts[6].to_string();
-                let account_id = parts[7].to_string();
-
-                if provider.is_empty() || account_id.is_empty() {
+            [provider, account_id, region] => {
+                if provider.is_empty() || account_id.is_empty() || region.is_empty() {
                     return Err(ArnParseError::InvalidComponent(
-                        "Provider and account ID cannot be empty".to_string(),
+                        "Provider, account ID, and region cannot be empty".to_string(),
                     ));
                 }
-
-                let cloud_mapping = Some(CloudMapping::new(provider, account_id));
-
-                // Resource is everything after account_id, joined back with ':'
-                let resource_part = parts[8..].join(":");
-
-                (cloud_mapping, resource_part)
-            } else {
-                // Native format (resource contains ':')
-                let resource_part = parts[6..].join(":");
-                (None, resource_part)
+                if *region == "global" {
+                    Some(CloudMapping::new(*provider, *account_id))
+                } else {
+                    Some(CloudMapping::with_region(*provider, *account_id, *region))
+                }
+            }
+            mapping => {
+                return Err(ArnParseError::InvalidFormat(format!(
+                    "Expected provider:account[:region] before the resource, got '{}'",
+                    mapping.join(":")
+                )))
             }
-        } else {
-            // Native format
-            let resource_part = parts[6..].join(":");
-            (None, resource_part)
         };
 
+        // Resource is everything from its first part on, joined back with ':'
+        let resource_part = parts[resource_start..].join(":");
+
         // Parse resource (type/id)
         let resource_parts: Vec<&str> = resource_part.split('/').collect();
         if resource_parts.len() < 2 {
@@ -377,6 +357,32 @@ mod tests {
         assert_eq!(arn.resource.resource_id, "path/to/policy");
     }
 
+    #[test]
+    fn test_parse_resource_with_colons() {
+        let original =
+            "arn:wami:sts:12345678:wami:999888777:assumed-role/MyRole/session:2024-01-01T00:00";
+        let arn = WamiArn::from_str(original).unwrap();
+
+        assert!(arn.cloud_mapping.is_none());
+        assert_eq!(arn.resource.resource_type, "assumed-role");
+        assert_eq!(arn.resource.resource_id, "MyRole/session:2024-01-01T00:00");
+        assert_eq!(arn.to_string(), original);
+
+        // Colons in the ID are not mistaken for a cloud mapping
+        let original =
+            "arn:wami:sts:12345678:wami:999888777:aws:223344556677:federated-user/alice:a:b";
+        let arn = WamiArn::from_str(original).unwrap();
+
+        assert_eq!(arn.provider(), Some("aws"));
+        assert_eq!(arn.resource.resource_type, "federated-user");
+        assert_eq!(arn.resource.resource_id, "alice:a:b");
+        assert_eq!(
+            arn.to_string(),
+            "arn:wami:sts:12345678:wami:999888777:aws:223344556677:global:federated-user/alice:a:b"
+        );
+        assert_eq!(WamiArn::from_str(&arn.to_string()).unwrap(), arn);
+    }
+
     #[test]
     fn test_parse_roundtrip_native() {
         let original = "arn:wami:iam:12345678/87654321/99999999:wami:999888777:user/77557755";
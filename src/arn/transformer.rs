@@ -1,7 +1,20 @@
 //! ARN transformers for converting between WAMI ARNs and provider-specific formats.
+//!
+//! Provider ARNs have no room for the WAMI tenant path and instance, so
+//! [`wami_labels`] returns them as labels to store on the provider resource
+//! (GCP labels, AWS and Azure tags). [`ArnTransformer::to_wami_arn`] reads
+//! them back to rebuild the original [`WamiArn`].
 
-use super::types::{Service, WamiArn};
+use super::types::{CloudMapping, Resource, Service, TenantPath, WamiArn};
 use crate::error::{AmiError, Result};
+use std::collections::HashMap;
+
+/// Label holding the tenant path, with tenants separated by `-`
+pub const TENANT_PATH_LABEL: &str = "wami-tenant-path";
+/// Label holding the WAMI instance ID
+pub const INSTANCE_LABEL: &str = "wami-instance";
+/// Label holding the region, for providers whose resource names omit it
+pub const REGION_LABEL: &str = "wami-region";
 
 /// Trait for transforming WAMI ARNs to and from provider-specific formats.
 pub trait ArnTransformer {
@@ -15,8 +28,61 @@ pub trait ArnTransformer {
     ///
     /// Note: This may require additional context (tenant_path, wami_instance_id)
     /// that may not be present in the provider ARN, so this operation may be lossy.
+    /// Use [`to_wami_arn`](Self::to_wami_arn) to rebuild the full WAMI ARN.
     #[allow(clippy::wrong_self_convention, clippy::result_large_err)]
     fn from_provider_arn(&self, provider_arn: &str) -> Result<ProviderArnInfo>;
+
+    /// Rebuilds a WAMI ARN from a provider ARN and the labels stored on the
+    /// provider resource by [`wami_labels`].
+    #[allow(clippy::result_large_err)]
+    fn to_wami_arn(&self, provider_arn: &str, labels: &HashMap<String, String>) -> Result<WamiArn> {
+        self.from_provider_arn(provider_arn)?.to_wami_arn(labels)
+    }
+}
+
+/// Returns the labels to store on a provider resource so that its WAMI ARN
+/// can be rebuilt from the provider ARN.
+///
+/// Values only use digits, lowercase letters and `-` for numeric tenants and
+/// instances, which keeps them valid GCP label values.
+///
+/// # Examples
+///
+/// ```
+/// use wami::arn::{wami_labels, ArnTransformer, GcpArnTransformer, WamiArn};
+///
+/// let arn: WamiArn = "arn:wami:iam:12345678/87654321:wami:999888777:gcp:554433221:user/77557755"
+///     .parse()
+///     .unwrap();
+///
+/// let transformer = GcpArnTransformer;
+/// let gcp_arn = transformer.to_provider_arn(&arn).unwrap();
+/// let labels = wami_labels(&arn);
+/// assert_eq!(labels["wami-tenant-path"], "12345678-87654321");
+///
+/// assert_eq!(transformer.to_wami_arn(&gcp_arn, &labels).unwrap(), arn);
+/// ```
+pub fn wami_labels(arn: &WamiArn) -> HashMap<String, String> {
+    let tenant_path = arn
+        .tenant_path
+        .segments
+        .iter()
+        .map(|tenant| tenant.to_string())
+        .collect::<Vec<_>>()
+        .join("-");
+
+    let mut labels = HashMap::from([
+        (TENANT_PATH_LABEL.to_string(), tenant_path),
+        (INSTANCE_LABEL.to_string(), arn.wami_instance_id.clone()),
+    ]);
+    if let Some(region) = arn
+        .cloud_mapping
+        .as_ref()
+        .and_then(|mapping| mapping.region.clone())
+    {
+        labels.insert(REGION_LABEL.to_string(), region);
+    }
+    labels
 }
 
 /// Information extracted from a provider-specific ARN.
@@ -39,6 +105,65 @@ pub struct ProviderArnInfo {
     pub region: Option<String>,
 }
 
+impl ProviderArnInfo {
+    /// Rebuilds the WAMI ARN using the context stored in `labels` by [`wami_labels`].
+    ///
+    /// A region present in the provider ARN takes precedence over the region label.
+    #[allow(clippy::result_large_err)]
+    pub fn to_wami_arn(&self, labels: &HashMap<String, String>) -> Result<WamiArn> {
+        let label = |key: &str| {
+            labels
+                .get(key)
+                .filter(|value| !value.is_empty())
+                .ok_or_else(|| AmiError::InvalidParameter {
+                    message: format!("Provider resource is missing the '{}' label", key),
+                })
+        };
+
+        let segments = label(TENANT_PATH_LABEL)?
+            .split('-')
+            .map(|tenant| {
+                tenant
+                    .parse::<u64>()
+                    .map_err(|_| AmiError::InvalidParameter {
+                        message: format!(
+                            "Invalid tenant ID '{}' in '{}' label",
+                            tenant, TENANT_PATH_LABEL
+                        ),
+                    })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let region = self
+            .region
+            .clone()
+            .or_else(|| labels.get(REGION_LABEL).cloned());
+
+        WamiArn::builder()
+            .service(self.wami_service())
+            .tenant_path(TenantPath::new(segments))
+            .wami_instance(label(INSTANCE_LABEL)?.clone())
+            .cloud_mapping(CloudMapping {
+                provider: self.provider.clone(),
+                account_id: self.account_id.clone(),
+                region,
+            })
+            .resource_obj(Resource::new(&self.resource_type, &self.resource_id))
+            .build()
+    }
+
+    /// Maps the provider service name back to the WAMI service
+    fn wami_service(&self) -> Service {
+        match (self.provider.as_str(), self.service.as_str()) {
+            ("aws" | "scaleway", "sso") => Service::SsoAdmin,
+            ("gcp", "iam.googleapis.com") | ("azure", "Microsoft.Authorization") => Service::Iam,
+            ("gcp", "cloudidentity.googleapis.com")
+            | ("azure", "Microsoft.AzureActiveDirectory") => Service::SsoAdmin,
+            (_, service) => Service::from(service),
+        }
+    }
+}
+
 /// AWS ARN transformer.
 ///
 /// Converts between WAMI ARNs and AWS ARN format:
@@ -324,10 +449,19 @@ impl ArnTransformer for AzureArnTransformer {
             });
         }
 
+        if parts[5] != "providers" {
+            return Err(AmiError::InvalidParameter {
+                message: format!(
+                    "Invalid Azure resource ID: expected 'providers', got '{}'",
+                    parts[5]
+                ),
+            });
+        }
+
         let account_id = parts[2].to_string();
-        let service = parts[5].to_string(); // providers/{namespace}
-        let resource_type = parts[6].to_string();
-        let resource_id = parts[7..].join("/");
+        let service = parts[6].to_string(); // providers/{namespace}
+        let resource_type = parts[7].to_string();
+        let resource_id = parts[8..].join("/");
 
         Ok(ProviderArnInfo {
             provider: "azure".to_string(),
@@ -724,4 +858,102 @@ mod tests {
         assert_eq!(info.service, "s3");
         assert_eq!(info.region, Some("us-east-1".to_string()));
     }
+
+    /// Representative WAMI ARNs synced to `provider`
+    fn representative_arns(provider: &str, account_id: &str) -> Vec<WamiArn> {
+        let base = || {
+            WamiArn::builder()
+                .tenant(12345678)
+                .wami_instance("999888777")
+                .cloud_provider(provider, account_id)
+        };
+
+        vec![
+            base()
+                .service(Service::Iam)
+                .resource("user", "77557755")
+                .build()
+                .unwrap(),
+            base()
+                .service(Service::SsoAdmin)
+                .tenant_hierarchy(vec![12345678, 87654321, 99999999])
+                .resource("permission-set", "ps-readonly")
+                .build()
+                .unwrap(),
+            base()
+                .service(Service::Iam)
+                .resource("policy", "path/to/policy")
+                .build()
+                .unwrap(),
+            base()
+                .service(Service::Custom("storage".to_string()))
+                .wami_instance("prod-eu")
+                .resource("bucket", "reports")
+                .build()
+                .unwrap(),
+            WamiArn::builder()
+                .service(Service::Iam)
+                .tenant_hierarchy(vec![1, 2])
+                .wami_instance("999888777")
+                .cloud_provider_with_region(provider, account_id, "europe-west1")
+                .resource("role", "deployer")
+                .build()
+                .unwrap(),
+        ]
+    }
+
+    #[test]
+    fn test_round_trip_through_every_provider() {
+        for (provider, account_id) in [
+            ("aws", "223344556677"),
+            ("gcp", "554433221"),
+            ("azure", "sub-12345"),
+            ("scaleway", "11111111-2222-3333-4444-555555555555"),
+        ] {
+            let transformer = get_transformer(provider).unwrap();
+            for arn in representative_arns(provider, account_id) {
+                let provider_arn = transformer.to_provider_arn(&arn).unwrap();
+                let rebuilt = transformer
+                    .to_wami_arn(&provider_arn, &wami_labels(&arn))
+                    .unwrap();
+                assert_eq!(rebuilt, arn, "{} via {}", arn, provider_arn);
+
+                // The rebuilt ARN reparses to the same value
+                let reparsed: WamiArn = rebuilt.to_string().parse().unwrap();
+                assert_eq!(reparsed, arn);
+            }
+        }
+    }
+
+    #[test]
+    fn test_to_wami_arn_requires_labels() {
+        let arn = representative_arns("gcp", "554433221").remove(0);
+        let transformer = GcpArnTransformer;
+        let gcp_arn = transformer.to_provider_arn(&arn).unwrap();
+
+        let mut labels = wami_labels(&arn);
+        labels.remove(INSTANCE_LABEL);
+        let err = transformer.to_wami_arn(&gcp_arn, &labels).unwrap_err();
+        assert!(err.to_string().contains(INSTANCE_LABEL));
+
+        let mut labels = wami_labels(&arn);
+        labels.insert(
+            TENANT_PATH_LABEL.to_string(),
+            "12345678/87654321".to_string(),
+        );
+        assert!(transformer.to_wami_arn(&gcp_arn, &labels).is_err());
+    }
+
+    #[test]
+    fn test_azure_transformer_from_arn() {
+        let info = AzureArnTransformer
+            .from_provider_arn(
+                "/subscriptions/sub-12345/resourceGroups/wami-resources/providers/Microsoft.Authorization/user/77557755",
+            )
+            .unwrap();
+        assert_eq!(info.account_id, "sub-12345");
+        assert_eq!(info.service, "Microsoft.Authorization");
+        assert_eq!(info.resource_type, "user");
+        assert_eq!(info.resource_id, "77557755");
+    }
 }
@@ -2,6 +2,7 @@
 
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::str::FromStr;
 
 /// Represents a WAMI ARN (Amazon Resource Name).
@@ -29,7 +30,12 @@ use std::str::FromStr;
 /// ```json
 /// "arn:wami:iam:12345678/87654321/99999999:wami:999888777:user/77557755"
 /// ```
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+///
+/// # Equality
+///
+/// ARNs are compared and hashed by their [canonical](WamiArn::canonical) form,
+/// so ARNs differing only by empty or trailing path segments are equal.
+#[derive(Debug, Clone)]
 pub struct WamiArn {
     /// The service this resource belongs to (iam, sts, sso-admin, etc.)
     pub service: Service,
@@ -161,12 +167,9 @@ impl<'de> Deserialize<'de> for TenantPath {
         D: serde::Deserializer<'de>,
     {
         let s = String::deserialize(deserializer)?;
-        if s.is_empty() {
-            return Err(serde::de::Error::custom("Tenant path cannot be empty"));
-        }
-
         let segments: Result<Vec<u64>, _> = s
             .split('/')
+            .filter(|seg| !seg.is_empty())
             .map(|seg| {
                 seg.parse::<u64>().map_err(|_| {
                     serde::de::Error::custom(format!(
@@ -176,10 +179,12 @@ impl<'de> Deserialize<'de> for TenantPath {
                 })
             })
             .collect();
+        let segments = segments?;
+        if segments.is_empty() {
+            return Err(serde::de::Error::custom("Tenant path cannot be empty"));
+        }
 
-        Ok(Self {
-            segments: segments?,
-        })
+        Ok(Self { segments })
     }
 }
 
@@ -381,6 +386,23 @@ impl WamiArn {
         self.to_string().starts_with(prefix)
     }
 
+    /// Returns true if this ARN matches a wildcard ARN pattern.
+    ///
+    /// See [`matcher`](super::matcher) for the pattern syntax.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wami::arn::WamiArn;
+    ///
+    /// let arn: WamiArn = "arn:wami:iam:12345678:wami:999888777:user/77557755".parse().unwrap();
+    /// assert!(arn.matches_pattern("arn:wami:iam:*:wami:*:user/*"));
+    /// assert!(!arn.matches_pattern("arn:wami:iam:*:wami:*:role/*"));
+    /// ```
+    pub fn matches_pattern(&self, pattern: &str) -> bool {
+        super::matcher::matches_pattern(self, pattern)
+    }
+
     /// Returns true if this ARN belongs to the given tenant path or its descendants.
     pub fn belongs_to_tenant(&self, tenant_path: &TenantPath) -> bool {
         &self.tenant_path == tenant_path || self.tenant_path.is_descendant_of(tenant_path)
@@ -395,6 +417,65 @@ impl WamiArn {
     pub fn resource_id(&self) -> &str {
         &self.resource.resource_id
     }
+
+    /// Returns this ARN in canonical form.
+    ///
+    /// Empty and trailing segments are dropped from the resource path, so
+    /// `user/team//alice/` becomes `user/team/alice`. Tenant paths hold
+    /// numeric segments only, empty segments being dropped when parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wami::arn::WamiArn;
+    ///
+    /// let arn: WamiArn = "arn:wami:iam:12345678//87654321:wami:999888777:user/team//alice/"
+    ///     .parse()
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     arn.canonical().to_string(),
+    ///     "arn:wami:iam:12345678/87654321:wami:999888777:user/team/alice"
+    /// );
+    /// ```
+    pub fn canonical(&self) -> WamiArn {
+        let mut canonical = self.clone();
+        canonical.resource.resource_id = self.resource_id_segments().collect::<Vec<_>>().join("/");
+        canonical
+    }
+
+    /// Non-empty segments of the resource ID
+    fn resource_id_segments(&self) -> impl Iterator<Item = &str> {
+        self.resource
+            .resource_id
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+    }
+}
+
+impl PartialEq for WamiArn {
+    fn eq(&self, other: &Self) -> bool {
+        self.service == other.service
+            && self.tenant_path == other.tenant_path
+            && self.wami_instance_id == other.wami_instance_id
+            && self.cloud_mapping == other.cloud_mapping
+            && self.resource.resource_type == other.resource.resource_type
+            && self.resource_id_segments().eq(other.resource_id_segments())
+    }
+}
+
+impl Eq for WamiArn {}
+
+impl Hash for WamiArn {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.service.hash(state);
+        self.tenant_path.hash(state);
+        self.wami_instance_id.hash(state);
+        self.cloud_mapping.hash(state);
+        self.resource.resource_type.hash(state);
+        for segment in self.resource_id_segments() {
+            segment.hash(state);
+        }
+    }
 }
 
 impl fmt::Display for WamiArn {
@@ -745,4 +826,70 @@ mod tests {
         let deserialized: WamiArn = serde_json::from_str(&json).unwrap();
         assert_eq!(arn, deserialized);
     }
+
+    #[test]
+    fn test_serialization_embedded_in_resource() {
+        use crate::context::WamiContext;
+        use crate::wami::identity::user::builder::build_user;
+        use crate::wami::identity::User;
+
+        let arn: WamiArn = "arn:wami:iam:12345678:wami:999888777:user/test"
+            .parse()
+            .unwrap();
+        let context = WamiContext::builder()
+            .instance_id("999888777")
+            .tenant_path(TenantPath::single(12345678))
+            .caller_arn(arn)
+            .is_root(false)
+            .build()
+            .unwrap();
+        let user = build_user("alice".to_string(), Some("/".to_string()), &context).unwrap();
+
+        // Embedded ARNs are plain strings in the resource JSON
+        let value = serde_json::to_value(&user).unwrap();
+        assert_eq!(
+            value["wami_arn"],
+            serde_json::Value::String(user.wami_arn.to_string())
+        );
+
+        let deserialized: User = serde_json::from_value(value).unwrap();
+        assert_eq!(deserialized.wami_arn, user.wami_arn);
+        assert_eq!(deserialized.user_name, "alice");
+
+        // Malformed ARN strings fail the whole resource
+        let mut value = serde_json::to_value(&user).unwrap();
+        value["wami_arn"] = serde_json::Value::String("not-an-arn".to_string());
+        assert!(serde_json::from_value::<User>(value).is_err());
+    }
+
+    #[test]
+    fn test_wami_arn_canonical() {
+        use std::collections::HashSet;
+
+        let messy: WamiArn = "arn:wami:iam:12345678//87654321/:wami:999888777:user/team//alice/"
+            .parse()
+            .unwrap();
+        let clean: WamiArn = "arn:wami:iam:12345678/87654321:wami:999888777:user/team/alice"
+            .parse()
+            .unwrap();
+
+        assert_eq!(messy.tenant_path, clean.tenant_path);
+        assert_eq!(messy.canonical().to_string(), clean.to_string());
+        assert_eq!(messy, clean);
+
+        // Equal ARNs collapse to a single map key
+        let keys: HashSet<WamiArn> = [messy, clean.clone()].into_iter().collect();
+        assert_eq!(keys.len(), 1);
+
+        // Distinct resources stay distinct
+        let other: WamiArn = "arn:wami:iam:12345678/87654321:wami:999888777:user/team/alice2"
+            .parse()
+            .unwrap();
+        assert_ne!(other, clean);
+
+        // The tenant path deserializes the same way
+        let path: TenantPath = serde_json::from_str("\"12345678//87654321/\"").unwrap();
+        assert_eq!(path, clean.tenant_path);
+        assert!(serde_json::from_str::<TenantPath>("\"/\"").is_err());
+    }
 }
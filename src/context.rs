@@ -36,7 +36,10 @@
 
 use crate::arn::{TenantPath, WamiArn};
 use crate::error::{AmiError, Result};
+use crate::types::Tag;
+use crate::wami::policies::evaluation::ContextEntry;
 use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
 
 /// Session information for temporary credentials
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -47,6 +50,16 @@ pub struct SessionInfo {
     pub expiration: i64,
     /// Assumed role ARN (if this is an assumed role session)
     pub assumed_role_arn: Option<WamiArn>,
+    /// Inline session policy (JSON) that further scopes the session's permissions
+    #[serde(default)]
+    pub policy: Option<String>,
+    /// Session tags, visible to policies as `aws:PrincipalTag/<key>`
+    #[serde(default)]
+    pub tags: Vec<Tag>,
+    /// ARN of the principal that requested the session, such as the
+    /// federating user of a federation token
+    #[serde(default)]
+    pub principal_arn: Option<String>,
 }
 
 /// WAMI Context - carries authentication and authorization information
@@ -72,6 +85,14 @@ pub struct WamiContext {
 
     /// Optional session information for temporary credentials
     session_info: Option<SessionInfo>,
+
+    /// IP address the request originated from (`aws:SourceIp`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    source_ip: Option<IpAddr>,
+
+    /// Tags passed in the request (`aws:RequestTag/*`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    request_tags: Vec<Tag>,
 }
 
 impl WamiContext {
@@ -112,6 +133,59 @@ impl WamiContext {
         self.session_info.as_ref()
     }
 
+    /// Get the IP address the request originated from (if known)
+    pub fn source_ip(&self) -> Option<IpAddr> {
+        self.source_ip
+    }
+
+    /// Get the tags passed in the request
+    pub fn request_tags(&self) -> &[Tag] {
+        &self.request_tags
+    }
+
+    /// Build the global condition keys this request provides to policy evaluation
+    ///
+    /// Emits `aws:SourceIp` when the source IP is known, one
+    /// `aws:PrincipalTag/<key>` entry per session tag, and one
+    /// `aws:RequestTag/<key>` entry per request tag along with `aws:TagKeys`.
+    pub fn condition_context(&self) -> Vec<ContextEntry> {
+        let mut entries = Vec::new();
+
+        if let Some(ip) = self.source_ip {
+            entries.push(ContextEntry {
+                context_key_name: "aws:SourceIp".to_string(),
+                context_key_values: vec![ip.to_string()],
+                context_key_type: "ip".to_string(),
+            });
+        }
+
+        let session_tags = self.session_info.iter().flat_map(|session| &session.tags);
+        for tag in session_tags {
+            entries.push(ContextEntry {
+                context_key_name: format!("aws:PrincipalTag/{}", tag.key),
+                context_key_values: vec![tag.value.clone()],
+                context_key_type: "string".to_string(),
+            });
+        }
+
+        for tag in &self.request_tags {
+            entries.push(ContextEntry {
+                context_key_name: format!("aws:RequestTag/{}", tag.key),
+                context_key_values: vec![tag.value.clone()],
+                context_key_type: "string".to_string(),
+            });
+        }
+        if !self.request_tags.is_empty() {
+            entries.push(ContextEntry {
+                context_key_name: "aws:TagKeys".to_string(),
+                context_key_values: self.request_tags.iter().map(|t| t.key.clone()).collect(),
+                context_key_type: "stringList".to_string(),
+            });
+        }
+
+        entries
+    }
+
     /// Check if this context can access a specific tenant path
     ///
     /// A context can access:
@@ -128,6 +202,17 @@ impl WamiContext {
         target_tenant.starts_with(self.tenant_path())
     }
 
+    /// Check if list operations should return resources of `tenant`
+    ///
+    /// Listings are confined to the caller's own tenant. Descendant tenants
+    /// are included only when `include_descendants` is set, and root callers
+    /// see every tenant.
+    pub fn lists_tenant(&self, tenant: &TenantPath, include_descendants: bool) -> bool {
+        self.is_root
+            || tenant == self.tenant_path()
+            || (include_descendants && tenant.is_descendant_of(self.tenant_path()))
+    }
+
     /// Check if the session has expired (for temporary credentials)
     pub fn is_expired(&self) -> bool {
         if let Some(session) = &self.session_info {
@@ -147,6 +232,8 @@ pub struct WamiContextBuilder {
     is_root: bool,
     region: Option<String>,
     session_info: Option<SessionInfo>,
+    source_ip: Option<IpAddr>,
+    request_tags: Vec<Tag>,
 }
 
 impl WamiContextBuilder {
@@ -186,6 +273,18 @@ impl WamiContextBuilder {
         self
     }
 
+    /// Set the IP address the request originated from
+    pub fn source_ip(mut self, source_ip: IpAddr) -> Self {
+        self.source_ip = Some(source_ip);
+        self
+    }
+
+    /// Set the tags passed in the request
+    pub fn request_tags(mut self, request_tags: Vec<Tag>) -> Self {
+        self.request_tags = request_tags;
+        self
+    }
+
     /// Build the WamiContext
     #[allow(clippy::result_large_err)]
     pub fn build(self) -> Result<WamiContext> {
@@ -215,6 +314,8 @@ impl WamiContextBuilder {
             is_root: self.is_root,
             region: self.region,
             session_info: self.session_info,
+            source_ip: self.source_ip,
+            request_tags: self.request_tags,
         })
     }
 }
@@ -288,6 +389,26 @@ mod tests {
         assert!(!context.can_access_tenant(&TenantPath::single(0)));
     }
 
+    #[test]
+    fn test_lists_tenant() {
+        let arn: WamiArn = "arn:wami:iam:12345678:wami:999888777:user/12345"
+            .parse()
+            .unwrap();
+        let context = WamiContext::builder()
+            .instance_id("999888777")
+            .tenant_path(TenantPath::single(12345678))
+            .caller_arn(arn)
+            .is_root(false)
+            .build()
+            .unwrap();
+        let child = TenantPath::new(vec![12345678, 87654321]);
+
+        assert!(context.lists_tenant(&TenantPath::single(12345678), false));
+        assert!(!context.lists_tenant(&child, false));
+        assert!(context.lists_tenant(&child, true));
+        assert!(!context.lists_tenant(&TenantPath::single(99999999), true));
+    }
+
     #[test]
     fn test_root_can_access_any_tenant() {
         let arn: WamiArn = "arn:wami:iam:0:wami:999888777:user/root".parse().unwrap();
@@ -317,6 +438,9 @@ mod tests {
             session_token: "token123".to_string(),
             expiration: future_time,
             assumed_role_arn: None,
+            policy: None,
+            tags: vec![],
+            principal_arn: None,
         };
 
         let context = WamiContext::builder()
@@ -341,6 +465,9 @@ mod tests {
             session_token: "token123".to_string(),
             expiration: past_time,
             assumed_role_arn: None,
+            policy: None,
+            tags: vec![],
+            principal_arn: None,
         };
 
         let context = WamiContext::builder()
@@ -364,6 +491,9 @@ mod tests {
             session_token: "token123".to_string(),
             expiration: future_time,
             assumed_role_arn: None,
+            policy: None,
+            tags: vec![],
+            principal_arn: None,
         };
 
         let context = WamiContext::builder()
@@ -401,6 +531,44 @@ mod tests {
 
         assert_eq!(context.region(), None);
         assert!(context.session_info().is_none());
+        assert!(context.source_ip().is_none());
+        assert!(context.condition_context().is_empty());
+    }
+
+    #[test]
+    fn test_condition_context_includes_source_ip_and_request_tags() {
+        let arn: WamiArn = "arn:wami:iam:12345678:wami:999888777:user/12345"
+            .parse()
+            .unwrap();
+
+        let context = WamiContext::builder()
+            .instance_id("999888777")
+            .tenant_path(TenantPath::single(12345678))
+            .caller_arn(arn)
+            .source_ip("203.0.113.7".parse().unwrap())
+            .request_tags(vec![Tag {
+                key: "team".to_string(),
+                value: "platform".to_string(),
+            }])
+            .build()
+            .unwrap();
+
+        let entries = context.condition_context();
+        let value_of = |key: &str| {
+            entries
+                .iter()
+                .find(|entry| entry.context_key_name == key)
+                .map(|entry| entry.context_key_values.clone())
+        };
+        assert_eq!(
+            value_of("aws:SourceIp"),
+            Some(vec!["203.0.113.7".to_string()])
+        );
+        assert_eq!(
+            value_of("aws:RequestTag/team"),
+            Some(vec!["platform".to_string()])
+        );
+        assert_eq!(value_of("aws:TagKeys"), Some(vec!["team".to_string()]));
     }
 
     #[test]
@@ -35,8 +35,205 @@ pub enum AmiError {
     #[error("Resource already exists: {resource}")]
     ResourceExists { resource: String },
 
+    #[error("Delete conflict: {message}")]
+    DeleteConflict { message: String },
+
     #[error("Store error: {0}")]
     StoreError(String),
 }
 
 pub type Result<T> = std::result::Result<T, AmiError>;
+
+/// XML namespace of IAM query API responses
+pub(crate) const AWS_IAM_XML_NAMESPACE: &str = "https://iam.amazonaws.com/doc/2010-05-08/";
+
+impl AmiError {
+    /// The AWS error code clients expect for this error (e.g. `NoSuchEntity`)
+    ///
+    /// Errors from the AWS SDKs and the store are reported as `ServiceFailure`.
+    pub fn aws_error_code(&self) -> &'static str {
+        match self {
+            AmiError::AwsSdk(_)
+            | AmiError::StsSdk(_)
+            | AmiError::SsoAdminSdk(_)
+            | AmiError::StoreError(_) => "ServiceFailure",
+            AmiError::Serialization(_) => "MalformedInput",
+            AmiError::InvalidParameter { .. } => "InvalidInput",
+            AmiError::OperationNotSupported { .. } => "InvalidAction",
+            AmiError::ResourceNotFound { .. } => "NoSuchEntity",
+            AmiError::PermissionDenied { .. } | AmiError::AccessDenied { .. } => "AccessDenied",
+            AmiError::ResourceLimitExceeded { .. } => "LimitExceeded",
+            AmiError::ResourceExists { .. } => "EntityAlreadyExists",
+            AmiError::DeleteConflict { .. } => "DeleteConflict",
+        }
+    }
+
+    /// The HTTP status code AWS returns alongside [`aws_error_code`](Self::aws_error_code)
+    pub fn http_status(&self) -> u16 {
+        match self {
+            AmiError::AwsSdk(_)
+            | AmiError::StsSdk(_)
+            | AmiError::SsoAdminSdk(_)
+            | AmiError::StoreError(_) => 500,
+            AmiError::Serialization(_)
+            | AmiError::InvalidParameter { .. }
+            | AmiError::OperationNotSupported { .. } => 400,
+            AmiError::ResourceNotFound { .. } => 404,
+            AmiError::PermissionDenied { .. } | AmiError::AccessDenied { .. } => 403,
+            AmiError::ResourceLimitExceeded { .. }
+            | AmiError::ResourceExists { .. }
+            | AmiError::DeleteConflict { .. } => 409,
+        }
+    }
+
+    /// Render the AWS query API XML error response body for this error
+    ///
+    /// The error `Type` is `Sender` for client errors (4xx) and `Receiver`
+    /// for server errors (5xx).
+    pub fn to_aws_error_xml(&self, request_id: &str) -> String {
+        let error_type = if self.http_status() >= 500 {
+            "Receiver"
+        } else {
+            "Sender"
+        };
+        format!(
+            "<ErrorResponse xmlns=\"{}\"><Error><Type>{}</Type><Code>{}</Code><Message>{}</Message></Error><RequestId>{}</RequestId></ErrorResponse>",
+            AWS_IAM_XML_NAMESPACE,
+            error_type,
+            self.aws_error_code(),
+            escape_xml(&self.to_string()),
+            escape_xml(request_id)
+        )
+    }
+}
+
+pub(crate) fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aws_error_code_and_http_status() {
+        let cases: Vec<(AmiError, &str, u16)> = vec![
+            (
+                AmiError::StsSdk(aws_sdk_sts::Error::ExpiredTokenException(
+                    aws_sdk_sts::types::error::ExpiredTokenException::builder().build(),
+                )),
+                "ServiceFailure",
+                500,
+            ),
+            (
+                AmiError::Serialization(serde_json::from_str::<u32>("x").unwrap_err()),
+                "MalformedInput",
+                400,
+            ),
+            (
+                AmiError::InvalidParameter {
+                    message: "bad".to_string(),
+                },
+                "InvalidInput",
+                400,
+            ),
+            (
+                AmiError::OperationNotSupported {
+                    operation: "Frobnicate".to_string(),
+                },
+                "InvalidAction",
+                400,
+            ),
+            (
+                AmiError::ResourceNotFound {
+                    resource: "User alice".to_string(),
+                },
+                "NoSuchEntity",
+                404,
+            ),
+            (
+                AmiError::PermissionDenied {
+                    reason: "no".to_string(),
+                },
+                "AccessDenied",
+                403,
+            ),
+            (
+                AmiError::AccessDenied {
+                    message: "no".to_string(),
+                },
+                "AccessDenied",
+                403,
+            ),
+            (
+                AmiError::ResourceLimitExceeded {
+                    resource_type: "Users".to_string(),
+                    limit: 5,
+                },
+                "LimitExceeded",
+                409,
+            ),
+            (
+                AmiError::ResourceExists {
+                    resource: "User alice".to_string(),
+                },
+                "EntityAlreadyExists",
+                409,
+            ),
+            (
+                AmiError::DeleteConflict {
+                    message: "attached".to_string(),
+                },
+                "DeleteConflict",
+                409,
+            ),
+            (
+                AmiError::StoreError("disk".to_string()),
+                "ServiceFailure",
+                500,
+            ),
+        ];
+
+        for (error, code, status) in cases {
+            assert_eq!(error.aws_error_code(), code, "{:?}", error);
+            assert_eq!(error.http_status(), status, "{:?}", error);
+        }
+    }
+
+    #[test]
+    fn test_to_aws_error_xml() {
+        let error = AmiError::ResourceNotFound {
+            resource: "User <alice> & co".to_string(),
+        };
+        let xml = error.to_aws_error_xml("req-1");
+        assert_eq!(
+            xml,
+            "<ErrorResponse xmlns=\"https://iam.amazonaws.com/doc/2010-05-08/\"><Error><Type>Sender</Type><Code>NoSuchEntity</Code><Message>Resource not found: User &lt;alice&gt; &amp; co</Message></Error><RequestId>req-1</RequestId></ErrorResponse>"
+        );
+
+        // The body parses and server errors are reported by the receiver
+        let xml = AmiError::StoreError("disk".to_string()).to_aws_error_xml("req-2");
+        let document = roxmltree::Document::parse(&xml).unwrap();
+        let text = |name: &str| {
+            document
+                .descendants()
+                .find(|node| node.has_tag_name(name))
+                .and_then(|node| node.text())
+                .map(str::to_string)
+        };
+        assert_eq!(text("Type").as_deref(), Some("Receiver"));
+        assert_eq!(text("Code").as_deref(), Some("ServiceFailure"));
+        assert_eq!(text("RequestId").as_deref(), Some("req-2"));
+    }
+}
@@ -73,14 +73,19 @@ pub mod arn;
 pub mod context;
 pub mod error;
 pub mod provider;
+#[cfg(feature = "server")]
+pub mod server;
 pub mod service;
 pub mod store;
+mod telemetry;
 pub mod types;
 pub mod wami;
 
 // Re-export main types for convenience
 pub use error::{AmiError, Result};
-pub use types::{AmiResponse, AwsConfig, PaginationParams, PolicyDocument, PolicyStatement, Tag};
+pub use types::{
+    AmiResponse, AwsConfig, Effect, PaginationParams, PolicyDocument, PolicyStatement, Tag,
+};
 
 // Re-export ARN types
 pub use arn::{
@@ -102,12 +107,13 @@ pub use provider::ProviderConfig;
 // Re-export service layer
 pub use service::{
     hash_secret, verify_secret, AccessKeyService, AccountAssignmentService, ApplicationService,
-    AssumeRoleService, AttachmentService, AuthenticationService, AuthorizationService,
+    AssumeRoleService, AttachmentService, AuditEvent, AuditOutcome, AuditSink,
+    AuthenticationService, AuthorizationDetailsService, AuthorizationService,
     CredentialReportService, EvaluationService, FederationService, GroupService, IdentityService,
     InlinePolicyService, InstanceService as SsoInstanceService, LoginProfileService,
     MfaDeviceService, PermissionSetService, PolicyService, RoleService, ServerCertificateService,
     ServiceCredentialService, ServiceLinkedRoleService, SessionService, SessionTokenService,
-    SigningCertificateService, TenantService, TrustedTokenIssuerService, UserService,
+    SigningCertificateService, TenantService, TrustedTokenIssuerService, UserService, VecAuditSink,
 };
 
 // Re-export WAMI modules for convenience (Legacy compatibility)
@@ -121,7 +127,8 @@ pub use wami::identity::{Group, Role, RootUser, User};
 
 // Re-export credential types
 pub use wami::credentials::{
-    AccessKey, LoginProfile, MfaDevice, ServerCertificate, ServiceSpecificCredential,
+    AccessKey, LoginProfile, MfaDevice, PasswordPolicy, ServerCertificate,
+    ServiceSpecificCredential,
 };
 // pub use wami::credentials::server_certificate::ServerCertificateMetadata; // TODO: fix path
 
@@ -129,7 +136,7 @@ pub use wami::credentials::{
 pub use wami::policies::Policy;
 
 // Re-export report types
-pub use wami::reports::CredentialReport;
+pub use wami::reports::{AccountAuthorizationDetails, CredentialReport};
 
 // Re-export STS types
 pub use wami::sts::{Credentials, StsSession};
@@ -156,6 +163,7 @@ pub use wami::credentials::login_profile::{
     CreateLoginProfileRequest, GetLoginProfileRequest, UpdateLoginProfileRequest,
 };
 pub use wami::credentials::mfa_device::{EnableMfaDeviceRequest, ListMfaDevicesRequest};
+pub use wami::credentials::password_policy::UpdateAccountPasswordPolicyRequest;
 pub use wami::credentials::server_certificate::{
     DeleteServerCertificateRequest, GetServerCertificateRequest, GetServerCertificateResponse,
     ListServerCertificatesRequest, ListServerCertificatesResponse, UpdateServerCertificateRequest,
@@ -197,8 +205,8 @@ pub use wami::policies::attachment::{
     ListAttachedUserPoliciesRequest, ListAttachedUserPoliciesResponse,
 };
 pub use wami::policies::evaluation::{
-    ContextEntry, EvaluationResult, SimulateCustomPolicyRequest, SimulatePolicyResponse,
-    SimulatePrincipalPolicyRequest, StatementMatch,
+    ContextEntry, DecisionSource, EvaluationResult, SimulateCustomPolicyRequest,
+    SimulatePolicyResponse, SimulatePrincipalPolicyRequest, StatementMatch,
 };
 pub use wami::policies::inline::{
     DeleteGroupPolicyRequest, DeleteGroupPolicyResponse, DeleteRolePolicyRequest,
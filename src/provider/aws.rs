@@ -3,8 +3,11 @@
 //! This module contains the AWS-specific implementation of the CloudProvider trait,
 //! including AWS ARN formats, ID generation patterns, and resource limits.
 
-use super::{CloudProvider, ResourceLimits, ResourceType};
+use super::{CloudProvider, ResourceIdLengths, ResourceLimits, ResourceType};
 use crate::error::{AmiError, Result};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::sync::{Arc, Mutex};
 
 /// AWS cloud provider implementation
 ///
@@ -16,6 +19,9 @@ use crate::error::{AmiError, Result};
 #[derive(Debug, Clone)]
 pub struct AwsProvider {
     limits: ResourceLimits,
+    id_lengths: ResourceIdLengths,
+    /// Source of deterministic IDs, shared by clones; IDs are random when unset
+    id_rng: Option<Arc<Mutex<StdRng>>>,
 }
 
 impl Default for AwsProvider {
@@ -29,6 +35,8 @@ impl AwsProvider {
     pub fn new() -> Self {
         Self {
             limits: ResourceLimits::default(),
+            id_lengths: ResourceIdLengths::default(),
+            id_rng: None,
         }
     }
 
@@ -46,7 +54,49 @@ impl AwsProvider {
     /// let provider = AwsProvider::with_limits(limits);
     /// ```
     pub fn with_limits(limits: ResourceLimits) -> Self {
-        Self { limits }
+        Self {
+            limits,
+            id_lengths: ResourceIdLengths::default(),
+            id_rng: None,
+        }
+    }
+
+    /// Overrides the length of the random part of generated resource IDs
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use wami::provider::{AwsProvider, CloudProvider, ResourceIdLengths, ResourceType};
+    ///
+    /// let provider = AwsProvider::new().with_resource_id_lengths(ResourceIdLengths::new(10));
+    /// assert_eq!(provider.generate_resource_id(ResourceType::User).len(), 14); // AIDA + 10
+    /// ```
+    pub fn with_resource_id_lengths(mut self, id_lengths: ResourceIdLengths) -> Self {
+        self.id_lengths = id_lengths;
+        self
+    }
+
+    /// Generates resource IDs deterministically from `seed`
+    ///
+    /// Meant for golden tests: two providers with the same seed generate the
+    /// same sequence of IDs. Clones share the sequence. Providers are random
+    /// unless a seed is set.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use wami::provider::{AwsProvider, CloudProvider, ResourceType};
+    ///
+    /// let first = AwsProvider::new().with_seed(42);
+    /// let second = AwsProvider::new().with_seed(42);
+    /// assert_eq!(
+    ///     first.generate_resource_id(ResourceType::User),
+    ///     second.generate_resource_id(ResourceType::User)
+    /// );
+    /// ```
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.id_rng = Some(Arc::new(Mutex::new(StdRng::seed_from_u64(seed))));
+        self
     }
 
     /// Extracts service name from AWS service principal
@@ -82,16 +132,6 @@ impl AwsProvider {
             })
             .collect()
     }
-
-    /// Generates a random alphanumeric string of specified length
-    fn random_alphanumeric(length: usize) -> String {
-        uuid::Uuid::new_v4()
-            .to_string()
-            .replace('-', "")
-            .chars()
-            .take(length)
-            .collect()
-    }
 }
 
 impl CloudProvider for AwsProvider {
@@ -155,8 +195,15 @@ impl CloudProvider for AwsProvider {
             ResourceType::Tenant => "AORG",
         };
 
-        // AWS IDs are: 4-letter prefix + 17 random alphanumeric characters
-        format!("{}{}", prefix, Self::random_alphanumeric(17))
+        // AWS IDs are: 4-letter prefix + 17 random alphanumeric characters (configurable)
+        let suffix = match &self.id_rng {
+            Some(rng) => {
+                let mut rng = rng.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+                self.id_lengths.suffix_from(resource_type, &mut *rng)
+            }
+            None => self.id_lengths.random_suffix(resource_type),
+        };
+        format!("{}{}", prefix, suffix)
     }
 
     fn resource_limits(&self) -> &ResourceLimits {
@@ -265,6 +312,32 @@ mod tests {
         assert_eq!(id.len(), 21); // AIDA + 17 chars
     }
 
+    #[test]
+    #[allow(deprecated)]
+    fn test_seeded_provider_generates_stable_ids() {
+        use crate::wami::identity::user::builder::build_user_legacy;
+
+        let build = |provider: &AwsProvider| {
+            build_user_legacy("alice".to_string(), None, provider, "123456789012").user_id
+        };
+
+        let first = AwsProvider::new().with_seed(7);
+        let second = AwsProvider::new().with_seed(7);
+        let id = build(&first);
+        assert_eq!(id, build(&second));
+        assert!(id.starts_with("AIDA"));
+        assert_eq!(id.len(), 21);
+
+        // Each provider walks the same sequence
+        let next = build(&first);
+        assert_ne!(next, id);
+        assert_eq!(build(&second), next);
+
+        // Other seeds and unseeded providers differ
+        assert_ne!(build(&AwsProvider::new().with_seed(8)), id);
+        assert_ne!(build(&AwsProvider::new()), build(&AwsProvider::new()));
+    }
+
     #[test]
     fn test_generate_group_id() {
         let provider = AwsProvider::new();
@@ -289,6 +362,20 @@ mod tests {
         assert_eq!(id.len(), 21);
     }
 
+    #[test]
+    fn test_generate_id_with_custom_lengths() {
+        let provider = AwsProvider::new().with_resource_id_lengths(
+            ResourceIdLengths::default().with_length(ResourceType::AccessKey, 16),
+        );
+
+        let access_key_id = provider.generate_resource_id(ResourceType::AccessKey);
+        assert!(access_key_id.starts_with("AKIA"));
+        assert_eq!(access_key_id.len(), 20);
+
+        // Other resource types keep the AWS default
+        assert_eq!(provider.generate_resource_id(ResourceType::User).len(), 21);
+    }
+
     #[test]
     fn test_resource_limits() {
         let provider = AwsProvider::new();
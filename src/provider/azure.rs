@@ -41,6 +41,10 @@ impl AzureProvider {
             resource_group: resource_group.into(),
             limits: ResourceLimits {
                 max_tags_per_resource: 50, // Azure tag limit
+                max_tag_key_length: 512,
+                reserved_tag_key_prefixes: ["microsoft", "azure", "windows"]
+                    .map(String::from)
+                    .to_vec(),
                 ..Default::default()
             },
         }
@@ -3,7 +3,7 @@
 //! This module allows users to create their own custom provider implementations
 //! with configurable ARN formats, ID prefixes, and resource limits.
 
-use super::{CloudProvider, ResourceLimits, ResourceType};
+use super::{CloudProvider, ResourceIdLengths, ResourceLimits, ResourceType};
 use crate::error::Result;
 
 /// Custom provider implementation for user-defined cloud platforms
@@ -29,7 +29,9 @@ pub struct CustomProvider {
     name: String,
     arn_template: String,
     id_prefix: String,
+    id_lengths: ResourceIdLengths,
     limits: ResourceLimits,
+    default_session_duration: Option<i32>,
 }
 
 impl CustomProvider {
@@ -62,16 +64,11 @@ impl CloudProvider for CustomProvider {
             .replace("{service}", "identity")
     }
 
-    fn generate_resource_id(&self, _resource_type: ResourceType) -> String {
+    fn generate_resource_id(&self, resource_type: ResourceType) -> String {
         format!(
             "{}{}",
             self.id_prefix,
-            uuid::Uuid::new_v4()
-                .to_string()
-                .replace('-', "")
-                .chars()
-                .take(17)
-                .collect::<String>()
+            self.id_lengths.random_suffix(resource_type)
         )
     }
 
@@ -79,6 +76,11 @@ impl CloudProvider for CustomProvider {
         &self.limits
     }
 
+    fn default_session_duration(&self) -> i32 {
+        self.default_session_duration
+            .unwrap_or(self.limits.session_duration_min)
+    }
+
     fn validate_service_name(&self, _service: &str) -> Result<()> {
         // Custom providers can define their own validation
         Ok(())
@@ -112,7 +114,9 @@ pub struct CustomProviderBuilder {
     name: Option<String>,
     arn_template: Option<String>,
     id_prefix: Option<String>,
+    id_lengths: Option<ResourceIdLengths>,
     limits: Option<ResourceLimits>,
+    default_session_duration: Option<i32>,
 }
 
 impl CustomProviderBuilder {
@@ -141,6 +145,25 @@ impl CustomProviderBuilder {
         self
     }
 
+    /// Sets the length of the random part of IDs for every resource type (default: 17)
+    pub fn id_length(mut self, length: usize) -> Self {
+        self.id_lengths = Some(ResourceIdLengths::new(length));
+        self
+    }
+
+    /// Sets per-resource-type ID lengths
+    pub fn id_lengths(mut self, id_lengths: ResourceIdLengths) -> Self {
+        self.id_lengths = Some(id_lengths);
+        self
+    }
+
+    /// Sets the session duration used when a request does not specify one
+    /// (default: the minimum session duration from the limits)
+    pub fn default_session_duration(mut self, seconds: i32) -> Self {
+        self.default_session_duration = Some(seconds);
+        self
+    }
+
     /// Sets custom resource limits
     pub fn limits(mut self, limits: ResourceLimits) -> Self {
         self.limits = Some(limits);
@@ -155,7 +178,9 @@ impl CustomProviderBuilder {
                 .arn_template
                 .unwrap_or_else(|| "urn:{service}:{account}:{type}/{path}{name}".to_string()),
             id_prefix: self.id_prefix.unwrap_or_else(|| "CUST".to_string()),
+            id_lengths: self.id_lengths.unwrap_or_default(),
             limits: self.limits.unwrap_or_default(),
+            default_session_duration: self.default_session_duration,
         }
     }
 }
@@ -195,6 +220,39 @@ mod tests {
         assert_eq!(id.len(), 21); // TEST (4) + 17 random chars
     }
 
+    #[test]
+    fn test_custom_id_length() {
+        let provider = CustomProvider::builder()
+            .id_prefix("MC")
+            .id_length(10)
+            .build();
+
+        let id = provider.generate_resource_id(ResourceType::User);
+        assert!(id.starts_with("MC"));
+        assert_eq!(id.len(), 12); // MC (2) + 10 random chars
+
+        let provider = CustomProvider::builder()
+            .id_prefix("MC")
+            .id_lengths(ResourceIdLengths::new(10).with_length(ResourceType::AccessKey, 40))
+            .build();
+        assert_eq!(provider.generate_resource_id(ResourceType::Group).len(), 12);
+        assert_eq!(
+            provider.generate_resource_id(ResourceType::AccessKey).len(),
+            42
+        );
+    }
+
+    #[test]
+    fn test_custom_default_session_duration() {
+        let provider = CustomProvider::builder().build();
+        assert_eq!(provider.default_session_duration(), 3600);
+
+        let provider = CustomProvider::builder()
+            .default_session_duration(7200)
+            .build();
+        assert_eq!(provider.default_session_duration(), 7200);
+    }
+
     #[test]
     fn test_custom_limits() {
         let limits = ResourceLimits {
@@ -41,7 +41,10 @@ impl GcpProvider {
             limits: ResourceLimits {
                 max_access_keys_per_user: 10, // GCP allows more keys
                 max_service_credentials_per_user_per_service: 10,
-                max_tags_per_resource: 64,  // GCP label limit
+                max_tags_per_resource: 64, // GCP label limit
+                max_tag_key_length: 63,
+                max_tag_value_length: 63,
+                reserved_tag_key_prefixes: vec!["goog".to_string()],
                 session_duration_max: 3600, // GCP default: 1 hour
                 ..Default::default()
             },
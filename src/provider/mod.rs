@@ -1,6 +1,7 @@
 //! Cloud Provider Abstraction
 //!
-//! This module provides abstractions for different cloud providers (AWS, GCP, Azure, custom).
+//! This module provides abstractions for different cloud providers (AWS, GCP, Azure, Scaleway,
+//! custom).
 //! It allows the IAM system to work across multiple cloud platforms by abstracting
 //! provider-specific details like ARN formats, ID generation, and resource limits.
 //!
@@ -36,12 +37,14 @@ pub mod azure;
 pub mod custom;
 pub mod gcp;
 pub mod provider_info;
+pub mod scaleway;
 
 // #[cfg(test)]
 // mod tests;  // TODO: Update tests after service layer rebuild
 
 use crate::error::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Provider configuration for tracking which cloud providers a resource exists on
 ///
@@ -121,7 +124,10 @@ pub enum ResourceType {
 }
 
 /// Resource limits configuration per cloud provider
+///
+/// Fields missing from a serialized configuration take their AWS default.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ResourceLimits {
     /// Maximum number of access keys per user
     pub max_access_keys_per_user: usize,
@@ -131,12 +137,30 @@ pub struct ResourceLimits {
     pub max_service_credentials_per_user_per_service: usize,
     /// Maximum number of tags per resource
     pub max_tags_per_resource: usize,
+    /// Maximum length of a tag key, in characters
+    pub max_tag_key_length: usize,
+    /// Maximum length of a tag value, in characters
+    pub max_tag_value_length: usize,
+    /// Tag key prefixes reserved for the provider, compared case-insensitively
+    pub reserved_tag_key_prefixes: Vec<String>,
     /// Maximum number of MFA devices per user
     pub max_mfa_devices_per_user: usize,
     /// Minimum session duration in seconds
     pub session_duration_min: i32,
     /// Maximum session duration in seconds
     pub session_duration_max: i32,
+    /// Maximum number of users per account
+    pub max_users: usize,
+    /// Maximum number of groups per account
+    pub max_groups: usize,
+    /// Maximum number of roles per account
+    pub max_roles: usize,
+    /// Maximum number of customer managed policies per account
+    pub max_policies: usize,
+    /// Maximum number of server certificates per account
+    pub max_server_certificates: usize,
+    /// Maximum number of roles assumed in a row, counting the first one
+    pub max_role_chain_length: usize,
 }
 
 impl Default for ResourceLimits {
@@ -147,10 +171,92 @@ impl Default for ResourceLimits {
             max_signing_certificates_per_user: 2,
             max_service_credentials_per_user_per_service: 2,
             max_tags_per_resource: 50,
+            max_tag_key_length: 128,
+            max_tag_value_length: 256,
+            reserved_tag_key_prefixes: vec!["aws:".to_string()],
             max_mfa_devices_per_user: 8,
             session_duration_min: 3600,  // 1 hour
             session_duration_max: 43200, // 12 hours
+            max_users: 5000,
+            max_groups: 300,
+            max_roles: 1000,
+            max_policies: 1500,
+            max_server_certificates: 20,
+            max_role_chain_length: 5,
+        }
+    }
+}
+
+/// Length of the random suffix of AWS resource IDs (e.g. `AIDA` + 17 characters)
+pub const DEFAULT_RESOURCE_ID_LENGTH: usize = 17;
+
+/// Length of the random part of generated resource IDs, per resource type
+///
+/// # Example
+///
+/// ```rust
+/// use wami::provider::{ResourceIdLengths, ResourceType};
+///
+/// let lengths = ResourceIdLengths::new(10).with_length(ResourceType::AccessKey, 16);
+/// assert_eq!(lengths.length_for(ResourceType::User), 10);
+/// assert_eq!(lengths.length_for(ResourceType::AccessKey), 16);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResourceIdLengths {
+    /// Length used for resource types without an override
+    pub default_length: usize,
+    /// Per-resource-type overrides
+    pub overrides: HashMap<ResourceType, usize>,
+}
+
+impl Default for ResourceIdLengths {
+    fn default() -> Self {
+        Self::new(DEFAULT_RESOURCE_ID_LENGTH)
+    }
+}
+
+impl ResourceIdLengths {
+    /// Creates a configuration using the same length for every resource type
+    pub fn new(default_length: usize) -> Self {
+        Self {
+            default_length,
+            overrides: HashMap::new(),
+        }
+    }
+
+    /// Overrides the length for a single resource type
+    pub fn with_length(mut self, resource_type: ResourceType, length: usize) -> Self {
+        self.overrides.insert(resource_type, length);
+        self
+    }
+
+    /// Returns the configured length for a resource type
+    pub fn length_for(&self, resource_type: ResourceType) -> usize {
+        self.overrides
+            .get(&resource_type)
+            .copied()
+            .unwrap_or(self.default_length)
+    }
+
+    /// Generates a random alphanumeric suffix for a resource type
+    pub fn random_suffix(&self, resource_type: ResourceType) -> String {
+        let length = self.length_for(resource_type);
+        let mut suffix = String::with_capacity(length);
+        while suffix.len() < length {
+            suffix.push_str(&uuid::Uuid::new_v4().simple().to_string());
         }
+        suffix.truncate(length);
+        suffix
+    }
+
+    /// Generates a suffix for a resource type drawn from `rng`
+    ///
+    /// The suffix has the same length and alphabet as [`random_suffix`](Self::random_suffix),
+    /// but a seeded `rng` always yields the same sequence of suffixes.
+    pub fn suffix_from<R: rand::Rng>(&self, resource_type: ResourceType, rng: &mut R) -> String {
+        (0..self.length_for(resource_type))
+            .map(|_| char::from_digit(rng.gen_range(0..16), 16).unwrap_or('0'))
+            .collect()
     }
 }
 
@@ -160,7 +266,7 @@ impl Default for ResourceLimits {
 /// by abstracting provider-specific details like ARN formats, ID generation,
 /// resource limits, and validation rules.
 pub trait CloudProvider: Send + Sync + std::fmt::Debug {
-    /// Returns the provider name (e.g., "aws", "gcp", "azure", "custom")
+    /// Returns the provider name (e.g., "aws", "gcp", "azure", "scaleway", "custom")
     fn name(&self) -> &str;
 
     /// Generates a resource identifier (ARN, URN, Resource ID, etc.)
@@ -284,6 +390,49 @@ pub trait CloudProvider: Send + Sync + std::fmt::Debug {
         Ok(())
     }
 
+    /// Validates tags supplied for a resource
+    ///
+    /// # Default Implementation
+    ///
+    /// Checks the tag count, key and value lengths, characters and reserved
+    /// key prefixes against the provider's resource limits
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use wami::provider::{AwsProvider, CloudProvider};
+    /// use wami::types::Tag;
+    ///
+    /// let provider = AwsProvider::default();
+    /// let tag = Tag { key: "aws:createdBy".to_string(), value: "me".to_string() };
+    /// assert!(provider.validate_tags(&[tag]).is_err());
+    /// ```
+    #[allow(clippy::result_large_err)]
+    fn validate_tags(&self, tags: &[crate::types::Tag]) -> Result<()> {
+        crate::wami::tags::operations::tag_operations::validate_tags_with_limits(
+            tags,
+            self.resource_limits(),
+        )
+    }
+
+    /// Returns the session duration used when a request does not specify one
+    ///
+    /// # Default Implementation
+    ///
+    /// Uses the provider's minimum session duration
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use wami::provider::{AwsProvider, CloudProvider};
+    ///
+    /// let provider = AwsProvider::default();
+    /// assert_eq!(provider.default_session_duration(), 3600);
+    /// ```
+    fn default_session_duration(&self) -> i32 {
+        self.resource_limits().session_duration_min
+    }
+
     /// Generates a service-linked role name
     ///
     /// # Arguments
@@ -488,3 +637,4 @@ pub use aws::AwsProvider;
 pub use azure::AzureProvider;
 pub use custom::CustomProvider;
 pub use gcp::GcpProvider;
+pub use scaleway::ScalewayProvider;
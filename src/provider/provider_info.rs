@@ -28,7 +28,7 @@ use serde::{Deserialize, Serialize};
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ProviderInfo {
-    /// Provider type ("aws", "gcp", "azure", "custom")
+    /// Provider type ("aws", "gcp", "azure", "scaleway", "custom")
     pub provider_type: String,
 
     /// Native ARN/URN/Resource ID from the cloud provider
@@ -86,9 +86,14 @@ impl ProviderInfo {
         self.provider_type == "azure"
     }
 
+    /// Checks if this provider is Scaleway
+    pub fn is_scaleway(&self) -> bool {
+        self.provider_type == "scaleway"
+    }
+
     /// Checks if this is a custom provider
     pub fn is_custom(&self) -> bool {
-        !self.is_aws() && !self.is_gcp() && !self.is_azure()
+        !self.is_aws() && !self.is_gcp() && !self.is_azure() && !self.is_scaleway()
     }
 }
 
@@ -115,11 +120,14 @@ mod tests {
         let aws = ProviderInfo::new("aws", "arn:...", None, "123");
         let gcp = ProviderInfo::new("gcp", "projects/...", None, "project-id");
         let azure = ProviderInfo::new("azure", "/subscriptions/...", None, "sub-id");
+        let scaleway = ProviderInfo::new("scaleway", "scw:...", None, "project-id");
         let custom = ProviderInfo::new("mycloud", "mycloud://...", None, "tenant-123");
 
         assert!(aws.is_aws());
         assert!(gcp.is_gcp());
         assert!(azure.is_azure());
+        assert!(scaleway.is_scaleway());
+        assert!(!scaleway.is_custom());
         assert!(custom.is_custom());
     }
 }
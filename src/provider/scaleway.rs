@@ -0,0 +1,234 @@
+//! Scaleway Provider Implementation
+//!
+//! This module contains the Scaleway-specific implementation of the CloudProvider trait.
+
+use super::{CloudProvider, ResourceLimits, ResourceType};
+use crate::error::{AmiError, Result};
+
+/// Scaleway provider implementation
+///
+/// # Scaleway Differences from AWS
+///
+/// - Uses UUIDs for resource IDs
+/// - Resource format: `scw:{project_id}:iam:{resource_type}/{name}`, readable by
+///   [`ScalewayArnTransformer`](crate::arn::ScalewayArnTransformer)
+/// - Access key IDs are `SCW` followed by 17 uppercase alphanumeric characters
+/// - Non-human principals are IAM applications rather than roles
+/// - No concept of paths
+#[derive(Debug, Clone)]
+pub struct ScalewayProvider {
+    project_id: String,
+    limits: ResourceLimits,
+}
+
+impl ScalewayProvider {
+    /// Creates a new Scaleway provider for a specific project
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use wami::provider::ScalewayProvider;
+    ///
+    /// let provider = ScalewayProvider::new("11111111-2222-3333-4444-555555555555");
+    /// ```
+    pub fn new(project_id: impl Into<String>) -> Self {
+        Self {
+            project_id: project_id.into(),
+            limits: ResourceLimits {
+                max_access_keys_per_user: 10, // API keys per principal
+                max_service_credentials_per_user_per_service: 10,
+                max_tags_per_resource: 64,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Returns the project the provider generates identifiers for
+    pub fn project_id(&self) -> &str {
+        &self.project_id
+    }
+}
+
+impl CloudProvider for ScalewayProvider {
+    fn name(&self) -> &str {
+        "scaleway"
+    }
+
+    fn generate_resource_identifier(
+        &self,
+        resource_type: ResourceType,
+        _account_id: &str,
+        _path: &str,
+        name: &str,
+    ) -> String {
+        let resource_type_name = match resource_type {
+            ResourceType::User => "user",
+            ResourceType::Group => "group",
+            ResourceType::Role | ResourceType::ServiceLinkedRole => "application",
+            ResourceType::Policy => "policy",
+            ResourceType::AccessKey => "api-key",
+            ResourceType::SamlProvider => "saml",
+            ResourceType::OidcProvider => "oidc",
+            _ => "resource",
+        };
+
+        format!(
+            "scw:{}:iam:{}/{}",
+            self.project_id, resource_type_name, name
+        )
+    }
+
+    fn generate_resource_id(&self, resource_type: ResourceType) -> String {
+        match resource_type {
+            ResourceType::AccessKey => {
+                let suffix = uuid::Uuid::new_v4().simple().to_string().to_uppercase();
+                format!("SCW{}", &suffix[..17])
+            }
+            // Every other Scaleway resource is identified by a UUID
+            _ => uuid::Uuid::new_v4().to_string(),
+        }
+    }
+
+    fn resource_limits(&self) -> &ResourceLimits {
+        &self.limits
+    }
+
+    fn validate_service_name(&self, service: &str) -> Result<()> {
+        // Scaleway product names are lowercase words joined by hyphens,
+        // e.g. "instance" or "object-storage"
+        let valid = !service.is_empty()
+            && !service.starts_with('-')
+            && !service.ends_with('-')
+            && service
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+        if !valid {
+            return Err(AmiError::InvalidParameter {
+                message: format!(
+                    "Invalid Scaleway service name: '{}'. Must be lowercase alphanumeric words separated by '-'",
+                    service
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    fn validate_path(&self, path: &str) -> Result<()> {
+        // Scaleway IAM has no paths, only the root is accepted
+        if !path.is_empty() && path != "/" {
+            return Err(AmiError::InvalidParameter {
+                message: format!(
+                    "Invalid path: '{}'. Scaleway resources do not support paths",
+                    path
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    fn generate_service_linked_role_name(
+        &self,
+        service_name: &str,
+        custom_suffix: Option<&str>,
+    ) -> String {
+        if let Some(suffix) = custom_suffix {
+            format!("{}-{}", service_name, suffix)
+        } else {
+            service_name.to_string()
+        }
+    }
+
+    fn generate_service_linked_role_path(&self, _service_name: &str) -> String {
+        String::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arn::{ArnTransformer, ScalewayArnTransformer};
+
+    const PROJECT_ID: &str = "11111111-2222-3333-4444-555555555555";
+
+    #[test]
+    fn test_scaleway_provider_name() {
+        let provider = ScalewayProvider::new(PROJECT_ID);
+        assert_eq!(provider.name(), "scaleway");
+    }
+
+    #[test]
+    fn test_generate_project_scoped_identifier() {
+        let provider = ScalewayProvider::new(PROJECT_ID);
+        let user = provider.generate_resource_identifier(ResourceType::User, "", "/", "alice");
+        assert_eq!(
+            user,
+            "scw:11111111-2222-3333-4444-555555555555:iam:user/alice"
+        );
+
+        let role = provider.generate_resource_identifier(ResourceType::Role, "", "", "deployer");
+        assert_eq!(
+            role,
+            "scw:11111111-2222-3333-4444-555555555555:iam:application/deployer"
+        );
+
+        // The identifier is readable by the Scaleway ARN transformer
+        let info = ScalewayArnTransformer.from_provider_arn(&user).unwrap();
+        assert_eq!(info.account_id, PROJECT_ID);
+        assert_eq!(info.resource_type, "user");
+        assert_eq!(info.resource_id, "alice");
+    }
+
+    #[test]
+    fn test_generate_ids() {
+        let provider = ScalewayProvider::new(PROJECT_ID);
+
+        let id = provider.generate_resource_id(ResourceType::User);
+        assert!(uuid::Uuid::parse_str(&id).is_ok());
+
+        let key_id = provider.generate_resource_id(ResourceType::AccessKey);
+        assert!(key_id.starts_with("SCW"));
+        assert_eq!(key_id.len(), 20);
+        assert!(key_id
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit()));
+    }
+
+    #[test]
+    fn test_generate_wami_arn() {
+        let provider = ScalewayProvider::new(PROJECT_ID);
+        let wami_arn = provider.generate_wami_arn(ResourceType::User, PROJECT_ID, "/", "alice");
+        assert_eq!(
+            wami_arn,
+            "arn:wami:iam::11111111-2222-3333-4444-555555555555:user/alice"
+        );
+    }
+
+    #[test]
+    fn test_validate_path() {
+        let provider = ScalewayProvider::new(PROJECT_ID);
+        assert!(provider.validate_path("").is_ok());
+        assert!(provider.validate_path("/").is_ok());
+        assert!(provider.validate_path("/engineering/").is_err());
+    }
+
+    #[test]
+    fn test_validate_service_name() {
+        let provider = ScalewayProvider::new(PROJECT_ID);
+        assert!(provider.validate_service_name("instance").is_ok());
+        assert!(provider.validate_service_name("object-storage").is_ok());
+        assert!(provider.validate_service_name("").is_err());
+        assert!(provider.validate_service_name("Object_Storage").is_err());
+        assert!(provider.validate_service_name("-instance").is_err());
+        assert!(provider
+            .validate_service_name("codecommit.amazonaws.com")
+            .is_err());
+    }
+
+    #[test]
+    fn test_scaleway_resource_limits() {
+        let provider = ScalewayProvider::new(PROJECT_ID);
+        let limits = provider.resource_limits();
+        assert_eq!(limits.max_access_keys_per_user, 10);
+        assert_eq!(limits.max_tags_per_resource, 64);
+    }
+}
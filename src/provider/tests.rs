@@ -207,10 +207,14 @@ mod integration_tests {
             max_access_keys_per_user: 5,
             max_service_credentials_per_user_per_service: 3,
             max_tags_per_resource: 100,
+            max_tag_key_length: 128,
+            max_tag_value_length: 256,
+            reserved_tag_key_prefixes: vec!["aws:".to_string()],
             session_duration_min: 1800, // 30 minutes
             session_duration_max: 7200, // 2 hours
             max_mfa_devices_per_user: 5,
             max_signing_certificates_per_user: 2,
+            ..Default::default()
         };
 
         let provider_impl = CustomProvider::builder()
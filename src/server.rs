@@ -0,0 +1,717 @@
+//! HTTP Facade
+//!
+//! An [axum] router serving the IAM query API on top of the WAMI services.
+//! Requests are `POST`ed as `application/x-www-form-urlencoded` AWS query
+//! parameters (`Action=CreateUser&UserName=alice`) and answered with IAM XML.
+//! Errors are rendered with [`AmiError::to_aws_error_xml`] under the status
+//! given by [`AmiError::http_status`].
+//!
+//! The router does not authenticate requests: every request runs as the
+//! context given to [`iam_router`], so deployments should put authentication
+//! in front of it.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use std::sync::{Arc, RwLock};
+//! use wami::arn::{TenantPath, WamiArn};
+//! use wami::context::WamiContext;
+//! use wami::server::iam_router;
+//! use wami::store::memory::InMemoryWamiStore;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let store = Arc::new(RwLock::new(InMemoryWamiStore::default()));
+//! let context = WamiContext::builder()
+//!     .instance_id("123456789012")
+//!     .tenant_path(TenantPath::single(0))
+//!     .caller_arn("arn:wami:.*:0:wami:123456789012:user/admin".parse::<WamiArn>()?)
+//!     .is_root(true)
+//!     .build()?;
+//! let router = iam_router(store, context);
+//! # let _ = router;
+//! # Ok(())
+//! # }
+//! ```
+
+#![allow(clippy::result_large_err)]
+
+use crate::context::WamiContext;
+use crate::error::{escape_xml, AmiError, Result, AWS_IAM_XML_NAMESPACE};
+use crate::service::{AttachmentService, GroupService, PolicyService, RoleService, UserService};
+use crate::store::traits::{GroupStore, PolicyStore, RoleStore, UserStore};
+use crate::types::{PaginationParams, Tag};
+use crate::wami::identity::group::{CreateGroupRequest, Group, ListGroupsRequest};
+use crate::wami::identity::role::{CreateRoleRequest, ListRolesRequest, Role};
+use crate::wami::identity::user::{CreateUserRequest, ListUsersRequest, User};
+use crate::wami::policies::attachment::*;
+use crate::wami::policies::policy::{CreatePolicyRequest, ListPoliciesRequest, Policy};
+use axum::extract::{Form, State};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::post;
+use axum::Router;
+use chrono::{DateTime, SecondsFormat, Utc};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// Query parameters of a single IAM request
+type Params = HashMap<String, String>;
+
+/// State shared by the requests of an [`iam_router`]
+struct IamState<S> {
+    store: Arc<RwLock<S>>,
+    context: WamiContext,
+}
+
+/// Build a router answering IAM query API requests posted to `/`
+///
+/// Supported actions cover users, groups, roles, managed policies and their
+/// attachments (`CreateUser`, `ListUsers`, `AttachUserPolicy`, ...). Any
+/// other action is answered with `InvalidAction`.
+pub fn iam_router<S>(store: Arc<RwLock<S>>, context: WamiContext) -> Router
+where
+    S: UserStore + GroupStore + RoleStore + PolicyStore + Send + Sync + 'static,
+{
+    Router::new()
+        .route("/", post(handle::<S>))
+        .with_state(Arc::new(IamState { store, context }))
+}
+
+async fn handle<S>(State(state): State<Arc<IamState<S>>>, Form(params): Form<Params>) -> Response
+where
+    S: UserStore + GroupStore + RoleStore + PolicyStore + Send + Sync + 'static,
+{
+    let request_id = uuid::Uuid::new_v4().to_string();
+
+    // Services hold the store lock across awaits, so their futures are not
+    // `Send`; drive them to completion on a blocking thread instead.
+    let runtime = tokio::runtime::Handle::current();
+    let id = request_id.clone();
+    let outcome = tokio::task::spawn_blocking(move || {
+        runtime.block_on(dispatch(&state.store, &state.context, &params, &id))
+    })
+    .await
+    .unwrap_or_else(|e| Err(AmiError::StoreError(e.to_string())));
+
+    match outcome {
+        Ok(body) => xml_response(StatusCode::OK, body),
+        Err(error) => xml_response(
+            StatusCode::from_u16(error.http_status()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+            error.to_aws_error_xml(&request_id),
+        ),
+    }
+}
+
+fn xml_response(status: StatusCode, body: String) -> Response {
+    (status, [(header::CONTENT_TYPE, "text/xml")], body).into_response()
+}
+
+/// Run the action named by the `Action` parameter and render its response
+async fn dispatch<S>(
+    store: &Arc<RwLock<S>>,
+    context: &WamiContext,
+    params: &Params,
+    request_id: &str,
+) -> Result<String>
+where
+    S: UserStore + GroupStore + RoleStore + PolicyStore,
+{
+    let action = required(params, "Action")?;
+    let result = match action.as_str() {
+        // Users
+        "CreateUser" => {
+            let user = UserService::new(store.clone())
+                .create_user(
+                    context,
+                    CreateUserRequest {
+                        user_name: required(params, "UserName")?,
+                        path: optional(params, "Path"),
+                        permissions_boundary: optional(params, "PermissionsBoundary"),
+                        tags: tags(params),
+                    },
+                )
+                .await?;
+            Some(element("User", &user_fields(&user)))
+        }
+        "GetUser" => {
+            let user_name = required(params, "UserName")?;
+            let user = UserService::new(store.clone())
+                .get_user(&user_name)
+                .await?
+                .ok_or_else(|| not_found("User", &user_name))?;
+            Some(element("User", &user_fields(&user)))
+        }
+        "ListUsers" => {
+            let (users, is_truncated, marker) = UserService::new(store.clone())
+                .list_users(
+                    context,
+                    ListUsersRequest {
+                        path_prefix: optional(params, "PathPrefix"),
+                        pagination: pagination(params)?,
+                        include_descendants: false,
+                    },
+                )
+                .await?;
+            Some(list(
+                "Users",
+                users.iter().map(user_fields),
+                is_truncated,
+                marker,
+            ))
+        }
+        "DeleteUser" => {
+            UserService::new(store.clone())
+                .delete_user(&required(params, "UserName")?)
+                .await?;
+            None
+        }
+
+        // Groups
+        "CreateGroup" => {
+            let group = GroupService::new(store.clone())
+                .create_group(
+                    context,
+                    CreateGroupRequest {
+                        group_name: required(params, "GroupName")?,
+                        path: optional(params, "Path"),
+                        tags: tags(params),
+                    },
+                )
+                .await?;
+            Some(element("Group", &group_fields(&group)))
+        }
+        "GetGroup" => {
+            let group_name = required(params, "GroupName")?;
+            let service = GroupService::new(store.clone());
+            let group = service
+                .get_group(&group_name)
+                .await?
+                .ok_or_else(|| not_found("Group", &group_name))?;
+            let users = service.list_users_in_group(&group_name).await?;
+            Some(format!(
+                "{}{}",
+                element("Group", &group_fields(&group)),
+                list("Users", users.iter().map(user_fields), false, None)
+            ))
+        }
+        "ListGroups" => {
+            let (groups, is_truncated, marker) = GroupService::new(store.clone())
+                .list_groups(
+                    context,
+                    ListGroupsRequest {
+                        path_prefix: optional(params, "PathPrefix"),
+                        pagination: pagination(params)?,
+                        include_descendants: false,
+                    },
+                )
+                .await?;
+            Some(list(
+                "Groups",
+                groups.iter().map(group_fields),
+                is_truncated,
+                marker,
+            ))
+        }
+        "DeleteGroup" => {
+            GroupService::new(store.clone())
+                .delete_group(&required(params, "GroupName")?)
+                .await?;
+            None
+        }
+        "AddUserToGroup" => {
+            GroupService::new(store.clone())
+                .add_user_to_group(
+                    &required(params, "GroupName")?,
+                    &required(params, "UserName")?,
+                )
+                .await?;
+            None
+        }
+        "RemoveUserFromGroup" => {
+            GroupService::new(store.clone())
+                .remove_user_from_group(
+                    &required(params, "GroupName")?,
+                    &required(params, "UserName")?,
+                )
+                .await?;
+            None
+        }
+
+        // Roles
+        "CreateRole" => {
+            let role = RoleService::new(store.clone())
+                .create_role(
+                    context,
+                    CreateRoleRequest {
+                        role_name: required(params, "RoleName")?,
+                        assume_role_policy_document: required(params, "AssumeRolePolicyDocument")?,
+                        path: optional(params, "Path"),
+                        description: optional(params, "Description"),
+                        max_session_duration: integer(params, "MaxSessionDuration")?,
+                        permissions_boundary: optional(params, "PermissionsBoundary"),
+                        tags: tags(params),
+                    },
+                )
+                .await?;
+            Some(element("Role", &role_fields(&role)))
+        }
+        "GetRole" => {
+            let role_name = required(params, "RoleName")?;
+            let role = RoleService::new(store.clone())
+                .get_role(&role_name)
+                .await?
+                .ok_or_else(|| not_found("Role", &role_name))?;
+            Some(element("Role", &role_fields(&role)))
+        }
+        "ListRoles" => {
+            let (roles, is_truncated, marker) = RoleService::new(store.clone())
+                .list_roles(
+                    context,
+                    ListRolesRequest {
+                        path_prefix: optional(params, "PathPrefix"),
+                        pagination: pagination(params)?,
+                        include_descendants: false,
+                    },
+                )
+                .await?;
+            Some(list(
+                "Roles",
+                roles.iter().map(role_fields),
+                is_truncated,
+                marker,
+            ))
+        }
+        "DeleteRole" => {
+            RoleService::new(store.clone())
+                .delete_role(&required(params, "RoleName")?)
+                .await?;
+            None
+        }
+
+        // Managed policies
+        "CreatePolicy" => {
+            let policy = PolicyService::new(store.clone())
+                .create_policy(
+                    context,
+                    CreatePolicyRequest {
+                        policy_name: required(params, "PolicyName")?,
+                        policy_document: required(params, "PolicyDocument")?,
+                        path: optional(params, "Path"),
+                        description: optional(params, "Description"),
+                        tags: tags(params),
+                    },
+                )
+                .await?;
+            Some(element("Policy", &policy_fields(&policy)))
+        }
+        "GetPolicy" => {
+            let policy_arn = required(params, "PolicyArn")?;
+            let policy = PolicyService::new(store.clone())
+                .get_policy(&policy_arn)
+                .await?
+                .ok_or_else(|| not_found("Policy", &policy_arn))?;
+            Some(element("Policy", &policy_fields(&policy)))
+        }
+        "ListPolicies" => {
+            let (policies, is_truncated, marker) = PolicyService::new(store.clone())
+                .list_policies(
+                    context,
+                    ListPoliciesRequest {
+                        scope: optional(params, "Scope"),
+                        only_attached: None,
+                        path_prefix: optional(params, "PathPrefix"),
+                        pagination: pagination(params)?,
+                        include_descendants: false,
+                    },
+                )
+                .await?;
+            Some(list(
+                "Policies",
+                policies.iter().map(policy_fields),
+                is_truncated,
+                marker,
+            ))
+        }
+        "DeletePolicy" => {
+            PolicyService::new(store.clone())
+                .delete_policy(&required(params, "PolicyArn")?)
+                .await?;
+            None
+        }
+
+        // Attachments
+        "AttachUserPolicy" => {
+            AttachmentService::new(store.clone())
+                .attach_user_policy(AttachUserPolicyRequest {
+                    user_name: required(params, "UserName")?,
+                    policy_arn: required(params, "PolicyArn")?,
+                })
+                .await?;
+            None
+        }
+        "DetachUserPolicy" => {
+            AttachmentService::new(store.clone())
+                .detach_user_policy(DetachUserPolicyRequest {
+                    user_name: required(params, "UserName")?,
+                    policy_arn: required(params, "PolicyArn")?,
+                })
+                .await?;
+            None
+        }
+        "ListAttachedUserPolicies" => {
+            let response = AttachmentService::new(store.clone())
+                .list_attached_user_policies(ListAttachedUserPoliciesRequest {
+                    user_name: required(params, "UserName")?,
+                })
+                .await?;
+            Some(attached_policies(&response.attached_policies))
+        }
+        "AttachGroupPolicy" => {
+            AttachmentService::new(store.clone())
+                .attach_group_policy(AttachGroupPolicyRequest {
+                    group_name: required(params, "GroupName")?,
+                    policy_arn: required(params, "PolicyArn")?,
+                })
+                .await?;
+            None
+        }
+        "DetachGroupPolicy" => {
+            AttachmentService::new(store.clone())
+                .detach_group_policy(DetachGroupPolicyRequest {
+                    group_name: required(params, "GroupName")?,
+                    policy_arn: required(params, "PolicyArn")?,
+                })
+                .await?;
+            None
+        }
+        "ListAttachedGroupPolicies" => {
+            let response = AttachmentService::new(store.clone())
+                .list_attached_group_policies(ListAttachedGroupPoliciesRequest {
+                    group_name: required(params, "GroupName")?,
+                })
+                .await?;
+            Some(attached_policies(&response.attached_policies))
+        }
+        "AttachRolePolicy" => {
+            AttachmentService::new(store.clone())
+                .attach_role_policy(AttachRolePolicyRequest {
+                    role_name: required(params, "RoleName")?,
+                    policy_arn: required(params, "PolicyArn")?,
+                })
+                .await?;
+            None
+        }
+        "DetachRolePolicy" => {
+            AttachmentService::new(store.clone())
+                .detach_role_policy(DetachRolePolicyRequest {
+                    role_name: required(params, "RoleName")?,
+                    policy_arn: required(params, "PolicyArn")?,
+                })
+                .await?;
+            None
+        }
+        "ListAttachedRolePolicies" => {
+            let response = AttachmentService::new(store.clone())
+                .list_attached_role_policies(ListAttachedRolePoliciesRequest {
+                    role_name: required(params, "RoleName")?,
+                })
+                .await?;
+            Some(attached_policies(&response.attached_policies))
+        }
+
+        _ => return Err(AmiError::OperationNotSupported { operation: action }),
+    };
+
+    Ok(respond(&action, result, request_id))
+}
+
+// Parameter parsing
+
+fn required(params: &Params, name: &str) -> Result<String> {
+    optional(params, name).ok_or_else(|| AmiError::InvalidParameter {
+        message: format!("Missing required parameter {}", name),
+    })
+}
+
+fn optional(params: &Params, name: &str) -> Option<String> {
+    params.get(name).filter(|value| !value.is_empty()).cloned()
+}
+
+fn integer(params: &Params, name: &str) -> Result<Option<i32>> {
+    optional(params, name)
+        .map(|value| {
+            value.parse().map_err(|_| AmiError::InvalidParameter {
+                message: format!("Parameter {} must be an integer", name),
+            })
+        })
+        .transpose()
+}
+
+fn pagination(params: &Params) -> Result<Option<PaginationParams>> {
+    let max_items = integer(params, "MaxItems")?;
+    let marker = optional(params, "Marker");
+    Ok((max_items.is_some() || marker.is_some()).then_some(PaginationParams { max_items, marker }))
+}
+
+/// Tags given as `Tags.member.N.Key` / `Tags.member.N.Value`, numbered from 1
+fn tags(params: &Params) -> Option<Vec<Tag>> {
+    let tags: Vec<Tag> = (1..)
+        .map_while(|n| {
+            let key = params.get(&format!("Tags.member.{}.Key", n))?;
+            let value = params
+                .get(&format!("Tags.member.{}.Value", n))
+                .cloned()
+                .unwrap_or_default();
+            Some(Tag {
+                key: key.clone(),
+                value,
+            })
+        })
+        .collect();
+    (!tags.is_empty()).then_some(tags)
+}
+
+fn not_found(resource_type: &str, name: &str) -> AmiError {
+    AmiError::ResourceNotFound {
+        resource: format!("{}: {}", resource_type, name),
+    }
+}
+
+// XML rendering
+
+/// Wrap an action result in its `<ActionResponse>` envelope
+fn respond(action: &str, result: Option<String>, request_id: &str) -> String {
+    let result = result
+        .map(|result| element(&format!("{}Result", action), &result))
+        .unwrap_or_default();
+    format!(
+        "<{action}Response xmlns=\"{}\">{}<ResponseMetadata>{}</ResponseMetadata></{action}Response>",
+        AWS_IAM_XML_NAMESPACE,
+        result,
+        text("RequestId", request_id),
+    )
+}
+
+/// An element around already rendered XML
+fn element(name: &str, inner: &str) -> String {
+    format!("<{name}>{inner}</{name}>")
+}
+
+/// An element around escaped text
+fn text(name: &str, value: &str) -> String {
+    element(name, &escape_xml(value))
+}
+
+fn optional_text(name: &str, value: Option<&str>) -> String {
+    value.map(|value| text(name, value)).unwrap_or_default()
+}
+
+fn date(name: &str, value: &DateTime<Utc>) -> String {
+    text(name, &value.to_rfc3339_opts(SecondsFormat::Secs, true))
+}
+
+fn members(name: &str, members: impl Iterator<Item = String>) -> String {
+    element(
+        name,
+        &members
+            .map(|member| element("member", &member))
+            .collect::<String>(),
+    )
+}
+
+fn list(
+    name: &str,
+    items: impl Iterator<Item = String>,
+    is_truncated: bool,
+    marker: Option<String>,
+) -> String {
+    format!(
+        "{}{}{}",
+        members(name, items),
+        text("IsTruncated", &is_truncated.to_string()),
+        optional_text("Marker", marker.as_deref())
+    )
+}
+
+fn tag_members(tags: &[Tag]) -> String {
+    if tags.is_empty() {
+        return String::new();
+    }
+    members(
+        "Tags",
+        tags.iter()
+            .map(|tag| format!("{}{}", text("Key", &tag.key), text("Value", &tag.value))),
+    )
+}
+
+fn permissions_boundary(arn: Option<&str>) -> String {
+    arn.map(|arn| {
+        element(
+            "PermissionsBoundary",
+            &format!(
+                "{}{}",
+                text("PermissionsBoundaryType", "Policy"),
+                text("PermissionsBoundaryArn", arn)
+            ),
+        )
+    })
+    .unwrap_or_default()
+}
+
+fn user_fields(user: &User) -> String {
+    [
+        text("Path", &user.path),
+        text("UserName", &user.user_name),
+        text("UserId", &user.user_id),
+        text("Arn", &user.arn),
+        date("CreateDate", &user.create_date),
+        permissions_boundary(user.permissions_boundary.as_deref()),
+        tag_members(&user.tags),
+    ]
+    .concat()
+}
+
+fn group_fields(group: &Group) -> String {
+    [
+        text("Path", &group.path),
+        text("GroupName", &group.group_name),
+        text("GroupId", &group.group_id),
+        text("Arn", &group.arn),
+        date("CreateDate", &group.create_date),
+    ]
+    .concat()
+}
+
+fn role_fields(role: &Role) -> String {
+    [
+        text("Path", &role.path),
+        text("RoleName", &role.role_name),
+        text("RoleId", &role.role_id),
+        text("Arn", &role.arn),
+        date("CreateDate", &role.create_date),
+        text(
+            "AssumeRolePolicyDocument",
+            &role.assume_role_policy_document,
+        ),
+        optional_text("Description", role.description.as_deref()),
+        optional_text(
+            "MaxSessionDuration",
+            role.max_session_duration.map(|d| d.to_string()).as_deref(),
+        ),
+        permissions_boundary(role.permissions_boundary.as_deref()),
+        tag_members(&role.tags),
+    ]
+    .concat()
+}
+
+fn policy_fields(policy: &Policy) -> String {
+    [
+        text("PolicyName", &policy.policy_name),
+        text("PolicyId", &policy.policy_id),
+        text("Arn", &policy.arn),
+        text("Path", &policy.path),
+        text("DefaultVersionId", &policy.default_version_id),
+        text("AttachmentCount", &policy.attachment_count.to_string()),
+        text(
+            "PermissionsBoundaryUsageCount",
+            &policy.permissions_boundary_usage_count.to_string(),
+        ),
+        text("IsAttachable", &policy.is_attachable.to_string()),
+        optional_text("Description", policy.description.as_deref()),
+        date("CreateDate", &policy.create_date),
+        date("UpdateDate", &policy.update_date),
+        tag_members(&policy.tags),
+    ]
+    .concat()
+}
+
+fn attached_policies(policies: &[AttachedPolicy]) -> String {
+    list(
+        "AttachedPolicies",
+        policies.iter().map(|policy| {
+            format!(
+                "{}{}",
+                text("PolicyName", &policy.policy_name),
+                text("PolicyArn", &policy.policy_arn)
+            )
+        }),
+        false,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arn::{TenantPath, WamiArn};
+    use crate::store::memory::InMemoryWamiStore;
+    use axum::body::{to_bytes, Body};
+    use axum::http::Request;
+    use tower::ServiceExt;
+
+    fn test_context() -> WamiContext {
+        let arn: WamiArn = "arn:wami:.*:12345678:wami:123456789012:user/test"
+            .parse()
+            .unwrap();
+        WamiContext::builder()
+            .instance_id("123456789012")
+            .tenant_path(TenantPath::single(12345678))
+            .caller_arn(arn)
+            .is_root(false)
+            .build()
+            .unwrap()
+    }
+
+    async fn post(router: Router, body: &'static str) -> (StatusCode, String) {
+        let request = Request::post("/")
+            .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(Body::from(body))
+            .unwrap();
+        let response = router.oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_create_user_over_http() {
+        let store = Arc::new(RwLock::new(InMemoryWamiStore::default()));
+        let router = iam_router(store.clone(), test_context());
+
+        let (status, body) = post(router.clone(), "Action=CreateUser&UserName=alice").await;
+        assert_eq!(status, StatusCode::OK);
+
+        let snapshot = store.read().unwrap().clone();
+        let user = snapshot.get_user("alice").await.unwrap().unwrap();
+        assert!(body.starts_with(
+            "<CreateUserResponse xmlns=\"https://iam.amazonaws.com/doc/2010-05-08/\"><CreateUserResult><User>"
+        ));
+        assert!(body.contains(&format!("<Arn>{}</Arn>", user.arn)));
+
+        let (status, body) = post(router.clone(), "Action=ListUsers").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.contains("<UserName>alice</UserName>"));
+
+        let (status, body) = post(router, "Action=CreateUser&UserName=alice").await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert!(body.contains("<Code>EntityAlreadyExists</Code>"));
+    }
+
+    #[tokio::test]
+    async fn test_errors_over_http() {
+        let store = Arc::new(RwLock::new(InMemoryWamiStore::default()));
+        let router = iam_router(store, test_context());
+
+        let (status, body) = post(router.clone(), "Action=GetUser&UserName=ghost").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert!(body.contains("<Code>NoSuchEntity</Code>"));
+
+        let (status, body) = post(router.clone(), "Action=CreateUser").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(body.contains("<Code>InvalidInput</Code>"));
+
+        let (status, body) = post(router, "Action=LaunchRocket").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(body.contains("<Code>InvalidAction</Code>"));
+    }
+}
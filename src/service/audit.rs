@@ -0,0 +1,108 @@
+//! Audit Logging
+//!
+//! Services given an [`AuditSink`] (via their `with_audit_sink` method) report
+//! every mutating operation and authorization decision to it as an
+//! [`AuditEvent`], whether the operation succeeded or not.
+
+use crate::context::WamiContext;
+use crate::error::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// How an audited operation ended
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AuditOutcome {
+    /// The operation completed
+    Succeeded,
+    /// The operation failed with the given error
+    Failed {
+        /// The error message
+        message: String,
+    },
+    /// The authorization decision allowed the request
+    Allowed,
+    /// The authorization decision denied the request
+    Denied,
+}
+
+/// A record of one audited operation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEvent {
+    /// The operation (e.g. `CreateUser`), or the action decided on for authorization decisions
+    pub operation: String,
+    /// The caller's ARN, when the operation is performed with a [`WamiContext`]
+    pub actor_arn: Option<String>,
+    /// The target resource's WAMI ARN, or its name when the resource could not be resolved
+    pub target_arn: String,
+    /// When the operation completed
+    pub timestamp: DateTime<Utc>,
+    /// How the operation ended
+    pub outcome: AuditOutcome,
+}
+
+/// Destination of audit events
+pub trait AuditSink: Send + Sync {
+    /// Record an event
+    fn record(&self, event: AuditEvent);
+}
+
+/// Audit sink keeping events in memory, mainly for tests
+#[derive(Debug, Default)]
+pub struct VecAuditSink {
+    events: Mutex<Vec<AuditEvent>>,
+}
+
+impl VecAuditSink {
+    /// Create an empty sink
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The events recorded so far, oldest first
+    pub fn events(&self) -> Vec<AuditEvent> {
+        self.events.lock().unwrap().clone()
+    }
+}
+
+impl AuditSink for VecAuditSink {
+    fn record(&self, event: AuditEvent) {
+        self.events.lock().unwrap().push(event);
+    }
+}
+
+/// Record the result of an operation on `sink`, if there is one
+pub(crate) fn record_result<T>(
+    sink: Option<&Arc<dyn AuditSink>>,
+    operation: &str,
+    actor: Option<&WamiContext>,
+    target_arn: String,
+    result: &Result<T>,
+) {
+    let outcome = match result {
+        Ok(_) => AuditOutcome::Succeeded,
+        Err(e) => AuditOutcome::Failed {
+            message: e.to_string(),
+        },
+    };
+    record(sink, operation, actor, target_arn, outcome);
+}
+
+/// Record an outcome on `sink`, if there is one
+pub(crate) fn record(
+    sink: Option<&Arc<dyn AuditSink>>,
+    operation: &str,
+    actor: Option<&WamiContext>,
+    target_arn: String,
+    outcome: AuditOutcome,
+) {
+    if let Some(sink) = sink {
+        sink.record(AuditEvent {
+            operation: operation.to_string(),
+            actor_arn: actor.map(|context| context.caller_arn().to_string()),
+            target_arn,
+            timestamp: Utc::now(),
+            outcome,
+        });
+    }
+}
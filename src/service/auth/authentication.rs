@@ -32,11 +32,16 @@
 //! ```
 
 use crate::arn::TenantPath;
-use crate::context::WamiContext;
+use crate::context::{SessionInfo, WamiContext};
 use crate::error::{AmiError, Result};
-use crate::store::traits::{AccessKeyStore, UserStore};
+use crate::store::traits::{AccessKeyStore, SessionStore, UserStore};
 use crate::wami::identity::root_user::ROOT_USER_NAME;
 use crate::wami::identity::User;
+use crate::wami::sts::session::SessionStatus;
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use chrono::{DateTime, Utc};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
@@ -79,18 +84,68 @@ where
     /// - The access key doesn't exist
     /// - The secret doesn't match
     /// - The access key is inactive
-    /// - The user doesn't exist
+    /// - The user doesn't exist or has expired
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(operation = "Authenticate", resource = %access_key_id))
+    )]
     pub async fn authenticate(
         &self,
         access_key_id: &str,
         secret_access_key: &str,
+    ) -> Result<WamiContext> {
+        self.authenticate_at(access_key_id, secret_access_key, Utc::now())
+            .await
+    }
+
+    /// Authenticate with access key credentials as of `now`
+    ///
+    /// Same as [`authenticate`](Self::authenticate), with the time used to
+    /// check user expiration supplied by the caller. The key's last use is
+    /// recorded as a global call to `iam`.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(operation = "Authenticate", resource = %access_key_id))
+    )]
+    pub async fn authenticate_at(
+        &self,
+        access_key_id: &str,
+        secret_access_key: &str,
+        now: DateTime<Utc>,
+    ) -> Result<WamiContext> {
+        self.authenticate_request(access_key_id, secret_access_key, "iam", None, now)
+            .await
+    }
+
+    /// Authenticate a call to `service_name` in `region` as of `now`
+    ///
+    /// On success, the call is recorded as the access key's last use, with
+    /// `now` as its date.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(operation = "Authenticate", resource = %access_key_id))
+    )]
+    pub async fn authenticate_request(
+        &self,
+        access_key_id: &str,
+        secret_access_key: &str,
+        service_name: &str,
+        region: Option<&str>,
+        now: DateTime<Utc>,
     ) -> Result<WamiContext> {
         // Step 1: Validate access key and get the user
         let user = self
-            .validate_access_key(access_key_id, secret_access_key)
+            .validate_access_key(access_key_id, secret_access_key, now)
+            .await?;
+
+        // Step 2: Record the key's last use
+        self.store
+            .write()
+            .await
+            .update_access_key_last_used(access_key_id, service_name, region, now)
             .await?;
 
-        // Step 2: Create context from user
+        // Step 3: Create context from user
         self.create_context_from_user(&user).await
     }
 
@@ -100,7 +155,7 @@ where
     /// - The access key exists
     /// - The secret matches (constant-time comparison)
     /// - The access key is active
-    /// - The owning user exists
+    /// - The owning user exists and has not expired as of `now`
     ///
     /// # Returns
     ///
@@ -109,6 +164,7 @@ where
         &self,
         access_key_id: &str,
         secret_access_key: &str,
+        now: DateTime<Utc>,
     ) -> Result<User> {
         let store = self.store.read().await;
 
@@ -126,20 +182,16 @@ where
             });
         }
 
-        // Verify the secret (constant-time comparison)
-        // Note: In production, secret_access_key in the model should be the hash
-        // For now, we'll do a simple comparison
-        let secret_matches = if let Some(stored_secret) = &access_key.secret_access_key {
-            // Try bcrypt verification first (if it's hashed)
-            if stored_secret.starts_with("$2") {
-                // It's a bcrypt hash
-                bcrypt::verify(secret_access_key, stored_secret).unwrap_or(false)
-            } else {
-                // Plaintext comparison (not secure, for backward compatibility)
+        // Verify the secret against its stored hash
+        let secret_matches = match &access_key.secret_access_key {
+            Some(stored_secret) if is_hashed_secret(stored_secret) => {
+                verify_secret(secret_access_key, stored_secret).unwrap_or(false)
+            }
+            // Plaintext comparison (not secure, for keys stored before hashing)
+            Some(stored_secret) => {
                 constant_time_compare(secret_access_key.as_bytes(), stored_secret.as_bytes())
             }
-        } else {
-            false
+            None => false,
         };
 
         if !secret_matches {
@@ -152,6 +204,7 @@ where
         let user = store
             .get_user(&access_key.user_name)
             .await?
+            .filter(|user| !user.is_expired_at(now))
             .ok_or_else(|| AmiError::ResourceNotFound {
                 resource: format!("User {}", access_key.user_name),
             })?;
@@ -187,6 +240,16 @@ where
     /// Create context for a root user
     ///
     /// Convenience method for authenticating as root user.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                operation = "AuthenticateRoot",
+                resource = %access_key_id,
+            )
+        )
+    )]
     pub async fn authenticate_root(
         &self,
         instance_id: &str,
@@ -195,7 +258,7 @@ where
     ) -> Result<WamiContext> {
         // Validate credentials
         let user = self
-            .validate_access_key(access_key_id, secret_access_key)
+            .validate_access_key(access_key_id, secret_access_key, Utc::now())
             .await?;
 
         // Verify this is actually the root user
@@ -224,6 +287,115 @@ where
     }
 }
 
+impl<S> AuthenticationService<S>
+where
+    S: AccessKeyStore + UserStore + SessionStore + Send + Sync,
+{
+    /// Authenticate with temporary credentials issued by STS
+    ///
+    /// The session token identifies the session; the access key ID and
+    /// secret must be the ones issued with it.
+    ///
+    /// # Returns
+    ///
+    /// A `WamiContext` for the session's ARN, whose `session_info` carries
+    /// the session's expiration, assumed role and scoped policy
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - No session matches the token, or its access key ID or secret differ
+    /// - The session has expired or is no longer active
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                operation = "AuthenticateSession",
+                resource = %access_key_id,
+            )
+        )
+    )]
+    pub async fn authenticate_session(
+        &self,
+        access_key_id: &str,
+        secret_access_key: &str,
+        session_token: &str,
+    ) -> Result<WamiContext> {
+        self.authenticate_session_at(access_key_id, secret_access_key, session_token, Utc::now())
+            .await
+    }
+
+    /// Authenticate with temporary credentials as of `now`
+    ///
+    /// Same as [`authenticate_session`](Self::authenticate_session), with the
+    /// time used to check the session's expiration supplied by the caller.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                operation = "AuthenticateSession",
+                resource = %access_key_id,
+            )
+        )
+    )]
+    pub async fn authenticate_session_at(
+        &self,
+        access_key_id: &str,
+        secret_access_key: &str,
+        session_token: &str,
+        now: DateTime<Utc>,
+    ) -> Result<WamiContext> {
+        let invalid = || AmiError::InvalidParameter {
+            message: "Invalid access key ID, secret or session token".to_string(),
+        };
+
+        let session = self
+            .store
+            .read()
+            .await
+            .get_session(session_token)
+            .await?
+            .ok_or_else(invalid)?;
+
+        let credentials_match =
+            constant_time_compare(access_key_id.as_bytes(), session.access_key_id.as_bytes())
+                && constant_time_compare(
+                    secret_access_key.as_bytes(),
+                    session.secret_access_key.as_bytes(),
+                );
+        if !credentials_match {
+            return Err(invalid());
+        }
+
+        if session.status != SessionStatus::Active || session.expiration <= now {
+            return Err(AmiError::AccessDenied {
+                message: "The security token included in the request is expired".to_string(),
+            });
+        }
+
+        let arn = &session.wami_arn;
+        WamiContext::builder()
+            .instance_id(arn.wami_instance_id.clone())
+            .tenant_path(arn.tenant_path.clone())
+            .caller_arn(arn.clone())
+            .is_root(false)
+            .session_info(SessionInfo {
+                session_token: session.session_token.clone(),
+                expiration: session.expiration.timestamp(),
+                assumed_role_arn: session
+                    .assumed_role_arn
+                    .as_deref()
+                    .and_then(|role_arn| role_arn.parse().ok()),
+                policy: session.policy.clone(),
+                tags: session.tags.clone(),
+                principal_arn: session.principal_arn.clone(),
+            })
+            .build()
+    }
+}
+
 /// Constant-time string comparison to prevent timing attacks
 ///
 /// This is important for security-sensitive comparisons like secrets.
@@ -242,17 +414,42 @@ fn constant_time_compare(a: &[u8], b: &[u8]) -> bool {
 
 /// Helper function to hash a secret access key
 ///
-/// This should be used when creating new access keys to store the hash
-/// instead of the plaintext secret.
+/// Produces an Argon2id PHC string with a random per-secret salt. This should
+/// be used when creating new access keys to store the hash instead of the
+/// plaintext secret.
 pub fn hash_secret(secret: &str) -> Result<String> {
-    bcrypt::hash(secret, bcrypt::DEFAULT_COST)
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(secret.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
         .map_err(|e| AmiError::StoreError(format!("Failed to hash secret: {}", e)))
 }
 
 /// Helper function to verify a secret against a hash
+///
+/// Accepts Argon2 hashes as well as bcrypt hashes from earlier releases.
 pub fn verify_secret(secret: &str, hash: &str) -> Result<bool> {
-    bcrypt::verify(secret, hash)
-        .map_err(|e| AmiError::StoreError(format!("Failed to verify secret: {}", e)))
+    if hash.starts_with("$2") {
+        return bcrypt::verify(secret, hash)
+            .map_err(|e| AmiError::StoreError(format!("Failed to verify secret: {}", e)));
+    }
+
+    let parsed = PasswordHash::new(hash)
+        .map_err(|e| AmiError::StoreError(format!("Failed to verify secret: {}", e)))?;
+    match Argon2::default().verify_password(secret.as_bytes(), &parsed) {
+        Ok(()) => Ok(true),
+        Err(argon2::password_hash::Error::Password) => Ok(false),
+        Err(e) => Err(AmiError::StoreError(format!(
+            "Failed to verify secret: {}",
+            e
+        ))),
+    }
+}
+
+/// Whether a stored secret is a hash produced by [`hash_secret`] (or bcrypt)
+/// rather than a plaintext secret
+fn is_hashed_secret(stored_secret: &str) -> bool {
+    stored_secret.starts_with("$argon2") || stored_secret.starts_with("$2")
 }
 
 #[cfg(test)]
@@ -271,8 +468,256 @@ mod tests {
         let secret = "my-super-secret-key";
         let hash = hash_secret(secret).unwrap();
 
-        assert!(hash.starts_with("$2")); // Bcrypt hash marker
+        assert!(hash.starts_with("$argon2id$")); // Argon2id PHC string
         assert!(verify_secret(secret, &hash).unwrap());
         assert!(!verify_secret("wrong-secret", &hash).unwrap());
+
+        // Each secret gets its own salt
+        assert_ne!(hash, hash_secret(secret).unwrap());
+
+        // Hashes from earlier releases still verify
+        let legacy = bcrypt::hash(secret, 4).unwrap();
+        assert!(verify_secret(secret, &legacy).unwrap());
+        assert!(!verify_secret("wrong-secret", &legacy).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_authenticate_rejects_expired_user() {
+        use crate::arn::WamiArn;
+        use crate::store::memory::InMemoryWamiStore;
+        use crate::wami::credentials::access_key::builder::build_access_key;
+        use crate::wami::identity::user::builder::{build_user, set_expiration};
+        use chrono::Duration;
+
+        let arn: WamiArn = "arn:wami:.*:12345678:wami:123456789012:user/test"
+            .parse()
+            .unwrap();
+        let context = WamiContext::builder()
+            .instance_id("123456789012")
+            .tenant_path(TenantPath::single(12345678))
+            .caller_arn(arn)
+            .is_root(false)
+            .build()
+            .unwrap();
+
+        let now = Utc::now();
+        let mut store = InMemoryWamiStore::default();
+        let guest = build_user("guest".to_string(), None, &context).unwrap();
+        let guest = set_expiration(guest, now + Duration::minutes(15));
+        store.create_user(guest).await.unwrap();
+        let key = build_access_key("guest".to_string(), &context).unwrap();
+        let secret = key.secret_access_key.clone().unwrap();
+        store.create_access_key(key.clone()).await.unwrap();
+
+        let service = AuthenticationService::new(Arc::new(RwLock::new(store)));
+
+        // Valid before expiry
+        let authenticated = service
+            .authenticate_at(&key.access_key_id, &secret, now)
+            .await
+            .unwrap();
+        assert_eq!(authenticated.instance_id(), "123456789012");
+
+        // Advance the clock past the TTL
+        let result = service
+            .authenticate_at(&key.access_key_id, &secret, now + Duration::minutes(16))
+            .await;
+        assert!(matches!(result, Err(AmiError::ResourceNotFound { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_authenticate_records_access_key_last_used() {
+        use crate::arn::WamiArn;
+        use crate::store::memory::InMemoryWamiStore;
+        use crate::wami::credentials::access_key::builder::build_access_key;
+        use crate::wami::identity::user::builder::build_user;
+        use chrono::Duration;
+
+        let arn: WamiArn = "arn:wami:.*:12345678:wami:123456789012:user/test"
+            .parse()
+            .unwrap();
+        let context = WamiContext::builder()
+            .instance_id("123456789012")
+            .tenant_path(TenantPath::single(12345678))
+            .caller_arn(arn)
+            .is_root(false)
+            .build()
+            .unwrap();
+
+        let mut store = InMemoryWamiStore::default();
+        store
+            .create_user(build_user("alice".to_string(), None, &context).unwrap())
+            .await
+            .unwrap();
+        let key = build_access_key("alice".to_string(), &context).unwrap();
+        let secret = key.secret_access_key.clone().unwrap();
+        store.create_access_key(key.clone()).await.unwrap();
+
+        let store = Arc::new(RwLock::new(store));
+        let service = AuthenticationService::new(store.clone());
+
+        // Never used yet
+        let last_used = store
+            .read()
+            .await
+            .get_access_key_last_used(&key.access_key_id)
+            .await
+            .unwrap();
+        assert!(last_used.last_used_date.is_none());
+        assert_eq!(last_used.service_name.as_deref(), Some("N/A"));
+        assert_eq!(last_used.region.as_deref(), Some("N/A"));
+
+        let now = Utc::now();
+        service
+            .authenticate_request(&key.access_key_id, &secret, "s3", Some("eu-west-1"), now)
+            .await
+            .unwrap();
+
+        let last_used = store
+            .read()
+            .await
+            .get_access_key_last_used(&key.access_key_id)
+            .await
+            .unwrap();
+        assert_eq!(last_used.last_used_date, Some(now));
+        assert_eq!(last_used.service_name.as_deref(), Some("s3"));
+        assert_eq!(last_used.region.as_deref(), Some("eu-west-1"));
+
+        // A failed authentication is not recorded
+        let later = now + Duration::minutes(5);
+        assert!(service
+            .authenticate_at(&key.access_key_id, "wrong-secret", later)
+            .await
+            .is_err());
+        service
+            .authenticate_at(&key.access_key_id, &secret, later)
+            .await
+            .unwrap();
+        let last_used = store
+            .read()
+            .await
+            .get_access_key_last_used(&key.access_key_id)
+            .await
+            .unwrap();
+        assert_eq!(last_used.last_used_date, Some(later));
+        assert_eq!(last_used.service_name.as_deref(), Some("iam"));
+        assert!(last_used.region.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_authenticate_session_round_trips_assume_role() {
+        use crate::arn::WamiArn;
+        use crate::service::sts::AssumeRoleService;
+        use crate::store::memory::InMemoryWamiStore;
+        use crate::store::traits::RoleStore;
+        use crate::wami::identity::role::builder::build_role;
+        use crate::wami::sts::assume_role::AssumeRoleRequest;
+        use chrono::Duration;
+
+        let arn: WamiArn = "arn:wami:.*:12345678:wami:123456789012:user/test"
+            .parse()
+            .unwrap();
+        let context = WamiContext::builder()
+            .instance_id("123456789012")
+            .tenant_path(TenantPath::single(12345678))
+            .caller_arn(arn)
+            .is_root(false)
+            .build()
+            .unwrap();
+
+        let store = Arc::new(std::sync::RwLock::new(InMemoryWamiStore::default()));
+        let role = build_role(
+            "Reader".to_string(),
+            r#"{"Version":"2012-10-17","Statement":[{"Effect":"Allow","Principal":{"AWS":"123456789012"},"Action":"sts:AssumeRole"}]}"#.to_string(),
+            None,
+            None,
+            None,
+            &context,
+        )
+        .unwrap();
+        let role_arn = role.wami_arn.to_string();
+        store.write().unwrap().create_role(role).await.unwrap();
+
+        let session_policy = r#"{"Version":"2012-10-17","Statement":[{"Effect":"Allow","Action":"s3:GetObject","Resource":"*"}]}"#;
+        let assumed = AssumeRoleService::new(store.clone())
+            .assume_role(
+                &context,
+                AssumeRoleRequest {
+                    role_arn: role_arn.clone(),
+                    role_session_name: "reader-session".to_string(),
+                    duration_seconds: Some(900),
+                    external_id: None,
+                    policy: Some(session_policy.to_string()),
+                    tags: vec![],
+                    transitive_tag_keys: vec![],
+                },
+                "arn:aws:iam::123456789012:user/alice",
+            )
+            .await
+            .unwrap();
+        let credentials = assumed.credentials;
+
+        let store = store.read().unwrap().clone();
+        let service = AuthenticationService::new(Arc::new(RwLock::new(store)));
+
+        let session_context = service
+            .authenticate_session(
+                &credentials.access_key_id,
+                &credentials.secret_access_key,
+                &credentials.session_token,
+            )
+            .await
+            .unwrap();
+        assert_eq!(session_context.caller_arn(), &credentials.wami_arn);
+        assert_eq!(session_context.instance_id(), "123456789012");
+        assert!(!session_context.is_root());
+        let session_info = session_context.session_info().unwrap();
+        assert_eq!(session_info.session_token, credentials.session_token);
+        assert_eq!(session_info.expiration, credentials.expiration.timestamp());
+        assert_eq!(
+            session_info
+                .assumed_role_arn
+                .as_ref()
+                .map(|arn| arn.to_string()),
+            Some(role_arn)
+        );
+        assert_eq!(session_info.policy.as_deref(), Some(session_policy));
+
+        // Mismatched token, access key or secret
+        for (access_key_id, secret, token) in [
+            (
+                credentials.access_key_id.as_str(),
+                credentials.secret_access_key.as_str(),
+                "TOKENunknown",
+            ),
+            (
+                "AKIAOTHER",
+                credentials.secret_access_key.as_str(),
+                credentials.session_token.as_str(),
+            ),
+            (
+                credentials.access_key_id.as_str(),
+                "wrong-secret",
+                credentials.session_token.as_str(),
+            ),
+        ] {
+            assert!(matches!(
+                service
+                    .authenticate_session(access_key_id, secret, token)
+                    .await,
+                Err(AmiError::InvalidParameter { .. })
+            ));
+        }
+
+        // Expired once the session's duration has elapsed
+        let result = service
+            .authenticate_session_at(
+                &credentials.access_key_id,
+                &credentials.secret_access_key,
+                &credentials.session_token,
+                credentials.expiration + Duration::seconds(1),
+            )
+            .await;
+        assert!(matches!(result, Err(AmiError::AccessDenied { .. })));
     }
 }
@@ -4,7 +4,19 @@
 //! 1. Root users bypass all checks (full access)
 //! 2. Regular users are subject to policy evaluation
 //! 3. Policies are evaluated from user, groups, and roles
-//! 4. Deny overrides Allow
+//! 4. SSO permission sets apply to resources in the accounts they are assigned for
+//! 5. Deny overrides Allow
+//!
+//! # Decision logic
+//!
+//! [`AuthorizationService::decide`] combines every policy source of a request
+//! following the AWS evaluation order:
+//!
+//! 1. An explicit `Deny` in any identity, resource, boundary or session policy wins.
+//! 2. Within an account, an `Allow` in an identity *or* a resource policy grants access;
+//!    across accounts, both an identity and a resource policy must allow.
+//! 3. A permissions boundary and session policies never grant anything: when present,
+//!    each must also allow the request, or it is implicitly denied.
 //!
 //! # Example
 //!
@@ -37,31 +49,115 @@
 //! }
 //! ```
 
-use crate::arn::WamiArn;
+use crate::arn::{TenantPath, WamiArn};
 use crate::context::WamiContext;
 use crate::error::{AmiError, Result};
-use crate::store::traits::{GroupStore, PolicyStore, RoleStore, UserStore};
-use crate::types::PolicyDocument;
+use crate::service::audit::{self, AuditOutcome, AuditSink};
+use crate::store::traits::{
+    AccountAssignmentStore, GroupStore, PermissionSetStore, PolicyStore, RoleStore, UserStore,
+};
+use crate::telemetry;
+use crate::types::{PolicyDocument, PolicyStatement};
+use crate::wami::policies::evaluation::condition::evaluate_conditions;
+use crate::wami::policies::evaluation::ContextEntry;
+use crate::wami::sts::DecodedMessage;
+use crate::wami::tenant::TenantId;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
+/// Prefix of the encoded authorization failure message in `AccessDenied` errors
+pub const ENCODED_MESSAGE_PREFIX: &str = "Encoded authorization failure message: ";
+
+/// The policies that bear on a single request, grouped by where they come from
+#[derive(Debug, Clone, Default)]
+pub struct PolicySources {
+    /// Identity-based policies of the principal (its own, its groups', SSO permission sets)
+    pub identity: Vec<PolicyDocument>,
+    /// Resource-based policies attached to the target resource
+    pub resource: Vec<PolicyDocument>,
+    /// The principal's permissions boundary, if it has one
+    pub permissions_boundary: Option<PolicyDocument>,
+    /// Session policies passed when the caller's temporary credentials were issued
+    pub session: Vec<PolicyDocument>,
+    /// Whether the principal and the resource belong to different accounts
+    pub cross_account: bool,
+}
+
 /// Authorization Service
 ///
 /// Handles permission checking based on IAM policies.
 pub struct AuthorizationService<S>
 where
-    S: UserStore + GroupStore + RoleStore + PolicyStore + Send + Sync,
+    S: UserStore
+        + GroupStore
+        + RoleStore
+        + PolicyStore
+        + AccountAssignmentStore
+        + PermissionSetStore
+        + Send
+        + Sync,
 {
     store: Arc<RwLock<S>>,
+    /// When set, only resources and policies of this tenant (and its descendants) are considered
+    tenant_scope: Option<TenantPath>,
+    audit_sink: Option<Arc<dyn AuditSink>>,
 }
 
 impl<S> AuthorizationService<S>
 where
-    S: UserStore + GroupStore + RoleStore + PolicyStore + Send + Sync,
+    S: UserStore
+        + GroupStore
+        + RoleStore
+        + PolicyStore
+        + AccountAssignmentStore
+        + PermissionSetStore
+        + Send
+        + Sync,
 {
     /// Create a new authorization service
     pub fn new(store: Arc<RwLock<S>>) -> Self {
-        Self { store }
+        Self {
+            store,
+            tenant_scope: None,
+            audit_sink: None,
+        }
+    }
+
+    /// Create an authorization service confined to a single tenant
+    ///
+    /// A tenant-scoped service denies any request whose caller or target
+    /// resource lies outside the tenant, and ignores policies and users that
+    /// belong to other tenants even when their names or AWS ARNs coincide.
+    pub fn for_tenant(store: Arc<RwLock<S>>, tenant_id: &TenantId) -> Self {
+        Self {
+            store,
+            tenant_scope: Some(TenantPath::from_tenant_id(tenant_id)),
+            audit_sink: None,
+        }
+    }
+
+    /// Returns a new service instance reporting the decisions of
+    /// [`explain`](Self::explain) (and the methods built on it) to `sink`
+    ///
+    /// Each event's operation is the decided action and its target the resource ARN.
+    pub fn with_audit_sink(&self, sink: Arc<dyn AuditSink>) -> Self {
+        Self {
+            store: self.store.clone(),
+            tenant_scope: self.tenant_scope.clone(),
+            audit_sink: Some(sink),
+        }
+    }
+
+    /// Returns the tenant this service is confined to, if any
+    pub fn tenant_scope(&self) -> Option<&TenantPath> {
+        self.tenant_scope.as_ref()
+    }
+
+    /// Whether an ARN falls within the tenant scope (always true when unscoped)
+    fn in_scope(&self, arn: &WamiArn) -> bool {
+        self.tenant_scope
+            .as_ref()
+            .is_none_or(|scope| arn.belongs_to_tenant(scope))
     }
 
     /// Authorize an action on a resource
@@ -90,38 +186,149 @@ where
         action: &str,
         resource_arn: &WamiArn,
     ) -> Result<bool> {
+        Ok(self.explain(context, action, resource_arn).await?.allowed)
+    }
+
+    /// Authorize an action and explain the decision
+    ///
+    /// Returns the decision along with the statements that determined it.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                operation = "Authorize",
+                tenant = %context.tenant_path(),
+                resource = %resource_arn,
+                action = %action,
+                decision = tracing::field::Empty,
+            )
+        )
+    )]
+    pub async fn explain(
+        &self,
+        context: &WamiContext,
+        action: &str,
+        resource_arn: &WamiArn,
+    ) -> Result<DecodedMessage> {
+        let decision = self.evaluate(context, action, resource_arn).await?;
+        telemetry::record_decision(decision.allowed);
+        audit::record(
+            self.audit_sink.as_ref(),
+            action,
+            Some(context),
+            resource_arn.to_string(),
+            if decision.allowed {
+                AuditOutcome::Allowed
+            } else {
+                AuditOutcome::Denied
+            },
+        );
+        Ok(decision)
+    }
+
+    /// Evaluate the caller's policies for an action on a resource
+    async fn evaluate(
+        &self,
+        context: &WamiContext,
+        action: &str,
+        resource_arn: &WamiArn,
+    ) -> Result<DecodedMessage> {
+        let mut decision = DecodedMessage {
+            allowed: false,
+            explicit_deny: false,
+            matched_statements: vec![],
+            action: action.to_string(),
+            resource: resource_arn.to_string(),
+        };
+
+        // A tenant-scoped service never authorizes anything outside its tenant
+        if !self.in_scope(resource_arn) {
+            return Ok(decision);
+        }
+
         // Root users bypass all authorization checks
         if context.is_root() {
-            return Ok(true);
+            decision.allowed = true;
+            return Ok(decision);
         }
 
-        // Extract user name from caller ARN
-        let user_name = self.extract_user_name_from_arn(context.caller_arn())?;
+        if !self.in_scope(context.caller_arn()) {
+            return Ok(decision);
+        }
+
+        // A federated user acts with the permissions of its federating user,
+        // bounded by the policy passed for the federation token
+        let federated_session = context
+            .session_info()
+            .filter(|_| context.caller_arn().resource.resource_type == "federated-user");
+        let user_name = match federated_session {
+            Some(session) => match session.principal_arn.as_deref() {
+                Some(principal_arn) => federating_user_name(principal_arn)?,
+                None => return Ok(decision),
+            },
+            None => self.extract_user_name_from_arn(context.caller_arn())?,
+        };
+
+        // Without a session policy, a federated user is granted nothing
+        let session_policies: Vec<PolicyDocument> = context
+            .session_info()
+            .and_then(|session| session.policy.as_deref())
+            .map(parse_policy_document)
+            .into_iter()
+            .collect();
+        if federated_session.is_some() && session_policies.is_empty() {
+            return Ok(decision);
+        }
 
         // Evaluate policies for this user
-        self.evaluate_user_policies(&user_name, action, resource_arn)
-            .await
+        let context_entries = context.condition_context();
+        self.evaluate_user_policies(
+            &user_name,
+            session_policies,
+            &mut decision,
+            resource_arn,
+            &context_entries,
+        )
+        .await?;
+        Ok(decision)
     }
 
     /// Check if access is denied (returns an error if not authorized)
     ///
     /// This is a convenience method that throws an `AccessDenied` error
-    /// if the authorization check fails.
+    /// if the authorization check fails. The error message carries an
+    /// encoded authorization failure message that can be decoded with
+    /// [`decode_authorization_message`](crate::wami::sts::decode_authorization_message).
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                operation = "Authorize",
+                tenant = %context.tenant_path(),
+                resource = %resource_arn,
+                action = %action,
+            )
+        )
+    )]
     pub async fn check_or_deny(
         &self,
         context: &WamiContext,
         action: &str,
         resource_arn: &WamiArn,
     ) -> Result<()> {
-        let allowed = self.authorize(context, action, resource_arn).await?;
+        let decision = self.explain(context, action, resource_arn).await?;
 
-        if !allowed {
+        if !decision.allowed {
             return Err(AmiError::AccessDenied {
                 message: format!(
-                    "User {} is not authorized to perform {} on {}",
+                    "User {} is not authorized to perform {} on {}. {}{}",
                     context.caller_arn(),
                     action,
-                    resource_arn
+                    resource_arn,
+                    ENCODED_MESSAGE_PREFIX,
+                    decision.encode()
                 ),
             });
         }
@@ -129,40 +336,158 @@ where
         Ok(())
     }
 
-    /// Evaluate all policies for a user
+    /// Decide a request from all of its policy sources
+    ///
+    /// Implements the full decision order described in the [module docs](self).
+    /// The returned message lists the deny statements that matched for an
+    /// explicit deny, or the identity and resource statements that granted an
+    /// allow; an implicit deny carries no statements.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                operation = "Authorize",
+                resource = %resource_arn,
+                action = %action,
+                decision = tracing::field::Empty,
+            )
+        )
+    )]
+    pub fn decide(
+        &self,
+        sources: &PolicySources,
+        action: &str,
+        resource_arn: &WamiArn,
+        context_entries: &[ContextEntry],
+    ) -> DecodedMessage {
+        let decision = self.resolve(sources, action, resource_arn, context_entries);
+        telemetry::record_decision(decision.allowed);
+        decision
+    }
+
+    /// Apply the decision order to a request's policy sources
+    fn resolve(
+        &self,
+        sources: &PolicySources,
+        action: &str,
+        resource_arn: &WamiArn,
+        context_entries: &[ContextEntry],
+    ) -> DecodedMessage {
+        let mut decision = DecodedMessage {
+            allowed: false,
+            explicit_deny: false,
+            matched_statements: vec![],
+            action: action.to_string(),
+            resource: resource_arn.to_string(),
+        };
+        let statements = |documents: &[PolicyDocument], effect: &str| -> Vec<PolicyStatement> {
+            documents
+                .iter()
+                .flat_map(|document| {
+                    self.matching_statements(
+                        document,
+                        effect,
+                        action,
+                        resource_arn,
+                        context_entries,
+                    )
+                })
+                .collect()
+        };
+
+        // 1. An explicit deny anywhere wins
+        let denies: Vec<PolicyStatement> = [
+            statements(&sources.identity, "deny"),
+            statements(&sources.resource, "deny"),
+            statements(sources.permissions_boundary.as_slice(), "deny"),
+            statements(&sources.session, "deny"),
+        ]
+        .concat();
+        if !denies.is_empty() {
+            decision.explicit_deny = true;
+            decision.matched_statements = denies;
+            return decision;
+        }
+
+        // 2. Identity and resource policies grant access
+        let identity_allows = statements(&sources.identity, "allow");
+        let resource_allows = statements(&sources.resource, "allow");
+        let granted = if sources.cross_account {
+            !identity_allows.is_empty() && !resource_allows.is_empty()
+        } else {
+            !identity_allows.is_empty() || !resource_allows.is_empty()
+        };
+        if !granted {
+            return decision;
+        }
+
+        // 3. A boundary and session policies can only narrow the grant
+        let permits = |document: &PolicyDocument| {
+            self.evaluate_policy_document(document, action, resource_arn, context_entries)
+                == PolicyEffect::Allow
+        };
+        if sources
+            .permissions_boundary
+            .as_ref()
+            .is_some_and(|boundary| !permits(boundary))
+        {
+            return decision;
+        }
+        if !sources.session.is_empty() && !sources.session.iter().any(permits) {
+            return decision;
+        }
+
+        decision.allowed = true;
+        decision.matched_statements = [identity_allows, resource_allows].concat();
+        decision
+    }
+
+    /// Evaluate all policies for a user, recording the outcome in `decision`
     ///
     /// This includes:
     /// - User's attached managed policies
     /// - User's inline policies
-    /// - Policies from user's groups
+    /// - Managed and inline policies of the user's groups
+    /// - Inline policies of permission sets assigned to the user or its groups
+    ///   through SSO, when the resource lies in the assigned account
+    /// - The user's permissions boundary
+    /// - The `session_policies` of the caller's temporary credentials
     /// - TODO: Assumed role policies
+    ///
+    /// Resource-based policies are not stored, so these requests are decided
+    /// as same-account requests from identity policies alone.
     async fn evaluate_user_policies(
         &self,
         user_name: &str,
-        action: &str,
+        session_policies: Vec<PolicyDocument>,
+        decision: &mut DecodedMessage,
         resource_arn: &WamiArn,
-    ) -> Result<bool> {
+        context_entries: &[ContextEntry],
+    ) -> Result<()> {
         let store = self.store.read().await;
 
+        // A same-named user from another tenant must not lend us its policies
+        if self.tenant_scope.is_some() {
+            match store.get_user(user_name).await? {
+                Some(user) if self.in_scope(&user.wami_arn) => {}
+                _ => return Ok(()),
+            }
+        }
+
+        let mut documents = Vec::new();
+
         // Get user's attached managed policies
         let attached_policies = store.list_attached_user_policies(user_name).await?;
 
-        // Check each attached policy
         for policy_arn in attached_policies {
             // Get the policy document
             if let Some(policy) = store.get_policy(&policy_arn).await? {
-                let policy_doc: PolicyDocument = serde_json::from_str(&policy.policy_document)
-                    .unwrap_or_else(|_| PolicyDocument {
-                        version: "2012-10-17".to_string(),
-                        statement: vec![],
-                    });
-
-                // Evaluate the policy
-                match self.evaluate_policy_document(&policy_doc, action, resource_arn) {
-                    PolicyEffect::Allow => return Ok(true),
-                    PolicyEffect::Deny => return Ok(false),
-                    PolicyEffect::NoMatch => continue,
+                // Skip policies owned by another tenant
+                if !self.in_scope(&policy.wami_arn) {
+                    continue;
                 }
+                documents.push(policy.policy_document);
             }
         }
 
@@ -171,25 +496,96 @@ where
 
         for policy_name in inline_policies {
             if let Some(policy_doc_str) = store.get_user_policy(user_name, &policy_name).await? {
-                let policy_doc: PolicyDocument = serde_json::from_str(&policy_doc_str)
-                    .unwrap_or_else(|_| PolicyDocument {
-                        version: "2012-10-17".to_string(),
-                        statement: vec![],
-                    });
-
-                match self.evaluate_policy_document(&policy_doc, action, resource_arn) {
-                    PolicyEffect::Allow => return Ok(true),
-                    PolicyEffect::Deny => return Ok(false),
-                    PolicyEffect::NoMatch => continue,
+                documents.push(policy_doc_str);
+            }
+        }
+
+        // Get managed and inline policies from the user's groups
+        let groups: Vec<_> = store
+            .list_groups_for_user(user_name)
+            .await?
+            .into_iter()
+            .filter(|group| self.in_scope(&group.wami_arn))
+            .collect();
+
+        for group in &groups {
+            for policy_arn in store
+                .list_attached_group_policies(&group.group_name)
+                .await?
+            {
+                if let Some(policy) = store.get_policy(&policy_arn).await? {
+                    if !self.in_scope(&policy.wami_arn) {
+                        continue;
+                    }
+                    documents.push(policy.policy_document);
+                }
+            }
+
+            for policy_name in store.list_group_policies(&group.group_name).await? {
+                if let Some(policy_doc_str) = store
+                    .get_group_policy(&group.group_name, &policy_name)
+                    .await?
+                {
+                    documents.push(policy_doc_str);
                 }
             }
         }
 
-        // TODO: Get policies from user's groups
+        // Permission sets assigned through SSO grant access in their target account,
+        // which may belong to another tenant
+        let user_id = store.get_user(user_name).await?.map(|user| user.user_id);
+        for assignment in store.list_all_account_assignments().await? {
+            if !resource_in_account(resource_arn, &assignment.account_id) {
+                continue;
+            }
+
+            let principal_id = assignment.principal_id.as_str();
+            let assigned = if assignment.principal_type.eq_ignore_ascii_case("USER") {
+                principal_id == user_name || user_id.as_deref() == Some(principal_id)
+            } else if assignment.principal_type.eq_ignore_ascii_case("GROUP") {
+                groups
+                    .iter()
+                    .any(|group| principal_id == group.group_name || principal_id == group.group_id)
+            } else {
+                false
+            };
+            if !assigned {
+                continue;
+            }
+
+            if let Some(permission_set) = store
+                .get_permission_set(&assignment.permission_set_arn)
+                .await?
+            {
+                documents.extend(permission_set.inline_policy);
+            }
+        }
+
         // TODO: Get policies from assumed roles
 
-        // Default deny - if no policy explicitly allows, deny
-        Ok(false)
+        // The user's permissions boundary caps whatever the policies above grant
+        let mut permissions_boundary = None;
+        if let Some(boundary_arn) = store
+            .get_user(user_name)
+            .await?
+            .and_then(|user| user.permissions_boundary)
+        {
+            if let Some(policy) = store.get_policy(&boundary_arn).await? {
+                permissions_boundary = Some(parse_policy_document(&policy.policy_document));
+            }
+        }
+
+        let sources = PolicySources {
+            identity: documents.iter().map(|d| parse_policy_document(d)).collect(),
+            permissions_boundary,
+            session: session_policies,
+            ..Default::default()
+        };
+        let outcome = self.decide(&sources, &decision.action, resource_arn, context_entries);
+        decision.allowed = outcome.allowed;
+        decision.explicit_deny = outcome.explicit_deny;
+        decision.matched_statements = outcome.matched_statements;
+        Ok(())
     }
 
     /// Evaluate a single policy document
@@ -203,32 +599,53 @@ where
         policy: &PolicyDocument,
         action: &str,
         resource_arn: &WamiArn,
+        context_entries: &[ContextEntry],
     ) -> PolicyEffect {
-        let resource_str = resource_arn.to_string();
-
         // First check for explicit denies (deny overrides allow)
-        for statement in &policy.statement {
-            if statement.effect.to_lowercase() == "deny"
-                && self.matches_action(&statement.action, action)
-                && self.matches_resource(&statement.resource, &resource_str)
-            {
-                return PolicyEffect::Deny;
-            }
+        if !self
+            .matching_statements(policy, "deny", action, resource_arn, context_entries)
+            .is_empty()
+        {
+            return PolicyEffect::Deny;
         }
 
         // Then check for allows
-        for statement in &policy.statement {
-            if statement.effect.to_lowercase() == "allow"
-                && self.matches_action(&statement.action, action)
-                && self.matches_resource(&statement.resource, &resource_str)
-            {
-                return PolicyEffect::Allow;
-            }
+        if !self
+            .matching_statements(policy, "allow", action, resource_arn, context_entries)
+            .is_empty()
+        {
+            return PolicyEffect::Allow;
         }
 
         PolicyEffect::NoMatch
     }
 
+    /// Collect the statements with the given effect that apply to the action and resource
+    ///
+    /// A statement only applies when its conditions hold for the request context.
+    fn matching_statements(
+        &self,
+        policy: &PolicyDocument,
+        effect: &str,
+        action: &str,
+        resource_arn: &WamiArn,
+        context_entries: &[ContextEntry],
+    ) -> Vec<PolicyStatement> {
+        let resource_str = resource_arn.to_string();
+
+        policy
+            .statement
+            .iter()
+            .filter(|statement| {
+                statement.effect.eq_ignore_ascii_case(effect)
+                    && self.matches_action(&statement.action, action)
+                    && self.matches_resource(&statement.resource, &resource_str)
+                    && evaluate_conditions(statement.condition.as_ref(), context_entries)
+            })
+            .cloned()
+            .collect()
+    }
+
     /// Check if an action matches the policy statement
     ///
     /// Supports wildcards: `iam:*`, `*`
@@ -328,6 +745,44 @@ where
     }
 }
 
+/// The name of the user a federation token was requested by
+///
+/// Accepts either the user's WAMI ARN, whose resource ID names the user, or
+/// an AWS ARN such as `arn:aws:iam::123456789012:user/division/alice`.
+#[allow(clippy::result_large_err)]
+fn federating_user_name(principal_arn: &str) -> Result<String> {
+    if let Ok(arn) = principal_arn.parse::<WamiArn>() {
+        if arn.resource.resource_type == "user" {
+            return Ok(arn.resource.resource_id);
+        }
+    }
+    principal_arn
+        .split_once(":user/")
+        .and_then(|(_, name)| name.rsplit('/').next())
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .ok_or_else(|| AmiError::InvalidParameter {
+            message: format!("Federating principal is not a user ARN: {}", principal_arn),
+        })
+}
+
+/// Parse a stored policy document, treating an unparsable one as granting nothing
+fn parse_policy_document(document: &str) -> PolicyDocument {
+    serde_json::from_str(document).unwrap_or_else(|_| PolicyDocument {
+        version: "2012-10-17".to_string(),
+        statement: vec![],
+    })
+}
+
+/// Whether a resource lives in an account, by WAMI instance or synced provider account
+fn resource_in_account(resource_arn: &WamiArn, account_id: &str) -> bool {
+    resource_arn.wami_instance_id == account_id
+        || resource_arn
+            .cloud_mapping
+            .as_ref()
+            .is_some_and(|mapping| mapping.account_id == account_id)
+}
+
 /// Policy evaluation result
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum PolicyEffect {
@@ -343,14 +798,12 @@ enum PolicyEffect {
 mod tests {
     use super::*;
     use crate::store::memory::InMemoryWamiStore;
-    use crate::types::{PolicyDocument, PolicyStatement};
+    use crate::store::traits::{PolicyStore, UserStore};
 
     #[test]
     fn test_matches_action() {
         let store = InMemoryWamiStore::new();
-        let service = AuthorizationService {
-            store: Arc::new(RwLock::new(store)),
-        };
+        let service = AuthorizationService::new(Arc::new(RwLock::new(store)));
 
         // Exact match
         assert!(service.matches_action(&["iam:GetUser".to_string()], "iam:GetUser"));
@@ -369,9 +822,7 @@ mod tests {
     #[test]
     fn test_matches_resource() {
         let store = InMemoryWamiStore::new();
-        let service = AuthorizationService {
-            store: Arc::new(RwLock::new(store)),
-        };
+        let service = AuthorizationService::new(Arc::new(RwLock::new(store)));
 
         // Exact match
         assert!(service.matches_resource(
@@ -401,9 +852,7 @@ mod tests {
     #[test]
     fn test_wildcard_match() {
         let store = InMemoryWamiStore::new();
-        let service = AuthorizationService {
-            store: Arc::new(RwLock::new(store)),
-        };
+        let service = AuthorizationService::new(Arc::new(RwLock::new(store)));
 
         assert!(service.wildcard_match("arn:*:user/*", "arn:wami:iam:12345678:wami:999:user/alice"));
         assert!(service.wildcard_match("*.example.com", "api.example.com"));
@@ -417,9 +866,7 @@ mod tests {
     #[test]
     fn test_matches_action_edge_cases() {
         let store = InMemoryWamiStore::new();
-        let service = AuthorizationService {
-            store: Arc::new(RwLock::new(store)),
-        };
+        let service = AuthorizationService::new(Arc::new(RwLock::new(store)));
 
         // Empty actions
         assert!(!service.matches_action(&[], "iam:GetUser"));
@@ -443,9 +890,7 @@ mod tests {
     #[test]
     fn test_matches_resource_edge_cases() {
         let store = InMemoryWamiStore::new();
-        let service = AuthorizationService {
-            store: Arc::new(RwLock::new(store)),
-        };
+        let service = AuthorizationService::new(Arc::new(RwLock::new(store)));
 
         // Empty resources
         assert!(!service.matches_resource(&[], "arn:wami:iam:12345678:wami:999:user/alice"));
@@ -469,9 +914,7 @@ mod tests {
     #[test]
     fn test_evaluate_policy_deny_overrides_allow() {
         let store = InMemoryWamiStore::new();
-        let service = AuthorizationService {
-            store: Arc::new(RwLock::new(store)),
-        };
+        let service = AuthorizationService::new(Arc::new(RwLock::new(store)));
 
         let policy = PolicyDocument {
             version: "2012-10-17".to_string(),
@@ -492,7 +935,7 @@ mod tests {
         };
 
         let resource: WamiArn = "arn:wami:iam:12345678:wami:999:user/alice".parse().unwrap();
-        let effect = service.evaluate_policy_document(&policy, "iam:DeleteUser", &resource);
+        let effect = service.evaluate_policy_document(&policy, "iam:DeleteUser", &resource, &[]);
 
         // Deny should override Allow
         assert_eq!(effect, PolicyEffect::Deny);
@@ -501,9 +944,7 @@ mod tests {
     #[test]
     fn test_evaluate_policy_no_match() {
         let store = InMemoryWamiStore::new();
-        let service = AuthorizationService {
-            store: Arc::new(RwLock::new(store)),
-        };
+        let service = AuthorizationService::new(Arc::new(RwLock::new(store)));
 
         let policy = PolicyDocument {
             version: "2012-10-17".to_string(),
@@ -516,7 +957,7 @@ mod tests {
         };
 
         let resource: WamiArn = "arn:wami:iam:12345678:wami:999:user/alice".parse().unwrap();
-        let effect = service.evaluate_policy_document(&policy, "iam:GetUser", &resource);
+        let effect = service.evaluate_policy_document(&policy, "iam:GetUser", &resource, &[]);
 
         assert_eq!(effect, PolicyEffect::NoMatch);
     }
@@ -524,9 +965,7 @@ mod tests {
     #[test]
     fn test_evaluate_policy_case_insensitive_effect() {
         let store = InMemoryWamiStore::new();
-        let service = AuthorizationService {
-            store: Arc::new(RwLock::new(store)),
-        };
+        let service = AuthorizationService::new(Arc::new(RwLock::new(store)));
 
         let policy = PolicyDocument {
             version: "2012-10-17".to_string(),
@@ -539,8 +978,909 @@ mod tests {
         };
 
         let resource: WamiArn = "arn:wami:iam:12345678:wami:999:user/alice".parse().unwrap();
-        let effect = service.evaluate_policy_document(&policy, "iam:GetUser", &resource);
+        let effect = service.evaluate_policy_document(&policy, "iam:GetUser", &resource, &[]);
 
         assert_eq!(effect, PolicyEffect::Deny);
     }
+
+    fn tenant_context(tenant_id: u64) -> WamiContext {
+        tenant_context_with_root(tenant_id, false)
+    }
+
+    fn tenant_context_with_root(tenant_id: u64, is_root: bool) -> WamiContext {
+        let arn: WamiArn = format!("arn:wami:iam:{}:wami:123456789012:user/alice", tenant_id)
+            .parse()
+            .unwrap();
+        WamiContext::builder()
+            .instance_id("123456789012")
+            .tenant_path(TenantPath::single(tenant_id))
+            .caller_arn(arn)
+            .is_root(is_root)
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_decisions_are_audited() {
+        use crate::service::audit::VecAuditSink;
+
+        let sink = Arc::new(VecAuditSink::new());
+        let service = AuthorizationService::new(Arc::new(RwLock::new(InMemoryWamiStore::new())))
+            .with_audit_sink(sink.clone());
+        let resource: WamiArn = "arn:wami:iam:11111111:wami:123456789012:user/bob"
+            .parse()
+            .unwrap();
+
+        let root = tenant_context_with_root(11111111, true);
+        assert!(service
+            .authorize(&root, "iam:GetUser", &resource)
+            .await
+            .unwrap());
+        // Alice has no policies
+        let alice = tenant_context(11111111);
+        assert!(service
+            .check_or_deny(&alice, "iam:DeleteUser", &resource)
+            .await
+            .is_err());
+
+        let events = sink.events();
+        let summary: Vec<_> = events
+            .iter()
+            .map(|e| (e.operation.as_str(), e.outcome.clone()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("iam:GetUser", AuditOutcome::Allowed),
+                ("iam:DeleteUser", AuditOutcome::Denied)
+            ]
+        );
+        assert_eq!(events[1].actor_arn, Some(alice.caller_arn().to_string()));
+        assert_eq!(events[1].target_arn, resource.to_string());
+    }
+
+    #[tokio::test]
+    async fn test_for_tenant_ignores_policies_from_other_tenants() {
+        use crate::wami::identity::user::builder::build_user;
+        use crate::wami::policies::policy::builder::build_policy;
+
+        let store = Arc::new(RwLock::new(InMemoryWamiStore::new()));
+        let tenant_a = tenant_context(11111111);
+        let tenant_b = tenant_context(22222222);
+        let document = r#"{"Version":"2012-10-17","Statement":[{"Effect":"Allow","Action":["iam:GetUser"],"Resource":["*"]}]}"#;
+
+        // Tenant B owns a policy whose AWS ARN is identical to what tenant A would get
+        let foreign_policy = build_policy(
+            "ReadUsers".to_string(),
+            document.to_string(),
+            None,
+            None,
+            None,
+            &tenant_b,
+        )
+        .unwrap();
+        let policy_arn = foreign_policy.arn.clone();
+        {
+            let mut guard = store.write().await;
+            guard
+                .create_user(build_user("alice".to_string(), None, &tenant_a).unwrap())
+                .await
+                .unwrap();
+            guard.create_policy(foreign_policy).await.unwrap();
+            guard
+                .attach_user_policy("alice", &policy_arn)
+                .await
+                .unwrap();
+        }
+
+        let resource: WamiArn = "arn:wami:iam:11111111:wami:123456789012:user/bob"
+            .parse()
+            .unwrap();
+
+        // An unscoped authorizer leaks tenant B's policy into tenant A
+        let unscoped = AuthorizationService::new(store.clone());
+        assert!(unscoped
+            .authorize(&tenant_a, "iam:GetUser", &resource)
+            .await
+            .unwrap());
+
+        let tenant_id = TenantId::from_string("11111111").unwrap();
+        let scoped = AuthorizationService::for_tenant(store.clone(), &tenant_id);
+        assert_eq!(scoped.tenant_scope(), Some(&TenantPath::single(11111111)));
+        assert!(!scoped
+            .authorize(&tenant_a, "iam:GetUser", &resource)
+            .await
+            .unwrap());
+
+        // Resources outside the tenant are never authorized, even for root
+        let foreign_resource: WamiArn = "arn:wami:iam:22222222:wami:123456789012:user/bob"
+            .parse()
+            .unwrap();
+        let root = tenant_context_with_root(11111111, true);
+        assert!(!scoped
+            .authorize(&root, "iam:GetUser", &foreign_resource)
+            .await
+            .unwrap());
+
+        // Replacing the attachment with tenant A's own policy grants access
+        let own_policy = build_policy(
+            "ReadUsers".to_string(),
+            document.to_string(),
+            None,
+            None,
+            None,
+            &tenant_a,
+        )
+        .unwrap();
+        {
+            let mut guard = store.write().await;
+            guard.delete_policy(&policy_arn).await.unwrap();
+            guard.create_policy(own_policy).await.unwrap();
+        }
+        assert!(scoped
+            .authorize(&tenant_a, "iam:GetUser", &resource)
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_deny_message_round_trips_matched_statements() {
+        use crate::wami::identity::user::builder::build_user;
+        use crate::wami::policies::policy::builder::build_policy;
+        use crate::wami::sts::decode_authorization_message;
+
+        let store = Arc::new(RwLock::new(InMemoryWamiStore::new()));
+        let context = tenant_context(11111111);
+        let document = r#"{"Version":"2012-10-17","Statement":[
+            {"Effect":"Allow","Action":["iam:*"],"Resource":["*"]},
+            {"Effect":"Deny","Action":["iam:DeleteUser"],"Resource":["arn:wami:iam:*:user/*"]}
+        ]}"#;
+        let policy = build_policy(
+            "NoDeletes".to_string(),
+            document.to_string(),
+            None,
+            None,
+            None,
+            &context,
+        )
+        .unwrap();
+        let policy_arn = policy.arn.clone();
+        {
+            let mut guard = store.write().await;
+            guard
+                .create_user(build_user("alice".to_string(), None, &context).unwrap())
+                .await
+                .unwrap();
+            guard.create_policy(policy).await.unwrap();
+            guard
+                .attach_user_policy("alice", &policy_arn)
+                .await
+                .unwrap();
+        }
+
+        let service = AuthorizationService::new(store);
+        let resource: WamiArn = "arn:wami:iam:11111111:wami:123456789012:user/bob"
+            .parse()
+            .unwrap();
+
+        let err = service
+            .check_or_deny(&context, "iam:DeleteUser", &resource)
+            .await
+            .unwrap_err();
+        let AmiError::AccessDenied { message } = err else {
+            panic!("expected AccessDenied");
+        };
+        let encoded = message
+            .split(ENCODED_MESSAGE_PREFIX)
+            .nth(1)
+            .expect("deny carries an encoded message");
+
+        let decoded = decode_authorization_message(encoded).unwrap();
+        assert!(!decoded.allowed);
+        assert!(decoded.explicit_deny);
+        assert_eq!(decoded.action, "iam:DeleteUser");
+        assert_eq!(decoded.resource, resource.to_string());
+        assert_eq!(decoded.matched_statements.len(), 1);
+        assert_eq!(decoded.matched_statements[0].effect, "Deny");
+        assert_eq!(decoded.matched_statements[0].action, vec!["iam:DeleteUser"]);
+
+        // An implicit deny carries no matched statements
+        let decision = service
+            .explain(&context, "s3:GetObject", &resource)
+            .await
+            .unwrap();
+        assert!(!decision.allowed);
+        assert!(!decision.explicit_deny);
+        assert!(decision.matched_statements.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_source_ip_condition_restricts_access_to_cidr() {
+        use crate::wami::identity::user::builder::build_user;
+
+        let store = Arc::new(RwLock::new(InMemoryWamiStore::new()));
+        let context = tenant_context(11111111);
+        {
+            let mut guard = store.write().await;
+            guard
+                .create_user(build_user("alice".to_string(), None, &context).unwrap())
+                .await
+                .unwrap();
+            guard
+                .put_user_policy(
+                    "alice",
+                    "OfficeOnly",
+                    r#"{"Version":"2012-10-17","Statement":[{"Effect":"Allow","Action":["s3:GetObject"],"Resource":["*"],"Condition":{"IpAddress":{"aws:SourceIp":"203.0.113.0/24"}}}]}"#
+                        .to_string(),
+                )
+                .await
+                .unwrap();
+        }
+
+        let service = AuthorizationService::new(store);
+        let bucket: WamiArn = "arn:wami:s3:11111111:wami:123456789012:bucket/reports"
+            .parse()
+            .unwrap();
+        let from_ip = |ip: &str| {
+            WamiContext::builder()
+                .instance_id("123456789012")
+                .tenant_path(TenantPath::single(11111111))
+                .caller_arn(context.caller_arn().clone())
+                .source_ip(ip.parse().unwrap())
+                .build()
+                .unwrap()
+        };
+
+        assert!(service
+            .authorize(&from_ip("203.0.113.42"), "s3:GetObject", &bucket)
+            .await
+            .unwrap());
+        assert!(!service
+            .authorize(&from_ip("198.51.100.7"), "s3:GetObject", &bucket)
+            .await
+            .unwrap());
+
+        // Without a known source IP the condition cannot match
+        assert!(!service
+            .authorize(&context, "s3:GetObject", &bucket)
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_session_tags_are_principal_tags() {
+        use crate::context::SessionInfo;
+        use crate::types::Tag;
+        use crate::wami::identity::user::builder::build_user;
+
+        let store = Arc::new(RwLock::new(InMemoryWamiStore::new()));
+        let context = tenant_context(11111111);
+        {
+            let mut guard = store.write().await;
+            guard
+                .create_user(build_user("alice".to_string(), None, &context).unwrap())
+                .await
+                .unwrap();
+            guard
+                .put_user_policy(
+                    "alice",
+                    "TeamOnly",
+                    r#"{"Version":"2012-10-17","Statement":[{"Effect":"Allow","Action":["s3:GetObject"],"Resource":["*"],"Condition":{"StringEquals":{"aws:PrincipalTag/team":"payments"}}}]}"#
+                        .to_string(),
+                )
+                .await
+                .unwrap();
+        }
+
+        let service = AuthorizationService::new(store);
+        let bucket: WamiArn = "arn:wami:s3:11111111:wami:123456789012:bucket/reports"
+            .parse()
+            .unwrap();
+        let with_team = |team: &str| {
+            WamiContext::builder()
+                .instance_id("123456789012")
+                .tenant_path(TenantPath::single(11111111))
+                .caller_arn(context.caller_arn().clone())
+                .session_info(SessionInfo {
+                    session_token: "token".to_string(),
+                    expiration: chrono::Utc::now().timestamp() + 3600,
+                    assumed_role_arn: None,
+                    policy: None,
+                    tags: vec![Tag {
+                        key: "team".to_string(),
+                        value: team.to_string(),
+                    }],
+                    principal_arn: None,
+                })
+                .build()
+                .unwrap()
+        };
+
+        assert!(service
+            .authorize(&with_team("payments"), "s3:GetObject", &bucket)
+            .await
+            .unwrap());
+        assert!(!service
+            .authorize(&with_team("fraud"), "s3:GetObject", &bucket)
+            .await
+            .unwrap());
+        assert!(!service
+            .authorize(&context, "s3:GetObject", &bucket)
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_federation_policy_bounds_federated_user() {
+        use crate::service::auth::AuthenticationService;
+        use crate::service::sts::FederationService;
+        use crate::wami::identity::user::builder::build_user;
+        use crate::wami::sts::federation::GetFederationTokenRequest;
+
+        let context = tenant_context(11111111);
+        let mut store = InMemoryWamiStore::new();
+        store
+            .create_user(build_user("alice".to_string(), None, &context).unwrap())
+            .await
+            .unwrap();
+        store
+            .put_user_policy(
+                "alice",
+                "S3ReadWrite",
+                r#"{"Version":"2012-10-17","Statement":[{"Effect":"Allow","Action":["s3:GetObject","s3:PutObject"],"Resource":["*"]}]}"#
+                    .to_string(),
+            )
+            .await
+            .unwrap();
+
+        let sts_store = Arc::new(std::sync::RwLock::new(store));
+        let federation = FederationService::new(sts_store.clone());
+        let federate = |name: &str, policy: Option<&str>| {
+            let request = GetFederationTokenRequest {
+                name: name.to_string(),
+                duration_seconds: Some(3600),
+                policy: policy.map(str::to_string),
+            };
+            federation.get_federation_token(
+                &context,
+                request,
+                "arn:aws:iam::123456789012:user/alice",
+            )
+        };
+        let scoped = federate(
+            "alice-reader",
+            Some(r#"{"Version":"2012-10-17","Statement":[{"Effect":"Allow","Action":["s3:GetObject"],"Resource":["*"]}]}"#),
+        )
+        .await
+        .unwrap()
+        .credentials;
+        let unscoped = federate("alice-bare", None).await.unwrap().credentials;
+
+        let store = Arc::new(RwLock::new(sts_store.read().unwrap().clone()));
+        let authentication = AuthenticationService::new(store.clone());
+        let mut contexts = Vec::new();
+        for credentials in [&scoped, &unscoped] {
+            let session_context = authentication
+                .authenticate_session(
+                    &credentials.access_key_id,
+                    &credentials.secret_access_key,
+                    &credentials.session_token,
+                )
+                .await
+                .unwrap();
+            contexts.push(session_context);
+        }
+        let (scoped_context, unscoped_context) = (&contexts[0], &contexts[1]);
+
+        let service = AuthorizationService::new(store);
+        let bucket: WamiArn = "arn:wami:s3:11111111:wami:123456789012:bucket/reports"
+            .parse()
+            .unwrap();
+
+        // The user itself can read and write
+        assert!(service
+            .authorize(&context, "s3:PutObject", &bucket)
+            .await
+            .unwrap());
+
+        // The federated user only gets what both the user and the federation policy allow
+        assert!(service
+            .authorize(scoped_context, "s3:GetObject", &bucket)
+            .await
+            .unwrap());
+        assert!(!service
+            .authorize(scoped_context, "s3:PutObject", &bucket)
+            .await
+            .unwrap());
+
+        // A federation token without a policy grants nothing
+        assert!(!service
+            .authorize(unscoped_context, "s3:GetObject", &bucket)
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_sso_assignment_grants_permission_set_in_assigned_account() {
+        use crate::wami::identity::user::builder::build_user;
+        use crate::wami::sso_admin::{AccountAssignment, AssignmentStatus, PermissionSet};
+
+        let store = Arc::new(RwLock::new(InMemoryWamiStore::new()));
+        let context = tenant_context(11111111);
+        let permission_set_arn = "arn:aws:sso:::permissionSet/ssoins-1/ps-readonly";
+        let permission_set = PermissionSet {
+            permission_set_arn: permission_set_arn.to_string(),
+            name: "ReadOnly".to_string(),
+            description: None,
+            session_duration: None,
+            relay_state: None,
+            inline_policy: Some(
+                r#"{"Version":"2012-10-17","Statement":[{"Effect":"Allow","Action":["s3:Get*","s3:List*"],"Resource":["*"]}]}"#
+                    .to_string(),
+            ),
+            instance_arn: "arn:aws:sso:::instance/ssoins-1".to_string(),
+            created_date: chrono::Utc::now(),
+            wami_arn: "arn:wami:sso-admin:11111111:wami:123456789012:permission-set/ps-readonly"
+                .parse()
+                .unwrap(),
+            providers: vec![],
+        };
+        let assignment = AccountAssignment {
+            assignment_id: AccountAssignment::assignment_key(
+                "999888777666",
+                permission_set_arn,
+                "alice",
+            ),
+            instance_arn: "arn:aws:sso:::instance/ssoins-1".to_string(),
+            account_id: "999888777666".to_string(),
+            permission_set_arn: permission_set_arn.to_string(),
+            principal_type: "USER".to_string(),
+            principal_id: "alice".to_string(),
+            target_id: "999888777666".to_string(),
+            target_type: "AWS_ACCOUNT".to_string(),
+            created_date: chrono::Utc::now(),
+            wami_arn: "arn:wami:sso-admin:11111111:wami:123456789012:assignment/1"
+                .parse()
+                .unwrap(),
+            providers: vec![],
+            status: AssignmentStatus::Succeeded,
+            request_id: String::new(),
+            failure_reason: None,
+        };
+        {
+            let mut guard = store.write().await;
+            guard
+                .create_user(build_user("alice".to_string(), None, &context).unwrap())
+                .await
+                .unwrap();
+            guard.create_permission_set(permission_set).await.unwrap();
+            guard.create_account_assignment(assignment).await.unwrap();
+        }
+
+        let service = AuthorizationService::new(store);
+        let bucket: WamiArn = "arn:wami:s3:87654321:wami:999888777666:bucket/reports"
+            .parse()
+            .unwrap();
+
+        // Read actions in the assigned account are granted by the permission set
+        assert!(service
+            .authorize(&context, "s3:GetObject", &bucket)
+            .await
+            .unwrap());
+        assert!(service
+            .authorize(&context, "s3:ListBucket", &bucket)
+            .await
+            .unwrap());
+
+        // Writes are outside the read-only permission set
+        assert!(!service
+            .authorize(&context, "s3:PutObject", &bucket)
+            .await
+            .unwrap());
+
+        // The permission set does not apply in other accounts
+        let other_bucket: WamiArn = "arn:wami:s3:87654321:wami:111122223333:bucket/reports"
+            .parse()
+            .unwrap();
+        assert!(!service
+            .authorize(&context, "s3:GetObject", &other_bucket)
+            .await
+            .unwrap());
+    }
+
+    /// A policy with a single statement on `s3:GetObject` for every resource
+    fn s3_get(effect: &str) -> PolicyDocument {
+        PolicyDocument {
+            version: "2012-10-17".to_string(),
+            statement: vec![PolicyStatement {
+                effect: effect.to_string(),
+                action: vec!["s3:GetObject".to_string()],
+                resource: vec!["*".to_string()],
+                condition: None,
+            }],
+        }
+    }
+
+    /// A policy allowing only `s3:ListBucket`
+    fn s3_list_only() -> PolicyDocument {
+        PolicyDocument {
+            version: "2012-10-17".to_string(),
+            statement: vec![PolicyStatement {
+                effect: "Allow".to_string(),
+                action: vec!["s3:ListBucket".to_string()],
+                resource: vec!["*".to_string()],
+                condition: None,
+            }],
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    #[tracing_test::traced_test]
+    fn test_decide_records_decision_on_span() {
+        let service = AuthorizationService::new(Arc::new(RwLock::new(InMemoryWamiStore::new())));
+        let resource: WamiArn = "arn:wami:s3:11111111:wami:123456789012:bucket/reports"
+            .parse()
+            .unwrap();
+
+        let sources = PolicySources {
+            identity: vec![s3_get("Allow")],
+            ..Default::default()
+        };
+        assert!(
+            service
+                .decide(&sources, "s3:GetObject", &resource, &[])
+                .allowed
+        );
+        assert!(logs_contain(r#"decide{operation="Authorize""#));
+        assert!(logs_contain(r#"action=s3:GetObject decision="allow""#));
+
+        assert!(
+            !service
+                .decide(&PolicySources::default(), "s3:GetObject", &resource, &[])
+                .allowed
+        );
+        assert!(logs_contain(r#"decision="deny""#));
+    }
+
+    #[test]
+    fn test_decide_matrix() {
+        let service = AuthorizationService::new(Arc::new(RwLock::new(InMemoryWamiStore::new())));
+        let resource: WamiArn = "arn:wami:s3:11111111:wami:123456789012:bucket/reports"
+            .parse()
+            .unwrap();
+
+        #[derive(Debug, PartialEq)]
+        enum Outcome {
+            Allow,
+            ImplicitDeny,
+            ExplicitDeny,
+        }
+        use Outcome::*;
+
+        let allow = || s3_get("Allow");
+        let deny = || s3_get("Deny");
+        let cases: Vec<(&str, PolicySources, Outcome)> = vec![
+            ("no policies", PolicySources::default(), ImplicitDeny),
+            (
+                "same account, identity allow",
+                PolicySources {
+                    identity: vec![allow()],
+                    ..Default::default()
+                },
+                Allow,
+            ),
+            (
+                "same account, resource allow",
+                PolicySources {
+                    resource: vec![allow()],
+                    ..Default::default()
+                },
+                Allow,
+            ),
+            (
+                "same account, unrelated identity policy",
+                PolicySources {
+                    identity: vec![s3_list_only()],
+                    ..Default::default()
+                },
+                ImplicitDeny,
+            ),
+            (
+                "identity deny beats identity allow",
+                PolicySources {
+                    identity: vec![allow(), deny()],
+                    ..Default::default()
+                },
+                ExplicitDeny,
+            ),
+            (
+                "resource deny beats identity allow",
+                PolicySources {
+                    identity: vec![allow()],
+                    resource: vec![deny()],
+                    ..Default::default()
+                },
+                ExplicitDeny,
+            ),
+            (
+                "cross account, identity allow only",
+                PolicySources {
+                    identity: vec![allow()],
+                    cross_account: true,
+                    ..Default::default()
+                },
+                ImplicitDeny,
+            ),
+            (
+                "cross account, resource allow only",
+                PolicySources {
+                    resource: vec![allow()],
+                    cross_account: true,
+                    ..Default::default()
+                },
+                ImplicitDeny,
+            ),
+            (
+                "cross account, identity and resource allow",
+                PolicySources {
+                    identity: vec![allow()],
+                    resource: vec![allow()],
+                    cross_account: true,
+                    ..Default::default()
+                },
+                Allow,
+            ),
+            (
+                "cross account, resource deny",
+                PolicySources {
+                    identity: vec![allow()],
+                    resource: vec![allow(), deny()],
+                    cross_account: true,
+                    ..Default::default()
+                },
+                ExplicitDeny,
+            ),
+            (
+                "boundary allows the granted action",
+                PolicySources {
+                    identity: vec![allow()],
+                    permissions_boundary: Some(allow()),
+                    ..Default::default()
+                },
+                Allow,
+            ),
+            (
+                "boundary does not cover the action",
+                PolicySources {
+                    identity: vec![allow()],
+                    permissions_boundary: Some(s3_list_only()),
+                    ..Default::default()
+                },
+                ImplicitDeny,
+            ),
+            (
+                "boundary alone grants nothing",
+                PolicySources {
+                    permissions_boundary: Some(allow()),
+                    ..Default::default()
+                },
+                ImplicitDeny,
+            ),
+            (
+                "boundary deny",
+                PolicySources {
+                    identity: vec![allow()],
+                    permissions_boundary: Some(deny()),
+                    ..Default::default()
+                },
+                ExplicitDeny,
+            ),
+            (
+                "boundary restricts a cross-account grant",
+                PolicySources {
+                    identity: vec![allow()],
+                    resource: vec![allow()],
+                    permissions_boundary: Some(s3_list_only()),
+                    cross_account: true,
+                    ..Default::default()
+                },
+                ImplicitDeny,
+            ),
+            (
+                "session policy allows the granted action",
+                PolicySources {
+                    identity: vec![allow()],
+                    session: vec![s3_list_only(), allow()],
+                    ..Default::default()
+                },
+                Allow,
+            ),
+            (
+                "session policy does not cover the action",
+                PolicySources {
+                    identity: vec![allow()],
+                    session: vec![s3_list_only()],
+                    ..Default::default()
+                },
+                ImplicitDeny,
+            ),
+            (
+                "session policy alone grants nothing",
+                PolicySources {
+                    session: vec![allow()],
+                    ..Default::default()
+                },
+                ImplicitDeny,
+            ),
+            (
+                "session deny",
+                PolicySources {
+                    identity: vec![allow()],
+                    session: vec![deny()],
+                    ..Default::default()
+                },
+                ExplicitDeny,
+            ),
+            (
+                "boundary and session both allow",
+                PolicySources {
+                    identity: vec![allow()],
+                    permissions_boundary: Some(allow()),
+                    session: vec![allow()],
+                    ..Default::default()
+                },
+                Allow,
+            ),
+        ];
+
+        for (name, sources, expected) in cases {
+            let decision = service.decide(&sources, "s3:GetObject", &resource, &[]);
+            let outcome = match (decision.allowed, decision.explicit_deny) {
+                (true, _) => Allow,
+                (false, true) => ExplicitDeny,
+                (false, false) => ImplicitDeny,
+            };
+            assert_eq!(outcome, expected, "case: {}", name);
+            if outcome == ImplicitDeny {
+                assert!(decision.matched_statements.is_empty(), "case: {}", name);
+            } else {
+                assert!(!decision.matched_statements.is_empty(), "case: {}", name);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_user_permissions_boundary_limits_grants() {
+        use crate::wami::identity::user::builder::build_user;
+        use crate::wami::policies::policy::builder::build_policy;
+
+        let store = Arc::new(RwLock::new(InMemoryWamiStore::new()));
+        let context = tenant_context(11111111);
+        let boundary = build_policy(
+            "S3ReadBoundary".to_string(),
+            r#"{"Version":"2012-10-17","Statement":[{"Effect":"Allow","Action":["s3:Get*"],"Resource":["*"]}]}"#
+                .to_string(),
+            None,
+            None,
+            None,
+            &context,
+        )
+        .unwrap();
+        {
+            let mut guard = store.write().await;
+            let mut user = build_user("alice".to_string(), None, &context).unwrap();
+            user.permissions_boundary = Some(boundary.arn.clone());
+            guard.create_user(user).await.unwrap();
+            guard.create_policy(boundary).await.unwrap();
+            guard
+                .put_user_policy(
+                    "alice",
+                    "S3Full",
+                    r#"{"Version":"2012-10-17","Statement":[{"Effect":"Allow","Action":["s3:*"],"Resource":["*"]}]}"#
+                        .to_string(),
+                )
+                .await
+                .unwrap();
+        }
+
+        let service = AuthorizationService::new(store);
+        let bucket: WamiArn = "arn:wami:s3:11111111:wami:123456789012:bucket/reports"
+            .parse()
+            .unwrap();
+
+        assert!(service
+            .authorize(&context, "s3:GetObject", &bucket)
+            .await
+            .unwrap());
+        // Granted by the inline policy, but outside the boundary
+        assert!(!service
+            .authorize(&context, "s3:PutObject", &bucket)
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_group_policies_grant_and_deny_for_members() {
+        use crate::wami::identity::group::builder::build_group;
+        use crate::wami::identity::user::builder::build_user;
+        use crate::wami::policies::policy::builder::build_policy;
+
+        let store = Arc::new(RwLock::new(InMemoryWamiStore::new()));
+        let context = tenant_context(11111111);
+        let read_only = build_policy(
+            "S3Read".to_string(),
+            r#"{"Version":"2012-10-17","Statement":[{"Effect":"Allow","Action":["s3:GetObject"],"Resource":["*"]}]}"#
+                .to_string(),
+            None,
+            None,
+            None,
+            &context,
+        )
+        .unwrap();
+        let policy_arn = read_only.arn.clone();
+        {
+            let mut guard = store.write().await;
+            guard
+                .create_user(build_user("alice".to_string(), None, &context).unwrap())
+                .await
+                .unwrap();
+            guard
+                .create_group(build_group("readers".to_string(), None, &context).unwrap())
+                .await
+                .unwrap();
+            guard.create_policy(read_only).await.unwrap();
+            guard
+                .attach_group_policy("readers", &policy_arn)
+                .await
+                .unwrap();
+        }
+
+        let service = AuthorizationService::new(store.clone());
+        let bucket: WamiArn = "arn:wami:s3:11111111:wami:123456789012:bucket/reports"
+            .parse()
+            .unwrap();
+
+        // The user has no policies of its own until it joins the group
+        assert!(!service
+            .authorize(&context, "s3:GetObject", &bucket)
+            .await
+            .unwrap());
+        store
+            .write()
+            .await
+            .add_user_to_group("readers", "alice")
+            .await
+            .unwrap();
+        assert!(service
+            .authorize(&context, "s3:GetObject", &bucket)
+            .await
+            .unwrap());
+
+        // A group-level deny overrides the managed policy's allow
+        store
+            .write()
+            .await
+            .put_group_policy(
+                "readers",
+                "DenyReports",
+                r#"{"Version":"2012-10-17","Statement":[{"Effect":"Deny","Action":["s3:*"],"Resource":["*"]}]}"#
+                    .to_string(),
+            )
+            .await
+            .unwrap();
+        let decision = service
+            .explain(&context, "s3:GetObject", &bucket)
+            .await
+            .unwrap();
+        assert!(!decision.allowed);
+        assert!(decision.explicit_deny);
+
+        // Root callers bypass group policies entirely
+        let root = tenant_context_with_root(11111111, true);
+        assert!(service
+            .authorize(&root, "s3:GetObject", &bucket)
+            .await
+            .unwrap());
+    }
 }
@@ -55,4 +55,4 @@ pub mod authentication;
 pub mod authorization;
 
 pub use authentication::{hash_secret, verify_secret, AuthenticationService};
-pub use authorization::AuthorizationService;
+pub use authorization::{AuthorizationService, PolicySources};
@@ -3,10 +3,15 @@
 //! Orchestrates access key management operations.
 
 use crate::context::WamiContext;
-use crate::error::Result;
+use crate::error::{AmiError, Result};
+use crate::provider::{AwsProvider, CloudProvider};
+use crate::service::auth::hash_secret;
 use crate::store::traits::AccessKeyStore;
+use crate::telemetry;
+use crate::types::PaginationParams;
 use crate::wami::credentials::access_key::{
-    builder as access_key_builder, AccessKey, CreateAccessKeyRequest, ListAccessKeysRequest,
+    builder as access_key_builder, AccessKey, AccessKeyLastUsed, CreateAccessKeyRequest,
+    ListAccessKeysRequest, RotateAccessKeyResponse,
 };
 use std::sync::{Arc, RwLock};
 
@@ -15,32 +20,62 @@ use std::sync::{Arc, RwLock};
 /// Provides high-level operations for access key management.
 pub struct AccessKeyService<S> {
     store: Arc<RwLock<S>>,
+    provider: Arc<dyn CloudProvider>,
 }
 
 impl<S: AccessKeyStore> AccessKeyService<S> {
-    /// Create a new AccessKeyService
+    /// Create a new AccessKeyService with default AWS provider
     pub fn new(store: Arc<RwLock<S>>) -> Self {
-        Self { store }
+        Self {
+            store,
+            provider: Arc::new(AwsProvider::new()),
+        }
+    }
+
+    /// Returns a new service instance with different provider
+    pub fn with_provider(&self, provider: Arc<dyn CloudProvider>) -> Self {
+        Self {
+            store: self.store.clone(),
+            provider,
+        }
     }
 
     /// Create a new access key
+    ///
+    /// Returns `ResourceLimitExceeded` if the user already has the provider's
+    /// `max_access_keys_per_user` keys, counting inactive ones.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                operation = "CreateAccessKey",
+                tenant = %context.tenant_path(),
+                resource = %request.user_name,
+            )
+        )
+    )]
     pub async fn create_access_key(
         &self,
         context: &WamiContext,
         request: CreateAccessKeyRequest,
     ) -> Result<AccessKey> {
+        let mut store = self.store.write().unwrap();
+        let limit = self.provider.resource_limits().max_access_keys_per_user;
+        ensure_below_access_key_limit(&*store, &request.user_name, limit).await?;
+
         // Use wami builder to create access key
         let access_key = access_key_builder::build_access_key(request.user_name, context)?;
 
-        // Store it
-        self.store
-            .write()
-            .unwrap()
-            .create_access_key(access_key)
-            .await
+        // Store it with its secret hashed
+        store_with_hashed_secret(&mut *store, access_key).await
     }
 
     /// Get an access key by ID
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(operation = "GetAccessKey", resource = %access_key_id))
+    )]
     pub async fn get_access_key(&self, access_key_id: &str) -> Result<Option<AccessKey>> {
         self.store
             .read()
@@ -50,6 +85,16 @@ impl<S: AccessKeyStore> AccessKeyService<S> {
     }
 
     /// Update an access key (e.g., change status)
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                operation = "UpdateAccessKey",
+                resource = %access_key.access_key_id,
+            )
+        )
+    )]
     pub async fn update_access_key(&self, access_key: AccessKey) -> Result<AccessKey> {
         self.store
             .write()
@@ -59,6 +104,16 @@ impl<S: AccessKeyStore> AccessKeyService<S> {
     }
 
     /// Delete an access key
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                operation = "DeleteAccessKey",
+                resource = %access_key_id,
+            )
+        )
+    )]
     pub async fn delete_access_key(&self, access_key_id: &str) -> Result<()> {
         self.store
             .write()
@@ -67,7 +122,91 @@ impl<S: AccessKeyStore> AccessKeyService<S> {
             .await
     }
 
+    /// Get when and where an access key was last used
+    ///
+    /// A key that was never used reports "N/A" for its region and service.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                operation = "GetAccessKeyLastUsed",
+                resource = %access_key_id,
+            )
+        )
+    )]
+    pub async fn get_access_key_last_used(&self, access_key_id: &str) -> Result<AccessKeyLastUsed> {
+        self.store
+            .read()
+            .unwrap()
+            .get_access_key_last_used(access_key_id)
+            .await
+    }
+
+    /// Rotate a user's access key
+    ///
+    /// Creates a new active key and deactivates the user's oldest active key,
+    /// whose ID is returned alongside the new key. The old key is kept so it
+    /// can be reactivated if the rotation has to be rolled back.
+    ///
+    /// Inactive keys still count towards the provider's
+    /// `max_access_keys_per_user`: a user already at the limit gets
+    /// `ResourceLimitExceeded` and nothing is changed.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                operation = "RotateAccessKey",
+                tenant = %context.tenant_path(),
+                resource = %user_name,
+            )
+        )
+    )]
+    pub async fn rotate_access_key(
+        &self,
+        context: &WamiContext,
+        user_name: &str,
+    ) -> Result<RotateAccessKeyResponse> {
+        let mut store = self.store.write().unwrap();
+
+        let limit = self.provider.resource_limits().max_access_keys_per_user;
+        let existing = ensure_below_access_key_limit(&*store, user_name, limit).await?;
+
+        let previous = existing
+            .into_iter()
+            .filter(|key| key.status == "Active")
+            .min_by_key(|key| key.create_date);
+
+        let access_key = access_key_builder::build_access_key(user_name.to_string(), context)?;
+        let access_key = store_with_hashed_secret(&mut *store, access_key).await?;
+
+        let deactivated_access_key_id = match previous {
+            Some(previous) => {
+                let previous =
+                    access_key_builder::update_access_key_status(previous, "Inactive".to_string());
+                Some(store.update_access_key(previous).await?.access_key_id)
+            }
+            None => None,
+        };
+
+        Ok(RotateAccessKeyResponse {
+            access_key,
+            deactivated_access_key_id,
+        })
+    }
+
     /// List access keys for a user
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                operation = "ListAccessKeys",
+                resource = %request.user_name,
+            )
+        )
+    )]
     pub async fn list_access_keys(
         &self,
         request: ListAccessKeysRequest,
@@ -78,6 +217,91 @@ impl<S: AccessKeyStore> AccessKeyService<S> {
             .list_access_keys(&request.user_name, request.pagination.as_ref())
             .await
     }
+
+    /// List access keys across all users for an account-wide audit
+    ///
+    /// Secrets are never returned. Non-root callers only see keys belonging to
+    /// their own tenant or its descendants. Results are sorted by access key ID
+    /// and the marker is the last access key ID of the previous page.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                operation = "ListAccessKeys",
+                tenant = %context.tenant_path(),
+            )
+        )
+    )]
+    pub async fn list_all_access_keys(
+        &self,
+        context: &WamiContext,
+        pagination: Option<&PaginationParams>,
+    ) -> Result<(Vec<AccessKey>, bool, Option<String>)> {
+        let all_keys = self.store.read().unwrap().list_all_access_keys().await?;
+
+        let mut access_keys: Vec<AccessKey> = all_keys
+            .into_iter()
+            .filter(|key| context.can_access_tenant(&key.wami_arn.tenant_path))
+            .filter(|key| {
+                pagination
+                    .and_then(|p| p.marker.as_deref())
+                    .is_none_or(|marker| key.access_key_id.as_str() > marker)
+            })
+            .map(|mut key| {
+                key.secret_access_key = None;
+                key
+            })
+            .collect();
+
+        let mut is_truncated = false;
+        let mut marker = None;
+
+        if let Some(max_items) = pagination.and_then(|p| p.max_items) {
+            if access_keys.len() > max_items as usize {
+                access_keys.truncate(max_items as usize);
+                is_truncated = true;
+                marker = access_keys.last().map(|key| key.access_key_id.clone());
+            }
+        }
+
+        Ok((access_keys, is_truncated, marker))
+    }
+}
+
+/// Fail with `ResourceLimitExceeded` if the user already has `limit` keys, active or not
+///
+/// Returns the user's existing keys.
+async fn ensure_below_access_key_limit<S: AccessKeyStore>(
+    store: &S,
+    user_name: &str,
+    limit: usize,
+) -> Result<Vec<AccessKey>> {
+    let (existing, _, _) = store.list_access_keys(user_name, None).await?;
+    if existing.len() >= limit {
+        return Err(AmiError::ResourceLimitExceeded {
+            resource_type: "AccessKeysPerUser".to_string(),
+            limit,
+        });
+    }
+    Ok(existing)
+}
+
+/// Store a freshly built access key with only the hash of its secret
+///
+/// Returns the stored key carrying the plaintext secret, which is handed to
+/// the caller once and cannot be retrieved afterwards.
+async fn store_with_hashed_secret<S: AccessKeyStore>(
+    store: &mut S,
+    mut access_key: AccessKey,
+) -> Result<AccessKey> {
+    let secret = access_key.secret_access_key.take();
+    access_key.secret_access_key = secret.as_deref().map(hash_secret).transpose()?;
+
+    let mut access_key = store.create_access_key(access_key).await?;
+    telemetry::record_created("access_key", &access_key.access_key_id);
+    access_key.secret_access_key = secret;
+    Ok(access_key)
 }
 
 #[cfg(test)]
@@ -152,8 +376,8 @@ mod tests {
     async fn test_list_access_keys() {
         let service = setup_service();
 
-        // Create multiple access keys for same user
-        for _ in 0..3 {
+        // Create as many access keys for the same user as the provider allows
+        for _ in 0..2 {
             let request = CreateAccessKeyRequest {
                 user_name: "charlie".to_string(),
             };
@@ -166,6 +390,281 @@ mod tests {
             pagination: None,
         };
         let (keys, _, _) = service.list_access_keys(list_request).await.unwrap();
+        assert_eq!(keys.len(), 2);
+    }
+
+    fn tenant_context(tenant_id: u64, is_root: bool) -> WamiContext {
+        let arn: WamiArn = format!("arn:wami:iam:{}:wami:123456789012:user/admin", tenant_id)
+            .parse()
+            .unwrap();
+        WamiContext::builder()
+            .instance_id("123456789012")
+            .tenant_path(TenantPath::single(tenant_id))
+            .caller_arn(arn)
+            .is_root(is_root)
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_list_all_access_keys_across_users() {
+        let service = setup_service();
+        let context = test_context();
+
+        for user_name in ["alice", "bob", "carol"] {
+            let request = CreateAccessKeyRequest {
+                user_name: user_name.to_string(),
+            };
+            service.create_access_key(&context, request).await.unwrap();
+        }
+
+        let (keys, is_truncated, marker) =
+            service.list_all_access_keys(&context, None).await.unwrap();
         assert_eq!(keys.len(), 3);
+        assert!(!is_truncated);
+        assert!(marker.is_none());
+        assert!(keys.iter().all(|k| k.secret_access_key.is_none()));
+
+        let mut owners: Vec<&str> = keys.iter().map(|k| k.user_name.as_str()).collect();
+        owners.sort();
+        assert_eq!(owners, vec!["alice", "bob", "carol"]);
+
+        let ids: Vec<&str> = keys.iter().map(|k| k.access_key_id.as_str()).collect();
+        let mut sorted_ids = ids.clone();
+        sorted_ids.sort();
+        assert_eq!(ids, sorted_ids);
+
+        // Paging through one key at a time yields the same stable order
+        let mut paged = Vec::new();
+        let mut pagination = PaginationParams {
+            max_items: Some(1),
+            marker: None,
+        };
+        loop {
+            let (page, is_truncated, marker) = service
+                .list_all_access_keys(&context, Some(&pagination))
+                .await
+                .unwrap();
+            paged.extend(page.into_iter().map(|k| k.access_key_id));
+            if !is_truncated {
+                break;
+            }
+            pagination.marker = marker;
+        }
+        assert_eq!(paged, ids);
+    }
+
+    #[tokio::test]
+    async fn test_list_all_access_keys_tenant_scoped() {
+        let service = setup_service();
+        let tenant_a = tenant_context(11111111, false);
+        let tenant_b = tenant_context(22222222, false);
+
+        for (user_name, context) in [
+            ("alice", &tenant_a),
+            ("bob", &tenant_a),
+            ("carol", &tenant_b),
+        ] {
+            let request = CreateAccessKeyRequest {
+                user_name: user_name.to_string(),
+            };
+            service.create_access_key(context, request).await.unwrap();
+        }
+
+        let (keys, _, _) = service.list_all_access_keys(&tenant_a, None).await.unwrap();
+        assert_eq!(keys.len(), 2);
+        assert!(keys.iter().all(|k| k.user_name != "carol"));
+
+        let (keys, _, _) = service.list_all_access_keys(&tenant_b, None).await.unwrap();
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].user_name, "carol");
+
+        let root = tenant_context(11111111, true);
+        let (keys, _, _) = service.list_all_access_keys(&root, None).await.unwrap();
+        assert_eq!(keys.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_rotate_access_key() {
+        let service = setup_service();
+        let context = test_context();
+
+        // Without an active key, rotation only creates one
+        let first = service.rotate_access_key(&context, "dave").await.unwrap();
+        assert!(first.deactivated_access_key_id.is_none());
+        assert_eq!(first.access_key.status, "Active");
+
+        let second = service.rotate_access_key(&context, "dave").await.unwrap();
+        assert!(second.access_key.secret_access_key.is_some());
+        assert_eq!(
+            second.deactivated_access_key_id.as_deref(),
+            Some(first.access_key.access_key_id.as_str())
+        );
+
+        let old = service
+            .get_access_key(&first.access_key.access_key_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(old.status, "Inactive");
+        let new = service
+            .get_access_key(&second.access_key.access_key_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(new.status, "Active");
+    }
+
+    #[tokio::test]
+    async fn test_rotate_access_key_at_limit() {
+        use crate::provider::ResourceLimits;
+
+        let limits = ResourceLimits {
+            max_access_keys_per_user: 2,
+            ..Default::default()
+        };
+        let service = setup_service()
+            .with_provider(Arc::new(crate::provider::AwsProvider::with_limits(limits)));
+        let context = test_context();
+
+        for _ in 0..2 {
+            let request = CreateAccessKeyRequest {
+                user_name: "erin".to_string(),
+            };
+            service.create_access_key(&context, request).await.unwrap();
+        }
+
+        let err = service
+            .rotate_access_key(&context, "erin")
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            AmiError::ResourceLimitExceeded { limit: 2, .. }
+        ));
+
+        // Nothing was deactivated or created
+        let (keys, _, _) = service
+            .list_access_keys(ListAccessKeysRequest {
+                user_name: "erin".to_string(),
+                pagination: None,
+            })
+            .await
+            .unwrap();
+        assert_eq!(keys.len(), 2);
+        assert!(keys.iter().all(|key| key.status == "Active"));
+    }
+
+    #[tokio::test]
+    async fn test_create_access_key_at_limit() {
+        use crate::provider::ResourceLimits;
+
+        let limits = ResourceLimits {
+            max_access_keys_per_user: 3,
+            ..Default::default()
+        };
+        let service = setup_service()
+            .with_provider(Arc::new(crate::provider::AwsProvider::with_limits(limits)));
+        let context = test_context();
+        let request = || CreateAccessKeyRequest {
+            user_name: "frank".to_string(),
+        };
+
+        let mut keys = Vec::new();
+        for _ in 0..3 {
+            keys.push(
+                service
+                    .create_access_key(&context, request())
+                    .await
+                    .unwrap(),
+            );
+        }
+
+        // Inactive keys still count towards the limit
+        let inactive = access_key_builder::update_access_key_status(
+            keys.pop().unwrap(),
+            "Inactive".to_string(),
+        );
+        service.update_access_key(inactive.clone()).await.unwrap();
+        let err = service
+            .create_access_key(&context, request())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            AmiError::ResourceLimitExceeded { limit: 3, .. }
+        ));
+
+        // Deleting a key makes room for a new one
+        service
+            .delete_access_key(&inactive.access_key_id)
+            .await
+            .unwrap();
+        service
+            .create_access_key(&context, request())
+            .await
+            .unwrap();
+        let (keys, _, _) = service
+            .list_access_keys(ListAccessKeysRequest {
+                user_name: "frank".to_string(),
+                pagination: None,
+            })
+            .await
+            .unwrap();
+        assert_eq!(keys.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_created_secret_is_stored_hashed() {
+        use crate::service::auth::AuthenticationService;
+        use crate::store::traits::UserStore;
+        use crate::wami::identity::user::builder::build_user;
+
+        let service = setup_service();
+        let context = test_context();
+        service
+            .store
+            .write()
+            .unwrap()
+            .create_user(build_user("frank".to_string(), None, &context).unwrap())
+            .await
+            .unwrap();
+
+        let created = service
+            .create_access_key(
+                &context,
+                CreateAccessKeyRequest {
+                    user_name: "frank".to_string(),
+                },
+            )
+            .await
+            .unwrap();
+        let secret = created.secret_access_key.clone().unwrap();
+
+        // Only the hash is stored
+        let stored = service
+            .get_access_key(&created.access_key_id)
+            .await
+            .unwrap()
+            .unwrap()
+            .secret_access_key
+            .unwrap();
+        assert_ne!(stored, secret);
+        assert!(stored.starts_with("$argon2id$"));
+
+        let store = service.store.read().unwrap().clone();
+        let auth = AuthenticationService::new(Arc::new(tokio::sync::RwLock::new(store)));
+        let authenticated = auth
+            .authenticate(&created.access_key_id, &secret)
+            .await
+            .unwrap();
+        assert_eq!(authenticated.instance_id(), "123456789012");
+
+        for wrong in ["wrong-secret", stored.as_str()] {
+            assert!(matches!(
+                auth.authenticate(&created.access_key_id, wrong).await,
+                Err(AmiError::InvalidParameter { .. })
+            ));
+        }
     }
 }
@@ -2,40 +2,54 @@
 //!
 //! Orchestrates login profile management operations.
 
+use crate::arn::TenantPath;
 use crate::context::WamiContext;
-use crate::error::Result;
-use crate::store::traits::LoginProfileStore;
+use crate::error::{AmiError, Result};
+use crate::service::{hash_secret, verify_secret};
+use crate::store::traits::{LoginProfileStore, PasswordPolicyStore};
 use crate::wami::credentials::login_profile::{
     builder as login_builder, CreateLoginProfileRequest, LoginProfile, UpdateLoginProfileRequest,
 };
+use crate::wami::credentials::password_policy::{
+    builder as password_policy_builder, PasswordPolicy, UpdateAccountPasswordPolicyRequest,
+};
 use std::sync::{Arc, RwLock};
 
 /// Service for managing IAM login profiles
 ///
-/// Provides high-level operations for console password management.
+/// Provides high-level operations for console password management, including
+/// the account password policy that console passwords must satisfy.
 pub struct LoginProfileService<S> {
     store: Arc<RwLock<S>>,
 }
 
-impl<S: LoginProfileStore> LoginProfileService<S> {
+impl<S: LoginProfileStore + PasswordPolicyStore> LoginProfileService<S> {
     /// Create a new LoginProfileService
     pub fn new(store: Arc<RwLock<S>>) -> Self {
         Self { store }
     }
 
     /// Create a new login profile
+    ///
+    /// The password must satisfy the password policy of the caller's account.
     pub async fn create_login_profile(
         &self,
         context: &WamiContext,
         request: CreateLoginProfileRequest,
     ) -> Result<LoginProfile> {
-        // Use wami builder to create login profile
-        // Note: Password is validated but not stored in the model for security
+        let policy = self.password_policy(context.tenant_path()).await?;
+        if let Some(policy) = &policy {
+            policy.validate_password(&request.password)?;
+        }
+
+        // Use wami builder to create login profile; only the password hash is kept
         let login_profile = login_builder::build_login_profile(
             request.user_name,
             request.password_reset_required,
             context,
         )?;
+        let login_profile =
+            login_builder::set_password_hash(login_profile, hash_secret(&request.password)?);
 
         // Store it
         self.store
@@ -55,23 +69,22 @@ impl<S: LoginProfileStore> LoginProfileService<S> {
     }
 
     /// Update a login profile
+    ///
+    /// A new password must satisfy the password policy of the profile's account,
+    /// including its reuse prevention.
     pub async fn update_login_profile(
         &self,
         request: UpdateLoginProfileRequest,
     ) -> Result<LoginProfile> {
         // Get existing profile
-        let profile = self
-            .store
-            .read()
-            .unwrap()
-            .get_login_profile(&request.user_name)
-            .await?
-            .ok_or_else(|| crate::error::AmiError::ResourceNotFound {
-                resource: format!("LoginProfile for user: {}", request.user_name),
-            })?;
+        let mut profile = self.existing_login_profile(&request.user_name).await?;
+
+        if let Some(password) = &request.password {
+            let policy = self.password_policy(&profile.wami_arn.tenant_path).await?;
+            profile = set_new_password(profile, policy.as_ref(), password)?;
+        }
 
         // Apply updates using builder functions
-        // Note: Password updates are handled separately for security
         let updated_profile =
             login_builder::update_login_profile(profile, request.password_reset_required);
 
@@ -83,6 +96,53 @@ impl<S: LoginProfileStore> LoginProfileService<S> {
             .await
     }
 
+    /// Change a user's own password
+    ///
+    /// `old_password` must match the current password, and the new password
+    /// must satisfy the account password policy, including reuse prevention.
+    /// When the account has a password policy, it must allow users to change
+    /// their password.
+    pub async fn change_password(
+        &self,
+        user_name: &str,
+        old_password: &str,
+        new_password: &str,
+    ) -> Result<LoginProfile> {
+        let profile = self.existing_login_profile(user_name).await?;
+        let policy = self.password_policy(&profile.wami_arn.tenant_path).await?;
+
+        if policy
+            .as_ref()
+            .is_some_and(|policy| !policy.allow_users_to_change_password)
+        {
+            return Err(AmiError::AccessDenied {
+                message:
+                    "The account password policy does not allow users to change their password"
+                        .to_string(),
+            });
+        }
+
+        let old_password_matches = match &profile.password_hash {
+            Some(hash) => verify_secret(old_password, hash)?,
+            None => false,
+        };
+        if !old_password_matches {
+            return Err(AmiError::AccessDenied {
+                message: format!("The old password for user {} is incorrect", user_name),
+            });
+        }
+
+        let mut profile = set_new_password(profile, policy.as_ref(), new_password)?;
+        // Changing the password satisfies a pending reset
+        profile.password_reset_required = false;
+
+        self.store
+            .write()
+            .unwrap()
+            .update_login_profile(profile)
+            .await
+    }
+
     /// Delete a login profile
     pub async fn delete_login_profile(&self, user_name: &str) -> Result<()> {
         self.store
@@ -91,6 +151,101 @@ impl<S: LoginProfileStore> LoginProfileService<S> {
             .delete_login_profile(user_name)
             .await
     }
+
+    /// Set the password policy of the caller's account, replacing any existing one
+    pub async fn update_account_password_policy(
+        &self,
+        context: &WamiContext,
+        request: UpdateAccountPasswordPolicyRequest,
+    ) -> Result<PasswordPolicy> {
+        let policy = password_policy_builder::build_password_policy(request)?;
+        self.store
+            .write()
+            .unwrap()
+            .put_password_policy(context.tenant_path(), policy)
+            .await
+    }
+
+    /// Get the password policy of the caller's account
+    ///
+    /// Returns `ResourceNotFound` if the account has no password policy.
+    pub async fn get_account_password_policy(
+        &self,
+        context: &WamiContext,
+    ) -> Result<PasswordPolicy> {
+        self.store
+            .read()
+            .unwrap()
+            .get_password_policy(context.tenant_path())
+            .await?
+            .ok_or_else(|| password_policy_not_found(context.tenant_path()))
+    }
+
+    /// Delete the password policy of the caller's account
+    ///
+    /// Returns `ResourceNotFound` if the account has no password policy.
+    pub async fn delete_account_password_policy(&self, context: &WamiContext) -> Result<()> {
+        self.get_account_password_policy(context).await?;
+        self.store
+            .write()
+            .unwrap()
+            .delete_password_policy(context.tenant_path())
+            .await
+    }
+
+    async fn existing_login_profile(&self, user_name: &str) -> Result<LoginProfile> {
+        self.store
+            .read()
+            .unwrap()
+            .get_login_profile(user_name)
+            .await?
+            .ok_or_else(|| AmiError::ResourceNotFound {
+                resource: format!("LoginProfile for user: {}", user_name),
+            })
+    }
+
+    async fn password_policy(&self, tenant_path: &TenantPath) -> Result<Option<PasswordPolicy>> {
+        self.store
+            .read()
+            .unwrap()
+            .get_password_policy(tenant_path)
+            .await
+    }
+}
+
+/// Validate a new password against the account policy, if any, and store its hash
+#[allow(clippy::result_large_err)]
+fn set_new_password(
+    profile: LoginProfile,
+    policy: Option<&PasswordPolicy>,
+    password: &str,
+) -> Result<LoginProfile> {
+    if let Some(policy) = policy {
+        policy.validate_password(password)?;
+
+        let remembered = policy.password_reuse_prevention.unwrap_or(0) as usize;
+        for hash in profile.recent_password_hashes(remembered) {
+            if verify_secret(password, hash)? {
+                return Err(AmiError::InvalidParameter {
+                    message: format!(
+                        "Password was used recently; the account password policy prevents reusing the last {} passwords",
+                        remembered
+                    ),
+                });
+            }
+        }
+    }
+
+    Ok(login_builder::set_password_hash(
+        profile,
+        hash_secret(password)?,
+    ))
+}
+
+fn password_policy_not_found(tenant_path: &TenantPath) -> AmiError {
+    AmiError::ResourceNotFound {
+        resource: format!("PasswordPolicy for account: {}", tenant_path),
+    }
 }
 
 #[cfg(test)]
@@ -189,4 +344,209 @@ mod tests {
         let retrieved = service.get_login_profile("charlie").await.unwrap();
         assert!(retrieved.is_none());
     }
+
+    fn strict_policy_request() -> UpdateAccountPasswordPolicyRequest {
+        UpdateAccountPasswordPolicyRequest {
+            minimum_password_length: Some(12),
+            require_symbols: true,
+            require_numbers: true,
+            require_uppercase_characters: true,
+            require_lowercase_characters: true,
+            allow_users_to_change_password: true,
+            max_password_age: Some(90),
+            password_reuse_prevention: Some(5),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_account_password_policy_lifecycle() {
+        let service = setup_service();
+        let context = test_context();
+
+        assert!(matches!(
+            service.get_account_password_policy(&context).await,
+            Err(AmiError::ResourceNotFound { .. })
+        ));
+
+        let policy = service
+            .update_account_password_policy(&context, strict_policy_request())
+            .await
+            .unwrap();
+        assert_eq!(policy.minimum_password_length, 12);
+        assert!(policy.expire_passwords());
+        assert_eq!(
+            service.get_account_password_policy(&context).await.unwrap(),
+            policy
+        );
+
+        service
+            .delete_account_password_policy(&context)
+            .await
+            .unwrap();
+        assert!(matches!(
+            service.delete_account_password_policy(&context).await,
+            Err(AmiError::ResourceNotFound { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_update_account_password_policy_rejects_out_of_range_settings() {
+        let service = setup_service();
+        let context = test_context();
+
+        for request in [
+            UpdateAccountPasswordPolicyRequest {
+                minimum_password_length: Some(5),
+                ..Default::default()
+            },
+            UpdateAccountPasswordPolicyRequest {
+                max_password_age: Some(0),
+                ..Default::default()
+            },
+            UpdateAccountPasswordPolicyRequest {
+                password_reuse_prevention: Some(25),
+                ..Default::default()
+            },
+        ] {
+            let result = service
+                .update_account_password_policy(&context, request)
+                .await;
+            assert!(matches!(result, Err(AmiError::InvalidParameter { .. })));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_login_profile_passwords_follow_account_policy() {
+        let service = setup_service();
+        let context = test_context();
+        service
+            .update_account_password_policy(&context, strict_policy_request())
+            .await
+            .unwrap();
+
+        let create = |password: &str| CreateLoginProfileRequest {
+            user_name: "dave".to_string(),
+            password: password.to_string(),
+            password_reset_required: false,
+        };
+        for weak in [
+            "Sh0rt!",
+            "nouppercase1!",
+            "NOLOWERCASE1!",
+            "NoNumbers!!!",
+            "NoSymbols123",
+        ] {
+            let result = service.create_login_profile(&context, create(weak)).await;
+            assert!(
+                matches!(result, Err(AmiError::InvalidParameter { .. })),
+                "{} should be rejected",
+                weak
+            );
+        }
+        assert!(service.get_login_profile("dave").await.unwrap().is_none());
+
+        service
+            .create_login_profile(&context, create("C0rrect-Horse"))
+            .await
+            .unwrap();
+
+        let update = |password: &str| UpdateLoginProfileRequest {
+            user_name: "dave".to_string(),
+            password: Some(password.to_string()),
+            password_reset_required: None,
+        };
+        let result = service.update_login_profile(update("weak")).await;
+        assert!(matches!(result, Err(AmiError::InvalidParameter { .. })));
+        service
+            .update_login_profile(update("B4ttery-Staple"))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_change_password_prevents_reuse() {
+        let service = setup_service();
+        let context = test_context();
+        service
+            .update_account_password_policy(
+                &context,
+                UpdateAccountPasswordPolicyRequest {
+                    allow_users_to_change_password: true,
+                    password_reuse_prevention: Some(2),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        service
+            .create_login_profile(
+                &context,
+                CreateLoginProfileRequest {
+                    user_name: "erin".to_string(),
+                    password: "first-password".to_string(),
+                    password_reset_required: true,
+                },
+            )
+            .await
+            .unwrap();
+
+        let result = service
+            .change_password("erin", "wrong-password", "second-password")
+            .await;
+        assert!(matches!(result, Err(AmiError::AccessDenied { .. })));
+
+        // The current password counts towards the remembered passwords
+        let result = service
+            .change_password("erin", "first-password", "first-password")
+            .await;
+        assert!(matches!(result, Err(AmiError::InvalidParameter { .. })));
+
+        let profile = service
+            .change_password("erin", "first-password", "second-password")
+            .await
+            .unwrap();
+        assert!(!profile.password_reset_required);
+        assert_eq!(profile.password_history.len(), 1);
+
+        let result = service
+            .change_password("erin", "second-password", "first-password")
+            .await;
+        assert!(matches!(result, Err(AmiError::InvalidParameter { .. })));
+
+        // Passwords older than the last two may be reused
+        service
+            .change_password("erin", "second-password", "third-password")
+            .await
+            .unwrap();
+        service
+            .change_password("erin", "third-password", "first-password")
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_change_password_requires_policy_permission() {
+        let service = setup_service();
+        let context = test_context();
+        service
+            .create_login_profile(
+                &context,
+                CreateLoginProfileRequest {
+                    user_name: "frank".to_string(),
+                    password: "first-password".to_string(),
+                    password_reset_required: false,
+                },
+            )
+            .await
+            .unwrap();
+        service
+            .update_account_password_policy(&context, UpdateAccountPasswordPolicyRequest::default())
+            .await
+            .unwrap();
+
+        let result = service
+            .change_password("frank", "first-password", "second-password")
+            .await;
+        assert!(matches!(result, Err(AmiError::AccessDenied { .. })));
+    }
 }
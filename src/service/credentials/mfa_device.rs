@@ -3,11 +3,13 @@
 //! Orchestrates MFA device management operations.
 
 use crate::context::WamiContext;
-use crate::error::Result;
+use crate::error::{AmiError, Result};
 use crate::store::traits::MfaDeviceStore;
 use crate::wami::credentials::mfa_device::{
-    builder as mfa_builder, EnableMfaDeviceRequest, ListMfaDevicesRequest, MfaDevice,
+    builder as mfa_builder, totp, CreateVirtualMfaDeviceRequest, EnableMfaDeviceRequest,
+    ListMfaDevicesRequest, MfaDevice,
 };
+use chrono::{DateTime, Utc};
 use std::sync::{Arc, RwLock};
 
 /// Service for managing IAM MFA devices
@@ -41,6 +43,155 @@ impl<S: MfaDeviceStore> MfaDeviceService<S> {
             .await
     }
 
+    /// Create a virtual MFA device
+    ///
+    /// Generates a TOTP secret and an `otpauth://` provisioning URI, both returned
+    /// on the device. The device is not assigned to any user until it is enabled
+    /// with [`enable_mfa_device`](Self::enable_mfa_device).
+    pub async fn create_virtual_mfa_device(
+        &self,
+        context: &WamiContext,
+        request: CreateVirtualMfaDeviceRequest,
+    ) -> Result<MfaDevice> {
+        if request.virtual_mfa_device_name.trim().is_empty() {
+            return Err(AmiError::InvalidParameter {
+                message: "VirtualMFADeviceName cannot be empty".to_string(),
+            });
+        }
+
+        let device = mfa_builder::build_virtual_mfa_device(
+            request.virtual_mfa_device_name,
+            request.path,
+            context,
+        )?;
+
+        let mut store = self.store.write().unwrap();
+        if store.get_mfa_device(&device.serial_number).await?.is_some() {
+            return Err(AmiError::ResourceExists {
+                resource: format!("MFA device {}", device.serial_number),
+            });
+        }
+        store.create_mfa_device(device).await
+    }
+
+    /// Enable a virtual MFA device for a user
+    ///
+    /// As in AWS, the request must carry two consecutive codes generated by the
+    /// device, the second one for the current time step.
+    pub async fn enable_mfa_device(
+        &self,
+        request: EnableMfaDeviceRequest,
+        now: DateTime<Utc>,
+    ) -> Result<MfaDevice> {
+        let mut store = self.store.write().unwrap();
+        let mut device = store
+            .get_mfa_device(&request.serial_number)
+            .await?
+            .ok_or_else(|| AmiError::ResourceNotFound {
+                resource: format!("MFA device {}", request.serial_number),
+            })?;
+
+        if device.is_enabled() {
+            return Err(AmiError::ResourceExists {
+                resource: format!(
+                    "MFA device {} is already enabled for user {}",
+                    device.serial_number, device.user_name
+                ),
+            });
+        }
+        let seed =
+            device
+                .base32_string_seed
+                .as_deref()
+                .ok_or_else(|| AmiError::InvalidParameter {
+                    message: format!(
+                        "MFA device {} is not a virtual device",
+                        device.serial_number
+                    ),
+                })?;
+
+        if !totp::verify_consecutive_codes(
+            seed,
+            &request.authentication_code_1,
+            &request.authentication_code_2,
+            now,
+        )? {
+            return Err(AmiError::InvalidParameter {
+                message: "Authentication codes are invalid or not consecutive".to_string(),
+            });
+        }
+
+        device.user_name = request.user_name;
+        device.enable_date = now;
+        store.update_mfa_device(device).await
+    }
+
+    /// Resynchronize a virtual MFA device whose clock has drifted
+    ///
+    /// Searches a wide window for two consecutive codes and records the device's
+    /// clock skew on it, so later [`verify_totp`](Self::verify_totp) calls are
+    /// evaluated against the device's own notion of time.
+    pub async fn resync_mfa_device(
+        &self,
+        user_name: &str,
+        serial_number: &str,
+        authentication_code_1: &str,
+        authentication_code_2: &str,
+        now: DateTime<Utc>,
+    ) -> Result<MfaDevice> {
+        let mut store = self.store.write().unwrap();
+        let mut device = store
+            .get_mfa_device(serial_number)
+            .await?
+            .filter(|device| device.user_name == user_name)
+            .ok_or_else(|| AmiError::ResourceNotFound {
+                resource: format!("MFA device {} for user {}", serial_number, user_name),
+            })?;
+        let seed =
+            device
+                .base32_string_seed
+                .as_deref()
+                .ok_or_else(|| AmiError::InvalidParameter {
+                    message: format!("MFA device {} is not a virtual device", serial_number),
+                })?;
+
+        let offset = totp::resync_offset(seed, authentication_code_1, authentication_code_2, now)?
+            .ok_or_else(|| AmiError::InvalidParameter {
+                message: "Authentication codes are invalid or not consecutive".to_string(),
+            })?;
+
+        device.time_step_offset = offset;
+        store.update_mfa_device(device).await
+    }
+
+    /// Verify a TOTP code from a virtual MFA device
+    ///
+    /// Accepts the code for the current 30-second step or the one before or after it,
+    /// shifted by the offset recorded when the device was last resynchronized.
+    pub async fn verify_totp(
+        &self,
+        serial_number: &str,
+        code: &str,
+        now: DateTime<Utc>,
+    ) -> Result<bool> {
+        let device = self
+            .store
+            .read()
+            .unwrap()
+            .get_mfa_device(serial_number)
+            .await?
+            .ok_or_else(|| AmiError::ResourceNotFound {
+                resource: format!("MFA device {}", serial_number),
+            })?;
+
+        match device.base32_string_seed.as_deref() {
+            Some(seed) => totp::verify_code_with_offset(seed, code, now, device.time_step_offset),
+            None => Err(AmiError::InvalidParameter {
+                message: format!("MFA device {} is not a virtual device", serial_number),
+            }),
+        }
+    }
+
     /// Get an MFA device by serial number
     pub async fn get_mfa_device(&self, serial_number: &str) -> Result<Option<MfaDevice>> {
         self.store
@@ -163,4 +314,188 @@ mod tests {
         let devices = service.list_mfa_devices(list_request).await.unwrap();
         assert_eq!(devices.len(), 3);
     }
+
+    fn virtual_device_request(name: &str) -> CreateVirtualMfaDeviceRequest {
+        CreateVirtualMfaDeviceRequest {
+            virtual_mfa_device_name: name.to_string(),
+            path: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_virtual_mfa_device() {
+        let service = setup_service();
+        let context = test_context();
+
+        let device = service
+            .create_virtual_mfa_device(&context, virtual_device_request("alice-phone"))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            device.serial_number,
+            "arn:aws:iam::123456789012:mfa/alice-phone"
+        );
+        assert!(device.is_virtual());
+        assert!(!device.is_enabled());
+        let seed = device.base32_string_seed.clone().unwrap();
+        assert!(device
+            .provisioning_uri
+            .as_deref()
+            .unwrap()
+            .starts_with("otpauth://totp/WAMI:alice-phone%40123456789012?secret="));
+        assert!(device.provisioning_uri.as_deref().unwrap().contains(&seed));
+
+        // The same name cannot be reused
+        let duplicate = service
+            .create_virtual_mfa_device(&context, virtual_device_request("alice-phone"))
+            .await;
+        assert!(matches!(duplicate, Err(AmiError::ResourceExists { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_enable_virtual_mfa_device_requires_consecutive_codes() {
+        let service = setup_service();
+        let context = test_context();
+        let device = service
+            .create_virtual_mfa_device(&context, virtual_device_request("bob-phone"))
+            .await
+            .unwrap();
+        let seed = device.base32_string_seed.clone().unwrap();
+        let now = Utc::now();
+        let step = totp::time_step(now);
+
+        let enable = |code_1: String, code_2: String| EnableMfaDeviceRequest {
+            user_name: "bob".to_string(),
+            serial_number: device.serial_number.clone(),
+            authentication_code_1: code_1,
+            authentication_code_2: code_2,
+        };
+
+        // Stale codes from a few minutes ago are rejected
+        let stale = service
+            .enable_mfa_device(
+                enable(
+                    totp::code_for_step(&seed, step - 10).unwrap(),
+                    totp::code_for_step(&seed, step - 9).unwrap(),
+                ),
+                now,
+            )
+            .await;
+        assert!(matches!(stale, Err(AmiError::InvalidParameter { .. })));
+
+        let enabled = service
+            .enable_mfa_device(
+                enable(
+                    totp::code_for_step(&seed, step - 1).unwrap(),
+                    totp::code_for_step(&seed, step).unwrap(),
+                ),
+                now,
+            )
+            .await
+            .unwrap();
+        assert_eq!(enabled.user_name, "bob");
+        assert!(enabled.is_enabled());
+
+        let listed = service
+            .list_mfa_devices(ListMfaDevicesRequest {
+                user_name: "bob".to_string(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(listed.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_verify_totp() {
+        let service = setup_service();
+        let context = test_context();
+        let device = service
+            .create_virtual_mfa_device(&context, virtual_device_request("carol-phone"))
+            .await
+            .unwrap();
+        let seed = device.base32_string_seed.clone().unwrap();
+        let now = Utc::now();
+
+        let current = totp::code_at(&seed, now).unwrap();
+        assert!(service
+            .verify_totp(&device.serial_number, &current, now)
+            .await
+            .unwrap());
+
+        let stale = totp::code_at(&seed, now - chrono::Duration::minutes(5)).unwrap();
+        assert!(!service
+            .verify_totp(&device.serial_number, &stale, now)
+            .await
+            .unwrap());
+
+        let missing = service.verify_totp("arn:aws:iam::123456789012:mfa/none", &current, now);
+        assert!(matches!(
+            missing.await,
+            Err(AmiError::ResourceNotFound { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_resync_mfa_device_records_clock_offset() {
+        let service = setup_service();
+        let context = test_context();
+        let device = service
+            .create_virtual_mfa_device(&context, virtual_device_request("dave-phone"))
+            .await
+            .unwrap();
+        let seed = device.base32_string_seed.clone().unwrap();
+        let now = Utc::now();
+        let step = totp::time_step(now);
+        service
+            .enable_mfa_device(
+                EnableMfaDeviceRequest {
+                    user_name: "dave".to_string(),
+                    serial_number: device.serial_number.clone(),
+                    authentication_code_1: totp::code_for_step(&seed, step - 1).unwrap(),
+                    authentication_code_2: totp::code_for_step(&seed, step).unwrap(),
+                },
+                now,
+            )
+            .await
+            .unwrap();
+
+        // The device's clock now runs four minutes (eight steps) ahead
+        let skewed = |offset: u64| totp::code_for_step(&seed, step + offset).unwrap();
+        assert!(!service
+            .verify_totp(&device.serial_number, &skewed(8), now)
+            .await
+            .unwrap());
+
+        // Codes that are not consecutive are rejected
+        let result = service
+            .resync_mfa_device("dave", &device.serial_number, &skewed(7), &skewed(9), now)
+            .await;
+        assert!(matches!(result, Err(AmiError::InvalidParameter { .. })));
+
+        // Only the owner can resync the device
+        let result = service
+            .resync_mfa_device(
+                "mallory",
+                &device.serial_number,
+                &skewed(7),
+                &skewed(8),
+                now,
+            )
+            .await;
+        assert!(matches!(result, Err(AmiError::ResourceNotFound { .. })));
+
+        let resynced = service
+            .resync_mfa_device("dave", &device.serial_number, &skewed(7), &skewed(8), now)
+            .await
+            .unwrap();
+        assert_eq!(resynced.time_step_offset, 8);
+
+        // A minute later, the skewed device's current code verifies
+        let later = now + chrono::Duration::seconds(60);
+        assert!(service
+            .verify_totp(&device.serial_number, &skewed(10), later)
+            .await
+            .unwrap());
+    }
 }
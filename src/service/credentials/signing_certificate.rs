@@ -3,7 +3,8 @@
 //! Orchestrates signing certificate management operations.
 
 use crate::context::WamiContext;
-use crate::error::Result;
+use crate::error::{AmiError, Result};
+use crate::provider::{AwsProvider, CloudProvider};
 use crate::store::traits::SigningCertificateStore;
 use crate::wami::credentials::signing_certificate::{
     builder as cert_builder, DeleteSigningCertificateRequest, ListSigningCertificatesRequest,
@@ -16,33 +17,60 @@ use std::sync::{Arc, RwLock};
 /// Provides high-level operations for X.509 certificate management.
 pub struct SigningCertificateService<S> {
     store: Arc<RwLock<S>>,
+    provider: Arc<dyn CloudProvider>,
 }
 
 impl<S: SigningCertificateStore> SigningCertificateService<S> {
-    /// Create a new SigningCertificateService
+    /// Create a new SigningCertificateService with default AWS provider
     pub fn new(store: Arc<RwLock<S>>) -> Self {
-        Self { store }
+        Self {
+            store,
+            provider: Arc::new(AwsProvider::new()),
+        }
+    }
+
+    /// Returns a new service instance with different provider
+    pub fn with_provider(&self, provider: Arc<dyn CloudProvider>) -> Self {
+        Self {
+            store: self.store.clone(),
+            provider,
+        }
     }
 
     /// Upload a new signing certificate
+    ///
+    /// The body must parse as an X.509 certificate, otherwise `InvalidParameter`
+    /// is returned. The certificate starts out `Active`. A user already holding
+    /// the provider's `max_signing_certificates_per_user` gets `ResourceLimitExceeded`.
     pub async fn upload_signing_certificate(
         &self,
         context: &WamiContext,
         request: UploadSigningCertificateRequest,
     ) -> Result<SigningCertificate> {
-        // Use wami builder to create certificate
+        // Use wami builder to validate and create the certificate
         let certificate = cert_builder::build_signing_certificate(
             request.user_name,
             request.certificate_body,
             context,
         )?;
 
+        let mut store = self.store.write().unwrap();
+        let existing = store
+            .list_signing_certificates(Some(&certificate.user_name))
+            .await?;
+        let limit = self
+            .provider
+            .resource_limits()
+            .max_signing_certificates_per_user;
+        if existing.len() >= limit {
+            return Err(AmiError::ResourceLimitExceeded {
+                resource_type: "SigningCertificatesPerUser".to_string(),
+                limit,
+            });
+        }
+
         // Store it
-        self.store
-            .write()
-            .unwrap()
-            .create_signing_certificate(certificate)
-            .await
+        store.create_signing_certificate(certificate).await
     }
 
     /// Get a signing certificate by ID
@@ -69,7 +97,7 @@ impl<S: SigningCertificateStore> SigningCertificateService<S> {
             .unwrap()
             .get_signing_certificate(&request.certificate_id)
             .await?
-            .ok_or_else(|| crate::error::AmiError::ResourceNotFound {
+            .ok_or_else(|| AmiError::ResourceNotFound {
                 resource: format!("SigningCertificate: {}", request.certificate_id),
             })?;
 
@@ -114,6 +142,7 @@ mod tests {
     use super::*;
     use crate::store::memory::InMemoryWamiStore;
     use crate::wami::credentials::signing_certificate::CertificateStatus;
+    use crate::wami::credentials::x509::tests::SELF_SIGNED_PEM;
 
     fn setup_service() -> SigningCertificateService<InMemoryWamiStore> {
         let store = Arc::new(RwLock::new(InMemoryWamiStore::default()));
@@ -146,8 +175,7 @@ mod tests {
 
         let request = UploadSigningCertificateRequest {
             user_name: "alice".to_string(),
-            certificate_body: "-----BEGIN CERTIFICATE-----\ntest\n-----END CERTIFICATE-----"
-                .to_string(),
+            certificate_body: SELF_SIGNED_PEM.to_string(),
         };
 
         let certificate = service
@@ -172,8 +200,7 @@ mod tests {
 
         let upload_req = UploadSigningCertificateRequest {
             user_name: "bob".to_string(),
-            certificate_body: "-----BEGIN CERTIFICATE-----\ntest\n-----END CERTIFICATE-----"
-                .to_string(),
+            certificate_body: SELF_SIGNED_PEM.to_string(),
         };
         let certificate = service
             .upload_signing_certificate(&context, upload_req)
@@ -199,8 +226,7 @@ mod tests {
 
         let upload_req = UploadSigningCertificateRequest {
             user_name: "charlie".to_string(),
-            certificate_body: "-----BEGIN CERTIFICATE-----\ntest\n-----END CERTIFICATE-----"
-                .to_string(),
+            certificate_body: SELF_SIGNED_PEM.to_string(),
         };
         let certificate = service
             .upload_signing_certificate(&context, upload_req)
@@ -225,15 +251,21 @@ mod tests {
 
     #[tokio::test]
     async fn test_list_signing_certificates() {
-        let service = setup_service();
+        use crate::provider::ResourceLimits;
+
+        let limits = ResourceLimits {
+            max_signing_certificates_per_user: 3,
+            ..Default::default()
+        };
+        let service = setup_service()
+            .with_provider(Arc::new(crate::provider::AwsProvider::with_limits(limits)));
         let context = test_context();
 
         // Upload multiple certificates for same user
         for _ in 0..3 {
             let request = UploadSigningCertificateRequest {
                 user_name: "david".to_string(),
-                certificate_body: "-----BEGIN CERTIFICATE-----\ntest\n-----END CERTIFICATE-----"
-                    .to_string(),
+                certificate_body: SELF_SIGNED_PEM.to_string(),
             };
             service
                 .upload_signing_certificate(&context, request)
@@ -250,4 +282,85 @@ mod tests {
             .unwrap();
         assert_eq!(certificates.len(), 3);
     }
+
+    #[tokio::test]
+    async fn test_upload_records_certificate_details() {
+        let service = setup_service();
+        let context = test_context();
+
+        let certificate = service
+            .upload_signing_certificate(
+                &context,
+                UploadSigningCertificateRequest {
+                    user_name: "alice".to_string(),
+                    certificate_body: SELF_SIGNED_PEM.to_string(),
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(certificate.status, CertificateStatus::Active);
+        assert_eq!(certificate.serial_number, "1A2B3C");
+        assert_eq!(
+            certificate.fingerprint,
+            "3FDE08931FADE84BFEE9A432BAE966325721BACB"
+        );
+        assert_eq!(certificate.certificate_body, SELF_SIGNED_PEM);
+    }
+
+    #[tokio::test]
+    async fn test_upload_rejects_malformed_certificate() {
+        let service = setup_service();
+        let context = test_context();
+
+        let result = service
+            .upload_signing_certificate(
+                &context,
+                UploadSigningCertificateRequest {
+                    user_name: "alice".to_string(),
+                    certificate_body:
+                        "-----BEGIN CERTIFICATE-----\nZ2FyYmFnZQ==\n-----END CERTIFICATE-----"
+                            .to_string(),
+                },
+            )
+            .await;
+        assert!(matches!(result, Err(AmiError::InvalidParameter { .. })));
+        assert!(service
+            .list_signing_certificates(ListSigningCertificatesRequest {
+                user_name: Some("alice".to_string()),
+            })
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_upload_enforces_per_user_limit() {
+        let service = setup_service();
+        let context = test_context();
+        let upload = |user_name: &str| UploadSigningCertificateRequest {
+            user_name: user_name.to_string(),
+            certificate_body: SELF_SIGNED_PEM.to_string(),
+        };
+
+        // The default AWS quota is two certificates per user
+        for _ in 0..2 {
+            service
+                .upload_signing_certificate(&context, upload("erin"))
+                .await
+                .unwrap();
+        }
+        let result = service
+            .upload_signing_certificate(&context, upload("erin"))
+            .await;
+        assert!(matches!(
+            result,
+            Err(AmiError::ResourceLimitExceeded { limit: 2, .. })
+        ));
+
+        // Other users are unaffected
+        service
+            .upload_signing_certificate(&context, upload("frank"))
+            .await
+            .unwrap();
+    }
 }
@@ -2,12 +2,17 @@
 //!
 //! Orchestrates group management operations including membership management.
 
+use super::precheck::{id_collision, name_collision, MAX_ID_ATTEMPTS};
 use crate::context::WamiContext;
 use crate::error::Result;
+use crate::service::audit::{self, AuditSink};
+use crate::store::memory::pagination::paginate;
 use crate::store::traits::GroupStore;
+use crate::telemetry;
 use crate::wami::identity::group::{
     builder as group_builder, CreateGroupRequest, Group, ListGroupsRequest, UpdateGroupRequest,
 };
+use crate::wami::identity::User;
 use std::sync::{Arc, RwLock};
 
 /// Service for managing IAM groups
@@ -15,34 +20,128 @@ use std::sync::{Arc, RwLock};
 /// Provides high-level operations for group management and membership.
 pub struct GroupService<S> {
     store: Arc<RwLock<S>>,
+    audit_sink: Option<Arc<dyn AuditSink>>,
 }
 
 impl<S: GroupStore> GroupService<S> {
     /// Create a new GroupService
     pub fn new(store: Arc<RwLock<S>>) -> Self {
-        Self { store }
+        Self {
+            store,
+            audit_sink: None,
+        }
+    }
+
+    /// Returns a new service instance reporting group creation, update and deletion to `sink`
+    pub fn with_audit_sink(&self, sink: Arc<dyn AuditSink>) -> Self {
+        Self {
+            store: self.store.clone(),
+            audit_sink: Some(sink),
+        }
     }
 
     /// Create a new group
+    ///
+    /// Returns `ResourceExists` if a group with the same name already exists.
+    /// A generated ID that collides with an existing group is regenerated.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                operation = "CreateGroup",
+                tenant = %context.tenant_path(),
+                resource = %request.group_name,
+            )
+        )
+    )]
     pub async fn create_group(
         &self,
         context: &WamiContext,
         request: CreateGroupRequest,
     ) -> Result<Group> {
-        // Use wami builder to create group
-        let group = group_builder::build_group(request.group_name, request.path, context)?;
+        let group_name = request.group_name.clone();
+        let result = self.store_new_group(context, request).await;
+        audit::record_result(
+            self.audit_sink.as_ref(),
+            "CreateGroup",
+            Some(context),
+            result
+                .as_ref()
+                .map_or(group_name, |group| group.wami_arn.to_string()),
+            &result,
+        );
+        result
+    }
 
-        // Store it
-        self.store.write().unwrap().create_group(group).await
+    async fn store_new_group(
+        &self,
+        context: &WamiContext,
+        request: CreateGroupRequest,
+    ) -> Result<Group> {
+        let mut store = self.store.write().unwrap();
+
+        if store.get_group(&request.group_name).await?.is_some() {
+            return Err(name_collision("Group", &request.group_name));
+        }
+
+        let (existing, _, _) = store.list_groups(None, None).await?;
+        for _ in 0..MAX_ID_ATTEMPTS {
+            // Use wami builder to create group
+            let group = group_builder::build_group(
+                request.group_name.clone(),
+                request.path.clone(),
+                context,
+            )?;
+            let taken = existing
+                .iter()
+                .any(|g| g.group_id == group.group_id || g.wami_arn == group.wami_arn);
+            if !taken {
+                let group = store.create_group(group).await?;
+                telemetry::record_created("group", &group.group_name);
+                return Ok(group);
+            }
+        }
+
+        Err(id_collision("group", &request.group_name))
     }
 
     /// Get a group by name
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(operation = "GetGroup", resource = %group_name))
+    )]
     pub async fn get_group(&self, group_name: &str) -> Result<Option<Group>> {
         self.store.read().unwrap().get_group(group_name).await
     }
 
     /// Update a group
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                operation = "UpdateGroup",
+                resource = %request.group_name,
+            )
+        )
+    )]
     pub async fn update_group(&self, request: UpdateGroupRequest) -> Result<Group> {
+        let group_name = request.group_name.clone();
+        let result = self.apply_group_update(request).await;
+        audit::record_result(
+            self.audit_sink.as_ref(),
+            "UpdateGroup",
+            None,
+            result
+                .as_ref()
+                .map_or(group_name, |group| group.wami_arn.to_string()),
+            &result,
+        );
+        result
+    }
+
+    async fn apply_group_update(&self, request: UpdateGroupRequest) -> Result<Group> {
         // Get existing group
         let mut group = self
             .store
@@ -68,23 +167,66 @@ impl<S: GroupStore> GroupService<S> {
     }
 
     /// Delete a group
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(operation = "DeleteGroup", resource = %group_name))
+    )]
     pub async fn delete_group(&self, group_name: &str) -> Result<()> {
-        self.store.write().unwrap().delete_group(group_name).await
+        let mut store = self.store.write().unwrap();
+        let existing = store.get_group(group_name).await?;
+        let result = store.delete_group(group_name).await;
+        audit::record_result(
+            self.audit_sink.as_ref(),
+            "DeleteGroup",
+            None,
+            existing.map_or_else(
+                || group_name.to_string(),
+                |group| group.wami_arn.to_string(),
+            ),
+            &result,
+        );
+        result
     }
 
     /// List groups with optional filtering
+    ///
+    /// Only groups of the caller's tenant are returned, plus those of its
+    /// descendant tenants when `include_descendants` is set. Root callers see
+    /// groups of every tenant.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(operation = "ListGroups", tenant = %context.tenant_path())
+        )
+    )]
     pub async fn list_groups(
         &self,
+        context: &WamiContext,
         request: ListGroupsRequest,
     ) -> Result<(Vec<Group>, bool, Option<String>)> {
-        self.store
+        let (mut groups, _, _) = self
+            .store
             .read()
             .unwrap()
-            .list_groups(request.path_prefix.as_deref(), request.pagination.as_ref())
-            .await
+            .list_groups(request.path_prefix.as_deref(), None)
+            .await?;
+        groups.retain(|item| {
+            context.lists_tenant(&item.wami_arn.tenant_path, request.include_descendants)
+        });
+        Ok(paginate(groups, request.pagination.as_ref(), |item| {
+            &item.group_name
+        }))
     }
 
     /// Add a user to a group
+    ///
+    /// Returns `ResourceNotFound` if the user or the group does not exist.
+    /// Adding an existing member again is a no-op.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(operation = "AddUserToGroup", resource = %group_name))
+    )]
     pub async fn add_user_to_group(&self, group_name: &str, user_name: &str) -> Result<()> {
         self.store
             .write()
@@ -94,6 +236,16 @@ impl<S: GroupStore> GroupService<S> {
     }
 
     /// Remove a user from a group
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                operation = "RemoveUserFromGroup",
+                resource = %group_name,
+            )
+        )
+    )]
     pub async fn remove_user_from_group(&self, group_name: &str, user_name: &str) -> Result<()> {
         self.store
             .write()
@@ -103,6 +255,16 @@ impl<S: GroupStore> GroupService<S> {
     }
 
     /// List all groups for a user
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                operation = "ListGroupsForUser",
+                resource = %user_name,
+            )
+        )
+    )]
     pub async fn list_groups_for_user(&self, user_name: &str) -> Result<Vec<Group>> {
         self.store
             .read()
@@ -110,6 +272,19 @@ impl<S: GroupStore> GroupService<S> {
             .list_groups_for_user(user_name)
             .await
     }
+
+    /// List all users in a group
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(operation = "GetGroup", resource = %group_name))
+    )]
+    pub async fn list_users_in_group(&self, group_name: &str) -> Result<Vec<User>> {
+        self.store
+            .read()
+            .unwrap()
+            .list_users_in_group(group_name)
+            .await
+    }
 }
 
 #[cfg(test)]
@@ -222,11 +397,44 @@ mod tests {
         let list_request = ListGroupsRequest {
             path_prefix: Some("/test/".to_string()),
             pagination: None,
+            include_descendants: false,
         };
-        let (groups, _, _) = service.list_groups(list_request).await.unwrap();
+        let (groups, _, _) = service
+            .list_groups(&test_context(), list_request)
+            .await
+            .unwrap();
         assert_eq!(groups.len(), 3);
     }
 
+    #[tokio::test]
+    async fn test_list_groups_by_path_prefix() {
+        let service = setup_service();
+        let context = test_context();
+        for (name, path) in [("developers", "/eng/"), ("oncall", "/ops/")] {
+            let request = CreateGroupRequest {
+                group_name: name.to_string(),
+                path: Some(path.to_string()),
+                tags: None,
+            };
+            service.create_group(&context, request).await.unwrap();
+        }
+
+        for (path_prefix, expected) in [
+            (Some("/eng/"), vec!["developers"]),
+            (Some("/ops/"), vec!["oncall"]),
+            (None, vec!["developers", "oncall"]),
+        ] {
+            let request = ListGroupsRequest {
+                path_prefix: path_prefix.map(str::to_string),
+                pagination: None,
+                include_descendants: false,
+            };
+            let (groups, _, _) = service.list_groups(&context, request).await.unwrap();
+            let names: Vec<_> = groups.iter().map(|g| g.group_name.as_str()).collect();
+            assert_eq!(names, expected, "{:?}", path_prefix);
+        }
+    }
+
     #[tokio::test]
     async fn test_group_membership() {
         let store = Arc::new(RwLock::new(InMemoryWamiStore::default()));
@@ -263,4 +471,99 @@ mod tests {
         let groups_after = service.list_groups_for_user("alice").await.unwrap();
         assert_eq!(groups_after.len(), 0);
     }
+
+    #[tokio::test]
+    async fn test_group_membership_both_directions() {
+        let store = Arc::new(RwLock::new(InMemoryWamiStore::default()));
+        let service = GroupService::new(store.clone());
+        let context = test_context();
+
+        for name in ["alice", "bob"] {
+            let user = user_builder::build_user(name.to_string(), None, &context).unwrap();
+            store.write().unwrap().create_user(user).await.unwrap();
+        }
+        for name in ["admins", "devs"] {
+            let request = CreateGroupRequest {
+                group_name: name.to_string(),
+                path: None,
+                tags: None,
+            };
+            service.create_group(&context, request).await.unwrap();
+        }
+
+        service.add_user_to_group("admins", "alice").await.unwrap();
+        service.add_user_to_group("devs", "alice").await.unwrap();
+        service.add_user_to_group("devs", "bob").await.unwrap();
+        // Adding an existing member again is a no-op
+        service.add_user_to_group("devs", "bob").await.unwrap();
+
+        let members: Vec<String> = service
+            .list_users_in_group("devs")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|user| user.user_name)
+            .collect();
+        assert_eq!(members, vec!["alice", "bob"]);
+        assert_eq!(service.list_groups_for_user("bob").await.unwrap().len(), 1);
+        assert_eq!(
+            service.list_groups_for_user("alice").await.unwrap().len(),
+            2
+        );
+
+        service
+            .remove_user_from_group("devs", "alice")
+            .await
+            .unwrap();
+        let members = service.list_users_in_group("devs").await.unwrap();
+        assert_eq!(members.len(), 1);
+        assert_eq!(members[0].user_name, "bob");
+        let alice_groups = service.list_groups_for_user("alice").await.unwrap();
+        assert_eq!(alice_groups.len(), 1);
+        assert_eq!(alice_groups[0].group_name, "admins");
+    }
+
+    #[tokio::test]
+    async fn test_group_membership_requires_existing_entities() {
+        let store = Arc::new(RwLock::new(InMemoryWamiStore::default()));
+        let service = GroupService::new(store.clone());
+        let context = test_context();
+
+        let user = user_builder::build_user("alice".to_string(), None, &context).unwrap();
+        store.write().unwrap().create_user(user).await.unwrap();
+        let request = CreateGroupRequest {
+            group_name: "admins".to_string(),
+            path: None,
+            tags: None,
+        };
+        service.create_group(&context, request).await.unwrap();
+
+        let missing_group = service.add_user_to_group("ghosts", "alice").await;
+        assert!(matches!(
+            missing_group,
+            Err(crate::error::AmiError::ResourceNotFound { .. })
+        ));
+        let missing_user = service.add_user_to_group("admins", "nobody").await;
+        assert!(matches!(
+            missing_user,
+            Err(crate::error::AmiError::ResourceNotFound { .. })
+        ));
+        let missing_listing = service.list_users_in_group("ghosts").await;
+        assert!(matches!(
+            missing_listing,
+            Err(crate::error::AmiError::ResourceNotFound { .. })
+        ));
+
+        // Nothing was recorded by the failed adds
+        assert!(service
+            .list_groups_for_user("alice")
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(service
+            .list_users_in_group("admins")
+            .await
+            .unwrap()
+            .is_empty());
+    }
 }
@@ -4,6 +4,7 @@
 
 use crate::context::WamiContext;
 use crate::error::{AmiError, Result};
+use crate::provider::{AwsProvider, CloudProvider};
 use crate::store::traits::IdentityProviderStore;
 use crate::types::Tag;
 use crate::wami::identity::identity_provider::{
@@ -13,6 +14,7 @@ use crate::wami::identity::identity_provider::{
     RemoveClientIDFromOpenIDConnectProviderRequest, SamlProvider,
     UpdateOpenIDConnectProviderThumbprintRequest, UpdateSAMLProviderRequest,
 };
+use crate::wami::tags::operations::tag_operations;
 use std::sync::{Arc, RwLock};
 
 /// Service for managing identity providers (SAML and OIDC)
@@ -20,12 +22,26 @@ use std::sync::{Arc, RwLock};
 /// Provides high-level operations for federated authentication setup.
 pub struct IdentityProviderService<S> {
     store: Arc<RwLock<S>>,
+    provider: Arc<dyn CloudProvider>,
 }
 
 impl<S: IdentityProviderStore> IdentityProviderService<S> {
-    /// Create a new IdentityProviderService
+    /// Create a new IdentityProviderService with default AWS provider
     pub fn new(store: Arc<RwLock<S>>) -> Self {
-        Self { store }
+        Self {
+            store,
+            provider: Arc::new(AwsProvider::new()),
+        }
+    }
+
+    /// Returns a new service instance with different provider
+    ///
+    /// The provider's resource limits govern identity provider tags.
+    pub fn with_provider(&self, provider: Arc<dyn CloudProvider>) -> Self {
+        Self {
+            store: self.store.clone(),
+            provider,
+        }
     }
 
     // ===========================
@@ -33,6 +49,11 @@ impl<S: IdentityProviderStore> IdentityProviderService<S> {
     // ===========================
 
     /// Create a new SAML provider
+    ///
+    /// The metadata document must contain an `EntityDescriptor` with an
+    /// `entityID`. The entity ID and signing certificates are stored on the
+    /// provider, and `AssumeRoleWithSAML` checks assertions are issued by that
+    /// entity.
     pub async fn create_saml_provider(
         &self,
         context: &WamiContext,
@@ -41,8 +62,8 @@ impl<S: IdentityProviderStore> IdentityProviderService<S> {
         // Validate name
         SamlProvider::validate_name(&request.name)?;
 
-        // Validate metadata
-        operations::validate_saml_metadata(&request.saml_metadata_document)?;
+        // Parse metadata for the entity ID and signing certificates
+        let metadata = operations::parse_saml_metadata(&request.saml_metadata_document)?;
 
         // Build SAML provider
         let mut provider = builder::build_saml_provider(
@@ -50,6 +71,7 @@ impl<S: IdentityProviderStore> IdentityProviderService<S> {
             request.saml_metadata_document.clone(),
             context,
         )?;
+        provider = builder::set_saml_metadata_details(provider, metadata);
 
         // Extract validity if present
         if let Ok(Some(valid_until)) =
@@ -60,6 +82,7 @@ impl<S: IdentityProviderStore> IdentityProviderService<S> {
 
         // Add tags
         if let Some(tags) = request.tags {
+            tag_operations::validate_tags_with_limits(&tags, self.provider.resource_limits())?;
             provider = builder::add_saml_tags(provider, tags);
         }
 
@@ -79,8 +102,8 @@ impl<S: IdentityProviderStore> IdentityProviderService<S> {
         &self,
         request: UpdateSAMLProviderRequest,
     ) -> Result<SamlProvider> {
-        // Validate metadata
-        operations::validate_saml_metadata(&request.saml_metadata_document)?;
+        // Parse metadata for the entity ID and signing certificates
+        let metadata = operations::parse_saml_metadata(&request.saml_metadata_document)?;
 
         // Get existing provider
         let existing = {
@@ -95,6 +118,7 @@ impl<S: IdentityProviderStore> IdentityProviderService<S> {
         // Update metadata
         let mut updated =
             builder::update_saml_metadata(existing, request.saml_metadata_document.clone());
+        updated = builder::set_saml_metadata_details(updated, metadata);
 
         // Extract and update validity if present
         if let Ok(Some(valid_until)) =
@@ -139,19 +163,20 @@ impl<S: IdentityProviderStore> IdentityProviderService<S> {
         // Validate client IDs
         operations::validate_client_id_list(&request.client_id_list)?;
 
-        // Validate thumbprints
-        operations::validate_thumbprint_list(&request.thumbprint_list)?;
+        // Validate and deduplicate thumbprints
+        let thumbprint_list = operations::normalize_thumbprint_list(request.thumbprint_list)?;
 
         // Build OIDC provider
         let mut provider = builder::build_oidc_provider(
             request.url,
             request.client_id_list,
-            request.thumbprint_list,
+            thumbprint_list,
             context,
         )?;
 
         // Add tags
         if let Some(tags) = request.tags {
+            tag_operations::validate_tags_with_limits(&tags, self.provider.resource_limits())?;
             provider = builder::add_oidc_tags(provider, tags);
         }
 
@@ -166,29 +191,26 @@ impl<S: IdentityProviderStore> IdentityProviderService<S> {
         store.get_oidc_provider(arn).await
     }
 
-    /// Update an OIDC provider's thumbprints
-    pub async fn update_oidc_thumbprints(
+    /// Replace an OIDC provider's thumbprints
+    ///
+    /// The new list is validated and deduplicated like on creation, then
+    /// replaces the old one as a whole. The provider is read and written under
+    /// a single store lock, so concurrent updates cannot interleave.
+    pub async fn update_open_id_connect_provider_thumbprint(
         &self,
         request: UpdateOpenIDConnectProviderThumbprintRequest,
     ) -> Result<OidcProvider> {
-        // Validate thumbprints
-        operations::validate_thumbprint_list(&request.thumbprint_list)?;
-
-        // Get existing provider
-        let existing = {
-            let store = self.store.read().unwrap();
-            store.get_oidc_provider(&request.arn).await?
-        };
-
-        let existing = existing.ok_or_else(|| AmiError::ResourceNotFound {
-            resource: format!("OidcProvider: {}", request.arn),
-        })?;
+        let thumbprint_list = operations::normalize_thumbprint_list(request.thumbprint_list)?;
 
-        // Update thumbprints
-        let updated = builder::update_thumbprints(existing, request.thumbprint_list);
-
-        // Persist
         let mut store = self.store.write().unwrap();
+        let existing = store
+            .get_oidc_provider(&request.arn)
+            .await?
+            .ok_or_else(|| AmiError::ResourceNotFound {
+                resource: format!("OidcProvider: {}", request.arn),
+            })?;
+
+        let updated = builder::update_thumbprints(existing, thumbprint_list);
         store.update_oidc_provider(updated).await
     }
 
@@ -258,8 +280,28 @@ impl<S: IdentityProviderStore> IdentityProviderService<S> {
     // ===========================
 
     /// Tag an identity provider (SAML or OIDC)
+    ///
+    /// Tags with an existing key replace its value. Returns `InvalidParameter`
+    /// if a tag is malformed or uses a reserved key prefix, and
+    /// `ResourceLimitExceeded` if the provider would end up with more tags
+    /// than the cloud provider's `max_tags_per_resource`.
     pub async fn tag_identity_provider(&self, arn: &str, tags: Vec<Tag>) -> Result<()> {
+        let limits = self.provider.resource_limits();
+        tag_operations::validate_tags_with_limits(&tags, limits)?;
+
         let mut store = self.store.write().unwrap();
+        let existing = store.list_identity_provider_tags(arn).await?;
+        let added = tags
+            .iter()
+            .filter(|tag| !existing.iter().any(|t| t.key == tag.key))
+            .count();
+        if existing.len() + added > limits.max_tags_per_resource {
+            return Err(AmiError::ResourceLimitExceeded {
+                resource_type: format!("Tags on identity provider {}", arn),
+                limit: limits.max_tags_per_resource,
+            });
+        }
+
         store.tag_identity_provider(arn, tags).await
     }
 
@@ -270,6 +312,8 @@ impl<S: IdentityProviderStore> IdentityProviderService<S> {
     }
 
     /// Untag an identity provider
+    ///
+    /// Keys the provider is not tagged with are ignored.
     pub async fn untag_identity_provider(&self, arn: &str, tag_keys: Vec<String>) -> Result<()> {
         let mut store = self.store.write().unwrap();
         store.untag_identity_provider(arn, tag_keys).await
@@ -308,7 +352,7 @@ mod tests {
         let context = test_context();
 
         let metadata = r#"<?xml version="1.0"?>
-            <EntityDescriptor xmlns="urn:oasis:names:tc:SAML:2.0:metadata">
+            <EntityDescriptor xmlns="urn:oasis:names:tc:SAML:2.0:metadata" entityID="https://idp.example.com">
                 <IDPSSODescriptor />
             </EntityDescriptor>"#;
 
@@ -377,7 +421,10 @@ mod tests {
             arn: created.arn.clone(),
             thumbprint_list: vec!["AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA".to_string()],
         };
-        let updated = service.update_oidc_thumbprints(update_req).await.unwrap();
+        let updated = service
+            .update_open_id_connect_provider_thumbprint(update_req)
+            .await
+            .unwrap();
         assert_eq!(updated.thumbprint_list.len(), 1);
 
         // Add client ID
@@ -408,4 +455,253 @@ mod tests {
         let after_delete = service.get_oidc_provider(&created.arn).await.unwrap();
         assert!(after_delete.is_none());
     }
+
+    fn oidc_request(thumbprint_list: Vec<String>) -> CreateOpenIDConnectProviderRequest {
+        CreateOpenIDConnectProviderRequest {
+            url: "https://token.actions.githubusercontent.com".to_string(),
+            client_id_list: vec!["sts.amazonaws.com".to_string()],
+            thumbprint_list,
+            tags: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_oidc_provider_rejects_malformed_thumbprint() {
+        let store = Arc::new(RwLock::new(InMemoryWamiStore::default()));
+        let service = IdentityProviderService::new(store);
+
+        for thumbprint in [
+            "0123456789abcdef",
+            "g123456789abcdef0123456789abcdef01234567",
+        ] {
+            let result = service
+                .create_oidc_provider(&test_context(), oidc_request(vec![thumbprint.to_string()]))
+                .await;
+            assert!(matches!(result, Err(AmiError::InvalidParameter { .. })));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_oidc_thumbprints_are_deduplicated_and_capped() {
+        let store = Arc::new(RwLock::new(InMemoryWamiStore::default()));
+        let service = IdentityProviderService::new(store);
+        let thumbprint = |i: usize| format!("{:040x}", i);
+
+        let too_many: Vec<String> = (0..6).map(thumbprint).collect();
+        let result = service
+            .create_oidc_provider(&test_context(), oidc_request(too_many))
+            .await;
+        assert!(matches!(result, Err(AmiError::InvalidParameter { .. })));
+
+        // Duplicates, including ones differing only in case, count once
+        let mut with_duplicates: Vec<String> = (0..5).map(thumbprint).collect();
+        with_duplicates.push(thumbprint(0));
+        with_duplicates.push("A".repeat(40));
+        with_duplicates[4] = "a".repeat(40);
+        let provider = service
+            .create_oidc_provider(&test_context(), oidc_request(with_duplicates))
+            .await
+            .unwrap();
+        assert_eq!(provider.thumbprint_list.len(), 5);
+        assert_eq!(provider.thumbprint_list[4], "a".repeat(40));
+    }
+
+    #[tokio::test]
+    async fn test_update_oidc_thumbprints_replaces_list() {
+        let store = Arc::new(RwLock::new(InMemoryWamiStore::default()));
+        let service = IdentityProviderService::new(store);
+        let created = service
+            .create_oidc_provider(
+                &test_context(),
+                oidc_request(vec!["1".repeat(40), "2".repeat(40)]),
+            )
+            .await
+            .unwrap();
+
+        let rejected = service
+            .update_open_id_connect_provider_thumbprint(
+                UpdateOpenIDConnectProviderThumbprintRequest {
+                    arn: created.arn.clone(),
+                    thumbprint_list: vec!["3".repeat(40), "not-a-thumbprint".to_string()],
+                },
+            )
+            .await;
+        assert!(matches!(rejected, Err(AmiError::InvalidParameter { .. })));
+        let unchanged = service
+            .get_oidc_provider(&created.arn)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(unchanged.thumbprint_list, ["1".repeat(40), "2".repeat(40)]);
+
+        let updated = service
+            .update_open_id_connect_provider_thumbprint(
+                UpdateOpenIDConnectProviderThumbprintRequest {
+                    arn: created.arn.clone(),
+                    thumbprint_list: vec!["3".repeat(40), "3".repeat(40)],
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(updated.thumbprint_list, ["3".repeat(40)]);
+        let stored = service
+            .get_oidc_provider(&created.arn)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.thumbprint_list, ["3".repeat(40)]);
+    }
+
+    #[tokio::test]
+    async fn test_create_saml_provider_parses_metadata() {
+        let store = Arc::new(RwLock::new(InMemoryWamiStore::default()));
+        let service = IdentityProviderService::new(store);
+
+        let metadata = r#"<?xml version="1.0"?>
+            <md:EntityDescriptor xmlns:md="urn:oasis:names:tc:SAML:2.0:metadata"
+                                 xmlns:ds="http://www.w3.org/2000/09/xmldsig#"
+                                 entityID="http://www.okta.com/exk1abcd">
+                <md:IDPSSODescriptor protocolSupportEnumeration="urn:oasis:names:tc:SAML:2.0:protocol">
+                    <md:KeyDescriptor use="signing">
+                        <ds:KeyInfo><ds:X509Data>
+                            <ds:X509Certificate>MIIDpDCCAoygAwIBAgIGAWk</ds:X509Certificate>
+                        </ds:X509Data></ds:KeyInfo>
+                    </md:KeyDescriptor>
+                </md:IDPSSODescriptor>
+            </md:EntityDescriptor>"#;
+        let created = service
+            .create_saml_provider(
+                &test_context(),
+                CreateSAMLProviderRequest {
+                    name: "Okta".to_string(),
+                    saml_metadata_document: metadata.to_string(),
+                    tags: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            created.entity_id.as_deref(),
+            Some("http://www.okta.com/exk1abcd")
+        );
+        assert_eq!(created.signing_certificates, ["MIIDpDCCAoygAwIBAgIGAWk"]);
+        let stored = service
+            .get_saml_provider(&created.arn)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.entity_id, created.entity_id);
+    }
+
+    #[tokio::test]
+    async fn test_create_saml_provider_rejects_invalid_metadata() {
+        let store = Arc::new(RwLock::new(InMemoryWamiStore::default()));
+        let service = IdentityProviderService::new(store);
+
+        for metadata in [
+            "<EntityDescriptor entityID=\"https://idp.example.com\">",
+            "<EntitiesDescriptor><Extensions/></EntitiesDescriptor>",
+            "<EntityDescriptor><IDPSSODescriptor/></EntityDescriptor>",
+        ] {
+            let result = service
+                .create_saml_provider(
+                    &test_context(),
+                    CreateSAMLProviderRequest {
+                        name: "Broken".to_string(),
+                        saml_metadata_document: metadata.to_string(),
+                        tags: None,
+                    },
+                )
+                .await;
+            assert!(
+                matches!(result, Err(AmiError::InvalidParameter { .. })),
+                "{} should be rejected",
+                metadata
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_oidc_provider_tagging() {
+        use crate::provider::ResourceLimits;
+
+        let limits = ResourceLimits {
+            max_tags_per_resource: 2,
+            ..Default::default()
+        };
+        let store = Arc::new(RwLock::new(InMemoryWamiStore::default()));
+        let service = IdentityProviderService::new(store)
+            .with_provider(Arc::new(AwsProvider::with_limits(limits)));
+        let created = service
+            .create_oidc_provider(&test_context(), oidc_request(vec!["1".repeat(40)]))
+            .await
+            .unwrap();
+        let tag = |key: &str, value: &str| Tag {
+            key: key.to_string(),
+            value: value.to_string(),
+        };
+
+        service
+            .tag_identity_provider(
+                &created.arn,
+                vec![tag("team", "platform"), tag("env", "dev")],
+            )
+            .await
+            .unwrap();
+        // Overwriting an existing key does not count towards the limit
+        service
+            .tag_identity_provider(&created.arn, vec![tag("env", "prod")])
+            .await
+            .unwrap();
+        let result = service
+            .tag_identity_provider(&created.arn, vec![tag("owner", "alice")])
+            .await;
+        assert!(matches!(
+            result,
+            Err(AmiError::ResourceLimitExceeded { limit: 2, .. })
+        ));
+
+        let mut tags: Vec<(String, String)> = service
+            .list_identity_provider_tags(&created.arn)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|t| (t.key, t.value))
+            .collect();
+        tags.sort();
+        assert_eq!(
+            tags,
+            [
+                ("env".to_string(), "prod".to_string()),
+                ("team".to_string(), "platform".to_string())
+            ]
+        );
+
+        // Unknown keys are ignored
+        service
+            .untag_identity_provider(
+                &created.arn,
+                vec!["team".to_string(), "missing".to_string()],
+            )
+            .await
+            .unwrap();
+        let tags = service
+            .list_identity_provider_tags(&created.arn)
+            .await
+            .unwrap();
+        assert_eq!(tags.len(), 1);
+        assert_eq!(
+            (tags[0].key.as_str(), tags[0].value.as_str()),
+            ("env", "prod")
+        );
+
+        let result = service
+            .tag_identity_provider(
+                "arn:aws:iam::123456789012:oidc-provider/unknown",
+                vec![tag("a", "b")],
+            )
+            .await;
+        assert!(matches!(result, Err(AmiError::ResourceNotFound { .. })));
+    }
 }
@@ -4,6 +4,7 @@
 
 pub mod group;
 pub mod identity_provider;
+pub mod precheck;
 pub mod role;
 pub mod service_linked_role;
 pub mod user;
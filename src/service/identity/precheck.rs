@@ -0,0 +1,29 @@
+//! Resource-Exists Prechecks
+//!
+//! Shared collision handling for identity create operations.
+//!
+//! A create can collide in two ways:
+//! - on the friendly name chosen by the caller, which is reported as
+//!   [`AmiError::ResourceExists`];
+//! - on the generated ID or WAMI ARN, which is an internal accident and is
+//!   resolved by generating a new ID, up to [`MAX_ID_ATTEMPTS`] times.
+
+use crate::error::AmiError;
+
+/// Number of IDs generated for a create before giving up on ID collisions
+pub const MAX_ID_ATTEMPTS: usize = 5;
+
+/// Error returned when a resource with the same friendly name already exists
+pub(crate) fn name_collision(resource_type: &str, name: &str) -> AmiError {
+    AmiError::ResourceExists {
+        resource: format!("{}: {}", resource_type, name),
+    }
+}
+
+/// Error returned when every generated ID collided with an existing resource
+pub(crate) fn id_collision(resource_type: &str, name: &str) -> AmiError {
+    AmiError::StoreError(format!(
+        "Could not generate a unique ID for {} {} after {} attempts",
+        resource_type, name, MAX_ID_ATTEMPTS
+    ))
+}
@@ -2,12 +2,17 @@
 //!
 //! Orchestrates role management operations.
 
+use super::precheck::{id_collision, name_collision, MAX_ID_ATTEMPTS};
 use crate::context::WamiContext;
 use crate::error::Result;
+use crate::service::audit::{self, AuditSink};
+use crate::store::memory::pagination::paginate;
 use crate::store::traits::RoleStore;
+use crate::telemetry;
 use crate::wami::identity::role::{
     builder as role_builder, CreateRoleRequest, ListRolesRequest, Role, UpdateRoleRequest,
 };
+use crate::wami::tags::operations::tag_operations;
 use std::sync::{Arc, RwLock};
 
 /// Service for managing IAM roles
@@ -15,53 +20,146 @@ use std::sync::{Arc, RwLock};
 /// Provides high-level operations for role management.
 pub struct RoleService<S> {
     store: Arc<RwLock<S>>,
+    audit_sink: Option<Arc<dyn AuditSink>>,
 }
 
 impl<S: RoleStore> RoleService<S> {
     /// Create a new RoleService
     pub fn new(store: Arc<RwLock<S>>) -> Self {
-        Self { store }
+        Self {
+            store,
+            audit_sink: None,
+        }
+    }
+
+    /// Returns a new service instance reporting role creation, update and deletion to `sink`
+    pub fn with_audit_sink(&self, sink: Arc<dyn AuditSink>) -> Self {
+        Self {
+            store: self.store.clone(),
+            audit_sink: Some(sink),
+        }
     }
 
     /// Create a new role
+    ///
+    /// Returns `ResourceExists` if a role with the same name already exists.
+    /// A generated ID that collides with an existing role is regenerated.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                operation = "CreateRole",
+                tenant = %context.tenant_path(),
+                resource = %request.role_name,
+            )
+        )
+    )]
     pub async fn create_role(
         &self,
         context: &WamiContext,
         request: CreateRoleRequest,
     ) -> Result<Role> {
-        // Use wami builder to create role with context
-        let mut role = role_builder::build_role(
-            request.role_name,
-            request.assume_role_policy_document,
-            request.path,
-            request.description,
-            request.max_session_duration,
-            context,
-        )?;
-
-        // Apply permissions boundary if specified
-        if let Some(boundary_arn) = request.permissions_boundary {
-            role = role_builder::set_permissions_boundary(role, boundary_arn);
+        let role_name = request.role_name.clone();
+        let result = self.store_new_role(context, request).await;
+        audit::record_result(
+            self.audit_sink.as_ref(),
+            "CreateRole",
+            Some(context),
+            result
+                .as_ref()
+                .map_or(role_name, |role| role.wami_arn.to_string()),
+            &result,
+        );
+        result
+    }
+
+    async fn store_new_role(
+        &self,
+        context: &WamiContext,
+        request: CreateRoleRequest,
+    ) -> Result<Role> {
+        if let Some(tags) = &request.tags {
+            tag_operations::validate_tags(tags)?;
         }
 
-        // Apply tags if specified
-        let role = if let Some(tags) = request.tags {
-            role_builder::add_tags(role, tags)
-        } else {
-            role
-        };
+        let mut store = self.store.write().unwrap();
 
-        // Store it
-        self.store.write().unwrap().create_role(role).await
+        if store.get_role(&request.role_name).await?.is_some() {
+            return Err(name_collision("Role", &request.role_name));
+        }
+
+        let (existing, _, _) = store.list_roles(None, None).await?;
+        for _ in 0..MAX_ID_ATTEMPTS {
+            // Use wami builder to create role with context
+            let mut role = role_builder::build_role(
+                request.role_name.clone(),
+                request.assume_role_policy_document.clone(),
+                request.path.clone(),
+                request.description.clone(),
+                request.max_session_duration,
+                context,
+            )?;
+
+            // Apply permissions boundary if specified
+            if let Some(boundary_arn) = &request.permissions_boundary {
+                role = role_builder::set_permissions_boundary(role, boundary_arn.clone());
+            }
+
+            // Apply tags if specified
+            if let Some(tags) = &request.tags {
+                role = role_builder::add_tags(role, tags.clone());
+            }
+
+            let taken = existing
+                .iter()
+                .any(|r| r.role_id == role.role_id || r.wami_arn == role.wami_arn);
+            if !taken {
+                let role = store.create_role(role).await?;
+                telemetry::record_created("role", &role.role_name);
+                return Ok(role);
+            }
+        }
+
+        Err(id_collision("role", &request.role_name))
     }
 
     /// Get a role by name
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(operation = "GetRole", resource = %role_name))
+    )]
     pub async fn get_role(&self, role_name: &str) -> Result<Option<Role>> {
         self.store.read().unwrap().get_role(role_name).await
     }
 
     /// Update a role
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                operation = "UpdateRole",
+                resource = %request.role_name,
+            )
+        )
+    )]
     pub async fn update_role(&self, request: UpdateRoleRequest) -> Result<Role> {
+        let role_name = request.role_name.clone();
+        let result = self.apply_role_update(request).await;
+        audit::record_result(
+            self.audit_sink.as_ref(),
+            "UpdateRole",
+            None,
+            result
+                .as_ref()
+                .map_or(role_name, |role| role.wami_arn.to_string()),
+            &result,
+        );
+        result
+    }
+
+    async fn apply_role_update(&self, request: UpdateRoleRequest) -> Result<Role> {
         // Get existing role
         let mut role = self
             .store
@@ -87,20 +185,53 @@ impl<S: RoleStore> RoleService<S> {
     }
 
     /// Delete a role
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(operation = "DeleteRole", resource = %role_name))
+    )]
     pub async fn delete_role(&self, role_name: &str) -> Result<()> {
-        self.store.write().unwrap().delete_role(role_name).await
+        let mut store = self.store.write().unwrap();
+        let existing = store.get_role(role_name).await?;
+        let result = store.delete_role(role_name).await;
+        audit::record_result(
+            self.audit_sink.as_ref(),
+            "DeleteRole",
+            None,
+            existing.map_or_else(|| role_name.to_string(), |role| role.wami_arn.to_string()),
+            &result,
+        );
+        result
     }
 
     /// List roles with optional filtering
+    ///
+    /// Only roles of the caller's tenant are returned, plus those of its
+    /// descendant tenants when `include_descendants` is set. Root callers see
+    /// roles of every tenant.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(operation = "ListRoles", tenant = %context.tenant_path())
+        )
+    )]
     pub async fn list_roles(
         &self,
+        context: &WamiContext,
         request: ListRolesRequest,
     ) -> Result<(Vec<Role>, bool, Option<String>)> {
-        self.store
+        let (mut roles, _, _) = self
+            .store
             .read()
             .unwrap()
-            .list_roles(request.path_prefix.as_deref(), request.pagination.as_ref())
-            .await
+            .list_roles(request.path_prefix.as_deref(), None)
+            .await?;
+        roles.retain(|item| {
+            context.lists_tenant(&item.wami_arn.tenant_path, request.include_descendants)
+        });
+        Ok(paginate(roles, request.pagination.as_ref(), |item| {
+            &item.role_name
+        }))
     }
 }
 
@@ -228,11 +359,49 @@ mod tests {
         let list_request = ListRolesRequest {
             path_prefix: Some("/test/".to_string()),
             pagination: None,
+            include_descendants: false,
         };
-        let (roles, _, _) = service.list_roles(list_request).await.unwrap();
+        let (roles, _, _) = service
+            .list_roles(&test_context(), list_request)
+            .await
+            .unwrap();
         assert_eq!(roles.len(), 3);
     }
 
+    #[tokio::test]
+    async fn test_list_roles_by_path_prefix() {
+        let service = setup_service();
+        let context = test_context();
+        for (name, path) in [("deployer", "/eng/"), ("pager", "/ops/")] {
+            let request = CreateRoleRequest {
+                role_name: name.to_string(),
+                assume_role_policy_document: r#"{"Version":"2012-10-17","Statement":[]}"#
+                    .to_string(),
+                path: Some(path.to_string()),
+                description: None,
+                max_session_duration: None,
+                permissions_boundary: None,
+                tags: None,
+            };
+            service.create_role(&context, request).await.unwrap();
+        }
+
+        for (path_prefix, expected) in [
+            (Some("/eng/"), vec!["deployer"]),
+            (Some("/ops/"), vec!["pager"]),
+            (None, vec!["deployer", "pager"]),
+        ] {
+            let request = ListRolesRequest {
+                path_prefix: path_prefix.map(str::to_string),
+                pagination: None,
+                include_descendants: false,
+            };
+            let (roles, _, _) = service.list_roles(&context, request).await.unwrap();
+            let names: Vec<_> = roles.iter().map(|r| r.role_name.as_str()).collect();
+            assert_eq!(names, expected, "{:?}", path_prefix);
+        }
+    }
+
     #[tokio::test]
     async fn test_create_role_with_tags() {
         let service = setup_service();
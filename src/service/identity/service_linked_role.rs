@@ -3,15 +3,22 @@
 //! Orchestrates service-linked role management operations.
 
 use crate::context::WamiContext;
-use crate::error::Result;
+use crate::error::{AmiError, Result};
 use crate::store::traits::{RoleStore, ServiceLinkedRoleStore};
 use crate::wami::identity::role::builder as role_builder;
 use crate::wami::identity::service_linked_role::{
-    operations as slr_ops, CreateServiceLinkedRoleRequest, DeletionTaskInfo,
+    builder as slr_builder, operations as slr_ops, CreateServiceLinkedRoleRequest,
+    DeleteServiceLinkedRoleRequest, DeleteServiceLinkedRoleResponse, DeletionTaskInfo,
+    DeletionTaskStatus, GetServiceLinkedRoleDeletionStatusRequest,
+    GetServiceLinkedRoleDeletionStatusResponse, RoleUsageType,
 };
 use crate::wami::identity::Role;
+use chrono::{DateTime, Utc};
 use std::sync::{Arc, RwLock};
 
+/// Path prefix under which service-linked roles are created
+const SERVICE_ROLE_PATH_PREFIX: &str = "/aws-service-role/";
+
 /// Service for managing service-linked roles
 ///
 /// Service-linked roles are predefined AWS roles that are linked to specific AWS services.
@@ -46,7 +53,7 @@ impl<S: RoleStore + ServiceLinkedRoleStore> ServiceLinkedRoleService<S> {
         );
 
         // Service-linked roles use a fixed path
-        let path = "/aws-service-role/".to_string() + &request.aws_service_name + "/";
+        let path = SERVICE_ROLE_PATH_PREFIX.to_string() + &request.aws_service_name + "/";
 
         // Build assume role policy document for service-linked role
         let assume_role_policy = format!(
@@ -68,6 +75,100 @@ impl<S: RoleStore + ServiceLinkedRoleStore> ServiceLinkedRoleService<S> {
         self.store.write().unwrap().create_role(role).await
     }
 
+    /// Start deleting a service-linked role
+    ///
+    /// The role is not removed right away: a deletion task is enqueued as
+    /// `InProgress` and carried out by [`advance_deletion_tasks`](Self::advance_deletion_tasks).
+    pub async fn delete_service_linked_role(
+        &self,
+        request: DeleteServiceLinkedRoleRequest,
+    ) -> Result<DeleteServiceLinkedRoleResponse> {
+        let mut store = self.store.write().unwrap();
+
+        let role = store.get_role(&request.role_name).await?.ok_or_else(|| {
+            AmiError::ResourceNotFound {
+                resource: format!("Role: {}", request.role_name),
+            }
+        })?;
+        if !role.path.starts_with(SERVICE_ROLE_PATH_PREFIX) {
+            return Err(AmiError::InvalidParameter {
+                message: format!("Role {} is not a service-linked role", request.role_name),
+            });
+        }
+
+        let task = slr_builder::build_deletion_task(request.role_name);
+        let deletion_task_id = task.deletion_task_id.clone();
+        store.create_service_linked_role_deletion_task(task).await?;
+
+        Ok(DeleteServiceLinkedRoleResponse { deletion_task_id })
+    }
+
+    /// Carry out the deletion tasks enqueued up to `now`
+    ///
+    /// A task succeeds, deleting its role, once the role has no managed or inline
+    /// policies left. Otherwise it fails, listing the policies still attached.
+    /// Returns the tasks that changed state.
+    pub async fn advance_deletion_tasks(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<DeletionTaskInfo>> {
+        let mut store = self.store.write().unwrap();
+
+        let mut advanced = Vec::new();
+        for task in store.list_service_linked_role_deletion_tasks().await? {
+            if task.status != DeletionTaskStatus::InProgress || task.create_date > now {
+                continue;
+            }
+
+            let task = if store.get_role(&task.role_name).await?.is_none() {
+                // Deleted by an earlier task for the same role
+                slr_builder::succeed_deletion_task(task)
+            } else {
+                let mut resources = store.list_attached_role_policies(&task.role_name).await?;
+                resources.extend(store.list_role_policies(&task.role_name).await?);
+                if resources.is_empty() {
+                    store.delete_role(&task.role_name).await?;
+                    slr_builder::succeed_deletion_task(task)
+                } else {
+                    let reason = format!("Role {} still has policies attached", task.role_name);
+                    slr_builder::fail_deletion_task(
+                        task,
+                        reason,
+                        vec![RoleUsageType {
+                            region: None,
+                            resources,
+                        }],
+                    )
+                }
+            };
+
+            store
+                .update_service_linked_role_deletion_task(task.clone())
+                .await?;
+            advanced.push(task);
+        }
+
+        Ok(advanced)
+    }
+
+    /// Get the current status of a service-linked role deletion task
+    pub async fn get_service_linked_role_deletion_status(
+        &self,
+        request: GetServiceLinkedRoleDeletionStatusRequest,
+    ) -> Result<GetServiceLinkedRoleDeletionStatusResponse> {
+        let task = self
+            .get_service_linked_role_deletion_task(&request.deletion_task_id)
+            .await?
+            .ok_or_else(|| AmiError::ResourceNotFound {
+                resource: format!("DeletionTask: {}", request.deletion_task_id),
+            })?;
+
+        Ok(GetServiceLinkedRoleDeletionStatusResponse {
+            status: task.status.clone(),
+            deletion_task_info: task,
+        })
+    }
+
     /// Get the status of a service-linked role deletion task
     pub async fn get_service_linked_role_deletion_task(
         &self,
@@ -184,4 +285,135 @@ mod tests {
             .unwrap();
         assert!(task.is_none());
     }
+
+    async fn create_elastic_beanstalk_role(
+        service: &ServiceLinkedRoleService<InMemoryWamiStore>,
+    ) -> Role {
+        let request = CreateServiceLinkedRoleRequest {
+            aws_service_name: "elasticbeanstalk.amazonaws.com".to_string(),
+            description: None,
+            custom_suffix: None,
+        };
+        service
+            .create_service_linked_role(&test_context(), request)
+            .await
+            .unwrap()
+    }
+
+    async fn deletion_status(
+        service: &ServiceLinkedRoleService<InMemoryWamiStore>,
+        deletion_task_id: &str,
+    ) -> GetServiceLinkedRoleDeletionStatusResponse {
+        service
+            .get_service_linked_role_deletion_status(GetServiceLinkedRoleDeletionStatusRequest {
+                deletion_task_id: deletion_task_id.to_string(),
+            })
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_deletion_task_succeeds_and_removes_role() {
+        let service = setup_service();
+        let role = create_elastic_beanstalk_role(&service).await;
+
+        let response = service
+            .delete_service_linked_role(DeleteServiceLinkedRoleRequest {
+                role_name: role.role_name.clone(),
+            })
+            .await
+            .unwrap();
+        let status = deletion_status(&service, &response.deletion_task_id).await;
+        assert_eq!(status.status, DeletionTaskStatus::InProgress);
+
+        // Tasks enqueued after `now` are left alone
+        let before = status.deletion_task_info.create_date - chrono::Duration::seconds(1);
+        assert!(service
+            .advance_deletion_tasks(before)
+            .await
+            .unwrap()
+            .is_empty());
+
+        let advanced = service.advance_deletion_tasks(Utc::now()).await.unwrap();
+        assert_eq!(advanced.len(), 1);
+        let status = deletion_status(&service, &response.deletion_task_id).await;
+        assert_eq!(status.status, DeletionTaskStatus::Succeeded);
+        assert!(status.deletion_task_info.failure_reason.is_none());
+        assert!(service
+            .store
+            .read()
+            .unwrap()
+            .get_role(&role.role_name)
+            .await
+            .unwrap()
+            .is_none());
+
+        // Finished tasks are not advanced again
+        assert!(service
+            .advance_deletion_tasks(Utc::now())
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_deletion_task_fails_while_policies_attached() {
+        let service = setup_service();
+        let role = create_elastic_beanstalk_role(&service).await;
+        let policy_arn = "arn:aws:iam::aws:policy/aws-service-role/AWSElasticBeanstalkRolePolicy";
+        service
+            .store
+            .write()
+            .unwrap()
+            .attach_role_policy(&role.role_name, policy_arn)
+            .await
+            .unwrap();
+
+        let response = service
+            .delete_service_linked_role(DeleteServiceLinkedRoleRequest {
+                role_name: role.role_name.clone(),
+            })
+            .await
+            .unwrap();
+        service.advance_deletion_tasks(Utc::now()).await.unwrap();
+
+        let status = deletion_status(&service, &response.deletion_task_id).await;
+        assert_eq!(status.status, DeletionTaskStatus::Failed);
+        let reason = status.deletion_task_info.failure_reason.unwrap();
+        assert_eq!(reason.role_usage_list.len(), 1);
+        assert_eq!(reason.role_usage_list[0].resources, vec![policy_arn]);
+        assert!(service
+            .store
+            .read()
+            .unwrap()
+            .get_role(&role.role_name)
+            .await
+            .unwrap()
+            .is_some());
+
+        // Only service-linked roles can be deleted this way
+        let context = test_context();
+        let plain = role_builder::build_role(
+            "PlainRole".to_string(),
+            r#"{"Version":"2012-10-17","Statement":[]}"#.to_string(),
+            None,
+            None,
+            None,
+            &context,
+        )
+        .unwrap();
+        service
+            .store
+            .write()
+            .unwrap()
+            .create_role(plain)
+            .await
+            .unwrap();
+        let result = service
+            .delete_service_linked_role(DeleteServiceLinkedRoleRequest {
+                role_name: "PlainRole".to_string(),
+            })
+            .await;
+        assert!(matches!(result, Err(AmiError::InvalidParameter { .. })));
+    }
 }
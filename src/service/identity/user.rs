@@ -2,13 +2,23 @@
 //!
 //! Orchestrates user management operations by combining wami builders with store persistence.
 
+use super::precheck::{id_collision, name_collision, MAX_ID_ATTEMPTS};
 use crate::context::WamiContext;
-use crate::error::Result;
-use crate::store::traits::UserStore;
+use crate::error::{AmiError, Result};
+use crate::provider::{AwsProvider, CloudProvider, ProviderConfig, ResourceType};
+use crate::service::audit::{self, AuditSink};
+use crate::service::quota::{QuotaObserver, QuotaWarning};
+use crate::store::memory::pagination::paginate;
+use crate::store::traits::{TenantStore, UserStore};
+use crate::telemetry;
 use crate::types::Tag;
 use crate::wami::identity::user::{
-    builder as user_builder, CreateUserRequest, ListUsersRequest, UpdateUserRequest, User,
+    builder as user_builder, BulkFailure, BulkResult, CreateUserRequest, ListUsersRequest,
+    UpdateUserRequest, User,
 };
+use crate::wami::tags::operations::tag_operations;
+use crate::wami::tenant::TenantId;
+use chrono::{DateTime, Utc};
 use std::sync::{Arc, RwLock};
 
 /// Service for managing IAM users
@@ -16,46 +26,238 @@ use std::sync::{Arc, RwLock};
 /// Provides high-level operations that combine wami pure functions with store persistence.
 pub struct UserService<S> {
     store: Arc<RwLock<S>>,
+    provider: Arc<dyn CloudProvider>,
+    audit_sink: Option<Arc<dyn AuditSink>>,
+    quota_warning: Option<QuotaWarning>,
 }
 
 impl<S: UserStore> UserService<S> {
-    /// Create a new UserService
+    /// Create a new UserService with default AWS provider
     pub fn new(store: Arc<RwLock<S>>) -> Self {
-        Self { store }
+        Self {
+            store,
+            provider: Arc::new(AwsProvider::new()),
+            audit_sink: None,
+            quota_warning: None,
+        }
+    }
+
+    /// Returns a new service instance with different provider
+    pub fn with_provider(&self, provider: Arc<dyn CloudProvider>) -> Self {
+        Self {
+            store: self.store.clone(),
+            provider,
+            audit_sink: self.audit_sink.clone(),
+            quota_warning: self.quota_warning.clone(),
+        }
+    }
+
+    /// Returns a new service instance reporting user creation, update and deletion to `sink`
+    pub fn with_audit_sink(&self, sink: Arc<dyn AuditSink>) -> Self {
+        Self {
+            store: self.store.clone(),
+            provider: self.provider.clone(),
+            audit_sink: Some(sink),
+            quota_warning: self.quota_warning.clone(),
+        }
+    }
+
+    /// Returns a new service instance notifying `observer` when a tenant's
+    /// user count reaches `threshold` (a fraction, such as `0.8`) of its quota
+    ///
+    /// Only operations that enforce tenant quotas, such as
+    /// [`create_users_bulk`](Self::create_users_bulk), report to the observer.
+    pub fn with_quota_observer(&self, observer: Arc<dyn QuotaObserver>, threshold: f64) -> Self {
+        Self {
+            store: self.store.clone(),
+            provider: self.provider.clone(),
+            audit_sink: self.audit_sink.clone(),
+            quota_warning: Some(QuotaWarning::new(observer, threshold)),
+        }
     }
 
     /// Create a new user
+    ///
+    /// Returns `ResourceExists` if a user with the same name already exists,
+    /// and `InvalidParameter` if the path is not valid for the provider.
+    /// A generated ID that collides with an existing user is regenerated.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                operation = "CreateUser",
+                tenant = %context.tenant_path(),
+                resource = %request.user_name,
+            )
+        )
+    )]
     pub async fn create_user(
         &self,
         context: &WamiContext,
         request: CreateUserRequest,
     ) -> Result<User> {
-        // Use wami builder to create user with context
-        let mut user = user_builder::build_user(request.user_name, request.path, context)?;
+        self.create_expiring_user(context, request, None).await
+    }
+
+    /// Create a temporary user that expires at `expires_at`
+    ///
+    /// Once expired, the user is no longer returned by [`get_user`](Self::get_user)
+    /// nor accepted by authentication, and is removed by
+    /// [`prune_expired_users`](Self::prune_expired_users).
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                operation = "CreateUser",
+                tenant = %context.tenant_path(),
+                resource = %request.user_name,
+            )
+        )
+    )]
+    pub async fn create_temporary_user(
+        &self,
+        context: &WamiContext,
+        request: CreateUserRequest,
+        expires_at: DateTime<Utc>,
+    ) -> Result<User> {
+        self.create_expiring_user(context, request, Some(expires_at))
+            .await
+    }
+
+    async fn create_expiring_user(
+        &self,
+        context: &WamiContext,
+        request: CreateUserRequest,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<User> {
+        let user_name = request.user_name.clone();
+        let result = self
+            .build_and_store_user(context, request, expires_at)
+            .await;
+        audit::record_result(
+            self.audit_sink.as_ref(),
+            "CreateUser",
+            Some(context),
+            result
+                .as_ref()
+                .map_or(user_name, |user| user.wami_arn.to_string()),
+            &result,
+        );
+        result
+    }
+
+    async fn build_and_store_user(
+        &self,
+        context: &WamiContext,
+        request: CreateUserRequest,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<User> {
+        let CreateUserRequest {
+            user_name,
+            path,
+            permissions_boundary,
+            tags,
+        } = request;
 
-        // Apply permissions boundary if specified
-        if let Some(boundary_arn) = request.permissions_boundary {
-            user = user_builder::set_permissions_boundary(user, boundary_arn);
+        if let Some(path) = &path {
+            self.provider.validate_path(path)?;
+        }
+        if let Some(tags) = &tags {
+            tag_operations::validate_tags(tags)?;
         }
 
-        // Apply tags if specified
-        let user = if let Some(tags) = request.tags {
-            user_builder::add_tags(user, tags)
-        } else {
-            user
-        };
+        self.create_user_with(&user_name, || {
+            // Use wami builder to create user with context
+            let mut user = user_builder::build_user(user_name.clone(), path.clone(), context)?;
+
+            // Apply permissions boundary if specified
+            if let Some(boundary_arn) = &permissions_boundary {
+                user = user_builder::set_permissions_boundary(user, boundary_arn.clone());
+            }
+
+            // Apply tags if specified
+            if let Some(tags) = &tags {
+                user = user_builder::add_tags(user, tags.clone());
+            }
+
+            if let Some(expires_at) = expires_at {
+                user = user_builder::set_expiration(user, expires_at);
+            }
+
+            Ok(user)
+        })
+        .await
+    }
+
+    /// Store a user built by `build`, rebuilding it while its ID or ARN is taken
+    async fn create_user_with(
+        &self,
+        user_name: &str,
+        mut build: impl FnMut() -> Result<User>,
+    ) -> Result<User> {
+        let mut store = self.store.write().unwrap();
+
+        if store.get_user(user_name).await?.is_some() {
+            return Err(name_collision("User", user_name));
+        }
+
+        let (existing, _, _) = store.list_users(None, None).await?;
+        for _ in 0..MAX_ID_ATTEMPTS {
+            let user = build()?;
+            let taken = existing
+                .iter()
+                .any(|u| u.user_id == user.user_id || u.wami_arn == user.wami_arn);
+            if !taken {
+                let user = store.create_user(user).await?;
+                telemetry::record_created("user", &user.user_name);
+                return Ok(user);
+            }
+        }
 
-        // Store it
-        self.store.write().unwrap().create_user(user).await
+        Err(id_collision("user", user_name))
     }
 
     /// Get a user by name
+    ///
+    /// Expired users are treated as not found, even before they are pruned.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(operation = "GetUser", resource = %user_name))
+    )]
     pub async fn get_user(&self, user_name: &str) -> Result<Option<User>> {
-        self.store.read().unwrap().get_user(user_name).await
+        let user = self.store.read().unwrap().get_user(user_name).await?;
+        Ok(user.filter(|u| !u.is_expired_at(Utc::now())))
     }
 
     /// Update a user
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                operation = "UpdateUser",
+                resource = %request.user_name,
+            )
+        )
+    )]
     pub async fn update_user(&self, request: UpdateUserRequest) -> Result<User> {
+        let user_name = request.user_name.clone();
+        let result = self.apply_user_update(request).await;
+        audit::record_result(
+            self.audit_sink.as_ref(),
+            "UpdateUser",
+            None,
+            result
+                .as_ref()
+                .map_or(user_name, |user| user.wami_arn.to_string()),
+            &result,
+        );
+        result
+    }
+
+    async fn apply_user_update(&self, request: UpdateUserRequest) -> Result<User> {
         // Get existing user
         let mut user = self
             .store
@@ -81,33 +283,108 @@ impl<S: UserStore> UserService<S> {
     }
 
     /// Delete a user
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(operation = "DeleteUser", resource = %user_name))
+    )]
     pub async fn delete_user(&self, user_name: &str) -> Result<()> {
-        self.store.write().unwrap().delete_user(user_name).await
+        let mut store = self.store.write().unwrap();
+        let existing = store.get_user(user_name).await?;
+        let result = store.delete_user(user_name).await;
+        audit::record_result(
+            self.audit_sink.as_ref(),
+            "DeleteUser",
+            None,
+            existing.map_or_else(|| user_name.to_string(), |user| user.wami_arn.to_string()),
+            &result,
+        );
+        result
+    }
+
+    /// Delete all users that expired at or before `now`
+    ///
+    /// Returns the number of users removed.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(operation = "PruneExpiredUsers"))
+    )]
+    pub async fn prune_expired_users(&self, now: DateTime<Utc>) -> Result<usize> {
+        let mut store = self.store.write().unwrap();
+        let (users, _, _) = store.list_users(None, None).await?;
+
+        let mut pruned = 0;
+        for user in users.iter().filter(|u| u.is_expired_at(now)) {
+            let result = store.delete_user(&user.user_name).await;
+            audit::record_result(
+                self.audit_sink.as_ref(),
+                "DeleteUser",
+                None,
+                user.wami_arn.to_string(),
+                &result,
+            );
+            result?;
+            pruned += 1;
+        }
+        Ok(pruned)
     }
 
     /// List users with optional filtering
+    ///
+    /// Only users of the caller's tenant are returned, plus those of its
+    /// descendant tenants when `include_descendants` is set. Root callers see
+    /// users of every tenant.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(operation = "ListUsers", tenant = %context.tenant_path())
+        )
+    )]
     pub async fn list_users(
         &self,
+        context: &WamiContext,
         request: ListUsersRequest,
     ) -> Result<(Vec<User>, bool, Option<String>)> {
-        self.store
+        let (mut users, _, _) = self
+            .store
             .read()
             .unwrap()
-            .list_users(request.path_prefix.as_deref(), request.pagination.as_ref())
-            .await
+            .list_users(request.path_prefix.as_deref(), None)
+            .await?;
+        users.retain(|item| {
+            context.lists_tenant(&item.wami_arn.tenant_path, request.include_descendants)
+        });
+        Ok(paginate(users, request.pagination.as_ref(), |item| {
+            &item.user_name
+        }))
     }
 
     /// Tag a user
+    ///
+    /// Returns `InvalidParameter` if a tag is malformed or uses a reserved key prefix.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(operation = "TagUser", resource = %user_name))
+    )]
     pub async fn tag_user(&self, user_name: &str, tags: Vec<Tag>) -> Result<()> {
+        tag_operations::validate_tags(&tags)?;
         self.store.write().unwrap().tag_user(user_name, tags).await
     }
 
     /// List tags for a user
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(operation = "ListUserTags", resource = %user_name))
+    )]
     pub async fn list_user_tags(&self, user_name: &str) -> Result<Vec<Tag>> {
         self.store.read().unwrap().list_user_tags(user_name).await
     }
 
     /// Untag a user
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(operation = "UntagUser", resource = %user_name))
+    )]
     pub async fn untag_user(&self, user_name: &str, tag_keys: Vec<String>) -> Result<()> {
         self.store
             .write()
@@ -115,6 +392,137 @@ impl<S: UserStore> UserService<S> {
             .untag_user(user_name, tag_keys)
             .await
     }
+
+    /// Record a user as synced to another cloud provider
+    ///
+    /// Computes the user's native identifier in `account_id` of `provider`
+    /// and appends it to the user's provider configs. Syncing to a provider
+    /// the user already has is a no-op.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(operation = "SyncUserToProvider", resource = %user_name, provider = %provider.name())
+        )
+    )]
+    pub async fn sync_to_provider(
+        &self,
+        user_name: &str,
+        provider: &dyn CloudProvider,
+        account_id: &str,
+    ) -> Result<User> {
+        let user = self
+            .store
+            .read()
+            .unwrap()
+            .get_user(user_name)
+            .await?
+            .ok_or_else(|| AmiError::ResourceNotFound {
+                resource: format!("User: {}", user_name),
+            })?;
+
+        if user
+            .providers
+            .iter()
+            .any(|p| p.provider_name == provider.name())
+        {
+            return Ok(user);
+        }
+
+        let native_arn = provider.generate_resource_identifier(
+            ResourceType::User,
+            account_id,
+            &user.path,
+            &user.user_name,
+        );
+        let config = ProviderConfig {
+            provider_name: provider.name().to_string(),
+            account_id: account_id.to_string(),
+            native_arn,
+            synced_at: Utc::now(),
+            tenant_id: user.tenant_id.as_ref().map(|id| id.to_string()),
+        };
+        let user = user_builder::add_provider_to_user(user, config);
+
+        self.store.write().unwrap().update_user(user).await
+    }
+}
+
+impl<S: UserStore + TenantStore> UserService<S> {
+    /// Create many users in one call
+    ///
+    /// Each request is handled as by [`create_user`](Self::create_user); a
+    /// failing request (such as a name collision) is reported and the rest
+    /// of the batch still goes ahead. When the context's tenant is
+    /// registered, its `max_users` quota is enforced across the batch:
+    /// once reached, the remaining requests fail with
+    /// `ResourceLimitExceeded`. A quota observer set with
+    /// [`with_quota_observer`](Self::with_quota_observer) is notified when
+    /// the batch takes the tenant's user count across its threshold.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                operation = "CreateUsersBulk",
+                tenant = %context.tenant_path(),
+            )
+        )
+    )]
+    pub async fn create_users_bulk(
+        &self,
+        context: &WamiContext,
+        requests: Vec<CreateUserRequest>,
+    ) -> Result<BulkResult> {
+        let tenant_id = TenantId::from_string(&context.tenant_path().as_string())?;
+        let mut remaining = self.remaining_user_quota(&tenant_id).await?;
+        let mut result = BulkResult::default();
+
+        for (index, request) in requests.into_iter().enumerate() {
+            let user_name = request.user_name.clone();
+            let outcome = match &mut remaining {
+                Some((0, limit)) => Err(AmiError::ResourceLimitExceeded {
+                    resource_type: "Users".to_string(),
+                    limit: *limit,
+                }),
+                _ => self.create_user(context, request).await,
+            };
+
+            match outcome {
+                Ok(user) => {
+                    if let Some((remaining, limit)) = &mut remaining {
+                        if let Some(warning) = &self.quota_warning {
+                            let used = *limit - *remaining;
+                            warning.check(&tenant_id, ResourceType::User, *limit, used, used + 1);
+                        }
+                        *remaining -= 1;
+                    }
+                    result.succeeded.push(user);
+                }
+                Err(error) => result.failed.push(BulkFailure {
+                    index,
+                    user_name,
+                    error,
+                }),
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Users the tenant may still create, with its quota
+    ///
+    /// `None` when the tenant is not registered in the store.
+    async fn remaining_user_quota(&self, tenant_id: &TenantId) -> Result<Option<(usize, usize)>> {
+        let store = self.store.read().unwrap();
+        if store.get_tenant(tenant_id).await?.is_none() {
+            return Ok(None);
+        }
+
+        let limit = store.get_effective_quotas(tenant_id).await?.max_users;
+        let used = store.get_tenant_usage(tenant_id).await?.current_users;
+        Ok(Some((limit.saturating_sub(used), limit)))
+    }
 }
 
 #[cfg(test)]
@@ -142,6 +550,59 @@ mod tests {
             .unwrap()
     }
 
+    #[tokio::test]
+    async fn test_create_user_emits_audit_event() {
+        use crate::service::audit::{AuditOutcome, VecAuditSink};
+
+        let sink = Arc::new(VecAuditSink::new());
+        let service = setup_service().with_audit_sink(sink.clone());
+        let context = test_context();
+
+        let before = Utc::now();
+        let user = service
+            .create_user(
+                &context,
+                CreateUserRequest {
+                    user_name: "alice".to_string(),
+                    path: None,
+                    permissions_boundary: None,
+                    tags: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        let events = sink.events();
+        assert_eq!(events.len(), 1);
+        let event = &events[0];
+        assert_eq!(event.operation, "CreateUser");
+        assert_eq!(
+            event.actor_arn.as_deref(),
+            Some("arn:wami:.*:12345678:wami:123456789012:user/test")
+        );
+        assert_eq!(event.target_arn, user.wami_arn.to_string());
+        assert!(event.timestamp >= before && event.timestamp <= Utc::now());
+        assert_eq!(event.outcome, AuditOutcome::Succeeded);
+
+        // Failures are recorded too, against the requested name
+        let result = service
+            .create_user(
+                &context,
+                CreateUserRequest {
+                    user_name: "alice".to_string(),
+                    path: None,
+                    permissions_boundary: None,
+                    tags: None,
+                },
+            )
+            .await;
+        assert!(result.is_err());
+        let events = sink.events();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1].target_arn, "alice");
+        assert!(matches!(events[1].outcome, AuditOutcome::Failed { .. }));
+    }
+
     #[tokio::test]
     async fn test_create_and_get_user() {
         let service = setup_service();
@@ -163,6 +624,75 @@ mod tests {
         assert_eq!(retrieved.unwrap().user_name, "alice");
     }
 
+    #[tokio::test]
+    async fn test_temporary_user_expires_and_is_pruned() {
+        let service = setup_service();
+        let context = test_context();
+        let now = Utc::now();
+
+        let guest = CreateUserRequest {
+            user_name: "guest".to_string(),
+            path: None,
+            permissions_boundary: None,
+            tags: None,
+        };
+        let user = service
+            .create_temporary_user(&context, guest, now + chrono::Duration::hours(1))
+            .await
+            .unwrap();
+        assert!(user.expires_at.is_some());
+
+        let permanent = CreateUserRequest {
+            user_name: "alice".to_string(),
+            path: None,
+            permissions_boundary: None,
+            tags: None,
+        };
+        service.create_user(&context, permanent).await.unwrap();
+
+        // Still valid: visible and not pruned
+        assert!(service.get_user("guest").await.unwrap().is_some());
+        assert_eq!(service.prune_expired_users(now).await.unwrap(), 0);
+
+        // Advance the clock past the guest's expiry
+        let later = now + chrono::Duration::hours(2);
+        assert_eq!(service.prune_expired_users(later).await.unwrap(), 1);
+        assert!(service.get_user("guest").await.unwrap().is_none());
+        assert!(service.get_user("alice").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_get_user_hides_expired_user() {
+        let service = setup_service();
+        let context = test_context();
+
+        let request = CreateUserRequest {
+            user_name: "contractor".to_string(),
+            path: None,
+            permissions_boundary: None,
+            tags: None,
+        };
+        service
+            .create_temporary_user(&context, request, Utc::now() - chrono::Duration::seconds(1))
+            .await
+            .unwrap();
+
+        // Not yet pruned, but no longer visible
+        assert!(service.get_user("contractor").await.unwrap().is_none());
+        let (users, _, _) = service
+            .list_users(
+                &test_context(),
+                ListUsersRequest {
+                    path_prefix: None,
+                    pagination: None,
+                    include_descendants: false,
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(users.len(), 1);
+    }
+
     #[tokio::test]
     async fn test_update_user() {
         let service = setup_service();
@@ -188,6 +718,59 @@ mod tests {
         assert_eq!(updated.path, "/admin/");
     }
 
+    #[tokio::test]
+    async fn test_sync_user_to_gcp() {
+        let service = setup_service();
+        let context = test_context();
+
+        let request = CreateUserRequest {
+            user_name: "alice".to_string(),
+            path: Some("/eng/".to_string()),
+            permissions_boundary: None,
+            tags: None,
+        };
+        service.create_user(&context, request).await.unwrap();
+
+        let aws = AwsProvider::new();
+        let gcp = crate::provider::GcpProvider::new("my-project");
+        service
+            .sync_to_provider("alice", &aws, "123456789012")
+            .await
+            .unwrap();
+        let synced = service
+            .sync_to_provider("alice", &gcp, "my-project")
+            .await
+            .unwrap();
+
+        assert_eq!(synced.providers.len(), 2);
+        assert_eq!(synced.providers[0].provider_name, "aws");
+        assert_eq!(
+            synced.providers[0].native_arn,
+            "arn:aws:iam::123456789012:user/eng/alice"
+        );
+        assert_eq!(synced.providers[1].provider_name, "gcp");
+        assert_eq!(synced.providers[1].account_id, "my-project");
+        assert_eq!(
+            synced.providers[1].native_arn,
+            "projects/my-project/serviceAccounts/alice@my-project.iam.gserviceaccount.com"
+        );
+
+        // Syncing again keeps the original config
+        let resynced = service
+            .sync_to_provider("alice", &gcp, "my-project")
+            .await
+            .unwrap();
+        assert_eq!(resynced.providers, synced.providers);
+
+        let stored = service.get_user("alice").await.unwrap().unwrap();
+        assert_eq!(stored.providers, synced.providers);
+
+        assert!(matches!(
+            service.sync_to_provider("nobody", &gcp, "my-project").await,
+            Err(AmiError::ResourceNotFound { .. })
+        ));
+    }
+
     #[tokio::test]
     async fn test_delete_user() {
         let service = setup_service();
@@ -226,11 +809,103 @@ mod tests {
         let list_request = ListUsersRequest {
             path_prefix: Some("/test/".to_string()),
             pagination: None,
+            include_descendants: false,
         };
-        let (users, _, _) = service.list_users(list_request).await.unwrap();
+        let (users, _, _) = service
+            .list_users(&test_context(), list_request)
+            .await
+            .unwrap();
         assert_eq!(users.len(), 3);
     }
 
+    #[tokio::test]
+    async fn test_list_users_by_path_prefix() {
+        let service = setup_service();
+        let context = test_context();
+        for (name, path) in [("ana", "/eng/"), ("bo", "/eng/backend/"), ("cy", "/ops/")] {
+            let request = CreateUserRequest {
+                user_name: name.to_string(),
+                path: Some(path.to_string()),
+                permissions_boundary: None,
+                tags: None,
+            };
+            service.create_user(&context, request).await.unwrap();
+        }
+
+        for (path_prefix, expected) in [
+            (Some("/eng/"), vec!["ana", "bo"]),
+            (Some("/ops/"), vec!["cy"]),
+            (Some("/sales/"), vec![]),
+            (None, vec!["ana", "bo", "cy"]),
+        ] {
+            let request = ListUsersRequest {
+                path_prefix: path_prefix.map(str::to_string),
+                pagination: None,
+                include_descendants: false,
+            };
+            let (users, _, _) = service.list_users(&context, request).await.unwrap();
+            let names: Vec<_> = users.iter().map(|u| u.user_name.as_str()).collect();
+            assert_eq!(names, expected, "{:?}", path_prefix);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_users_is_confined_to_the_caller_tenant() {
+        let service = setup_service();
+        let tenant_context = |segments: Vec<u64>, is_root: bool| {
+            let tenant_path = TenantPath::new(segments);
+            WamiContext::builder()
+                .instance_id("123456789012")
+                .caller_arn(
+                    format!("arn:wami:.*:{}:wami:123456789012:user/admin", tenant_path)
+                        .parse()
+                        .unwrap(),
+                )
+                .tenant_path(tenant_path)
+                .is_root(is_root)
+                .build()
+                .unwrap()
+        };
+        let parent = tenant_context(vec![100], false);
+        let sibling_a = tenant_context(vec![100, 1], false);
+        let sibling_b = tenant_context(vec![100, 2], false);
+
+        for (context, name) in [(&sibling_a, "alice"), (&sibling_b, "bob")] {
+            let request = CreateUserRequest {
+                user_name: name.to_string(),
+                path: None,
+                permissions_boundary: None,
+                tags: None,
+            };
+            service.create_user(context, request).await.unwrap();
+        }
+
+        let names = |context: WamiContext, include_descendants: bool| {
+            let service = &service;
+            async move {
+                let request = ListUsersRequest {
+                    path_prefix: None,
+                    pagination: None,
+                    include_descendants,
+                };
+                let (users, _, _) = service.list_users(&context, request).await.unwrap();
+                users
+                    .into_iter()
+                    .map(|user| user.user_name)
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        assert_eq!(names(sibling_a, false).await, ["alice"]);
+        assert_eq!(names(sibling_b, true).await, ["bob"]);
+        assert!(names(parent.clone(), false).await.is_empty());
+        assert_eq!(names(parent, true).await, ["alice", "bob"]);
+        assert_eq!(
+            names(tenant_context(vec![0], true), false).await,
+            ["alice", "bob"]
+        );
+    }
+
     #[tokio::test]
     async fn test_tag_operations() {
         let service = setup_service();
@@ -266,6 +941,329 @@ mod tests {
         assert_eq!(tags_after.len(), 0);
     }
 
+    #[tokio::test]
+    async fn test_tag_user_rejects_invalid_tags() {
+        let service = setup_service();
+        let context = test_context();
+
+        let tag = |key: &str, value: &str| Tag {
+            key: key.to_string(),
+            value: value.to_string(),
+        };
+
+        // Invalid tags are rejected at creation as well
+        let request = CreateUserRequest {
+            user_name: "alice".to_string(),
+            path: None,
+            permissions_boundary: None,
+            tags: Some(vec![tag("aws:team", "platform")]),
+        };
+        assert!(matches!(
+            service.create_user(&context, request).await,
+            Err(crate::error::AmiError::InvalidParameter { .. })
+        ));
+
+        let request = CreateUserRequest {
+            user_name: "alice".to_string(),
+            path: None,
+            permissions_boundary: None,
+            tags: None,
+        };
+        service.create_user(&context, request).await.unwrap();
+
+        assert!(service
+            .tag_user("alice", vec![tag("team", &"x".repeat(257))])
+            .await
+            .is_err());
+        assert!(service
+            .tag_user("alice", vec![tag("aws:team", "platform")])
+            .await
+            .is_err());
+        assert!(service.list_user_tags("alice").await.unwrap().is_empty());
+
+        service
+            .tag_user("alice", vec![tag("team", "platform")])
+            .await
+            .unwrap();
+        assert_eq!(service.list_user_tags("alice").await.unwrap().len(), 1);
+    }
+
     // Note: test_with_provider removed as with_provider() method no longer exists
     // Provider selection is now handled through WamiContext and CloudMapping
+
+    #[tokio::test]
+    async fn test_create_duplicate_name_is_resource_exists() {
+        let service = setup_service();
+        let context = test_context();
+
+        let request = || CreateUserRequest {
+            user_name: "alice".to_string(),
+            path: None,
+            permissions_boundary: None,
+            tags: None,
+        };
+        let original = service.create_user(&context, request()).await.unwrap();
+
+        let result = service.create_user(&context, request()).await;
+        assert!(matches!(
+            result,
+            Err(crate::error::AmiError::ResourceExists { ref resource }) if resource == "User: alice"
+        ));
+
+        // The existing user is left untouched
+        let stored = service.get_user("alice").await.unwrap().unwrap();
+        assert_eq!(stored.user_id, original.user_id);
+    }
+
+    #[tokio::test]
+    async fn test_create_regenerates_colliding_id() {
+        let service = setup_service();
+        let context = test_context();
+
+        let request = CreateUserRequest {
+            user_name: "alice".to_string(),
+            path: None,
+            permissions_boundary: None,
+            tags: None,
+        };
+        let alice = service.create_user(&context, request).await.unwrap();
+
+        // The first build reuses alice's ID and ARN, forcing an ID collision
+        let mut attempts = 0;
+        let bob = service
+            .create_user_with("bob", || {
+                attempts += 1;
+                let mut user = user_builder::build_user("bob".to_string(), None, &context)?;
+                if attempts == 1 {
+                    user.user_id = alice.user_id.clone();
+                    user.wami_arn = alice.wami_arn.clone();
+                }
+                Ok(user)
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(attempts, 2);
+        assert_ne!(bob.user_id, alice.user_id);
+        assert_ne!(bob.wami_arn, alice.wami_arn);
+    }
+
+    #[tokio::test]
+    async fn test_create_gives_up_after_repeated_id_collisions() {
+        let service = setup_service();
+        let context = test_context();
+
+        let request = CreateUserRequest {
+            user_name: "alice".to_string(),
+            path: None,
+            permissions_boundary: None,
+            tags: None,
+        };
+        let alice = service.create_user(&context, request).await.unwrap();
+
+        let mut attempts = 0;
+        let result = service
+            .create_user_with("bob", || {
+                attempts += 1;
+                let mut user = user_builder::build_user("bob".to_string(), None, &context)?;
+                user.user_id = alice.user_id.clone();
+                Ok(user)
+            })
+            .await;
+
+        assert_eq!(attempts, MAX_ID_ATTEMPTS);
+        assert!(matches!(result, Err(crate::error::AmiError::StoreError(_))));
+        assert!(service.get_user("bob").await.unwrap().is_none());
+    }
+
+    fn bulk_request(user_name: &str, path: Option<&str>) -> CreateUserRequest {
+        CreateUserRequest {
+            user_name: user_name.to_string(),
+            path: path.map(str::to_string),
+            permissions_boundary: None,
+            tags: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_users_bulk_reports_partial_failures() {
+        let service = setup_service();
+        let context = test_context();
+        service
+            .create_user(&context, bulk_request("existing", None))
+            .await
+            .unwrap();
+
+        let result = service
+            .create_users_bulk(
+                &context,
+                vec![
+                    bulk_request("ana", None),
+                    bulk_request("existing", None),
+                    bulk_request("ben", Some("engineering")),
+                    bulk_request("cleo", Some("/engineering/")),
+                    bulk_request("ana", None),
+                ],
+            )
+            .await
+            .unwrap();
+
+        assert!(!result.is_complete());
+        let created: Vec<_> = result
+            .succeeded
+            .iter()
+            .map(|u| u.user_name.as_str())
+            .collect();
+        assert_eq!(created, vec!["ana", "cleo"]);
+
+        let failures: Vec<_> = result
+            .failed
+            .iter()
+            .map(|f| (f.index, f.user_name.as_str()))
+            .collect();
+        assert_eq!(failures, vec![(1, "existing"), (2, "ben"), (4, "ana")]);
+        assert!(matches!(
+            result.failed[0].error,
+            crate::error::AmiError::ResourceExists { .. }
+        ));
+        assert!(matches!(
+            result.failed[1].error,
+            crate::error::AmiError::InvalidParameter { .. }
+        ));
+        assert!(matches!(
+            result.failed[2].error,
+            crate::error::AmiError::ResourceExists { .. }
+        ));
+
+        // The rejected path created nothing
+        assert!(service.get_user("ben").await.unwrap().is_none());
+        assert_eq!(
+            service.get_user("cleo").await.unwrap().unwrap().path,
+            "/engineering/"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_users_bulk_enforces_tenant_quota() {
+        use crate::store::traits::TenantStore;
+        use crate::wami::tenant::operations::tenant_operations::build_tenant;
+
+        let service = setup_service();
+        let context = test_context();
+        let mut tenant = build_tenant(
+            TenantId::from_string("12345678").unwrap(),
+            "acme".to_string(),
+            None,
+            None,
+        );
+        tenant.quotas.max_users = 3;
+        service
+            .store
+            .write()
+            .unwrap()
+            .create_tenant(tenant)
+            .await
+            .unwrap();
+        service
+            .create_user(&context, bulk_request("existing", None))
+            .await
+            .unwrap();
+
+        let requests = ["a", "a", "b", "c", "d"]
+            .into_iter()
+            .map(|name| bulk_request(name, None))
+            .collect();
+        let result = service.create_users_bulk(&context, requests).await.unwrap();
+
+        // Two slots were left; the duplicate does not use one
+        let created: Vec<_> = result
+            .succeeded
+            .iter()
+            .map(|u| u.user_name.as_str())
+            .collect();
+        assert_eq!(created, vec!["a", "b"]);
+        assert_eq!(result.failed.len(), 3);
+        assert!(matches!(
+            result.failed[0].error,
+            crate::error::AmiError::ResourceExists { .. }
+        ));
+        for failure in &result.failed[1..] {
+            assert!(matches!(
+                failure.error,
+                crate::error::AmiError::ResourceLimitExceeded { limit: 3, .. }
+            ));
+        }
+        assert!(service.get_user("d").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_create_users_bulk_notifies_quota_observer() {
+        use crate::service::quota::QuotaObserver;
+        use crate::store::traits::TenantStore;
+        use crate::wami::tenant::operations::tenant_operations::build_tenant;
+        use std::sync::Mutex;
+
+        #[derive(Default)]
+        struct RecordingObserver {
+            warnings: Mutex<Vec<(TenantId, ResourceType)>>,
+        }
+
+        impl QuotaObserver for RecordingObserver {
+            fn threshold_crossed(&self, tenant_id: &TenantId, resource_type: ResourceType) {
+                self.warnings
+                    .lock()
+                    .unwrap()
+                    .push((tenant_id.clone(), resource_type));
+            }
+        }
+
+        let observer = Arc::new(RecordingObserver::default());
+        let service = setup_service().with_quota_observer(observer.clone(), 0.8);
+        let context = test_context();
+        let tenant_id = TenantId::from_string("12345678").unwrap();
+        let mut tenant = build_tenant(tenant_id.clone(), "acme".to_string(), None, None);
+        tenant.quotas.max_users = 10;
+        service
+            .store
+            .write()
+            .unwrap()
+            .create_tenant(tenant)
+            .await
+            .unwrap();
+
+        // 7 of 10 users stays under the threshold
+        let requests = (0..7)
+            .map(|i| bulk_request(&format!("user-{}", i), None))
+            .collect();
+        service.create_users_bulk(&context, requests).await.unwrap();
+        assert!(observer.warnings.lock().unwrap().is_empty());
+
+        // Reaching 8, then going past it and over the quota, warns once
+        let requests = (7..12)
+            .map(|i| bulk_request(&format!("user-{}", i), None))
+            .collect();
+        let result = service.create_users_bulk(&context, requests).await.unwrap();
+        assert_eq!(result.succeeded.len(), 3);
+        assert_eq!(
+            *observer.warnings.lock().unwrap(),
+            vec![(tenant_id, ResourceType::User)]
+        );
+    }
+
+    #[cfg(feature = "tracing")]
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn test_create_user_emits_span() {
+        let service = setup_service();
+        service
+            .create_user(&test_context(), bulk_request("traced", None))
+            .await
+            .unwrap();
+
+        assert!(logs_contain("create_user{"));
+        assert!(logs_contain(r#"operation="CreateUser""#));
+        assert!(logs_contain("tenant=12345678"));
+        assert!(logs_contain("resource=traced"));
+        assert!(logs_contain(r#"resource_type="user""#));
+    }
 }
@@ -22,25 +22,32 @@
 //! # Structure
 //!
 //! Services mirror the wami/ and store/ directory structure:
+//! - `audit` - Audit sink receiving events from services
 //! - `auth/` - Authentication and Authorization services
 //! - `identity/` - User, Group, Role, ServiceLinkedRole services
 //! - `credentials/` - AccessKey, MfaDevice, LoginProfile services
 //! - `policies/` - Policy service
-//! - `reports/` - CredentialReport service
+//! - `quota` - Observer notified when tenant usage nears a quota
+//! - `reports/` - CredentialReport and AuthorizationDetails services
 //! - `sts/` - Session, Identity services
 //! - `tenant/` - Tenant service
 
+pub mod audit;
 pub mod auth;
 pub mod credentials;
 pub mod identity;
 pub mod policies;
+pub mod quota;
 pub mod reports;
 pub mod sso_admin;
 pub mod sts;
 pub mod tenant;
 
 // Re-export main services for convenience
-pub use auth::{hash_secret, verify_secret, AuthenticationService, AuthorizationService};
+pub use audit::{AuditEvent, AuditOutcome, AuditSink, VecAuditSink};
+pub use auth::{
+    hash_secret, verify_secret, AuthenticationService, AuthorizationService, PolicySources,
+};
 pub use credentials::{
     AccessKeyService, LoginProfileService, MfaDeviceService, ServerCertificateService,
     ServiceCredentialService, SigningCertificateService,
@@ -52,7 +59,8 @@ pub use policies::{
     AttachmentService, EvaluationService, InlinePolicyService, PermissionsBoundaryService,
     PolicyService,
 };
-pub use reports::CredentialReportService;
+pub use quota::QuotaObserver;
+pub use reports::{AuthorizationDetailsService, CredentialReportService};
 pub use sso_admin::{
     AccountAssignmentService, ApplicationService, InstanceService, PermissionSetService,
     TrustedTokenIssuerService,
@@ -24,6 +24,9 @@ where
     // User policy attachment methods
 
     /// Attach a managed policy to a user
+    ///
+    /// The policy must exist and be attachable. Attaching a policy that is
+    /// already attached leaves its `attachment_count` unchanged.
     pub async fn attach_user_policy(
         &self,
         request: AttachUserPolicyRequest,
@@ -53,15 +56,20 @@ where
             });
         }
 
-        // Attach the policy
-        store
-            .attach_user_policy(&request.user_name, &request.policy_arn)
+        // Attaching an already attached policy is a no-op and must not be counted twice
+        let attached = store
+            .list_attached_user_policies(&request.user_name)
             .await?;
+        if !attached.contains(&request.policy_arn) {
+            store
+                .attach_user_policy(&request.user_name, &request.policy_arn)
+                .await?;
 
-        // Update policy attachment count
-        let mut updated_policy = policy.clone();
-        updated_policy.attachment_count += 1;
-        store.update_policy(updated_policy).await?;
+            // Update policy attachment count
+            let mut updated_policy = policy.clone();
+            updated_policy.attachment_count += 1;
+            store.update_policy(updated_policy).await?;
+        }
 
         Ok(AttachUserPolicyResponse {
             message: format!(
@@ -72,6 +80,8 @@ where
     }
 
     /// Detach a managed policy from a user
+    ///
+    /// Returns `ResourceNotFound` if the policy is not attached to the user.
     pub async fn detach_user_policy(
         &self,
         request: DetachUserPolicyRequest,
@@ -86,6 +96,19 @@ where
                 resource: format!("User: {}", request.user_name),
             })?;
 
+        // Only an attached policy can be detached
+        let attached = store
+            .list_attached_user_policies(&request.user_name)
+            .await?;
+        if !attached.contains(&request.policy_arn) {
+            return Err(AmiError::ResourceNotFound {
+                resource: format!(
+                    "Policy {} attached to user {}",
+                    request.policy_arn, request.user_name
+                ),
+            });
+        }
+
         // Detach the policy
         store
             .detach_user_policy(&request.user_name, &request.policy_arn)
@@ -143,6 +166,9 @@ where
     // Group policy attachment methods
 
     /// Attach a managed policy to a group
+    ///
+    /// The policy must exist and be attachable. Attaching a policy that is
+    /// already attached leaves its `attachment_count` unchanged.
     pub async fn attach_group_policy(
         &self,
         request: AttachGroupPolicyRequest,
@@ -172,15 +198,20 @@ where
             });
         }
 
-        // Attach the policy
-        store
-            .attach_group_policy(&request.group_name, &request.policy_arn)
+        // Attaching an already attached policy is a no-op and must not be counted twice
+        let attached = store
+            .list_attached_group_policies(&request.group_name)
             .await?;
+        if !attached.contains(&request.policy_arn) {
+            store
+                .attach_group_policy(&request.group_name, &request.policy_arn)
+                .await?;
 
-        // Update policy attachment count
-        let mut updated_policy = policy.clone();
-        updated_policy.attachment_count += 1;
-        store.update_policy(updated_policy).await?;
+            // Update policy attachment count
+            let mut updated_policy = policy.clone();
+            updated_policy.attachment_count += 1;
+            store.update_policy(updated_policy).await?;
+        }
 
         Ok(AttachGroupPolicyResponse {
             message: format!(
@@ -191,6 +222,8 @@ where
     }
 
     /// Detach a managed policy from a group
+    ///
+    /// Returns `ResourceNotFound` if the policy is not attached to the group.
     pub async fn detach_group_policy(
         &self,
         request: DetachGroupPolicyRequest,
@@ -205,6 +238,19 @@ where
                 resource: format!("Group: {}", request.group_name),
             })?;
 
+        // Only an attached policy can be detached
+        let attached = store
+            .list_attached_group_policies(&request.group_name)
+            .await?;
+        if !attached.contains(&request.policy_arn) {
+            return Err(AmiError::ResourceNotFound {
+                resource: format!(
+                    "Policy {} attached to group {}",
+                    request.policy_arn, request.group_name
+                ),
+            });
+        }
+
         // Detach the policy
         store
             .detach_group_policy(&request.group_name, &request.policy_arn)
@@ -262,6 +308,9 @@ where
     // Role policy attachment methods
 
     /// Attach a managed policy to a role
+    ///
+    /// The policy must exist and be attachable. Attaching a policy that is
+    /// already attached leaves its `attachment_count` unchanged.
     pub async fn attach_role_policy(
         &self,
         request: AttachRolePolicyRequest,
@@ -291,15 +340,20 @@ where
             });
         }
 
-        // Attach the policy
-        store
-            .attach_role_policy(&request.role_name, &request.policy_arn)
+        // Attaching an already attached policy is a no-op and must not be counted twice
+        let attached = store
+            .list_attached_role_policies(&request.role_name)
             .await?;
+        if !attached.contains(&request.policy_arn) {
+            store
+                .attach_role_policy(&request.role_name, &request.policy_arn)
+                .await?;
 
-        // Update policy attachment count
-        let mut updated_policy = policy.clone();
-        updated_policy.attachment_count += 1;
-        store.update_policy(updated_policy).await?;
+            // Update policy attachment count
+            let mut updated_policy = policy.clone();
+            updated_policy.attachment_count += 1;
+            store.update_policy(updated_policy).await?;
+        }
 
         Ok(AttachRolePolicyResponse {
             message: format!(
@@ -310,6 +364,8 @@ where
     }
 
     /// Detach a managed policy from a role
+    ///
+    /// Returns `ResourceNotFound` if the policy is not attached to the role.
     pub async fn detach_role_policy(
         &self,
         request: DetachRolePolicyRequest,
@@ -324,6 +380,19 @@ where
                 resource: format!("Role: {}", request.role_name),
             })?;
 
+        // Only an attached policy can be detached
+        let attached = store
+            .list_attached_role_policies(&request.role_name)
+            .await?;
+        if !attached.contains(&request.policy_arn) {
+            return Err(AmiError::ResourceNotFound {
+                resource: format!(
+                    "Policy {} attached to role {}",
+                    request.policy_arn, request.role_name
+                ),
+            });
+        }
+
         // Detach the policy
         store
             .detach_role_policy(&request.role_name, &request.policy_arn)
@@ -613,4 +682,165 @@ mod tests {
         let result = service.attach_user_policy(request).await;
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_role_policy_attachment_count_round_trip() {
+        let store = Arc::new(RwLock::new(InMemoryWamiStore::new()));
+        let service = AttachmentService::new(store.clone());
+        let context = create_test_context().await;
+
+        let role = build_role(
+            "AdminRole".to_string(),
+            r#"{"Version":"2012-10-17","Statement":[]}"#.to_string(),
+            Some("/".to_string()),
+            None,
+            None,
+            &context,
+        )
+        .unwrap();
+        store.write().unwrap().create_role(role).await.unwrap();
+        let policy = build_policy(
+            "TestPolicy".to_string(),
+            r#"{"Version":"2012-10-17","Statement":[]}"#.to_string(),
+            None,
+            None,
+            None,
+            &context,
+        )
+        .unwrap();
+        let policy_arn = store
+            .write()
+            .unwrap()
+            .create_policy(policy)
+            .await
+            .unwrap()
+            .arn;
+        let attachment_count = || async {
+            let store = store.read().unwrap();
+            store
+                .get_policy(&policy_arn)
+                .await
+                .unwrap()
+                .unwrap()
+                .attachment_count
+        };
+
+        // Attaching twice records a single attachment
+        for _ in 0..2 {
+            service
+                .attach_role_policy(AttachRolePolicyRequest {
+                    role_name: "AdminRole".to_string(),
+                    policy_arn: policy_arn.clone(),
+                })
+                .await
+                .unwrap();
+        }
+        let listed = service
+            .list_attached_role_policies(ListAttachedRolePoliciesRequest {
+                role_name: "AdminRole".to_string(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(listed.attached_policies.len(), 1);
+        assert_eq!(listed.attached_policies[0].policy_name, "TestPolicy");
+        assert_eq!(attachment_count().await, 1);
+
+        let detach = || {
+            service.detach_role_policy(DetachRolePolicyRequest {
+                role_name: "AdminRole".to_string(),
+                policy_arn: policy_arn.clone(),
+            })
+        };
+        detach().await.unwrap();
+        assert_eq!(attachment_count().await, 0);
+        assert!(service
+            .list_attached_role_policies(ListAttachedRolePoliciesRequest {
+                role_name: "AdminRole".to_string(),
+            })
+            .await
+            .unwrap()
+            .attached_policies
+            .is_empty());
+
+        // The policy is no longer attached
+        assert!(matches!(
+            detach().await.unwrap_err(),
+            AmiError::ResourceNotFound { .. }
+        ));
+        assert_eq!(attachment_count().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_detach_policy_not_attached() {
+        let store = Arc::new(RwLock::new(InMemoryWamiStore::new()));
+        let service = AttachmentService::new(store.clone());
+        let context = create_test_context().await;
+
+        let user = build_user("alice".to_string(), Some("/".to_string()), &context).unwrap();
+        store.write().unwrap().create_user(user).await.unwrap();
+        let group = build_group("developers".to_string(), Some("/".to_string()), &context).unwrap();
+        store.write().unwrap().create_group(group).await.unwrap();
+        let policy = build_policy(
+            "TestPolicy".to_string(),
+            r#"{"Version":"2012-10-17","Statement":[]}"#.to_string(),
+            None,
+            None,
+            None,
+            &context,
+        )
+        .unwrap();
+        let policy_arn = store
+            .write()
+            .unwrap()
+            .create_policy(policy)
+            .await
+            .unwrap()
+            .arn;
+
+        // Attached to the group only
+        service
+            .attach_group_policy(AttachGroupPolicyRequest {
+                group_name: "developers".to_string(),
+                policy_arn: policy_arn.clone(),
+            })
+            .await
+            .unwrap();
+
+        let result = service
+            .detach_user_policy(DetachUserPolicyRequest {
+                user_name: "alice".to_string(),
+                policy_arn: policy_arn.clone(),
+            })
+            .await;
+        assert!(matches!(
+            result.unwrap_err(),
+            AmiError::ResourceNotFound { .. }
+        ));
+
+        // The failed detach left the group's attachment counted
+        let policy = store
+            .read()
+            .unwrap()
+            .get_policy(&policy_arn)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(policy.attachment_count, 1);
+
+        service
+            .detach_group_policy(DetachGroupPolicyRequest {
+                group_name: "developers".to_string(),
+                policy_arn: policy_arn.clone(),
+            })
+            .await
+            .unwrap();
+        let policy = store
+            .read()
+            .unwrap()
+            .get_policy(&policy_arn)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(policy.attachment_count, 0);
+    }
 }
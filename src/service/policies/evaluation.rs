@@ -4,11 +4,17 @@
 
 use crate::error::{AmiError, Result};
 use crate::provider::{AwsProvider, CloudProvider};
-use crate::store::traits::{PolicyStore, RoleStore, UserStore};
+use crate::store::traits::{GroupStore, PolicyStore, RoleStore, UserStore};
 use crate::types::PolicyDocument;
+use crate::wami::policies::evaluation::condition::{
+    evaluate_conditions, missing_context_keys, wildcard_match,
+};
+use crate::wami::policies::evaluation::delta::permission_delta;
+use crate::wami::policies::evaluation::variables::expand_policy_variables;
 use crate::wami::policies::evaluation::{
-    EvaluationResult, SimulateCustomPolicyRequest, SimulatePolicyResponse,
-    SimulatePrincipalPolicyRequest, StatementMatch,
+    ContextEntry, DecisionSource, EvaluationResult, PrincipalPermissionDelta,
+    SimulateCustomPolicyRequest, SimulatePolicyResponse, SimulatePrincipalPolicyRequest,
+    StatementMatch,
 };
 use std::sync::{Arc, RwLock};
 
@@ -22,7 +28,7 @@ pub struct EvaluationService<S> {
     account_id: String,
 }
 
-impl<S: UserStore + RoleStore + PolicyStore> EvaluationService<S> {
+impl<S: UserStore + GroupStore + RoleStore + PolicyStore> EvaluationService<S> {
     /// Create a new EvaluationService with default AWS provider
     pub fn new(store: Arc<RwLock<S>>, account_id: String) -> Self {
         Self {
@@ -60,26 +66,49 @@ impl<S: UserStore + RoleStore + PolicyStore> EvaluationService<S> {
             .collect();
 
         let policies = policies?;
+        let resource_policy = Self::parse_resource_policy(request.resource_policy.as_deref())?;
+        let cross_account = Self::is_cross_account(
+            request.caller_arn.as_deref(),
+            request.resource_owner.as_deref(),
+        );
 
         // Default resource if not provided
         let resources = request
             .resource_arns
             .unwrap_or_else(|| vec!["*".to_string()]);
 
+        let context_entries = request.context_entries.unwrap_or_default();
+
         // Evaluate each action against each resource
         let mut results = Vec::new();
 
         for action in &request.action_names {
             for resource in &resources {
-                let decision = self.evaluate_action(&policies, action, resource);
-                let matched_statements = self.find_matching_statements(&policies, action, resource);
+                let decision = self.evaluate_action(&policies, action, resource, &context_entries);
+                let (decision, decision_source) = self.apply_resource_policy(
+                    decision,
+                    resource_policy.as_ref(),
+                    cross_account,
+                    action,
+                    resource,
+                    &context_entries,
+                );
+                let matched_statements =
+                    self.find_matching_statements(&policies, action, resource, &context_entries);
+                let missing_context_values = Self::find_missing_context_values(
+                    &policies,
+                    action,
+                    resource,
+                    &context_entries,
+                );
 
                 results.push(EvaluationResult {
                     eval_action_name: action.clone(),
                     eval_resource_name: resource.clone(),
                     eval_decision: decision,
                     matched_statements,
-                    missing_context_values: vec![], // TODO: Context evaluation
+                    missing_context_values,
+                    decision_source,
                 });
             }
         }
@@ -92,7 +121,9 @@ impl<S: UserStore + RoleStore + PolicyStore> EvaluationService<S> {
 
     /// Simulate a principal's (user or role) effective policies
     ///
-    /// Fetches the principal's attached policies from the store and evaluates them.
+    /// Resolves the principal's attached and inline policies from the store,
+    /// along with those of its groups for a user, and evaluates them together
+    /// with any documents from `policy_input_list`.
     pub async fn simulate_principal_policy(
         &self,
         request: SimulatePrincipalPolicyRequest,
@@ -103,7 +134,7 @@ impl<S: UserStore + RoleStore + PolicyStore> EvaluationService<S> {
 
         // Fetch principal's policies from store
         let mut policies = self
-            .fetch_principal_policies(&principal_type, &principal_name)
+            .fetch_effective_policies(&request.policy_source_arn)
             .await?;
 
         // Fetch permissions boundary if present
@@ -122,11 +153,19 @@ impl<S: UserStore + RoleStore + PolicyStore> EvaluationService<S> {
             }
         }
 
+        let resource_policy = Self::parse_resource_policy(request.resource_policy.as_deref())?;
+        let cross_account = Self::is_cross_account(
+            Some(&request.policy_source_arn),
+            request.resource_owner.as_deref(),
+        );
+
         // Default resource if not provided
         let resources = request
             .resource_arns
             .unwrap_or_else(|| vec!["*".to_string()]);
 
+        let context_entries = request.context_entries.unwrap_or_default();
+
         // Evaluate each action against each resource
         let mut results = Vec::new();
 
@@ -138,15 +177,32 @@ impl<S: UserStore + RoleStore + PolicyStore> EvaluationService<S> {
                     action,
                     resource,
                     boundary.as_ref(),
+                    &context_entries,
+                );
+                let (decision, decision_source) = self.apply_resource_policy(
+                    decision,
+                    resource_policy.as_ref(),
+                    cross_account,
+                    action,
+                    resource,
+                    &context_entries,
+                );
+                let matched_statements =
+                    self.find_matching_statements(&policies, action, resource, &context_entries);
+                let missing_context_values = Self::find_missing_context_values(
+                    &policies,
+                    action,
+                    resource,
+                    &context_entries,
                 );
-                let matched_statements = self.find_matching_statements(&policies, action, resource);
 
                 results.push(EvaluationResult {
                     eval_action_name: action.clone(),
                     eval_resource_name: resource.clone(),
                     eval_decision: decision,
                     matched_statements,
-                    missing_context_values: vec![], // TODO: Context evaluation
+                    missing_context_values,
+                    decision_source,
                 });
             }
         }
@@ -190,58 +246,6 @@ impl<S: UserStore + RoleStore + PolicyStore> EvaluationService<S> {
         Ok((principal_type, principal_name))
     }
 
-    /// Fetch policies for a user or role
-    async fn fetch_principal_policies(
-        &self,
-        principal_type: &str,
-        principal_name: &str,
-    ) -> Result<Vec<PolicyDocument>> {
-        let policies = Vec::new();
-
-        match principal_type {
-            "user" => {
-                // Verify user exists
-                let _user = self
-                    .store
-                    .read()
-                    .unwrap()
-                    .get_user(principal_name)
-                    .await?
-                    .ok_or_else(|| AmiError::ResourceNotFound {
-                        resource: format!("User: {}", principal_name),
-                    })?;
-
-                // TODO: Policy attachments are not yet implemented in the User model
-                // In a full implementation, we would:
-                // 1. Query a policy_attachments table/map
-                // 2. Fetch all attached policies
-                // 3. Include inline policies if any
-                // For now, return empty list (will use policy_input_list from request instead)
-            }
-            "role" => {
-                // Verify role exists
-                let _role = self
-                    .store
-                    .read()
-                    .unwrap()
-                    .get_role(principal_name)
-                    .await?
-                    .ok_or_else(|| AmiError::ResourceNotFound {
-                        resource: format!("Role: {}", principal_name),
-                    })?;
-
-                // TODO: Same as user - policy attachments need separate tracking
-            }
-            _ => {
-                return Err(AmiError::InvalidParameter {
-                    message: format!("Unsupported principal type: {}", principal_type),
-                })
-            }
-        }
-
-        Ok(policies)
-    }
-
     /// Fetch permissions boundary for a user or role
     async fn fetch_permissions_boundary(
         &self,
@@ -290,7 +294,13 @@ impl<S: UserStore + RoleStore + PolicyStore> EvaluationService<S> {
     }
 
     /// Evaluate a single action/resource combination against policies
-    fn evaluate_action(&self, policies: &[PolicyDocument], action: &str, resource: &str) -> String {
+    fn evaluate_action(
+        &self,
+        policies: &[PolicyDocument],
+        action: &str,
+        resource: &str,
+        context_entries: &[ContextEntry],
+    ) -> String {
         let mut has_allow = false;
         let mut has_deny = false;
 
@@ -304,9 +314,12 @@ impl<S: UserStore + RoleStore + PolicyStore> EvaluationService<S> {
                 let resource_matches = statement
                     .resource
                     .iter()
-                    .any(|r| Self::matches_pattern(resource, r));
+                    .any(|r| Self::matches_resource(resource, r, context_entries));
 
-                if action_matches && resource_matches {
+                let conditions_match =
+                    evaluate_conditions(statement.condition.as_ref(), context_entries);
+
+                if action_matches && resource_matches && conditions_match {
                     if statement.effect == "Deny" {
                         has_deny = true;
                     } else if statement.effect == "Allow" {
@@ -339,6 +352,7 @@ impl<S: UserStore + RoleStore + PolicyStore> EvaluationService<S> {
         action: &str,
         resource: &str,
         boundary: Option<&crate::wami::policies::Policy>,
+        context_entries: &[ContextEntry],
     ) -> String {
         // Step 1: Check explicit deny in identity policies
         for policy in policies {
@@ -351,9 +365,16 @@ impl<S: UserStore + RoleStore + PolicyStore> EvaluationService<S> {
                 let resource_matches = statement
                     .resource
                     .iter()
-                    .any(|r| Self::matches_pattern(resource, r));
+                    .any(|r| Self::matches_resource(resource, r, context_entries));
+
+                let conditions_match =
+                    evaluate_conditions(statement.condition.as_ref(), context_entries);
 
-                if action_matches && resource_matches && statement.effect == "Deny" {
+                if action_matches
+                    && resource_matches
+                    && conditions_match
+                    && statement.effect == "Deny"
+                {
                     return "denied".to_string();
                 }
             }
@@ -369,9 +390,14 @@ impl<S: UserStore + RoleStore + PolicyStore> EvaluationService<S> {
                 let resource_matches = statement
                     .resource
                     .iter()
-                    .any(|r| Self::matches_pattern(resource, r));
-
-                action_matches && resource_matches && statement.effect == "Allow"
+                    .any(|r| Self::matches_resource(resource, r, context_entries));
+                let conditions_match =
+                    evaluate_conditions(statement.condition.as_ref(), context_entries);
+
+                action_matches
+                    && resource_matches
+                    && conditions_match
+                    && statement.effect == "Allow"
             })
         });
 
@@ -402,12 +428,101 @@ impl<S: UserStore + RoleStore + PolicyStore> EvaluationService<S> {
         "allowed".to_string()
     }
 
+    /// Parse the resource-based policy of a simulation request, if any
+    #[allow(clippy::result_large_err)]
+    fn parse_resource_policy(resource_policy: Option<&str>) -> Result<Option<PolicyDocument>> {
+        resource_policy
+            .map(|policy_str| {
+                serde_json::from_str(policy_str).map_err(|e| AmiError::InvalidParameter {
+                    message: format!("Invalid resource policy document: {}", e),
+                })
+            })
+            .transpose()
+    }
+
+    /// Whether the resource owner differs from the caller's account or tenant
+    ///
+    /// A WAMI caller ARN belongs to its tenant path and its WAMI instance; any
+    /// other ARN belongs to its account field. Without a resource owner the
+    /// simulation is same-account; with one but no known caller, it is
+    /// cross-account.
+    fn is_cross_account(caller_arn: Option<&str>, resource_owner: Option<&str>) -> bool {
+        let Some(resource_owner) = resource_owner else {
+            return false;
+        };
+
+        let owners: Vec<String> = match caller_arn {
+            Some(arn) => match arn.parse::<crate::arn::WamiArn>() {
+                Ok(wami_arn) => vec![
+                    wami_arn.tenant_path.as_string(),
+                    wami_arn.wami_instance_id.clone(),
+                ],
+                Err(_) => arn
+                    .split(':')
+                    .nth(4)
+                    .filter(|account| !account.is_empty())
+                    .map(str::to_string)
+                    .into_iter()
+                    .collect(),
+            },
+            None => Vec::new(),
+        };
+
+        !owners.iter().any(|owner| owner == resource_owner)
+    }
+
+    /// Combine the identity decision with the resource-based policy's
+    ///
+    /// An explicit deny in either wins. Within an account, an allow from
+    /// either policy is enough; across accounts, both must allow. Also
+    /// returns which policies produced the decision.
+    fn apply_resource_policy(
+        &self,
+        identity_decision: String,
+        resource_policy: Option<&PolicyDocument>,
+        cross_account: bool,
+        action: &str,
+        resource: &str,
+        context_entries: &[ContextEntry],
+    ) -> (String, Option<DecisionSource>) {
+        let resource_decision = resource_policy.map(|policy| {
+            self.evaluate_action(
+                std::slice::from_ref(policy),
+                action,
+                resource,
+                context_entries,
+            )
+        });
+
+        if identity_decision == "denied" {
+            return ("denied".to_string(), Some(DecisionSource::IdentityPolicy));
+        }
+        if resource_decision.as_deref() == Some("denied") {
+            return ("denied".to_string(), Some(DecisionSource::ResourcePolicy));
+        }
+
+        let identity_allows = identity_decision == "allowed";
+        let resource_allows = resource_decision.as_deref() == Some("allowed");
+        let source = match (identity_allows, resource_allows) {
+            (true, true) => Some(DecisionSource::IdentityAndResourcePolicy),
+            (true, false) if !cross_account => Some(DecisionSource::IdentityPolicy),
+            (false, true) if !cross_account => Some(DecisionSource::ResourcePolicy),
+            _ => None,
+        };
+
+        match source {
+            Some(source) => ("allowed".to_string(), Some(source)),
+            None => ("implicitDeny".to_string(), None),
+        }
+    }
+
     /// Find all statements that match the action/resource
     fn find_matching_statements(
         &self,
         policies: &[PolicyDocument],
         action: &str,
         resource: &str,
+        context_entries: &[ContextEntry],
     ) -> Vec<StatementMatch> {
         let mut matches = Vec::new();
 
@@ -421,7 +536,7 @@ impl<S: UserStore + RoleStore + PolicyStore> EvaluationService<S> {
                 let resource_matches = statement
                     .resource
                     .iter()
-                    .any(|r| Self::matches_pattern(resource, r));
+                    .any(|r| Self::matches_resource(resource, r, context_entries));
 
                 if action_matches || resource_matches {
                     matches.push(StatementMatch {
@@ -437,6 +552,39 @@ impl<S: UserStore + RoleStore + PolicyStore> EvaluationService<S> {
         matches
     }
 
+    /// Collect condition keys required by applicable statements but absent from the context
+    fn find_missing_context_values(
+        policies: &[PolicyDocument],
+        action: &str,
+        resource: &str,
+        context_entries: &[ContextEntry],
+    ) -> Vec<String> {
+        let mut missing: Vec<String> = Vec::new();
+
+        for policy in policies {
+            for statement in &policy.statement {
+                let applies = statement
+                    .action
+                    .iter()
+                    .any(|a| Self::matches_pattern(action, a))
+                    && statement
+                        .resource
+                        .iter()
+                        .any(|r| Self::matches_resource(resource, r, context_entries));
+
+                if applies {
+                    for key in missing_context_keys(statement.condition.as_ref(), context_entries) {
+                        if !missing.contains(&key) {
+                            missing.push(key);
+                        }
+                    }
+                }
+            }
+        }
+
+        missing
+    }
+
     /// Check if a value matches a pattern (with wildcard support)
     fn matches_pattern(value: &str, pattern: &str) -> bool {
         if pattern == "*" {
@@ -449,6 +597,135 @@ impl<S: UserStore + RoleStore + PolicyStore> EvaluationService<S> {
 
         value == pattern
     }
+
+    /// Check if a target resource matches a statement's resource pattern
+    ///
+    /// `Resource: "*"` matches every target, including the literal `*` used by
+    /// actions that do not act on a specific resource (e.g. `s3:ListAllMyBuckets`).
+    /// Any other pattern is matched with `*` and `?` wildcards anywhere in the
+    /// ARN, so a pseudo-resource `*` is only covered by a bare `*` statement.
+    ///
+    /// Policy variables in the pattern (e.g. `${aws:username, 'guest'}`) are
+    /// expanded from the context first; a pattern with an unresolvable
+    /// variable never matches.
+    fn matches_resource(resource: &str, pattern: &str, context_entries: &[ContextEntry]) -> bool {
+        if pattern == "*" {
+            return true;
+        }
+
+        if resource == "*" {
+            return false;
+        }
+
+        expand_policy_variables(pattern, context_entries)
+            .is_some_and(|pattern| wildcard_match(&pattern, resource))
+    }
+    /// Compare the effective permissions of two principals (users or roles)
+    ///
+    /// Reports the action/resource grants each principal has that the other
+    /// lacks, based on their attached and inline policies and, for users, the
+    /// policies of their groups. Wildcard grants such as `s3:*` are reported
+    /// as broad grants rather than expanded into individual actions.
+    pub async fn compare_principals(
+        &self,
+        principal_a_arn: &str,
+        principal_b_arn: &str,
+    ) -> Result<PrincipalPermissionDelta> {
+        let policies_a = self.fetch_effective_policies(principal_a_arn).await?;
+        let policies_b = self.fetch_effective_policies(principal_b_arn).await?;
+
+        let (only_in_a, only_in_b) = permission_delta(&policies_a, &policies_b);
+
+        Ok(PrincipalPermissionDelta {
+            principal_a: principal_a_arn.to_string(),
+            principal_b: principal_b_arn.to_string(),
+            only_in_a,
+            only_in_b,
+        })
+    }
+
+    /// Fetch the identity-based policy documents in effect for a principal
+    async fn fetch_effective_policies(&self, principal_arn: &str) -> Result<Vec<PolicyDocument>> {
+        let (principal_type, principal_name) = self.parse_principal_arn(principal_arn)?;
+        let store = self.store.read().unwrap();
+
+        let mut documents = Vec::new();
+        match principal_type.as_str() {
+            "user" => {
+                store.get_user(&principal_name).await?.ok_or_else(|| {
+                    AmiError::ResourceNotFound {
+                        resource: format!("User: {}", principal_name),
+                    }
+                })?;
+
+                for policy_arn in store.list_attached_user_policies(&principal_name).await? {
+                    if let Some(policy) = store.get_policy(&policy_arn).await? {
+                        documents.push(policy.policy_document);
+                    }
+                }
+                for policy_name in store.list_user_policies(&principal_name).await? {
+                    if let Some(document) =
+                        store.get_user_policy(&principal_name, &policy_name).await?
+                    {
+                        documents.push(document);
+                    }
+                }
+
+                for group in store.list_groups_for_user(&principal_name).await? {
+                    for policy_arn in store
+                        .list_attached_group_policies(&group.group_name)
+                        .await?
+                    {
+                        if let Some(policy) = store.get_policy(&policy_arn).await? {
+                            documents.push(policy.policy_document);
+                        }
+                    }
+                    for policy_name in store.list_group_policies(&group.group_name).await? {
+                        if let Some(document) = store
+                            .get_group_policy(&group.group_name, &policy_name)
+                            .await?
+                        {
+                            documents.push(document);
+                        }
+                    }
+                }
+            }
+            "role" => {
+                store.get_role(&principal_name).await?.ok_or_else(|| {
+                    AmiError::ResourceNotFound {
+                        resource: format!("Role: {}", principal_name),
+                    }
+                })?;
+
+                for policy_arn in store.list_attached_role_policies(&principal_name).await? {
+                    if let Some(policy) = store.get_policy(&policy_arn).await? {
+                        documents.push(policy.policy_document);
+                    }
+                }
+                for policy_name in store.list_role_policies(&principal_name).await? {
+                    if let Some(document) =
+                        store.get_role_policy(&principal_name, &policy_name).await?
+                    {
+                        documents.push(document);
+                    }
+                }
+            }
+            _ => {
+                return Err(AmiError::InvalidParameter {
+                    message: format!("Unsupported principal type: {}", principal_type),
+                })
+            }
+        }
+
+        documents
+            .iter()
+            .map(|document| {
+                serde_json::from_str(document).map_err(|e| AmiError::InvalidParameter {
+                    message: format!("Invalid policy document: {}", e),
+                })
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -497,6 +774,9 @@ mod tests {
             action_names: vec!["s3:GetObject".to_string()],
             resource_arns: Some(vec!["arn:aws:s3:::mybucket/file.txt".to_string()]),
             context_entries: None,
+            caller_arn: None,
+            resource_owner: None,
+            resource_policy: None,
         };
 
         let response = service.simulate_custom_policy(request).await.unwrap();
@@ -505,6 +785,151 @@ mod tests {
         assert_eq!(response.evaluation_results[0].eval_decision, "allowed");
     }
 
+    #[tokio::test]
+    async fn test_simulate_cross_account_requires_resource_policy() {
+        let service = setup_service();
+
+        let identity_policy = r#"{"Version":"2012-10-17","Statement":[
+            {"Effect":"Allow","Action":"s3:GetObject","Resource":"arn:aws:s3:::shared/*"}
+        ]}"#;
+        let bucket_policy = r#"{"Version":"2012-10-17","Statement":[
+            {"Effect":"Allow","Action":"s3:GetObject","Resource":"arn:aws:s3:::shared/*"}
+        ]}"#;
+        let request =
+            |resource_owner: &str, resource_policy: Option<&str>| SimulateCustomPolicyRequest {
+                policy_input_list: vec![identity_policy.to_string()],
+                action_names: vec!["s3:GetObject".to_string()],
+                resource_arns: Some(vec!["arn:aws:s3:::shared/report.csv".to_string()]),
+                context_entries: None,
+                caller_arn: Some("arn:aws:iam::111111111111:user/alice".to_string()),
+                resource_owner: Some(resource_owner.to_string()),
+                resource_policy: resource_policy.map(str::to_string),
+            };
+        let decision =
+            |response: SimulatePolicyResponse| response.evaluation_results[0].eval_decision.clone();
+
+        // Same account: the identity policy alone is enough
+        let response = service
+            .simulate_custom_policy(request("111111111111", None))
+            .await
+            .unwrap();
+        assert_eq!(decision(response), "allowed");
+
+        // Another account: denied without a resource policy trusting the caller
+        let response = service
+            .simulate_custom_policy(request("222222222222", None))
+            .await
+            .unwrap();
+        assert_eq!(decision(response), "implicitDeny");
+
+        let response = service
+            .simulate_custom_policy(request("222222222222", Some(bucket_policy)))
+            .await
+            .unwrap();
+        assert_eq!(decision(response), "allowed");
+
+        // The resource policy alone does not grant cross-account access
+        let mut no_identity = request("222222222222", Some(bucket_policy));
+        no_identity.policy_input_list = vec![];
+        let response = service.simulate_custom_policy(no_identity).await.unwrap();
+        assert_eq!(decision(response), "implicitDeny");
+    }
+
+    #[tokio::test]
+    async fn test_simulate_custom_policy_decision_source() {
+        let service = setup_service();
+
+        let allow = r#"{"Version":"2012-10-17","Statement":[
+            {"Effect":"Allow","Action":"s3:GetObject","Resource":"*"}
+        ]}"#;
+        let deny = r#"{"Version":"2012-10-17","Statement":[
+            {"Effect":"Deny","Action":"s3:GetObject","Resource":"*"}
+        ]}"#;
+        let simulate = |identity: Option<&str>, resource_policy: Option<&str>| {
+            let request = SimulateCustomPolicyRequest {
+                policy_input_list: identity.map(str::to_string).into_iter().collect(),
+                action_names: vec!["s3:GetObject".to_string()],
+                resource_arns: Some(vec!["arn:aws:s3:::bucket/key".to_string()]),
+                context_entries: None,
+                caller_arn: Some("arn:aws:iam::111111111111:user/alice".to_string()),
+                resource_owner: Some("111111111111".to_string()),
+                resource_policy: resource_policy.map(str::to_string),
+            };
+            let service = &service;
+            async move {
+                let response = service.simulate_custom_policy(request).await.unwrap();
+                let result = &response.evaluation_results[0];
+                (result.eval_decision.clone(), result.decision_source)
+            }
+        };
+
+        assert_eq!(
+            simulate(Some(allow), None).await,
+            ("allowed".to_string(), Some(DecisionSource::IdentityPolicy))
+        );
+        // Within an account, the resource policy alone grants access
+        assert_eq!(
+            simulate(None, Some(allow)).await,
+            ("allowed".to_string(), Some(DecisionSource::ResourcePolicy))
+        );
+        assert_eq!(
+            simulate(Some(allow), Some(allow)).await,
+            (
+                "allowed".to_string(),
+                Some(DecisionSource::IdentityAndResourcePolicy)
+            )
+        );
+        // An explicit deny in the resource policy overrides the identity allow
+        assert_eq!(
+            simulate(Some(allow), Some(deny)).await,
+            ("denied".to_string(), Some(DecisionSource::ResourcePolicy))
+        );
+        assert_eq!(
+            simulate(None, None).await,
+            ("implicitDeny".to_string(), None)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_simulate_principal_cross_account() {
+        let service = setup_service();
+        let context = test_context();
+
+        let user = build_user("alice".to_string(), None, &context).unwrap();
+        service
+            .store
+            .write()
+            .unwrap()
+            .create_user(user)
+            .await
+            .unwrap();
+
+        let policy = r#"{"Version":"2012-10-17","Statement":[
+            {"Effect":"Allow","Action":"s3:GetObject","Resource":"*"}
+        ]}"#;
+        let request = |resource_policy: Option<&str>| SimulatePrincipalPolicyRequest {
+            policy_source_arn: "arn:aws:iam::123456789012:user/alice".to_string(),
+            action_names: vec!["s3:GetObject".to_string()],
+            resource_arns: None,
+            policy_input_list: Some(vec![policy.to_string()]),
+            context_entries: None,
+            resource_owner: Some("999999999999".to_string()),
+            resource_policy: resource_policy.map(str::to_string),
+        };
+
+        let response = service
+            .simulate_principal_policy(request(None))
+            .await
+            .unwrap();
+        assert_eq!(response.evaluation_results[0].eval_decision, "implicitDeny");
+
+        let response = service
+            .simulate_principal_policy(request(Some(policy)))
+            .await
+            .unwrap();
+        assert_eq!(response.evaluation_results[0].eval_decision, "allowed");
+    }
+
     #[tokio::test]
     async fn test_simulate_custom_policy_deny() {
         let service = setup_service();
@@ -525,6 +950,9 @@ mod tests {
             action_names: vec!["s3:DeleteObject".to_string()],
             resource_arns: Some(vec!["arn:aws:s3:::mybucket/file.txt".to_string()]),
             context_entries: None,
+            caller_arn: None,
+            resource_owner: None,
+            resource_policy: None,
         };
 
         let response = service.simulate_custom_policy(request).await.unwrap();
@@ -553,6 +981,9 @@ mod tests {
             action_names: vec!["s3:PutObject".to_string()],
             resource_arns: Some(vec!["arn:aws:s3:::mybucket/file.txt".to_string()]),
             context_entries: None,
+            caller_arn: None,
+            resource_owner: None,
+            resource_policy: None,
         };
 
         let response = service.simulate_custom_policy(request).await.unwrap();
@@ -581,6 +1012,9 @@ mod tests {
             action_names: vec!["s3:GetObject".to_string(), "s3:PutObject".to_string()],
             resource_arns: Some(vec!["arn:aws:s3:::anybucket/anyfile".to_string()]),
             context_entries: None,
+            caller_arn: None,
+            resource_owner: None,
+            resource_policy: None,
         };
 
         let response = service.simulate_custom_policy(request).await.unwrap();
@@ -592,6 +1026,248 @@ mod tests {
             .all(|r| r.eval_decision == "allowed"));
     }
 
+    #[tokio::test]
+    async fn test_simulate_custom_policy_wildcard_resource_matches_pseudo_resource() {
+        let service = setup_service();
+
+        let policy_doc = r#"{
+            "Version": "2012-10-17",
+            "Statement": [
+                {
+                    "Effect": "Allow",
+                    "Action": "s3:*",
+                    "Resource": "*"
+                }
+            ]
+        }"#;
+
+        let request = SimulateCustomPolicyRequest {
+            policy_input_list: vec![policy_doc.to_string()],
+            action_names: vec![
+                "s3:ListAllMyBuckets".to_string(),
+                "s3:GetObject".to_string(),
+            ],
+            resource_arns: Some(vec![
+                "*".to_string(),
+                "arn:aws:s3:::mybucket/file.txt".to_string(),
+            ]),
+            context_entries: None,
+            caller_arn: None,
+            resource_owner: None,
+            resource_policy: None,
+        };
+
+        let response = service.simulate_custom_policy(request).await.unwrap();
+
+        assert_eq!(response.evaluation_results.len(), 4);
+        let decision = |action: &str, resource: &str| {
+            response
+                .evaluation_results
+                .iter()
+                .find(|r| r.eval_action_name == action && r.eval_resource_name == resource)
+                .map(|r| r.eval_decision.clone())
+                .unwrap()
+        };
+        assert_eq!(decision("s3:ListAllMyBuckets", "*"), "allowed");
+        assert_eq!(
+            decision("s3:GetObject", "arn:aws:s3:::mybucket/file.txt"),
+            "allowed"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_simulate_custom_policy_arn_pattern_does_not_match_pseudo_resource() {
+        let service = setup_service();
+
+        let policy_doc = r#"{
+            "Version": "2012-10-17",
+            "Statement": [
+                {
+                    "Effect": "Allow",
+                    "Action": "s3:*",
+                    "Resource": "arn:aws:s3:::*"
+                }
+            ]
+        }"#;
+
+        let request = SimulateCustomPolicyRequest {
+            policy_input_list: vec![policy_doc.to_string()],
+            action_names: vec!["s3:ListAllMyBuckets".to_string()],
+            resource_arns: None,
+            context_entries: None,
+            caller_arn: None,
+            resource_owner: None,
+            resource_policy: None,
+        };
+
+        let response = service.simulate_custom_policy(request).await.unwrap();
+        assert_eq!(response.evaluation_results[0].eval_resource_name, "*");
+        assert_eq!(response.evaluation_results[0].eval_decision, "implicitDeny");
+    }
+
+    #[test]
+    fn test_matches_resource() {
+        type Service = EvaluationService<InMemoryWamiStore>;
+
+        assert!(Service::matches_resource("*", "*", &[]));
+        assert!(Service::matches_resource(
+            "arn:aws:s3:::bucket/key",
+            "*",
+            &[]
+        ));
+        assert!(Service::matches_resource(
+            "arn:aws:s3:::bucket/logs/app.log",
+            "arn:aws:s3:::bucket/*/app.log",
+            &[]
+        ));
+        assert!(!Service::matches_resource("*", "arn:aws:s3:::*", &[]));
+        assert!(!Service::matches_resource(
+            "arn:aws:s3:::other/key",
+            "arn:aws:s3:::bucket/*",
+            &[]
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_simulate_custom_policy_variable_default() {
+        let service = setup_service();
+
+        let policy_doc = r#"{
+            "Version": "2012-10-17",
+            "Statement": [
+                {
+                    "Effect": "Allow",
+                    "Action": "s3:GetObject",
+                    "Resource": "arn:aws:s3:::teams/${aws:PrincipalTag/team, 'none'}/*"
+                }
+            ]
+        }"#;
+
+        let simulate = |resource: &str, context_entries: Option<Vec<ContextEntry>>| {
+            SimulateCustomPolicyRequest {
+                policy_input_list: vec![policy_doc.to_string()],
+                action_names: vec!["s3:GetObject".to_string()],
+                resource_arns: Some(vec![resource.to_string()]),
+                context_entries,
+                caller_arn: None,
+                resource_owner: None,
+                resource_policy: None,
+            }
+        };
+
+        // Tag absent: the variable falls back to its default
+        let response = service
+            .simulate_custom_policy(simulate("arn:aws:s3:::teams/none/file.txt", None))
+            .await
+            .unwrap();
+        assert_eq!(response.evaluation_results[0].eval_decision, "allowed");
+
+        // Tag present: the variable uses the tag value
+        let platform = || {
+            Some(vec![ContextEntry {
+                context_key_name: "aws:PrincipalTag/team".to_string(),
+                context_key_values: vec!["platform".to_string()],
+                context_key_type: "string".to_string(),
+            }])
+        };
+        let response = service
+            .simulate_custom_policy(simulate("arn:aws:s3:::teams/platform/file.txt", platform()))
+            .await
+            .unwrap();
+        assert_eq!(response.evaluation_results[0].eval_decision, "allowed");
+
+        let response = service
+            .simulate_custom_policy(simulate("arn:aws:s3:::teams/none/file.txt", platform()))
+            .await
+            .unwrap();
+        assert_eq!(response.evaluation_results[0].eval_decision, "implicitDeny");
+    }
+
+    #[tokio::test]
+    async fn test_simulate_custom_policy_if_exists_condition() {
+        let service = setup_service();
+
+        let policy_doc = r#"{
+            "Version": "2012-10-17",
+            "Statement": [
+                {
+                    "Effect": "Allow",
+                    "Action": "s3:GetObject",
+                    "Resource": "*",
+                    "Condition": {
+                        "StringLikeIfExists": { "aws:PrincipalTag/team": "eng-*" }
+                    }
+                }
+            ]
+        }"#;
+
+        let simulate = |context_entries: Option<Vec<ContextEntry>>| SimulateCustomPolicyRequest {
+            policy_input_list: vec![policy_doc.to_string()],
+            action_names: vec!["s3:GetObject".to_string()],
+            resource_arns: None,
+            context_entries,
+            caller_arn: None,
+            resource_owner: None,
+            resource_policy: None,
+        };
+
+        // Key absent: the IfExists condition passes
+        let response = service
+            .simulate_custom_policy(simulate(None))
+            .await
+            .unwrap();
+        assert_eq!(response.evaluation_results[0].eval_decision, "allowed");
+        assert!(response.evaluation_results[0]
+            .missing_context_values
+            .is_empty());
+
+        // Key present with a wrong value: the base operator is enforced
+        let wrong_team = vec![ContextEntry {
+            context_key_name: "aws:PrincipalTag/team".to_string(),
+            context_key_values: vec!["sales".to_string()],
+            context_key_type: "string".to_string(),
+        }];
+        let response = service
+            .simulate_custom_policy(simulate(Some(wrong_team)))
+            .await
+            .unwrap();
+        assert_eq!(response.evaluation_results[0].eval_decision, "implicitDeny");
+    }
+
+    #[tokio::test]
+    async fn test_simulate_custom_policy_reports_missing_context_values() {
+        let service = setup_service();
+
+        let policy_doc = r#"{
+            "Version": "2012-10-17",
+            "Statement": [
+                {
+                    "Effect": "Allow",
+                    "Action": "s3:GetObject",
+                    "Resource": "*",
+                    "Condition": { "StringEquals": { "aws:username": "alice" } }
+                }
+            ]
+        }"#;
+
+        let request = SimulateCustomPolicyRequest {
+            policy_input_list: vec![policy_doc.to_string()],
+            action_names: vec!["s3:GetObject".to_string()],
+            resource_arns: None,
+            context_entries: None,
+            caller_arn: None,
+            resource_owner: None,
+            resource_policy: None,
+        };
+
+        let response = service.simulate_custom_policy(request).await.unwrap();
+        assert_eq!(response.evaluation_results[0].eval_decision, "implicitDeny");
+        assert_eq!(
+            response.evaluation_results[0].missing_context_values,
+            vec!["aws:username".to_string()]
+        );
+    }
+
     #[tokio::test]
     async fn test_simulate_principal_policy_user() {
         let service = setup_service();
@@ -620,14 +1296,15 @@ mod tests {
             ]
         }"#;
 
-        // Note: Since User model doesn't have attached_policies yet,
-        // we pass the policy via policy_input_list
+        // The user has no policies of its own; pass one via policy_input_list
         let request = SimulatePrincipalPolicyRequest {
             policy_source_arn: "arn:aws:iam::123456789012:user/alice".to_string(),
             action_names: vec!["ec2:DescribeInstances".to_string()],
             resource_arns: None,
             policy_input_list: Some(vec![policy_doc.to_string()]),
             context_entries: None,
+            resource_owner: None,
+            resource_policy: None,
         };
 
         let response = service.simulate_principal_policy(request).await.unwrap();
@@ -636,6 +1313,66 @@ mod tests {
         assert_eq!(response.evaluation_results[0].eval_decision, "allowed");
     }
 
+    #[tokio::test]
+    async fn test_simulate_principal_policy_resolves_group_policies() {
+        use crate::store::traits::{GroupStore, PolicyStore, UserStore};
+        use crate::wami::identity::group::builder::build_group;
+        use crate::wami::policies::policy::builder::build_policy;
+
+        let service = setup_service();
+        let context = test_context();
+
+        let user = build_user("alice".to_string(), None, &context).unwrap();
+        let user_arn = user.arn.clone();
+        let ec2_read = build_policy(
+            "Ec2Read".to_string(),
+            r#"{"Version":"2012-10-17","Statement":[{"Effect":"Allow","Action":"ec2:Describe*","Resource":"*"}]}"#
+                .to_string(),
+            None,
+            None,
+            None,
+            &context,
+        )
+        .unwrap();
+        let ec2_read_arn = ec2_read.arn.clone();
+        {
+            let mut store = service.store.write().unwrap();
+            store.create_user(user).await.unwrap();
+            store
+                .create_group(build_group("operators".to_string(), None, &context).unwrap())
+                .await
+                .unwrap();
+            store.create_policy(ec2_read).await.unwrap();
+            store
+                .attach_group_policy("operators", &ec2_read_arn)
+                .await
+                .unwrap();
+            store.add_user_to_group("operators", "alice").await.unwrap();
+        }
+
+        let request = SimulatePrincipalPolicyRequest {
+            policy_source_arn: user_arn,
+            action_names: vec![
+                "ec2:DescribeInstances".to_string(),
+                "ec2:TerminateInstances".to_string(),
+            ],
+            resource_arns: None,
+            policy_input_list: None,
+            context_entries: None,
+            resource_owner: None,
+            resource_policy: None,
+        };
+
+        let response = service.simulate_principal_policy(request).await.unwrap();
+
+        let decisions: Vec<_> = response
+            .evaluation_results
+            .iter()
+            .map(|result| result.eval_decision.as_str())
+            .collect();
+        assert_eq!(decisions, ["allowed", "implicitDeny"]);
+    }
+
     #[tokio::test]
     async fn test_parse_principal_arn_user() {
         let service = setup_service();
@@ -673,4 +1410,72 @@ mod tests {
         // Path is /department/team/ and name is alice
         assert_eq!(principal_name, "alice");
     }
+
+    #[tokio::test]
+    async fn test_compare_principals_reports_broad_grant() {
+        use crate::store::traits::{PolicyStore, UserStore};
+        use crate::wami::policies::evaluation::PermissionGrant;
+        use crate::wami::policies::policy::builder::build_policy;
+
+        let store = Arc::new(RwLock::new(InMemoryWamiStore::default()));
+        let service = EvaluationService::new(store.clone(), "123456789012".to_string());
+        let context = test_context();
+
+        let alice = build_user("alice".to_string(), None, &context).unwrap();
+        let bob = build_user("bob".to_string(), None, &context).unwrap();
+        let (alice_arn, bob_arn) = (alice.arn.clone(), bob.arn.clone());
+        let s3_full = build_policy(
+            "S3Full".to_string(),
+            r#"{"Version":"2012-10-17","Statement":[{"Effect":"Allow","Action":"s3:*","Resource":"*"}]}"#
+                .to_string(),
+            None,
+            None,
+            None,
+            &context,
+        )
+        .unwrap();
+        let s3_full_arn = s3_full.arn.clone();
+        {
+            let mut store = store.write().unwrap();
+            store.create_user(alice).await.unwrap();
+            store.create_user(bob).await.unwrap();
+            store.create_policy(s3_full).await.unwrap();
+            store
+                .attach_user_policy("alice", &s3_full_arn)
+                .await
+                .unwrap();
+            store
+                .put_user_policy(
+                    "bob",
+                    "ReadObjects",
+                    r#"{"Version":"2012-10-17","Statement":[{"Effect":"Allow","Action":"s3:GetObject","Resource":"*"}]}"#
+                        .to_string(),
+                )
+                .await
+                .unwrap();
+        }
+
+        let delta = service
+            .compare_principals(&alice_arn, &bob_arn)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            delta.only_in_a,
+            vec![PermissionGrant {
+                action: "s3:*".to_string(),
+                resource: "*".to_string(),
+            }]
+        );
+        assert!(delta.only_in_a[0].is_broad());
+        assert!(delta.only_in_b.is_empty());
+        assert!(!delta.is_equivalent());
+
+        // Comparing a principal with itself yields no difference
+        let same = service
+            .compare_principals(&alice_arn, &alice_arn)
+            .await
+            .unwrap();
+        assert!(same.is_equivalent());
+    }
 }
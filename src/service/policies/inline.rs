@@ -4,6 +4,10 @@
 
 use crate::error::{AmiError, Result};
 use crate::store::traits::{GroupStore, RoleStore, UserStore};
+use crate::wami::policies::inline::operations::{
+    validate_inline_policy_document, validate_inline_policy_size, GROUP_INLINE_POLICY_SIZE_LIMIT,
+    ROLE_INLINE_POLICY_SIZE_LIMIT, USER_INLINE_POLICY_SIZE_LIMIT,
+};
 use crate::wami::policies::inline::*;
 use std::sync::{Arc, RwLock};
 
@@ -38,12 +42,27 @@ where
                 resource: format!("User: {}", request.user_name),
             })?;
 
-        // Validate policy document is valid JSON
-        serde_json::from_str::<serde_json::Value>(&request.policy_document).map_err(|e| {
-            AmiError::InvalidParameter {
-                message: format!("Invalid policy document JSON: {}", e),
+        // Validate policy document is a well-formed policy
+        validate_inline_policy_document(&request.policy_document)?;
+
+        // The principal's inline policies, with this one added or replaced, must fit the quota
+        let mut documents = Vec::new();
+        for policy_name in store.list_user_policies(&request.user_name).await? {
+            if policy_name != request.policy_name {
+                if let Some(document) = store
+                    .get_user_policy(&request.user_name, &policy_name)
+                    .await?
+                {
+                    documents.push(document);
+                }
             }
-        })?;
+        }
+        documents.push(request.policy_document.clone());
+        validate_inline_policy_size(
+            "User",
+            documents.iter().map(String::as_str),
+            USER_INLINE_POLICY_SIZE_LIMIT,
+        )?;
 
         // Put the inline policy
         store
@@ -161,12 +180,27 @@ where
                 resource: format!("Group: {}", request.group_name),
             })?;
 
-        // Validate policy document is valid JSON
-        serde_json::from_str::<serde_json::Value>(&request.policy_document).map_err(|e| {
-            AmiError::InvalidParameter {
-                message: format!("Invalid policy document JSON: {}", e),
+        // Validate policy document is a well-formed policy
+        validate_inline_policy_document(&request.policy_document)?;
+
+        // The principal's inline policies, with this one added or replaced, must fit the quota
+        let mut documents = Vec::new();
+        for policy_name in store.list_group_policies(&request.group_name).await? {
+            if policy_name != request.policy_name {
+                if let Some(document) = store
+                    .get_group_policy(&request.group_name, &policy_name)
+                    .await?
+                {
+                    documents.push(document);
+                }
             }
-        })?;
+        }
+        documents.push(request.policy_document.clone());
+        validate_inline_policy_size(
+            "Group",
+            documents.iter().map(String::as_str),
+            GROUP_INLINE_POLICY_SIZE_LIMIT,
+        )?;
 
         // Put the inline policy
         store
@@ -284,12 +318,27 @@ where
                 resource: format!("Role: {}", request.role_name),
             })?;
 
-        // Validate policy document is valid JSON
-        serde_json::from_str::<serde_json::Value>(&request.policy_document).map_err(|e| {
-            AmiError::InvalidParameter {
-                message: format!("Invalid policy document JSON: {}", e),
+        // Validate policy document is a well-formed policy
+        validate_inline_policy_document(&request.policy_document)?;
+
+        // The principal's inline policies, with this one added or replaced, must fit the quota
+        let mut documents = Vec::new();
+        for policy_name in store.list_role_policies(&request.role_name).await? {
+            if policy_name != request.policy_name {
+                if let Some(document) = store
+                    .get_role_policy(&request.role_name, &policy_name)
+                    .await?
+                {
+                    documents.push(document);
+                }
             }
-        })?;
+        }
+        documents.push(request.policy_document.clone());
+        validate_inline_policy_size(
+            "Role",
+            documents.iter().map(String::as_str),
+            ROLE_INLINE_POLICY_SIZE_LIMIT,
+        )?;
 
         // Put the inline policy
         store
@@ -582,4 +631,255 @@ mod tests {
             AmiError::InvalidParameter { .. }
         ));
     }
+
+    #[tokio::test]
+    async fn test_group_policy_lifecycle() {
+        let store = Arc::new(RwLock::new(InMemoryWamiStore::new()));
+        let service = InlinePolicyService::new(store.clone());
+        let context = create_test_context().await;
+
+        let group = build_group("developers".to_string(), None, &context).unwrap();
+        store.write().unwrap().create_group(group).await.unwrap();
+
+        let document = r#"{"Version":"2012-10-17","Statement":[]}"#.to_string();
+        service
+            .put_group_policy(PutGroupPolicyRequest {
+                group_name: "developers".to_string(),
+                policy_name: "ReadOnly".to_string(),
+                policy_document: document.clone(),
+            })
+            .await
+            .unwrap();
+
+        let response = service
+            .get_group_policy(GetGroupPolicyRequest {
+                group_name: "developers".to_string(),
+                policy_name: "ReadOnly".to_string(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(response.group_name, "developers");
+        assert_eq!(response.policy_document, document);
+
+        let response = service
+            .list_group_policies(ListGroupPoliciesRequest {
+                group_name: "developers".to_string(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(response.policy_names, vec!["ReadOnly".to_string()]);
+
+        service
+            .delete_group_policy(DeleteGroupPolicyRequest {
+                group_name: "developers".to_string(),
+                policy_name: "ReadOnly".to_string(),
+            })
+            .await
+            .unwrap();
+        let result = service
+            .get_group_policy(GetGroupPolicyRequest {
+                group_name: "developers".to_string(),
+                policy_name: "ReadOnly".to_string(),
+            })
+            .await;
+        assert!(matches!(result, Err(AmiError::ResourceNotFound { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_group_member_inherits_inline_policy() {
+        use crate::service::auth::AuthorizationService;
+
+        let store = Arc::new(RwLock::new(InMemoryWamiStore::new()));
+        let service = InlinePolicyService::new(store.clone());
+        let context = create_test_context().await;
+
+        {
+            let mut guard = store.write().unwrap();
+            let user = build_user("admin".to_string(), None, &context).unwrap();
+            guard.create_user(user).await.unwrap();
+            let group = build_group("developers".to_string(), None, &context).unwrap();
+            guard.create_group(group).await.unwrap();
+            guard
+                .add_user_to_group("developers", "admin")
+                .await
+                .unwrap();
+        }
+
+        service
+            .put_group_policy(PutGroupPolicyRequest {
+                group_name: "developers".to_string(),
+                policy_name: "ReadUsers".to_string(),
+                policy_document: r#"{"Version":"2012-10-17","Statement":[{"Effect":"Allow","Action":["iam:GetUser"],"Resource":["*"]}]}"#.to_string(),
+            })
+            .await
+            .unwrap();
+
+        let snapshot = store.read().unwrap().clone();
+        let authz = AuthorizationService::new(Arc::new(tokio::sync::RwLock::new(snapshot)));
+        let resource = WamiArn::builder()
+            .service(crate::arn::Service::Iam)
+            .tenant_path(TenantPath::single(0))
+            .wami_instance("123456789012")
+            .resource("user", "bob")
+            .build()
+            .unwrap();
+
+        assert!(authz
+            .authorize(&context, "iam:GetUser", &resource)
+            .await
+            .unwrap());
+        assert!(!authz
+            .authorize(&context, "iam:DeleteUser", &resource)
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_oversized_group_policy_rejected() {
+        let store = Arc::new(RwLock::new(InMemoryWamiStore::new()));
+        let service = InlinePolicyService::new(store.clone());
+        let context = create_test_context().await;
+
+        let group = build_group("developers".to_string(), None, &context).unwrap();
+        store.write().unwrap().create_group(group).await.unwrap();
+
+        let oversized = format!(
+            r#"{{"Version":"2012-10-17","Statement":[{{"Effect":"Allow","Action":["s3:GetObject"],"Resource":["arn:aws:s3:::{}"]}}]}}"#,
+            "a".repeat(GROUP_INLINE_POLICY_SIZE_LIMIT)
+        );
+        let result = service
+            .put_group_policy(PutGroupPolicyRequest {
+                group_name: "developers".to_string(),
+                policy_name: "TooBig".to_string(),
+                policy_document: oversized,
+            })
+            .await;
+        assert!(matches!(
+            result,
+            Err(AmiError::ResourceLimitExceeded {
+                limit: GROUP_INLINE_POLICY_SIZE_LIMIT,
+                ..
+            })
+        ));
+        assert!(store
+            .read()
+            .unwrap()
+            .list_group_policies("developers")
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_inline_policy_size_counts_all_user_policies() {
+        let store = Arc::new(RwLock::new(InMemoryWamiStore::new()));
+        let service = InlinePolicyService::new(store.clone());
+        let context = create_test_context().await;
+
+        let user = build_user("alice".to_string(), None, &context).unwrap();
+        store.write().unwrap().create_user(user).await.unwrap();
+
+        // Each document fits on its own, but two of them exceed the user quota
+        let document = format!(
+            r#"{{"Version":"2012-10-17","Statement":[{{"Effect":"Allow","Action":"s3:GetObject","Resource":"arn:aws:s3:::{}"}}]}}"#,
+            "a".repeat(1400)
+        );
+        let put = |policy_name: &str| PutUserPolicyRequest {
+            user_name: "alice".to_string(),
+            policy_name: policy_name.to_string(),
+            policy_document: document.clone(),
+        };
+        service.put_user_policy(put("First")).await.unwrap();
+
+        // Replacing a policy does not count its previous version
+        service.put_user_policy(put("First")).await.unwrap();
+
+        let result = service.put_user_policy(put("Second")).await;
+        assert!(matches!(
+            result,
+            Err(AmiError::ResourceLimitExceeded {
+                limit: USER_INLINE_POLICY_SIZE_LIMIT,
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_user_inline_policy_round_trip_and_authorization() {
+        use crate::service::auth::AuthorizationService;
+
+        let store = Arc::new(RwLock::new(InMemoryWamiStore::new()));
+        let service = InlinePolicyService::new(store.clone());
+        let context = create_test_context().await;
+
+        let user = build_user("admin".to_string(), None, &context).unwrap();
+        store.write().unwrap().create_user(user).await.unwrap();
+
+        // The document is returned exactly as it was put, whitespace included
+        let document = r#"{
+  "Version": "2012-10-17",
+  "Statement": [{"Effect": "Allow", "Action": "iam:GetUser", "Resource": "*"}]
+}"#;
+        for policy_name in ["ReadUsers", "Empty"] {
+            service
+                .put_user_policy(PutUserPolicyRequest {
+                    user_name: "admin".to_string(),
+                    policy_name: policy_name.to_string(),
+                    policy_document: if policy_name == "Empty" {
+                        r#"{"Version":"2012-10-17","Statement":[]}"#.to_string()
+                    } else {
+                        document.to_string()
+                    },
+                })
+                .await
+                .unwrap();
+        }
+
+        let response = service
+            .get_user_policy(GetUserPolicyRequest {
+                user_name: "admin".to_string(),
+                policy_name: "ReadUsers".to_string(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(response.policy_document, document);
+
+        let mut names = service
+            .list_user_policies(ListUserPoliciesRequest {
+                user_name: "admin".to_string(),
+            })
+            .await
+            .unwrap()
+            .policy_names;
+        names.sort();
+        assert_eq!(names, vec!["Empty".to_string(), "ReadUsers".to_string()]);
+
+        // A JSON value that is not a policy document is rejected
+        let result = service
+            .put_user_policy(PutUserPolicyRequest {
+                user_name: "admin".to_string(),
+                policy_name: "NotAPolicy".to_string(),
+                policy_document: r#"{"Statement":"iam:*"}"#.to_string(),
+            })
+            .await;
+        assert!(matches!(result, Err(AmiError::InvalidParameter { .. })));
+
+        let snapshot = store.read().unwrap().clone();
+        let authz = AuthorizationService::new(Arc::new(tokio::sync::RwLock::new(snapshot)));
+        let resource = WamiArn::builder()
+            .service(crate::arn::Service::Iam)
+            .tenant_path(TenantPath::single(0))
+            .wami_instance("123456789012")
+            .resource("user", "bob")
+            .build()
+            .unwrap();
+        assert!(authz
+            .authorize(&context, "iam:GetUser", &resource)
+            .await
+            .unwrap());
+        assert!(!authz
+            .authorize(&context, "iam:DeleteUser", &resource)
+            .await
+            .unwrap());
+    }
 }
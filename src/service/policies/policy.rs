@@ -4,10 +4,13 @@
 
 use crate::context::WamiContext;
 use crate::error::Result;
+use crate::service::audit::{self, AuditSink};
+use crate::store::memory::pagination::paginate;
 use crate::store::traits::PolicyStore;
+use crate::telemetry;
 use crate::wami::policies::policy::{
-    builder as policy_builder, CreatePolicyRequest, ListPoliciesRequest, Policy,
-    UpdatePolicyRequest,
+    builder as policy_builder, validate_managed_policy_document, CreatePolicyRequest,
+    ListPoliciesRequest, Policy, PolicyVersion, UpdatePolicyRequest,
 };
 use std::sync::{Arc, RwLock};
 
@@ -16,20 +19,67 @@ use std::sync::{Arc, RwLock};
 /// Provides high-level operations for policy management.
 pub struct PolicyService<S> {
     store: Arc<RwLock<S>>,
+    audit_sink: Option<Arc<dyn AuditSink>>,
 }
 
 impl<S: PolicyStore> PolicyService<S> {
     /// Create a new PolicyService
     pub fn new(store: Arc<RwLock<S>>) -> Self {
-        Self { store }
+        Self {
+            store,
+            audit_sink: None,
+        }
+    }
+
+    /// Returns a new service instance reporting policy creation, update and deletion to `sink`
+    pub fn with_audit_sink(&self, sink: Arc<dyn AuditSink>) -> Self {
+        Self {
+            store: self.store.clone(),
+            audit_sink: Some(sink),
+        }
     }
 
     /// Create a new policy
+    ///
+    /// Returns `InvalidParameter` if the policy document is malformed, too
+    /// large, lacks a `Version`, or has no statements.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                operation = "CreatePolicy",
+                tenant = %context.tenant_path(),
+                resource = %request.policy_name,
+            )
+        )
+    )]
     pub async fn create_policy(
         &self,
         context: &WamiContext,
         request: CreatePolicyRequest,
     ) -> Result<Policy> {
+        let policy_name = request.policy_name.clone();
+        let result = self.store_new_policy(context, request).await;
+        audit::record_result(
+            self.audit_sink.as_ref(),
+            "CreatePolicy",
+            Some(context),
+            result
+                .as_ref()
+                .map_or(policy_name, |policy| policy.wami_arn.to_string()),
+            &result,
+        );
+        result
+    }
+
+    async fn store_new_policy(
+        &self,
+        context: &WamiContext,
+        request: CreatePolicyRequest,
+    ) -> Result<Policy> {
+        validate_managed_policy_document(&request.policy_document)?;
+
         // Use wami builder to create policy (includes tags)
         let policy = policy_builder::build_policy(
             request.policy_name,
@@ -41,16 +91,47 @@ impl<S: PolicyStore> PolicyService<S> {
         )?;
 
         // Store it
-        self.store.write().unwrap().create_policy(policy).await
+        let policy = self.store.write().unwrap().create_policy(policy).await?;
+        telemetry::record_created("policy", &policy.arn);
+        Ok(policy)
     }
 
     /// Get a policy by ARN
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(operation = "GetPolicy", resource = %policy_arn))
+    )]
     pub async fn get_policy(&self, policy_arn: &str) -> Result<Option<Policy>> {
         self.store.read().unwrap().get_policy(policy_arn).await
     }
 
     /// Update a policy
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                operation = "UpdatePolicy",
+                resource = %request.policy_arn,
+            )
+        )
+    )]
     pub async fn update_policy(&self, request: UpdatePolicyRequest) -> Result<Policy> {
+        let policy_arn = request.policy_arn.clone();
+        let result = self.apply_policy_update(request).await;
+        audit::record_result(
+            self.audit_sink.as_ref(),
+            "UpdatePolicy",
+            None,
+            result
+                .as_ref()
+                .map_or(policy_arn, |policy| policy.wami_arn.to_string()),
+            &result,
+        );
+        result
+    }
+
+    async fn apply_policy_update(&self, request: UpdatePolicyRequest) -> Result<Policy> {
         // Get existing policy
         let policy = self
             .store
@@ -74,21 +155,152 @@ impl<S: PolicyStore> PolicyService<S> {
             .await
     }
 
+    /// Create a new version of a policy
+    ///
+    /// A policy keeps at most five versions; creating a sixth fails with
+    /// `ResourceLimitExceeded`. When `set_as_default` is true the new version
+    /// is the one used by policy evaluation.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                operation = "CreatePolicyVersion",
+                resource = %policy_arn,
+            )
+        )
+    )]
+    pub async fn create_policy_version(
+        &self,
+        policy_arn: &str,
+        policy_document: String,
+        set_as_default: bool,
+    ) -> Result<PolicyVersion> {
+        let policy = self.require_policy(policy_arn).await?;
+        let (policy, version) =
+            policy_builder::add_policy_version(policy, policy_document, set_as_default)?;
+        self.store.write().unwrap().update_policy(policy).await?;
+        Ok(version)
+    }
+
+    /// List the versions of a policy, oldest first
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                operation = "ListPolicyVersions",
+                resource = %policy_arn,
+            )
+        )
+    )]
+    pub async fn list_policy_versions(&self, policy_arn: &str) -> Result<Vec<PolicyVersion>> {
+        let policy = self.require_policy(policy_arn).await?;
+        Ok(policy_builder::policy_versions(policy))
+    }
+
+    /// Make an existing version the default version of a policy
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                operation = "SetDefaultPolicyVersion",
+                resource = %policy_arn,
+            )
+        )
+    )]
+    pub async fn set_default_policy_version(
+        &self,
+        policy_arn: &str,
+        version_id: &str,
+    ) -> Result<Policy> {
+        let policy = self.require_policy(policy_arn).await?;
+        let policy = policy_builder::set_default_policy_version(policy, version_id)?;
+        self.store.write().unwrap().update_policy(policy).await
+    }
+
+    /// Delete a non-default version of a policy
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                operation = "DeletePolicyVersion",
+                resource = %policy_arn,
+            )
+        )
+    )]
+    pub async fn delete_policy_version(&self, policy_arn: &str, version_id: &str) -> Result<()> {
+        let policy = self.require_policy(policy_arn).await?;
+        let policy = policy_builder::delete_policy_version(policy, version_id)?;
+        self.store.write().unwrap().update_policy(policy).await?;
+        Ok(())
+    }
+
+    /// Fetch a policy, failing if it does not exist
+    async fn require_policy(&self, policy_arn: &str) -> Result<Policy> {
+        self.store
+            .read()
+            .unwrap()
+            .get_policy(policy_arn)
+            .await?
+            .ok_or_else(|| crate::error::AmiError::ResourceNotFound {
+                resource: format!("Policy: {}", policy_arn),
+            })
+    }
+
     /// Delete a policy
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(operation = "DeletePolicy", resource = %policy_arn))
+    )]
     pub async fn delete_policy(&self, policy_arn: &str) -> Result<()> {
-        self.store.write().unwrap().delete_policy(policy_arn).await
+        let mut store = self.store.write().unwrap();
+        let existing = store.get_policy(policy_arn).await?;
+        let result = store.delete_policy(policy_arn).await;
+        audit::record_result(
+            self.audit_sink.as_ref(),
+            "DeletePolicy",
+            None,
+            existing.map_or_else(
+                || policy_arn.to_string(),
+                |policy| policy.wami_arn.to_string(),
+            ),
+            &result,
+        );
+        result
     }
 
     /// List policies with optional filtering
+    ///
+    /// Only policies of the caller's tenant are returned, plus those of its
+    /// descendant tenants when `include_descendants` is set. Root callers see
+    /// policies of every tenant.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(operation = "ListPolicies", tenant = %context.tenant_path())
+        )
+    )]
     pub async fn list_policies(
         &self,
+        context: &WamiContext,
         request: ListPoliciesRequest,
     ) -> Result<(Vec<Policy>, bool, Option<String>)> {
-        self.store
+        let (mut policies, _, _) = self
+            .store
             .read()
             .unwrap()
-            .list_policies(request.scope.as_deref(), request.pagination.as_ref())
-            .await
+            .list_policies(request.scope.as_deref(), None)
+            .await?;
+        policies.retain(|policy| {
+            context.lists_tenant(&policy.wami_arn.tenant_path, request.include_descendants)
+        });
+        Ok(paginate(policies, request.pagination.as_ref(), |policy| {
+            &policy.arn
+        }))
     }
 }
 
@@ -96,6 +308,7 @@ impl<S: PolicyStore> PolicyService<S> {
 mod tests {
     use super::*;
     use crate::arn::{TenantPath, WamiArn};
+    use crate::error::AmiError;
     use crate::store::memory::InMemoryWamiStore;
 
     fn setup_service() -> PolicyService<InMemoryWamiStore> {
@@ -143,6 +356,49 @@ mod tests {
         assert_eq!(retrieved.unwrap().policy_name, "S3FullAccess");
     }
 
+    #[tokio::test]
+    async fn test_create_policy_validates_document() {
+        let service = setup_service();
+        let context = test_context();
+        let request = |policy_document: String| CreatePolicyRequest {
+            policy_name: "Checked".to_string(),
+            policy_document,
+            path: None,
+            description: None,
+            tags: None,
+        };
+
+        let oversized = format!(
+            r#"{{"Version":"2012-10-17","Statement":[{{"Effect":"Allow","Action":"s3:GetObject","Resource":"arn:aws:s3:::{}"}}]}}"#,
+            "b".repeat(6144)
+        );
+        for document in [oversized, r#"{"Version":"2012-10-17","#.to_string()] {
+            let result = service.create_policy(&context, request(document)).await;
+            assert!(matches!(result, Err(AmiError::InvalidParameter { .. })));
+        }
+        let (policies, _, _) = service
+            .list_policies(
+                &test_context(),
+                ListPoliciesRequest {
+                    scope: None,
+                    only_attached: None,
+                    path_prefix: None,
+                    pagination: None,
+                    include_descendants: false,
+                },
+            )
+            .await
+            .unwrap();
+        assert!(policies.is_empty());
+
+        let valid = r#"{"Version":"2012-10-17","Statement":[{"Effect":"Allow","Action":"s3:GetObject","Resource":"*"}]}"#;
+        let policy = service
+            .create_policy(&context, request(valid.to_string()))
+            .await
+            .unwrap();
+        assert_eq!(policy.policy_document, valid);
+    }
+
     #[tokio::test]
     async fn test_update_policy() {
         let service = setup_service();
@@ -177,7 +433,7 @@ mod tests {
     async fn test_delete_policy() {
         let service = setup_service();
 
-        let policy_doc = r#"{"Version":"2012-10-17","Statement":[]}"#;
+        let policy_doc = r#"{"Version":"2012-10-17","Statement":[{"Effect":"Allow","Action":"s3:GetObject","Resource":"*"}]}"#;
         let request = CreatePolicyRequest {
             policy_name: "TempPolicy".to_string(),
             policy_document: policy_doc.to_string(),
@@ -200,7 +456,7 @@ mod tests {
 
         // Create multiple policies
         for i in 0..3 {
-            let policy_doc = r#"{"Version":"2012-10-17","Statement":[]}"#;
+            let policy_doc = r#"{"Version":"2012-10-17","Statement":[{"Effect":"Allow","Action":"s3:GetObject","Resource":"*"}]}"#;
             let request = CreatePolicyRequest {
                 policy_name: format!("Policy{}", i),
                 policy_document: policy_doc.to_string(),
@@ -217,8 +473,113 @@ mod tests {
             only_attached: None,
             path_prefix: Some("/test/".to_string()),
             pagination: None,
+            include_descendants: false,
         };
-        let (policies, _, _) = service.list_policies(list_request).await.unwrap();
+        let (policies, _, _) = service
+            .list_policies(&test_context(), list_request)
+            .await
+            .unwrap();
         assert_eq!(policies.len(), 3);
     }
+
+    #[tokio::test]
+    async fn test_policy_versions_default_and_cap() {
+        use crate::wami::policies::policy::MAX_POLICY_VERSIONS;
+
+        let service = setup_service();
+        let context = test_context();
+        let document = |action: &str| {
+            format!(
+                r#"{{"Version":"2012-10-17","Statement":[{{"Effect":"Allow","Action":"{}","Resource":"*"}}]}}"#,
+                action
+            )
+        };
+        let policy = service
+            .create_policy(
+                &context,
+                CreatePolicyRequest {
+                    policy_name: "Versioned".to_string(),
+                    policy_document: document("s3:GetObject"),
+                    path: None,
+                    description: None,
+                    tags: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        // A non-default version leaves the evaluated document untouched
+        let v2 = service
+            .create_policy_version(&policy.arn, document("s3:PutObject"), false)
+            .await
+            .unwrap();
+        assert_eq!(v2.version_id, "v2");
+        assert!(!v2.is_default_version);
+        let current = service.get_policy(&policy.arn).await.unwrap().unwrap();
+        assert_eq!(current.default_version_id, "v1");
+        assert_eq!(current.policy_document, document("s3:GetObject"));
+
+        let v3 = service
+            .create_policy_version(&policy.arn, document("s3:DeleteObject"), true)
+            .await
+            .unwrap();
+        assert!(v3.is_default_version);
+        let current = service.get_policy(&policy.arn).await.unwrap().unwrap();
+        assert_eq!(current.default_version_id, "v3");
+        assert_eq!(current.policy_document, document("s3:DeleteObject"));
+
+        // Flip the default back to v2
+        let current = service
+            .set_default_policy_version(&policy.arn, "v2")
+            .await
+            .unwrap();
+        assert_eq!(current.policy_document, document("s3:PutObject"));
+        let versions = service.list_policy_versions(&policy.arn).await.unwrap();
+        let defaults: Vec<_> = versions
+            .iter()
+            .filter(|version| version.is_default_version)
+            .map(|version| version.version_id.as_str())
+            .collect();
+        assert_eq!(defaults, vec!["v2"]);
+        assert!(matches!(
+            service.set_default_policy_version(&policy.arn, "v9").await,
+            Err(AmiError::ResourceNotFound { .. })
+        ));
+
+        // The default version cannot be deleted, others can
+        assert!(matches!(
+            service.delete_policy_version(&policy.arn, "v2").await,
+            Err(AmiError::DeleteConflict { .. })
+        ));
+        service
+            .delete_policy_version(&policy.arn, "v1")
+            .await
+            .unwrap();
+        assert_eq!(
+            service
+                .list_policy_versions(&policy.arn)
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+
+        // Fill up to the cap, then the next version is rejected
+        for _ in 2..MAX_POLICY_VERSIONS {
+            service
+                .create_policy_version(&policy.arn, document("s3:ListBucket"), false)
+                .await
+                .unwrap();
+        }
+        let result = service
+            .create_policy_version(&policy.arn, document("s3:ListBucket"), true)
+            .await;
+        assert!(matches!(
+            result,
+            Err(AmiError::ResourceLimitExceeded { limit: 5, .. })
+        ));
+        let current = service.get_policy(&policy.arn).await.unwrap().unwrap();
+        assert_eq!(current.versions.len(), MAX_POLICY_VERSIONS);
+        assert_eq!(current.default_version_id, "v2");
+    }
 }
@@ -0,0 +1,48 @@
+//! Quota Warnings
+//!
+//! Services given a [`QuotaObserver`] (via their `with_quota_observer` method)
+//! notify it when a tenant's usage of a resource crosses a warn threshold,
+//! expressed as a fraction of the tenant's quota. The notification does not
+//! affect whether the operation is allowed.
+
+use crate::provider::ResourceType;
+use crate::wami::tenant::TenantId;
+use std::sync::Arc;
+
+/// Receiver of quota warnings
+pub trait QuotaObserver: Send + Sync {
+    /// `tenant_id`'s usage of `resource_type` reached the warn threshold
+    fn threshold_crossed(&self, tenant_id: &TenantId, resource_type: ResourceType);
+}
+
+/// An observer with the fraction of the quota at which it is notified
+#[derive(Clone)]
+pub(crate) struct QuotaWarning {
+    observer: Arc<dyn QuotaObserver>,
+    threshold: f64,
+}
+
+impl QuotaWarning {
+    pub(crate) fn new(observer: Arc<dyn QuotaObserver>, threshold: f64) -> Self {
+        Self {
+            observer,
+            threshold,
+        }
+    }
+
+    /// Notify the observer if usage going from `before` to `after` crosses
+    /// the threshold of `limit`
+    pub(crate) fn check(
+        &self,
+        tenant_id: &TenantId,
+        resource_type: ResourceType,
+        limit: usize,
+        before: usize,
+        after: usize,
+    ) {
+        let threshold = self.threshold * limit as f64;
+        if (before as f64) < threshold && (after as f64) >= threshold {
+            self.observer.threshold_crossed(tenant_id, resource_type);
+        }
+    }
+}
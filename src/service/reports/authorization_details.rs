@@ -0,0 +1,305 @@
+//! Authorization Details Service
+//!
+//! Exports the full identity graph of an account for auditing, in the shape
+//! of the AWS `GetAccountAuthorizationDetails` response.
+
+use crate::error::Result;
+use crate::store::traits::{GroupStore, PolicyStore, RoleStore, UserStore};
+use crate::wami::policies::policy::builder::policy_versions;
+use crate::wami::policies::policy::Policy;
+use crate::wami::reports::authorization_details::{
+    AccountAuthorizationDetails, AttachedPolicyDetail, GroupDetail, InlinePolicyDetail,
+    ManagedPolicyDetail, RoleDetail, UserDetail,
+};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// Service exporting every principal and managed policy with their policies
+pub struct AuthorizationDetailsService<S> {
+    store: Arc<RwLock<S>>,
+}
+
+impl<S> AuthorizationDetailsService<S>
+where
+    S: UserStore + GroupStore + RoleStore + PolicyStore,
+{
+    /// Create a new AuthorizationDetailsService
+    pub fn new(store: Arc<RwLock<S>>) -> Self {
+        Self { store }
+    }
+
+    /// Export every user, group, role and managed policy
+    ///
+    /// Users list their group memberships, attached managed policies and
+    /// inline policies; groups and roles list their attached and inline
+    /// policies, and roles their trust policy. Managed policies carry all
+    /// their versions, with the default one flagged.
+    pub async fn get_account_authorization_details(&self) -> Result<AccountAuthorizationDetails> {
+        let store = self.store.read().unwrap();
+
+        let (policies, _, _) = store.list_policies(None, None).await?;
+        let policy_names: HashMap<&str, &str> = policies
+            .iter()
+            .map(|policy| (policy.arn.as_str(), policy.policy_name.as_str()))
+            .collect();
+        let attached = |arns: Vec<String>| attached_policies(&policy_names, arns);
+
+        let (users, _, _) = store.list_users(None, None).await?;
+        let mut user_detail_list = Vec::with_capacity(users.len());
+        for user in &users {
+            let groups = store.list_groups_for_user(&user.user_name).await?;
+            let mut inline = Vec::new();
+            for policy_name in store.list_user_policies(&user.user_name).await? {
+                if let Some(document) = store.get_user_policy(&user.user_name, &policy_name).await?
+                {
+                    inline.push(inline_policy(policy_name, document));
+                }
+            }
+            user_detail_list.push(UserDetail::new(
+                user,
+                groups.into_iter().map(|group| group.group_name).collect(),
+                attached(store.list_attached_user_policies(&user.user_name).await?),
+                inline,
+            ));
+        }
+
+        let (groups, _, _) = store.list_groups(None, None).await?;
+        let mut group_detail_list = Vec::with_capacity(groups.len());
+        for group in &groups {
+            let mut inline = Vec::new();
+            for policy_name in store.list_group_policies(&group.group_name).await? {
+                if let Some(document) = store
+                    .get_group_policy(&group.group_name, &policy_name)
+                    .await?
+                {
+                    inline.push(inline_policy(policy_name, document));
+                }
+            }
+            group_detail_list.push(GroupDetail::new(
+                group,
+                attached(
+                    store
+                        .list_attached_group_policies(&group.group_name)
+                        .await?,
+                ),
+                inline,
+            ));
+        }
+
+        let (roles, _, _) = store.list_roles(None, None).await?;
+        let mut role_detail_list = Vec::with_capacity(roles.len());
+        for role in &roles {
+            let mut inline = Vec::new();
+            for policy_name in store.list_role_policies(&role.role_name).await? {
+                if let Some(document) = store.get_role_policy(&role.role_name, &policy_name).await?
+                {
+                    inline.push(inline_policy(policy_name, document));
+                }
+            }
+            role_detail_list.push(RoleDetail::new(
+                role,
+                attached(store.list_attached_role_policies(&role.role_name).await?),
+                inline,
+            ));
+        }
+
+        let policies = policies
+            .iter()
+            .map(|policy: &Policy| {
+                ManagedPolicyDetail::new(policy, policy_versions(policy.clone()))
+            })
+            .collect();
+
+        Ok(AccountAuthorizationDetails {
+            user_detail_list,
+            group_detail_list,
+            role_detail_list,
+            policies,
+            is_truncated: false,
+        })
+    }
+}
+
+/// Name the attached policies, falling back to the last ARN segment for
+/// policies that are no longer stored
+fn attached_policies(
+    policy_names: &HashMap<&str, &str>,
+    arns: Vec<String>,
+) -> Vec<AttachedPolicyDetail> {
+    arns.into_iter()
+        .map(|policy_arn| AttachedPolicyDetail {
+            policy_name: policy_names
+                .get(policy_arn.as_str())
+                .map(|name| name.to_string())
+                .unwrap_or_else(|| {
+                    policy_arn
+                        .rsplit('/')
+                        .next()
+                        .unwrap_or(&policy_arn)
+                        .to_string()
+                }),
+            policy_arn,
+        })
+        .collect()
+}
+
+fn inline_policy(policy_name: String, policy_document: String) -> InlinePolicyDetail {
+    InlinePolicyDetail {
+        policy_name,
+        policy_document,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arn::{TenantPath, WamiArn};
+    use crate::context::WamiContext;
+    use crate::store::memory::InMemoryWamiStore;
+    use crate::wami::identity::group::builder::build_group;
+    use crate::wami::identity::role::builder::build_role;
+    use crate::wami::identity::user::builder::build_user;
+    use crate::wami::policies::policy::builder::{add_policy_version, build_policy};
+
+    const READ_ONLY: &str = r#"{"Version":"2012-10-17","Statement":[{"Effect":"Allow","Action":"s3:GetObject","Resource":"*"}]}"#;
+    const READ_WRITE: &str = r#"{"Version":"2012-10-17","Statement":[{"Effect":"Allow","Action":["s3:GetObject","s3:PutObject"],"Resource":"*"}]}"#;
+    const DENY_DELETE: &str = r#"{"Version":"2012-10-17","Statement":[{"Effect":"Deny","Action":"s3:DeleteObject","Resource":"*"}]}"#;
+    const TRUST: &str = r#"{"Version":"2012-10-17","Statement":[{"Effect":"Allow","Principal":{"Service":"ec2.amazonaws.com"},"Action":"sts:AssumeRole"}]}"#;
+
+    fn test_context() -> WamiContext {
+        let arn: WamiArn = "arn:wami:.*:12345678:wami:123456789012:user/test"
+            .parse()
+            .unwrap();
+        WamiContext::builder()
+            .instance_id("123456789012")
+            .tenant_path(TenantPath::single(12345678))
+            .caller_arn(arn)
+            .is_root(false)
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_export_contains_every_entity_with_its_policies() {
+        let context = test_context();
+        let mut store = InMemoryWamiStore::default();
+
+        let read_only = build_policy(
+            "ReadOnly".to_string(),
+            READ_ONLY.to_string(),
+            None,
+            None,
+            None,
+            &context,
+        )
+        .unwrap();
+        let (read_only, _) = add_policy_version(read_only, READ_WRITE.to_string(), true).unwrap();
+        let policy_arn = read_only.arn.clone();
+        store.create_policy(read_only).await.unwrap();
+
+        store
+            .create_user(build_user("alice".to_string(), None, &context).unwrap())
+            .await
+            .unwrap();
+        store
+            .create_group(build_group("devs".to_string(), None, &context).unwrap())
+            .await
+            .unwrap();
+        store.add_user_to_group("devs", "alice").await.unwrap();
+        store
+            .attach_user_policy("alice", &policy_arn)
+            .await
+            .unwrap();
+        store
+            .put_user_policy("alice", "NoDelete", DENY_DELETE.to_string())
+            .await
+            .unwrap();
+        store
+            .attach_group_policy("devs", &policy_arn)
+            .await
+            .unwrap();
+        store
+            .put_group_policy("devs", "DevsNoDelete", DENY_DELETE.to_string())
+            .await
+            .unwrap();
+
+        store
+            .create_role(
+                build_role(
+                    "Worker".to_string(),
+                    TRUST.to_string(),
+                    None,
+                    None,
+                    None,
+                    &context,
+                )
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        store
+            .attach_role_policy("Worker", "arn:aws:iam::aws:policy/Gone")
+            .await
+            .unwrap();
+        store
+            .put_role_policy("Worker", "WorkerReads", READ_ONLY.to_string())
+            .await
+            .unwrap();
+
+        let service = AuthorizationDetailsService::new(Arc::new(RwLock::new(store)));
+        let details = service.get_account_authorization_details().await.unwrap();
+        let attached_read_only = vec![AttachedPolicyDetail {
+            policy_name: "ReadOnly".to_string(),
+            policy_arn: policy_arn.clone(),
+        }];
+
+        assert_eq!(details.user_detail_list.len(), 1);
+        let alice = &details.user_detail_list[0];
+        assert_eq!(alice.user_name, "alice");
+        assert_eq!(alice.group_list, vec!["devs".to_string()]);
+        assert_eq!(alice.attached_managed_policies, attached_read_only);
+        assert_eq!(
+            alice.user_policy_list,
+            vec![inline_policy(
+                "NoDelete".to_string(),
+                DENY_DELETE.to_string()
+            )]
+        );
+
+        assert_eq!(details.group_detail_list.len(), 1);
+        let devs = &details.group_detail_list[0];
+        assert_eq!(devs.group_name, "devs");
+        assert_eq!(devs.attached_managed_policies, attached_read_only);
+        assert_eq!(devs.group_policy_list[0].policy_name, "DevsNoDelete");
+
+        assert_eq!(details.role_detail_list.len(), 1);
+        let worker = &details.role_detail_list[0];
+        assert_eq!(worker.assume_role_policy_document, TRUST);
+        assert_eq!(worker.attached_managed_policies[0].policy_name, "Gone");
+        assert_eq!(worker.role_policy_list[0].policy_name, "WorkerReads");
+
+        assert_eq!(details.policies.len(), 1);
+        let policy = &details.policies[0];
+        assert_eq!(policy.default_version_id, "v2");
+        assert_eq!(policy.policy_version_list.len(), 2);
+        assert_eq!(policy.default_document(), Some(READ_WRITE));
+
+        // Serializes to the AWS response shape
+        let json = serde_json::to_value(&details).unwrap();
+        assert_eq!(json["UserDetailList"][0]["GroupList"][0], "devs");
+        assert_eq!(
+            json["UserDetailList"][0]["AttachedManagedPolicies"][0]["PolicyArn"],
+            policy_arn.as_str()
+        );
+        assert_eq!(
+            json["GroupDetailList"][0]["GroupPolicyList"][0]["PolicyName"],
+            "DevsNoDelete"
+        );
+        assert_eq!(json["RoleDetailList"][0]["AssumeRolePolicyDocument"], TRUST);
+        assert_eq!(
+            json["Policies"][0]["PolicyVersionList"][1]["IsDefaultVersion"],
+            true
+        );
+        assert_eq!(json["IsTruncated"], false);
+    }
+}
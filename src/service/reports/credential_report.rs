@@ -6,13 +6,19 @@ use crate::error::{AmiError, Result};
 use crate::provider::{AwsProvider, CloudProvider};
 use crate::store::traits::{
     AccessKeyStore, CredentialReportStore, GroupStore, LoginProfileStore, MfaDeviceStore,
-    PolicyStore, RoleStore, ServerCertificateStore, UserStore,
+    PasswordPolicyStore, PolicyStore, RoleStore, ServerCertificateStore, SigningCertificateStore,
+    UserStore,
+};
+use crate::wami::credentials::PasswordPolicy;
+use crate::wami::reports::credential_report::operations::credential_report_operations::{
+    format_credential_report_row, CREDENTIAL_REPORT_HEADER,
 };
 use crate::wami::reports::credential_report::{
     AccountSummaryMap, CredentialReport, CredentialReportStatus, GenerateCredentialReportRequest,
     GenerateCredentialReportResponse, GetAccountSummaryRequest, GetAccountSummaryResponse,
     GetCredentialReportRequest, GetCredentialReportResponse,
 };
+use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 
 /// Service for generating credential reports and account summaries
@@ -20,7 +26,6 @@ use std::sync::{Arc, RwLock};
 /// Provides high-level operations for IAM reporting and auditing.
 pub struct CredentialReportService<S> {
     store: Arc<RwLock<S>>,
-    #[allow(dead_code)]
     provider: Arc<dyn CloudProvider>,
     account_id: String,
 }
@@ -35,7 +40,9 @@ where
         + MfaDeviceStore
         + AccessKeyStore
         + LoginProfileStore
-        + ServerCertificateStore,
+        + PasswordPolicyStore
+        + ServerCertificateStore
+        + SigningCertificateStore,
 {
     /// Create a new CredentialReportService with default AWS provider
     pub fn new(store: Arc<RwLock<S>>, account_id: String) -> Self {
@@ -57,89 +64,70 @@ where
 
     /// Generate a new credential report
     ///
-    /// Creates a CSV report of all IAM users and their credential status.
-    pub async fn generate_credential_report(
-        &self,
-        _request: GenerateCredentialReportRequest,
-    ) -> Result<GenerateCredentialReportResponse> {
-        // Fetch all users
-        let (users, _, _) = self.store.read().unwrap().list_users(None, None).await?;
-
-        // Generate CSV content
-        let mut csv_content = String::from(
-            "user,arn,user_creation_time,password_enabled,password_last_used,password_last_changed,\
-             mfa_active,access_key_1_active,access_key_1_last_rotated,access_key_2_active,\
-             access_key_2_last_rotated\n",
-        );
+    /// Builds the AWS-format CSV (one row per user covering password,
+    /// MFA, access key and signing certificate status) and stores it via
+    /// [`CredentialReportStore`], replacing any previous report.
+    pub async fn generate(&self) -> Result<CredentialReport> {
+        let store = self.store.read().unwrap();
+        let (users, _, _) = store.list_users(None, None).await?;
+
+        let mut csv_content = String::from(CREDENTIAL_REPORT_HEADER);
+        csv_content.push('\n');
+
+        // Password policies by account, fetched once per tenant
+        let mut password_policies: HashMap<String, Option<PasswordPolicy>> = HashMap::new();
 
         for user in users {
-            // Fetch user's credentials
-            let mfa_devices = self
-                .store
-                .read()
-                .unwrap()
-                .list_mfa_devices(&user.user_name)
+            let login_profile = store.get_login_profile(&user.user_name).await?;
+            let password_policy = match &login_profile {
+                Some(profile) => {
+                    let tenant_path = &profile.wami_arn.tenant_path;
+                    let key = tenant_path.as_string();
+                    if !password_policies.contains_key(&key) {
+                        let policy = store.get_password_policy(tenant_path).await?;
+                        password_policies.insert(key.clone(), policy);
+                    }
+                    password_policies[&key].clone()
+                }
+                None => None,
+            };
+            let mfa_active = !store.list_mfa_devices(&user.user_name).await?.is_empty();
+            let (access_keys, _, _) = store.list_access_keys(&user.user_name, None).await?;
+            let certificates = store
+                .list_signing_certificates(Some(&user.user_name))
                 .await?;
 
-            let access_keys = self
-                .store
-                .read()
-                .unwrap()
-                .list_access_keys(&user.user_name, None)
-                .await?
-                .0;
-
-            let has_login_profile = self
-                .store
-                .read()
-                .unwrap()
-                .get_login_profile(&user.user_name)
-                .await?
-                .is_some();
-
-            // Add row for this user
-            csv_content.push_str(&format!(
-                "{},{},{},{},{},{},{},{},{},{},{}\n",
-                user.user_name,
-                user.arn,
-                user.create_date.to_rfc3339(),
-                has_login_profile,
-                user.password_last_used
-                    .map_or("N/A".to_string(), |d| d.to_rfc3339()),
-                "N/A", // password_last_changed not tracked yet
-                !mfa_devices.is_empty(),
-                access_keys
-                    .first()
-                    .map_or("false", |k| if k.status == "Active" {
-                        "true"
-                    } else {
-                        "false"
-                    }),
-                access_keys
-                    .first()
-                    .map_or("N/A".to_string(), |k| k.create_date.to_rfc3339()),
-                access_keys
-                    .get(1)
-                    .map_or("false", |k| if k.status == "Active" {
-                        "true"
-                    } else {
-                        "false"
-                    }),
-                access_keys
-                    .get(1)
-                    .map_or("N/A".to_string(), |k| k.create_date.to_rfc3339()),
+            csv_content.push_str(&format_credential_report_row(
+                &user,
+                login_profile.as_ref(),
+                password_policy.as_ref(),
+                mfa_active,
+                &access_keys,
+                &certificates,
             ));
+            csv_content.push('\n');
         }
+        drop(store);
 
-        // Create and store report
         let report = CredentialReport::new(csv_content.into_bytes());
-
         self.store
             .write()
             .unwrap()
-            .store_credential_report(report)
+            .store_credential_report(report.clone())
             .await?;
 
+        Ok(report)
+    }
+
+    /// Generate a new credential report
+    ///
+    /// Creates a CSV report of all IAM users and their credential status.
+    pub async fn generate_credential_report(
+        &self,
+        _request: GenerateCredentialReportRequest,
+    ) -> Result<GenerateCredentialReportResponse> {
+        self.generate().await?;
+
         Ok(GenerateCredentialReportResponse {
             state: CredentialReportStatus::Complete,
             description: Some("Report generated successfully".to_string()),
@@ -171,6 +159,7 @@ where
             content: content_base64,
             report_format: report.report_format,
             generated_time: report.generated_time,
+            state: CredentialReportStatus::Complete,
         })
     }
 
@@ -210,22 +199,24 @@ where
 
         drop(store_read);
 
-        // Build summary map with AWS default quotas
+        // Quotas come from the provider's resource limits
+        let limits = self.provider.resource_limits();
+        let quota = |limit: usize| u32::try_from(limit).unwrap_or(u32::MAX);
         let summary = AccountSummaryMap {
             users: users_count,
-            users_quota: 5000, // AWS default
+            users_quota: quota(limits.max_users),
             groups: groups_count,
-            groups_quota: 300, // AWS default
+            groups_quota: quota(limits.max_groups),
             roles: roles_count,
-            roles_quota: 1000, // AWS default
+            roles_quota: quota(limits.max_roles),
             policies: policies_count,
-            policies_quota: 1500, // AWS default (customer managed)
+            policies_quota: quota(limits.max_policies),
             mfa_devices: mfa_count,
-            mfa_devices_in_use: mfa_count, // Assume all are in use
+            mfa_devices_in_use: mfa_count, // Devices are listed per user, so all are in use
             server_certificates: certs_count,
-            server_certificates_quota: 20,          // AWS default
-            access_keys_per_user_quota: 2,          // AWS default
-            signing_certificates_per_user_quota: 2, // AWS default
+            server_certificates_quota: quota(limits.max_server_certificates),
+            access_keys_per_user_quota: quota(limits.max_access_keys_per_user),
+            signing_certificates_per_user_quota: quota(limits.max_signing_certificates_per_user),
         };
 
         Ok(GetAccountSummaryResponse {
@@ -240,7 +231,11 @@ mod tests {
     use crate::arn::{TenantPath, WamiArn};
     use crate::context::WamiContext;
     use crate::store::memory::InMemoryWamiStore;
+    use crate::wami::credentials::access_key::builder::build_access_key;
+    use crate::wami::credentials::login_profile::builder::build_login_profile;
     use crate::wami::identity::user::builder::build_user;
+    use crate::wami::reports::credential_report::ReportState;
+    use chrono::{TimeZone, Utc};
 
     fn setup_service() -> CredentialReportService<InMemoryWamiStore> {
         let store = Arc::new(RwLock::new(InMemoryWamiStore::default()));
@@ -314,6 +309,115 @@ mod tests {
         assert!(!response.content.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_credential_report_csv_matches_aws_layout() {
+        let service = setup_service();
+        let context = test_context();
+
+        let user = build_user("alice".to_string(), Some("/".to_string()), &context).unwrap();
+        let profile = build_login_profile("alice".to_string(), false, &context).unwrap();
+        let key = build_access_key("alice".to_string(), &context).unwrap();
+        let used_at = Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap();
+        {
+            let mut store = service.store.write().unwrap();
+            store.create_user(user.clone()).await.unwrap();
+            store.create_login_profile(profile.clone()).await.unwrap();
+            store.create_access_key(key.clone()).await.unwrap();
+            store
+                .update_access_key_last_used(&key.access_key_id, "s3", Some("eu-west-1"), used_at)
+                .await
+                .unwrap();
+        }
+
+        service.generate().await.unwrap();
+        let response = service
+            .get_credential_report(GetCredentialReportRequest {})
+            .await
+            .unwrap();
+        assert_eq!(response.state, ReportState::Complete);
+
+        let decoded = base64::Engine::decode(
+            &base64::engine::general_purpose::STANDARD,
+            &response.content,
+        )
+        .unwrap();
+        let csv = String::from_utf8(decoded).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(
+            lines[0],
+            "user,arn,user_creation_time,password_enabled,password_last_used,\
+             password_last_changed,password_next_rotation,mfa_active,access_key_1_active,\
+             access_key_1_last_rotated,access_key_1_last_used_date,access_key_1_last_used_region,\
+             access_key_1_last_used_service,access_key_2_active,access_key_2_last_rotated,\
+             access_key_2_last_used_date,access_key_2_last_used_region,\
+             access_key_2_last_used_service,cert_1_active,cert_1_last_rotated,cert_2_active,\
+             cert_2_last_rotated"
+        );
+        let expected = [
+            "alice".to_string(),
+            user.arn.clone(),
+            user.create_date.to_rfc3339(),
+            "true".to_string(),
+            "no_information".to_string(),
+            profile.create_date.to_rfc3339(),
+            "N/A".to_string(),
+            "false".to_string(),
+            "true".to_string(),
+            key.create_date.to_rfc3339(),
+            used_at.to_rfc3339(),
+            "eu-west-1".to_string(),
+            "s3".to_string(),
+            "false".to_string(),
+            "N/A".to_string(),
+            "N/A".to_string(),
+            "N/A".to_string(),
+            "N/A".to_string(),
+            "false".to_string(),
+            "N/A".to_string(),
+            "false".to_string(),
+            "N/A".to_string(),
+        ]
+        .join(",");
+        assert_eq!(lines[1], expected);
+        assert_eq!(lines.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_credential_report_shows_password_expiry() {
+        let service = setup_service();
+        let context = test_context();
+        let policy = PasswordPolicy {
+            max_password_age: Some(30),
+            ..PasswordPolicy::default()
+        };
+
+        let changed_at = Utc::now() - chrono::Duration::days(45);
+        let mut profile = build_login_profile("alice".to_string(), false, &context).unwrap();
+        profile.password_last_changed = Some(changed_at);
+        assert!(profile.is_password_expired(&policy, Utc::now()));
+        assert!(!profile.is_password_expired(&PasswordPolicy::default(), Utc::now()));
+        {
+            let mut store = service.store.write().unwrap();
+            let user = build_user("alice".to_string(), None, &context).unwrap();
+            store.create_user(user).await.unwrap();
+            store.create_login_profile(profile).await.unwrap();
+            store
+                .put_password_policy(context.tenant_path(), policy)
+                .await
+                .unwrap();
+        }
+
+        let report = service.generate().await.unwrap();
+        let csv = String::from_utf8(report.report_content).unwrap();
+        let row: Vec<&str> = csv.lines().nth(1).unwrap().split(',').collect();
+
+        assert_eq!(row[5], changed_at.to_rfc3339());
+        let next_rotation = chrono::DateTime::parse_from_rfc3339(row[6]).unwrap();
+        assert_eq!(next_rotation, changed_at + chrono::Duration::days(30));
+        assert!(next_rotation < Utc::now());
+    }
+
     #[tokio::test]
     async fn test_get_credential_report_not_generated() {
         let service = setup_service();
@@ -362,4 +466,56 @@ mod tests {
         assert_eq!(response.summary_map.users, 5);
         assert_eq!(response.summary_map.users_quota, 5000); // AWS default quota
     }
+
+    #[tokio::test]
+    async fn test_get_account_summary_counts_and_provider_quotas() {
+        use crate::provider::ResourceLimits;
+        use crate::wami::identity::group::builder::build_group;
+        use crate::wami::identity::role::builder::build_role;
+
+        let limits = ResourceLimits {
+            max_users: 10,
+            max_access_keys_per_user: 4,
+            ..Default::default()
+        };
+        let service = setup_service().with_provider(Arc::new(AwsProvider::with_limits(limits)));
+        let context = test_context();
+        let trust_policy = r#"{"Version":"2012-10-17","Statement":[]}"#;
+
+        {
+            let mut store = service.store.write().unwrap();
+            for i in 0..3 {
+                let user = build_user(format!("user{}", i), None, &context).unwrap();
+                store.create_user(user).await.unwrap();
+            }
+            for i in 0..2 {
+                let group = build_group(format!("group{}", i), None, &context).unwrap();
+                store.create_group(group).await.unwrap();
+            }
+            let role = build_role(
+                "deployer".to_string(),
+                trust_policy.to_string(),
+                None,
+                None,
+                None,
+                &context,
+            )
+            .unwrap();
+            store.create_role(role).await.unwrap();
+        }
+
+        let response = service
+            .get_account_summary(GetAccountSummaryRequest {})
+            .await
+            .unwrap();
+        let summary = response.summary_map;
+
+        assert_eq!(summary.users, 3);
+        assert_eq!(summary.groups, 2);
+        assert_eq!(summary.roles, 1);
+        assert_eq!(summary.policies, 0);
+        assert_eq!(summary.users_quota, 10);
+        assert_eq!(summary.access_keys_per_user_quota, 4);
+        assert_eq!(summary.groups_quota, 300);
+    }
 }
@@ -2,6 +2,8 @@
 //!
 //! Services for generating IAM reports and account summaries.
 
+pub mod authorization_details;
 pub mod credential_report;
 
+pub use authorization_details::AuthorizationDetailsService;
 pub use credential_report::CredentialReportService;
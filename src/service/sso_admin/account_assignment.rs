@@ -2,10 +2,10 @@
 //!
 //! Orchestrates account assignment operations.
 
-use crate::error::Result;
+use crate::error::{AmiError, Result};
 use crate::provider::{AwsProvider, CloudProvider};
-use crate::store::traits::AccountAssignmentStore;
-use crate::wami::sso_admin::account_assignment::AccountAssignment;
+use crate::store::traits::{AccountAssignmentStore, PermissionSetStore};
+use crate::wami::sso_admin::account_assignment::{AccountAssignment, AssignmentStatus};
 use std::sync::{Arc, RwLock};
 
 /// Service for managing account assignments
@@ -33,15 +33,32 @@ impl<S: AccountAssignmentStore> AccountAssignmentService<S> {
     }
 
     /// Create a new account assignment
+    ///
+    /// The assignment ID is derived from the (account, permission set, principal)
+    /// tuple. Creating an assignment for a tuple that already exists is a no-op
+    /// that returns the existing assignment.
+    ///
+    /// A new assignment starts `IN_PROGRESS` under a fresh `request_id`; see
+    /// [`provision`](Self::provision) and
+    /// [`describe_account_assignment_creation_status`](Self::describe_account_assignment_creation_status).
     pub async fn create_account_assignment(
         &self,
-        assignment: AccountAssignment,
+        mut assignment: AccountAssignment,
     ) -> Result<AccountAssignment> {
-        self.store
-            .write()
-            .unwrap()
-            .create_account_assignment(assignment)
-            .await
+        assignment.assignment_id = assignment.key();
+        assignment.status = AssignmentStatus::InProgress;
+        assignment.request_id = uuid::Uuid::new_v4().to_string();
+        assignment.failure_reason = None;
+
+        let mut store = self.store.write().unwrap();
+        if let Some(existing) = store
+            .get_account_assignment(&assignment.assignment_id)
+            .await?
+        {
+            return Ok(existing);
+        }
+
+        store.create_account_assignment(assignment).await
     }
 
     /// Get an account assignment by ID
@@ -56,6 +73,45 @@ impl<S: AccountAssignmentStore> AccountAssignmentService<S> {
             .await
     }
 
+    /// Get the assignment created by a `create_account_assignment` request, with its status
+    pub async fn describe_account_assignment_creation_status(
+        &self,
+        request_id: &str,
+    ) -> Result<AccountAssignment> {
+        let store = self.store.read().unwrap();
+        assignment_for_request(&*store, request_id).await
+    }
+
+    /// Complete the provisioning of an assignment created by a request
+    ///
+    /// The assignment moves from `IN_PROGRESS` to `SUCCEEDED`, or to `FAILED`
+    /// when a `failure_reason` is given. Returns `InvalidParameter` if its
+    /// provisioning has already completed.
+    pub async fn provision(
+        &self,
+        request_id: &str,
+        failure_reason: Option<String>,
+    ) -> Result<AccountAssignment> {
+        let mut store = self.store.write().unwrap();
+        let mut assignment = assignment_for_request(&*store, request_id).await?;
+        if assignment.status != AssignmentStatus::InProgress {
+            return Err(AmiError::InvalidParameter {
+                message: format!(
+                    "Account assignment request {} is no longer in progress",
+                    request_id
+                ),
+            });
+        }
+
+        assignment.status = match failure_reason {
+            Some(_) => AssignmentStatus::Failed,
+            None => AssignmentStatus::Succeeded,
+        };
+        assignment.failure_reason = failure_reason;
+        // Creating an assignment under an existing ID replaces it
+        store.create_account_assignment(assignment).await
+    }
+
     /// Delete an account assignment
     pub async fn delete_account_assignment(&self, assignment_id: &str) -> Result<()> {
         self.store
@@ -65,6 +121,18 @@ impl<S: AccountAssignmentStore> AccountAssignmentService<S> {
             .await
     }
 
+    /// Delete the account assignment matching an (account, permission set, principal) tuple
+    pub async fn delete_account_assignment_for(
+        &self,
+        account_id: &str,
+        permission_set_arn: &str,
+        principal_id: &str,
+    ) -> Result<()> {
+        let assignment_id =
+            AccountAssignment::assignment_key(account_id, permission_set_arn, principal_id);
+        self.delete_account_assignment(&assignment_id).await
+    }
+
     /// List account assignments for a permission set in an account
     pub async fn list_account_assignments(
         &self,
@@ -79,6 +147,55 @@ impl<S: AccountAssignmentStore> AccountAssignmentService<S> {
     }
 }
 
+impl<S: AccountAssignmentStore + PermissionSetStore> AccountAssignmentService<S> {
+    /// List account assignments whose permission set no longer exists
+    pub async fn list_orphaned_assignments(&self) -> Result<Vec<AccountAssignment>> {
+        let store = self.store.read().unwrap();
+
+        let mut orphans = Vec::new();
+        for assignment in store.list_all_account_assignments().await? {
+            if store
+                .get_permission_set(&assignment.permission_set_arn)
+                .await?
+                .is_none()
+            {
+                orphans.push(assignment);
+            }
+        }
+        Ok(orphans)
+    }
+
+    /// Delete every orphaned account assignment
+    ///
+    /// Returns the number of assignments removed.
+    pub async fn delete_orphaned_assignments(&self) -> Result<usize> {
+        let orphans = self.list_orphaned_assignments().await?;
+
+        let mut store = self.store.write().unwrap();
+        for orphan in &orphans {
+            store
+                .delete_account_assignment(&orphan.assignment_id)
+                .await?;
+        }
+        Ok(orphans.len())
+    }
+}
+
+/// Find the assignment created by a request, failing with `ResourceNotFound` if there is none
+async fn assignment_for_request<S: AccountAssignmentStore>(
+    store: &S,
+    request_id: &str,
+) -> Result<AccountAssignment> {
+    store
+        .list_all_account_assignments()
+        .await?
+        .into_iter()
+        .find(|assignment| assignment.request_id == request_id)
+        .ok_or_else(|| AmiError::ResourceNotFound {
+            resource: format!("AccountAssignmentCreationStatus: {}", request_id),
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -105,6 +222,9 @@ mod tests {
                 .parse()
                 .unwrap(),
             providers: vec![],
+            status: AssignmentStatus::Succeeded,
+            request_id: String::new(),
+            failure_reason: None,
         }
     }
 
@@ -117,31 +237,89 @@ mod tests {
             .create_account_assignment(assignment.clone())
             .await
             .unwrap();
-        assert_eq!(created.assignment_id, "assign1");
+        assert_eq!(created.assignment_id, "123456789012-ps-1-user-assign1");
 
         let retrieved = service
-            .get_account_assignment(&assignment.assignment_id)
+            .get_account_assignment(&created.assignment_id)
             .await
             .unwrap();
         assert!(retrieved.is_some());
     }
 
+    #[tokio::test]
+    async fn test_assignment_provisioning_status() {
+        let service = setup_service();
+
+        let created = service
+            .create_account_assignment(create_test_assignment("new", "ps-1"))
+            .await
+            .unwrap();
+        assert_eq!(created.status, AssignmentStatus::InProgress);
+        assert!(!created.request_id.is_empty());
+
+        let status = service
+            .describe_account_assignment_creation_status(&created.request_id)
+            .await
+            .unwrap();
+        assert_eq!(status.assignment_id, created.assignment_id);
+        assert_eq!(status.status, AssignmentStatus::InProgress);
+
+        let provisioned = service.provision(&created.request_id, None).await.unwrap();
+        assert_eq!(provisioned.status, AssignmentStatus::Succeeded);
+        let status = service
+            .describe_account_assignment_creation_status(&created.request_id)
+            .await
+            .unwrap();
+        assert_eq!(status.status, AssignmentStatus::Succeeded);
+
+        // Provisioning completes only once
+        let result = service.provision(&created.request_id, None).await;
+        assert!(matches!(result, Err(AmiError::InvalidParameter { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_assignment_provisioning_failure() {
+        let service = setup_service();
+        let created = service
+            .create_account_assignment(create_test_assignment("broken", "ps-1"))
+            .await
+            .unwrap();
+
+        let failed = service
+            .provision(
+                &created.request_id,
+                Some("Account is suspended".to_string()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(failed.status, AssignmentStatus::Failed);
+        assert_eq!(
+            failed.failure_reason.as_deref(),
+            Some("Account is suspended")
+        );
+
+        let result = service
+            .describe_account_assignment_creation_status("unknown-request")
+            .await;
+        assert!(matches!(result, Err(AmiError::ResourceNotFound { .. })));
+    }
+
     #[tokio::test]
     async fn test_delete_assignment() {
         let service = setup_service();
         let assignment = create_test_assignment("temp", "ps-1");
 
-        service
+        let created = service
             .create_account_assignment(assignment.clone())
             .await
             .unwrap();
         service
-            .delete_account_assignment(&assignment.assignment_id)
+            .delete_account_assignment(&created.assignment_id)
             .await
             .unwrap();
 
         let retrieved = service
-            .get_account_assignment(&assignment.assignment_id)
+            .get_account_assignment(&created.assignment_id)
             .await
             .unwrap();
         assert!(retrieved.is_none());
@@ -168,4 +346,89 @@ mod tests {
             .unwrap();
         assert_eq!(assignments.len(), 2);
     }
+
+    #[tokio::test]
+    async fn test_create_assignment_is_idempotent() {
+        let service = setup_service();
+
+        let first = service
+            .create_account_assignment(create_test_assignment("dup", "ps-1"))
+            .await
+            .unwrap();
+        // Same tuple, different caller-supplied ID
+        let mut duplicate = create_test_assignment("dup", "ps-1");
+        duplicate.assignment_id = "another-id".to_string();
+        let second = service.create_account_assignment(duplicate).await.unwrap();
+
+        assert_eq!(first.assignment_id, second.assignment_id);
+        assert_eq!(first.created_date, second.created_date);
+        let assignments = service
+            .list_account_assignments("123456789012", "ps-1")
+            .await
+            .unwrap();
+        assert_eq!(assignments.len(), 1);
+
+        service
+            .delete_account_assignment_for("123456789012", "ps-1", "user-dup")
+            .await
+            .unwrap();
+        let assignments = service
+            .list_account_assignments("123456789012", "ps-1")
+            .await
+            .unwrap();
+        assert!(assignments.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_list_orphaned_assignments() {
+        use crate::wami::sso_admin::PermissionSet;
+
+        let service = setup_service();
+        let permission_set = PermissionSet {
+            permission_set_arn: "ps-live".to_string(),
+            name: "live".to_string(),
+            description: None,
+            session_duration: None,
+            relay_state: None,
+            inline_policy: None,
+            instance_arn: "arn:aws:sso:::instance/test-instance".to_string(),
+            created_date: Utc::now(),
+            wami_arn: "arn:wami:.*:0:wami:123456789012:permission-set/ps-live"
+                .parse()
+                .unwrap(),
+            providers: vec![],
+        };
+        service
+            .store
+            .write()
+            .unwrap()
+            .create_permission_set(permission_set)
+            .await
+            .unwrap();
+
+        service
+            .create_account_assignment(create_test_assignment("kept", "ps-live"))
+            .await
+            .unwrap();
+        let orphan = service
+            .create_account_assignment(create_test_assignment("orphan", "ps-deleted"))
+            .await
+            .unwrap();
+
+        let orphans = service.list_orphaned_assignments().await.unwrap();
+        assert_eq!(orphans.len(), 1);
+        assert_eq!(orphans[0].assignment_id, orphan.assignment_id);
+
+        assert_eq!(service.delete_orphaned_assignments().await.unwrap(), 1);
+        assert!(service
+            .list_orphaned_assignments()
+            .await
+            .unwrap()
+            .is_empty());
+        let remaining = service
+            .list_account_assignments("123456789012", "ps-live")
+            .await
+            .unwrap();
+        assert_eq!(remaining.len(), 1);
+    }
 }
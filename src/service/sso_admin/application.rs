@@ -2,10 +2,10 @@
 //!
 //! Orchestrates application operations.
 
-use crate::error::Result;
+use crate::error::{AmiError, Result};
 use crate::provider::{AwsProvider, CloudProvider};
 use crate::store::traits::ApplicationStore;
-use crate::wami::sso_admin::application::Application;
+use crate::wami::sso_admin::application::{Application, ApplicationAssignment};
 use std::sync::{Arc, RwLock};
 
 /// Service for managing applications
@@ -58,6 +58,99 @@ impl<S: ApplicationStore> ApplicationService<S> {
             .list_applications(instance_arn)
             .await
     }
+
+    /// Assign an application to a user or group
+    ///
+    /// `principal_type` is `USER` or `GROUP`. Assigning an application to a
+    /// principal it is already assigned to returns the existing assignment.
+    /// Returns `ResourceNotFound` if the application does not exist.
+    pub async fn put_application_assignment(
+        &self,
+        application_arn: &str,
+        principal_type: &str,
+        principal_id: &str,
+    ) -> Result<ApplicationAssignment> {
+        let principal_type = validate_principal_type(principal_type)?;
+
+        let mut store = self.store.write().unwrap();
+        ensure_application_exists(&*store, application_arn).await?;
+
+        let key =
+            ApplicationAssignment::assignment_key(application_arn, principal_type, principal_id);
+        if let Some(existing) = store
+            .list_application_assignments(application_arn)
+            .await?
+            .into_iter()
+            .find(|assignment| assignment.key() == key)
+        {
+            return Ok(existing);
+        }
+
+        store
+            .put_application_assignment(ApplicationAssignment {
+                application_arn: application_arn.to_string(),
+                principal_type: principal_type.to_string(),
+                principal_id: principal_id.to_string(),
+                created_date: chrono::Utc::now(),
+            })
+            .await
+    }
+
+    /// Remove the assignment of an application to a user or group
+    pub async fn delete_application_assignment(
+        &self,
+        application_arn: &str,
+        principal_type: &str,
+        principal_id: &str,
+    ) -> Result<()> {
+        let principal_type = validate_principal_type(principal_type)?;
+        let key =
+            ApplicationAssignment::assignment_key(application_arn, principal_type, principal_id);
+        self.store
+            .write()
+            .unwrap()
+            .delete_application_assignment(&key)
+            .await
+    }
+
+    /// List the users and groups an application is assigned to
+    ///
+    /// Returns `ResourceNotFound` if the application does not exist.
+    pub async fn list_application_assignments(
+        &self,
+        application_arn: &str,
+    ) -> Result<Vec<ApplicationAssignment>> {
+        let store = self.store.read().unwrap();
+        ensure_application_exists(&*store, application_arn).await?;
+        store.list_application_assignments(application_arn).await
+    }
+}
+
+/// Normalize a principal type to `USER` or `GROUP`
+#[allow(clippy::result_large_err)]
+fn validate_principal_type(principal_type: &str) -> Result<&'static str> {
+    ["USER", "GROUP"]
+        .into_iter()
+        .find(|known| known.eq_ignore_ascii_case(principal_type))
+        .ok_or_else(|| AmiError::InvalidParameter {
+            message: format!(
+                "Invalid principal type '{}': expected USER or GROUP",
+                principal_type
+            ),
+        })
+}
+
+/// Fail with `ResourceNotFound` unless the application exists
+async fn ensure_application_exists<S: ApplicationStore>(
+    store: &S,
+    application_arn: &str,
+) -> Result<()> {
+    match store.get_application(application_arn).await? {
+        Some(_) => Ok(()),
+        None => Err(AmiError::ResourceNotFound {
+            resource: format!("Application: {}", application_arn),
+        }),
+    }
 }
 
 #[cfg(test)]
@@ -123,4 +216,63 @@ mod tests {
         let applications = service.list_applications(instance_arn).await.unwrap();
         assert_eq!(applications.len(), 2);
     }
+
+    #[tokio::test]
+    async fn test_application_assignments() {
+        let service = setup_service();
+        let application = create_test_application("portal", "instance-1");
+        let arn = application.application_arn.clone();
+        service.create_application(application).await.unwrap();
+
+        service
+            .put_application_assignment(&arn, "USER", "alice")
+            .await
+            .unwrap();
+        service
+            .put_application_assignment(&arn, "group", "engineers")
+            .await
+            .unwrap();
+        // Assigning the same principal again changes nothing
+        service
+            .put_application_assignment(&arn, "USER", "alice")
+            .await
+            .unwrap();
+
+        let assignments = service.list_application_assignments(&arn).await.unwrap();
+        let principals: Vec<_> = assignments
+            .iter()
+            .map(|a| (a.principal_type.as_str(), a.principal_id.as_str()))
+            .collect();
+        assert_eq!(principals, [("GROUP", "engineers"), ("USER", "alice")]);
+
+        service
+            .delete_application_assignment(&arn, "USER", "alice")
+            .await
+            .unwrap();
+        let assignments = service.list_application_assignments(&arn).await.unwrap();
+        assert_eq!(assignments.len(), 1);
+        assert_eq!(assignments[0].principal_id, "engineers");
+    }
+
+    #[tokio::test]
+    async fn test_application_assignment_validation() {
+        let service = setup_service();
+
+        let result = service
+            .put_application_assignment("arn:aws:sso:::application/missing", "USER", "alice")
+            .await;
+        assert!(matches!(result, Err(AmiError::ResourceNotFound { .. })));
+        let result = service
+            .list_application_assignments("arn:aws:sso:::application/missing")
+            .await;
+        assert!(matches!(result, Err(AmiError::ResourceNotFound { .. })));
+
+        let application = create_test_application("portal", "instance-1");
+        let arn = application.application_arn.clone();
+        service.create_application(application).await.unwrap();
+        let result = service
+            .put_application_assignment(&arn, "ROLE", "admin")
+            .await;
+        assert!(matches!(result, Err(AmiError::InvalidParameter { .. })));
+    }
 }
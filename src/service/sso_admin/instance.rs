@@ -2,9 +2,11 @@
 //!
 //! Orchestrates SSO instance operations.
 
-use crate::error::Result;
+use crate::context::WamiContext;
+use crate::error::{AmiError, Result};
 use crate::provider::{AwsProvider, CloudProvider};
 use crate::store::traits::SsoInstanceStore;
+use crate::wami::sso_admin::instance::builder::{build_instance, generate_identity_store_id};
 use crate::wami::sso_admin::instance::SsoInstance;
 use std::sync::{Arc, RwLock};
 
@@ -32,8 +34,24 @@ impl<S: SsoInstanceStore> InstanceService<S> {
         }
     }
 
-    /// Create a new SSO instance
-    pub async fn create_instance(&self, instance: SsoInstance) -> Result<SsoInstance> {
+    /// Create a new SSO instance for an identity store owned by the given account
+    pub async fn create_instance(
+        &self,
+        context: &WamiContext,
+        identity_store_id: &str,
+        owner_account_id: &str,
+    ) -> Result<SsoInstance> {
+        if identity_store_id.is_empty() || owner_account_id.is_empty() {
+            return Err(AmiError::InvalidParameter {
+                message: "Identity store ID and owner account ID cannot be empty".to_string(),
+            });
+        }
+
+        let instance = build_instance(
+            identity_store_id.to_string(),
+            owner_account_id.to_string(),
+            context,
+        )?;
         self.store.write().unwrap().create_instance(instance).await
     }
 
@@ -42,6 +60,38 @@ impl<S: SsoInstanceStore> InstanceService<S> {
         self.store.read().unwrap().get_instance(instance_arn).await
     }
 
+    /// Describe an SSO instance by ARN
+    ///
+    /// Unlike [`get_instance`](Self::get_instance), a missing instance is an error.
+    pub async fn describe_instance(&self, instance_arn: &str) -> Result<SsoInstance> {
+        self.get_instance(instance_arn)
+            .await?
+            .ok_or_else(|| AmiError::ResourceNotFound {
+                resource: format!("SSO instance: {}", instance_arn),
+            })
+    }
+
+    /// Get the default SSO instance of the caller's account, creating it on first use
+    ///
+    /// The default instance is the oldest instance owned by the context's
+    /// instance ID; if none exists, one is created with a fresh identity store.
+    pub async fn default_instance(&self, context: &WamiContext) -> Result<SsoInstance> {
+        let owner_account_id = context.instance_id();
+
+        let existing = self
+            .list_instances()
+            .await?
+            .into_iter()
+            .filter(|i| i.owner_account_id.as_deref() == Some(owner_account_id))
+            .min_by_key(|i| i.created_date);
+        if let Some(instance) = existing {
+            return Ok(instance);
+        }
+
+        self.create_instance(context, &generate_identity_store_id(), owner_account_id)
+            .await
+    }
+
     /// List all SSO instances
     pub async fn list_instances(&self) -> Result<Vec<SsoInstance>> {
         self.store.read().unwrap().list_instances().await
@@ -51,55 +101,103 @@ impl<S: SsoInstanceStore> InstanceService<S> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::arn::{TenantPath, WamiArn};
     use crate::store::memory::InMemoryWamiStore;
-    use chrono::Utc;
 
     fn setup_service() -> InstanceService<InMemoryWamiStore> {
         let store = Arc::new(RwLock::new(InMemoryWamiStore::default()));
         InstanceService::new(store)
     }
 
-    fn create_test_instance(name: &str) -> SsoInstance {
-        SsoInstance {
-            instance_arn: format!("arn:aws:sso:::instance/{}", name),
-            identity_store_id: format!("d-{}", name),
-            name: Some(name.to_string()),
-            status: "ACTIVE".to_string(),
-            created_date: Utc::now(),
-            wami_arn: format!("arn:wami:.*:0:wami:123456789012:instance/{}", name)
-                .parse()
-                .unwrap(),
-            providers: vec![],
-        }
+    fn test_context() -> WamiContext {
+        let arn: WamiArn = "arn:wami:.*:12345678:wami:123456789012:user/test"
+            .parse()
+            .unwrap();
+        WamiContext::builder()
+            .instance_id("123456789012")
+            .tenant_path(TenantPath::single(12345678))
+            .caller_arn(arn)
+            .is_root(false)
+            .build()
+            .unwrap()
     }
 
     #[tokio::test]
     async fn test_create_and_get_instance() {
         let service = setup_service();
-        let instance = create_test_instance("test-instance");
-
-        let created = service.create_instance(instance.clone()).await.unwrap();
-        assert_eq!(created.name, Some("test-instance".to_string()));
+        let context = test_context();
 
-        let retrieved = service.get_instance(&instance.instance_arn).await.unwrap();
+        let created = service
+            .create_instance(&context, "d-1234567890", "123456789012")
+            .await
+            .unwrap();
+        assert!(created
+            .instance_arn
+            .starts_with("arn:aws:sso:::instance/ssoins-"));
+        assert_eq!(created.identity_store_id, "d-1234567890");
+        assert_eq!(created.owner_account_id.as_deref(), Some("123456789012"));
+        assert_eq!(created.status, "ACTIVE");
+
+        let retrieved = service.get_instance(&created.instance_arn).await.unwrap();
         assert!(retrieved.is_some());
-        assert_eq!(retrieved.unwrap().name, Some("test-instance".to_string()));
+        assert_eq!(retrieved.unwrap().identity_store_id, "d-1234567890");
     }
 
     #[tokio::test]
     async fn test_list_instances() {
         let service = setup_service();
+        let context = test_context();
 
-        service
-            .create_instance(create_test_instance("instance1"))
+        let first = service
+            .create_instance(&context, "d-1111111111", "123456789012")
             .await
             .unwrap();
         service
-            .create_instance(create_test_instance("instance2"))
+            .create_instance(&context, "d-2222222222", "123456789012")
             .await
             .unwrap();
 
         let instances = service.list_instances().await.unwrap();
         assert_eq!(instances.len(), 2);
+        assert!(instances
+            .iter()
+            .any(|i| i.instance_arn == first.instance_arn));
+    }
+
+    #[tokio::test]
+    async fn test_describe_instance() {
+        let service = setup_service();
+        let context = test_context();
+
+        let created = service
+            .create_instance(&context, "d-1234567890", "123456789012")
+            .await
+            .unwrap();
+
+        let described = service
+            .describe_instance(&created.instance_arn)
+            .await
+            .unwrap();
+        assert_eq!(described.instance_arn, created.instance_arn);
+
+        let result = service
+            .describe_instance("arn:aws:sso:::instance/ssoins-missing")
+            .await;
+        assert!(matches!(result, Err(AmiError::ResourceNotFound { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_default_instance_created_on_first_use() {
+        let service = setup_service();
+        let context = test_context();
+
+        let default = service.default_instance(&context).await.unwrap();
+        assert_eq!(default.owner_account_id.as_deref(), Some("123456789012"));
+        assert!(default.identity_store_id.starts_with("d-"));
+
+        // Subsequent calls reuse the same instance
+        let again = service.default_instance(&context).await.unwrap();
+        assert_eq!(again.instance_arn, default.instance_arn);
+        assert_eq!(service.list_instances().await.unwrap().len(), 1);
     }
 }
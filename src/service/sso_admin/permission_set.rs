@@ -2,9 +2,10 @@
 //!
 //! Orchestrates permission set operations.
 
-use crate::error::Result;
+use crate::error::{AmiError, Result};
 use crate::provider::{AwsProvider, CloudProvider};
-use crate::store::traits::PermissionSetStore;
+use crate::store::traits::{AccountAssignmentStore, PermissionSetStore};
+use crate::wami::policies::inline::operations::validate_inline_policy_document;
 use crate::wami::sso_admin::permission_set::PermissionSet;
 use std::sync::{Arc, RwLock};
 
@@ -13,6 +14,7 @@ pub struct PermissionSetService<S> {
     store: Arc<RwLock<S>>,
     #[allow(dead_code)]
     provider: Arc<dyn CloudProvider>,
+    cascade_assignments: bool,
 }
 
 impl<S: PermissionSetStore> PermissionSetService<S> {
@@ -21,6 +23,7 @@ impl<S: PermissionSetStore> PermissionSetService<S> {
         Self {
             store,
             provider: Arc::new(AwsProvider::new()),
+            cascade_assignments: false,
         }
     }
 
@@ -29,14 +32,33 @@ impl<S: PermissionSetStore> PermissionSetService<S> {
         Self {
             store: self.store.clone(),
             provider,
+            cascade_assignments: self.cascade_assignments,
+        }
+    }
+
+    /// Returns a new service instance that cascades permission set deletion
+    ///
+    /// When `cascade` is true, deleting a permission set also deletes its
+    /// account assignments; otherwise deletion is refused while any exist.
+    pub fn with_cascade_delete(&self, cascade: bool) -> Self {
+        Self {
+            store: self.store.clone(),
+            provider: self.provider.clone(),
+            cascade_assignments: cascade,
         }
     }
 
     /// Create a new permission set
+    ///
+    /// The session duration, if set, must be an ISO-8601 duration between 1 and 12 hours.
     pub async fn create_permission_set(
         &self,
         permission_set: PermissionSet,
     ) -> Result<PermissionSet> {
+        if let Some(session_duration) = &permission_set.session_duration {
+            PermissionSet::validate_session_duration(session_duration)?;
+        }
+
         self.store
             .write()
             .unwrap()
@@ -61,19 +83,14 @@ impl<S: PermissionSetStore> PermissionSetService<S> {
         &self,
         permission_set: PermissionSet,
     ) -> Result<PermissionSet> {
-        self.store
-            .write()
-            .unwrap()
-            .update_permission_set(permission_set)
-            .await
-    }
+        if let Some(session_duration) = &permission_set.session_duration {
+            PermissionSet::validate_session_duration(session_duration)?;
+        }
 
-    /// Delete a permission set
-    pub async fn delete_permission_set(&self, permission_set_arn: &str) -> Result<()> {
         self.store
             .write()
             .unwrap()
-            .delete_permission_set(permission_set_arn)
+            .update_permission_set(permission_set)
             .await
     }
 
@@ -85,12 +102,110 @@ impl<S: PermissionSetStore> PermissionSetService<S> {
             .list_permission_sets(instance_arn)
             .await
     }
+
+    /// Attach an inline policy to a permission set, replacing any existing one
+    ///
+    /// The policy is granted in each account the permission set is assigned to.
+    /// It must parse as a policy document.
+    pub async fn put_inline_policy_to_permission_set(
+        &self,
+        permission_set_arn: &str,
+        inline_policy: String,
+    ) -> Result<()> {
+        validate_inline_policy_document(&inline_policy)?;
+
+        let mut store = self.store.write().unwrap();
+        let mut permission_set = existing_permission_set(&*store, permission_set_arn).await?;
+        permission_set.inline_policy = Some(inline_policy);
+        store.update_permission_set(permission_set).await?;
+        Ok(())
+    }
+
+    /// Get the inline policy of a permission set, if it has one
+    pub async fn get_inline_policy_for_permission_set(
+        &self,
+        permission_set_arn: &str,
+    ) -> Result<Option<String>> {
+        let store = self.store.read().unwrap();
+        Ok(existing_permission_set(&*store, permission_set_arn)
+            .await?
+            .inline_policy)
+    }
+
+    /// Remove the inline policy of a permission set
+    ///
+    /// Succeeds even if the permission set has no inline policy.
+    pub async fn delete_inline_policy_from_permission_set(
+        &self,
+        permission_set_arn: &str,
+    ) -> Result<()> {
+        let mut store = self.store.write().unwrap();
+        let mut permission_set = existing_permission_set(&*store, permission_set_arn).await?;
+        if permission_set.inline_policy.take().is_some() {
+            store.update_permission_set(permission_set).await?;
+        }
+        Ok(())
+    }
+}
+
+impl<S: PermissionSetStore + AccountAssignmentStore> PermissionSetService<S> {
+    /// Delete a permission set
+    ///
+    /// If account assignments still reference the permission set, they are
+    /// deleted along with it when cascading is enabled (see
+    /// [`with_cascade_delete`](Self::with_cascade_delete)); otherwise the
+    /// deletion is refused with a `DeleteConflict` error.
+    pub async fn delete_permission_set(&self, permission_set_arn: &str) -> Result<()> {
+        let mut store = self.store.write().unwrap();
+
+        let assignments: Vec<_> = store
+            .list_all_account_assignments()
+            .await?
+            .into_iter()
+            .filter(|a| a.permission_set_arn == permission_set_arn)
+            .collect();
+
+        if !assignments.is_empty() {
+            if !self.cascade_assignments {
+                return Err(AmiError::DeleteConflict {
+                    message: format!(
+                        "Permission set {} still has {} account assignment(s)",
+                        permission_set_arn,
+                        assignments.len()
+                    ),
+                });
+            }
+
+            for assignment in assignments {
+                store
+                    .delete_account_assignment(&assignment.assignment_id)
+                    .await?;
+            }
+        }
+
+        store.delete_permission_set(permission_set_arn).await
+    }
+}
+
+/// Look up a permission set, failing with `ResourceNotFound` if it does not exist
+async fn existing_permission_set<S: PermissionSetStore>(
+    store: &S,
+    permission_set_arn: &str,
+) -> Result<PermissionSet> {
+    store
+        .get_permission_set(permission_set_arn)
+        .await?
+        .ok_or_else(|| AmiError::ResourceNotFound {
+            resource: format!("PermissionSet: {}", permission_set_arn),
+        })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::AmiError;
     use crate::store::memory::InMemoryWamiStore;
+    use crate::wami::sso_admin::{AccountAssignment, AssignmentStatus};
     use chrono::Utc;
 
     fn setup_service() -> PermissionSetService<InMemoryWamiStore> {
@@ -105,6 +220,7 @@ mod tests {
             description: Some(format!("Test permission set {}", name)),
             session_duration: Some("PT8H".to_string()),
             relay_state: None,
+            inline_policy: None,
             instance_arn: instance_arn.to_string(),
             created_date: Utc::now(),
             wami_arn: format!("arn:wami:.*:0:wami:123456789012:permission-set/ps-{}", name)
@@ -185,4 +301,156 @@ mod tests {
         let permission_sets = service.list_permission_sets(instance_arn).await.unwrap();
         assert_eq!(permission_sets.len(), 2);
     }
+
+    #[tokio::test]
+    async fn test_create_permission_set_session_duration() {
+        let service = setup_service();
+
+        let valid = create_test_permission_set("eight-hours", "instance-1");
+        assert!(service.create_permission_set(valid).await.is_ok());
+
+        let mut too_long = create_test_permission_set("thirteen-hours", "instance-1");
+        too_long.session_duration = Some("PT13H".to_string());
+        let result = service.create_permission_set(too_long).await;
+        assert!(
+            matches!(result, Err(AmiError::InvalidParameter { message }) if message.contains("between 1 and 12 hours"))
+        );
+
+        let mut malformed = create_test_permission_set("garbage", "instance-1");
+        malformed.session_duration = Some("garbage".to_string());
+        let result = service.create_permission_set(malformed).await;
+        assert!(
+            matches!(result, Err(AmiError::InvalidParameter { message }) if message.contains("ISO-8601"))
+        );
+
+        let permission_sets = service.list_permission_sets("instance-1").await.unwrap();
+        assert_eq!(permission_sets.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_permission_set_inline_policy() {
+        let service = setup_service();
+        let permission_set = create_test_permission_set("s3-reader", "instance-1");
+        let arn = permission_set.permission_set_arn.clone();
+        service.create_permission_set(permission_set).await.unwrap();
+
+        assert_eq!(
+            service
+                .get_inline_policy_for_permission_set(&arn)
+                .await
+                .unwrap(),
+            None
+        );
+
+        let policy = r#"{"Version":"2012-10-17","Statement":[{"Effect":"Allow","Action":"s3:GetObject","Resource":"*"}]}"#;
+        service
+            .put_inline_policy_to_permission_set(&arn, policy.to_string())
+            .await
+            .unwrap();
+        assert_eq!(
+            service
+                .get_inline_policy_for_permission_set(&arn)
+                .await
+                .unwrap()
+                .as_deref(),
+            Some(policy)
+        );
+
+        // Malformed documents are rejected and leave the stored policy alone
+        let result = service
+            .put_inline_policy_to_permission_set(&arn, "not a policy".to_string())
+            .await;
+        assert!(matches!(result, Err(AmiError::InvalidParameter { .. })));
+
+        service
+            .delete_inline_policy_from_permission_set(&arn)
+            .await
+            .unwrap();
+        let permission_set = service.get_permission_set(&arn).await.unwrap().unwrap();
+        assert!(permission_set.inline_policy.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_permission_set_inline_policy_missing_permission_set() {
+        let service = setup_service();
+        let policy = r#"{"Version":"2012-10-17","Statement":[]}"#;
+
+        let result = service
+            .put_inline_policy_to_permission_set("missing", policy.to_string())
+            .await;
+        assert!(matches!(result, Err(AmiError::ResourceNotFound { .. })));
+        let result = service
+            .get_inline_policy_for_permission_set("missing")
+            .await;
+        assert!(matches!(result, Err(AmiError::ResourceNotFound { .. })));
+    }
+
+    fn create_test_assignment(principal_id: &str, permission_set_arn: &str) -> AccountAssignment {
+        AccountAssignment {
+            assignment_id: String::new(),
+            instance_arn: "instance-1".to_string(),
+            account_id: "123456789012".to_string(),
+            permission_set_arn: permission_set_arn.to_string(),
+            principal_id: principal_id.to_string(),
+            principal_type: "USER".to_string(),
+            target_id: "123456789012".to_string(),
+            target_type: "AWS_ACCOUNT".to_string(),
+            created_date: Utc::now(),
+            wami_arn: format!(
+                "arn:wami:.*:0:wami:123456789012:assignment/{}",
+                principal_id
+            )
+            .parse()
+            .unwrap(),
+            providers: vec![],
+            status: AssignmentStatus::Succeeded,
+            request_id: String::new(),
+            failure_reason: None,
+        }
+    }
+
+    async fn setup_assigned_permission_set(
+        service: &PermissionSetService<InMemoryWamiStore>,
+    ) -> String {
+        let permission_set = create_test_permission_set("assigned", "instance-1");
+        let arn = permission_set.permission_set_arn.clone();
+        service.create_permission_set(permission_set).await.unwrap();
+
+        let mut store = service.store.write().unwrap();
+        for principal in ["alice", "bob"] {
+            let mut assignment = create_test_assignment(principal, &arn);
+            assignment.assignment_id = assignment.key();
+            store.create_account_assignment(assignment).await.unwrap();
+        }
+        arn
+    }
+
+    #[tokio::test]
+    async fn test_delete_permission_set_refused_with_assignments() {
+        let service = setup_service();
+        let arn = setup_assigned_permission_set(&service).await;
+
+        let result = service.delete_permission_set(&arn).await;
+        assert!(matches!(result, Err(AmiError::DeleteConflict { .. })));
+
+        assert!(service.get_permission_set(&arn).await.unwrap().is_some());
+        let store = service.store.read().unwrap();
+        assert_eq!(store.list_all_account_assignments().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_delete_permission_set_cascades_assignments() {
+        let service = setup_service().with_cascade_delete(true);
+        let arn = setup_assigned_permission_set(&service).await;
+
+        service.delete_permission_set(&arn).await.unwrap();
+
+        assert!(service.get_permission_set(&arn).await.unwrap().is_none());
+        let store = service.store.read().unwrap();
+        assert!(store
+            .list_all_account_assignments()
+            .await
+            .unwrap()
+            .is_empty());
+    }
 }
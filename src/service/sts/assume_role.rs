@@ -5,8 +5,19 @@
 use crate::arn::{Service, WamiArn};
 use crate::context::WamiContext;
 use crate::error::{AmiError, Result};
-use crate::store::traits::{RoleStore, SessionStore};
-use crate::wami::sts::assume_role::{AssumeRoleRequest, AssumeRoleResponse, AssumedRoleUser};
+use crate::provider::{AwsProvider, CloudProvider};
+use crate::store::traits::{IdentityProviderStore, RoleStore, SessionStore};
+use crate::types::{PaginationParams, Tag};
+use crate::wami::identity::identity_provider::OidcProvider;
+use crate::wami::identity::role::Role;
+use crate::wami::policies::evaluation::ContextEntry;
+use crate::wami::sts::assume_role::requests::validate_session_tags;
+use crate::wami::sts::assume_role::trust::EXTERNAL_ID_CONTEXT_KEY;
+use crate::wami::sts::assume_role::{
+    decode_saml_assertion, decode_web_identity_token, evaluate_trust_policy, AssumeRoleRequest,
+    AssumeRoleResponse, AssumeRoleWithSamlRequest, AssumeRoleWithWebIdentityRequest,
+    AssumedRoleUser, TrustDecision, TrustedCaller, ROLE_CHAINING_MAX_SESSION_DURATION,
+};
 use crate::wami::sts::session::SessionStatus;
 use crate::wami::sts::{Credentials, StsSession};
 use chrono::{Duration, Utc};
@@ -17,17 +28,59 @@ use std::sync::{Arc, RwLock};
 /// Provides high-level operations for role assumption and temporary credentials.
 pub struct AssumeRoleService<S> {
     store: Arc<RwLock<S>>,
+    provider: Arc<dyn CloudProvider>,
 }
 
 impl<S: SessionStore + RoleStore> AssumeRoleService<S> {
-    /// Create a new AssumeRoleService
+    /// Create a new AssumeRoleService with default AWS provider
     pub fn new(store: Arc<RwLock<S>>) -> Self {
-        Self { store }
+        Self {
+            store,
+            provider: Arc::new(AwsProvider::new()),
+        }
+    }
+
+    /// Returns a new service instance with different provider
+    ///
+    /// The provider supplies the session duration used when a request does
+    /// not specify one.
+    pub fn with_provider(&self, provider: Arc<dyn CloudProvider>) -> Self {
+        Self {
+            store: self.store.clone(),
+            provider,
+        }
     }
 
     /// Assume an IAM role
     ///
-    /// Returns temporary credentials for the assumed role.
+    /// Returns temporary credentials for the assumed role. The role's trust
+    /// policy must allow `principal_arn` (or the context's caller) to perform
+    /// `sts:AssumeRole`; the request's external ID is available to its
+    /// conditions as `sts:ExternalId`. Otherwise `AccessDenied` is returned.
+    /// A service principal such as `ec2.amazonaws.com` passed as `principal_arn`
+    /// is only trusted by statements naming it under `Service`.
+    ///
+    /// A role in another account than the context's can only be assumed when
+    /// its trust policy names the caller's account or principal; trusting the
+    /// role's own account or `*` is not enough. The assumed role user's ARN
+    /// belongs to the role's account.
+    ///
+    /// Assuming a role from an assumed-role session is role chaining: the
+    /// session lasts at most [`ROLE_CHAINING_MAX_SESSION_DURATION`], and a chain
+    /// longer than the provider's `max_role_chain_length` is rejected with
+    /// `InvalidParameter`. The calling session's transitive tags are carried
+    /// into the new session, and cannot be overridden by the request's tags.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                operation = "AssumeRole",
+                tenant = %context.tenant_path(),
+                resource = %request.role_arn,
+            )
+        )
+    )]
     pub async fn assume_role(
         &self,
         context: &WamiContext,
@@ -37,26 +90,174 @@ impl<S: SessionStore + RoleStore> AssumeRoleService<S> {
         // Validate request
         request.validate()?;
 
+        let role = self.find_role(&request.role_arn).await?;
+        self.check_trust(
+            context,
+            &role,
+            principal_arn,
+            request.external_id.as_deref(),
+        )?;
+
+        let caller_session = self.caller_session(context, principal_arn).await?;
+        // Sessions stored before chain tracking still count as one role
+        let role_chain_length = caller_session
+            .as_ref()
+            .map_or(0, |session| session.role_chain_length.max(1))
+            + 1;
+        let max_role_chain_length = self.provider.resource_limits().max_role_chain_length;
+        if role_chain_length as usize > max_role_chain_length {
+            return Err(AmiError::InvalidParameter {
+                message: format!(
+                    "Assuming {} would chain {} roles, exceeding the limit of {}",
+                    request.role_arn, role_chain_length, max_role_chain_length
+                ),
+            });
+        }
+
+        let (tags, transitive_tag_keys) = inherit_transitive_tags(
+            caller_session.as_ref(),
+            request.tags,
+            request.transitive_tag_keys,
+        )?;
+
+        self.issue_role_session(
+            context,
+            &role,
+            &request.role_arn,
+            &request.role_session_name,
+            request.duration_seconds,
+            request.policy,
+            principal_arn,
+            None,
+            role_chain_length,
+            tags,
+            transitive_tag_keys,
+        )
+        .await
+    }
+
+    // Helper methods
+
+    /// Check that the role's trust policy lets the caller assume it
+    #[allow(clippy::result_large_err)]
+    fn check_trust(
+        &self,
+        context: &WamiContext,
+        role: &Role,
+        principal_arn: &str,
+        external_id: Option<&str>,
+    ) -> Result<()> {
+        // A service principal (e.g. `ec2.amazonaws.com`) is only trusted when named
+        let is_service = !principal_arn.starts_with("arn:") && principal_arn.contains('.');
+
+        // The caller belongs to the context's account and to the one named in its ARN
+        let mut account_ids = vec![context.instance_id().to_string()];
+        if let Some(account_id) = principal_arn
+            .strip_prefix("arn:aws:")
+            .and_then(|rest| rest.split(':').nth(2))
+            .filter(|account_id| !account_id.is_empty())
+        {
+            account_ids.push(account_id.to_string());
+        }
+
+        // Across accounts, trusting the role's own account does not trust the caller
+        let role_account_id = role_account_id(role);
+        let cross_account = context.instance_id() != role_account_id;
+        if cross_account {
+            account_ids.retain(|account_id| account_id != role_account_id);
+        }
+        let caller = if is_service {
+            TrustedCaller {
+                services: vec![principal_arn.to_string()],
+                explicit_principal_required: true,
+                ..Default::default()
+            }
+        } else {
+            TrustedCaller {
+                principal_arns: vec![principal_arn.to_string(), context.caller_arn().to_string()],
+                account_ids,
+                explicit_principal_required: cross_account,
+                ..Default::default()
+            }
+        };
+
+        let mut context_entries = context.condition_context();
+        if let Some(external_id) = external_id {
+            context_entries.push(ContextEntry {
+                context_key_name: EXTERNAL_ID_CONTEXT_KEY.to_string(),
+                context_key_values: vec![external_id.to_string()],
+                context_key_type: "String".to_string(),
+            });
+        }
+
+        match evaluate_trust_policy(
+            &role.assume_role_policy_document,
+            "sts:AssumeRole",
+            &caller,
+            &context_entries,
+        )? {
+            TrustDecision::Allowed => Ok(()),
+            TrustDecision::PrincipalNotTrusted if cross_account && !is_service => Err(AmiError::AccessDenied {
+                message: format!(
+                    "{} is not authorized to perform sts:AssumeRole on {}: the role's trust policy does not trust account {}",
+                    principal_arn, role.arn, context.instance_id()
+                ),
+            }),
+            TrustDecision::PrincipalNotTrusted => Err(AmiError::AccessDenied {
+                message: format!(
+                    "{} is not authorized to perform sts:AssumeRole on {}: the role's trust policy does not trust it",
+                    principal_arn, role.arn
+                ),
+            }),
+            TrustDecision::ConditionsNotMet => Err(AmiError::AccessDenied {
+                message: format!(
+                    "{} is not authorized to perform sts:AssumeRole on {}: the trust policy conditions are not met (check the external ID)",
+                    principal_arn, role.arn
+                ),
+            }),
+        }
+    }
+
+    /// The active assumed-role session the caller acts from, if any
+    ///
+    /// When several match, the one at the end of the longest chain is returned.
+    async fn caller_session(
+        &self,
+        context: &WamiContext,
+        principal_arn: &str,
+    ) -> Result<Option<StsSession>> {
+        let sessions = self.store.read().unwrap().list_sessions(None).await?;
+        Ok(sessions
+            .into_iter()
+            .filter(|session| session.is_valid() && session.assumed_role_arn.is_some())
+            .filter(|session| {
+                session.arn == principal_arn || &session.wami_arn == context.caller_arn()
+            })
+            .max_by_key(|session| session.role_chain_length))
+    }
+
+    /// Look up a role by its WAMI ARN or AWS-format ARN
+    async fn find_role(&self, role_arn: &str) -> Result<Role> {
         // Verify role exists - try parsing as WAMI ARN first
-        let role = if let Ok(wami_arn) = request.role_arn.parse::<crate::arn::WamiArn>() {
+        if let Ok(wami_arn) = role_arn.parse::<crate::arn::WamiArn>() {
             if wami_arn.resource.resource_type == "role" {
                 // Search for role by matching wami_arn
                 let store_guard = self.store.read().unwrap();
                 let (roles, _, _) = store_guard.list_roles(None, None).await?;
                 roles
                     .into_iter()
-                    .find(|r| r.wami_arn.to_string() == request.role_arn)
+                    .find(|r| r.wami_arn.to_string() == role_arn)
                     .ok_or_else(|| AmiError::ResourceNotFound {
-                        resource: format!("Role: {}", request.role_arn),
-                    })?
+                        resource: format!("Role: {}", role_arn),
+                    })
             } else {
-                return Err(AmiError::InvalidParameter {
-                    message: format!("ARN is not a role: {}", request.role_arn),
-                });
+                Err(AmiError::InvalidParameter {
+                    message: format!("ARN is not a role: {}", role_arn),
+                })
             }
         } else {
             // Fall back to AWS format
-            let role_name = self.extract_role_name_from_arn(&request.role_arn)?;
+            let role_name = self.extract_role_name_from_arn(role_arn)?;
             self.store
                 .read()
                 .unwrap()
@@ -64,13 +265,41 @@ impl<S: SessionStore + RoleStore> AssumeRoleService<S> {
                 .await?
                 .ok_or_else(|| AmiError::ResourceNotFound {
                     resource: format!("Role: {}", role_name),
-                })?
-        };
+                })
+        }
+    }
 
-        // Determine session duration (default: 1 hour, max: role's max session duration or 12 hours)
-        let max_duration = role.max_session_duration.unwrap_or(43200);
-        let duration_seconds = request.duration_seconds.unwrap_or(3600).min(max_duration);
-        let expiration = Utc::now() + Duration::seconds(duration_seconds as i64);
+    /// Generate credentials for an assumed role and persist the session
+    #[allow(clippy::too_many_arguments)]
+    async fn issue_role_session(
+        &self,
+        context: &WamiContext,
+        role: &Role,
+        role_arn: &str,
+        role_session_name: &str,
+        duration_seconds: Option<i32>,
+        policy: Option<String>,
+        principal_arn: &str,
+        federated_subject: Option<String>,
+        role_chain_length: u32,
+        tags: Vec<Tag>,
+        transitive_tag_keys: Vec<String>,
+    ) -> Result<AssumeRoleResponse> {
+        // Determine session duration (default: provider's default, max: role's max session duration or 12 hours,
+        // capped by the provider's limit, or one hour when chaining roles)
+        let mut max_duration = role
+            .max_session_duration
+            .unwrap_or(43200)
+            .min(self.provider.resource_limits().session_duration_max);
+        if role_chain_length > 1 {
+            max_duration = max_duration.min(ROLE_CHAINING_MAX_SESSION_DURATION);
+        }
+        let duration_seconds = duration_seconds
+            .unwrap_or_else(|| self.provider.default_session_duration())
+            .min(max_duration);
+        // The returned credentials and the stored session share this expiration
+        let created_at = Utc::now();
+        let expiration = created_at + Duration::seconds(duration_seconds as i64);
 
         // Generate credentials
         let access_key_id = format!(
@@ -90,9 +319,9 @@ impl<S: SessionStore + RoleStore> AssumeRoleService<S> {
 
         let session_arn = format!(
             "arn:aws:sts::{}:assumed-role/{}/{}",
-            context.instance_id(),
+            role_account_id(role),
             &role.role_name,
-            request.role_session_name
+            role_session_name
         );
 
         // Build WAMI ARN for credentials using context
@@ -102,7 +331,7 @@ impl<S: SessionStore + RoleStore> AssumeRoleService<S> {
             .wami_instance(context.instance_id())
             .resource(
                 "session",
-                format!("{}/{}", role.role_name, request.role_session_name),
+                format!("{}/{}", role.role_name, role_session_name),
             )
             .build()?;
 
@@ -139,15 +368,19 @@ impl<S: SessionStore + RoleStore> AssumeRoleService<S> {
             secret_access_key,
             expiration,
             status: SessionStatus::Active,
-            assumed_role_arn: Some(request.role_arn.clone()),
-            federated_user_name: None,
+            assumed_role_arn: Some(role_arn.to_string()),
+            federated_user_name: federated_subject.clone(),
             principal_arn: Some(principal_arn.to_string()),
             arn: session_arn,
             wami_arn,
             providers: vec![],
             tenant_id: None,
-            created_at: Utc::now(),
+            created_at,
             last_used: None,
+            policy,
+            role_chain_length,
+            tags,
+            transitive_tag_keys,
         };
 
         self.store.write().unwrap().create_session(session).await?;
@@ -155,11 +388,10 @@ impl<S: SessionStore + RoleStore> AssumeRoleService<S> {
         Ok(AssumeRoleResponse {
             credentials,
             assumed_role_user,
+            federated_subject,
         })
     }
 
-    // Helper methods
-
     fn extract_role_name_from_arn(&self, arn: &str) -> Result<String> {
         // Try parsing as WAMI ARN first
         if let Ok(wami_arn) = arn.parse::<crate::arn::WamiArn>() {
@@ -194,13 +426,241 @@ impl<S: SessionStore + RoleStore> AssumeRoleService<S> {
     }
 }
 
+impl<S: SessionStore + RoleStore + IdentityProviderStore> AssumeRoleService<S> {
+    /// Assume an IAM role with an OIDC web identity token
+    ///
+    /// The token's payload is decoded (its signature is not verified) and an
+    /// OIDC provider matching `provider_id` (or the token's issuer) must exist
+    /// with the token's audience in its client ID list. The token subject is
+    /// surfaced as the federated subject of the session.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                operation = "AssumeRoleWithWebIdentity",
+                tenant = %context.tenant_path(),
+                resource = %request.role_arn,
+            )
+        )
+    )]
+    pub async fn assume_role_with_web_identity(
+        &self,
+        context: &WamiContext,
+        request: AssumeRoleWithWebIdentityRequest,
+    ) -> Result<AssumeRoleResponse> {
+        request.validate()?;
+
+        let claims = decode_web_identity_token(&request.web_identity_token)?;
+
+        let provider_id = request
+            .provider_id
+            .as_deref()
+            .or_else(|| claims.issuer_host())
+            .ok_or_else(|| AmiError::InvalidParameter {
+                message: "Provider ID is required when the token has no 'iss' claim".to_string(),
+            })?
+            .to_string();
+
+        let provider = self.find_oidc_provider(&provider_id).await?;
+
+        if !claims
+            .audiences
+            .iter()
+            .any(|aud| provider.client_id_list.contains(aud))
+        {
+            return Err(AmiError::InvalidParameter {
+                message: format!(
+                    "Web identity token audience is not registered with OIDC provider: {}",
+                    provider.arn
+                ),
+            });
+        }
+
+        let role = self.find_role(&request.role_arn).await?;
+
+        self.issue_role_session(
+            context,
+            &role,
+            &request.role_arn,
+            &request.role_session_name,
+            request.duration_seconds,
+            request.policy,
+            &provider.arn,
+            Some(claims.subject),
+            1,
+            vec![],
+            vec![],
+        )
+        .await
+    }
+
+    /// Assume an IAM role with a SAML assertion
+    ///
+    /// `principal_arn` must reference a SAML provider in the store. The
+    /// assertion's `NameID` becomes the session name and federated subject;
+    /// its `SessionDuration` attribute caps the session duration. An assertion
+    /// naming an `Issuer` must match the provider's metadata entity ID.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(
+                operation = "AssumeRoleWithSAML",
+                tenant = %context.tenant_path(),
+                resource = %request.role_arn,
+            )
+        )
+    )]
+    pub async fn assume_role_with_saml(
+        &self,
+        context: &WamiContext,
+        request: AssumeRoleWithSamlRequest,
+    ) -> Result<AssumeRoleResponse> {
+        request.validate()?;
+
+        let provider = self
+            .store
+            .read()
+            .unwrap()
+            .get_saml_provider(&request.principal_arn)
+            .await?
+            .ok_or_else(|| AmiError::ResourceNotFound {
+                resource: format!("SAML provider: {}", request.principal_arn),
+            })?;
+
+        let assertion = decode_saml_assertion(&request.saml_assertion)?;
+        if let (Some(issuer), Some(entity_id)) = (&assertion.issuer, &provider.entity_id) {
+            if issuer != entity_id {
+                return Err(AmiError::AccessDenied {
+                    message: format!(
+                        "SAML assertion issuer {} does not match provider entity {}",
+                        issuer, entity_id
+                    ),
+                });
+            }
+        }
+
+        let duration_seconds = match (request.duration_seconds, assertion.session_duration) {
+            (Some(requested), Some(asserted)) => Some(requested.min(asserted)),
+            (requested, asserted) => requested.or(asserted),
+        };
+
+        let role = self.find_role(&request.role_arn).await?;
+
+        self.issue_role_session(
+            context,
+            &role,
+            &request.role_arn,
+            &assertion.role_session_name(),
+            duration_seconds,
+            request.policy,
+            &provider.arn,
+            Some(assertion.name_id),
+            1,
+            vec![],
+            vec![],
+        )
+        .await
+    }
+
+    /// Find an OIDC provider by ARN or by URL host (e.g. `accounts.google.com`)
+    async fn find_oidc_provider(&self, provider_id: &str) -> Result<OidcProvider> {
+        let store = self.store.read().unwrap();
+
+        if let Some(provider) = store.get_oidc_provider(provider_id).await? {
+            return Ok(provider);
+        }
+
+        let host = provider_id
+            .trim_start_matches("https://")
+            .trim_end_matches('/');
+        let mut pagination = PaginationParams {
+            max_items: Some(100),
+            marker: None,
+        };
+        loop {
+            let (providers, is_truncated, marker) =
+                store.list_oidc_providers(Some(&pagination)).await?;
+            if let Some(provider) = providers
+                .into_iter()
+                .find(|p| p.url.trim_start_matches("https://").trim_end_matches('/') == host)
+            {
+                return Ok(provider);
+            }
+            if !is_truncated || marker.is_none() {
+                break;
+            }
+            pagination.marker = marker;
+        }
+
+        Err(AmiError::ResourceNotFound {
+            resource: format!("OIDC provider: {}", provider_id),
+        })
+    }
+}
+
+/// The account a role belongs to
+fn role_account_id(role: &Role) -> &str {
+    &role.wami_arn.wami_instance_id
+}
+
+/// Add the calling session's transitive tags to a new session's tags
+///
+/// Inherited tags stay transitive, count toward the session tag limit, and
+/// cannot be overridden by a tag of the same key.
+#[allow(clippy::result_large_err)]
+fn inherit_transitive_tags(
+    caller_session: Option<&StsSession>,
+    mut tags: Vec<Tag>,
+    mut transitive_tag_keys: Vec<String>,
+) -> Result<(Vec<Tag>, Vec<String>)> {
+    let Some(caller_session) = caller_session else {
+        return Ok((tags, transitive_tag_keys));
+    };
+
+    for key in &caller_session.transitive_tag_keys {
+        let Some(inherited) = caller_session
+            .tags
+            .iter()
+            .find(|tag| tag.key.eq_ignore_ascii_case(key))
+        else {
+            continue;
+        };
+        if tags.iter().any(|tag| tag.key.eq_ignore_ascii_case(key)) {
+            return Err(AmiError::InvalidParameter {
+                message: format!(
+                    "Session tag {} conflicts with a transitive tag of the calling session",
+                    key
+                ),
+            });
+        }
+        tags.push(inherited.clone());
+        transitive_tag_keys.push(inherited.key.clone());
+    }
+
+    validate_session_tags(&tags, &transitive_tag_keys)?;
+    Ok((tags, transitive_tag_keys))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::arn::{TenantPath, WamiArn};
     use crate::context::WamiContext;
+    use crate::provider::ResourceLimits;
     use crate::store::memory::InMemoryWamiStore;
+    use crate::wami::identity::identity_provider::builder::{
+        build_oidc_provider, build_saml_provider,
+    };
     use crate::wami::identity::role::builder::build_role;
+    use crate::wami::sts::assume_role::MAX_SESSION_TAGS;
+
+    /// Trusts every principal of the test account
+    const ACCOUNT_TRUST_POLICY: &str = r#"{"Version":"2012-10-17","Statement":[{"Effect":"Allow","Principal":{"AWS":"arn:aws:iam::123456789012:root"},"Action":"sts:AssumeRole"}]}"#;
+
+    /// Trusts a third-party account presenting its external ID
+    const EXTERNAL_ID_TRUST_POLICY: &str = r#"{"Version":"2012-10-17","Statement":[{"Effect":"Allow","Principal":{"AWS":"arn:aws:iam::999999999999:root"},"Action":"sts:AssumeRole","Condition":{"StringEquals":{"sts:ExternalId":"unique-external-id-12345"}}}]}"#;
 
     fn setup_service() -> AssumeRoleService<InMemoryWamiStore> {
         let store = Arc::new(RwLock::new(InMemoryWamiStore::default()));
@@ -220,13 +680,26 @@ mod tests {
             .unwrap()
     }
 
+    fn other_account_context() -> WamiContext {
+        let arn: WamiArn = "arn:wami:.*:87654321:wami:999999999999:user/mallory"
+            .parse()
+            .unwrap();
+        WamiContext::builder()
+            .instance_id("999999999999")
+            .tenant_path(TenantPath::single(87654321))
+            .caller_arn(arn)
+            .is_root(false)
+            .build()
+            .unwrap()
+    }
+
     #[tokio::test]
     async fn test_assume_role() {
         let service = setup_service();
         let context = test_context();
 
         // Create a role
-        let trust_policy = r#"{"Version":"2012-10-17","Statement":[]}"#;
+        let trust_policy = ACCOUNT_TRUST_POLICY;
         let role = build_role(
             "TestRole".to_string(),
             trust_policy.to_string(),
@@ -254,6 +727,8 @@ mod tests {
             duration_seconds: Some(3600),
             external_id: None,
             policy: None,
+            tags: vec![],
+            transitive_tag_keys: vec![],
         };
 
         let response = service
@@ -277,6 +752,8 @@ mod tests {
             duration_seconds: Some(3600),
             external_id: None,
             policy: None,
+            tags: vec![],
+            transitive_tag_keys: vec![],
         };
 
         let context = test_context();
@@ -294,7 +771,7 @@ mod tests {
         let context = test_context();
 
         // Create a role
-        let trust_policy = r#"{"Version":"2012-10-17","Statement":[]}"#;
+        let trust_policy = EXTERNAL_ID_TRUST_POLICY;
         let role = build_role(
             "CrossAccountRole".to_string(),
             trust_policy.to_string(),
@@ -322,6 +799,8 @@ mod tests {
             duration_seconds: Some(7200),
             external_id: Some("unique-external-id-12345".to_string()),
             policy: None,
+            tags: vec![],
+            transitive_tag_keys: vec![],
         };
 
         let response = service
@@ -342,7 +821,7 @@ mod tests {
         let context = test_context();
 
         // Create a role
-        let trust_policy = r#"{"Version":"2012-10-17","Statement":[]}"#;
+        let trust_policy = ACCOUNT_TRUST_POLICY;
         let role = build_role(
             "SessionRole".to_string(),
             trust_policy.to_string(),
@@ -370,6 +849,8 @@ mod tests {
             duration_seconds: Some(3600),
             external_id: None,
             policy: None,
+            tags: vec![],
+            transitive_tag_keys: vec![],
         };
 
         let response = service
@@ -393,6 +874,834 @@ mod tests {
         assert!(sessions[0].assumed_role_arn.is_some());
     }
 
+    async fn create_role_with_trust(
+        service: &AssumeRoleService<InMemoryWamiStore>,
+        role_name: &str,
+        trust_policy: &str,
+    ) -> String {
+        let role = build_role(
+            role_name.to_string(),
+            trust_policy.to_string(),
+            None,
+            None,
+            None,
+            &test_context(),
+        )
+        .unwrap();
+        let role_arn = role.wami_arn.to_string();
+        service
+            .store
+            .write()
+            .unwrap()
+            .create_role(role)
+            .await
+            .unwrap();
+        role_arn
+    }
+
+    fn assume_request(role_arn: &str, external_id: Option<&str>) -> AssumeRoleRequest {
+        AssumeRoleRequest {
+            role_arn: role_arn.to_string(),
+            role_session_name: "trust-check".to_string(),
+            duration_seconds: None,
+            external_id: external_id.map(str::to_string),
+            policy: None,
+            tags: vec![],
+            transitive_tag_keys: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_assume_role_trusted_principal() {
+        let service = setup_service();
+        let role_arn = create_role_with_trust(
+            &service,
+            "AliceOnly",
+            r#"{"Version":"2012-10-17","Statement":[{"Effect":"Allow","Principal":{"AWS":"arn:aws:iam::123456789012:user/alice"},"Action":"sts:AssumeRole"}]}"#,
+        )
+        .await;
+
+        service
+            .assume_role(
+                &test_context(),
+                assume_request(&role_arn, None),
+                "arn:aws:iam::123456789012:user/alice",
+            )
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_assume_role_untrusted_principal() {
+        let service = setup_service();
+        let role_arn = create_role_with_trust(&service, "AccountRole", ACCOUNT_TRUST_POLICY).await;
+
+        // A principal of another account is not trusted
+        let result = service
+            .assume_role(
+                &other_account_context(),
+                assume_request(&role_arn, None),
+                "arn:aws:iam::999999999999:user/mallory",
+            )
+            .await;
+        match result {
+            Err(AmiError::AccessDenied { message }) => {
+                assert!(message.contains("does not trust"), "{}", message)
+            }
+            other => panic!("expected AccessDenied, got {:?}", other.map(|_| ())),
+        }
+
+        // A role whose trust policy has no statements cannot be assumed at all
+        let role_arn = create_role_with_trust(
+            &service,
+            "NobodyRole",
+            r#"{"Version":"2012-10-17","Statement":[]}"#,
+        )
+        .await;
+        let result = service
+            .assume_role(
+                &test_context(),
+                assume_request(&role_arn, None),
+                "arn:aws:iam::123456789012:user/alice",
+            )
+            .await;
+        assert!(matches!(result, Err(AmiError::AccessDenied { .. })));
+
+        // No session was issued
+        assert!(service
+            .store
+            .read()
+            .unwrap()
+            .list_sessions(None)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    /// Create a role owned by the other account
+    async fn create_other_account_role(
+        service: &AssumeRoleService<InMemoryWamiStore>,
+        role_name: &str,
+        trust_policy: &str,
+    ) -> String {
+        let role = build_role(
+            role_name.to_string(),
+            trust_policy.to_string(),
+            None,
+            None,
+            None,
+            &other_account_context(),
+        )
+        .unwrap();
+        let role_arn = role.wami_arn.to_string();
+        service
+            .store
+            .write()
+            .unwrap()
+            .create_role(role)
+            .await
+            .unwrap();
+        role_arn
+    }
+
+    #[tokio::test]
+    async fn test_assume_role_cross_account_allowed() {
+        let service = setup_service();
+        let role_arn =
+            create_other_account_role(&service, "SharedRole", ACCOUNT_TRUST_POLICY).await;
+
+        let response = service
+            .assume_role(
+                &test_context(),
+                assume_request(&role_arn, None),
+                "arn:aws:iam::123456789012:user/alice",
+            )
+            .await
+            .unwrap();
+
+        // The session belongs to the role's account, not the caller's
+        assert_eq!(
+            response.assumed_role_user.arn,
+            "arn:aws:sts::999999999999:assumed-role/SharedRole/trust-check"
+        );
+        assert_eq!(response.credentials.arn, response.assumed_role_user.arn);
+    }
+
+    #[tokio::test]
+    async fn test_assume_role_cross_account_denied() {
+        let service = setup_service();
+
+        // Trusting the role's own account or anyone does not name the caller's account
+        for (role_name, trust_policy) in [
+            (
+                "OwnAccountRole",
+                r#"{"Version":"2012-10-17","Statement":[{"Effect":"Allow","Principal":{"AWS":"arn:aws:iam::999999999999:root"},"Action":"sts:AssumeRole"}]}"#,
+            ),
+            (
+                "WildcardRole",
+                r#"{"Version":"2012-10-17","Statement":[{"Effect":"Allow","Principal":{"AWS":"*"},"Action":"sts:AssumeRole"}]}"#,
+            ),
+        ] {
+            let role_arn = create_other_account_role(&service, role_name, trust_policy).await;
+            let result = service
+                .assume_role(
+                    &test_context(),
+                    assume_request(&role_arn, None),
+                    "arn:aws:iam::999999999999:user/alice",
+                )
+                .await;
+            match result {
+                Err(AmiError::AccessDenied { message }) => {
+                    assert!(
+                        message.contains("does not trust account 123456789012"),
+                        "{}",
+                        message
+                    )
+                }
+                other => panic!("expected AccessDenied, got {:?}", other.map(|_| ())),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_assume_role_chaining_limit() {
+        let service =
+            setup_service().with_provider(Arc::new(AwsProvider::with_limits(ResourceLimits {
+                max_role_chain_length: 2,
+                ..Default::default()
+            })));
+        let mut principal_arn = "arn:aws:iam::123456789012:user/alice".to_string();
+        let mut lifetimes = Vec::new();
+        for role_name in ["FirstRole", "SecondRole"] {
+            let role_arn = create_role_with_trust(&service, role_name, ACCOUNT_TRUST_POLICY).await;
+            let mut request = assume_request(&role_arn, None);
+            request.duration_seconds = Some(7200);
+            let response = service
+                .assume_role(&test_context(), request, &principal_arn)
+                .await
+                .unwrap();
+            lifetimes.push((response.credentials.expiration - Utc::now()).num_seconds());
+            principal_arn = response.assumed_role_user.arn;
+        }
+
+        // Only the chained session is clamped to one hour
+        assert!(lifetimes[0] > 3600, "{:?}", lifetimes);
+        assert!(lifetimes[1] <= 3600, "{:?}", lifetimes);
+        let sessions = service
+            .store
+            .read()
+            .unwrap()
+            .list_sessions(None)
+            .await
+            .unwrap();
+        let mut chain_lengths: Vec<u32> = sessions.iter().map(|s| s.role_chain_length).collect();
+        chain_lengths.sort();
+        assert_eq!(chain_lengths, [1, 2]);
+
+        // A third role would exceed the chain limit
+        let role_arn = create_role_with_trust(&service, "ThirdRole", ACCOUNT_TRUST_POLICY).await;
+        let result = service
+            .assume_role(
+                &test_context(),
+                assume_request(&role_arn, None),
+                &principal_arn,
+            )
+            .await;
+        match result {
+            Err(AmiError::InvalidParameter { message }) => {
+                assert!(message.contains("limit of 2"), "{}", message)
+            }
+            other => panic!("expected InvalidParameter, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    fn tag(key: &str, value: &str) -> Tag {
+        Tag {
+            key: key.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_assume_role_transitive_tags_survive_chaining() {
+        let service = setup_service();
+        let first_arn = create_role_with_trust(&service, "FirstRole", ACCOUNT_TRUST_POLICY).await;
+        let second_arn = create_role_with_trust(&service, "SecondRole", ACCOUNT_TRUST_POLICY).await;
+
+        let mut request = assume_request(&first_arn, None);
+        request.tags = vec![tag("team", "payments"), tag("ticket", "OPS-1")];
+        request.transitive_tag_keys = vec!["team".to_string()];
+        let first = service
+            .assume_role(
+                &test_context(),
+                request,
+                "arn:aws:iam::123456789012:user/alice",
+            )
+            .await
+            .unwrap();
+
+        let mut request = assume_request(&second_arn, None);
+        request.tags = vec![tag("stage", "prod")];
+        let second = service
+            .assume_role(&test_context(), request, &first.assumed_role_user.arn)
+            .await
+            .unwrap();
+
+        let session = service
+            .store
+            .read()
+            .unwrap()
+            .get_session(&second.credentials.session_token)
+            .await
+            .unwrap()
+            .unwrap();
+        // Only the transitive tag is carried over, and it stays transitive
+        let tags: Vec<_> = session
+            .tags
+            .iter()
+            .map(|t| (t.key.as_str(), t.value.as_str()))
+            .collect();
+        assert_eq!(tags, [("stage", "prod"), ("team", "payments")]);
+        assert_eq!(session.transitive_tag_keys, ["team"]);
+
+        // The chained session cannot override an inherited transitive tag
+        let third_arn = create_role_with_trust(&service, "ThirdRole", ACCOUNT_TRUST_POLICY).await;
+        let mut request = assume_request(&third_arn, None);
+        request.tags = vec![tag("Team", "fraud")];
+        let result = service
+            .assume_role(&test_context(), request, &first.assumed_role_user.arn)
+            .await;
+        assert!(matches!(result, Err(AmiError::InvalidParameter { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_assume_role_rejects_invalid_session_tags() {
+        let service = setup_service();
+        let role_arn = create_role_with_trust(&service, "TaggedRole", ACCOUNT_TRUST_POLICY).await;
+        let principal_arn = "arn:aws:iam::123456789012:user/alice";
+
+        let mut duplicate = assume_request(&role_arn, None);
+        duplicate.tags = vec![tag("team", "a"), tag("Team", "b")];
+        let too_many = AssumeRoleRequest {
+            tags: (0..=MAX_SESSION_TAGS)
+                .map(|i| tag(&format!("key{}", i), "value"))
+                .collect(),
+            ..assume_request(&role_arn, None)
+        };
+
+        for request in [duplicate, too_many] {
+            let result = service
+                .assume_role(&test_context(), request, principal_arn)
+                .await;
+            assert!(matches!(result, Err(AmiError::InvalidParameter { .. })));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_assume_role_wrong_external_id() {
+        let service = setup_service();
+        let role_arn =
+            create_role_with_trust(&service, "PartnerRole", EXTERNAL_ID_TRUST_POLICY).await;
+
+        for external_id in [Some("guessed-id"), None] {
+            let result = service
+                .assume_role(
+                    &test_context(),
+                    assume_request(&role_arn, external_id),
+                    "arn:aws:iam::999999999999:user/external-user",
+                )
+                .await;
+            match result {
+                Err(AmiError::AccessDenied { message }) => {
+                    assert!(message.contains("external ID"), "{}", message)
+                }
+                other => panic!("expected AccessDenied, got {:?}", other.map(|_| ())),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_assume_role_rejects_malformed_external_id() {
+        let service = setup_service();
+        let role_arn =
+            create_role_with_trust(&service, "PartnerRole", EXTERNAL_ID_TRUST_POLICY).await;
+
+        let too_long = "x".repeat(1225);
+        for external_id in ["x", too_long.as_str(), "unique external id", "id#12345"] {
+            let result = service
+                .assume_role(
+                    &test_context(),
+                    assume_request(&role_arn, Some(external_id)),
+                    "arn:aws:iam::999999999999:user/external-user",
+                )
+                .await;
+            match result {
+                Err(AmiError::InvalidParameter { message }) => {
+                    assert!(message.contains("External ID"), "{}", message)
+                }
+                other => panic!("expected InvalidParameter, got {:?}", other.map(|_| ())),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_assume_role_by_service_principal() {
+        let service = setup_service();
+        let ec2_role = create_role_with_trust(
+            &service,
+            "InstanceRole",
+            r#"{"Version":"2012-10-17","Statement":[{"Effect":"Allow","Principal":{"Service":"ec2.amazonaws.com"},"Action":"sts:AssumeRole"}]}"#,
+        )
+        .await;
+        let account_role =
+            create_role_with_trust(&service, "AccountRole", ACCOUNT_TRUST_POLICY).await;
+
+        for (role_arn, principal, trusted) in [
+            (&ec2_role, "ec2.amazonaws.com", true),
+            // Neither another service nor a user of the account is named
+            (&ec2_role, "lambda.amazonaws.com", false),
+            (&ec2_role, "arn:aws:iam::123456789012:user/alice", false),
+            // Trusting the account does not trust services
+            (&account_role, "ec2.amazonaws.com", false),
+        ] {
+            let result = service
+                .assume_role(&test_context(), assume_request(role_arn, None), principal)
+                .await;
+            match result {
+                Ok(_) => assert!(trusted, "{} should not assume {}", principal, role_arn),
+                Err(AmiError::AccessDenied { .. }) => {
+                    assert!(!trusted, "{} should assume {}", principal, role_arn)
+                }
+                Err(e) => panic!("unexpected error: {:?}", e),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_assume_role_validates_session_name() {
+        let service = setup_service();
+        let role_arn = create_role_with_trust(&service, "NamedRole", ACCOUNT_TRUST_POLICY).await;
+
+        let mut request = assume_request(&role_arn, None);
+        request.role_session_name = "ci+deploy=1,user@example.com".to_string();
+        let response = service
+            .assume_role(
+                &test_context(),
+                request,
+                "arn:aws:iam::123456789012:user/alice",
+            )
+            .await
+            .unwrap();
+        assert!(response
+            .assumed_role_user
+            .arn
+            .ends_with("/ci+deploy=1,user@example.com"));
+
+        let too_long = "s".repeat(65);
+        for name in ["s", too_long.as_str(), "my session", "session/1", "sessión"] {
+            let mut request = assume_request(&role_arn, None);
+            request.role_session_name = name.to_string();
+            let result = service
+                .assume_role(
+                    &test_context(),
+                    request,
+                    "arn:aws:iam::123456789012:user/alice",
+                )
+                .await;
+            match result {
+                Err(AmiError::InvalidParameter { message }) => {
+                    assert!(message.contains("Role session name"), "{}", message)
+                }
+                other => panic!("expected InvalidParameter, got {:?}", other.map(|_| ())),
+            }
+        }
+    }
+
+    fn web_identity_token(payload: &str) -> String {
+        use base64::Engine;
+        let engine = base64::engine::general_purpose::URL_SAFE_NO_PAD;
+        format!(
+            "{}.{}.signature",
+            engine.encode(r#"{"alg":"RS256","typ":"JWT"}"#),
+            engine.encode(payload)
+        )
+    }
+
+    async fn assume_role_without_duration(service: &AssumeRoleService<InMemoryWamiStore>) -> i64 {
+        let context = test_context();
+        let role = build_role(
+            "DefaultDurationRole".to_string(),
+            ACCOUNT_TRUST_POLICY.to_string(),
+            None,
+            None,
+            None,
+            &context,
+        )
+        .unwrap();
+        let role_arn = role.wami_arn.to_string();
+        service
+            .store
+            .write()
+            .unwrap()
+            .create_role(role)
+            .await
+            .unwrap();
+
+        let request = AssumeRoleRequest {
+            role_arn,
+            role_session_name: "default-duration".to_string(),
+            duration_seconds: None,
+            external_id: None,
+            policy: None,
+            tags: vec![],
+            transitive_tag_keys: vec![],
+        };
+        let response = service
+            .assume_role(&context, request, "arn:aws:iam::123456789012:user/alice")
+            .await
+            .unwrap();
+
+        (response.credentials.expiration - Utc::now()).num_seconds()
+    }
+
+    #[tokio::test]
+    async fn test_assume_role_uses_provider_default_duration() {
+        let service = setup_service();
+        let remaining = assume_role_without_duration(&service).await;
+        assert!((3590..=3600).contains(&remaining));
+
+        let provider = crate::provider::CustomProvider::builder()
+            .default_session_duration(7200)
+            .build();
+        let service = setup_service().with_provider(Arc::new(provider));
+        let remaining = assume_role_without_duration(&service).await;
+        assert!((7190..=7200).contains(&remaining));
+    }
+
+    #[tokio::test]
+    async fn test_assume_role_expiration_matches_stored_session() {
+        use crate::service::sts::identity::{GetCallerIdentityRequest, IdentityService};
+
+        let limits = ResourceLimits {
+            session_duration_max: 7200,
+            ..Default::default()
+        };
+        let service = setup_service().with_provider(Arc::new(AwsProvider::with_limits(limits)));
+        let role_arn = create_role_with_trust(&service, "Timed", ACCOUNT_TRUST_POLICY).await;
+
+        // Requested, default and over-limit durations
+        for (requested, expected) in [(Some(1800), 1800), (None, 3600), (Some(43200), 7200)] {
+            let mut request = assume_request(&role_arn, None);
+            request.duration_seconds = requested;
+            let credentials = service
+                .assume_role(
+                    &test_context(),
+                    request,
+                    "arn:aws:iam::123456789012:user/alice",
+                )
+                .await
+                .unwrap()
+                .credentials;
+
+            let session = service
+                .store
+                .read()
+                .unwrap()
+                .get_session(&credentials.session_token)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(session.expiration, credentials.expiration);
+            assert_eq!(
+                (session.expiration - session.created_at).num_seconds(),
+                expected
+            );
+        }
+
+        // Once its session expires, the assumed role is no longer a valid caller
+        let arn = {
+            let mut store = service.store.write().unwrap();
+            let sessions = store.list_sessions(None).await.unwrap();
+            for mut session in sessions.clone() {
+                session.expiration = Utc::now() - Duration::seconds(1);
+                store.create_session(session).await.unwrap();
+            }
+            sessions[0].arn.clone()
+        };
+        let identity = IdentityService::new(service.store.clone(), "123456789012".to_string());
+        let result = identity
+            .get_caller_identity(GetCallerIdentityRequest {}, &arn)
+            .await;
+        assert!(matches!(result, Err(AmiError::ResourceNotFound { .. })));
+    }
+
+    async fn setup_web_identity(service: &AssumeRoleService<InMemoryWamiStore>) -> String {
+        let context = test_context();
+        let role = build_role(
+            "WebIdentityRole".to_string(),
+            r#"{"Version":"2012-10-17","Statement":[]}"#.to_string(),
+            Some("/".to_string()),
+            None,
+            None,
+            &context,
+        )
+        .unwrap();
+        let role_arn = role.wami_arn.to_string();
+
+        let provider = build_oidc_provider(
+            "https://token.actions.githubusercontent.com".to_string(),
+            vec!["sts.amazonaws.com".to_string()],
+            vec!["6938fd4d98bab03faadb97b34396831e3780aea1".to_string()],
+            &context,
+        )
+        .unwrap();
+
+        let mut store = service.store.write().unwrap();
+        store.create_role(role).await.unwrap();
+        store.create_oidc_provider(provider).await.unwrap();
+        role_arn
+    }
+
+    #[tokio::test]
+    async fn test_assume_role_with_web_identity() {
+        let service = setup_service();
+        let context = test_context();
+        let role_arn = setup_web_identity(&service).await;
+
+        let request = AssumeRoleWithWebIdentityRequest {
+            role_arn,
+            role_session_name: "deploy".to_string(),
+            web_identity_token: web_identity_token(
+                r#"{"iss":"https://token.actions.githubusercontent.com","sub":"repo:org/app:ref:refs/heads/main","aud":"sts.amazonaws.com"}"#,
+            ),
+            provider_id: None,
+            duration_seconds: Some(3600),
+            policy: Some(r#"{"Version":"2012-10-17","Statement":[]}"#.to_string()),
+        };
+
+        let response = service
+            .assume_role_with_web_identity(&context, request)
+            .await
+            .unwrap();
+
+        assert!(response
+            .assumed_role_user
+            .arn
+            .contains("WebIdentityRole/deploy"));
+        assert_eq!(
+            response.federated_subject.as_deref(),
+            Some("repo:org/app:ref:refs/heads/main")
+        );
+
+        let session = service
+            .store
+            .read()
+            .unwrap()
+            .get_session(&response.credentials.session_token)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            session.federated_user_name.as_deref(),
+            Some("repo:org/app:ref:refs/heads/main")
+        );
+        assert!(session.policy.is_some());
+        assert!(session
+            .principal_arn
+            .unwrap()
+            .ends_with("oidc-provider/token.actions.githubusercontent.com"));
+    }
+
+    #[tokio::test]
+    async fn test_assume_role_with_web_identity_unknown_provider() {
+        let service = setup_service();
+        let context = test_context();
+        let role_arn = setup_web_identity(&service).await;
+
+        let request = AssumeRoleWithWebIdentityRequest {
+            role_arn: role_arn.clone(),
+            role_session_name: "deploy".to_string(),
+            web_identity_token: web_identity_token(
+                r#"{"iss":"https://accounts.google.com","sub":"1234","aud":"sts.amazonaws.com"}"#,
+            ),
+            provider_id: None,
+            duration_seconds: None,
+            policy: None,
+        };
+        let result = service
+            .assume_role_with_web_identity(&context, request)
+            .await;
+        assert!(matches!(result, Err(AmiError::ResourceNotFound { .. })));
+
+        // A known provider still rejects audiences it does not list
+        let request = AssumeRoleWithWebIdentityRequest {
+            role_arn,
+            role_session_name: "deploy".to_string(),
+            web_identity_token: web_identity_token(r#"{"sub":"1234","aud":"other-client"}"#),
+            provider_id: Some("token.actions.githubusercontent.com".to_string()),
+            duration_seconds: None,
+            policy: None,
+        };
+        let result = service
+            .assume_role_with_web_identity(&context, request)
+            .await;
+        assert!(matches!(result, Err(AmiError::InvalidParameter { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_assume_role_with_web_identity_invalid_duration() {
+        let service = setup_service();
+        let context = test_context();
+        let role_arn = setup_web_identity(&service).await;
+
+        let request = AssumeRoleWithWebIdentityRequest {
+            role_arn,
+            role_session_name: "deploy".to_string(),
+            web_identity_token: web_identity_token(r#"{"sub":"1234","aud":"sts.amazonaws.com"}"#),
+            provider_id: Some("token.actions.githubusercontent.com".to_string()),
+            duration_seconds: Some(60),
+            policy: None,
+        };
+        let result = service
+            .assume_role_with_web_identity(&context, request)
+            .await;
+        assert!(matches!(result, Err(AmiError::InvalidParameter { .. })));
+    }
+
+    fn saml_assertion(name_id: &str, session_duration: u32) -> String {
+        use base64::Engine;
+        let xml = format!(
+            r#"<samlp:Response xmlns:samlp="urn:oasis:names:tc:SAML:2.0:protocol" xmlns:saml="urn:oasis:names:tc:SAML:2.0:assertion"><saml:Assertion><saml:Subject><saml:NameID>{}</saml:NameID></saml:Subject><saml:AttributeStatement><saml:Attribute Name="https://aws.amazon.com/SAML/Attributes/SessionDuration"><saml:AttributeValue>{}</saml:AttributeValue></saml:Attribute></saml:AttributeStatement></saml:Assertion></samlp:Response>"#,
+            name_id, session_duration
+        );
+        base64::engine::general_purpose::STANDARD.encode(xml)
+    }
+
+    #[tokio::test]
+    async fn test_assume_role_with_saml() {
+        let service = setup_service();
+        let context = test_context();
+
+        let role = build_role(
+            "SamlRole".to_string(),
+            r#"{"Version":"2012-10-17","Statement":[]}"#.to_string(),
+            Some("/".to_string()),
+            None,
+            None,
+            &context,
+        )
+        .unwrap();
+        let role_arn = role.wami_arn.to_string();
+        let provider = build_saml_provider(
+            "Okta".to_string(),
+            r#"<EntityDescriptor entityID="https://okta.example.com"/>"#.to_string(),
+            &context,
+        )
+        .unwrap();
+        let principal_arn = provider.arn.clone();
+        {
+            let mut store = service.store.write().unwrap();
+            store.create_role(role).await.unwrap();
+            store.create_saml_provider(provider).await.unwrap();
+        }
+
+        let request = AssumeRoleWithSamlRequest {
+            role_arn,
+            principal_arn: principal_arn.clone(),
+            saml_assertion: saml_assertion("alice@example.com", 1800),
+            duration_seconds: Some(3600),
+            policy: None,
+        };
+        let response = service
+            .assume_role_with_saml(&context, request)
+            .await
+            .unwrap();
+
+        assert!(response
+            .assumed_role_user
+            .arn
+            .ends_with("assumed-role/SamlRole/alice@example.com"));
+        assert_eq!(
+            response.federated_subject.as_deref(),
+            Some("alice@example.com")
+        );
+
+        // The assertion's SessionDuration caps the requested duration
+        let remaining = response.credentials.expiration - Utc::now();
+        assert!(remaining <= Duration::seconds(1800));
+
+        let session = service
+            .store
+            .read()
+            .unwrap()
+            .get_session(&response.credentials.session_token)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            session.principal_arn.as_deref(),
+            Some(principal_arn.as_str())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_assume_role_with_saml_rejects_foreign_issuer() {
+        use base64::Engine;
+        let service = setup_service();
+        let context = test_context();
+
+        let role = build_role(
+            "SamlRole".to_string(),
+            r#"{"Version":"2012-10-17","Statement":[]}"#.to_string(),
+            Some("/".to_string()),
+            None,
+            None,
+            &context,
+        )
+        .unwrap();
+        let role_arn = role.wami_arn.to_string();
+        let mut provider = build_saml_provider(
+            "Okta".to_string(),
+            r#"<EntityDescriptor entityID="https://okta.example.com"/>"#.to_string(),
+            &context,
+        )
+        .unwrap();
+        provider.entity_id = Some("https://okta.example.com".to_string());
+        let principal_arn = provider.arn.clone();
+        {
+            let mut store = service.store.write().unwrap();
+            store.create_role(role).await.unwrap();
+            store.create_saml_provider(provider).await.unwrap();
+        }
+
+        let xml = r#"<samlp:Response xmlns:samlp="urn:oasis:names:tc:SAML:2.0:protocol" xmlns:saml="urn:oasis:names:tc:SAML:2.0:assertion"><saml:Issuer>https://evil.example.com</saml:Issuer><saml:Assertion><saml:Subject><saml:NameID>alice@example.com</saml:NameID></saml:Subject></saml:Assertion></samlp:Response>"#;
+        let request = AssumeRoleWithSamlRequest {
+            role_arn,
+            principal_arn,
+            saml_assertion: base64::engine::general_purpose::STANDARD.encode(xml),
+            duration_seconds: None,
+            policy: None,
+        };
+        let result = service.assume_role_with_saml(&context, request).await;
+        assert!(matches!(result, Err(AmiError::AccessDenied { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_assume_role_with_saml_missing_provider() {
+        let service = setup_service();
+        let context = test_context();
+
+        let request = AssumeRoleWithSamlRequest {
+            role_arn: "arn:aws:iam::123456789012:role/SamlRole".to_string(),
+            principal_arn: "arn:aws:iam::123456789012:saml-provider/Unknown".to_string(),
+            saml_assertion: saml_assertion("alice@example.com", 3600),
+            duration_seconds: None,
+            policy: None,
+        };
+        let result = service.assume_role_with_saml(&context, request).await;
+        assert!(matches!(result, Err(AmiError::ResourceNotFound { .. })));
+    }
+
     #[tokio::test]
     async fn test_extract_role_name_from_arn() {
         let service = setup_service();
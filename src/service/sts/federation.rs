@@ -5,6 +5,7 @@
 use crate::arn::{Service, WamiArn};
 use crate::context::WamiContext;
 use crate::error::Result;
+use crate::provider::{AwsProvider, CloudProvider, ResourceType};
 use crate::store::traits::SessionStore;
 use crate::wami::sts::federation::{
     FederatedUser, GetFederationTokenRequest, GetFederationTokenResponse,
@@ -19,17 +20,34 @@ use std::sync::{Arc, RwLock};
 /// Provides high-level operations for federation token creation.
 pub struct FederationService<S> {
     store: Arc<RwLock<S>>,
+    provider: Arc<dyn CloudProvider>,
 }
 
 impl<S: SessionStore> FederationService<S> {
-    /// Create a new FederationService
+    /// Create a new FederationService with default AWS provider
     pub fn new(store: Arc<RwLock<S>>) -> Self {
-        Self { store }
+        Self {
+            store,
+            provider: Arc::new(AwsProvider::new()),
+        }
+    }
+
+    /// Returns a new service instance with different provider
+    pub fn with_provider(&self, provider: Arc<dyn CloudProvider>) -> Self {
+        Self {
+            store: self.store.clone(),
+            provider,
+        }
     }
 
     /// Get a federation token
     ///
-    /// Returns temporary credentials for a federated user.
+    /// Returns temporary credentials for a federated user. The session duration
+    /// is checked against the provider's resource limits, the federated user ARN
+    /// is generated by the provider, and the inline `policy` from the request is
+    /// stored on the session. Authorization grants the federated user only what
+    /// both this policy and the federating user's own policies allow, and
+    /// nothing when no policy was passed.
     pub async fn get_federation_token(
         &self,
         context: &WamiContext,
@@ -39,9 +57,11 @@ impl<S: SessionStore> FederationService<S> {
         // Validate request
         request.validate()?;
 
-        // Determine session duration (default: 12 hours, max: 36 hours)
+        // Determine session duration (default: 12 hours) and check provider limits
         let duration_seconds = request.duration_seconds.unwrap_or(43200);
-        let expiration = Utc::now() + Duration::seconds(duration_seconds as i64);
+        self.provider.validate_session_duration(duration_seconds)?;
+        let created_at = Utc::now();
+        let expiration = created_at + Duration::seconds(duration_seconds as i64);
 
         // Generate credentials
         let access_key_id = format!(
@@ -59,10 +79,11 @@ impl<S: SessionStore> FederationService<S> {
         );
         let session_token = format!("TOKEN{}", uuid::Uuid::new_v4().to_string().replace('-', ""));
 
-        let session_arn = format!(
-            "arn:aws:sts::{}:federated-user/{}",
+        let session_arn = self.provider.generate_resource_identifier(
+            ResourceType::StsFederatedUser,
             context.instance_id(),
-            request.name
+            "/",
+            &request.name,
         );
 
         // Build WAMI ARN for credentials using context
@@ -113,8 +134,12 @@ impl<S: SessionStore> FederationService<S> {
             wami_arn,
             providers: vec![],
             tenant_id: None,
-            created_at: Utc::now(),
+            created_at,
             last_used: None,
+            policy: request.policy,
+            role_chain_length: 0,
+            tags: vec![],
+            transitive_tag_keys: vec![],
         };
 
         self.store.write().unwrap().create_session(session).await?;
@@ -129,6 +154,7 @@ impl<S: SessionStore> FederationService<S> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::AmiError;
     use crate::store::memory::InMemoryWamiStore;
 
     fn setup_service() -> FederationService<InMemoryWamiStore> {
@@ -195,18 +221,30 @@ mod tests {
     async fn test_get_federation_token_invalid_name() {
         let service = setup_service();
 
-        let request = GetFederationTokenRequest {
-            name: "invalid name with spaces".to_string(),
-            duration_seconds: Some(3600),
-            policy: None,
-        };
-
-        let context = test_context();
-        let result = service
-            .get_federation_token(&context, request, "arn:aws:iam::123456789012:user/alice")
-            .await;
-
-        assert!(result.is_err());
+        let too_long = "n".repeat(33);
+        for name in [
+            "invalid name with spaces",
+            "n",
+            too_long.as_str(),
+            "bob/admin",
+        ] {
+            let request = GetFederationTokenRequest {
+                name: name.to_string(),
+                duration_seconds: Some(3600),
+                policy: None,
+            };
+
+            let context = test_context();
+            let result = service
+                .get_federation_token(&context, request, "arn:aws:iam::123456789012:user/alice")
+                .await;
+
+            assert!(
+                matches!(result, Err(AmiError::InvalidParameter { .. })),
+                "{}",
+                name
+            );
+        }
     }
 
     #[tokio::test]
@@ -268,4 +306,107 @@ mod tests {
 
         assert!(!response.federated_user.federated_user_id.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_get_federation_token_duration_bounds() {
+        let service = setup_service();
+        let context = test_context();
+        let limits = crate::provider::ResourceLimits::default();
+
+        for duration in [limits.session_duration_min, limits.session_duration_max] {
+            let request = GetFederationTokenRequest {
+                name: "bounded-user".to_string(),
+                duration_seconds: Some(duration),
+                policy: None,
+            };
+            let response = service
+                .get_federation_token(&context, request, "arn:aws:iam::123456789012:user/alice")
+                .await
+                .unwrap();
+            let remaining = (response.credentials.expiration - Utc::now()).num_seconds();
+            assert!(remaining <= duration as i64 && remaining >= duration as i64 - 5);
+        }
+
+        for duration in [
+            limits.session_duration_min - 1,
+            limits.session_duration_max + 1,
+        ] {
+            let request = GetFederationTokenRequest {
+                name: "bounded-user".to_string(),
+                duration_seconds: Some(duration),
+                policy: None,
+            };
+            let result = service
+                .get_federation_token(&context, request, "arn:aws:iam::123456789012:user/alice")
+                .await;
+            assert!(result.is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_federation_token_respects_custom_provider_limits() {
+        let limits = crate::provider::ResourceLimits {
+            session_duration_min: 900,
+            session_duration_max: 1800,
+            ..Default::default()
+        };
+        let service = setup_service().with_provider(Arc::new(AwsProvider::with_limits(limits)));
+        let context = test_context();
+
+        let request = GetFederationTokenRequest {
+            name: "short-lived".to_string(),
+            duration_seconds: Some(900),
+            policy: None,
+        };
+        assert!(service
+            .get_federation_token(&context, request, "arn:aws:iam::123456789012:user/alice")
+            .await
+            .is_ok());
+
+        let request = GetFederationTokenRequest {
+            name: "short-lived".to_string(),
+            duration_seconds: Some(3600),
+            policy: None,
+        };
+        assert!(service
+            .get_federation_token(&context, request, "arn:aws:iam::123456789012:user/alice")
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_get_federation_token_arn_format_and_stored_policy() {
+        let service = setup_service();
+        let context = test_context();
+        let policy = r#"{"Version":"2012-10-17","Statement":[{"Effect":"Allow","Action":"s3:GetObject","Resource":"*"}]}"#;
+
+        let request = GetFederationTokenRequest {
+            name: "Bob".to_string(),
+            duration_seconds: Some(3600),
+            policy: Some(policy.to_string()),
+        };
+
+        let response = service
+            .get_federation_token(&context, request, "arn:aws:iam::123456789012:user/alice")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            response.federated_user.arn,
+            "arn:aws:sts::123456789012:federated-user/Bob"
+        );
+        assert_eq!(response.credentials.arn, response.federated_user.arn);
+
+        let session = service
+            .store
+            .read()
+            .unwrap()
+            .get_session(&response.credentials.session_token)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(session.policy.as_deref(), Some(policy));
+        assert_eq!(session.federated_user_name.as_deref(), Some("Bob"));
+        assert_eq!(session.expiration, response.credentials.expiration);
+    }
 }
@@ -2,9 +2,10 @@
 //!
 //! Orchestrates caller identity operations.
 
+use crate::context::WamiContext;
 use crate::error::{AmiError, Result};
 use crate::provider::{AwsProvider, CloudProvider};
-use crate::store::traits::{IdentityStore, UserStore};
+use crate::store::traits::{IdentityStore, RoleStore, SessionStore, UserStore};
 use crate::wami::sts::CallerIdentity;
 use std::sync::{Arc, RwLock};
 
@@ -30,7 +31,7 @@ pub struct IdentityService<S> {
     account_id: String,
 }
 
-impl<S: IdentityStore + UserStore> IdentityService<S> {
+impl<S: IdentityStore + SessionStore + UserStore> IdentityService<S> {
     /// Create a new IdentityService with default AWS provider
     pub fn new(store: Arc<RwLock<S>>, account_id: String) -> Self {
         Self {
@@ -49,15 +50,31 @@ impl<S: IdentityStore + UserStore> IdentityService<S> {
         }
     }
 
-    /// Get the identity of the caller
+    /// Get the identity of the caller with the given ARN
     ///
-    /// In a real implementation, this would extract the caller from the request context.
-    /// For now, we accept the ARN as a parameter.
+    /// Use [`get_caller_identity_from_context`](Self::get_caller_identity_from_context)
+    /// to get the identity of an authenticated caller.
     pub async fn get_caller_identity(
         &self,
         _request: GetCallerIdentityRequest,
         caller_arn: &str,
     ) -> Result<GetCallerIdentityResponse> {
+        // A caller whose temporary sessions have all expired no longer exists
+        {
+            let store_guard = self.store.read().unwrap();
+            let sessions: Vec<_> = store_guard
+                .list_sessions(None)
+                .await?
+                .into_iter()
+                .filter(|session| session.arn == caller_arn)
+                .collect();
+            if !sessions.is_empty() && sessions.iter().all(|session| session.is_expired()) {
+                return Err(AmiError::ResourceNotFound {
+                    resource: format!("Session for ARN: {} (expired)", caller_arn),
+                });
+            }
+        } // Drop read lock
+
         // Try to get from identity store first
         {
             let store_guard = self.store.read().unwrap();
@@ -135,6 +152,78 @@ impl<S: IdentityStore + UserStore> IdentityService<S> {
     }
 }
 
+impl<S: IdentityStore + SessionStore + UserStore + RoleStore> IdentityService<S> {
+    /// Get the identity of the principal authenticated in `context`
+    ///
+    /// For a context authenticated with temporary credentials, this is the
+    /// STS session's ARN (an `assumed-role` ARN for role sessions) with a user
+    /// ID of `<role id>:<session name>`, or `<account>:<name>` for federated
+    /// users. Otherwise it is the user the context was authenticated as.
+    ///
+    /// Returns `ResourceNotFound` if the session has expired or the principal
+    /// no longer exists.
+    pub async fn get_caller_identity_from_context(
+        &self,
+        context: &WamiContext,
+    ) -> Result<GetCallerIdentityResponse> {
+        let store = self.store.read().unwrap();
+
+        let Some(session_info) = context.session_info() else {
+            let (users, _, _) = store.list_users(None, None).await?;
+            let user = users
+                .into_iter()
+                .find(|user| &user.wami_arn == context.caller_arn())
+                .ok_or_else(|| AmiError::ResourceNotFound {
+                    resource: format!("User: {}", context.caller_arn()),
+                })?;
+            return Ok(GetCallerIdentityResponse {
+                account: arn_account(&user.arn)
+                    .unwrap_or(context.instance_id())
+                    .to_string(),
+                user_id: user.user_id,
+                arn: user.arn,
+            });
+        };
+
+        let session = store
+            .get_session(&session_info.session_token)
+            .await?
+            .filter(|session| !session.is_expired())
+            .ok_or_else(|| AmiError::ResourceNotFound {
+                resource: format!("Session for ARN: {}", context.caller_arn()),
+            })?;
+        let account = arn_account(&session.arn)
+            .unwrap_or(context.instance_id())
+            .to_string();
+        let session_name = session.arn.rsplit('/').next().unwrap_or_default();
+
+        let user_id = match &session.assumed_role_arn {
+            Some(role_arn) => {
+                let (roles, _, _) = store.list_roles(None, None).await?;
+                let role = roles
+                    .into_iter()
+                    .find(|role| &role.arn == role_arn || role.wami_arn.to_string() == *role_arn)
+                    .ok_or_else(|| AmiError::ResourceNotFound {
+                        resource: format!("Role: {}", role_arn),
+                    })?;
+                format!("{}:{}", role.role_id, session_name)
+            }
+            None => format!("{}:{}", account, session_name),
+        };
+
+        Ok(GetCallerIdentityResponse {
+            user_id,
+            account,
+            arn: session.arn,
+        })
+    }
+}
+
+/// The account field of an `arn:partition:service:region:account:resource` ARN
+fn arn_account(arn: &str) -> Option<&str> {
+    arn.split(':').nth(4).filter(|account| !account.is_empty())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -217,6 +306,161 @@ mod tests {
         assert_eq!(identities.len(), 3);
     }
 
+    #[tokio::test]
+    async fn test_get_caller_identity_expired_session() {
+        use crate::wami::sts::session::SessionStatus;
+        use crate::wami::sts::StsSession;
+        use chrono::{Duration, Utc};
+
+        let service = setup_service();
+        let session_arn = "arn:aws:sts::123456789012:assumed-role/Deploy/ci";
+        let wami_arn: WamiArn = "arn:wami:sts:12345678:wami:123456789012:session/Deploy/ci"
+            .parse()
+            .unwrap();
+
+        {
+            let mut store = service.store.write().unwrap();
+            store
+                .create_identity(CallerIdentity {
+                    user_id: "AROAEXAMPLE:ci".to_string(),
+                    account: "123456789012".to_string(),
+                    arn: session_arn.to_string(),
+                    wami_arn: wami_arn.clone(),
+                    providers: vec![],
+                })
+                .await
+                .unwrap();
+            store
+                .create_session(StsSession {
+                    session_token: "token-ci".to_string(),
+                    access_key_id: "ASIAEXAMPLE".to_string(),
+                    secret_access_key: "secret".to_string(),
+                    expiration: Utc::now() + Duration::hours(1),
+                    status: SessionStatus::Active,
+                    assumed_role_arn: None,
+                    federated_user_name: None,
+                    principal_arn: None,
+                    arn: session_arn.to_string(),
+                    wami_arn,
+                    providers: vec![],
+                    tenant_id: None,
+                    created_at: Utc::now(),
+                    last_used: None,
+                    policy: None,
+                    role_chain_length: 0,
+                    tags: vec![],
+                    transitive_tag_keys: vec![],
+                })
+                .await
+                .unwrap();
+        }
+
+        let response = service
+            .get_caller_identity(GetCallerIdentityRequest {}, session_arn)
+            .await
+            .unwrap();
+        assert_eq!(response.user_id, "AROAEXAMPLE:ci");
+
+        // Once the session expires the caller is no longer recognized
+        {
+            let mut store = service.store.write().unwrap();
+            let mut session = store.get_session("token-ci").await.unwrap().unwrap();
+            session.expiration = Utc::now() - Duration::seconds(1);
+            store.create_session(session).await.unwrap();
+        }
+        let result = service
+            .get_caller_identity(GetCallerIdentityRequest {}, session_arn)
+            .await;
+        assert!(matches!(result, Err(AmiError::ResourceNotFound { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_get_caller_identity_from_context() {
+        use crate::service::auth::AuthenticationService;
+        use crate::service::sts::AssumeRoleService;
+        use crate::store::traits::AccessKeyStore;
+        use crate::wami::credentials::access_key::builder::build_access_key;
+        use crate::wami::identity::role::builder::build_role;
+        use crate::wami::sts::assume_role::AssumeRoleRequest;
+
+        let service = setup_service();
+        let context = test_context();
+
+        let user = build_user("alice".to_string(), None, &context).unwrap();
+        let key = build_access_key("alice".to_string(), &context).unwrap();
+        let secret = key.secret_access_key.clone().unwrap();
+        let role = build_role(
+            "Deploy".to_string(),
+            r#"{"Version":"2012-10-17","Statement":[{"Effect":"Allow","Principal":{"AWS":"123456789012"},"Action":"sts:AssumeRole"}]}"#.to_string(),
+            None,
+            None,
+            None,
+            &context,
+        )
+        .unwrap();
+        let role_arn = role.wami_arn.to_string();
+        let role_id = role.role_id.clone();
+        {
+            let mut store = service.store.write().unwrap();
+            store.create_user(user.clone()).await.unwrap();
+            store.create_access_key(key.clone()).await.unwrap();
+            store.create_role(role).await.unwrap();
+        }
+
+        let assumed = AssumeRoleService::new(service.store.clone())
+            .assume_role(
+                &context,
+                AssumeRoleRequest {
+                    role_arn: role_arn.clone(),
+                    role_session_name: "ci".to_string(),
+                    duration_seconds: None,
+                    external_id: None,
+                    policy: None,
+                    tags: vec![],
+                    transitive_tag_keys: vec![],
+                },
+                &user.arn,
+            )
+            .await
+            .unwrap();
+        let credentials = assumed.credentials;
+
+        let snapshot = service.store.read().unwrap().clone();
+        let auth = AuthenticationService::new(Arc::new(tokio::sync::RwLock::new(snapshot)));
+        let user_context = auth
+            .authenticate(&key.access_key_id, &secret)
+            .await
+            .unwrap();
+        let role_context = auth
+            .authenticate_session(
+                &credentials.access_key_id,
+                &credentials.secret_access_key,
+                &credentials.session_token,
+            )
+            .await
+            .unwrap();
+
+        let as_user = service
+            .get_caller_identity_from_context(&user_context)
+            .await
+            .unwrap();
+        assert_eq!(as_user.arn, "arn:aws:iam::123456789012:user/alice");
+        assert_eq!(as_user.user_id, user.user_id);
+        assert_eq!(as_user.account, "123456789012");
+
+        let as_role = service
+            .get_caller_identity_from_context(&role_context)
+            .await
+            .unwrap();
+        assert_eq!(
+            as_role.arn,
+            "arn:aws:sts::123456789012:assumed-role/Deploy/ci"
+        );
+        assert_eq!(as_role.user_id, format!("{}:ci", role_id));
+        assert_eq!(as_role.account, "123456789012");
+        assert_ne!(as_role.user_id, as_user.user_id);
+    }
+
     #[tokio::test]
     async fn test_extract_user_name_from_arn() {
         let service = setup_service();
@@ -5,7 +5,9 @@
 use crate::error::Result;
 use crate::provider::{AwsProvider, CloudProvider};
 use crate::store::traits::SessionStore;
+use crate::wami::sts::session::SessionStatus;
 use crate::wami::sts::StsSession;
+use chrono::{DateTime, Utc};
 use std::sync::{Arc, RwLock};
 
 /// Service for managing STS sessions
@@ -43,8 +45,16 @@ impl<S: SessionStore> SessionService<S> {
     }
 
     /// Get a session by session token
+    ///
+    /// Expired sessions are treated as not found, even before they are purged.
     pub async fn get_session(&self, session_token: &str) -> Result<Option<StsSession>> {
-        self.store.read().unwrap().get_session(session_token).await
+        let session = self
+            .store
+            .read()
+            .unwrap()
+            .get_session(session_token)
+            .await?;
+        Ok(session.filter(|s| s.status != SessionStatus::Expired && !s.is_expired()))
     }
 
     /// Delete a session
@@ -60,6 +70,13 @@ impl<S: SessionStore> SessionService<S> {
     pub async fn list_sessions(&self, user_id: Option<&str>) -> Result<Vec<StsSession>> {
         self.store.read().unwrap().list_sessions(user_id).await
     }
+
+    /// Remove all sessions that expired at or before `now`
+    ///
+    /// Returns the number of sessions cleaned up.
+    pub async fn cleanup_expired_sessions(&self, now: DateTime<Utc>) -> Result<usize> {
+        self.store.write().unwrap().purge_expired(now).await
+    }
 }
 
 #[cfg(test)]
@@ -92,6 +109,10 @@ mod tests {
             tenant_id: None,
             created_at: Utc::now(),
             last_used: None,
+            policy: None,
+            role_chain_length: 0,
+            tags: vec![],
+            transitive_tag_keys: vec![],
         }
     }
 
@@ -134,4 +155,45 @@ mod tests {
         let sessions = service.list_sessions(None).await.unwrap();
         assert_eq!(sessions.len(), 3);
     }
+
+    #[tokio::test]
+    async fn test_cleanup_expired_sessions() {
+        let service = setup_service();
+
+        let mut short_lived = create_test_session("short");
+        short_lived.expiration = Utc::now() + Duration::minutes(15);
+        service.create_session(short_lived).await.unwrap();
+        service
+            .create_session(create_test_session("long"))
+            .await
+            .unwrap();
+
+        // Nothing has expired yet
+        assert_eq!(
+            service.cleanup_expired_sessions(Utc::now()).await.unwrap(),
+            0
+        );
+        assert!(service.get_session("token-short").await.unwrap().is_some());
+
+        // Advance the clock past the short-lived session only
+        let later = Utc::now() + Duration::minutes(30);
+        assert_eq!(service.cleanup_expired_sessions(later).await.unwrap(), 1);
+
+        let remaining = service.list_sessions(None).await.unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].session_token, "token-long");
+    }
+
+    #[tokio::test]
+    async fn test_get_session_hides_expired_session() {
+        let service = setup_service();
+
+        let mut expired = create_test_session("stale");
+        expired.expiration = Utc::now() - Duration::seconds(1);
+        service.create_session(expired).await.unwrap();
+
+        // Not yet purged, but no longer visible
+        assert!(service.get_session("token-stale").await.unwrap().is_none());
+        assert_eq!(service.list_sessions(None).await.unwrap().len(), 1);
+    }
 }
@@ -5,6 +5,7 @@
 use crate::arn::{Service, WamiArn};
 use crate::context::WamiContext;
 use crate::error::Result;
+use crate::provider::{AwsProvider, CloudProvider};
 use crate::store::traits::SessionStore;
 use crate::wami::sts::session::SessionStatus;
 use crate::wami::sts::session_token::GetSessionTokenRequest;
@@ -23,12 +24,27 @@ pub struct GetSessionTokenResponse {
 /// Provides high-level operations for session token creation.
 pub struct SessionTokenService<S> {
     store: Arc<RwLock<S>>,
+    provider: Arc<dyn CloudProvider>,
 }
 
 impl<S: SessionStore> SessionTokenService<S> {
-    /// Create a new SessionTokenService
+    /// Create a new SessionTokenService with default AWS provider
     pub fn new(store: Arc<RwLock<S>>) -> Self {
-        Self { store }
+        Self {
+            store,
+            provider: Arc::new(AwsProvider::new()),
+        }
+    }
+
+    /// Returns a new service instance with different provider
+    ///
+    /// The provider supplies the session duration used when a request does
+    /// not specify one.
+    pub fn with_provider(&self, provider: Arc<dyn CloudProvider>) -> Self {
+        Self {
+            store: self.store.clone(),
+            provider,
+        }
     }
 
     /// Get a session token
@@ -43,8 +59,10 @@ impl<S: SessionStore> SessionTokenService<S> {
         // Validate request
         request.validate()?;
 
-        // Determine session duration (default: 1 hour, max: 36 hours)
-        let duration_seconds = request.duration_seconds.unwrap_or(3600);
+        // Determine session duration (default: provider's default, max: 36 hours)
+        let duration_seconds = request
+            .duration_seconds
+            .unwrap_or_else(|| self.provider.default_session_duration());
         let expiration = Utc::now() + Duration::seconds(duration_seconds as i64);
 
         // Generate credentials
@@ -104,6 +122,10 @@ impl<S: SessionStore> SessionTokenService<S> {
             tenant_id: None,
             created_at: Utc::now(),
             last_used: None,
+            policy: None,
+            role_chain_length: 0,
+            tags: vec![],
+            transitive_tag_keys: vec![],
         };
 
         self.store.write().unwrap().create_session(session).await?;
@@ -157,6 +179,29 @@ mod tests {
         assert!(response.credentials.expiration > Utc::now());
     }
 
+    #[tokio::test]
+    async fn test_get_session_token_uses_provider_default_duration() {
+        let provider = crate::provider::CustomProvider::builder()
+            .default_session_duration(7200)
+            .build();
+        let service = setup_service().with_provider(Arc::new(provider));
+        let context = test_context();
+
+        let request = GetSessionTokenRequest {
+            duration_seconds: None,
+            serial_number: None,
+            token_code: None,
+        };
+
+        let response = service
+            .get_session_token(&context, request, "arn:aws:iam::123456789012:user/alice")
+            .await
+            .unwrap();
+
+        let remaining = (response.credentials.expiration - Utc::now()).num_seconds();
+        assert!((7190..=7200).contains(&remaining));
+    }
+
     #[tokio::test]
     async fn test_get_session_token_with_mfa() {
         let service = setup_service();
@@ -2,12 +2,14 @@
 //!
 //! Orchestrates tenant operations.
 
-use crate::arn::{Service, WamiArn};
+use crate::arn::{Service, TenantPath, WamiArn};
 use crate::context::WamiContext;
-use crate::error::Result;
-use crate::store::traits::TenantStore;
+use crate::error::{AmiError, Result};
+use crate::store::traits::{
+    AccessKeyStore, GroupStore, PolicyStore, RoleStore, TenantStore, UserStore,
+};
 use crate::wami::tenant::operations::tenant_operations;
-use crate::wami::tenant::{Tenant, TenantId, TenantQuotas, TenantUsage};
+use crate::wami::tenant::{Tenant, TenantDeletionSummary, TenantId, TenantQuotas, TenantUsage};
 use std::sync::{Arc, RwLock};
 
 /// Service for managing tenants
@@ -169,6 +171,107 @@ impl<S: TenantStore> TenantService<S> {
         self.store.write().unwrap().delete_tenant(tenant_id).await
     }
 
+    /// Move a tenant (and its whole subtree) under a new parent
+    ///
+    /// Because tenant IDs encode the hierarchy, the moved tenant and every
+    /// descendant are re-keyed: the old parent prefix of each ID is replaced
+    /// with the new parent's ID, and `parent_id` and the tenant ARN are updated
+    /// to match. All checks run before anything is changed and the whole
+    /// re-keying happens under a single store write lock. Resources scoped
+    /// under the subtree keep their existing ARNs.
+    ///
+    /// # Errors
+    ///
+    /// * `ResourceNotFound` - the tenant or the new parent does not exist
+    /// * `InvalidParameter` - the new parent is the tenant itself or one of its descendants
+    /// * `ResourceExists` - the new parent already has a child with the same name
+    pub async fn move_tenant(
+        &self,
+        tenant_id: &TenantId,
+        new_parent_id: &TenantId,
+    ) -> Result<Tenant> {
+        let mut store = self.store.write().unwrap();
+
+        let tenant =
+            store
+                .get_tenant(tenant_id)
+                .await?
+                .ok_or_else(|| AmiError::ResourceNotFound {
+                    resource: format!("Tenant {} not found", tenant_id),
+                })?;
+        if store.get_tenant(new_parent_id).await?.is_none() {
+            return Err(AmiError::ResourceNotFound {
+                resource: format!("Tenant {} not found", new_parent_id),
+            });
+        }
+
+        if new_parent_id == tenant_id || new_parent_id.is_descendant_of(tenant_id) {
+            return Err(AmiError::InvalidParameter {
+                message: format!(
+                    "Cannot move tenant {} under itself or one of its descendants",
+                    tenant_id
+                ),
+            });
+        }
+        if tenant.parent_id.as_ref() == Some(new_parent_id) {
+            return Ok(tenant);
+        }
+
+        let siblings = store.list_child_tenants(new_parent_id).await?;
+        if siblings.iter().any(|t| t.name == tenant.name) {
+            return Err(AmiError::ResourceExists {
+                resource: format!(
+                    "Tenant with name '{}' already exists in parent {}",
+                    tenant.name,
+                    new_parent_id.as_str()
+                ),
+            });
+        }
+
+        // Keep the tenant's own segment and hang it below the new parent
+        let own_segment = tenant.id.segments().last().copied().unwrap_or_default();
+        let new_id = TenantId::from_string(&format!("{}/{}", new_parent_id, own_segment))?;
+        if store.get_tenant(&new_id).await?.is_some() {
+            return Err(AmiError::ResourceExists {
+                resource: format!("Tenant {}", new_id),
+            });
+        }
+
+        let mut subtree = vec![tenant];
+        for descendant_id in store.get_descendants(tenant_id).await? {
+            if let Some(descendant) = store.get_tenant(&descendant_id).await? {
+                subtree.push(descendant);
+            }
+        }
+
+        let mut moved = Vec::with_capacity(subtree.len());
+        for mut member in subtree {
+            let old_id = member.id.clone();
+            let rekeyed = old_id
+                .rebase(tenant_id, &new_id)
+                .expect("subtree members descend from the moved tenant");
+            member.parent_id = if old_id == *tenant_id {
+                Some(new_parent_id.clone())
+            } else {
+                rekeyed.parent()
+            };
+            if let Some(prefix) = member.arn.strip_suffix(&old_id.as_str()) {
+                member.arn = format!("{}{}", prefix, rekeyed.as_str());
+            }
+            member.id = rekeyed;
+            store.delete_tenant(&old_id).await?;
+            moved.push(member);
+        }
+
+        // The moved tenant comes first, so parents are re-created before their children
+        let moved_tenant = store.create_tenant(moved.remove(0)).await?;
+        for member in moved {
+            store.create_tenant(member).await?;
+        }
+
+        Ok(moved_tenant)
+    }
+
     /// List all tenants
     pub async fn list_tenants(&self) -> Result<Vec<Tenant>> {
         self.store.read().unwrap().list_tenants().await
@@ -206,6 +309,173 @@ impl<S: TenantStore> TenantService<S> {
     pub async fn get_tenant_usage(&self, tenant_id: &TenantId) -> Result<TenantUsage> {
         self.store.read().unwrap().get_tenant_usage(tenant_id).await
     }
+
+    /// Get resource usage for a tenant, optionally rolled up over its subtree
+    ///
+    /// With `include_descendants`, the counts of every descendant are added to
+    /// the tenant's own and `current_sub_tenants` holds the size of the whole
+    /// subtree (excluding the tenant itself). Otherwise this is the same as
+    /// [`get_tenant_usage`](Self::get_tenant_usage).
+    pub async fn get_aggregated_usage(
+        &self,
+        tenant_id: &TenantId,
+        include_descendants: bool,
+    ) -> Result<TenantUsage> {
+        let mut usage = self.get_tenant_usage(tenant_id).await?;
+        if !include_descendants {
+            return Ok(usage);
+        }
+
+        let descendants = self.get_descendants(tenant_id).await?;
+        for descendant in &descendants {
+            let child = self.get_tenant_usage(descendant).await?;
+            usage.current_users += child.current_users;
+            usage.current_roles += child.current_roles;
+            usage.current_policies += child.current_policies;
+            usage.current_groups += child.current_groups;
+        }
+        usage.current_sub_tenants = descendants.len();
+        usage.include_descendants = true;
+
+        Ok(usage)
+    }
+}
+
+impl<S: TenantStore + UserStore + GroupStore + RoleStore + PolicyStore + AccessKeyStore>
+    TenantService<S>
+{
+    /// Delete a tenant together with its descendants and every resource scoped under them
+    ///
+    /// Users, groups, roles, managed policies and access keys whose ARN tenant
+    /// path falls under the tenant are removed first, then the tenants are
+    /// removed bottom-up so no child is ever left without its parent. With
+    /// `dry_run` set, nothing is mutated and the summary lists what would be
+    /// deleted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ResourceNotFound` if the tenant does not exist.
+    pub async fn delete_tenant_recursive(
+        &self,
+        tenant_id: &TenantId,
+        dry_run: bool,
+    ) -> Result<TenantDeletionSummary> {
+        if self.get_tenant(tenant_id).await?.is_none() {
+            return Err(AmiError::ResourceNotFound {
+                resource: format!("Tenant {} not found", tenant_id),
+            });
+        }
+
+        // Deepest tenants first, the target itself last
+        let mut tenants = self.get_descendants(tenant_id).await?;
+        tenants.sort_by_key(|id| std::cmp::Reverse(id.depth()));
+        tenants.push(tenant_id.clone());
+
+        let scope = TenantPath::from_tenant_id(tenant_id);
+        let in_scope = |arn: &WamiArn| arn.belongs_to_tenant(&scope);
+
+        let store = self.store.read().unwrap();
+        let access_keys: Vec<_> = store
+            .list_all_access_keys()
+            .await?
+            .into_iter()
+            .filter(|key| in_scope(&key.wami_arn))
+            .collect();
+        let (users, _, _) = store.list_users(None, None).await?;
+        let users: Vec<_> = users
+            .into_iter()
+            .filter(|u| in_scope(&u.wami_arn))
+            .collect();
+        let (groups, _, _) = store.list_groups(None, None).await?;
+        let groups: Vec<_> = groups
+            .into_iter()
+            .filter(|g| in_scope(&g.wami_arn))
+            .collect();
+        let (roles, _, _) = store.list_roles(None, None).await?;
+        let roles: Vec<_> = roles
+            .into_iter()
+            .filter(|r| in_scope(&r.wami_arn))
+            .collect();
+        let (policies, _, _) = store.list_policies(None, None).await?;
+        let policies: Vec<_> = policies
+            .into_iter()
+            .filter(|p| in_scope(&p.wami_arn))
+            .collect();
+        drop(store);
+
+        let resources = access_keys
+            .iter()
+            .map(|k| k.wami_arn.to_string())
+            .chain(users.iter().map(|u| u.wami_arn.to_string()))
+            .chain(groups.iter().map(|g| g.wami_arn.to_string()))
+            .chain(roles.iter().map(|r| r.wami_arn.to_string()))
+            .chain(policies.iter().map(|p| p.wami_arn.to_string()))
+            .collect();
+
+        let summary = TenantDeletionSummary {
+            dry_run,
+            tenants,
+            resources,
+        };
+        if dry_run {
+            return Ok(summary);
+        }
+
+        let mut store = self.store.write().unwrap();
+        for key in &access_keys {
+            store.delete_access_key(&key.access_key_id).await?;
+        }
+        for user in &users {
+            for policy_arn in store.list_attached_user_policies(&user.user_name).await? {
+                store
+                    .detach_user_policy(&user.user_name, &policy_arn)
+                    .await?;
+            }
+            for policy_name in store.list_user_policies(&user.user_name).await? {
+                store
+                    .delete_user_policy(&user.user_name, &policy_name)
+                    .await?;
+            }
+            store.delete_user(&user.user_name).await?;
+        }
+        for group in &groups {
+            for policy_arn in store
+                .list_attached_group_policies(&group.group_name)
+                .await?
+            {
+                store
+                    .detach_group_policy(&group.group_name, &policy_arn)
+                    .await?;
+            }
+            for policy_name in store.list_group_policies(&group.group_name).await? {
+                store
+                    .delete_group_policy(&group.group_name, &policy_name)
+                    .await?;
+            }
+            store.delete_group(&group.group_name).await?;
+        }
+        for role in &roles {
+            for policy_arn in store.list_attached_role_policies(&role.role_name).await? {
+                store
+                    .detach_role_policy(&role.role_name, &policy_arn)
+                    .await?;
+            }
+            for policy_name in store.list_role_policies(&role.role_name).await? {
+                store
+                    .delete_role_policy(&role.role_name, &policy_name)
+                    .await?;
+            }
+            store.delete_role(&role.role_name).await?;
+        }
+        for policy in &policies {
+            store.delete_policy(&policy.arn).await?;
+        }
+        for id in &summary.tenants {
+            store.delete_tenant(id).await?;
+        }
+
+        Ok(summary)
+    }
 }
 
 #[cfg(test)]
@@ -561,4 +831,329 @@ mod tests {
         // IDs should be different (even though both are root tenants)
         assert_ne!(tenant1.id, tenant2.id);
     }
+
+    fn tenant_scoped_context(tenant_id: &TenantId) -> crate::context::WamiContext {
+        use crate::arn::TenantPath;
+        let tenant_path = TenantPath::from_tenant_id(tenant_id);
+        let arn: WamiArn = format!(
+            "arn:wami:iam:{}:wami:123456789012:user/admin",
+            tenant_path.as_string()
+        )
+        .parse()
+        .unwrap();
+        crate::context::WamiContext::builder()
+            .instance_id("123456789012")
+            .tenant_path(tenant_path)
+            .caller_arn(arn)
+            .is_root(false)
+            .build()
+            .unwrap()
+    }
+
+    async fn seed_tenant_resources(service: &TenantService<InMemoryWamiStore>, tenant: &Tenant) {
+        use crate::wami::credentials::access_key::builder::build_access_key;
+        use crate::wami::identity::group::builder::build_group;
+        use crate::wami::identity::role::builder::build_role;
+        use crate::wami::identity::user::builder::build_user;
+
+        let context = tenant_scoped_context(&tenant.id);
+        let user_name = format!("{}-user", tenant.name);
+        let group_name = format!("{}-group", tenant.name);
+
+        let mut store = service.store.write().unwrap();
+        store
+            .create_user(build_user(user_name.clone(), None, &context).unwrap())
+            .await
+            .unwrap();
+        store
+            .create_access_key(build_access_key(user_name.clone(), &context).unwrap())
+            .await
+            .unwrap();
+        store
+            .create_group(build_group(group_name.clone(), None, &context).unwrap())
+            .await
+            .unwrap();
+        store
+            .add_user_to_group(&group_name, &user_name)
+            .await
+            .unwrap();
+        store
+            .create_role(
+                build_role(
+                    format!("{}-role", tenant.name),
+                    "{}".to_string(),
+                    None,
+                    None,
+                    None,
+                    &context,
+                )
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_delete_tenant_recursive() {
+        let service = setup_service();
+        let context = test_context();
+
+        let root = service
+            .create_tenant(&context, "root".to_string(), None, None)
+            .await
+            .unwrap();
+        let child = service
+            .create_tenant(&context, "child".to_string(), None, Some(root.id.clone()))
+            .await
+            .unwrap();
+        let grandchild = service
+            .create_tenant(
+                &context,
+                "grandchild".to_string(),
+                None,
+                Some(child.id.clone()),
+            )
+            .await
+            .unwrap();
+        let other = service
+            .create_tenant(&context, "other".to_string(), None, None)
+            .await
+            .unwrap();
+
+        for tenant in [&root, &child, &grandchild, &other] {
+            seed_tenant_resources(&service, tenant).await;
+        }
+
+        let plan = service
+            .delete_tenant_recursive(&child.id, true)
+            .await
+            .unwrap();
+        assert!(plan.dry_run);
+        assert_eq!(plan.tenants, vec![grandchild.id.clone(), child.id.clone()]);
+        // user, access key, group and role for each of the two tenants
+        assert_eq!(plan.resources.len(), 8);
+        assert_eq!(service.list_tenants().await.unwrap().len(), 4);
+
+        let summary = service
+            .delete_tenant_recursive(&child.id, false)
+            .await
+            .unwrap();
+        assert!(!summary.dry_run);
+        assert_eq!(summary.total(), plan.total());
+
+        // No tenant or resource remains under the deleted subtree
+        let remaining: Vec<TenantId> = service
+            .list_tenants()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|t| t.id)
+            .collect();
+        assert_eq!(remaining.len(), 2);
+        assert!(remaining.contains(&root.id));
+        assert!(remaining.contains(&other.id));
+
+        let store = service.store.read().unwrap();
+        let scope = crate::arn::TenantPath::from_tenant_id(&child.id);
+        let (users, _, _) = store.list_users(None, None).await.unwrap();
+        assert_eq!(users.len(), 2);
+        assert!(users.iter().all(|u| !u.wami_arn.belongs_to_tenant(&scope)));
+        let access_keys = store.list_all_access_keys().await.unwrap();
+        assert_eq!(access_keys.len(), 2);
+        let (groups, _, _) = store.list_groups(None, None).await.unwrap();
+        assert_eq!(groups.len(), 2);
+        let (roles, _, _) = store.list_roles(None, None).await.unwrap();
+        assert_eq!(roles.len(), 2);
+        assert!(store
+            .list_groups_for_user("child-user")
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_delete_tenant_recursive_not_found() {
+        let service = setup_service();
+
+        let result = service
+            .delete_tenant_recursive(&TenantId::root(), false)
+            .await;
+        assert!(matches!(result, Err(AmiError::ResourceNotFound { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_get_aggregated_usage() {
+        let service = setup_service();
+        let context = test_context();
+
+        let root = service
+            .create_tenant(&context, "root".to_string(), None, None)
+            .await
+            .unwrap();
+        let child = service
+            .create_tenant(&context, "child".to_string(), None, Some(root.id.clone()))
+            .await
+            .unwrap();
+        let leaf_a = service
+            .create_tenant(&context, "leaf-a".to_string(), None, Some(child.id.clone()))
+            .await
+            .unwrap();
+        let leaf_b = service
+            .create_tenant(&context, "leaf-b".to_string(), None, Some(child.id.clone()))
+            .await
+            .unwrap();
+
+        for tenant in [&root, &child, &leaf_a, &leaf_b] {
+            seed_tenant_resources(&service, tenant).await;
+        }
+        // One extra user in a leaf so the counts are not uniform
+        {
+            use crate::wami::identity::user::builder::build_user;
+            let leaf_context = tenant_scoped_context(&leaf_a.id);
+            let user = build_user("extra".to_string(), None, &leaf_context).unwrap();
+            service
+                .store
+                .write()
+                .unwrap()
+                .create_user(user)
+                .await
+                .unwrap();
+        }
+
+        let own = service
+            .get_aggregated_usage(&child.id, false)
+            .await
+            .unwrap();
+        assert!(!own.include_descendants);
+        assert_eq!(own.current_users, 1);
+        assert_eq!(own.current_sub_tenants, 2);
+
+        let aggregate = service.get_aggregated_usage(&child.id, true).await.unwrap();
+        assert!(aggregate.include_descendants);
+        assert_eq!(aggregate.current_sub_tenants, 2);
+
+        let mut expected_users = own.current_users;
+        let mut expected_roles = own.current_roles;
+        let mut expected_groups = own.current_groups;
+        for leaf in [&leaf_a, &leaf_b] {
+            let usage = service.get_tenant_usage(&leaf.id).await.unwrap();
+            expected_users += usage.current_users;
+            expected_roles += usage.current_roles;
+            expected_groups += usage.current_groups;
+        }
+        assert_eq!(aggregate.current_users, expected_users);
+        assert_eq!(aggregate.current_users, 4);
+        assert_eq!(aggregate.current_roles, expected_roles);
+        assert_eq!(aggregate.current_groups, expected_groups);
+
+        let whole_tree = service.get_aggregated_usage(&root.id, true).await.unwrap();
+        assert_eq!(whole_tree.current_users, 5);
+        assert_eq!(whole_tree.current_roles, 4);
+        assert_eq!(whole_tree.current_sub_tenants, 3);
+    }
+
+    #[tokio::test]
+    async fn test_move_tenant() {
+        let service = setup_service();
+        let context = test_context();
+
+        let old_parent = service
+            .create_tenant(&context, "old-parent".to_string(), None, None)
+            .await
+            .unwrap();
+        let new_parent = service
+            .create_tenant(&context, "new-parent".to_string(), None, None)
+            .await
+            .unwrap();
+        let team = service
+            .create_tenant(
+                &context,
+                "team".to_string(),
+                None,
+                Some(old_parent.id.clone()),
+            )
+            .await
+            .unwrap();
+        let squad = service
+            .create_tenant(&context, "squad".to_string(), None, Some(team.id.clone()))
+            .await
+            .unwrap();
+
+        let moved = service.move_tenant(&team.id, &new_parent.id).await.unwrap();
+        assert_eq!(moved.name, "team");
+        assert_eq!(moved.parent_id, Some(new_parent.id.clone()));
+        assert!(moved.id.is_descendant_of(&new_parent.id));
+        assert!(moved.arn.ends_with(&moved.id.as_str()));
+
+        // Old keys are gone and the subtree hangs below the new parent
+        assert!(service.get_tenant(&team.id).await.unwrap().is_none());
+        assert!(service.get_tenant(&squad.id).await.unwrap().is_none());
+        assert!(service
+            .list_child_tenants(&old_parent.id)
+            .await
+            .unwrap()
+            .is_empty());
+
+        let descendants = service.get_descendants(&new_parent.id).await.unwrap();
+        assert_eq!(descendants.len(), 2);
+        let moved_squad = service
+            .find_tenant_by_name("squad", Some(&moved.id))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(moved_squad.parent_id, Some(moved.id.clone()));
+        assert!(moved_squad.arn.ends_with(&moved_squad.id.as_str()));
+        assert_eq!(service.list_tenants().await.unwrap().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_move_tenant_rejects_cycle() {
+        let service = setup_service();
+        let context = test_context();
+
+        let root = service
+            .create_tenant(&context, "root".to_string(), None, None)
+            .await
+            .unwrap();
+        let child = service
+            .create_tenant(&context, "child".to_string(), None, Some(root.id.clone()))
+            .await
+            .unwrap();
+
+        let result = service.move_tenant(&root.id, &child.id).await;
+        assert!(matches!(result, Err(AmiError::InvalidParameter { .. })));
+        let result = service.move_tenant(&root.id, &root.id).await;
+        assert!(matches!(result, Err(AmiError::InvalidParameter { .. })));
+
+        // Nothing changed
+        assert!(service.get_tenant(&root.id).await.unwrap().is_some());
+        assert!(service.get_tenant(&child.id).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_move_tenant_rejects_name_collision() {
+        let service = setup_service();
+        let context = test_context();
+
+        let source = service
+            .create_tenant(&context, "source".to_string(), None, None)
+            .await
+            .unwrap();
+        let target = service
+            .create_tenant(&context, "target".to_string(), None, None)
+            .await
+            .unwrap();
+        let eng = service
+            .create_tenant(&context, "eng".to_string(), None, Some(source.id.clone()))
+            .await
+            .unwrap();
+        service
+            .create_tenant(&context, "eng".to_string(), None, Some(target.id.clone()))
+            .await
+            .unwrap();
+
+        let result = service.move_tenant(&eng.id, &target.id).await;
+        assert!(matches!(result, Err(AmiError::ResourceExists { .. })));
+        assert!(service.get_tenant(&eng.id).await.unwrap().is_some());
+    }
 }
@@ -0,0 +1,363 @@
+//! User, Group and Role Stores for ConcurrentWamiStore
+
+use super::{
+    attach, attached, delete_inline, detach, get_inline, inline_names, put_inline,
+    ConcurrentWamiStore,
+};
+use crate::error::{AmiError, Result};
+use crate::store::memory::pagination::paginate;
+use crate::store::traits::{GroupStore, RoleStore, UserStore};
+use crate::types::{PaginationParams, Tag};
+use crate::wami::identity::{Group, Role, User};
+use async_trait::async_trait;
+
+#[async_trait]
+impl UserStore for ConcurrentWamiStore {
+    async fn create_user(&mut self, user: User) -> Result<User> {
+        self.maps.users.insert(user.user_name.clone(), user.clone());
+        Ok(user)
+    }
+
+    async fn get_user(&self, user_name: &str) -> Result<Option<User>> {
+        Ok(self.maps.users.get(user_name).map(|user| user.clone()))
+    }
+
+    async fn update_user(&mut self, user: User) -> Result<User> {
+        self.maps.users.insert(user.user_name.clone(), user.clone());
+        Ok(user)
+    }
+
+    async fn delete_user(&mut self, user_name: &str) -> Result<()> {
+        self.maps.users.remove(user_name);
+        self.maps.user_groups.remove(user_name);
+        Ok(())
+    }
+
+    async fn list_users(
+        &self,
+        path_prefix: Option<&str>,
+        pagination: Option<&PaginationParams>,
+    ) -> Result<(Vec<User>, bool, Option<String>)> {
+        let mut users: Vec<User> = self
+            .maps
+            .users
+            .iter()
+            .filter(|user| path_prefix.is_none_or(|prefix| user.path.starts_with(prefix)))
+            .map(|user| user.clone())
+            .collect();
+        users.sort_by(|a, b| a.user_name.cmp(&b.user_name));
+
+        Ok(paginate(users, pagination, |item| &item.user_name))
+    }
+
+    async fn tag_user(&mut self, user_name: &str, tags: Vec<Tag>) -> Result<()> {
+        if let Some(mut user) = self.maps.users.get_mut(user_name) {
+            user.tags.extend(tags);
+        }
+        Ok(())
+    }
+
+    async fn list_user_tags(&self, user_name: &str) -> Result<Vec<Tag>> {
+        Ok(self
+            .maps
+            .users
+            .get(user_name)
+            .map(|user| user.tags.clone())
+            .unwrap_or_default())
+    }
+
+    async fn untag_user(&mut self, user_name: &str, tag_keys: Vec<String>) -> Result<()> {
+        if let Some(mut user) = self.maps.users.get_mut(user_name) {
+            user.tags.retain(|tag| !tag_keys.contains(&tag.key));
+        }
+        Ok(())
+    }
+
+    async fn attach_user_policy(&mut self, user_name: &str, policy_arn: &str) -> Result<()> {
+        attach(&self.maps.user_attached_policies, user_name, policy_arn);
+        Ok(())
+    }
+
+    async fn detach_user_policy(&mut self, user_name: &str, policy_arn: &str) -> Result<()> {
+        detach(&self.maps.user_attached_policies, user_name, policy_arn);
+        Ok(())
+    }
+
+    async fn list_attached_user_policies(&self, user_name: &str) -> Result<Vec<String>> {
+        Ok(attached(&self.maps.user_attached_policies, user_name))
+    }
+
+    async fn put_user_policy(
+        &mut self,
+        user_name: &str,
+        policy_name: &str,
+        policy_document: String,
+    ) -> Result<()> {
+        put_inline(
+            &self.maps.user_inline_policies,
+            user_name,
+            policy_name,
+            policy_document,
+        );
+        Ok(())
+    }
+
+    async fn get_user_policy(&self, user_name: &str, policy_name: &str) -> Result<Option<String>> {
+        Ok(get_inline(
+            &self.maps.user_inline_policies,
+            user_name,
+            policy_name,
+        ))
+    }
+
+    async fn delete_user_policy(&mut self, user_name: &str, policy_name: &str) -> Result<()> {
+        delete_inline(&self.maps.user_inline_policies, user_name, policy_name);
+        Ok(())
+    }
+
+    async fn list_user_policies(&self, user_name: &str) -> Result<Vec<String>> {
+        Ok(inline_names(&self.maps.user_inline_policies, user_name))
+    }
+}
+
+#[async_trait]
+impl GroupStore for ConcurrentWamiStore {
+    async fn create_group(&mut self, group: Group) -> Result<Group> {
+        self.maps
+            .groups
+            .insert(group.group_name.clone(), group.clone());
+        Ok(group)
+    }
+
+    async fn get_group(&self, group_name: &str) -> Result<Option<Group>> {
+        Ok(self.maps.groups.get(group_name).map(|group| group.clone()))
+    }
+
+    async fn update_group(&mut self, group: Group) -> Result<Group> {
+        self.maps
+            .groups
+            .insert(group.group_name.clone(), group.clone());
+        Ok(group)
+    }
+
+    async fn delete_group(&mut self, group_name: &str) -> Result<()> {
+        self.maps.groups.remove(group_name);
+        for mut groups in self.maps.user_groups.iter_mut() {
+            groups.retain(|g| g != group_name);
+        }
+        Ok(())
+    }
+
+    async fn list_groups(
+        &self,
+        path_prefix: Option<&str>,
+        pagination: Option<&PaginationParams>,
+    ) -> Result<(Vec<Group>, bool, Option<String>)> {
+        let mut groups: Vec<Group> = self
+            .maps
+            .groups
+            .iter()
+            .filter(|group| path_prefix.is_none_or(|prefix| group.path.starts_with(prefix)))
+            .map(|group| group.clone())
+            .collect();
+        groups.sort_by(|a, b| a.group_name.cmp(&b.group_name));
+
+        Ok(paginate(groups, pagination, |item| &item.group_name))
+    }
+
+    async fn list_groups_for_user(&self, user_name: &str) -> Result<Vec<Group>> {
+        let group_names = self
+            .maps
+            .user_groups
+            .get(user_name)
+            .map(|groups| groups.clone())
+            .unwrap_or_default();
+        Ok(group_names
+            .iter()
+            .filter_map(|name| self.maps.groups.get(name).map(|group| group.clone()))
+            .collect())
+    }
+
+    async fn list_users_in_group(&self, group_name: &str) -> Result<Vec<User>> {
+        if !self.maps.groups.contains_key(group_name) {
+            return Err(AmiError::ResourceNotFound {
+                resource: format!("Group: {}", group_name),
+            });
+        }
+
+        let mut users: Vec<User> = self
+            .maps
+            .user_groups
+            .iter()
+            .filter(|entry| entry.value().iter().any(|g| g == group_name))
+            .filter_map(|entry| self.maps.users.get(entry.key()).map(|user| user.clone()))
+            .collect();
+        users.sort_by(|a, b| a.user_name.cmp(&b.user_name));
+        Ok(users)
+    }
+
+    async fn add_user_to_group(&mut self, group_name: &str, user_name: &str) -> Result<()> {
+        if !self.maps.groups.contains_key(group_name) {
+            return Err(AmiError::ResourceNotFound {
+                resource: format!("Group: {}", group_name),
+            });
+        }
+        if !self.maps.users.contains_key(user_name) {
+            return Err(AmiError::ResourceNotFound {
+                resource: format!("User: {}", user_name),
+            });
+        }
+
+        let mut groups = self
+            .maps
+            .user_groups
+            .entry(user_name.to_string())
+            .or_default();
+        if !groups.iter().any(|g| g == group_name) {
+            groups.push(group_name.to_string());
+        }
+        Ok(())
+    }
+
+    async fn remove_user_from_group(&mut self, group_name: &str, user_name: &str) -> Result<()> {
+        if let Some(mut groups) = self.maps.user_groups.get_mut(user_name) {
+            groups.retain(|g| g != group_name);
+        }
+        Ok(())
+    }
+
+    async fn attach_group_policy(&mut self, group_name: &str, policy_arn: &str) -> Result<()> {
+        attach(&self.maps.group_attached_policies, group_name, policy_arn);
+        Ok(())
+    }
+
+    async fn detach_group_policy(&mut self, group_name: &str, policy_arn: &str) -> Result<()> {
+        detach(&self.maps.group_attached_policies, group_name, policy_arn);
+        Ok(())
+    }
+
+    async fn list_attached_group_policies(&self, group_name: &str) -> Result<Vec<String>> {
+        Ok(attached(&self.maps.group_attached_policies, group_name))
+    }
+
+    async fn put_group_policy(
+        &mut self,
+        group_name: &str,
+        policy_name: &str,
+        policy_document: String,
+    ) -> Result<()> {
+        put_inline(
+            &self.maps.group_inline_policies,
+            group_name,
+            policy_name,
+            policy_document,
+        );
+        Ok(())
+    }
+
+    async fn get_group_policy(
+        &self,
+        group_name: &str,
+        policy_name: &str,
+    ) -> Result<Option<String>> {
+        Ok(get_inline(
+            &self.maps.group_inline_policies,
+            group_name,
+            policy_name,
+        ))
+    }
+
+    async fn delete_group_policy(&mut self, group_name: &str, policy_name: &str) -> Result<()> {
+        delete_inline(&self.maps.group_inline_policies, group_name, policy_name);
+        Ok(())
+    }
+
+    async fn list_group_policies(&self, group_name: &str) -> Result<Vec<String>> {
+        Ok(inline_names(&self.maps.group_inline_policies, group_name))
+    }
+}
+
+#[async_trait]
+impl RoleStore for ConcurrentWamiStore {
+    async fn create_role(&mut self, role: Role) -> Result<Role> {
+        self.maps.roles.insert(role.role_name.clone(), role.clone());
+        Ok(role)
+    }
+
+    async fn get_role(&self, role_name: &str) -> Result<Option<Role>> {
+        Ok(self.maps.roles.get(role_name).map(|role| role.clone()))
+    }
+
+    async fn update_role(&mut self, role: Role) -> Result<Role> {
+        self.maps.roles.insert(role.role_name.clone(), role.clone());
+        Ok(role)
+    }
+
+    async fn delete_role(&mut self, role_name: &str) -> Result<()> {
+        self.maps.roles.remove(role_name);
+        Ok(())
+    }
+
+    async fn list_roles(
+        &self,
+        path_prefix: Option<&str>,
+        pagination: Option<&PaginationParams>,
+    ) -> Result<(Vec<Role>, bool, Option<String>)> {
+        let mut roles: Vec<Role> = self
+            .maps
+            .roles
+            .iter()
+            .filter(|role| path_prefix.is_none_or(|prefix| role.path.starts_with(prefix)))
+            .map(|role| role.clone())
+            .collect();
+        roles.sort_by(|a, b| a.role_name.cmp(&b.role_name));
+
+        Ok(paginate(roles, pagination, |item| &item.role_name))
+    }
+
+    async fn attach_role_policy(&mut self, role_name: &str, policy_arn: &str) -> Result<()> {
+        attach(&self.maps.role_attached_policies, role_name, policy_arn);
+        Ok(())
+    }
+
+    async fn detach_role_policy(&mut self, role_name: &str, policy_arn: &str) -> Result<()> {
+        detach(&self.maps.role_attached_policies, role_name, policy_arn);
+        Ok(())
+    }
+
+    async fn list_attached_role_policies(&self, role_name: &str) -> Result<Vec<String>> {
+        Ok(attached(&self.maps.role_attached_policies, role_name))
+    }
+
+    async fn put_role_policy(
+        &mut self,
+        role_name: &str,
+        policy_name: &str,
+        policy_document: String,
+    ) -> Result<()> {
+        put_inline(
+            &self.maps.role_inline_policies,
+            role_name,
+            policy_name,
+            policy_document,
+        );
+        Ok(())
+    }
+
+    async fn get_role_policy(&self, role_name: &str, policy_name: &str) -> Result<Option<String>> {
+        Ok(get_inline(
+            &self.maps.role_inline_policies,
+            role_name,
+            policy_name,
+        ))
+    }
+
+    async fn delete_role_policy(&mut self, role_name: &str, policy_name: &str) -> Result<()> {
+        delete_inline(&self.maps.role_inline_policies, role_name, policy_name);
+        Ok(())
+    }
+
+    async fn list_role_policies(&self, role_name: &str) -> Result<Vec<String>> {
+        Ok(inline_names(&self.maps.role_inline_policies, role_name))
+    }
+}
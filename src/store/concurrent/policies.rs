@@ -0,0 +1,64 @@
+//! Policy Store for ConcurrentWamiStore
+
+use super::ConcurrentWamiStore;
+use crate::error::Result;
+use crate::store::memory::pagination::paginate;
+use crate::store::traits::PolicyStore;
+use crate::types::PaginationParams;
+use crate::wami::policies::Policy;
+use async_trait::async_trait;
+
+#[async_trait]
+impl PolicyStore for ConcurrentWamiStore {
+    async fn create_policy(&mut self, policy: Policy) -> Result<Policy> {
+        self.maps
+            .policies
+            .insert(policy.arn.clone(), policy.clone());
+        Ok(policy)
+    }
+
+    async fn get_policy(&self, policy_arn: &str) -> Result<Option<Policy>> {
+        Ok(self
+            .maps
+            .policies
+            .get(policy_arn)
+            .map(|policy| policy.clone()))
+    }
+
+    async fn update_policy(&mut self, policy: Policy) -> Result<Policy> {
+        self.maps
+            .policies
+            .insert(policy.arn.clone(), policy.clone());
+        Ok(policy)
+    }
+
+    async fn delete_policy(&mut self, policy_arn: &str) -> Result<()> {
+        self.maps.policies.remove(policy_arn);
+        Ok(())
+    }
+
+    async fn list_policies(
+        &self,
+        path_prefix: Option<&str>,
+        pagination: Option<&PaginationParams>,
+    ) -> Result<(Vec<Policy>, bool, Option<String>)> {
+        let mut policies: Vec<Policy> = self
+            .maps
+            .policies
+            .iter()
+            .filter(|policy| path_prefix.is_none_or(|prefix| policy.path.starts_with(prefix)))
+            .map(|policy| policy.clone())
+            .collect();
+        policies.sort_by(|a, b| a.arn.cmp(&b.arn));
+
+        // Policies default to pages of 100, capped at 1000
+        let pagination = pagination.map(|params| PaginationParams {
+            max_items: Some(params.max_items.unwrap_or(100).min(1000)),
+            marker: params.marker.clone(),
+        });
+
+        Ok(paginate(policies, pagination.as_ref(), |policy| {
+            &policy.arn
+        }))
+    }
+}
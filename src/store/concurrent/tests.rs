@@ -0,0 +1,76 @@
+//! Tests for ConcurrentWamiStore
+
+use super::ConcurrentWamiStore;
+use crate::arn::{TenantPath, WamiArn};
+use crate::context::WamiContext;
+use crate::store::traits::{GroupStore, UserStore};
+use crate::wami::identity::group::builder::build_group;
+use crate::wami::identity::user::builder::build_user;
+
+fn test_context() -> WamiContext {
+    let arn: WamiArn = "arn:wami:.*:12345678:wami:123456789012:user/test"
+        .parse()
+        .unwrap();
+    WamiContext::builder()
+        .instance_id("123456789012")
+        .tenant_path(TenantPath::single(12345678))
+        .caller_arn(arn)
+        .is_root(false)
+        .build()
+        .unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_concurrent_user_creation_loses_no_updates() {
+    let store = ConcurrentWamiStore::new();
+
+    let tasks: Vec<_> = (0..100)
+        .map(|i| {
+            let mut store = store.clone();
+            tokio::spawn(async move {
+                let user = build_user(format!("user-{:03}", i), None, &test_context()).unwrap();
+                store.create_user(user).await.unwrap();
+            })
+        })
+        .collect();
+    for task in tasks {
+        task.await.unwrap();
+    }
+
+    let (users, truncated, _) = store.list_users(None, None).await.unwrap();
+    assert!(!truncated);
+    assert_eq!(users.len(), 100);
+    for i in 0..100 {
+        let name = format!("user-{:03}", i);
+        assert!(store.get_user(&name).await.unwrap().is_some(), "{}", name);
+    }
+}
+
+#[tokio::test]
+async fn test_group_membership() {
+    let mut store = ConcurrentWamiStore::new();
+    let context = test_context();
+
+    store
+        .create_user(build_user("alice".to_string(), None, &context).unwrap())
+        .await
+        .unwrap();
+    store
+        .create_group(build_group("admins".to_string(), None, &context).unwrap())
+        .await
+        .unwrap();
+    assert!(store.add_user_to_group("admins", "bob").await.is_err());
+    store.add_user_to_group("admins", "alice").await.unwrap();
+
+    let members = store.list_users_in_group("admins").await.unwrap();
+    assert_eq!(members.len(), 1);
+    assert_eq!(members[0].user_name, "alice");
+
+    // Deleting the group removes it from its members' groups
+    store.delete_group("admins").await.unwrap();
+    assert!(store
+        .list_groups_for_user("alice")
+        .await
+        .unwrap()
+        .is_empty());
+}
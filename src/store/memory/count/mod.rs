@@ -0,0 +1,133 @@
+//! Resource Count Store Implementation for InMemoryWamiStore
+
+use crate::arn::{TenantPath, WamiArn};
+use crate::error::{AmiError, Result};
+use crate::provider::ResourceType;
+use crate::store::memory::InMemoryWamiStore;
+use crate::store::traits::ResourceCountStore;
+use crate::wami::tenant::TenantId;
+use async_trait::async_trait;
+
+#[async_trait]
+impl ResourceCountStore for InMemoryWamiStore {
+    async fn count_resources(
+        &self,
+        resource_type: ResourceType,
+        tenant: Option<&TenantId>,
+    ) -> Result<usize> {
+        let tenant_path = tenant.map(TenantPath::from_tenant_id);
+        let owned = |arn: &WamiArn| {
+            tenant_path
+                .as_ref()
+                .is_none_or(|path| &arn.tenant_path == path)
+        };
+
+        let count = match resource_type {
+            ResourceType::User => self.users.values().filter(|u| owned(&u.wami_arn)).count(),
+            ResourceType::Group => self.groups.values().filter(|g| owned(&g.wami_arn)).count(),
+            ResourceType::Role => self.roles.values().filter(|r| owned(&r.wami_arn)).count(),
+            ResourceType::Policy => self
+                .policies
+                .values()
+                .filter(|p| owned(&p.wami_arn))
+                .count(),
+            other => {
+                return Err(AmiError::InvalidParameter {
+                    message: format!("Counting {:?} resources is not supported", other),
+                })
+            }
+        };
+
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::WamiContext;
+    use crate::store::traits::{GroupStore, UserStore};
+    use crate::wami::identity::group::builder::build_group;
+    use crate::wami::identity::user::builder::build_user;
+
+    fn tenant_context(tenant_id: u64) -> WamiContext {
+        let arn: WamiArn = format!("arn:wami:iam:{}:wami:123456789012:user/admin", tenant_id)
+            .parse()
+            .unwrap();
+        WamiContext::builder()
+            .instance_id("123456789012")
+            .tenant_path(TenantPath::single(tenant_id))
+            .caller_arn(arn)
+            .is_root(false)
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_count_resources_per_tenant_and_globally() {
+        let mut store = InMemoryWamiStore::default();
+        let tenant_a = tenant_context(11111111);
+        let tenant_b = tenant_context(22222222);
+
+        for name in ["alice", "bob", "carol"] {
+            let user = build_user(name.to_string(), None, &tenant_a).unwrap();
+            store.create_user(user).await.unwrap();
+        }
+        let user = build_user("dave".to_string(), None, &tenant_b).unwrap();
+        store.create_user(user).await.unwrap();
+        let group = build_group("admins".to_string(), None, &tenant_b).unwrap();
+        store.create_group(group).await.unwrap();
+
+        let a = TenantId::from_string("11111111").unwrap();
+        let b = TenantId::from_string("22222222").unwrap();
+        let empty = TenantId::from_string("33333333").unwrap();
+
+        assert_eq!(
+            store
+                .count_resources(ResourceType::User, None)
+                .await
+                .unwrap(),
+            4
+        );
+        assert_eq!(
+            store
+                .count_resources(ResourceType::User, Some(&a))
+                .await
+                .unwrap(),
+            3
+        );
+        assert_eq!(
+            store
+                .count_resources(ResourceType::User, Some(&b))
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            store
+                .count_resources(ResourceType::Group, Some(&a))
+                .await
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            store
+                .count_resources(ResourceType::Group, Some(&b))
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            store
+                .count_resources(ResourceType::Role, Some(&empty))
+                .await
+                .unwrap(),
+            0
+        );
+
+        assert!(store
+            .count_resources(ResourceType::MfaDevice, None)
+            .await
+            .is_err());
+    }
+}
@@ -1,11 +1,13 @@
 //! Access Key Store Implementation for InMemoryWamiStore
 
-use crate::error::Result;
+use crate::error::{AmiError, Result};
+use crate::store::memory::pagination::paginate;
 use crate::store::memory::InMemoryWamiStore;
 use crate::store::traits::AccessKeyStore;
 use crate::types::PaginationParams;
-use crate::wami::credentials::AccessKey;
+use crate::wami::credentials::access_key::{AccessKey, AccessKeyLastUsed};
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 
 #[async_trait]
 impl AccessKeyStore for InMemoryWamiStore {
@@ -44,19 +46,47 @@ impl AccessKeyStore for InMemoryWamiStore {
 
         access_keys.sort_by(|a, b| a.access_key_id.cmp(&b.access_key_id));
 
-        let mut is_truncated = false;
-        let mut marker = None;
+        Ok(paginate(access_keys, pagination, |item| {
+            &item.access_key_id
+        }))
+    }
+
+    async fn list_all_access_keys(&self) -> Result<Vec<AccessKey>> {
+        let mut access_keys: Vec<AccessKey> = self.access_keys.values().cloned().collect();
+        access_keys.sort_by(|a, b| a.access_key_id.cmp(&b.access_key_id));
+        Ok(access_keys)
+    }
+
+    async fn update_access_key_last_used(
+        &mut self,
+        access_key_id: &str,
+        service_name: &str,
+        region: Option<&str>,
+        timestamp: DateTime<Utc>,
+    ) -> Result<()> {
+        let access_key =
+            self.access_keys
+                .get_mut(access_key_id)
+                .ok_or_else(|| AmiError::ResourceNotFound {
+                    resource: format!("AccessKey {}", access_key_id),
+                })?;
+
+        access_key.last_used = Some(AccessKeyLastUsed {
+            last_used_date: Some(timestamp),
+            region: region.map(str::to_string),
+            service_name: Some(service_name.to_string()),
+        });
+        Ok(())
+    }
 
-        if let Some(pagination) = pagination {
-            if let Some(max_items) = pagination.max_items {
-                if access_keys.len() > max_items as usize {
-                    access_keys.truncate(max_items as usize);
-                    is_truncated = true;
-                    marker = Some(access_keys.last().unwrap().access_key_id.clone());
-                }
-            }
-        }
+    async fn get_access_key_last_used(&self, access_key_id: &str) -> Result<AccessKeyLastUsed> {
+        let access_key =
+            self.access_keys
+                .get(access_key_id)
+                .ok_or_else(|| AmiError::ResourceNotFound {
+                    resource: format!("AccessKey {}", access_key_id),
+                })?;
 
-        Ok((access_keys, is_truncated, marker))
+        Ok(access_key.last_used.clone().unwrap_or_default())
     }
 }
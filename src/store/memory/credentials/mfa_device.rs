@@ -18,6 +18,12 @@ impl MfaDeviceStore for InMemoryWamiStore {
         Ok(self.mfa_devices.get(serial_number).cloned())
     }
 
+    async fn update_mfa_device(&mut self, device: MfaDevice) -> Result<MfaDevice> {
+        self.mfa_devices
+            .insert(device.serial_number.clone(), device.clone());
+        Ok(device)
+    }
+
     async fn delete_mfa_device(&mut self, serial_number: &str) -> Result<()> {
         self.mfa_devices.remove(serial_number);
         Ok(())
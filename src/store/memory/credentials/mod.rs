@@ -5,6 +5,7 @@
 pub mod access_key;
 pub mod login_profile;
 pub mod mfa_device;
+pub mod password_policy;
 pub mod server_certificate;
 pub mod service_credential;
 pub mod signing_certificate;
@@ -0,0 +1,36 @@
+//! Password Policy Store Implementation for InMemoryWamiStore
+
+use crate::arn::TenantPath;
+use crate::error::Result;
+use crate::store::memory::InMemoryWamiStore;
+use crate::store::traits::PasswordPolicyStore;
+use crate::wami::credentials::PasswordPolicy;
+use async_trait::async_trait;
+
+#[async_trait]
+impl PasswordPolicyStore for InMemoryWamiStore {
+    async fn get_password_policy(
+        &self,
+        tenant_path: &TenantPath,
+    ) -> Result<Option<PasswordPolicy>> {
+        Ok(self
+            .password_policies
+            .get(&tenant_path.as_string())
+            .cloned())
+    }
+
+    async fn put_password_policy(
+        &mut self,
+        tenant_path: &TenantPath,
+        policy: PasswordPolicy,
+    ) -> Result<PasswordPolicy> {
+        self.password_policies
+            .insert(tenant_path.as_string(), policy.clone());
+        Ok(policy)
+    }
+
+    async fn delete_password_policy(&mut self, tenant_path: &TenantPath) -> Result<()> {
+        self.password_policies.remove(&tenant_path.as_string());
+        Ok(())
+    }
+}
@@ -1,16 +1,23 @@
 //! Group Store Implementation for InMemoryWamiStore
 
-use crate::error::Result;
+use crate::error::{AmiError, Result};
+use crate::provider::ResourceType;
+use crate::store::memory::pagination::paginate;
 use crate::store::memory::InMemoryWamiStore;
 use crate::store::traits::GroupStore;
 use crate::types::PaginationParams;
-use crate::wami::identity::Group;
+use crate::wami::identity::{Group, User};
 use async_trait::async_trait;
 
 #[async_trait]
 impl GroupStore for InMemoryWamiStore {
     async fn create_group(&mut self, group: Group) -> Result<Group> {
-        self.groups.insert(group.group_name.clone(), group.clone());
+        let previous = self.groups.insert(group.group_name.clone(), group.clone());
+        self.usage.replace(
+            ResourceType::Group,
+            previous.as_ref().map(|previous| &previous.wami_arn),
+            Some(&group.wami_arn),
+        );
         Ok(group)
     }
 
@@ -19,12 +26,20 @@ impl GroupStore for InMemoryWamiStore {
     }
 
     async fn update_group(&mut self, group: Group) -> Result<Group> {
-        self.groups.insert(group.group_name.clone(), group.clone());
+        let previous = self.groups.insert(group.group_name.clone(), group.clone());
+        self.usage.replace(
+            ResourceType::Group,
+            previous.as_ref().map(|previous| &previous.wami_arn),
+            Some(&group.wami_arn),
+        );
         Ok(group)
     }
 
     async fn delete_group(&mut self, group_name: &str) -> Result<()> {
-        self.groups.remove(group_name);
+        if let Some(removed) = self.groups.remove(group_name) {
+            self.usage
+                .replace(ResourceType::Group, Some(&removed.wami_arn), None);
+        }
         // Remove from all user-group mappings
         for groups in self.user_groups.values_mut() {
             groups.retain(|g| g != group_name);
@@ -45,20 +60,7 @@ impl GroupStore for InMemoryWamiStore {
 
         groups.sort_by(|a, b| a.group_name.cmp(&b.group_name));
 
-        let mut is_truncated = false;
-        let mut marker = None;
-
-        if let Some(pagination) = pagination {
-            if let Some(max_items) = pagination.max_items {
-                if groups.len() > max_items as usize {
-                    groups.truncate(max_items as usize);
-                    is_truncated = true;
-                    marker = Some(groups.last().unwrap().group_name.clone());
-                }
-            }
-        }
-
-        Ok((groups, is_truncated, marker))
+        Ok(paginate(groups, pagination, |item| &item.group_name))
     }
 
     async fn list_groups_for_user(&self, user_name: &str) -> Result<Vec<Group>> {
@@ -70,11 +72,39 @@ impl GroupStore for InMemoryWamiStore {
         Ok(groups)
     }
 
+    async fn list_users_in_group(&self, group_name: &str) -> Result<Vec<User>> {
+        if !self.groups.contains_key(group_name) {
+            return Err(AmiError::ResourceNotFound {
+                resource: format!("Group: {}", group_name),
+            });
+        }
+
+        let mut users: Vec<User> = self
+            .user_groups
+            .iter()
+            .filter(|(_, groups)| groups.iter().any(|g| g == group_name))
+            .filter_map(|(user_name, _)| self.users.get(user_name).cloned())
+            .collect();
+        users.sort_by(|a, b| a.user_name.cmp(&b.user_name));
+        Ok(users)
+    }
+
     async fn add_user_to_group(&mut self, group_name: &str, user_name: &str) -> Result<()> {
-        self.user_groups
-            .entry(user_name.to_string())
-            .or_default()
-            .push(group_name.to_string());
+        if !self.groups.contains_key(group_name) {
+            return Err(AmiError::ResourceNotFound {
+                resource: format!("Group: {}", group_name),
+            });
+        }
+        if !self.users.contains_key(user_name) {
+            return Err(AmiError::ResourceNotFound {
+                resource: format!("User: {}", user_name),
+            });
+        }
+
+        let groups = self.user_groups.entry(user_name.to_string()).or_default();
+        if !groups.iter().any(|g| g == group_name) {
+            groups.push(group_name.to_string());
+        }
         Ok(())
     }
 
@@ -1,6 +1,8 @@
 //! Role Store Implementation for InMemoryWamiStore
 
 use crate::error::Result;
+use crate::provider::ResourceType;
+use crate::store::memory::pagination::paginate;
 use crate::store::memory::InMemoryWamiStore;
 use crate::store::traits::RoleStore;
 use crate::types::PaginationParams;
@@ -10,7 +12,12 @@ use async_trait::async_trait;
 #[async_trait]
 impl RoleStore for InMemoryWamiStore {
     async fn create_role(&mut self, role: Role) -> Result<Role> {
-        self.roles.insert(role.role_name.clone(), role.clone());
+        let previous = self.roles.insert(role.role_name.clone(), role.clone());
+        self.usage.replace(
+            ResourceType::Role,
+            previous.as_ref().map(|previous| &previous.wami_arn),
+            Some(&role.wami_arn),
+        );
         Ok(role)
     }
 
@@ -19,12 +26,20 @@ impl RoleStore for InMemoryWamiStore {
     }
 
     async fn update_role(&mut self, role: Role) -> Result<Role> {
-        self.roles.insert(role.role_name.clone(), role.clone());
+        let previous = self.roles.insert(role.role_name.clone(), role.clone());
+        self.usage.replace(
+            ResourceType::Role,
+            previous.as_ref().map(|previous| &previous.wami_arn),
+            Some(&role.wami_arn),
+        );
         Ok(role)
     }
 
     async fn delete_role(&mut self, role_name: &str) -> Result<()> {
-        self.roles.remove(role_name);
+        if let Some(removed) = self.roles.remove(role_name) {
+            self.usage
+                .replace(ResourceType::Role, Some(&removed.wami_arn), None);
+        }
         Ok(())
     }
 
@@ -41,20 +56,7 @@ impl RoleStore for InMemoryWamiStore {
 
         roles.sort_by(|a, b| a.role_name.cmp(&b.role_name));
 
-        let mut is_truncated = false;
-        let mut marker = None;
-
-        if let Some(pagination) = pagination {
-            if let Some(max_items) = pagination.max_items {
-                if roles.len() > max_items as usize {
-                    roles.truncate(max_items as usize);
-                    is_truncated = true;
-                    marker = Some(roles.last().unwrap().role_name.clone());
-                }
-            }
-        }
-
-        Ok((roles, is_truncated, marker))
+        Ok(paginate(roles, pagination, |item| &item.role_name))
     }
 
     // Managed policy attachment methods
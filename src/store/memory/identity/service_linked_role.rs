@@ -1,6 +1,6 @@
 //! Service-Linked Role Store Implementation for InMemoryWamiStore
 
-use crate::error::Result;
+use crate::error::{AmiError, Result};
 use crate::store::memory::InMemoryWamiStore;
 use crate::store::traits::ServiceLinkedRoleStore;
 use crate::wami::identity::service_linked_role::DeletionTaskInfo;
@@ -26,4 +26,32 @@ impl ServiceLinkedRoleStore for InMemoryWamiStore {
             .get(deletion_task_id)
             .cloned())
     }
+
+    async fn update_service_linked_role_deletion_task(
+        &mut self,
+        task: DeletionTaskInfo,
+    ) -> Result<()> {
+        match self
+            .service_linked_role_deletion_tasks
+            .get_mut(&task.deletion_task_id)
+        {
+            Some(existing) => {
+                *existing = task;
+                Ok(())
+            }
+            None => Err(AmiError::ResourceNotFound {
+                resource: format!("DeletionTask: {}", task.deletion_task_id),
+            }),
+        }
+    }
+
+    async fn list_service_linked_role_deletion_tasks(&self) -> Result<Vec<DeletionTaskInfo>> {
+        let mut tasks: Vec<_> = self
+            .service_linked_role_deletion_tasks
+            .values()
+            .cloned()
+            .collect();
+        tasks.sort_by_key(|task| task.create_date);
+        Ok(tasks)
+    }
 }
@@ -11,6 +11,7 @@ use crate::wami::identity::group::builder as group_builder;
 use crate::wami::identity::role::builder as role_builder;
 use crate::wami::identity::service_linked_role::builder as slr_builder;
 use crate::wami::identity::user::builder as user_builder;
+use futures::StreamExt;
 
 fn test_context() -> WamiContext {
     let arn: WamiArn = "arn:wami:.*:12345678:wami:123456789012:user/test"
@@ -55,6 +56,30 @@ async fn test_user_get_nonexistent() {
     assert!(result.is_none());
 }
 
+#[tokio::test]
+async fn test_get_users_preserves_positions() {
+    let mut store = InMemoryWamiStore::new();
+    let context = test_context();
+
+    for name in ["alice", "bob"] {
+        let user = user_builder::build_user(name.to_string(), None, &context).unwrap();
+        store.create_user(user).await.unwrap();
+    }
+
+    let names: Vec<String> = ["bob", "ghost", "alice", "bob"]
+        .iter()
+        .map(|name| name.to_string())
+        .collect();
+    let users = store.get_users(&names).await.unwrap();
+
+    let found: Vec<Option<&str>> = users
+        .iter()
+        .map(|user| user.as_ref().map(|u| u.user_name.as_str()))
+        .collect();
+    assert_eq!(found, [Some("bob"), None, Some("alice"), Some("bob")]);
+    assert!(store.get_users(&[]).await.unwrap().is_empty());
+}
+
 #[tokio::test]
 async fn test_user_update() {
     let mut store = InMemoryWamiStore::new();
@@ -176,6 +201,100 @@ async fn test_user_list_with_pagination() {
     assert!(marker.is_some());
 }
 
+#[tokio::test]
+async fn test_user_list_pages_through_all_users() {
+    let mut store = InMemoryWamiStore::new();
+    store.seed(250, 0, 0).unwrap();
+
+    let mut seen = Vec::new();
+    let mut page_sizes = Vec::new();
+    let mut marker = None;
+    loop {
+        let pagination = PaginationParams {
+            max_items: Some(100),
+            marker: marker.clone(),
+        };
+        let (users, is_truncated, next_marker) =
+            store.list_users(None, Some(&pagination)).await.unwrap();
+        page_sizes.push(users.len());
+        seen.extend(users.into_iter().map(|u| u.user_name));
+
+        if !is_truncated {
+            assert!(next_marker.is_none());
+            break;
+        }
+        assert_eq!(next_marker.as_ref(), seen.last());
+        marker = next_marker;
+    }
+
+    assert_eq!(page_sizes, vec![100, 100, 50]);
+
+    // Pages come back in name order with no duplicates and no gaps
+    let mut expected: Vec<String> = store.users.keys().cloned().collect();
+    expected.sort();
+    assert_eq!(seen, expected);
+}
+
+#[tokio::test]
+async fn test_stream_users_matches_list_users() {
+    let mut store = InMemoryWamiStore::new();
+    store.seed(250, 0, 0).unwrap();
+
+    let streamed: Vec<String> = store
+        .stream_users()
+        .map(|user| user.unwrap().user_name)
+        .collect()
+        .await;
+
+    let (listed, _, _) = store.list_users(None, None).await.unwrap();
+    let listed: Vec<String> = listed.into_iter().map(|u| u.user_name).collect();
+    assert_eq!(streamed.len(), 250);
+    assert_eq!(streamed, listed);
+}
+
+#[tokio::test]
+async fn test_stream_roles_matches_list_roles() {
+    let mut store = InMemoryWamiStore::new();
+    store.seed(0, 0, 120).unwrap();
+
+    let count = store.stream_roles().count().await;
+    let (listed, _, _) = store.list_roles(None, None).await.unwrap();
+    assert_eq!(count, 120);
+    assert_eq!(count, listed.len());
+}
+
+#[tokio::test]
+async fn test_user_list_marker_stable_across_changes() {
+    let mut store = InMemoryWamiStore::new();
+    let context = test_context();
+
+    for i in 0..6 {
+        let user = user_builder::build_user(format!("user{:02}", i), None, &context).unwrap();
+        store.create_user(user).await.unwrap();
+    }
+
+    let pagination = PaginationParams {
+        max_items: Some(3),
+        marker: None,
+    };
+    let (_, _, marker) = store.list_users(None, Some(&pagination)).await.unwrap();
+    assert_eq!(marker.as_deref(), Some("user02"));
+
+    // Deleting the marker user and adding one before it does not shift the next page
+    store.delete_user("user02").await.unwrap();
+    let user = user_builder::build_user("user00a".to_string(), None, &context).unwrap();
+    store.create_user(user).await.unwrap();
+
+    let pagination = PaginationParams {
+        max_items: Some(3),
+        marker,
+    };
+    let (users, is_truncated, _) = store.list_users(None, Some(&pagination)).await.unwrap();
+    let names: Vec<&str> = users.iter().map(|u| u.user_name.as_str()).collect();
+    assert_eq!(names, vec!["user03", "user04", "user05"]);
+    assert!(!is_truncated);
+}
+
 #[tokio::test]
 async fn test_user_tag_operations() {
     let mut store = InMemoryWamiStore::new();
@@ -215,6 +334,117 @@ async fn test_user_tag_operations() {
     assert_eq!(remaining_tags[0].key, "Environment");
 }
 
+#[tokio::test]
+async fn test_filter_users_with_custom_predicate() {
+    let mut store = InMemoryWamiStore::new();
+    let context = test_context();
+    let platform_tag = Tag {
+        key: "team".to_string(),
+        value: "platform".to_string(),
+    };
+
+    let users = [
+        ("alice", "/engineering/", true),
+        ("bob", "/engineering/", false),
+        ("carol", "/sales/", true),
+        ("dave", "/engineering/", true),
+    ];
+    for (name, path, tagged) in users {
+        let mut user =
+            user_builder::build_user(name.to_string(), Some(path.to_string()), &context).unwrap();
+        if tagged {
+            user.tags.push(platform_tag.clone());
+        }
+        store.create_user(user).await.unwrap();
+    }
+
+    // Same tag and path, but in another tenant
+    let other_tenant = WamiContext::builder()
+        .instance_id("123456789012")
+        .tenant_path(TenantPath::single(87654321))
+        .caller_arn(context.caller_arn().clone())
+        .build()
+        .unwrap();
+    let mut eve = user_builder::build_user(
+        "eve".to_string(),
+        Some("/engineering/".to_string()),
+        &other_tenant,
+    )
+    .unwrap();
+    eve.tags.push(platform_tag);
+    store.create_user(eve).await.unwrap();
+
+    let is_platform_engineer = |user: &crate::wami::identity::User| {
+        user.path.starts_with("/engineering/")
+            && user
+                .tags
+                .iter()
+                .any(|tag| tag.key == "team" && tag.value == "platform")
+    };
+
+    let mut names: Vec<String> = store
+        .filter_users(is_platform_engineer)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|user| user.user_name)
+        .collect();
+    names.sort();
+    assert_eq!(names, vec!["alice", "dave", "eve"]);
+
+    // Composing with a tenant check scopes the query to one tenant
+    let tenant = context.tenant_path().clone();
+    let mut scoped: Vec<String> = store
+        .filter_users(move |user| {
+            user.wami_arn.belongs_to_tenant(&tenant) && is_platform_engineer(user)
+        })
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|user| user.user_name)
+        .collect();
+    scoped.sort();
+    assert_eq!(scoped, vec!["alice", "dave"]);
+}
+
+#[tokio::test]
+async fn test_filter_groups_and_roles() {
+    let mut store = InMemoryWamiStore::new();
+    let context = test_context();
+
+    for (name, path) in [("admins", "/ops/"), ("readers", "/")] {
+        let group =
+            group_builder::build_group(name.to_string(), Some(path.to_string()), &context).unwrap();
+        store.create_group(group).await.unwrap();
+    }
+    for name in ["deployer", "auditor"] {
+        let role = role_builder::build_role(
+            name.to_string(),
+            r#"{"Version":"2012-10-17","Statement":[]}"#.to_string(),
+            None,
+            None,
+            Some(7200),
+            &context,
+        )
+        .unwrap();
+        store.create_role(role).await.unwrap();
+    }
+
+    let groups = store
+        .filter_groups(|group| group.path == "/ops/")
+        .await
+        .unwrap();
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].group_name, "admins");
+
+    let roles = store
+        .filter_roles(|role| role.role_name.starts_with("dep"))
+        .await
+        .unwrap();
+    assert_eq!(roles.len(), 1);
+    assert_eq!(roles[0].role_name, "deployer");
+}
+
 // ============================================================================
 // GROUP STORE TESTS
 // ============================================================================
@@ -405,6 +635,39 @@ async fn test_role_list_multiple() {
     assert!(!is_truncated);
 }
 
+#[tokio::test]
+async fn test_get_roles_preserves_positions() {
+    let mut store = InMemoryWamiStore::new();
+    let context = test_context();
+    let trust_policy = r#"{"Version":"2012-10-17"}"#.to_string();
+
+    for name in ["deployer", "reader"] {
+        let role = role_builder::build_role(
+            name.to_string(),
+            trust_policy.clone(),
+            None,
+            None,
+            None,
+            &context,
+        )
+        .unwrap();
+        store.create_role(role).await.unwrap();
+    }
+
+    let names = vec![
+        "missing".to_string(),
+        "reader".to_string(),
+        "deployer".to_string(),
+    ];
+    let roles = store.get_roles(&names).await.unwrap();
+
+    let found: Vec<Option<&str>> = roles
+        .iter()
+        .map(|role| role.as_ref().map(|r| r.role_name.as_str()))
+        .collect();
+    assert_eq!(found, [None, Some("reader"), Some("deployer")]);
+}
+
 #[tokio::test]
 async fn test_role_with_path_prefix() {
     let mut store = InMemoryWamiStore::new();
@@ -1,6 +1,8 @@
 //! User Store Implementation for InMemoryWamiStore
 
 use crate::error::Result;
+use crate::provider::ResourceType;
+use crate::store::memory::pagination::paginate;
 use crate::store::memory::InMemoryWamiStore;
 use crate::store::traits::UserStore;
 use crate::types::{PaginationParams, Tag};
@@ -10,7 +12,12 @@ use async_trait::async_trait;
 #[async_trait]
 impl UserStore for InMemoryWamiStore {
     async fn create_user(&mut self, user: User) -> Result<User> {
-        self.users.insert(user.user_name.clone(), user.clone());
+        let previous = self.users.insert(user.user_name.clone(), user.clone());
+        self.usage.replace(
+            ResourceType::User,
+            previous.as_ref().map(|previous| &previous.wami_arn),
+            Some(&user.wami_arn),
+        );
         Ok(user)
     }
 
@@ -19,12 +26,20 @@ impl UserStore for InMemoryWamiStore {
     }
 
     async fn update_user(&mut self, user: User) -> Result<User> {
-        self.users.insert(user.user_name.clone(), user.clone());
+        let previous = self.users.insert(user.user_name.clone(), user.clone());
+        self.usage.replace(
+            ResourceType::User,
+            previous.as_ref().map(|previous| &previous.wami_arn),
+            Some(&user.wami_arn),
+        );
         Ok(user)
     }
 
     async fn delete_user(&mut self, user_name: &str) -> Result<()> {
-        self.users.remove(user_name);
+        if let Some(removed) = self.users.remove(user_name) {
+            self.usage
+                .replace(ResourceType::User, Some(&removed.wami_arn), None);
+        }
         // Also remove associated access keys
         self.access_keys.retain(|_, key| key.user_name != user_name);
         // Remove from user-groups mapping
@@ -47,21 +62,7 @@ impl UserStore for InMemoryWamiStore {
         // Sort by user name
         users.sort_by(|a, b| a.user_name.cmp(&b.user_name));
 
-        // Apply pagination
-        let mut is_truncated = false;
-        let mut marker = None;
-
-        if let Some(pagination) = pagination {
-            if let Some(max_items) = pagination.max_items {
-                if users.len() > max_items as usize {
-                    users.truncate(max_items as usize);
-                    is_truncated = true;
-                    marker = Some(users.last().unwrap().user_name.clone());
-                }
-            }
-        }
-
-        Ok((users, is_truncated, marker))
+        Ok(paginate(users, pagination, |item| &item.user_name))
     }
 
     async fn tag_user(&mut self, user_name: &str, tags: Vec<Tag>) -> Result<()> {
@@ -19,8 +19,10 @@ mod unified;
 mod wami;
 
 // Sub-directories for sub-trait implementations
+mod count;
 mod credentials;
 mod identity;
+pub(crate) mod pagination;
 mod policies;
 mod reports;
 
@@ -29,4 +31,6 @@ pub use sso_admin::InMemorySsoAdminStore;
 pub use sts::InMemoryStsStore;
 pub use tenant::InMemoryTenantStore;
 pub use unified::InMemoryStore;
-pub use wami::InMemoryWamiStore;
+pub use wami::{
+    InMemoryWamiStore, IntegrityIssue, SEED_INSTANCE_ID, SEED_TENANT_ID, SNAPSHOT_VERSION,
+};
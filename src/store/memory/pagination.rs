@@ -0,0 +1,71 @@
+//! Marker-Based Pagination for In-Memory Stores
+
+use crate::types::PaginationParams;
+
+/// Apply marker-based pagination to items sorted by `key`
+///
+/// Items whose key is less than or equal to the marker are skipped, so a
+/// marker stays valid even when the item it names has since been deleted or
+/// other items were added. When more than `max_items` remain, the page is
+/// truncated and the returned marker is the key of its last item. Without
+/// `max_items` every remaining item is returned.
+pub(crate) fn paginate<T>(
+    items: Vec<T>,
+    pagination: Option<&PaginationParams>,
+    key: impl Fn(&T) -> &str,
+) -> (Vec<T>, bool, Option<String>) {
+    let marker = pagination.and_then(|p| p.marker.as_deref());
+    let max_items = pagination.and_then(|p| p.max_items).map(|max| max as usize);
+
+    let mut page: Vec<T> = match marker {
+        Some(marker) => items
+            .into_iter()
+            .filter(|item| key(item) > marker)
+            .collect(),
+        None => items,
+    };
+
+    match max_items {
+        Some(max_items) if page.len() > max_items => {
+            page.truncate(max_items);
+            let next_marker = page.last().map(|item| key(item).to_string());
+            (page, true, next_marker)
+        }
+        _ => (page, false, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(count: usize) -> Vec<String> {
+        (0..count).map(|i| format!("item-{:03}", i)).collect()
+    }
+
+    fn params(max_items: i32, marker: Option<String>) -> PaginationParams {
+        PaginationParams {
+            max_items: Some(max_items),
+            marker,
+        }
+    }
+
+    #[test]
+    fn test_paginate_without_params_returns_everything() {
+        let (page, is_truncated, marker) = paginate(names(5), None, |s| s.as_str());
+        assert_eq!(page.len(), 5);
+        assert!(!is_truncated);
+        assert!(marker.is_none());
+    }
+
+    #[test]
+    fn test_paginate_marker_survives_deletion() {
+        let (_, _, marker) = paginate(names(10), Some(&params(3, None)), |s| s.as_str());
+        assert_eq!(marker.as_deref(), Some("item-002"));
+
+        // The marker item is removed before the next call
+        let remaining: Vec<String> = names(10).into_iter().filter(|n| n != "item-002").collect();
+        let (page, _, _) = paginate(remaining, Some(&params(3, marker)), |s| s.as_str());
+        assert_eq!(page, vec!["item-003", "item-004", "item-005"]);
+    }
+}
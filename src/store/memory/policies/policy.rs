@@ -1,6 +1,8 @@
 //! Policy Store Implementation for InMemoryWamiStore
 
 use crate::error::Result;
+use crate::provider::ResourceType;
+use crate::store::memory::pagination::paginate;
 use crate::store::memory::InMemoryWamiStore;
 use crate::store::traits::PolicyStore;
 use crate::types::PaginationParams;
@@ -10,7 +12,12 @@ use async_trait::async_trait;
 #[async_trait]
 impl PolicyStore for InMemoryWamiStore {
     async fn create_policy(&mut self, policy: Policy) -> Result<Policy> {
-        self.policies.insert(policy.arn.clone(), policy.clone());
+        let previous = self.policies.insert(policy.arn.clone(), policy.clone());
+        self.usage.replace(
+            ResourceType::Policy,
+            previous.as_ref().map(|previous| &previous.wami_arn),
+            Some(&policy.wami_arn),
+        );
         Ok(policy)
     }
 
@@ -19,12 +26,20 @@ impl PolicyStore for InMemoryWamiStore {
     }
 
     async fn update_policy(&mut self, policy: Policy) -> Result<Policy> {
-        self.policies.insert(policy.arn.clone(), policy.clone());
+        let previous = self.policies.insert(policy.arn.clone(), policy.clone());
+        self.usage.replace(
+            ResourceType::Policy,
+            previous.as_ref().map(|previous| &previous.wami_arn),
+            Some(&policy.wami_arn),
+        );
         Ok(policy)
     }
 
     async fn delete_policy(&mut self, policy_arn: &str) -> Result<()> {
-        self.policies.remove(policy_arn);
+        if let Some(removed) = self.policies.remove(policy_arn) {
+            self.usage
+                .replace(ResourceType::Policy, Some(&removed.wami_arn), None);
+        }
         Ok(())
     }
 
@@ -49,27 +64,14 @@ impl PolicyStore for InMemoryWamiStore {
         // Sort for consistent results
         policies.sort_by(|a, b| a.arn.cmp(&b.arn));
 
-        if let Some(params) = pagination {
-            let mut start_index = 0;
-            if let Some(ref marker) = params.marker {
-                if let Some(pos) = policies.iter().position(|p| &p.arn == marker) {
-                    start_index = pos + 1;
-                }
-            }
-
-            let max_items = params.max_items.unwrap_or(100).min(1000) as usize;
-            let end_index = (start_index + max_items).min(policies.len());
-            let is_truncated = end_index < policies.len();
-            let next_marker = if is_truncated {
-                policies.get(end_index - 1).map(|p| p.arn.clone())
-            } else {
-                None
-            };
+        // Policies default to pages of 100, capped at 1000
+        let pagination = pagination.map(|params| PaginationParams {
+            max_items: Some(params.max_items.unwrap_or(100).min(1000)),
+            marker: params.marker.clone(),
+        });
 
-            policies = policies[start_index..end_index].to_vec();
-            Ok((policies, is_truncated, next_marker))
-        } else {
-            Ok((policies, false, None))
-        }
+        Ok(paginate(policies, pagination.as_ref(), |policy| {
+            &policy.arn
+        }))
     }
 }
@@ -12,12 +12,8 @@ impl AccountAssignmentStore for InMemorySsoAdminStore {
         &mut self,
         assignment: AccountAssignment,
     ) -> Result<AccountAssignment> {
-        let assignment_id = format!(
-            "{}-{}-{}",
-            assignment.account_id, assignment.permission_set_arn, assignment.principal_id
-        );
         self.account_assignments
-            .insert(assignment_id, assignment.clone());
+            .insert(assignment.assignment_id.clone(), assignment.clone());
         Ok(assignment)
     }
 
@@ -49,6 +45,13 @@ impl AccountAssignmentStore for InMemorySsoAdminStore {
             .collect();
         Ok(assignments)
     }
+
+    async fn list_all_account_assignments(&self) -> Result<Vec<AccountAssignment>> {
+        let mut assignments: Vec<AccountAssignment> =
+            self.account_assignments.values().cloned().collect();
+        assignments.sort_by(|a, b| a.assignment_id.cmp(&b.assignment_id));
+        Ok(assignments)
+    }
 }
 
 /// Implement AccountAssignmentStore for InMemoryWamiStore (the main unified store)
@@ -87,4 +90,11 @@ impl AccountAssignmentStore for super::super::wami::InMemoryWamiStore {
             .cloned()
             .collect())
     }
+
+    async fn list_all_account_assignments(&self) -> Result<Vec<AccountAssignment>> {
+        let mut assignments: Vec<AccountAssignment> =
+            self.account_assignments.values().cloned().collect();
+        assignments.sort_by(|a, b| a.assignment_id.cmp(&b.assignment_id));
+        Ok(assignments)
+    }
 }
@@ -3,7 +3,7 @@
 use crate::error::Result;
 use crate::store::memory::sso_admin::InMemorySsoAdminStore;
 use crate::store::traits::ApplicationStore;
-use crate::wami::sso_admin::Application;
+use crate::wami::sso_admin::{Application, ApplicationAssignment};
 use async_trait::async_trait;
 
 #[async_trait]
@@ -21,6 +21,34 @@ impl ApplicationStore for InMemorySsoAdminStore {
     async fn list_applications(&self, _instance_arn: &str) -> Result<Vec<Application>> {
         Ok(self.applications.values().cloned().collect())
     }
+
+    async fn put_application_assignment(
+        &mut self,
+        assignment: ApplicationAssignment,
+    ) -> Result<ApplicationAssignment> {
+        self.application_assignments
+            .insert(assignment.key(), assignment.clone());
+        Ok(assignment)
+    }
+
+    async fn delete_application_assignment(&mut self, assignment_key: &str) -> Result<()> {
+        self.application_assignments.remove(assignment_key);
+        Ok(())
+    }
+
+    async fn list_application_assignments(
+        &self,
+        application_arn: &str,
+    ) -> Result<Vec<ApplicationAssignment>> {
+        let mut assignments: Vec<ApplicationAssignment> = self
+            .application_assignments
+            .values()
+            .filter(|assignment| assignment.application_arn == application_arn)
+            .cloned()
+            .collect();
+        assignments.sort_by_key(ApplicationAssignment::key);
+        Ok(assignments)
+    }
 }
 
 /// Implement ApplicationStore for InMemoryWamiStore (the main unified store)
@@ -44,4 +72,32 @@ impl ApplicationStore for super::super::wami::InMemoryWamiStore {
             .cloned()
             .collect())
     }
+
+    async fn put_application_assignment(
+        &mut self,
+        assignment: ApplicationAssignment,
+    ) -> Result<ApplicationAssignment> {
+        self.application_assignments
+            .insert(assignment.key(), assignment.clone());
+        Ok(assignment)
+    }
+
+    async fn delete_application_assignment(&mut self, assignment_key: &str) -> Result<()> {
+        self.application_assignments.remove(assignment_key);
+        Ok(())
+    }
+
+    async fn list_application_assignments(
+        &self,
+        application_arn: &str,
+    ) -> Result<Vec<ApplicationAssignment>> {
+        let mut assignments: Vec<ApplicationAssignment> = self
+            .application_assignments
+            .values()
+            .filter(|assignment| assignment.application_arn == application_arn)
+            .cloned()
+            .collect();
+        assignments.sort_by_key(ApplicationAssignment::key);
+        Ok(assignments)
+    }
 }
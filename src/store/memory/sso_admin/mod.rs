@@ -8,7 +8,8 @@
 //! - `trusted_token_issuer.rs` - TrustedTokenIssuerStore implementation
 
 use crate::wami::sso_admin::{
-    AccountAssignment, Application, PermissionSet, SsoInstance, TrustedTokenIssuer,
+    AccountAssignment, Application, ApplicationAssignment, PermissionSet, SsoInstance,
+    TrustedTokenIssuer,
 };
 use std::collections::HashMap;
 
@@ -34,6 +35,7 @@ pub struct InMemorySsoAdminStore {
     pub(super) account_assignments: HashMap<String, AccountAssignment>,
     pub(super) instances: HashMap<String, SsoInstance>,
     pub(super) applications: HashMap<String, Application>,
+    pub(super) application_assignments: HashMap<String, ApplicationAssignment>,
     pub(super) trusted_token_issuers: HashMap<String, TrustedTokenIssuer>,
 }
 
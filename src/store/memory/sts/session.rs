@@ -6,6 +6,7 @@ use crate::store::memory::InMemoryWamiStore;
 use crate::store::traits::SessionStore;
 use crate::wami::sts::StsSession;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 
 #[async_trait]
 impl SessionStore for InMemoryStsStore {
@@ -28,6 +29,12 @@ impl SessionStore for InMemoryStsStore {
         let sessions: Vec<StsSession> = self.sessions.values().cloned().collect();
         Ok(sessions)
     }
+
+    async fn purge_expired(&mut self, now: DateTime<Utc>) -> Result<usize> {
+        let before = self.sessions.len();
+        self.sessions.retain(|_, session| session.expiration > now);
+        Ok(before - self.sessions.len())
+    }
 }
 
 #[async_trait]
@@ -51,4 +58,10 @@ impl SessionStore for InMemoryWamiStore {
         let sessions: Vec<StsSession> = self.sessions.values().cloned().collect();
         Ok(sessions)
     }
+
+    async fn purge_expired(&mut self, now: DateTime<Utc>) -> Result<usize> {
+        let before = self.sessions.len();
+        self.sessions.retain(|_, session| session.expiration > now);
+        Ok(before - self.sessions.len())
+    }
 }
@@ -48,6 +48,10 @@ async fn test_session_create_and_get() {
         tenant_id: None,
         created_at: Utc::now(),
         last_used: None,
+        policy: None,
+        role_chain_length: 0,
+        tags: vec![],
+        transitive_tag_keys: vec![],
     };
 
     // Create session
@@ -89,6 +93,10 @@ async fn test_session_delete() {
         tenant_id: None,
         created_at: Utc::now(),
         last_used: None,
+        policy: None,
+        role_chain_length: 0,
+        tags: vec![],
+        transitive_tag_keys: vec![],
     };
 
     store.create_session(session).await.unwrap();
@@ -101,6 +109,52 @@ async fn test_session_delete() {
     assert!(result.is_none());
 }
 
+#[tokio::test]
+async fn test_session_purge_expired() {
+    let mut store = InMemoryStsStore::default();
+    let now = Utc::now();
+
+    for (token, lifetime) in [
+        ("short", Duration::minutes(15)),
+        ("long", Duration::hours(1)),
+    ] {
+        let session = StsSession {
+            session_token: token.to_string(),
+            access_key_id: format!("ASIA{}", token),
+            secret_access_key: "secret".to_string(),
+            expiration: now + lifetime,
+            status: SessionStatus::Active,
+            assumed_role_arn: None,
+            federated_user_name: None,
+            principal_arn: None,
+            arn: format!("arn:wami:sts::{}", token),
+            wami_arn: format!("arn:wami:.*:0:wami:123456789012:session/{}", token)
+                .parse()
+                .unwrap(),
+            providers: Vec::new(),
+            tenant_id: None,
+            created_at: now,
+            last_used: None,
+            policy: None,
+            role_chain_length: 0,
+            tags: vec![],
+            transitive_tag_keys: vec![],
+        };
+        store.create_session(session).await.unwrap();
+    }
+
+    assert_eq!(store.purge_expired(now).await.unwrap(), 0);
+    assert_eq!(
+        store
+            .purge_expired(now + Duration::minutes(15))
+            .await
+            .unwrap(),
+        1
+    );
+    assert!(store.get_session("short").await.unwrap().is_none());
+    assert!(store.get_session("long").await.unwrap().is_some());
+}
+
 #[tokio::test]
 async fn test_session_list_empty() {
     let store = InMemoryStsStore::default();
@@ -132,6 +186,10 @@ async fn test_session_list_multiple() {
             tenant_id: None,
             created_at: Utc::now(),
             last_used: None,
+            policy: None,
+            role_chain_length: 0,
+            tags: vec![],
+            transitive_tag_keys: vec![],
         };
         store.create_session(session).await.unwrap();
     }
@@ -161,6 +219,10 @@ async fn test_session_with_role() {
         tenant_id: None,
         created_at: Utc::now(),
         last_used: None,
+        policy: None,
+        role_chain_length: 1,
+        tags: vec![],
+        transitive_tag_keys: vec![],
     };
 
     store.create_session(session.clone()).await.unwrap();
@@ -202,6 +264,10 @@ async fn test_session_with_providers() {
         tenant_id: None,
         created_at: Utc::now(),
         last_used: None,
+        policy: None,
+        role_chain_length: 0,
+        tags: vec![],
+        transitive_tag_keys: vec![],
     };
 
     store.create_session(session).await.unwrap();
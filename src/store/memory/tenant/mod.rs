@@ -1,6 +1,8 @@
 //! In-Memory Tenant Store Implementation
 
+use crate::arn::TenantPath;
 use crate::error::{AmiError, Result};
+use crate::provider::ResourceType;
 use crate::store::traits::TenantStore;
 use crate::wami::tenant::{Tenant, TenantId, TenantQuotas, TenantUsage};
 use async_trait::async_trait;
@@ -233,15 +235,16 @@ impl TenantStore for super::wami::InMemoryWamiStore {
             });
         }
 
-        // TODO: Actually count resources for this tenant
-        // This will be populated by the IAM store
-        // For now, return empty usage
+        // Live counters cover only resources scoped directly to this tenant (not its descendants)
+        let tenant_path = TenantPath::from_tenant_id(tenant_id);
+        let count = |resource_type| self.usage.get(&tenant_path, resource_type);
+
         Ok(TenantUsage {
             tenant_id: tenant_id.clone(),
-            current_users: 0,
-            current_roles: 0,
-            current_policies: 0,
-            current_groups: 0,
+            current_users: count(ResourceType::User),
+            current_roles: count(ResourceType::Role),
+            current_policies: count(ResourceType::Policy),
+            current_groups: count(ResourceType::Group),
             current_sub_tenants: self.list_child_tenants(tenant_id).await?.len(),
             include_descendants: false,
         })
@@ -377,3 +377,97 @@ async fn test_tenant_different_types() {
     let retrieved_proj = store.get_tenant(&project.id).await.unwrap().unwrap();
     assert_eq!(retrieved_proj.tenant_type, TenantType::Project);
 }
+
+#[tokio::test]
+async fn test_tenant_usage_tracks_created_and_deleted_resources() {
+    use crate::arn::{TenantPath, WamiArn};
+    use crate::context::WamiContext;
+    use crate::store::memory::InMemoryWamiStore;
+    use crate::store::traits::{GroupStore, PolicyStore, RoleStore, UserStore};
+    use crate::wami::identity::group::builder::build_group;
+    use crate::wami::identity::role::builder::build_role;
+    use crate::wami::identity::user::builder::build_user;
+    use crate::wami::policies::policy::builder::build_policy;
+
+    let mut store = InMemoryWamiStore::default();
+    let tenant = build_test_tenant("acme-corp", None);
+    let tenant_id = tenant.id.clone();
+    store.create_tenant(tenant).await.unwrap();
+
+    let tenant_path = TenantPath::from_tenant_id(&tenant_id);
+    let caller_arn: WamiArn = format!("arn:wami:iam:{}:wami:123456789012:user/admin", tenant_path)
+        .parse()
+        .unwrap();
+    let context = WamiContext::builder()
+        .instance_id("123456789012")
+        .tenant_path(tenant_path)
+        .caller_arn(caller_arn)
+        .is_root(false)
+        .build()
+        .unwrap();
+
+    for name in ["alice", "bob", "carol"] {
+        let user = build_user(name.to_string(), None, &context).unwrap();
+        store.create_user(user).await.unwrap();
+    }
+    for name in ["admins", "devs"] {
+        let group = build_group(name.to_string(), None, &context).unwrap();
+        store.create_group(group).await.unwrap();
+    }
+    let role = build_role(
+        "deployer".to_string(),
+        r#"{"Version":"2012-10-17","Statement":[]}"#.to_string(),
+        None,
+        None,
+        None,
+        &context,
+    )
+    .unwrap();
+    store.create_role(role).await.unwrap();
+    let policy = build_policy(
+        "read-only".to_string(),
+        r#"{"Version":"2012-10-17","Statement":[]}"#.to_string(),
+        None,
+        None,
+        None,
+        &context,
+    )
+    .unwrap();
+    let policy_arn = policy.arn.clone();
+    store.create_policy(policy).await.unwrap();
+
+    // Updating a resource does not count it twice
+    let bob = store.get_user("bob").await.unwrap().unwrap();
+    store.update_user(bob).await.unwrap();
+
+    let usage = store.get_tenant_usage(&tenant_id).await.unwrap();
+    assert_eq!(usage.current_users, 3);
+    assert_eq!(usage.current_groups, 2);
+    assert_eq!(usage.current_roles, 1);
+    assert_eq!(usage.current_policies, 1);
+
+    store.delete_user("alice").await.unwrap();
+    store.delete_group("devs").await.unwrap();
+    store.delete_role("deployer").await.unwrap();
+    store.delete_policy(&policy_arn).await.unwrap();
+
+    // Deleting what is already gone does not drive the counts below zero
+    store.delete_role("deployer").await.unwrap();
+    store.delete_policy(&policy_arn).await.unwrap();
+    store.delete_user("nobody").await.unwrap();
+
+    let usage = store.get_tenant_usage(&tenant_id).await.unwrap();
+    assert_eq!(usage.current_users, 2);
+    assert_eq!(usage.current_groups, 1);
+    assert_eq!(usage.current_roles, 0);
+    assert_eq!(usage.current_policies, 0);
+
+    // Counters are rebuilt from an imported snapshot
+    let mut restored = InMemoryWamiStore::default();
+    restored
+        .import_snapshot(&store.export_snapshot().unwrap())
+        .unwrap();
+    let usage = restored.get_tenant_usage(&tenant_id).await.unwrap();
+    assert_eq!(usage.current_users, 2);
+    assert_eq!(usage.current_groups, 1);
+}
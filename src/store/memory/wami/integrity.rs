@@ -0,0 +1,224 @@
+//! Integrity Checks for the In-Memory WAMI Store
+//!
+//! Diagnostic helpers that walk the store's internal maps and report
+//! cross-resource references that no longer resolve.
+
+use super::InMemoryWamiStore;
+use serde::{Deserialize, Serialize};
+
+/// A broken invariant detected by [`InMemoryWamiStore::check_integrity`]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum IntegrityIssue {
+    /// A group membership entry references a user that does not exist
+    MembershipUserMissing {
+        /// The missing user
+        user_name: String,
+        /// The group the user is recorded as a member of
+        group_name: String,
+    },
+    /// A group membership entry references a group that does not exist
+    MembershipGroupMissing {
+        /// The member user
+        user_name: String,
+        /// The missing group
+        group_name: String,
+    },
+    /// An access key belongs to a user that does not exist
+    AccessKeyUserMissing {
+        /// The orphaned access key
+        access_key_id: String,
+        /// The missing owner
+        user_name: String,
+    },
+    /// A managed policy attachment references a policy that does not exist
+    AttachedPolicyMissing {
+        /// Principal type (`user`, `group` or `role`)
+        principal_type: String,
+        /// Principal name
+        principal_name: String,
+        /// The unresolved policy ARN
+        policy_arn: String,
+    },
+    /// A resource is stored with an empty ARN
+    EmptyArn {
+        /// Resource type (`user`, `group`, `role` or `policy`)
+        resource_type: String,
+        /// Resource name
+        resource_name: String,
+    },
+}
+
+impl InMemoryWamiStore {
+    /// Verify cross-resource invariants and report every violation found
+    ///
+    /// Checks that group memberships reference existing users and groups,
+    /// that every access key's owner exists, that every attached managed
+    /// policy resolves, and that no user, group, role or policy has an empty
+    /// ARN. The store is not modified. Issues are returned in a stable order.
+    pub fn check_integrity(&self) -> Vec<IntegrityIssue> {
+        let mut issues = Vec::new();
+
+        for (user_name, group_names) in &self.user_groups {
+            for group_name in group_names {
+                if !self.users.contains_key(user_name) {
+                    issues.push(IntegrityIssue::MembershipUserMissing {
+                        user_name: user_name.clone(),
+                        group_name: group_name.clone(),
+                    });
+                }
+                if !self.groups.contains_key(group_name) {
+                    issues.push(IntegrityIssue::MembershipGroupMissing {
+                        user_name: user_name.clone(),
+                        group_name: group_name.clone(),
+                    });
+                }
+            }
+        }
+
+        for key in self.access_keys.values() {
+            if !self.users.contains_key(&key.user_name) {
+                issues.push(IntegrityIssue::AccessKeyUserMissing {
+                    access_key_id: key.access_key_id.clone(),
+                    user_name: key.user_name.clone(),
+                });
+            }
+        }
+
+        for (principal_type, attachments) in [
+            ("user", &self.user_attached_policies),
+            ("group", &self.group_attached_policies),
+            ("role", &self.role_attached_policies),
+        ] {
+            for (principal_name, policy_arns) in attachments {
+                for policy_arn in policy_arns {
+                    if !self.policies.contains_key(policy_arn) {
+                        issues.push(IntegrityIssue::AttachedPolicyMissing {
+                            principal_type: principal_type.to_string(),
+                            principal_name: principal_name.clone(),
+                            policy_arn: policy_arn.clone(),
+                        });
+                    }
+                }
+            }
+        }
+
+        let arns = self
+            .users
+            .values()
+            .map(|u| ("user", &u.user_name, &u.arn))
+            .chain(
+                self.groups
+                    .values()
+                    .map(|g| ("group", &g.group_name, &g.arn)),
+            )
+            .chain(self.roles.values().map(|r| ("role", &r.role_name, &r.arn)))
+            .chain(
+                self.policies
+                    .values()
+                    .map(|p| ("policy", &p.policy_name, &p.arn)),
+            );
+        for (resource_type, resource_name, arn) in arns {
+            if arn.trim().is_empty() {
+                issues.push(IntegrityIssue::EmptyArn {
+                    resource_type: resource_type.to_string(),
+                    resource_name: resource_name.clone(),
+                });
+            }
+        }
+
+        // HashMap iteration order is arbitrary; sort for reproducible reports
+        issues.sort();
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arn::{TenantPath, WamiArn};
+    use crate::context::WamiContext;
+    use crate::store::traits::{AccessKeyStore, GroupStore, UserStore};
+    use crate::wami::credentials::access_key::builder as access_key_builder;
+    use crate::wami::identity::group::builder as group_builder;
+    use crate::wami::identity::user::builder as user_builder;
+
+    fn test_context() -> WamiContext {
+        let arn: WamiArn = "arn:wami:.*:12345678:wami:123456789012:user/test"
+            .parse()
+            .unwrap();
+        WamiContext::builder()
+            .instance_id("123456789012")
+            .tenant_path(TenantPath::single(12345678))
+            .caller_arn(arn)
+            .is_root(false)
+            .build()
+            .unwrap()
+    }
+
+    async fn seeded_store() -> InMemoryWamiStore {
+        let context = test_context();
+        let mut store = InMemoryWamiStore::new();
+
+        let user = user_builder::build_user("alice".to_string(), None, &context).unwrap();
+        store.create_user(user).await.unwrap();
+        let group = group_builder::build_group("admins".to_string(), None, &context).unwrap();
+        store.create_group(group).await.unwrap();
+        store.add_user_to_group("admins", "alice").await.unwrap();
+        let key = access_key_builder::build_access_key("alice".to_string(), &context).unwrap();
+        store.create_access_key(key).await.unwrap();
+
+        store
+    }
+
+    #[tokio::test]
+    async fn test_consistent_store_has_no_issues() {
+        let store = seeded_store().await;
+        assert!(store.check_integrity().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_detects_membership_for_deleted_user() {
+        let mut store = seeded_store().await;
+
+        // Corrupt the store: drop the user but keep its membership and key
+        store.users.remove("alice");
+
+        let issues = store.check_integrity();
+        assert_eq!(issues.len(), 2);
+        assert!(issues.contains(&IntegrityIssue::MembershipUserMissing {
+            user_name: "alice".to_string(),
+            group_name: "admins".to_string(),
+        }));
+        assert!(issues
+            .iter()
+            .any(|issue| matches!(issue, IntegrityIssue::AccessKeyUserMissing { user_name, .. } if user_name == "alice")));
+    }
+
+    #[tokio::test]
+    async fn test_detects_missing_group_policy_and_empty_arn() {
+        let mut store = seeded_store().await;
+
+        store.groups.remove("admins");
+        store
+            .attach_user_policy("alice", "arn:aws:iam::123456789012:policy/Gone")
+            .await
+            .unwrap();
+        store.users.get_mut("alice").unwrap().arn.clear();
+
+        let issues = store.check_integrity();
+        assert_eq!(issues.len(), 3);
+        assert!(issues.contains(&IntegrityIssue::MembershipGroupMissing {
+            user_name: "alice".to_string(),
+            group_name: "admins".to_string(),
+        }));
+        assert!(issues.contains(&IntegrityIssue::AttachedPolicyMissing {
+            principal_type: "user".to_string(),
+            principal_name: "alice".to_string(),
+            policy_arn: "arn:aws:iam::123456789012:policy/Gone".to_string(),
+        }));
+        assert!(issues.contains(&IntegrityIssue::EmptyArn {
+            resource_type: "user".to_string(),
+            resource_name: "alice".to_string(),
+        }));
+    }
+}
@@ -6,17 +6,28 @@
 //! - `credentials/` - AccessKeyStore, MfaDeviceStore, LoginProfileStore
 //! - `policies/` - PolicyStore
 
-use crate::wami::credentials::{AccessKey, LoginProfile, MfaDevice};
+use crate::wami::credentials::{AccessKey, LoginProfile, MfaDevice, PasswordPolicy};
 use crate::wami::identity::identity_provider::{OidcProvider, SamlProvider};
 use crate::wami::identity::{Group, Role, User};
 use crate::wami::policies::Policy;
 use crate::wami::sso_admin::{
-    AccountAssignment, Application, PermissionSet, SsoInstance, TrustedTokenIssuer,
+    AccountAssignment, Application, ApplicationAssignment, PermissionSet, SsoInstance,
+    TrustedTokenIssuer,
 };
 use crate::wami::sts::{CallerIdentity, StsSession};
 use crate::wami::tenant::{Tenant, TenantId};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+mod integrity;
+mod seed;
+mod snapshot;
+mod usage;
+
+pub use integrity::IntegrityIssue;
+pub use seed::{SEED_INSTANCE_ID, SEED_TENANT_ID};
+pub use snapshot::SNAPSHOT_VERSION;
+
 /// In-memory implementation of WAMI store
 ///
 /// This is a pure persistence layer that stores identity resources for ALL tenants
@@ -34,7 +45,12 @@ use std::collections::HashMap;
 /// - `AccessKeyStore` → `memory/credentials/access_key.rs`
 /// - `MfaDeviceStore` → `memory/credentials/mfa_device.rs` (TODO)
 /// - `LoginProfileStore` → `memory/credentials/login_profile.rs` (TODO)
-#[derive(Debug, Clone, Default)]
+///
+/// The store can be saved and restored as a whole with
+/// [`export_snapshot`](Self::export_snapshot) and
+/// [`import_snapshot`](Self::import_snapshot).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct InMemoryWamiStore {
     pub(super) users: HashMap<String, User>,
     pub(super) access_keys: HashMap<String, AccessKey>,
@@ -43,7 +59,8 @@ pub struct InMemoryWamiStore {
     pub(super) policies: HashMap<String, Policy>,
     pub(super) mfa_devices: HashMap<String, MfaDevice>,
     pub(super) login_profiles: HashMap<String, LoginProfile>,
-    pub(super) user_groups: HashMap<String, Vec<String>>, // user_name -> group_names
+    pub(super) password_policies: HashMap<String, PasswordPolicy>, // tenant_path -> policy
+    pub(super) user_groups: HashMap<String, Vec<String>>,          // user_name -> group_names
     pub(super) credential_report: Option<crate::wami::reports::credential_report::CredentialReport>,
     #[allow(dead_code)]
     pub(super) server_certificates: HashMap<String, crate::wami::credentials::ServerCertificate>,
@@ -65,6 +82,7 @@ pub struct InMemoryWamiStore {
     pub(super) permission_sets: HashMap<String, PermissionSet>,
     pub(super) account_assignments: HashMap<String, AccountAssignment>,
     pub(super) applications: HashMap<String, Application>,
+    pub(super) application_assignments: HashMap<String, ApplicationAssignment>,
     pub(super) trusted_token_issuers: HashMap<String, TrustedTokenIssuer>,
     // Identity Provider resources
     pub(super) saml_providers: HashMap<String, SamlProvider>,
@@ -78,6 +96,9 @@ pub struct InMemoryWamiStore {
     pub(super) user_inline_policies: HashMap<String, HashMap<String, String>>, // user_name -> {policy_name -> document}
     pub(super) group_inline_policies: HashMap<String, HashMap<String, String>>, // group_name -> {policy_name -> document}
     pub(super) role_inline_policies: HashMap<String, HashMap<String, String>>, // role_name -> {policy_name -> document}
+    // Per-tenant resource counts, derived from the maps above
+    #[serde(skip)]
+    pub(super) usage: usage::UsageCounters,
 }
 
 impl InMemoryWamiStore {
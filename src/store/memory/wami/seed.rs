@@ -0,0 +1,157 @@
+//! Bulk Seeding for the In-Memory WAMI Store
+//!
+//! Generates synthetic users, groups and roles for benchmarks and demos.
+
+#![allow(clippy::result_large_err)]
+
+use super::InMemoryWamiStore;
+use crate::arn::{Service, TenantPath, WamiArn};
+use crate::context::WamiContext;
+use crate::error::Result;
+use crate::provider::ResourceType;
+use crate::wami::identity::group::builder::build_group;
+use crate::wami::identity::role::builder::build_role;
+use crate::wami::identity::user::builder::build_user;
+
+/// Instance ID used for seeded resources
+pub const SEED_INSTANCE_ID: &str = "000000000000";
+
+/// Tenant ID used for seeded resources
+pub const SEED_TENANT_ID: u64 = 0;
+
+/// Trust policy attached to seeded roles
+const SEED_TRUST_POLICY: &str = r#"{"Version":"2012-10-17","Statement":[{"Effect":"Allow","Principal":{"Service":"ec2.amazonaws.com"},"Action":"sts:AssumeRole"}]}"#;
+
+impl InMemoryWamiStore {
+    /// Fill the store with synthetic users, groups and roles
+    ///
+    /// Resources are named `seed-user-N`, `seed-group-N` and `seed-role-N` and
+    /// get valid WAMI ARNs in tenant [`SEED_TENANT_ID`] of instance
+    /// [`SEED_INSTANCE_ID`]. Users are spread round-robin across the groups.
+    ///
+    /// Resources are written straight into the store's maps, skipping the
+    /// per-item checks of the service layer, so seeding is fast enough for
+    /// benchmark setup. Existing resources with the same names are replaced.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use wami::store::memory::InMemoryWamiStore;
+    ///
+    /// let mut store = InMemoryWamiStore::new();
+    /// store.seed(100, 5, 10).unwrap();
+    /// assert!(store.check_integrity().is_empty());
+    /// ```
+    pub fn seed(&mut self, users: usize, groups: usize, roles: usize) -> Result<()> {
+        let context = seed_context()?;
+
+        let group_names: Vec<String> = (0..groups).map(|i| format!("seed-group-{}", i)).collect();
+        for group_name in &group_names {
+            let group = build_group(group_name.clone(), None, &context)?;
+            self.usage
+                .replace(ResourceType::Group, None, Some(&group.wami_arn));
+            self.groups.insert(group_name.clone(), group);
+        }
+
+        self.users.reserve(users);
+        for i in 0..users {
+            let user_name = format!("seed-user-{}", i);
+            let user = build_user(user_name.clone(), None, &context)?;
+            self.usage
+                .replace(ResourceType::User, None, Some(&user.wami_arn));
+            self.users.insert(user_name.clone(), user);
+
+            if !group_names.is_empty() {
+                let group_name = &group_names[i % group_names.len()];
+                let memberships = self.user_groups.entry(user_name).or_default();
+                if !memberships.contains(group_name) {
+                    memberships.push(group_name.clone());
+                }
+            }
+        }
+
+        for i in 0..roles {
+            let role_name = format!("seed-role-{}", i);
+            let role = build_role(
+                role_name.clone(),
+                SEED_TRUST_POLICY.to_string(),
+                None,
+                None,
+                None,
+                &context,
+            )?;
+            self.usage
+                .replace(ResourceType::Role, None, Some(&role.wami_arn));
+            self.roles.insert(role_name, role);
+        }
+
+        Ok(())
+    }
+}
+
+/// Context under which seeded resources are created
+fn seed_context() -> Result<WamiContext> {
+    let caller_arn = WamiArn::builder()
+        .service(Service::Iam)
+        .tenant_path(TenantPath::single(SEED_TENANT_ID))
+        .wami_instance(SEED_INSTANCE_ID)
+        .resource("user", "seed")
+        .build()?;
+    WamiContext::builder()
+        .instance_id(SEED_INSTANCE_ID)
+        .tenant_path(TenantPath::single(SEED_TENANT_ID))
+        .caller_arn(caller_arn)
+        .is_root(false)
+        .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::traits::GroupStore;
+
+    #[tokio::test]
+    async fn test_seed_thousand_users() {
+        let mut store = InMemoryWamiStore::new();
+        store.seed(1000, 10, 50).unwrap();
+
+        assert_eq!(store.users.len(), 1000);
+        assert_eq!(store.groups.len(), 10);
+        assert_eq!(store.roles.len(), 50);
+        assert!(store.check_integrity().is_empty());
+
+        // Users are distributed evenly across the groups
+        let members = store
+            .user_groups
+            .values()
+            .flatten()
+            .filter(|group| *group == "seed-group-3")
+            .count();
+        assert_eq!(members, 100);
+
+        let groups = store.list_groups_for_user("seed-user-13").await.unwrap();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].group_name, "seed-group-3");
+    }
+
+    #[test]
+    fn test_seed_without_groups() {
+        let mut store = InMemoryWamiStore::new();
+        store.seed(10, 0, 0).unwrap();
+
+        assert_eq!(store.users.len(), 10);
+        assert!(store.user_groups.is_empty());
+        assert!(store.check_integrity().is_empty());
+    }
+
+    #[test]
+    fn test_seed_is_idempotent() {
+        let mut store = InMemoryWamiStore::new();
+        store.seed(20, 4, 2).unwrap();
+        store.seed(20, 4, 2).unwrap();
+
+        assert_eq!(store.users.len(), 20);
+        assert!(store.user_groups.values().all(|groups| groups.len() == 1));
+        assert!(store.check_integrity().is_empty());
+    }
+}
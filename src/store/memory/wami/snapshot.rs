@@ -0,0 +1,270 @@
+//! JSON Snapshots for the In-Memory WAMI Store
+//!
+//! Export the complete contents of an [`InMemoryWamiStore`] to a single
+//! versioned JSON document and load it back, for backups and test fixtures.
+
+#![allow(clippy::result_large_err)]
+
+use super::InMemoryWamiStore;
+use crate::error::{AmiError, Result};
+use serde::{Deserialize, Serialize};
+
+/// Current snapshot format version written by [`InMemoryWamiStore::export_snapshot`]
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// Snapshot document written by [`InMemoryWamiStore::export_snapshot`]
+#[derive(Serialize)]
+struct SnapshotRef<'a> {
+    version: u32,
+    store: &'a InMemoryWamiStore,
+}
+
+/// Snapshot document read by [`InMemoryWamiStore::import_snapshot`]
+#[derive(Deserialize)]
+struct Snapshot {
+    version: u32,
+    store: serde_json::Value,
+}
+
+impl InMemoryWamiStore {
+    /// Serialize every resource in the store to a versioned JSON document
+    ///
+    /// The document has the shape `{"version": 1, "store": {...}}` and can be
+    /// loaded with [`import_snapshot`](Self::import_snapshot).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use wami::store::memory::InMemoryWamiStore;
+    ///
+    /// let mut store = InMemoryWamiStore::new();
+    /// store.seed(10, 2, 1).unwrap();
+    ///
+    /// let json = store.export_snapshot().unwrap();
+    /// let mut restored = InMemoryWamiStore::new();
+    /// restored.import_snapshot(&json).unwrap();
+    ///
+    /// // Map ordering may differ, so compare the parsed documents
+    /// let original: serde_json::Value = serde_json::from_str(&json).unwrap();
+    /// let round_tripped: serde_json::Value =
+    ///     serde_json::from_str(&restored.export_snapshot().unwrap()).unwrap();
+    /// assert_eq!(original, round_tripped);
+    /// ```
+    pub fn export_snapshot(&self) -> Result<String> {
+        let snapshot = SnapshotRef {
+            version: SNAPSHOT_VERSION,
+            store: self,
+        };
+        Ok(serde_json::to_string(&snapshot)?)
+    }
+
+    /// Replace the contents of the store with a snapshot
+    ///
+    /// Resources missing from the snapshot (e.g. maps added in a later format)
+    /// are left empty. Snapshots written by a newer, unknown format version are
+    /// rejected. On error the store is left unchanged.
+    pub fn import_snapshot(&mut self, json: &str) -> Result<()> {
+        let snapshot: Snapshot = serde_json::from_str(json)?;
+
+        if snapshot.version == 0 || snapshot.version > SNAPSHOT_VERSION {
+            return Err(AmiError::InvalidParameter {
+                message: format!(
+                    "Unsupported snapshot version {} (supported: 1 to {})",
+                    snapshot.version, SNAPSHOT_VERSION
+                ),
+            });
+        }
+
+        // Older formats would be migrated here before deserializing
+        *self = serde_json::from_value(snapshot.store)?;
+        self.rebuild_usage();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arn::{TenantPath, WamiArn};
+    use crate::context::WamiContext;
+    use crate::provider::ProviderConfig;
+    use crate::store::traits::{
+        AccessKeyStore, GroupStore, IdentityProviderStore, PolicyStore, RoleStore, TenantStore,
+        UserStore,
+    };
+    use crate::types::Tag;
+    use crate::wami::credentials::access_key::builder::build_access_key;
+    use crate::wami::identity::group::builder::build_group;
+    use crate::wami::identity::identity_provider::builder::build_saml_provider;
+    use crate::wami::identity::role::builder::build_role;
+    use crate::wami::identity::user::builder::{add_provider_to_user, add_tags, build_user};
+    use crate::wami::policies::policy::builder::build_policy;
+    use crate::wami::sso_admin::instance::builder::build_instance;
+    use crate::wami::tenant::operations::tenant_operations::build_tenant;
+    use crate::wami::tenant::TenantId;
+
+    fn test_context() -> WamiContext {
+        let arn: WamiArn = "arn:wami:.*:12345678:wami:123456789012:user/test"
+            .parse()
+            .unwrap();
+        WamiContext::builder()
+            .instance_id("123456789012")
+            .tenant_path(TenantPath::single(12345678))
+            .caller_arn(arn)
+            .is_root(false)
+            .build()
+            .unwrap()
+    }
+
+    async fn populated_store() -> InMemoryWamiStore {
+        let context = test_context();
+        let mut store = InMemoryWamiStore::new();
+        let document = r#"{"Version":"2012-10-17","Statement":[]}"#;
+
+        let user = build_user("alice".to_string(), None, &context).unwrap();
+        let user = add_tags(
+            user,
+            vec![Tag {
+                key: "team".to_string(),
+                value: "platform".to_string(),
+            }],
+        );
+        let user = add_provider_to_user(
+            user,
+            ProviderConfig {
+                provider_name: "aws".to_string(),
+                account_id: "123456789012".to_string(),
+                native_arn: "arn:aws:iam::123456789012:user/alice".to_string(),
+                synced_at: chrono::Utc::now(),
+                tenant_id: Some("12345678".to_string()),
+            },
+        );
+        store.create_user(user).await.unwrap();
+        let key = build_access_key("alice".to_string(), &context).unwrap();
+        store.create_access_key(key).await.unwrap();
+
+        let group = build_group("admins".to_string(), None, &context).unwrap();
+        store.create_group(group).await.unwrap();
+        store.add_user_to_group("admins", "alice").await.unwrap();
+
+        let role = build_role(
+            "deployer".to_string(),
+            document.to_string(),
+            None,
+            None,
+            None,
+            &context,
+        )
+        .unwrap();
+        store.create_role(role).await.unwrap();
+
+        let policy = build_policy(
+            "ReadOnly".to_string(),
+            document.to_string(),
+            None,
+            None,
+            None,
+            &context,
+        )
+        .unwrap();
+        let policy_arn = policy.arn.clone();
+        store.create_policy(policy).await.unwrap();
+        store
+            .attach_user_policy("alice", &policy_arn)
+            .await
+            .unwrap();
+        store
+            .attach_group_policy("admins", &policy_arn)
+            .await
+            .unwrap();
+        store
+            .attach_role_policy("deployer", &policy_arn)
+            .await
+            .unwrap();
+        store
+            .put_user_policy("alice", "Inline", document.to_string())
+            .await
+            .unwrap();
+
+        let tenant_id = TenantId::from_string("12345678").unwrap();
+        let mut tenant = build_tenant(tenant_id, "acme".to_string(), None, None);
+        tenant
+            .provider_accounts
+            .insert("aws".to_string(), "123456789012".to_string());
+        store.create_tenant(tenant).await.unwrap();
+
+        let instance = build_instance(
+            "d-1234567890".to_string(),
+            "123456789012".to_string(),
+            &context,
+        )
+        .unwrap();
+        store
+            .sso_instances
+            .insert(instance.instance_arn.clone(), instance);
+
+        let saml = build_saml_provider(
+            "corp-idp".to_string(),
+            "<EntityDescriptor/>".to_string(),
+            &context,
+        )
+        .unwrap();
+        store.create_saml_provider(saml).await.unwrap();
+
+        store
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_round_trip() {
+        let store = populated_store().await;
+        let json = store.export_snapshot().unwrap();
+
+        let mut restored = InMemoryWamiStore::new();
+        restored.import_snapshot(&json).unwrap();
+
+        // Deep equality of every map, independent of hash map ordering
+        let original: serde_json::Value = serde_json::to_value(&store).unwrap();
+        let round_tripped: serde_json::Value = serde_json::to_value(&restored).unwrap();
+        assert_eq!(original, round_tripped);
+
+        let user = restored.get_user("alice").await.unwrap().unwrap();
+        assert_eq!(user.tags.len(), 1);
+        assert_eq!(user.providers.len(), 1);
+        assert_eq!(user.providers[0].tenant_id.as_deref(), Some("12345678"));
+        assert_eq!(
+            restored.list_groups_for_user("alice").await.unwrap().len(),
+            1
+        );
+        assert_eq!(restored.tenants.len(), 1);
+        assert_eq!(restored.sso_instances.len(), 1);
+        assert!(restored.check_integrity().is_empty());
+    }
+
+    #[test]
+    fn test_snapshot_has_version() {
+        let json = InMemoryWamiStore::new().export_snapshot().unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["version"], SNAPSHOT_VERSION);
+    }
+
+    #[test]
+    fn test_import_rejects_unknown_version() {
+        let mut store = InMemoryWamiStore::new();
+        store.seed(3, 1, 0).unwrap();
+
+        let result = store.import_snapshot(r#"{"version": 99, "store": {}}"#);
+        assert!(matches!(result, Err(AmiError::InvalidParameter { .. })));
+        assert_eq!(store.users.len(), 3);
+    }
+
+    #[test]
+    fn test_import_fills_missing_maps_with_defaults() {
+        let mut store = InMemoryWamiStore::new();
+        store.seed(3, 0, 0).unwrap();
+
+        store
+            .import_snapshot(r#"{"version": 1, "store": {}}"#)
+            .unwrap();
+        assert!(store.users.is_empty());
+    }
+}
@@ -0,0 +1,109 @@
+//! Live Tenant Usage Counters
+//!
+//! Per-tenant counts of users, groups, roles and policies, updated as those
+//! resources are stored and deleted so that tenant usage is read without
+//! scanning the resource maps.
+
+use super::InMemoryWamiStore;
+use crate::arn::{TenantPath, WamiArn};
+use crate::provider::ResourceType;
+use std::collections::HashMap;
+
+/// Resource counts by owning tenant and resource type
+#[derive(Debug, Clone, Default)]
+pub(crate) struct UsageCounters {
+    counts: HashMap<(TenantPath, ResourceType), usize>,
+}
+
+impl UsageCounters {
+    /// Number of resources of `resource_type` owned by `tenant_path`
+    pub(crate) fn get(&self, tenant_path: &TenantPath, resource_type: ResourceType) -> usize {
+        self.counts
+            .get(&(tenant_path.clone(), resource_type))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Account for a resource stored in place of `previous` (if any), or
+    /// removed when `current` is `None`
+    pub(crate) fn replace(
+        &mut self,
+        resource_type: ResourceType,
+        previous: Option<&WamiArn>,
+        current: Option<&WamiArn>,
+    ) {
+        if let Some(previous) = previous {
+            let key = (previous.tenant_path.clone(), resource_type);
+            if let Some(count) = self.counts.get_mut(&key) {
+                *count = count.saturating_sub(1);
+                if *count == 0 {
+                    self.counts.remove(&key);
+                }
+            }
+        }
+        if let Some(current) = current {
+            *self
+                .counts
+                .entry((current.tenant_path.clone(), resource_type))
+                .or_default() += 1;
+        }
+    }
+}
+
+impl InMemoryWamiStore {
+    /// Recount every tenant's resources from the resource maps
+    ///
+    /// Needed after the maps are replaced wholesale, as when importing a snapshot.
+    pub(crate) fn rebuild_usage(&mut self) {
+        let mut usage = UsageCounters::default();
+        let arns = self
+            .users
+            .values()
+            .map(|user| (ResourceType::User, &user.wami_arn))
+            .chain(
+                self.groups
+                    .values()
+                    .map(|group| (ResourceType::Group, &group.wami_arn)),
+            )
+            .chain(
+                self.roles
+                    .values()
+                    .map(|role| (ResourceType::Role, &role.wami_arn)),
+            )
+            .chain(
+                self.policies
+                    .values()
+                    .map(|policy| (ResourceType::Policy, &policy.wami_arn)),
+            );
+        for (resource_type, arn) in arns {
+            usage.replace(resource_type, None, Some(arn));
+        }
+        self.usage = usage;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts_never_go_negative() {
+        let arn: WamiArn = "arn:wami:iam:12345678:wami:123456789012:user/alice"
+            .parse()
+            .unwrap();
+        let tenant = TenantPath::single(12345678);
+        let mut usage = UsageCounters::default();
+
+        usage.replace(ResourceType::User, None, Some(&arn));
+        assert_eq!(usage.get(&tenant, ResourceType::User), 1);
+
+        // Replacing a resource keeps the count
+        usage.replace(ResourceType::User, Some(&arn), Some(&arn));
+        assert_eq!(usage.get(&tenant, ResourceType::User), 1);
+
+        usage.replace(ResourceType::User, Some(&arn), None);
+        usage.replace(ResourceType::User, Some(&arn), None);
+        assert_eq!(usage.get(&tenant, ResourceType::User), 0);
+        assert_eq!(usage.get(&tenant, ResourceType::Role), 0);
+    }
+}
@@ -2,13 +2,18 @@
 //!
 //! This module provides the storage abstraction layer for WAMI.
 //! It defines the traits for different store types (IAM, STS, SSO Admin, Tenant)
-//! and provides implementations (currently in-memory).
+//! and provides implementations (in-memory, plus Postgres with the `sql` feature).
 //!
 //! The store layer is a pure persistence layer with no provider coupling.
 //! Resources themselves carry their provider-specific information.
 
+#[cfg(feature = "concurrent")]
+pub mod concurrent;
 pub mod memory;
 pub mod resource;
+pub mod scoped;
+#[cfg(feature = "sql")]
+pub mod sql;
 pub mod traits;
 
 // Re-export traits for convenience
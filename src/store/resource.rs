@@ -47,6 +47,7 @@ use serde::{Deserialize, Serialize};
 ///     wami_arn: "arn:wami:iam:12345678:wami:123456789012:user/alice".parse().unwrap(),
 ///     providers: vec![],
 ///     tenant_id: None,
+///     expires_at: None,
 /// };
 ///
 /// let resource = Resource::User(user);
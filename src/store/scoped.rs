@@ -0,0 +1,597 @@
+//! Tenant-Scoped Store View
+//!
+//! [`TenantScopedStore`] wraps a store so that it only exposes the resources
+//! of one tenant and its descendants. Resources are matched on the tenant path
+//! of their WAMI ARN. Resources outside the scope behave as if they did not
+//! exist, and creating one is rejected with `AccessDenied`.
+//!
+//! The view implements the identity and policy sub-traits (`UserStore`,
+//! `GroupStore`, `RoleStore`, `PolicyStore`), so the services built on them
+//! work unchanged on a scoped store.
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::sync::{Arc, RwLock};
+//! use wami::store::memory::InMemoryWamiStore;
+//! use wami::store::scoped::TenantScopedStore;
+//! use wami::wami::tenant::TenantId;
+//! use wami::UserService;
+//!
+//! let tenant = TenantId::root();
+//! let scoped = TenantScopedStore::new(InMemoryWamiStore::default(), &tenant);
+//! let users = UserService::new(Arc::new(RwLock::new(scoped)));
+//! # let _ = users;
+//! ```
+
+use crate::arn::{TenantPath, WamiArn};
+use crate::error::{AmiError, Result};
+use crate::store::memory::pagination::paginate;
+use crate::store::traits::{GroupStore, PolicyStore, RoleStore, UserStore};
+use crate::types::{PaginationParams, Tag};
+use crate::wami::identity::{Group, Role, User};
+use crate::wami::policies::Policy;
+use crate::wami::tenant::TenantId;
+use async_trait::async_trait;
+
+/// A view of a store restricted to one tenant and its descendants
+#[derive(Debug, Clone)]
+pub struct TenantScopedStore<S> {
+    inner: S,
+    scope: TenantPath,
+}
+
+impl<S> TenantScopedStore<S> {
+    /// Restrict `inner` to the resources of `tenant_id` and its descendants
+    pub fn new(inner: S, tenant_id: &TenantId) -> Self {
+        Self {
+            inner,
+            scope: TenantPath::from_tenant_id(tenant_id),
+        }
+    }
+
+    /// The tenant path this view is restricted to
+    pub fn scope(&self) -> &TenantPath {
+        &self.scope
+    }
+
+    /// The unrestricted store behind this view
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Unwrap the view, returning the unrestricted store
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    fn in_scope(&self, arn: &WamiArn) -> bool {
+        arn.belongs_to_tenant(&self.scope)
+    }
+
+    /// Reject writes of resources whose ARN lies outside the scope
+    #[allow(clippy::result_large_err)]
+    fn check_writable(&self, arn: &WamiArn, resource: &str) -> Result<()> {
+        if self.in_scope(arn) {
+            Ok(())
+        } else {
+            Err(AmiError::AccessDenied {
+                message: format!(
+                    "{} belongs to tenant {}, outside the scope {}",
+                    resource, arn.tenant_path, self.scope
+                ),
+            })
+        }
+    }
+}
+
+fn not_found(resource: String) -> AmiError {
+    AmiError::ResourceNotFound { resource }
+}
+
+impl<S: UserStore> TenantScopedStore<S> {
+    async fn visible_user(&self, user_name: &str) -> Result<User> {
+        self.inner
+            .get_user(user_name)
+            .await?
+            .filter(|user| self.in_scope(&user.wami_arn))
+            .ok_or_else(|| not_found(format!("User: {}", user_name)))
+    }
+}
+
+impl<S: GroupStore> TenantScopedStore<S> {
+    async fn visible_group(&self, group_name: &str) -> Result<Group> {
+        self.inner
+            .get_group(group_name)
+            .await?
+            .filter(|group| self.in_scope(&group.wami_arn))
+            .ok_or_else(|| not_found(format!("Group: {}", group_name)))
+    }
+}
+
+impl<S: RoleStore> TenantScopedStore<S> {
+    async fn visible_role(&self, role_name: &str) -> Result<Role> {
+        self.inner
+            .get_role(role_name)
+            .await?
+            .filter(|role| self.in_scope(&role.wami_arn))
+            .ok_or_else(|| not_found(format!("Role: {}", role_name)))
+    }
+}
+
+impl<S: PolicyStore> TenantScopedStore<S> {
+    async fn visible_policy(&self, policy_arn: &str) -> Result<Policy> {
+        self.inner
+            .get_policy(policy_arn)
+            .await?
+            .filter(|policy| self.in_scope(&policy.wami_arn))
+            .ok_or_else(|| not_found(format!("Policy: {}", policy_arn)))
+    }
+}
+
+#[async_trait]
+impl<S: UserStore> UserStore for TenantScopedStore<S> {
+    async fn create_user(&mut self, user: User) -> Result<User> {
+        self.check_writable(&user.wami_arn, &format!("User {}", user.user_name))?;
+        self.inner.create_user(user).await
+    }
+
+    async fn get_user(&self, user_name: &str) -> Result<Option<User>> {
+        Ok(self
+            .inner
+            .get_user(user_name)
+            .await?
+            .filter(|user| self.in_scope(&user.wami_arn)))
+    }
+
+    async fn get_users(&self, user_names: &[String]) -> Result<Vec<Option<User>>> {
+        let users = self.inner.get_users(user_names).await?;
+        Ok(users
+            .into_iter()
+            .map(|user| user.filter(|user| self.in_scope(&user.wami_arn)))
+            .collect())
+    }
+
+    async fn update_user(&mut self, user: User) -> Result<User> {
+        self.visible_user(&user.user_name).await?;
+        self.check_writable(&user.wami_arn, &format!("User {}", user.user_name))?;
+        self.inner.update_user(user).await
+    }
+
+    async fn delete_user(&mut self, user_name: &str) -> Result<()> {
+        self.visible_user(user_name).await?;
+        self.inner.delete_user(user_name).await
+    }
+
+    async fn list_users(
+        &self,
+        path_prefix: Option<&str>,
+        pagination: Option<&PaginationParams>,
+    ) -> Result<(Vec<User>, bool, Option<String>)> {
+        let (mut users, _, _) = self.inner.list_users(path_prefix, None).await?;
+        users.retain(|user| self.in_scope(&user.wami_arn));
+        Ok(paginate(users, pagination, |user| &user.user_name))
+    }
+
+    async fn tag_user(&mut self, user_name: &str, tags: Vec<Tag>) -> Result<()> {
+        self.visible_user(user_name).await?;
+        self.inner.tag_user(user_name, tags).await
+    }
+
+    async fn list_user_tags(&self, user_name: &str) -> Result<Vec<Tag>> {
+        self.visible_user(user_name).await?;
+        self.inner.list_user_tags(user_name).await
+    }
+
+    async fn untag_user(&mut self, user_name: &str, tag_keys: Vec<String>) -> Result<()> {
+        self.visible_user(user_name).await?;
+        self.inner.untag_user(user_name, tag_keys).await
+    }
+
+    async fn attach_user_policy(&mut self, user_name: &str, policy_arn: &str) -> Result<()> {
+        self.visible_user(user_name).await?;
+        self.inner.attach_user_policy(user_name, policy_arn).await
+    }
+
+    async fn detach_user_policy(&mut self, user_name: &str, policy_arn: &str) -> Result<()> {
+        self.visible_user(user_name).await?;
+        self.inner.detach_user_policy(user_name, policy_arn).await
+    }
+
+    async fn list_attached_user_policies(&self, user_name: &str) -> Result<Vec<String>> {
+        self.visible_user(user_name).await?;
+        self.inner.list_attached_user_policies(user_name).await
+    }
+
+    async fn put_user_policy(
+        &mut self,
+        user_name: &str,
+        policy_name: &str,
+        policy_document: String,
+    ) -> Result<()> {
+        self.visible_user(user_name).await?;
+        self.inner
+            .put_user_policy(user_name, policy_name, policy_document)
+            .await
+    }
+
+    async fn get_user_policy(&self, user_name: &str, policy_name: &str) -> Result<Option<String>> {
+        self.visible_user(user_name).await?;
+        self.inner.get_user_policy(user_name, policy_name).await
+    }
+
+    async fn delete_user_policy(&mut self, user_name: &str, policy_name: &str) -> Result<()> {
+        self.visible_user(user_name).await?;
+        self.inner.delete_user_policy(user_name, policy_name).await
+    }
+
+    async fn list_user_policies(&self, user_name: &str) -> Result<Vec<String>> {
+        self.visible_user(user_name).await?;
+        self.inner.list_user_policies(user_name).await
+    }
+}
+
+#[async_trait]
+impl<S: GroupStore + UserStore> GroupStore for TenantScopedStore<S> {
+    async fn create_group(&mut self, group: Group) -> Result<Group> {
+        self.check_writable(&group.wami_arn, &format!("Group {}", group.group_name))?;
+        self.inner.create_group(group).await
+    }
+
+    async fn get_group(&self, group_name: &str) -> Result<Option<Group>> {
+        Ok(self
+            .inner
+            .get_group(group_name)
+            .await?
+            .filter(|group| self.in_scope(&group.wami_arn)))
+    }
+
+    async fn update_group(&mut self, group: Group) -> Result<Group> {
+        self.visible_group(&group.group_name).await?;
+        self.check_writable(&group.wami_arn, &format!("Group {}", group.group_name))?;
+        self.inner.update_group(group).await
+    }
+
+    async fn delete_group(&mut self, group_name: &str) -> Result<()> {
+        self.visible_group(group_name).await?;
+        self.inner.delete_group(group_name).await
+    }
+
+    async fn list_groups(
+        &self,
+        path_prefix: Option<&str>,
+        pagination: Option<&PaginationParams>,
+    ) -> Result<(Vec<Group>, bool, Option<String>)> {
+        let (mut groups, _, _) = self.inner.list_groups(path_prefix, None).await?;
+        groups.retain(|group| self.in_scope(&group.wami_arn));
+        Ok(paginate(groups, pagination, |group| &group.group_name))
+    }
+
+    async fn list_groups_for_user(&self, user_name: &str) -> Result<Vec<Group>> {
+        self.visible_user(user_name).await?;
+        let mut groups = self.inner.list_groups_for_user(user_name).await?;
+        groups.retain(|group| self.in_scope(&group.wami_arn));
+        Ok(groups)
+    }
+
+    async fn list_users_in_group(&self, group_name: &str) -> Result<Vec<User>> {
+        self.visible_group(group_name).await?;
+        let mut users = self.inner.list_users_in_group(group_name).await?;
+        users.retain(|user| self.in_scope(&user.wami_arn));
+        Ok(users)
+    }
+
+    async fn add_user_to_group(&mut self, group_name: &str, user_name: &str) -> Result<()> {
+        self.visible_group(group_name).await?;
+        self.visible_user(user_name).await?;
+        self.inner.add_user_to_group(group_name, user_name).await
+    }
+
+    async fn remove_user_from_group(&mut self, group_name: &str, user_name: &str) -> Result<()> {
+        self.visible_group(group_name).await?;
+        self.visible_user(user_name).await?;
+        self.inner
+            .remove_user_from_group(group_name, user_name)
+            .await
+    }
+
+    async fn attach_group_policy(&mut self, group_name: &str, policy_arn: &str) -> Result<()> {
+        self.visible_group(group_name).await?;
+        self.inner.attach_group_policy(group_name, policy_arn).await
+    }
+
+    async fn detach_group_policy(&mut self, group_name: &str, policy_arn: &str) -> Result<()> {
+        self.visible_group(group_name).await?;
+        self.inner.detach_group_policy(group_name, policy_arn).await
+    }
+
+    async fn list_attached_group_policies(&self, group_name: &str) -> Result<Vec<String>> {
+        self.visible_group(group_name).await?;
+        self.inner.list_attached_group_policies(group_name).await
+    }
+
+    async fn put_group_policy(
+        &mut self,
+        group_name: &str,
+        policy_name: &str,
+        policy_document: String,
+    ) -> Result<()> {
+        self.visible_group(group_name).await?;
+        self.inner
+            .put_group_policy(group_name, policy_name, policy_document)
+            .await
+    }
+
+    async fn get_group_policy(
+        &self,
+        group_name: &str,
+        policy_name: &str,
+    ) -> Result<Option<String>> {
+        self.visible_group(group_name).await?;
+        self.inner.get_group_policy(group_name, policy_name).await
+    }
+
+    async fn delete_group_policy(&mut self, group_name: &str, policy_name: &str) -> Result<()> {
+        self.visible_group(group_name).await?;
+        self.inner
+            .delete_group_policy(group_name, policy_name)
+            .await
+    }
+
+    async fn list_group_policies(&self, group_name: &str) -> Result<Vec<String>> {
+        self.visible_group(group_name).await?;
+        self.inner.list_group_policies(group_name).await
+    }
+}
+
+#[async_trait]
+impl<S: RoleStore> RoleStore for TenantScopedStore<S> {
+    async fn create_role(&mut self, role: Role) -> Result<Role> {
+        self.check_writable(&role.wami_arn, &format!("Role {}", role.role_name))?;
+        self.inner.create_role(role).await
+    }
+
+    async fn get_role(&self, role_name: &str) -> Result<Option<Role>> {
+        Ok(self
+            .inner
+            .get_role(role_name)
+            .await?
+            .filter(|role| self.in_scope(&role.wami_arn)))
+    }
+
+    async fn get_roles(&self, role_names: &[String]) -> Result<Vec<Option<Role>>> {
+        let roles = self.inner.get_roles(role_names).await?;
+        Ok(roles
+            .into_iter()
+            .map(|role| role.filter(|role| self.in_scope(&role.wami_arn)))
+            .collect())
+    }
+
+    async fn update_role(&mut self, role: Role) -> Result<Role> {
+        self.visible_role(&role.role_name).await?;
+        self.check_writable(&role.wami_arn, &format!("Role {}", role.role_name))?;
+        self.inner.update_role(role).await
+    }
+
+    async fn delete_role(&mut self, role_name: &str) -> Result<()> {
+        self.visible_role(role_name).await?;
+        self.inner.delete_role(role_name).await
+    }
+
+    async fn list_roles(
+        &self,
+        path_prefix: Option<&str>,
+        pagination: Option<&PaginationParams>,
+    ) -> Result<(Vec<Role>, bool, Option<String>)> {
+        let (mut roles, _, _) = self.inner.list_roles(path_prefix, None).await?;
+        roles.retain(|role| self.in_scope(&role.wami_arn));
+        Ok(paginate(roles, pagination, |role| &role.role_name))
+    }
+
+    async fn attach_role_policy(&mut self, role_name: &str, policy_arn: &str) -> Result<()> {
+        self.visible_role(role_name).await?;
+        self.inner.attach_role_policy(role_name, policy_arn).await
+    }
+
+    async fn detach_role_policy(&mut self, role_name: &str, policy_arn: &str) -> Result<()> {
+        self.visible_role(role_name).await?;
+        self.inner.detach_role_policy(role_name, policy_arn).await
+    }
+
+    async fn list_attached_role_policies(&self, role_name: &str) -> Result<Vec<String>> {
+        self.visible_role(role_name).await?;
+        self.inner.list_attached_role_policies(role_name).await
+    }
+
+    async fn put_role_policy(
+        &mut self,
+        role_name: &str,
+        policy_name: &str,
+        policy_document: String,
+    ) -> Result<()> {
+        self.visible_role(role_name).await?;
+        self.inner
+            .put_role_policy(role_name, policy_name, policy_document)
+            .await
+    }
+
+    async fn get_role_policy(&self, role_name: &str, policy_name: &str) -> Result<Option<String>> {
+        self.visible_role(role_name).await?;
+        self.inner.get_role_policy(role_name, policy_name).await
+    }
+
+    async fn delete_role_policy(&mut self, role_name: &str, policy_name: &str) -> Result<()> {
+        self.visible_role(role_name).await?;
+        self.inner.delete_role_policy(role_name, policy_name).await
+    }
+
+    async fn list_role_policies(&self, role_name: &str) -> Result<Vec<String>> {
+        self.visible_role(role_name).await?;
+        self.inner.list_role_policies(role_name).await
+    }
+}
+
+#[async_trait]
+impl<S: PolicyStore> PolicyStore for TenantScopedStore<S> {
+    async fn create_policy(&mut self, policy: Policy) -> Result<Policy> {
+        self.check_writable(&policy.wami_arn, &format!("Policy {}", policy.arn))?;
+        self.inner.create_policy(policy).await
+    }
+
+    async fn get_policy(&self, policy_arn: &str) -> Result<Option<Policy>> {
+        Ok(self
+            .inner
+            .get_policy(policy_arn)
+            .await?
+            .filter(|policy| self.in_scope(&policy.wami_arn)))
+    }
+
+    async fn update_policy(&mut self, policy: Policy) -> Result<Policy> {
+        self.visible_policy(&policy.arn).await?;
+        self.check_writable(&policy.wami_arn, &format!("Policy {}", policy.arn))?;
+        self.inner.update_policy(policy).await
+    }
+
+    async fn delete_policy(&mut self, policy_arn: &str) -> Result<()> {
+        self.visible_policy(policy_arn).await?;
+        self.inner.delete_policy(policy_arn).await
+    }
+
+    async fn list_policies(
+        &self,
+        scope: Option<&str>,
+        pagination: Option<&PaginationParams>,
+    ) -> Result<(Vec<Policy>, bool, Option<String>)> {
+        let (mut policies, _, _) = self.inner.list_policies(scope, None).await?;
+        policies.retain(|policy| self.in_scope(&policy.wami_arn));
+        Ok(paginate(policies, pagination, |policy| &policy.arn))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::WamiContext;
+    use crate::service::{GroupService, UserService};
+    use crate::store::memory::InMemoryWamiStore;
+    use crate::wami::identity::group::CreateGroupRequest;
+    use crate::wami::identity::user::CreateUserRequest;
+    use std::sync::{Arc, RwLock};
+
+    fn context(tenant: &TenantId) -> WamiContext {
+        let tenant_path = TenantPath::from_tenant_id(tenant);
+        let arn: WamiArn = format!("arn:wami:.*:{}:wami:123456789012:user/admin", tenant_path)
+            .parse()
+            .unwrap();
+        WamiContext::builder()
+            .instance_id("123456789012")
+            .tenant_path(tenant_path)
+            .caller_arn(arn)
+            .is_root(false)
+            .build()
+            .unwrap()
+    }
+
+    fn create_request(user_name: &str) -> CreateUserRequest {
+        CreateUserRequest {
+            user_name: user_name.to_string(),
+            path: None,
+            permissions_boundary: None,
+            tags: None,
+        }
+    }
+
+    /// A store holding `bob` in tenant B, scoped to tenant A
+    async fn scoped_to_a(
+        tenant_a: &TenantId,
+        tenant_b: &TenantId,
+    ) -> Arc<RwLock<TenantScopedStore<InMemoryWamiStore>>> {
+        let shared = Arc::new(RwLock::new(InMemoryWamiStore::default()));
+        UserService::new(shared.clone())
+            .create_user(&context(tenant_b), create_request("bob"))
+            .await
+            .unwrap();
+        let inner = shared.read().unwrap().clone();
+        Arc::new(RwLock::new(TenantScopedStore::new(inner, tenant_a)))
+    }
+
+    #[tokio::test]
+    async fn test_scoped_view_hides_other_tenants() {
+        let (tenant_a, tenant_b) = (TenantId::root(), TenantId::root());
+        let store = scoped_to_a(&tenant_a, &tenant_b).await;
+        let users = UserService::new(store.clone());
+
+        users
+            .create_user(&context(&tenant_a), create_request("alice"))
+            .await
+            .unwrap();
+        users
+            .create_user(&context(&tenant_a.child()), create_request("carol"))
+            .await
+            .unwrap();
+
+        assert!(users.get_user("bob").await.unwrap().is_none());
+        let (listed, _, _) = users
+            .list_users(
+                &context(&tenant_a),
+                crate::wami::identity::user::ListUsersRequest {
+                    path_prefix: None,
+                    pagination: None,
+                    include_descendants: true,
+                },
+            )
+            .await
+            .unwrap();
+        let names: Vec<_> = listed.iter().map(|u| u.user_name.as_str()).collect();
+        assert_eq!(names, ["alice", "carol"]);
+
+        let scoped = store.read().unwrap().clone();
+        let batch = scoped
+            .get_users(&["alice".to_string(), "bob".to_string()])
+            .await
+            .unwrap();
+        assert!(batch[0].is_some() && batch[1].is_none());
+
+        let inner = store.read().unwrap().inner().clone();
+        assert!(inner.get_user("bob").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_scoped_view_cannot_mutate_other_tenants() {
+        let (tenant_a, tenant_b) = (TenantId::root(), TenantId::root());
+        let store = scoped_to_a(&tenant_a, &tenant_b).await;
+        let users = UserService::new(store.clone());
+
+        let created = users
+            .create_user(&context(&tenant_b), create_request("mallory"))
+            .await;
+        assert!(matches!(created, Err(AmiError::AccessDenied { .. })));
+
+        let deleted = users.delete_user("bob").await;
+        assert!(matches!(deleted, Err(AmiError::ResourceNotFound { .. })));
+
+        let groups = GroupService::new(store.clone());
+        groups
+            .create_group(
+                &context(&tenant_a),
+                CreateGroupRequest {
+                    group_name: "admins".to_string(),
+                    path: None,
+                    tags: None,
+                },
+            )
+            .await
+            .unwrap();
+        let added = groups.add_user_to_group("admins", "bob").await;
+        assert!(matches!(added, Err(AmiError::ResourceNotFound { .. })));
+
+        let inner = store.read().unwrap().inner().clone();
+        assert!(inner.get_user("bob").await.unwrap().is_some());
+        assert!(inner.get_user("mallory").await.unwrap().is_none());
+        assert!(inner
+            .list_users_in_group("admins")
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
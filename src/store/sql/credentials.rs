@@ -0,0 +1,377 @@
+//! Credential Store Implementations for SqlWamiStore
+//!
+//! Access keys, MFA devices, login profiles, password policies, server certificates,
+//! service-specific credentials and signing certificates.
+
+use super::{store_error, tables, Record, SqlWamiStore};
+use crate::arn::TenantPath;
+use crate::error::{AmiError, Result};
+use crate::store::traits::{
+    AccessKeyStore, LoginProfileStore, MfaDeviceStore, PasswordPolicyStore, ServerCertificateStore,
+    ServiceCredentialStore, SigningCertificateStore,
+};
+use crate::types::PaginationParams;
+use crate::wami::credentials::access_key::{AccessKey, AccessKeyLastUsed};
+use crate::wami::credentials::login_profile::LoginProfile;
+use crate::wami::credentials::mfa_device::MfaDevice;
+use crate::wami::credentials::password_policy::PasswordPolicy;
+use crate::wami::credentials::server_certificate::{ServerCertificate, ServerCertificateMetadata};
+use crate::wami::credentials::service_credential::ServiceSpecificCredential;
+use crate::wami::credentials::signing_certificate::SigningCertificate;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::types::Json;
+
+#[async_trait]
+impl AccessKeyStore for SqlWamiStore {
+    async fn create_access_key(&mut self, access_key: AccessKey) -> Result<AccessKey> {
+        self.update_access_key(access_key).await
+    }
+
+    async fn get_access_key(&self, access_key_id: &str) -> Result<Option<AccessKey>> {
+        self.fetch(tables::ACCESS_KEYS, access_key_id).await
+    }
+
+    async fn update_access_key(&mut self, access_key: AccessKey) -> Result<AccessKey> {
+        let record = Record {
+            wami_arn: &access_key.wami_arn,
+            name: &access_key.access_key_id,
+            user_name: Some(&access_key.user_name),
+            path: None,
+        };
+        self.upsert(tables::ACCESS_KEYS, record, &access_key)
+            .await?;
+        Ok(access_key)
+    }
+
+    async fn delete_access_key(&mut self, access_key_id: &str) -> Result<()> {
+        self.remove(tables::ACCESS_KEYS, access_key_id).await
+    }
+
+    async fn list_access_keys(
+        &self,
+        user_name: &str,
+        pagination: Option<&PaginationParams>,
+    ) -> Result<(Vec<AccessKey>, bool, Option<String>)> {
+        let mut access_keys: Vec<AccessKey> = self
+            .fetch_by_user(tables::ACCESS_KEYS, Some(user_name))
+            .await?;
+
+        let mut is_truncated = false;
+        let mut marker = None;
+
+        if let Some(max_items) = pagination.and_then(|p| p.max_items) {
+            if access_keys.len() > max_items as usize {
+                access_keys.truncate(max_items as usize);
+                is_truncated = true;
+                marker = access_keys.last().map(|key| key.access_key_id.clone());
+            }
+        }
+
+        Ok((access_keys, is_truncated, marker))
+    }
+
+    async fn list_all_access_keys(&self) -> Result<Vec<AccessKey>> {
+        self.fetch_by_user(tables::ACCESS_KEYS, None).await
+    }
+
+    async fn update_access_key_last_used(
+        &mut self,
+        access_key_id: &str,
+        service_name: &str,
+        region: Option<&str>,
+        timestamp: DateTime<Utc>,
+    ) -> Result<()> {
+        let mut access_key = self.get_access_key(access_key_id).await?.ok_or_else(|| {
+            AmiError::ResourceNotFound {
+                resource: format!("AccessKey {}", access_key_id),
+            }
+        })?;
+
+        access_key.last_used = Some(AccessKeyLastUsed {
+            last_used_date: Some(timestamp),
+            region: region.map(str::to_string),
+            service_name: Some(service_name.to_string()),
+        });
+        self.update_access_key(access_key).await?;
+        Ok(())
+    }
+
+    async fn get_access_key_last_used(&self, access_key_id: &str) -> Result<AccessKeyLastUsed> {
+        let access_key = self.get_access_key(access_key_id).await?.ok_or_else(|| {
+            AmiError::ResourceNotFound {
+                resource: format!("AccessKey {}", access_key_id),
+            }
+        })?;
+
+        Ok(access_key.last_used.unwrap_or_default())
+    }
+}
+
+#[async_trait]
+impl MfaDeviceStore for SqlWamiStore {
+    async fn create_mfa_device(&mut self, device: MfaDevice) -> Result<MfaDevice> {
+        let record = Record {
+            wami_arn: &device.wami_arn,
+            name: &device.serial_number,
+            user_name: Some(&device.user_name),
+            path: None,
+        };
+        self.upsert(tables::MFA_DEVICES, record, &device).await?;
+        Ok(device)
+    }
+
+    async fn get_mfa_device(&self, serial_number: &str) -> Result<Option<MfaDevice>> {
+        self.fetch(tables::MFA_DEVICES, serial_number).await
+    }
+
+    async fn update_mfa_device(&mut self, device: MfaDevice) -> Result<MfaDevice> {
+        self.create_mfa_device(device).await
+    }
+
+    async fn delete_mfa_device(&mut self, serial_number: &str) -> Result<()> {
+        self.remove(tables::MFA_DEVICES, serial_number).await
+    }
+
+    async fn list_mfa_devices(&self, user_name: &str) -> Result<Vec<MfaDevice>> {
+        self.fetch_by_user(tables::MFA_DEVICES, Some(user_name))
+            .await
+    }
+}
+
+#[async_trait]
+impl LoginProfileStore for SqlWamiStore {
+    async fn create_login_profile(&mut self, profile: LoginProfile) -> Result<LoginProfile> {
+        self.update_login_profile(profile).await
+    }
+
+    async fn get_login_profile(&self, user_name: &str) -> Result<Option<LoginProfile>> {
+        self.fetch(tables::LOGIN_PROFILES, user_name).await
+    }
+
+    async fn update_login_profile(&mut self, profile: LoginProfile) -> Result<LoginProfile> {
+        let record = Record {
+            wami_arn: &profile.wami_arn,
+            name: &profile.user_name,
+            user_name: Some(&profile.user_name),
+            path: None,
+        };
+        self.upsert(tables::LOGIN_PROFILES, record, &profile)
+            .await?;
+        Ok(profile)
+    }
+
+    async fn delete_login_profile(&mut self, user_name: &str) -> Result<()> {
+        self.remove(tables::LOGIN_PROFILES, user_name).await
+    }
+}
+
+#[async_trait]
+impl PasswordPolicyStore for SqlWamiStore {
+    async fn get_password_policy(
+        &self,
+        tenant_path: &TenantPath,
+    ) -> Result<Option<PasswordPolicy>> {
+        let row: Option<Json<PasswordPolicy>> =
+            sqlx::query_scalar("SELECT data FROM wami_password_policies WHERE tenant_id = $1")
+                .bind(tenant_path.as_string())
+                .fetch_optional(self.pool())
+                .await
+                .map_err(store_error)?;
+        Ok(row.map(|Json(policy)| policy))
+    }
+
+    async fn put_password_policy(
+        &mut self,
+        tenant_path: &TenantPath,
+        policy: PasswordPolicy,
+    ) -> Result<PasswordPolicy> {
+        sqlx::query(
+            "INSERT INTO wami_password_policies (tenant_id, data) VALUES ($1, $2) \
+             ON CONFLICT (tenant_id) DO UPDATE SET data = EXCLUDED.data",
+        )
+        .bind(tenant_path.as_string())
+        .bind(Json(&policy))
+        .execute(self.pool())
+        .await
+        .map_err(store_error)?;
+        Ok(policy)
+    }
+
+    async fn delete_password_policy(&mut self, tenant_path: &TenantPath) -> Result<()> {
+        sqlx::query("DELETE FROM wami_password_policies WHERE tenant_id = $1")
+            .bind(tenant_path.as_string())
+            .execute(self.pool())
+            .await
+            .map_err(store_error)?;
+        Ok(())
+    }
+}
+
+impl SqlWamiStore {
+    async fn put_server_certificate(&self, certificate: &ServerCertificate) -> Result<()> {
+        let metadata = &certificate.server_certificate_metadata;
+        let record = Record {
+            wami_arn: &certificate.wami_arn,
+            name: &metadata.server_certificate_name,
+            user_name: None,
+            path: Some(&metadata.path),
+        };
+        self.upsert(tables::SERVER_CERTIFICATES, record, certificate)
+            .await
+    }
+}
+
+#[async_trait]
+impl ServerCertificateStore for SqlWamiStore {
+    async fn create_server_certificate(
+        &mut self,
+        certificate: ServerCertificate,
+    ) -> Result<ServerCertificateMetadata> {
+        self.put_server_certificate(&certificate).await?;
+        Ok(certificate.server_certificate_metadata)
+    }
+
+    async fn get_server_certificate(
+        &self,
+        certificate_name: &str,
+    ) -> Result<Option<ServerCertificateMetadata>> {
+        let certificate: Option<ServerCertificate> = self
+            .fetch(tables::SERVER_CERTIFICATES, certificate_name)
+            .await?;
+        Ok(certificate.map(|cert| cert.server_certificate_metadata))
+    }
+
+    async fn update_server_certificate(
+        &mut self,
+        certificate: ServerCertificateMetadata,
+    ) -> Result<ServerCertificateMetadata> {
+        let existing: Option<ServerCertificate> = self
+            .fetch(
+                tables::SERVER_CERTIFICATES,
+                &certificate.server_certificate_name,
+            )
+            .await?;
+        let mut existing = existing.ok_or_else(|| AmiError::ResourceNotFound {
+            resource: format!("Server certificate {}", certificate.server_certificate_name),
+        })?;
+
+        existing.server_certificate_metadata = certificate.clone();
+        self.put_server_certificate(&existing).await?;
+        Ok(certificate)
+    }
+
+    async fn delete_server_certificate(&mut self, certificate_name: &str) -> Result<()> {
+        self.remove(tables::SERVER_CERTIFICATES, certificate_name)
+            .await
+    }
+
+    async fn list_server_certificates(
+        &self,
+        path_prefix: Option<&str>,
+        pagination: Option<&PaginationParams>,
+    ) -> Result<(Vec<ServerCertificateMetadata>, bool, Option<String>)> {
+        let (certificates, is_truncated, marker): (Vec<ServerCertificate>, _, _) = self
+            .fetch_page(tables::SERVER_CERTIFICATES, path_prefix, pagination)
+            .await?;
+        let metadata = certificates
+            .into_iter()
+            .map(|cert| cert.server_certificate_metadata)
+            .collect();
+        Ok((metadata, is_truncated, marker))
+    }
+}
+
+#[async_trait]
+impl ServiceCredentialStore for SqlWamiStore {
+    async fn create_service_specific_credential(
+        &mut self,
+        credential: ServiceSpecificCredential,
+    ) -> Result<ServiceSpecificCredential> {
+        self.update_service_specific_credential(credential).await
+    }
+
+    async fn get_service_specific_credential(
+        &self,
+        credential_id: &str,
+    ) -> Result<Option<ServiceSpecificCredential>> {
+        self.fetch(tables::SERVICE_CREDENTIALS, credential_id).await
+    }
+
+    async fn update_service_specific_credential(
+        &mut self,
+        credential: ServiceSpecificCredential,
+    ) -> Result<ServiceSpecificCredential> {
+        let record = Record {
+            wami_arn: &credential.wami_arn,
+            name: &credential.service_specific_credential_id,
+            user_name: Some(&credential.user_name),
+            path: None,
+        };
+        self.upsert(tables::SERVICE_CREDENTIALS, record, &credential)
+            .await?;
+        Ok(credential)
+    }
+
+    async fn delete_service_specific_credential(
+        &mut self,
+        service_specific_credential_id: &str,
+    ) -> Result<()> {
+        self.remove(tables::SERVICE_CREDENTIALS, service_specific_credential_id)
+            .await
+    }
+
+    async fn list_service_specific_credentials(
+        &self,
+        user_name: &str,
+    ) -> Result<Vec<ServiceSpecificCredential>> {
+        self.fetch_by_user(tables::SERVICE_CREDENTIALS, Some(user_name))
+            .await
+    }
+}
+
+#[async_trait]
+impl SigningCertificateStore for SqlWamiStore {
+    async fn create_signing_certificate(
+        &mut self,
+        certificate: SigningCertificate,
+    ) -> Result<SigningCertificate> {
+        self.update_signing_certificate(certificate).await
+    }
+
+    async fn get_signing_certificate(
+        &self,
+        certificate_id: &str,
+    ) -> Result<Option<SigningCertificate>> {
+        self.fetch(tables::SIGNING_CERTIFICATES, certificate_id)
+            .await
+    }
+
+    async fn update_signing_certificate(
+        &mut self,
+        certificate: SigningCertificate,
+    ) -> Result<SigningCertificate> {
+        let record = Record {
+            wami_arn: &certificate.wami_arn,
+            name: &certificate.certificate_id,
+            user_name: Some(&certificate.user_name),
+            path: None,
+        };
+        self.upsert(tables::SIGNING_CERTIFICATES, record, &certificate)
+            .await?;
+        Ok(certificate)
+    }
+
+    async fn delete_signing_certificate(&mut self, certificate_id: &str) -> Result<()> {
+        self.remove(tables::SIGNING_CERTIFICATES, certificate_id)
+            .await
+    }
+
+    async fn list_signing_certificates(
+        &self,
+        user_name: Option<&str>,
+    ) -> Result<Vec<SigningCertificate>> {
+        self.fetch_by_user(tables::SIGNING_CERTIFICATES, user_name)
+            .await
+    }
+}
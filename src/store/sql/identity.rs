@@ -0,0 +1,352 @@
+//! User, Group and Role Store Implementations for SqlWamiStore
+
+use super::{store_error, tables, Record, SqlWamiStore};
+use crate::error::{AmiError, Result};
+use crate::store::traits::{GroupStore, RoleStore, UserStore};
+use crate::types::{PaginationParams, Tag};
+use crate::wami::identity::{Group, Role, User};
+use async_trait::async_trait;
+use sqlx::types::Json;
+
+#[async_trait]
+impl UserStore for SqlWamiStore {
+    async fn create_user(&mut self, user: User) -> Result<User> {
+        self.update_user(user).await
+    }
+
+    async fn get_user(&self, user_name: &str) -> Result<Option<User>> {
+        self.fetch(tables::USERS, user_name).await
+    }
+
+    async fn get_users(&self, user_names: &[String]) -> Result<Vec<Option<User>>> {
+        self.fetch_many(tables::USERS, user_names).await
+    }
+
+    async fn update_user(&mut self, user: User) -> Result<User> {
+        let record = Record {
+            wami_arn: &user.wami_arn,
+            name: &user.user_name,
+            user_name: Some(&user.user_name),
+            path: Some(&user.path),
+        };
+        self.upsert(tables::USERS, record, &user).await?;
+        Ok(user)
+    }
+
+    async fn delete_user(&mut self, user_name: &str) -> Result<()> {
+        let mut tx = self.pool.begin().await.map_err(store_error)?;
+        for statement in [
+            "DELETE FROM wami_users WHERE name = $1",
+            // Also remove associated access keys and group memberships
+            "DELETE FROM wami_access_keys WHERE user_name = $1",
+            "DELETE FROM wami_group_members WHERE user_name = $1",
+        ] {
+            sqlx::query(statement)
+                .bind(user_name)
+                .execute(&mut *tx)
+                .await
+                .map_err(store_error)?;
+        }
+        tx.commit().await.map_err(store_error)
+    }
+
+    async fn list_users(
+        &self,
+        path_prefix: Option<&str>,
+        pagination: Option<&PaginationParams>,
+    ) -> Result<(Vec<User>, bool, Option<String>)> {
+        self.fetch_page(tables::USERS, path_prefix, pagination)
+            .await
+    }
+
+    async fn tag_user(&mut self, user_name: &str, tags: Vec<Tag>) -> Result<()> {
+        if let Some(mut user) = self.get_user(user_name).await? {
+            user.tags.extend(tags);
+            self.update_user(user).await?;
+        }
+        Ok(())
+    }
+
+    async fn list_user_tags(&self, user_name: &str) -> Result<Vec<Tag>> {
+        Ok(self
+            .get_user(user_name)
+            .await?
+            .map(|u| u.tags)
+            .unwrap_or_default())
+    }
+
+    async fn untag_user(&mut self, user_name: &str, tag_keys: Vec<String>) -> Result<()> {
+        if let Some(mut user) = self.get_user(user_name).await? {
+            user.tags.retain(|tag| !tag_keys.contains(&tag.key));
+            self.update_user(user).await?;
+        }
+        Ok(())
+    }
+
+    // Managed policy attachment methods
+    async fn attach_user_policy(&mut self, user_name: &str, policy_arn: &str) -> Result<()> {
+        self.attach_policy("user", user_name, policy_arn).await
+    }
+
+    async fn detach_user_policy(&mut self, user_name: &str, policy_arn: &str) -> Result<()> {
+        self.detach_policy("user", user_name, policy_arn).await
+    }
+
+    async fn list_attached_user_policies(&self, user_name: &str) -> Result<Vec<String>> {
+        self.attached_policies("user", user_name).await
+    }
+
+    // Inline policy methods
+    async fn put_user_policy(
+        &mut self,
+        user_name: &str,
+        policy_name: &str,
+        policy_document: String,
+    ) -> Result<()> {
+        self.put_inline_policy("user", user_name, policy_name, policy_document)
+            .await
+    }
+
+    async fn get_user_policy(&self, user_name: &str, policy_name: &str) -> Result<Option<String>> {
+        self.inline_policy("user", user_name, policy_name).await
+    }
+
+    async fn delete_user_policy(&mut self, user_name: &str, policy_name: &str) -> Result<()> {
+        self.delete_inline_policy("user", user_name, policy_name)
+            .await
+    }
+
+    async fn list_user_policies(&self, user_name: &str) -> Result<Vec<String>> {
+        self.inline_policy_names("user", user_name).await
+    }
+}
+
+#[async_trait]
+impl GroupStore for SqlWamiStore {
+    async fn create_group(&mut self, group: Group) -> Result<Group> {
+        self.update_group(group).await
+    }
+
+    async fn get_group(&self, group_name: &str) -> Result<Option<Group>> {
+        self.fetch(tables::GROUPS, group_name).await
+    }
+
+    async fn update_group(&mut self, group: Group) -> Result<Group> {
+        let record = Record {
+            wami_arn: &group.wami_arn,
+            name: &group.group_name,
+            user_name: None,
+            path: Some(&group.path),
+        };
+        self.upsert(tables::GROUPS, record, &group).await?;
+        Ok(group)
+    }
+
+    async fn delete_group(&mut self, group_name: &str) -> Result<()> {
+        let mut tx = self.pool.begin().await.map_err(store_error)?;
+        for statement in [
+            "DELETE FROM wami_groups WHERE name = $1",
+            // Remove from all user-group mappings
+            "DELETE FROM wami_group_members WHERE group_name = $1",
+        ] {
+            sqlx::query(statement)
+                .bind(group_name)
+                .execute(&mut *tx)
+                .await
+                .map_err(store_error)?;
+        }
+        tx.commit().await.map_err(store_error)
+    }
+
+    async fn list_groups(
+        &self,
+        path_prefix: Option<&str>,
+        pagination: Option<&PaginationParams>,
+    ) -> Result<(Vec<Group>, bool, Option<String>)> {
+        self.fetch_page(tables::GROUPS, path_prefix, pagination)
+            .await
+    }
+
+    async fn list_groups_for_user(&self, user_name: &str) -> Result<Vec<Group>> {
+        let groups: Vec<Json<Group>> = sqlx::query_scalar(
+            "SELECT g.data FROM wami_groups g \
+             JOIN wami_group_members m ON m.group_name = g.name \
+             WHERE m.user_name = $1 ORDER BY g.name",
+        )
+        .bind(user_name)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(store_error)?;
+        Ok(groups.into_iter().map(|Json(group)| group).collect())
+    }
+
+    async fn list_users_in_group(&self, group_name: &str) -> Result<Vec<User>> {
+        if self.get_group(group_name).await?.is_none() {
+            return Err(AmiError::ResourceNotFound {
+                resource: format!("Group: {}", group_name),
+            });
+        }
+
+        let users: Vec<Json<User>> = sqlx::query_scalar(
+            "SELECT u.data FROM wami_users u \
+             JOIN wami_group_members m ON m.user_name = u.name \
+             WHERE m.group_name = $1 ORDER BY u.name",
+        )
+        .bind(group_name)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(store_error)?;
+        Ok(users.into_iter().map(|Json(user)| user).collect())
+    }
+
+    async fn add_user_to_group(&mut self, group_name: &str, user_name: &str) -> Result<()> {
+        if self.get_group(group_name).await?.is_none() {
+            return Err(AmiError::ResourceNotFound {
+                resource: format!("Group: {}", group_name),
+            });
+        }
+        if self.get_user(user_name).await?.is_none() {
+            return Err(AmiError::ResourceNotFound {
+                resource: format!("User: {}", user_name),
+            });
+        }
+
+        sqlx::query(
+            "INSERT INTO wami_group_members (group_name, user_name) VALUES ($1, $2) \
+             ON CONFLICT DO NOTHING",
+        )
+        .bind(group_name)
+        .bind(user_name)
+        .execute(&self.pool)
+        .await
+        .map_err(store_error)?;
+        Ok(())
+    }
+
+    async fn remove_user_from_group(&mut self, group_name: &str, user_name: &str) -> Result<()> {
+        sqlx::query("DELETE FROM wami_group_members WHERE group_name = $1 AND user_name = $2")
+            .bind(group_name)
+            .bind(user_name)
+            .execute(&self.pool)
+            .await
+            .map_err(store_error)?;
+        Ok(())
+    }
+
+    // Managed policy attachment methods
+    async fn attach_group_policy(&mut self, group_name: &str, policy_arn: &str) -> Result<()> {
+        self.attach_policy("group", group_name, policy_arn).await
+    }
+
+    async fn detach_group_policy(&mut self, group_name: &str, policy_arn: &str) -> Result<()> {
+        self.detach_policy("group", group_name, policy_arn).await
+    }
+
+    async fn list_attached_group_policies(&self, group_name: &str) -> Result<Vec<String>> {
+        self.attached_policies("group", group_name).await
+    }
+
+    // Inline policy methods
+    async fn put_group_policy(
+        &mut self,
+        group_name: &str,
+        policy_name: &str,
+        policy_document: String,
+    ) -> Result<()> {
+        self.put_inline_policy("group", group_name, policy_name, policy_document)
+            .await
+    }
+
+    async fn get_group_policy(
+        &self,
+        group_name: &str,
+        policy_name: &str,
+    ) -> Result<Option<String>> {
+        self.inline_policy("group", group_name, policy_name).await
+    }
+
+    async fn delete_group_policy(&mut self, group_name: &str, policy_name: &str) -> Result<()> {
+        self.delete_inline_policy("group", group_name, policy_name)
+            .await
+    }
+
+    async fn list_group_policies(&self, group_name: &str) -> Result<Vec<String>> {
+        self.inline_policy_names("group", group_name).await
+    }
+}
+
+#[async_trait]
+impl RoleStore for SqlWamiStore {
+    async fn create_role(&mut self, role: Role) -> Result<Role> {
+        self.update_role(role).await
+    }
+
+    async fn get_role(&self, role_name: &str) -> Result<Option<Role>> {
+        self.fetch(tables::ROLES, role_name).await
+    }
+
+    async fn get_roles(&self, role_names: &[String]) -> Result<Vec<Option<Role>>> {
+        self.fetch_many(tables::ROLES, role_names).await
+    }
+
+    async fn update_role(&mut self, role: Role) -> Result<Role> {
+        let record = Record {
+            wami_arn: &role.wami_arn,
+            name: &role.role_name,
+            user_name: None,
+            path: Some(&role.path),
+        };
+        self.upsert(tables::ROLES, record, &role).await?;
+        Ok(role)
+    }
+
+    async fn delete_role(&mut self, role_name: &str) -> Result<()> {
+        self.remove(tables::ROLES, role_name).await
+    }
+
+    async fn list_roles(
+        &self,
+        path_prefix: Option<&str>,
+        pagination: Option<&PaginationParams>,
+    ) -> Result<(Vec<Role>, bool, Option<String>)> {
+        self.fetch_page(tables::ROLES, path_prefix, pagination)
+            .await
+    }
+
+    // Managed policy attachment methods
+    async fn attach_role_policy(&mut self, role_name: &str, policy_arn: &str) -> Result<()> {
+        self.attach_policy("role", role_name, policy_arn).await
+    }
+
+    async fn detach_role_policy(&mut self, role_name: &str, policy_arn: &str) -> Result<()> {
+        self.detach_policy("role", role_name, policy_arn).await
+    }
+
+    async fn list_attached_role_policies(&self, role_name: &str) -> Result<Vec<String>> {
+        self.attached_policies("role", role_name).await
+    }
+
+    // Inline policy methods
+    async fn put_role_policy(
+        &mut self,
+        role_name: &str,
+        policy_name: &str,
+        policy_document: String,
+    ) -> Result<()> {
+        self.put_inline_policy("role", role_name, policy_name, policy_document)
+            .await
+    }
+
+    async fn get_role_policy(&self, role_name: &str, policy_name: &str) -> Result<Option<String>> {
+        self.inline_policy("role", role_name, policy_name).await
+    }
+
+    async fn delete_role_policy(&mut self, role_name: &str, policy_name: &str) -> Result<()> {
+        self.delete_inline_policy("role", role_name, policy_name)
+            .await
+    }
+
+    async fn list_role_policies(&self, role_name: &str) -> Result<Vec<String>> {
+        self.inline_policy_names("role", role_name).await
+    }
+}
@@ -0,0 +1,44 @@
+//! Policy Store Implementation for SqlWamiStore
+
+use super::{tables, Record, SqlWamiStore};
+use crate::error::Result;
+use crate::store::traits::PolicyStore;
+use crate::types::PaginationParams;
+use crate::wami::policies::Policy;
+use async_trait::async_trait;
+
+#[async_trait]
+impl PolicyStore for SqlWamiStore {
+    async fn create_policy(&mut self, policy: Policy) -> Result<Policy> {
+        self.update_policy(policy).await
+    }
+
+    async fn get_policy(&self, policy_arn: &str) -> Result<Option<Policy>> {
+        self.fetch(tables::POLICIES, policy_arn).await
+    }
+
+    async fn update_policy(&mut self, policy: Policy) -> Result<Policy> {
+        // Policies are looked up by their ARN, so it doubles as the lookup name
+        let record = Record {
+            wami_arn: &policy.wami_arn,
+            name: &policy.arn,
+            user_name: None,
+            path: Some(&policy.path),
+        };
+        self.upsert(tables::POLICIES, record, &policy).await?;
+        Ok(policy)
+    }
+
+    async fn delete_policy(&mut self, policy_arn: &str) -> Result<()> {
+        self.remove(tables::POLICIES, policy_arn).await
+    }
+
+    async fn list_policies(
+        &self,
+        path_prefix: Option<&str>,
+        pagination: Option<&PaginationParams>,
+    ) -> Result<(Vec<Policy>, bool, Option<String>)> {
+        self.fetch_page(tables::POLICIES, path_prefix, pagination)
+            .await
+    }
+}
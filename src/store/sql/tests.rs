@@ -0,0 +1,271 @@
+//! Integration tests for SqlWamiStore
+//!
+//! These run against a real Postgres database and are skipped unless
+//! `WAMI_TEST_DATABASE_URL` is set, e.g.
+//! `WAMI_TEST_DATABASE_URL=postgres://localhost/wami_test cargo test --features sql`.
+//! Resource names are randomized so tests can share one database.
+
+use super::SqlWamiStore;
+use crate::arn::{TenantPath, WamiArn};
+use crate::context::WamiContext;
+use crate::store::traits::{AccessKeyStore, GroupStore, PolicyStore, RoleStore, UserStore};
+use crate::types::{PaginationParams, Tag};
+use crate::wami::credentials::access_key::builder::build_access_key;
+use crate::wami::identity::group::builder::build_group;
+use crate::wami::identity::role::builder::build_role;
+use crate::wami::identity::user::builder::build_user;
+use crate::wami::policies::policy::builder::build_policy;
+use uuid::Uuid;
+
+async fn test_store() -> Option<SqlWamiStore> {
+    let url = std::env::var("WAMI_TEST_DATABASE_URL").ok()?;
+    let store = SqlWamiStore::connect(&url).await.unwrap();
+    store.migrate().await.unwrap();
+    Some(store)
+}
+
+fn test_context() -> WamiContext {
+    let arn: WamiArn = "arn:wami:.*:12345678:wami:123456789012:user/test"
+        .parse()
+        .unwrap();
+    WamiContext::builder()
+        .instance_id("123456789012")
+        .tenant_path(TenantPath::single(12345678))
+        .caller_arn(arn)
+        .is_root(false)
+        .build()
+        .unwrap()
+}
+
+fn unique(prefix: &str) -> String {
+    format!("{}-{}", prefix, Uuid::new_v4().simple())
+}
+
+#[tokio::test]
+async fn test_user_crud_and_tags() {
+    let Some(mut store) = test_store().await else {
+        return;
+    };
+    let context = test_context();
+    let user_name = unique("alice");
+
+    let user = build_user(user_name.clone(), Some("/sql/".to_string()), &context).unwrap();
+    store.create_user(user.clone()).await.unwrap();
+
+    let fetched = store.get_user(&user_name).await.unwrap().unwrap();
+    assert_eq!(fetched.wami_arn, user.wami_arn);
+    assert_eq!(fetched.path, "/sql/");
+
+    store
+        .tag_user(
+            &user_name,
+            vec![Tag {
+                key: "team".to_string(),
+                value: "platform".to_string(),
+            }],
+        )
+        .await
+        .unwrap();
+    assert_eq!(store.list_user_tags(&user_name).await.unwrap().len(), 1);
+
+    store
+        .untag_user(&user_name, vec!["team".to_string()])
+        .await
+        .unwrap();
+    assert!(store.list_user_tags(&user_name).await.unwrap().is_empty());
+
+    store.delete_user(&user_name).await.unwrap();
+    assert!(store.get_user(&user_name).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_get_users_preserves_positions() {
+    let Some(mut store) = test_store().await else {
+        return;
+    };
+    let context = test_context();
+    let (alice, bob) = (unique("alice"), unique("bob"));
+    for name in [&alice, &bob] {
+        let user = build_user(name.clone(), None, &context).unwrap();
+        store.create_user(user).await.unwrap();
+    }
+
+    let names = vec![bob.clone(), unique("ghost"), alice.clone(), bob.clone()];
+    let users = store.get_users(&names).await.unwrap();
+
+    let found: Vec<Option<&str>> = users
+        .iter()
+        .map(|user| user.as_ref().map(|u| u.user_name.as_str()))
+        .collect();
+    assert_eq!(
+        found,
+        [
+            Some(bob.as_str()),
+            None,
+            Some(alice.as_str()),
+            Some(bob.as_str())
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_list_users_with_path_prefix_and_pagination() {
+    let Some(mut store) = test_store().await else {
+        return;
+    };
+    let context = test_context();
+    let path = format!("/{}/", unique("page"));
+
+    for i in 0..3 {
+        let user = build_user(
+            format!("{}-{}", unique("user"), i),
+            Some(path.clone()),
+            &context,
+        )
+        .unwrap();
+        store.create_user(user).await.unwrap();
+    }
+
+    let pagination = PaginationParams {
+        max_items: Some(2),
+        marker: None,
+    };
+    let (first, is_truncated, marker) = store
+        .list_users(Some(&path), Some(&pagination))
+        .await
+        .unwrap();
+    assert_eq!(first.len(), 2);
+    assert!(is_truncated);
+
+    let pagination = PaginationParams {
+        max_items: Some(2),
+        marker,
+    };
+    let (second, is_truncated, _) = store
+        .list_users(Some(&path), Some(&pagination))
+        .await
+        .unwrap();
+    assert_eq!(second.len(), 1);
+    assert!(!is_truncated);
+    assert!(first.iter().all(|u| u.user_name != second[0].user_name));
+}
+
+#[tokio::test]
+async fn test_group_membership_and_cascading_delete() {
+    let Some(mut store) = test_store().await else {
+        return;
+    };
+    let context = test_context();
+    let user_name = unique("bob");
+    let group_name = unique("admins");
+
+    let user = build_user(user_name.clone(), None, &context).unwrap();
+    store.create_user(user).await.unwrap();
+    let group = build_group(group_name.clone(), None, &context).unwrap();
+    store.create_group(group).await.unwrap();
+    let key = build_access_key(user_name.clone(), &context).unwrap();
+    store.create_access_key(key.clone()).await.unwrap();
+
+    store
+        .add_user_to_group(&group_name, &user_name)
+        .await
+        .unwrap();
+    let groups = store.list_groups_for_user(&user_name).await.unwrap();
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].group_name, group_name);
+
+    let (keys, _, _) = store.list_access_keys(&user_name, None).await.unwrap();
+    assert_eq!(keys.len(), 1);
+
+    store.delete_user(&user_name).await.unwrap();
+    assert!(store
+        .list_groups_for_user(&user_name)
+        .await
+        .unwrap()
+        .is_empty());
+    assert!(store
+        .get_access_key(&key.access_key_id)
+        .await
+        .unwrap()
+        .is_none());
+
+    store.delete_group(&group_name).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_role_and_policy_attachments() {
+    let Some(mut store) = test_store().await else {
+        return;
+    };
+    let context = test_context();
+    let role_name = unique("deployer");
+    let document = r#"{"Version":"2012-10-17","Statement":[]}"#.to_string();
+
+    let role = build_role(
+        role_name.clone(),
+        document.clone(),
+        None,
+        None,
+        None,
+        &context,
+    )
+    .unwrap();
+    store.create_role(role).await.unwrap();
+
+    let policy = build_policy(
+        unique("policy"),
+        document.clone(),
+        None,
+        None,
+        None,
+        &context,
+    )
+    .unwrap();
+    store.create_policy(policy.clone()).await.unwrap();
+    assert!(store.get_policy(&policy.arn).await.unwrap().is_some());
+
+    store
+        .attach_role_policy(&role_name, &policy.arn)
+        .await
+        .unwrap();
+    assert_eq!(
+        store.list_attached_role_policies(&role_name).await.unwrap(),
+        vec![policy.arn.clone()]
+    );
+
+    store
+        .put_role_policy(&role_name, "inline", document.clone())
+        .await
+        .unwrap();
+    assert_eq!(
+        store.get_role_policy(&role_name, "inline").await.unwrap(),
+        Some(document)
+    );
+    assert_eq!(
+        store.list_role_policies(&role_name).await.unwrap(),
+        vec!["inline".to_string()]
+    );
+
+    store
+        .detach_role_policy(&role_name, &policy.arn)
+        .await
+        .unwrap();
+    store
+        .delete_role_policy(&role_name, "inline")
+        .await
+        .unwrap();
+    assert!(store
+        .list_attached_role_policies(&role_name)
+        .await
+        .unwrap()
+        .is_empty());
+    assert!(store
+        .list_role_policies(&role_name)
+        .await
+        .unwrap()
+        .is_empty());
+
+    store.delete_policy(&policy.arn).await.unwrap();
+    store.delete_role(&role_name).await.unwrap();
+    assert!(store.get_role(&role_name).await.unwrap().is_none());
+}
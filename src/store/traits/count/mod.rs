@@ -0,0 +1,25 @@
+//! Resource Count Store Trait
+
+use crate::error::Result;
+use crate::provider::ResourceType;
+use crate::wami::tenant::TenantId;
+use async_trait::async_trait;
+
+/// Trait for counting stored resources without listing them
+///
+/// Quota enforcement and usage reporting should use this instead of
+/// counting the results of a list call.
+#[async_trait]
+pub trait ResourceCountStore: Send + Sync {
+    /// Count the resources of a type, optionally only those owned by a tenant
+    ///
+    /// With a tenant, only resources whose ARN tenant path is exactly the
+    /// tenant's are counted, not those of its descendants.
+    ///
+    /// Returns `InvalidParameter` for resource types the store cannot count.
+    async fn count_resources(
+        &self,
+        resource_type: ResourceType,
+        tenant: Option<&TenantId>,
+    ) -> Result<usize>;
+}
@@ -4,8 +4,9 @@
 
 use crate::error::Result;
 use crate::types::PaginationParams;
-use crate::wami::credentials::AccessKey;
+use crate::wami::credentials::access_key::{AccessKey, AccessKeyLastUsed};
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 
 /// Store trait for IAM access key operations
 #[async_trait]
@@ -28,4 +29,25 @@ pub trait AccessKeyStore: Send + Sync {
         user_name: &str,
         pagination: Option<&PaginationParams>,
     ) -> Result<(Vec<AccessKey>, bool, Option<String>)>;
+
+    /// List access keys across all users, sorted by access key ID
+    async fn list_all_access_keys(&self) -> Result<Vec<AccessKey>>;
+
+    /// Record that an access key was used to call a service
+    ///
+    /// Returns `ResourceNotFound` if the access key does not exist.
+    async fn update_access_key_last_used(
+        &mut self,
+        access_key_id: &str,
+        service_name: &str,
+        region: Option<&str>,
+        timestamp: DateTime<Utc>,
+    ) -> Result<()>;
+
+    /// Get when and where an access key was last used
+    ///
+    /// A key that was never used yields [`AccessKeyLastUsed::default`], with
+    /// "N/A" for the region and service. Returns `ResourceNotFound` if the
+    /// access key does not exist.
+    async fn get_access_key_last_used(&self, access_key_id: &str) -> Result<AccessKeyLastUsed>;
 }
@@ -15,6 +15,9 @@ pub trait MfaDeviceStore: Send + Sync {
     /// Get an MFA device by serial number
     async fn get_mfa_device(&self, serial_number: &str) -> Result<Option<MfaDevice>>;
 
+    /// Update an MFA device
+    async fn update_mfa_device(&mut self, device: MfaDevice) -> Result<MfaDevice>;
+
     /// Delete an MFA device
     async fn delete_mfa_device(&mut self, serial_number: &str) -> Result<()>;
 
@@ -0,0 +1,28 @@
+//! Password Policy Store Trait
+//!
+//! Focused trait for account password policy storage operations
+
+use crate::arn::TenantPath;
+use crate::error::Result;
+use crate::wami::credentials::PasswordPolicy;
+use async_trait::async_trait;
+
+/// Store trait for account password policies
+///
+/// Each account (tenant) has at most one password policy.
+#[async_trait]
+pub trait PasswordPolicyStore: Send + Sync {
+    /// Get the password policy of an account, if one is set
+    async fn get_password_policy(&self, tenant_path: &TenantPath)
+        -> Result<Option<PasswordPolicy>>;
+
+    /// Set or replace the password policy of an account
+    async fn put_password_policy(
+        &mut self,
+        tenant_path: &TenantPath,
+        policy: PasswordPolicy,
+    ) -> Result<PasswordPolicy>;
+
+    /// Remove the password policy of an account
+    async fn delete_password_policy(&mut self, tenant_path: &TenantPath) -> Result<()>;
+}
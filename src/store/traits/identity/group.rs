@@ -4,7 +4,7 @@
 
 use crate::error::Result;
 use crate::types::PaginationParams;
-use crate::wami::identity::Group;
+use crate::wami::identity::{Group, User};
 use async_trait::async_trait;
 
 /// Store trait for IAM group operations
@@ -29,10 +29,32 @@ pub trait GroupStore: Send + Sync {
         pagination: Option<&PaginationParams>,
     ) -> Result<(Vec<Group>, bool, Option<String>)>;
 
+    /// List the groups matching an arbitrary predicate
+    ///
+    /// See [`UserStore::filter_users`](super::UserStore::filter_users).
+    async fn filter_groups<F>(&self, predicate: F) -> Result<Vec<Group>>
+    where
+        F: Fn(&Group) -> bool + Send + Sync,
+    {
+        let (groups, _, _) = self.list_groups(None, None).await?;
+        Ok(groups
+            .into_iter()
+            .filter(|group| predicate(group))
+            .collect())
+    }
+
     /// List groups for a specific user
     async fn list_groups_for_user(&self, user_name: &str) -> Result<Vec<Group>>;
 
+    /// List the users that are members of a group
+    ///
+    /// Returns `ResourceNotFound` if the group does not exist.
+    async fn list_users_in_group(&self, group_name: &str) -> Result<Vec<User>>;
+
     /// Add a user to a group
+    ///
+    /// Returns `ResourceNotFound` if the user or the group does not exist.
+    /// Adding a user that is already a member is a no-op.
     async fn add_user_to_group(&mut self, group_name: &str, user_name: &str) -> Result<()>;
 
     /// Remove a user from a group
@@ -6,6 +6,7 @@ use crate::error::Result;
 use crate::types::PaginationParams;
 use crate::wami::identity::Role;
 use async_trait::async_trait;
+use futures::Stream;
 
 /// Store trait for IAM role operations
 #[async_trait]
@@ -16,6 +17,17 @@ pub trait RoleStore: Send + Sync {
     /// Get a role by name
     async fn get_role(&self, role_name: &str) -> Result<Option<Role>>;
 
+    /// Get several roles by name
+    ///
+    /// See [`UserStore::get_users`](super::UserStore::get_users).
+    async fn get_roles(&self, role_names: &[String]) -> Result<Vec<Option<Role>>> {
+        let mut roles = Vec::with_capacity(role_names.len());
+        for role_name in role_names {
+            roles.push(self.get_role(role_name).await?);
+        }
+        Ok(roles)
+    }
+
     /// Update an existing role
     async fn update_role(&mut self, role: Role) -> Result<Role>;
 
@@ -29,6 +41,31 @@ pub trait RoleStore: Send + Sync {
         pagination: Option<&PaginationParams>,
     ) -> Result<(Vec<Role>, bool, Option<String>)>;
 
+    /// List the roles matching an arbitrary predicate
+    ///
+    /// See [`UserStore::filter_users`](super::UserStore::filter_users).
+    /// Stream all roles, fetching them from `list_roles` one page at a time
+    ///
+    /// Unlike `list_roles`, at most one page of
+    /// [`STREAM_PAGE_SIZE`](crate::store::traits::STREAM_PAGE_SIZE) roles is
+    /// held in memory at once.
+    fn stream_roles(&self) -> impl Stream<Item = Result<Role>> + Send + '_
+    where
+        Self: Sized,
+    {
+        crate::store::traits::paged_stream(move |pagination| async move {
+            self.list_roles(None, Some(&pagination)).await
+        })
+    }
+
+    async fn filter_roles<F>(&self, predicate: F) -> Result<Vec<Role>>
+    where
+        F: Fn(&Role) -> bool + Send + Sync,
+    {
+        let (roles, _, _) = self.list_roles(None, None).await?;
+        Ok(roles.into_iter().filter(|role| predicate(role)).collect())
+    }
+
     // Managed policy attachment methods
     /// Attach a managed policy to a role
     async fn attach_role_policy(&mut self, role_name: &str, policy_arn: &str) -> Result<()>;
@@ -16,4 +16,13 @@ pub trait ServiceLinkedRoleStore: Send + Sync {
         &self,
         deletion_task_id: &str,
     ) -> Result<Option<DeletionTaskInfo>>;
+
+    /// Replace a stored deletion task, typically to record a new status
+    async fn update_service_linked_role_deletion_task(
+        &mut self,
+        task: DeletionTaskInfo,
+    ) -> Result<()>;
+
+    /// List every deletion task, whatever its status
+    async fn list_service_linked_role_deletion_tasks(&self) -> Result<Vec<DeletionTaskInfo>>;
 }
@@ -6,6 +6,7 @@ use crate::error::Result;
 use crate::types::{PaginationParams, Tag};
 use crate::wami::identity::User;
 use async_trait::async_trait;
+use futures::Stream;
 
 /// Store trait for IAM user operations
 #[async_trait]
@@ -16,6 +17,20 @@ pub trait UserStore: Send + Sync {
     /// Get a user by name
     async fn get_user(&self, user_name: &str) -> Result<Option<User>>;
 
+    /// Get several users by name
+    ///
+    /// Results are positional: the user named `user_names[i]` is at index `i`,
+    /// or `None` there if it does not exist. Backends that can fetch in a
+    /// single round trip should override this default, which calls
+    /// `get_user` once per name.
+    async fn get_users(&self, user_names: &[String]) -> Result<Vec<Option<User>>> {
+        let mut users = Vec::with_capacity(user_names.len());
+        for user_name in user_names {
+            users.push(self.get_user(user_name).await?);
+        }
+        Ok(users)
+    }
+
     /// Update an existing user
     async fn update_user(&mut self, user: User) -> Result<User>;
 
@@ -29,6 +44,33 @@ pub trait UserStore: Send + Sync {
         pagination: Option<&PaginationParams>,
     ) -> Result<(Vec<User>, bool, Option<String>)>;
 
+    /// List the users matching an arbitrary predicate
+    ///
+    /// An escape hatch for queries the built-in filters do not cover. The
+    /// predicate runs in-process over every user, so scope it to a tenant by
+    /// composing it with a check such as `user.wami_arn.belongs_to_tenant(..)`.
+    /// Stream all users, fetching them from `list_users` one page at a time
+    ///
+    /// Unlike `list_users`, at most one page of
+    /// [`STREAM_PAGE_SIZE`](crate::store::traits::STREAM_PAGE_SIZE) users is
+    /// held in memory at once.
+    fn stream_users(&self) -> impl Stream<Item = Result<User>> + Send + '_
+    where
+        Self: Sized,
+    {
+        crate::store::traits::paged_stream(move |pagination| async move {
+            self.list_users(None, Some(&pagination)).await
+        })
+    }
+
+    async fn filter_users<F>(&self, predicate: F) -> Result<Vec<User>>
+    where
+        F: Fn(&User) -> bool + Send + Sync,
+    {
+        let (users, _, _) = self.list_users(None, None).await?;
+        Ok(users.into_iter().filter(|user| predicate(user)).collect())
+    }
+
     /// Tag a user
     async fn tag_user(&mut self, user_name: &str, tags: Vec<Tag>) -> Result<()>;
 
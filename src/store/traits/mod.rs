@@ -9,8 +9,9 @@
 //!
 //! The store traits follow the Interface Segregation Principle with focused sub-traits:
 //! - `UserStore`, `GroupStore`, `RoleStore` - Identity management
-//! - `AccessKeyStore`, `MfaDeviceStore`, `LoginProfileStore` - Credential management
+//! - `AccessKeyStore`, `MfaDeviceStore`, `LoginProfileStore`, `PasswordPolicyStore` - Credential management
 //! - `PolicyStore` - Authorization management
+//! - `ResourceCountStore` - Resource counts for quotas and usage reporting
 //! - `WamiStore` - Composite trait combining all IAM sub-traits
 //! - `StsStore`, `SsoAdminStore`, `TenantStore` - Service-specific traits
 //!
@@ -33,6 +34,8 @@ mod sts; // STS store (sessions + identities)
 mod wami; // WAMI store (identity + credentials + policies) // SSO Admin store (permission sets + assignments + instances + apps + issuers)
 
 // Supporting trait modules
+mod count; // Resource counts
+mod stream; // Paged streams over list operations
 mod tenant;
 
 // Export sub-traits from identity
@@ -42,7 +45,7 @@ pub use identity::{
 
 // Export sub-traits from credentials
 pub use credentials::{
-    AccessKeyStore, LoginProfileStore, MfaDeviceStore, ServerCertificateStore,
+    AccessKeyStore, LoginProfileStore, MfaDeviceStore, PasswordPolicyStore, ServerCertificateStore,
     ServiceCredentialStore, SigningCertificateStore,
 };
 
@@ -53,10 +56,13 @@ pub use policies::PolicyStore;
 pub use reports::CredentialReportStore;
 
 // Export composite traits
+pub use count::ResourceCountStore;
 pub use sso_admin::{
     AccountAssignmentStore, ApplicationStore, PermissionSetStore, SsoAdminStore, SsoInstanceStore,
     TrustedTokenIssuerStore,
 };
+pub(crate) use stream::paged_stream;
+pub use stream::STREAM_PAGE_SIZE;
 pub use sts::{IdentityStore, SessionStore, StsStore};
 pub use tenant::TenantStore;
 pub use wami::WamiStore;
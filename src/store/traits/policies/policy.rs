@@ -6,6 +6,7 @@ use crate::error::Result;
 use crate::types::PaginationParams;
 use crate::wami::policies::Policy;
 use async_trait::async_trait;
+use futures::Stream;
 
 /// Store trait for IAM policy operations
 #[async_trait]
@@ -28,4 +29,32 @@ pub trait PolicyStore: Send + Sync {
         scope: Option<&str>,
         pagination: Option<&PaginationParams>,
     ) -> Result<(Vec<Policy>, bool, Option<String>)>;
+
+    /// List the managed policies matching an arbitrary predicate
+    ///
+    /// See [`UserStore::filter_users`](crate::store::traits::UserStore::filter_users).
+    /// Stream all policies, fetching them from `list_policies` one page at a time
+    ///
+    /// Unlike `list_policies`, at most one page of
+    /// [`STREAM_PAGE_SIZE`](crate::store::traits::STREAM_PAGE_SIZE) policies is
+    /// held in memory at once.
+    fn stream_policies(&self) -> impl Stream<Item = Result<Policy>> + Send + '_
+    where
+        Self: Sized,
+    {
+        crate::store::traits::paged_stream(move |pagination| async move {
+            self.list_policies(None, Some(&pagination)).await
+        })
+    }
+
+    async fn filter_policies<F>(&self, predicate: F) -> Result<Vec<Policy>>
+    where
+        F: Fn(&Policy) -> bool + Send + Sync,
+    {
+        let (policies, _, _) = self.list_policies(None, None).await?;
+        Ok(policies
+            .into_iter()
+            .filter(|policy| predicate(policy))
+            .collect())
+    }
 }
@@ -24,4 +24,7 @@ pub trait AccountAssignmentStore: Send + Sync {
         account_id: &str,
         permission_set_arn: &str,
     ) -> Result<Vec<AccountAssignment>>;
+
+    /// List every account assignment across all accounts and permission sets, sorted by ID
+    async fn list_all_account_assignments(&self) -> Result<Vec<AccountAssignment>>;
 }
@@ -1,7 +1,7 @@
 //! Application Store Trait
 
 use crate::error::Result;
-use crate::wami::sso_admin::Application;
+use crate::wami::sso_admin::{Application, ApplicationAssignment};
 use async_trait::async_trait;
 
 /// Trait for SSO application storage operations
@@ -12,4 +12,19 @@ pub trait ApplicationStore: Send + Sync {
     async fn get_application(&self, application_arn: &str) -> Result<Option<Application>>;
 
     async fn list_applications(&self, instance_arn: &str) -> Result<Vec<Application>>;
+
+    /// Store an application assignment, replacing one with the same key
+    async fn put_application_assignment(
+        &mut self,
+        assignment: ApplicationAssignment,
+    ) -> Result<ApplicationAssignment>;
+
+    /// Delete the application assignment with the given key
+    async fn delete_application_assignment(&mut self, assignment_key: &str) -> Result<()>;
+
+    /// List the assignments of an application, sorted by key
+    async fn list_application_assignments(
+        &self,
+        application_arn: &str,
+    ) -> Result<Vec<ApplicationAssignment>>;
 }
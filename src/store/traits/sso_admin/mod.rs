@@ -9,7 +9,7 @@
 //!   ├── PermissionSetStore      - Permission set management (5 methods)
 //!   ├── AccountAssignmentStore  - Account assignments (4 methods)
 //!   ├── SsoInstanceStore        - SSO instances (3 methods)
-//!   ├── ApplicationStore        - Applications (3 methods)
+//!   ├── ApplicationStore        - Applications (6 methods)
 //!   └── TrustedTokenIssuerStore - Token issuers (4 methods)
 //! ```
 
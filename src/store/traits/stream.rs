@@ -0,0 +1,42 @@
+//! Paged Streams
+//!
+//! Adapts the marker-based `list_*` operations of the store traits into
+//! streams that fetch one page at a time.
+
+use crate::error::Result;
+use crate::types::PaginationParams;
+use futures::stream::{self, Stream, StreamExt};
+use std::future::Future;
+
+/// Number of items fetched per page by the `stream_*` operations
+pub const STREAM_PAGE_SIZE: i32 = 100;
+
+/// Stream the items of a paginated listing, one page of `fetch` at a time
+///
+/// Only the current page is held in memory. A failed fetch is yielded as the
+/// last item of the stream.
+pub(crate) fn paged_stream<'a, T, F, Fut>(fetch: F) -> impl Stream<Item = Result<T>> + Send + 'a
+where
+    T: Send + 'a,
+    F: Fn(PaginationParams) -> Fut + Send + 'a,
+    Fut: Future<Output = Result<(Vec<T>, bool, Option<String>)>> + Send + 'a,
+{
+    // The state holds the marker of the next page, or `None` once done
+    stream::unfold((fetch, Some(None)), |(fetch, next)| async move {
+        let marker = next?;
+        let page = fetch(PaginationParams {
+            max_items: Some(STREAM_PAGE_SIZE),
+            marker,
+        })
+        .await;
+        let (items, next) = match page {
+            Ok((items, true, Some(marker))) => {
+                (items.into_iter().map(Ok).collect(), Some(Some(marker)))
+            }
+            Ok((items, _, _)) => (items.into_iter().map(Ok).collect(), None),
+            Err(e) => (vec![Err(e)], None),
+        };
+        Some((stream::iter::<Vec<Result<T>>>(items), (fetch, next)))
+    })
+    .flatten()
+}
@@ -5,6 +5,7 @@
 use crate::error::Result;
 use crate::wami::sts::StsSession;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 
 /// Trait for STS session storage operations
 #[async_trait]
@@ -20,4 +21,9 @@ pub trait SessionStore: Send + Sync {
 
     /// List all sessions, optionally filtered by user ID
     async fn list_sessions(&self, user_id: Option<&str>) -> Result<Vec<StsSession>>;
+
+    /// Remove every session whose expiration is at or before `now`
+    ///
+    /// Returns the number of sessions removed.
+    async fn purge_expired(&mut self, now: DateTime<Utc>) -> Result<usize>;
 }
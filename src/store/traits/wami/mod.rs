@@ -6,9 +6,10 @@
 //!
 //! This trait uses the Interface Segregation Principle to compose focused sub-traits:
 //! - **Identity**: `UserStore`, `GroupStore`, `RoleStore`, `ServiceLinkedRoleStore`
-//! - **Credentials**: `AccessKeyStore`, `MfaDeviceStore`, `LoginProfileStore`, `ServerCertificateStore`, `SigningCertificateStore`, `ServiceCredentialStore`
+//! - **Credentials**: `AccessKeyStore`, `MfaDeviceStore`, `LoginProfileStore`, `PasswordPolicyStore`, `ServerCertificateStore`, `SigningCertificateStore`, `ServiceCredentialStore`
 //! - **Policies**: `PolicyStore`
 //! - **Reports**: `CredentialReportStore`
+//! - **Counts**: `ResourceCountStore`
 //!
 //! # Flexibility
 //!
@@ -21,7 +22,8 @@
 //!
 //! See the `InMemoryWamiStore` for a complete example implementation of all sub-traits.
 
-use super::credentials::{AccessKeyStore, LoginProfileStore, MfaDeviceStore};
+use super::count::ResourceCountStore;
+use super::credentials::{AccessKeyStore, LoginProfileStore, MfaDeviceStore, PasswordPolicyStore};
 use super::identity::{GroupStore, RoleStore, ServiceLinkedRoleStore, UserStore};
 use super::policies::PolicyStore;
 use super::reports::CredentialReportStore;
@@ -49,6 +51,7 @@ pub trait WamiStore:
     + AccessKeyStore
     + MfaDeviceStore
     + LoginProfileStore
+    + PasswordPolicyStore
     // TODO: Temporarily disabled during refactor
     // + ServerCertificateStore
     // + SigningCertificateStore
@@ -57,6 +60,8 @@ pub trait WamiStore:
     + PolicyStore
     // Reports
     + CredentialReportStore
+    // Counts
+    + ResourceCountStore
     // STS
     + SessionStore
     + IdentityStore
@@ -84,12 +89,14 @@ impl<T> WamiStore for T where
         + AccessKeyStore
         + MfaDeviceStore
         + LoginProfileStore
+        + PasswordPolicyStore
         // TODO: Temporarily disabled during refactor
         // + ServerCertificateStore
         // + SigningCertificateStore
         // + ServiceCredentialStore
         + PolicyStore
         + CredentialReportStore
+        + ResourceCountStore
         + SessionStore
         + IdentityStore
         + SsoInstanceStore
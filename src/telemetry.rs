@@ -0,0 +1,27 @@
+//! Optional Tracing Instrumentation
+//!
+//! With the `tracing` feature, service operations run in spans attached with
+//! `#[cfg_attr(feature = "tracing", tracing::instrument(...))]`, carrying the
+//! operation name, the caller's tenant path and the target resource. The
+//! helpers here record events on those spans and compile to nothing without
+//! the feature.
+
+/// Record the creation of a resource as an event of the current span
+pub(crate) fn record_created(resource_type: &str, resource_id: &str) {
+    #[cfg(feature = "tracing")]
+    tracing::debug!(resource_type, resource_id, "created");
+    #[cfg(not(feature = "tracing"))]
+    let _ = (resource_type, resource_id);
+}
+
+/// Record an authorization outcome as the `decision` field of the current span
+pub(crate) fn record_decision(allowed: bool) {
+    #[cfg(feature = "tracing")]
+    {
+        let decision = if allowed { "allow" } else { "deny" };
+        tracing::Span::current().record("decision", decision);
+        tracing::debug!(decision, "authorization decided");
+    }
+    #[cfg(not(feature = "tracing"))]
+    let _ = allowed;
+}
@@ -1,6 +1,16 @@
 use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Value;
 
+pub mod policy_builder;
+pub mod policy_lint;
+mod policy_merge;
+
+pub use policy_builder::{Effect, PolicyDocumentBuilder, StatementBuilder};
+pub use policy_lint::{FindingKind, FindingSeverity, PolicyFinding};
+
+/// Placeholder printed instead of secrets in `Debug` output
+pub const REDACTED: &str = "***";
+
 /// Common response wrapper for AWS operations
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AmiResponse<T> {
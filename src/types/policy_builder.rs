@@ -0,0 +1,346 @@
+//! Fluent builder for constructing policy documents.
+
+#![allow(clippy::result_large_err)]
+
+use super::{PolicyDocument, PolicyStatement};
+use crate::error::{AmiError, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fmt;
+use std::str::FromStr;
+
+/// The default policy language version used by AWS.
+pub const DEFAULT_POLICY_VERSION: &str = "2012-10-17";
+
+/// The effect of a policy statement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Effect {
+    /// Grants the matched actions
+    Allow,
+    /// Explicitly denies the matched actions
+    Deny,
+}
+
+impl Effect {
+    /// Returns the effect as it appears in a policy document.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Effect::Allow => "Allow",
+            Effect::Deny => "Deny",
+        }
+    }
+}
+
+impl fmt::Display for Effect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Effect {
+    type Err = AmiError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "Allow" => Ok(Effect::Allow),
+            "Deny" => Ok(Effect::Deny),
+            other => Err(AmiError::InvalidParameter {
+                message: format!("Invalid policy effect: {} (expected Allow or Deny)", other),
+            }),
+        }
+    }
+}
+
+/// A fluent builder for constructing validated policy documents.
+///
+/// # Examples
+///
+/// ```
+/// use wami::types::policy_builder::Effect;
+/// use wami::PolicyDocument;
+///
+/// let document = PolicyDocument::builder()
+///     .statement(|s| {
+///         s.effect(Effect::Allow)
+///             .actions(["s3:GetObject"])
+///             .resource("arn:aws:s3:::my-bucket/*")
+///     })
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(document.version, "2012-10-17");
+/// assert_eq!(document.statement[0].effect, "Allow");
+/// ```
+#[derive(Debug, Clone)]
+pub struct PolicyDocumentBuilder {
+    version: String,
+    statements: Vec<StatementBuilder>,
+}
+
+impl Default for PolicyDocumentBuilder {
+    fn default() -> Self {
+        Self {
+            version: DEFAULT_POLICY_VERSION.to_string(),
+            statements: Vec::new(),
+        }
+    }
+}
+
+impl PolicyDocumentBuilder {
+    /// Creates a new policy document builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Overrides the policy language version (defaults to `2012-10-17`).
+    pub fn version(mut self, version: impl Into<String>) -> Self {
+        self.version = version.into();
+        self
+    }
+
+    /// Adds a statement configured by the given closure.
+    pub fn statement(
+        mut self,
+        configure: impl FnOnce(StatementBuilder) -> StatementBuilder,
+    ) -> Self {
+        self.statements.push(configure(StatementBuilder::new()));
+        self
+    }
+
+    /// Builds the policy document.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the document has no statements or if any statement
+    /// is missing its effect, actions, or resources.
+    pub fn build(self) -> Result<PolicyDocument> {
+        if self.version.trim().is_empty() {
+            return Err(AmiError::InvalidParameter {
+                message: "Policy version cannot be empty".to_string(),
+            });
+        }
+
+        if self.statements.is_empty() {
+            return Err(AmiError::InvalidParameter {
+                message: "Policy document must contain at least one statement".to_string(),
+            });
+        }
+
+        let statement = self
+            .statements
+            .into_iter()
+            .enumerate()
+            .map(|(index, statement)| statement.build(index))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(PolicyDocument {
+            version: self.version,
+            statement,
+        })
+    }
+}
+
+/// A fluent builder for a single policy statement.
+#[derive(Debug, Clone, Default)]
+pub struct StatementBuilder {
+    effect: Option<Effect>,
+    actions: Vec<String>,
+    resources: Vec<String>,
+    condition: Option<Map<String, Value>>,
+}
+
+impl StatementBuilder {
+    /// Creates a new statement builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the statement effect.
+    pub fn effect(mut self, effect: Effect) -> Self {
+        self.effect = Some(effect);
+        self
+    }
+
+    /// Adds a single action.
+    pub fn action(mut self, action: impl Into<String>) -> Self {
+        self.actions.push(action.into());
+        self
+    }
+
+    /// Adds several actions.
+    pub fn actions<I, A>(mut self, actions: I) -> Self
+    where
+        I: IntoIterator<Item = A>,
+        A: Into<String>,
+    {
+        self.actions.extend(actions.into_iter().map(Into::into));
+        self
+    }
+
+    /// Adds a single resource.
+    pub fn resource(mut self, resource: impl Into<String>) -> Self {
+        self.resources.push(resource.into());
+        self
+    }
+
+    /// Adds several resources.
+    pub fn resources<I, R>(mut self, resources: I) -> Self
+    where
+        I: IntoIterator<Item = R>,
+        R: Into<String>,
+    {
+        self.resources.extend(resources.into_iter().map(Into::into));
+        self
+    }
+
+    /// Adds a condition, e.g. `.condition("StringEquals", "aws:username", "alice")`.
+    ///
+    /// Conditions sharing an operator are merged into the same operator block.
+    pub fn condition(
+        mut self,
+        operator: impl Into<String>,
+        key: impl Into<String>,
+        value: impl Into<Value>,
+    ) -> Self {
+        self.condition
+            .get_or_insert_with(Map::new)
+            .entry(operator.into())
+            .or_insert_with(|| Value::Object(Map::new()))
+            .as_object_mut()
+            .expect("condition operator block is always an object")
+            .insert(key.into(), value.into());
+        self
+    }
+
+    fn build(self, index: usize) -> Result<PolicyStatement> {
+        let effect = self.effect.ok_or_else(|| AmiError::InvalidParameter {
+            message: format!("Statement {} is missing an effect", index),
+        })?;
+
+        if self.actions.is_empty() {
+            return Err(AmiError::InvalidParameter {
+                message: format!("Statement {} must contain at least one action", index),
+            });
+        }
+
+        if self.resources.is_empty() {
+            return Err(AmiError::InvalidParameter {
+                message: format!("Statement {} must contain at least one resource", index),
+            });
+        }
+
+        if let Some(action) = self.actions.iter().find(|a| a.trim().is_empty()) {
+            return Err(AmiError::InvalidParameter {
+                message: format!(
+                    "Statement {} contains an invalid action: '{}'",
+                    index, action
+                ),
+            });
+        }
+
+        Ok(PolicyStatement {
+            effect: effect.to_string(),
+            action: self.actions,
+            resource: self.resources,
+            condition: self.condition.map(Value::Object),
+        })
+    }
+}
+
+impl PolicyDocument {
+    /// Creates a fluent builder for a policy document.
+    pub fn builder() -> PolicyDocumentBuilder {
+        PolicyDocumentBuilder::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_build_serializes_to_aws_json() {
+        let document = PolicyDocument::builder()
+            .statement(|s| {
+                s.effect(Effect::Allow)
+                    .actions(["s3:GetObject", "s3:PutObject"])
+                    .resource("arn:aws:s3:::my-bucket/*")
+                    .condition("StringEquals", "aws:username", "alice")
+            })
+            .build()
+            .unwrap();
+
+        let value = serde_json::to_value(&document).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "Version": "2012-10-17",
+                "Statement": [{
+                    "Effect": "Allow",
+                    "Action": ["s3:GetObject", "s3:PutObject"],
+                    "Resource": ["arn:aws:s3:::my-bucket/*"],
+                    "Condition": { "StringEquals": { "aws:username": "alice" } }
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn test_round_trip_through_serde() {
+        let document = PolicyDocument::builder()
+            .statement(|s| s.effect(Effect::Allow).action("iam:GetUser").resource("*"))
+            .statement(|s| {
+                s.effect(Effect::Deny)
+                    .action("iam:DeleteUser")
+                    .resources(["arn:aws:iam::123456789012:user/admin"])
+                    .condition("Bool", "aws:MultiFactorAuthPresent", "false")
+                    .condition("StringNotEquals", "aws:username", "root")
+            })
+            .build()
+            .unwrap();
+
+        let json = serde_json::to_string(&document).unwrap();
+        let parsed: PolicyDocument = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed.version, DEFAULT_POLICY_VERSION);
+        assert_eq!(parsed.statement.len(), 2);
+        assert_eq!(parsed.statement[0].effect, "Allow");
+        assert_eq!(parsed.statement[0].action, vec!["iam:GetUser"]);
+        assert_eq!(parsed.statement[1].effect, "Deny");
+        assert_eq!(
+            parsed.statement[1].condition,
+            document.statement[1].condition
+        );
+        assert_eq!(serde_json::to_string(&parsed).unwrap(), json);
+    }
+
+    #[test]
+    fn test_build_rejects_invalid_documents() {
+        assert!(PolicyDocument::builder().build().is_err());
+
+        let missing_effect = PolicyDocument::builder()
+            .statement(|s| s.action("s3:GetObject").resource("*"))
+            .build();
+        assert!(missing_effect.is_err());
+
+        let missing_action = PolicyDocument::builder()
+            .statement(|s| s.effect(Effect::Allow).resource("*"))
+            .build();
+        assert!(missing_action.is_err());
+
+        let missing_resource = PolicyDocument::builder()
+            .statement(|s| s.effect(Effect::Allow).action("s3:GetObject"))
+            .build();
+        assert!(missing_resource.is_err());
+    }
+
+    #[test]
+    fn test_effect_parse_and_display() {
+        assert_eq!("Allow".parse::<Effect>().unwrap(), Effect::Allow);
+        assert_eq!("Deny".parse::<Effect>().unwrap(), Effect::Deny);
+        assert!("allow".parse::<Effect>().is_err());
+        assert_eq!(Effect::Deny.to_string(), "Deny");
+        assert_eq!(serde_json::to_string(&Effect::Allow).unwrap(), "\"Allow\"");
+    }
+}
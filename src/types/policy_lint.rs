@@ -0,0 +1,199 @@
+//! Static analysis flagging overly permissive policy statements.
+//!
+//! This only inspects the document; it does not evaluate requests against it.
+
+use super::{PolicyDocument, PolicyStatement};
+use serde::{Deserialize, Serialize};
+
+/// Actions that should normally only be granted under a condition.
+pub const SENSITIVE_ACTIONS: &[&str] = &[
+    "iam:PassRole",
+    "iam:CreateAccessKey",
+    "iam:CreateLoginProfile",
+    "iam:UpdateLoginProfile",
+    "iam:AttachUserPolicy",
+    "iam:AttachRolePolicy",
+    "iam:AttachGroupPolicy",
+    "iam:PutUserPolicy",
+    "iam:PutRolePolicy",
+    "iam:PutGroupPolicy",
+    "iam:CreatePolicyVersion",
+    "iam:UpdateAssumeRolePolicy",
+    "sts:AssumeRole",
+    "kms:Decrypt",
+    "s3:DeleteBucket",
+    "s3:PutBucketPolicy",
+];
+
+/// How serious a finding is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum FindingSeverity {
+    /// Worth tightening, but limited in scope
+    Medium,
+    /// Grants control over identities or permissions
+    High,
+    /// Grants full administrative access
+    Critical,
+}
+
+/// The kind of problem a finding reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum FindingKind {
+    /// `Allow` of action `*` on resource `*`
+    AdminWildcard,
+    /// `Allow` of every IAM action (`iam:*`) on resource `*`
+    BroadIamAccess,
+    /// `Allow` of a [sensitive action](SENSITIVE_ACTIONS) without any condition
+    UnconditionedSensitiveAction,
+}
+
+/// An overly permissive statement found in a policy document.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PolicyFinding {
+    /// How serious the finding is
+    pub severity: FindingSeverity,
+    /// What the statement does wrong
+    pub kind: FindingKind,
+    /// Index of the offending statement in the document
+    pub statement_index: usize,
+    /// Human-readable description of the finding
+    pub message: String,
+}
+
+impl PolicyDocument {
+    /// Lists the overly permissive statements of this document.
+    ///
+    /// Only `Allow` statements are reported, each at most once under its most
+    /// severe finding. Findings are ordered by statement index.
+    pub fn findings(&self) -> Vec<PolicyFinding> {
+        self.statement
+            .iter()
+            .enumerate()
+            .filter(|(_, statement)| statement.effect == "Allow")
+            .filter_map(|(index, statement)| statement_finding(index, statement))
+            .collect()
+    }
+}
+
+fn statement_finding(index: usize, statement: &PolicyStatement) -> Option<PolicyFinding> {
+    let any_resource = statement.resource.iter().any(|resource| resource == "*");
+    let finding = |severity, kind, message: String| PolicyFinding {
+        severity,
+        kind,
+        statement_index: index,
+        message,
+    };
+
+    if any_resource && statement.action.iter().any(|action| action == "*") {
+        return Some(finding(
+            FindingSeverity::Critical,
+            FindingKind::AdminWildcard,
+            format!("Statement {} allows every action on every resource", index),
+        ));
+    }
+
+    if any_resource
+        && statement
+            .action
+            .iter()
+            .any(|action| action.eq_ignore_ascii_case("iam:*"))
+    {
+        return Some(finding(
+            FindingSeverity::High,
+            FindingKind::BroadIamAccess,
+            format!(
+                "Statement {} allows every IAM action on every resource",
+                index
+            ),
+        ));
+    }
+
+    if statement.condition.is_none() {
+        let sensitive: Vec<&str> = SENSITIVE_ACTIONS
+            .iter()
+            .copied()
+            .filter(|sensitive| {
+                statement
+                    .action
+                    .iter()
+                    .any(|action| action_covers(action, sensitive))
+            })
+            .collect();
+        if !sensitive.is_empty() {
+            return Some(finding(
+                FindingSeverity::Medium,
+                FindingKind::UnconditionedSensitiveAction,
+                format!(
+                    "Statement {} allows {} without a condition",
+                    index,
+                    sensitive.join(", ")
+                ),
+            ));
+        }
+    }
+
+    None
+}
+
+/// Whether an action pattern (possibly ending in `*`) covers `action`
+fn action_covers(pattern: &str, action: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => action
+            .get(..prefix.len())
+            .is_some_and(|head| head.eq_ignore_ascii_case(prefix)),
+        None => pattern.eq_ignore_ascii_case(action),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(document: &str) -> PolicyDocument {
+        serde_json::from_str(document).unwrap()
+    }
+
+    #[test]
+    fn test_findings_on_known_bad_policy() {
+        let policy = parse(
+            r#"{"Version":"2012-10-17","Statement":[
+                {"Effect":"Allow","Action":"*","Resource":"*"},
+                {"Effect":"Allow","Action":["iam:*"],"Resource":"*"},
+                {"Effect":"Allow","Action":["iam:Pass*","s3:GetObject"],"Resource":"arn:aws:iam::123456789012:role/app"},
+                {"Effect":"Deny","Action":"*","Resource":"*"}
+            ]}"#,
+        );
+
+        let findings = policy.findings();
+        let summary: Vec<_> = findings
+            .iter()
+            .map(|f| (f.statement_index, f.kind, f.severity))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (0, FindingKind::AdminWildcard, FindingSeverity::Critical),
+                (1, FindingKind::BroadIamAccess, FindingSeverity::High),
+                (
+                    2,
+                    FindingKind::UnconditionedSensitiveAction,
+                    FindingSeverity::Medium
+                ),
+            ]
+        );
+        assert!(findings[2].message.contains("iam:PassRole"));
+    }
+
+    #[test]
+    fn test_scoped_policy_has_no_findings() {
+        let policy = parse(
+            r#"{"Version":"2012-10-17","Statement":[
+                {"Effect":"Allow","Action":["s3:GetObject","s3:PutObject"],"Resource":"arn:aws:s3:::bucket/*"},
+                {"Effect":"Allow","Action":"iam:PassRole","Resource":"arn:aws:iam::123456789012:role/app",
+                 "Condition":{"StringEquals":{"iam:PassedToService":"ec2.amazonaws.com"}}}
+            ]}"#,
+        );
+
+        assert!(policy.findings().is_empty());
+    }
+}
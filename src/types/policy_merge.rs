@@ -0,0 +1,184 @@
+//! Merging and deduplication of policy documents.
+
+#![allow(clippy::result_large_err)]
+
+use super::{PolicyDocument, PolicyStatement};
+use crate::error::{AmiError, Result};
+use crate::wami::policies::inline::operations::policy_size;
+use crate::wami::policies::policy::MANAGED_POLICY_SIZE_LIMIT;
+use std::collections::BTreeSet;
+
+impl PolicyDocument {
+    /// Combines the statements of two documents into one deduplicated document.
+    ///
+    /// The result keeps the later of the two policy language versions.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the merged document exceeds the managed policy size limit.
+    pub fn merge(self, other: PolicyDocument) -> Result<PolicyDocument> {
+        let version = self.version.max(other.version);
+        let mut statement = self.statement;
+        statement.extend(other.statement);
+        PolicyDocument { version, statement }.dedup()
+    }
+
+    /// Coalesces redundant statements and drops exact duplicates.
+    ///
+    /// Statements with the same effect and condition are combined when they
+    /// share either their actions (resources are unioned) or their resources
+    /// (actions are unioned). Statements differing in both are left apart,
+    /// since unioning both lists would grant action/resource pairs neither
+    /// statement granted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the document exceeds the managed policy size limit.
+    pub fn dedup(mut self) -> Result<PolicyDocument> {
+        // Coalescing can make earlier statements share a list they did not
+        // share before, so repeat until nothing changes.
+        loop {
+            let before = self.statement.len();
+            self.statement = coalesce(self.statement);
+            if self.statement.len() == before {
+                break;
+            }
+        }
+
+        ensure_within_size_limit(&self)?;
+        Ok(self)
+    }
+}
+
+/// One pass folding each statement into the first earlier one it can join
+fn coalesce(statements: Vec<PolicyStatement>) -> Vec<PolicyStatement> {
+    let mut merged: Vec<PolicyStatement> = Vec::with_capacity(statements.len());
+    for statement in statements {
+        match merged
+            .iter_mut()
+            .find(|kept| can_coalesce(kept, &statement))
+        {
+            Some(kept) => {
+                union_into(&mut kept.action, statement.action);
+                union_into(&mut kept.resource, statement.resource);
+            }
+            None => merged.push(statement),
+        }
+    }
+    merged
+}
+
+fn can_coalesce(a: &PolicyStatement, b: &PolicyStatement) -> bool {
+    a.effect == b.effect
+        && a.condition == b.condition
+        && (as_set(&a.action) == as_set(&b.action) || as_set(&a.resource) == as_set(&b.resource))
+}
+
+fn as_set(values: &[String]) -> BTreeSet<&str> {
+    values.iter().map(String::as_str).collect()
+}
+
+fn union_into(target: &mut Vec<String>, values: Vec<String>) {
+    for value in values {
+        if !target.contains(&value) {
+            target.push(value);
+        }
+    }
+}
+
+fn ensure_within_size_limit(document: &PolicyDocument) -> Result<()> {
+    let json = serde_json::to_string(document)?;
+    let size = policy_size(&json);
+    if size > MANAGED_POLICY_SIZE_LIMIT {
+        return Err(AmiError::InvalidParameter {
+            message: format!(
+                "Policy document is {} characters, exceeding the limit of {}",
+                size, MANAGED_POLICY_SIZE_LIMIT
+            ),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn statement(effect: &str, actions: &[&str], resources: &[&str]) -> PolicyStatement {
+        PolicyStatement {
+            effect: effect.to_string(),
+            action: actions.iter().map(|a| a.to_string()).collect(),
+            resource: resources.iter().map(|r| r.to_string()).collect(),
+            condition: None,
+        }
+    }
+
+    fn document(statement: Vec<PolicyStatement>) -> PolicyDocument {
+        PolicyDocument {
+            version: "2012-10-17".to_string(),
+            statement,
+        }
+    }
+
+    #[test]
+    fn test_merge_coalesces_overlapping_allows() {
+        let reads = document(vec![statement(
+            "Allow",
+            &["s3:GetObject"],
+            &["arn:aws:s3:::bucket/*"],
+        )]);
+        let writes = document(vec![statement(
+            "Allow",
+            &["s3:PutObject", "s3:GetObject"],
+            &["arn:aws:s3:::bucket/*"],
+        )]);
+
+        let merged = reads.merge(writes).unwrap();
+        assert_eq!(merged.statement.len(), 1);
+        assert_eq!(merged.statement[0].action, ["s3:GetObject", "s3:PutObject"]);
+        assert_eq!(merged.statement[0].resource, ["arn:aws:s3:::bucket/*"]);
+    }
+
+    #[test]
+    fn test_dedup_drops_duplicates_and_keeps_distinct_statements() {
+        let mut conditional = statement("Allow", &["s3:GetObject"], &["*"]);
+        conditional.condition = Some(json!({"Bool": {"aws:SecureTransport": "true"}}));
+
+        let deduped = document(vec![
+            statement("Allow", &["s3:GetObject"], &["*"]),
+            statement("Allow", &["s3:GetObject"], &["*"]),
+            statement("Deny", &["s3:GetObject"], &["*"]),
+            conditional,
+            statement(
+                "Allow",
+                &["ec2:RunInstances"],
+                &["arn:aws:ec2:::instance/i-1"],
+            ),
+        ])
+        .dedup()
+        .unwrap();
+
+        assert_eq!(deduped.statement.len(), 4);
+        assert_eq!(deduped.statement[0].action, ["s3:GetObject"]);
+        assert_eq!(deduped.statement[0].resource, ["*"]);
+        assert_eq!(deduped.statement[1].effect, "Deny");
+        assert!(deduped.statement[2].condition.is_some());
+        assert_eq!(deduped.statement[3].action, ["ec2:RunInstances"]);
+    }
+
+    #[test]
+    fn test_merge_rejects_oversized_result() {
+        let resources: Vec<String> = (0..400)
+            .map(|i| format!("arn:aws:s3:::bucket-{}/*", i))
+            .collect();
+        let half = |range: std::ops::Range<usize>| {
+            let resources: Vec<&str> = resources[range].iter().map(String::as_str).collect();
+            document(vec![statement("Allow", &["s3:GetObject"], &resources)])
+        };
+
+        assert!(half(0..200).dedup().is_ok());
+        let result = half(0..200).merge(half(200..400));
+        assert!(matches!(result, Err(AmiError::InvalidParameter { .. })));
+    }
+}
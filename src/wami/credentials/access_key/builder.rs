@@ -34,6 +34,7 @@ pub fn build_access_key(user_name: String, context: &WamiContext) -> Result<Acce
         secret_access_key: Some(secret_access_key),
         wami_arn,
         providers: Vec::new(),
+        last_used: None,
     })
 }
 
@@ -11,5 +11,6 @@ pub use model::{AccessKey, AccessKeyLastUsed};
 // Operations moved to service layer
 // pub use operations::AccessKeyOperations;
 pub use requests::{
-    CreateAccessKeyRequest, ListAccessKeysRequest, ListAccessKeysResponse, UpdateAccessKeyRequest,
+    CreateAccessKeyRequest, ListAccessKeysRequest, ListAccessKeysResponse, RotateAccessKeyResponse,
+    UpdateAccessKeyRequest,
 };
@@ -35,6 +35,15 @@ pub struct ListAccessKeysRequest {
     pub pagination: Option<PaginationParams>,
 }
 
+/// Response for rotating a user's access key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RotateAccessKeyResponse {
+    /// The newly created, active access key (including its secret)
+    pub access_key: AccessKey,
+    /// The ID of the access key that was deactivated, if the user had an active one
+    pub deactivated_access_key_id: Option<String>,
+}
+
 /// Response for listing access keys
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ListAccessKeysResponse {
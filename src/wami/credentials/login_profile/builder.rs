@@ -5,6 +5,7 @@ use crate::arn::{Service, WamiArn};
 use crate::context::WamiContext;
 use crate::error::Result;
 use crate::provider::ProviderConfig;
+use crate::wami::credentials::password_policy::builder::PASSWORD_REUSE_PREVENTION_RANGE;
 
 /// Build a new LoginProfile resource with context-based identifiers
 #[allow(clippy::result_large_err)]
@@ -27,6 +28,9 @@ pub fn build_login_profile(
         password_reset_required,
         wami_arn,
         providers: Vec::new(),
+        password_hash: None,
+        password_history: Vec::new(),
+        password_last_changed: None,
     })
 }
 
@@ -41,6 +45,20 @@ pub fn update_login_profile(
     login_profile
 }
 
+/// Replace the password of a LoginProfile with a new password hash
+///
+/// The previous hash moves to the password history, which keeps as many
+/// passwords as the largest reuse prevention count allows.
+pub fn set_password_hash(mut login_profile: LoginProfile, password_hash: String) -> LoginProfile {
+    if let Some(previous) = login_profile.password_hash.replace(password_hash) {
+        login_profile.password_history.insert(0, previous);
+        let remembered = *PASSWORD_REUSE_PREVENTION_RANGE.end() as usize - 1;
+        login_profile.password_history.truncate(remembered);
+    }
+    login_profile.password_last_changed = Some(chrono::Utc::now());
+    login_profile
+}
+
 /// Add a provider configuration to a LoginProfile
 pub fn add_provider_to_login_profile(
     mut login_profile: LoginProfile,
@@ -1,7 +1,8 @@
 //! LoginProfile Domain Model
 
 use crate::arn::WamiArn;
-use chrono::{DateTime, Utc};
+use crate::wami::credentials::PasswordPolicy;
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 
 /// Represents a login profile (console password) for an IAM user
@@ -17,4 +18,45 @@ pub struct LoginProfile {
     pub wami_arn: WamiArn,
     /// List of cloud providers where this resource exists
     pub providers: Vec<crate::provider::ProviderConfig>,
+    /// Hash of the current password, if one has been set
+    #[serde(default)]
+    pub password_hash: Option<String>,
+    /// Hashes of previous passwords, most recent first
+    #[serde(default)]
+    pub password_history: Vec<String>,
+    /// When the password was last changed, if ever
+    #[serde(default)]
+    pub password_last_changed: Option<DateTime<Utc>>,
+}
+
+impl LoginProfile {
+    /// When the current password was set
+    ///
+    /// This is the creation date of the profile until the password is changed.
+    pub fn password_changed_at(&self) -> DateTime<Utc> {
+        self.password_last_changed.unwrap_or(self.create_date)
+    }
+
+    /// When the current password expires under `policy`, if passwords expire
+    pub fn password_expires_at(&self, policy: &PasswordPolicy) -> Option<DateTime<Utc>> {
+        policy
+            .max_password_age
+            .map(|days| self.password_changed_at() + Duration::days(i64::from(days)))
+    }
+
+    /// Whether the current password is expired under `policy` at `now`
+    pub fn is_password_expired(&self, policy: &PasswordPolicy, now: DateTime<Utc>) -> bool {
+        self.password_expires_at(policy)
+            .is_some_and(|expires_at| expires_at <= now)
+    }
+
+    /// Hashes of the passwords remembered by a reuse prevention count of
+    /// `count`, starting with the current password
+    pub fn recent_password_hashes(&self, count: usize) -> impl Iterator<Item = &str> {
+        self.password_hash
+            .iter()
+            .chain(&self.password_history)
+            .take(count)
+            .map(String::as_str)
+    }
 }
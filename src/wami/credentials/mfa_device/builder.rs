@@ -1,6 +1,7 @@
 //! MfaDevice Builder
 
 use super::model::MfaDevice;
+use super::totp;
 use crate::arn::{Service, WamiArn};
 use crate::context::WamiContext;
 use crate::error::Result;
@@ -27,9 +28,39 @@ pub fn build_mfa_device(
         enable_date: chrono::Utc::now(),
         wami_arn,
         providers: Vec::new(),
+        base32_string_seed: None,
+        provisioning_uri: None,
+        time_step_offset: 0,
     })
 }
 
+/// Build a new, unassigned virtual MFA device with a fresh TOTP secret
+///
+/// The serial number follows the AWS format `arn:aws:iam::{account}:mfa{path}{name}`.
+/// The device is assigned to a user when it is enabled.
+#[allow(clippy::result_large_err)]
+pub fn build_virtual_mfa_device(
+    virtual_mfa_device_name: String,
+    path: Option<String>,
+    context: &WamiContext,
+) -> Result<MfaDevice> {
+    let path = path.unwrap_or_else(|| "/".to_string());
+    let serial_number = format!(
+        "arn:aws:iam::{}:mfa{}{}",
+        context.instance_id(),
+        path,
+        virtual_mfa_device_name
+    );
+    let seed = totp::generate_secret();
+    let account_name = format!("{}@{}", virtual_mfa_device_name, context.instance_id());
+    let provisioning_uri = totp::provisioning_uri("WAMI", &account_name, &seed);
+
+    let mut device = build_mfa_device(String::new(), serial_number, context)?;
+    device.base32_string_seed = Some(seed);
+    device.provisioning_uri = Some(provisioning_uri);
+    Ok(device)
+}
+
 /// Add a provider configuration to an MfaDevice
 pub fn add_provider_to_mfa_device(mut mfa_device: MfaDevice, config: ProviderConfig) -> MfaDevice {
     mfa_device.providers.push(config);
@@ -6,8 +6,9 @@ pub mod builder;
 pub mod model;
 // pub mod operations; // TODO: Fix field mismatches in tests
 pub mod requests;
+pub mod totp;
 
 pub use model::MfaDevice;
 // Operations moved to service layer
 // pub use operations::MfaDeviceOperations;
-pub use requests::{EnableMfaDeviceRequest, ListMfaDevicesRequest};
+pub use requests::{CreateVirtualMfaDeviceRequest, EnableMfaDeviceRequest, ListMfaDevicesRequest};
@@ -1,13 +1,15 @@
 //! MfaDevice Domain Model
 
 use crate::arn::WamiArn;
+use crate::types::REDACTED;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 /// Represents an MFA (Multi-Factor Authentication) device
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct MfaDevice {
-    /// The user with whom the MFA device is associated
+    /// The user with whom the MFA device is associated (empty for an unassigned virtual device)
     pub user_name: String,
     /// The serial number that uniquely identifies the MFA device
     pub serial_number: String,
@@ -17,4 +19,46 @@ pub struct MfaDevice {
     pub wami_arn: WamiArn,
     /// List of cloud providers where this resource exists
     pub providers: Vec<crate::provider::ProviderConfig>,
+    /// Base32-encoded TOTP secret of a virtual MFA device
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base32_string_seed: Option<String>,
+    /// `otpauth://` URI used to enroll a virtual MFA device in an authenticator app
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provisioning_uri: Option<String>,
+    /// Clock skew of the device in TOTP time steps, recorded when it is resynchronized
+    #[serde(default)]
+    pub time_step_offset: i64,
+}
+
+impl MfaDevice {
+    /// Whether this is a virtual (TOTP) device
+    pub fn is_virtual(&self) -> bool {
+        self.base32_string_seed.is_some()
+    }
+
+    /// Whether the device has been enabled for a user
+    pub fn is_enabled(&self) -> bool {
+        !self.user_name.is_empty()
+    }
+}
+
+impl fmt::Debug for MfaDevice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MfaDevice")
+            .field("user_name", &self.user_name)
+            .field("serial_number", &self.serial_number)
+            .field("enable_date", &self.enable_date)
+            .field("wami_arn", &self.wami_arn)
+            .field("providers", &self.providers)
+            .field(
+                "base32_string_seed",
+                &self.base32_string_seed.as_ref().map(|_| REDACTED),
+            )
+            .field(
+                "provisioning_uri",
+                &self.provisioning_uri.as_ref().map(|_| REDACTED),
+            )
+            .field("time_step_offset", &self.time_step_offset)
+            .finish()
+    }
 }
@@ -15,6 +15,15 @@ pub struct EnableMfaDeviceRequest {
     pub authentication_code_2: String,
 }
 
+/// Request parameters for creating a virtual MFA device
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateVirtualMfaDeviceRequest {
+    /// The name of the virtual MFA device
+    pub virtual_mfa_device_name: String,
+    /// The path for the virtual MFA device (defaults to `/`)
+    pub path: Option<String>,
+}
+
 /// Request parameters for listing MFA devices
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ListMfaDevicesRequest {
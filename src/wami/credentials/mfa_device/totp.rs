@@ -0,0 +1,344 @@
+//! Time-based One-Time Passwords (RFC 6238) for virtual MFA devices
+//!
+//! Virtual MFA devices share a random secret with an authenticator app. Both
+//! sides derive a 6-digit code from the secret and the current 30-second time
+//! step using HMAC-SHA1 (RFC 4226 dynamic truncation). The secret is exchanged
+//! as an unpadded base32 string, usually through an `otpauth://` URI.
+
+use crate::error::{AmiError, Result};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use sha1::Sha1;
+
+/// Length of a TOTP time step, in seconds
+pub const TOTP_STEP_SECONDS: i64 = 30;
+
+/// Number of digits in a TOTP code
+pub const TOTP_DIGITS: usize = 6;
+
+/// Number of time steps before and after the current one that are accepted
+pub const TOTP_WINDOW: u64 = 1;
+
+/// Number of time steps searched in each direction when resynchronizing a device
+pub const TOTP_RESYNC_WINDOW: u64 = 20;
+
+/// Size of a generated secret, in bytes (160 bits, as recommended by RFC 4226)
+const SECRET_BYTES: usize = 20;
+
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Generate a random TOTP secret, encoded as unpadded base32
+pub fn generate_secret() -> String {
+    let mut secret = [0u8; SECRET_BYTES];
+    rand::thread_rng().fill_bytes(&mut secret);
+    base32_encode(&secret)
+}
+
+/// Build the `otpauth://` provisioning URI authenticator apps scan to enroll a device
+///
+/// The label is `issuer:account_name`, so apps can group codes by issuer.
+pub fn provisioning_uri(issuer: &str, account_name: &str, secret: &str) -> String {
+    format!(
+        "otpauth://totp/{}:{}?secret={}&issuer={}&algorithm=SHA1&digits={}&period={}",
+        percent_encode(issuer),
+        percent_encode(account_name),
+        secret,
+        percent_encode(issuer),
+        TOTP_DIGITS,
+        TOTP_STEP_SECONDS
+    )
+}
+
+/// The TOTP time step (counter) containing `now`
+pub fn time_step(now: DateTime<Utc>) -> u64 {
+    now.timestamp().div_euclid(TOTP_STEP_SECONDS).max(0) as u64
+}
+
+/// Compute the code for a given time step
+#[allow(clippy::result_large_err)]
+pub fn code_for_step(secret: &str, step: u64) -> Result<String> {
+    let key = base32_decode(secret).ok_or_else(|| AmiError::InvalidParameter {
+        message: "TOTP secret is not valid base32".to_string(),
+    })?;
+    let mut mac = Hmac::<Sha1>::new_from_slice(&key).map_err(|_| AmiError::InvalidParameter {
+        message: "TOTP secret cannot be used as an HMAC key".to_string(),
+    })?;
+    mac.update(&step.to_be_bytes());
+    let digest = mac.finalize().into_bytes();
+
+    // Dynamic truncation (RFC 4226 section 5.3)
+    let offset = (digest[digest.len() - 1] & 0x0f) as usize;
+    let binary = u32::from_be_bytes([
+        digest[offset] & 0x7f,
+        digest[offset + 1],
+        digest[offset + 2],
+        digest[offset + 3],
+    ]);
+
+    Ok(format!(
+        "{:0width$}",
+        binary % 10u32.pow(TOTP_DIGITS as u32),
+        width = TOTP_DIGITS
+    ))
+}
+
+/// Compute the code valid at `now`
+#[allow(clippy::result_large_err)]
+pub fn code_at(secret: &str, now: DateTime<Utc>) -> Result<String> {
+    code_for_step(secret, time_step(now))
+}
+
+/// Find the time step a code belongs to, within [`TOTP_WINDOW`] steps of `now`
+#[allow(clippy::result_large_err)]
+pub fn matching_step(secret: &str, code: &str, now: DateTime<Utc>) -> Result<Option<u64>> {
+    find_step(secret, code, time_step(now), TOTP_WINDOW)
+}
+
+/// Verify a code against the current time step, tolerating one step of clock drift
+#[allow(clippy::result_large_err)]
+pub fn verify_code(secret: &str, code: &str, now: DateTime<Utc>) -> Result<bool> {
+    verify_code_with_offset(secret, code, now, 0)
+}
+
+/// Verify a code for a device whose clock is `offset_steps` time steps off ours
+///
+/// The offset is the one recorded by [`resync_offset`].
+#[allow(clippy::result_large_err)]
+pub fn verify_code_with_offset(
+    secret: &str,
+    code: &str,
+    now: DateTime<Utc>,
+    offset_steps: i64,
+) -> Result<bool> {
+    let center = time_step(now).saturating_add_signed(offset_steps);
+    Ok(find_step(secret, code, center, TOTP_WINDOW)?.is_some())
+}
+
+/// Measure a device's clock skew from two consecutive codes
+///
+/// Searches [`TOTP_RESYNC_WINDOW`] steps around `now` for a step producing
+/// `code_2` right after a step producing `code_1`, and returns how many steps
+/// that is from the current one. Returns `None` when the codes are not
+/// consecutive within the window.
+#[allow(clippy::result_large_err)]
+pub fn resync_offset(
+    secret: &str,
+    code_1: &str,
+    code_2: &str,
+    now: DateTime<Utc>,
+) -> Result<Option<i64>> {
+    let current = time_step(now);
+    Ok(
+        find_consecutive_step(secret, code_1, code_2, current, TOTP_RESYNC_WINDOW)?
+            .map(|step| step as i64 - current as i64),
+    )
+}
+
+/// Verify two codes from consecutive time steps, the second one being current
+///
+/// This is how AWS checks a virtual MFA device when it is enabled: a single
+/// code could be guessed or replayed, two consecutive ones prove the device
+/// holds the secret and its clock is in sync.
+#[allow(clippy::result_large_err)]
+pub fn verify_consecutive_codes(
+    secret: &str,
+    code_1: &str,
+    code_2: &str,
+    now: DateTime<Utc>,
+) -> Result<bool> {
+    Ok(find_consecutive_step(secret, code_1, code_2, time_step(now), TOTP_WINDOW)?.is_some())
+}
+
+/// Find the step within `window` steps of `center` whose code is `code`
+#[allow(clippy::result_large_err)]
+fn find_step(secret: &str, code: &str, center: u64, window: u64) -> Result<Option<u64>> {
+    for step in center.saturating_sub(window)..=center.saturating_add(window) {
+        if code_for_step(secret, step)? == code.trim() {
+            return Ok(Some(step));
+        }
+    }
+    Ok(None)
+}
+
+/// Find the step within `window` steps of `center` producing `code_2`, preceded by `code_1`
+#[allow(clippy::result_large_err)]
+fn find_consecutive_step(
+    secret: &str,
+    code_1: &str,
+    code_2: &str,
+    center: u64,
+    window: u64,
+) -> Result<Option<u64>> {
+    for step in center.saturating_sub(window).max(1)..=center.saturating_add(window) {
+        if code_for_step(secret, step)? == code_2.trim()
+            && code_for_step(secret, step - 1)? == code_1.trim()
+        {
+            return Ok(Some(step));
+        }
+    }
+    Ok(None)
+}
+
+/// Encode bytes as unpadded RFC 4648 base32
+fn base32_encode(data: &[u8]) -> String {
+    let mut encoded = String::with_capacity(data.len().div_ceil(5) * 8);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+
+    for &byte in data {
+        buffer = (buffer << 8) | u32::from(byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            encoded.push(BASE32_ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        encoded.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+
+    encoded
+}
+
+/// Decode RFC 4648 base32, ignoring case, padding and whitespace
+fn base32_decode(encoded: &str) -> Option<Vec<u8>> {
+    let mut decoded = Vec::with_capacity(encoded.len() * 5 / 8);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+
+    for c in encoded.chars() {
+        if c == '=' || c.is_whitespace() {
+            continue;
+        }
+        let value = BASE32_ALPHABET
+            .iter()
+            .position(|&a| a as char == c.to_ascii_uppercase())?;
+        buffer = (buffer << 5) | value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            decoded.push((buffer >> bits) as u8);
+        }
+    }
+
+    if decoded.is_empty() {
+        None
+    } else {
+        Some(decoded)
+    }
+}
+
+/// Percent-encode a URI component, keeping RFC 3986 unreserved characters
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            }
+            other => format!("%{:02X}", other),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    /// Base32 of the RFC 6238 SHA1 test secret "12345678901234567890"
+    const RFC_SECRET: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+
+    fn at(timestamp: i64) -> DateTime<Utc> {
+        Utc.timestamp_opt(timestamp, 0).unwrap()
+    }
+
+    #[test]
+    fn test_base32_round_trip() {
+        assert_eq!(base32_encode(b"12345678901234567890"), RFC_SECRET);
+        assert_eq!(
+            base32_decode(RFC_SECRET).unwrap(),
+            b"12345678901234567890".to_vec()
+        );
+        assert_eq!(base32_decode("gezdgnbv").unwrap(), b"12345".to_vec());
+        assert!(base32_decode("not base32!").is_none());
+    }
+
+    #[test]
+    fn test_rfc6238_vectors() {
+        // The RFC lists 8-digit codes; 6-digit codes are their last six digits
+        assert_eq!(code_at(RFC_SECRET, at(59)).unwrap(), "287082");
+        assert_eq!(code_at(RFC_SECRET, at(1_111_111_109)).unwrap(), "081804");
+        assert_eq!(code_at(RFC_SECRET, at(1_111_111_111)).unwrap(), "050471");
+        assert_eq!(code_at(RFC_SECRET, at(1_234_567_890)).unwrap(), "005924");
+        assert_eq!(code_at(RFC_SECRET, at(2_000_000_000)).unwrap(), "279037");
+    }
+
+    #[test]
+    fn test_verify_code_window() {
+        let now = at(1_234_567_890);
+        let current = code_at(RFC_SECRET, now).unwrap();
+        let previous = code_at(RFC_SECRET, now - chrono::Duration::seconds(30)).unwrap();
+        let stale = code_at(RFC_SECRET, now - chrono::Duration::seconds(90)).unwrap();
+
+        assert!(verify_code(RFC_SECRET, &current, now).unwrap());
+        assert!(verify_code(RFC_SECRET, &previous, now).unwrap());
+        assert!(!verify_code(RFC_SECRET, &stale, now).unwrap());
+    }
+
+    #[test]
+    fn test_verify_consecutive_codes() {
+        let now = at(1_234_567_890);
+        let step = time_step(now);
+        let code_1 = code_for_step(RFC_SECRET, step - 1).unwrap();
+        let code_2 = code_for_step(RFC_SECRET, step).unwrap();
+
+        assert!(verify_consecutive_codes(RFC_SECRET, &code_1, &code_2, now).unwrap());
+        // Order matters, and both codes must be present
+        assert!(!verify_consecutive_codes(RFC_SECRET, &code_2, &code_1, now).unwrap());
+        assert!(!verify_consecutive_codes(RFC_SECRET, &code_2, &code_2, now).unwrap());
+    }
+
+    #[test]
+    fn test_resync_offset() {
+        let now = at(1_234_567_890);
+        let step = time_step(now);
+
+        // A device running five minutes (ten steps) behind
+        let code_1 = code_for_step(RFC_SECRET, step - 11).unwrap();
+        let code_2 = code_for_step(RFC_SECRET, step - 10).unwrap();
+        assert_eq!(
+            resync_offset(RFC_SECRET, &code_1, &code_2, now).unwrap(),
+            Some(-10)
+        );
+
+        // Too far off, or not consecutive
+        let far_1 = code_for_step(RFC_SECRET, step + 40).unwrap();
+        let far_2 = code_for_step(RFC_SECRET, step + 41).unwrap();
+        assert_eq!(
+            resync_offset(RFC_SECRET, &far_1, &far_2, now).unwrap(),
+            None
+        );
+        assert_eq!(
+            resync_offset(RFC_SECRET, &code_2, &code_1, now).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_generate_secret_and_provisioning_uri() {
+        let secret = generate_secret();
+        assert_eq!(secret.len(), 32);
+        assert_eq!(base32_decode(&secret).unwrap().len(), SECRET_BYTES);
+
+        let uri = provisioning_uri("WAMI", "alice@123456789012", &secret);
+        assert_eq!(
+            uri,
+            format!(
+                "otpauth://totp/WAMI:alice%40123456789012?secret={}&issuer=WAMI&algorithm=SHA1&digits=6&period=30",
+                secret
+            )
+        );
+    }
+}
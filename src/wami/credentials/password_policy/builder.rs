@@ -0,0 +1,67 @@
+//! PasswordPolicy Builder
+
+use super::model::PasswordPolicy;
+use super::requests::UpdateAccountPasswordPolicyRequest;
+use crate::error::{AmiError, Result};
+use std::ops::RangeInclusive;
+
+/// Allowed minimum password lengths
+pub const MINIMUM_PASSWORD_LENGTH_RANGE: RangeInclusive<u32> = 6..=128;
+/// Allowed maximum password ages, in days
+pub const MAX_PASSWORD_AGE_RANGE: RangeInclusive<u32> = 1..=1095;
+/// Allowed numbers of remembered previous passwords
+pub const PASSWORD_REUSE_PREVENTION_RANGE: RangeInclusive<u32> = 1..=24;
+
+/// Build a password policy from an update request, validating its settings
+#[allow(clippy::result_large_err)]
+pub fn build_password_policy(
+    request: UpdateAccountPasswordPolicyRequest,
+) -> Result<PasswordPolicy> {
+    let defaults = PasswordPolicy::default();
+    let minimum_password_length = request
+        .minimum_password_length
+        .unwrap_or(defaults.minimum_password_length);
+
+    check_range(
+        "MinimumPasswordLength",
+        Some(minimum_password_length),
+        MINIMUM_PASSWORD_LENGTH_RANGE,
+    )?;
+    check_range(
+        "MaxPasswordAge",
+        request.max_password_age,
+        MAX_PASSWORD_AGE_RANGE,
+    )?;
+    check_range(
+        "PasswordReusePrevention",
+        request.password_reuse_prevention,
+        PASSWORD_REUSE_PREVENTION_RANGE,
+    )?;
+
+    Ok(PasswordPolicy {
+        minimum_password_length,
+        require_symbols: request.require_symbols,
+        require_numbers: request.require_numbers,
+        require_uppercase_characters: request.require_uppercase_characters,
+        require_lowercase_characters: request.require_lowercase_characters,
+        allow_users_to_change_password: request.allow_users_to_change_password,
+        max_password_age: request.max_password_age,
+        password_reuse_prevention: request.password_reuse_prevention,
+    })
+}
+
+#[allow(clippy::result_large_err)]
+fn check_range(name: &str, value: Option<u32>, range: RangeInclusive<u32>) -> Result<()> {
+    match value {
+        Some(value) if !range.contains(&value) => Err(AmiError::InvalidParameter {
+            message: format!(
+                "{} must be between {} and {}, got {}",
+                name,
+                range.start(),
+                range.end(),
+                value
+            ),
+        }),
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,10 @@
+//! PasswordPolicy Resource Module
+//!
+//! This module provides self-contained handling of account password policies.
+
+pub mod builder;
+pub mod model;
+pub mod requests;
+
+pub use model::PasswordPolicy;
+pub use requests::UpdateAccountPasswordPolicyRequest;
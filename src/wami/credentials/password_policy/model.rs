@@ -0,0 +1,142 @@
+//! PasswordPolicy Domain Model
+
+use crate::error::{AmiError, Result};
+use serde::{Deserialize, Serialize};
+
+/// Password requirements applied to the login profiles of an account
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PasswordPolicy {
+    /// Minimum number of characters allowed in a password
+    pub minimum_password_length: u32,
+    /// Whether passwords must contain at least one symbol
+    pub require_symbols: bool,
+    /// Whether passwords must contain at least one number
+    pub require_numbers: bool,
+    /// Whether passwords must contain at least one uppercase letter
+    pub require_uppercase_characters: bool,
+    /// Whether passwords must contain at least one lowercase letter
+    pub require_lowercase_characters: bool,
+    /// Whether users may change their own password
+    pub allow_users_to_change_password: bool,
+    /// Number of days a password stays valid, if passwords expire
+    pub max_password_age: Option<u32>,
+    /// Number of previous passwords that cannot be reused
+    pub password_reuse_prevention: Option<u32>,
+}
+
+impl Default for PasswordPolicy {
+    /// The policy AWS applies when none is set: six characters, no other rules
+    fn default() -> Self {
+        Self {
+            minimum_password_length: 6,
+            require_symbols: false,
+            require_numbers: false,
+            require_uppercase_characters: false,
+            require_lowercase_characters: false,
+            allow_users_to_change_password: false,
+            max_password_age: None,
+            password_reuse_prevention: None,
+        }
+    }
+}
+
+impl PasswordPolicy {
+    /// Whether passwords governed by this policy expire
+    pub fn expire_passwords(&self) -> bool {
+        self.max_password_age.is_some()
+    }
+
+    /// Check a password against the composition rules of this policy
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` naming the first rule the password breaks.
+    #[allow(clippy::result_large_err)]
+    pub fn validate_password(&self, password: &str) -> Result<()> {
+        let violation = if (password.chars().count() as u32) < self.minimum_password_length {
+            Some(format!(
+                "at least {} characters",
+                self.minimum_password_length
+            ))
+        } else if self.require_symbols && !password.chars().any(|c| c.is_ascii_punctuation()) {
+            Some("at least one symbol".to_string())
+        } else if self.require_numbers && !password.chars().any(|c| c.is_ascii_digit()) {
+            Some("at least one number".to_string())
+        } else if self.require_uppercase_characters && !password.chars().any(char::is_uppercase) {
+            Some("at least one uppercase letter".to_string())
+        } else if self.require_lowercase_characters && !password.chars().any(char::is_lowercase) {
+            Some("at least one lowercase letter".to_string())
+        } else {
+            None
+        };
+
+        match violation {
+            Some(rule) => Err(AmiError::InvalidParameter {
+                message: format!(
+                    "Password does not conform to the account password policy: it must contain {}",
+                    rule
+                ),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strict_policy() -> PasswordPolicy {
+        PasswordPolicy {
+            minimum_password_length: 10,
+            require_symbols: true,
+            require_numbers: true,
+            require_uppercase_characters: true,
+            require_lowercase_characters: true,
+            ..PasswordPolicy::default()
+        }
+    }
+
+    fn assert_rejected(policy: &PasswordPolicy, password: &str, rule: &str) {
+        match policy.validate_password(password) {
+            Err(AmiError::InvalidParameter { message }) => {
+                assert!(message.contains(rule), "{}: {}", password, message)
+            }
+            other => panic!("{} should be rejected, got {:?}", password, other),
+        }
+    }
+
+    #[test]
+    fn test_strict_policy_accepts_conforming_password() {
+        assert!(strict_policy().validate_password("Str0ng!Pass").is_ok());
+    }
+
+    #[test]
+    fn test_minimum_length_rule() {
+        assert_rejected(&strict_policy(), "Sh0rt!", "at least 10 characters");
+        assert!(PasswordPolicy::default()
+            .validate_password("abcdef")
+            .is_ok());
+        assert_rejected(&PasswordPolicy::default(), "abcde", "at least 6 characters");
+    }
+
+    #[test]
+    fn test_symbol_rule() {
+        assert_rejected(&strict_policy(), "NoSymbols123", "symbol");
+    }
+
+    #[test]
+    fn test_number_rule() {
+        assert_rejected(&strict_policy(), "NoNumbers!!x", "number");
+    }
+
+    #[test]
+    fn test_uppercase_rule() {
+        assert_rejected(&strict_policy(), "no-upper-123", "uppercase");
+    }
+
+    #[test]
+    fn test_lowercase_rule() {
+        assert_rejected(&strict_policy(), "NO-LOWER-123", "lowercase");
+    }
+}
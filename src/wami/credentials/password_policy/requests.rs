@@ -0,0 +1,32 @@
+//! PasswordPolicy Request Types
+
+use serde::{Deserialize, Serialize};
+
+/// Request to set the account password policy
+///
+/// The request replaces the whole policy: omitted settings take their
+/// default value rather than keeping the current one.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateAccountPasswordPolicyRequest {
+    /// Minimum number of characters allowed in a password (6-128, default 6)
+    pub minimum_password_length: Option<u32>,
+    /// Whether passwords must contain at least one symbol
+    #[serde(default)]
+    pub require_symbols: bool,
+    /// Whether passwords must contain at least one number
+    #[serde(default)]
+    pub require_numbers: bool,
+    /// Whether passwords must contain at least one uppercase letter
+    #[serde(default)]
+    pub require_uppercase_characters: bool,
+    /// Whether passwords must contain at least one lowercase letter
+    #[serde(default)]
+    pub require_lowercase_characters: bool,
+    /// Whether users may change their own password
+    #[serde(default)]
+    pub allow_users_to_change_password: bool,
+    /// Number of days a password stays valid (1-1095); passwords never expire if omitted
+    pub max_password_age: Option<u32>,
+    /// Number of previous passwords that cannot be reused (1-24)
+    pub password_reuse_prevention: Option<u32>,
+}
@@ -4,10 +4,14 @@ use super::model::*;
 use crate::arn::{Service, WamiArn};
 use crate::context::WamiContext;
 use crate::error::Result;
+use crate::wami::credentials::x509;
 use chrono::Utc;
 use uuid::Uuid;
 
 /// Build a new ServerCertificate resource with context-based identifiers
+///
+/// The body must be a valid X.509 certificate; its `notAfter` becomes the
+/// metadata's expiration.
 #[allow(clippy::result_large_err)]
 pub fn build_server_certificate(
     server_certificate_name: String,
@@ -17,6 +21,7 @@ pub fn build_server_certificate(
     tags: Vec<crate::types::Tag>,
     context: &WamiContext,
 ) -> Result<ServerCertificate> {
+    let details = x509::parse_certificate(&certificate_body)?;
     let cert_id = Uuid::new_v4().to_string();
 
     // Build WAMI ARN using context
@@ -41,7 +46,7 @@ pub fn build_server_certificate(
         arn,
         server_certificate_id: cert_id,
         upload_date: Utc::now(),
-        expiration: None, // Would need to parse cert to get actual expiration
+        expiration: Some(details.not_after),
     };
 
     Ok(ServerCertificate {
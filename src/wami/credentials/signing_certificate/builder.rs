@@ -4,16 +4,21 @@ use super::model::*;
 use crate::arn::{Service, WamiArn};
 use crate::context::WamiContext;
 use crate::error::Result;
+use crate::wami::credentials::x509;
 use chrono::Utc;
 use uuid::Uuid;
 
-/// Build a new SigningCertificate resource with context-based identifiers
+/// Build a new, active SigningCertificate resource with context-based identifiers
+///
+/// The body must be a valid X.509 certificate, in PEM or base64 DER form;
+/// its serial number and fingerprint are recorded on the resource.
 #[allow(clippy::result_large_err)]
 pub fn build_signing_certificate(
     user_name: String,
     certificate_body: String,
     context: &WamiContext,
 ) -> Result<SigningCertificate> {
+    let details = x509::parse_certificate(&certificate_body)?;
     let certificate_id = Uuid::new_v4().to_string();
 
     // Build WAMI ARN using context
@@ -28,6 +33,8 @@ pub fn build_signing_certificate(
         user_name,
         certificate_id,
         certificate_body,
+        serial_number: details.serial_number,
+        fingerprint: details.fingerprint,
         status: CertificateStatus::Active,
         upload_date: Utc::now(),
         wami_arn,
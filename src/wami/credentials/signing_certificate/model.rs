@@ -28,6 +28,14 @@ pub struct SigningCertificate {
     #[serde(rename = "CertificateBody")]
     pub certificate_body: String,
 
+    /// The serial number of the certificate, as uppercase hex
+    #[serde(rename = "SerialNumber", default)]
+    pub serial_number: String,
+
+    /// The SHA-1 fingerprint of the certificate, as uppercase hex
+    #[serde(rename = "Fingerprint", default)]
+    pub fingerprint: String,
+
     /// The status of the signing certificate
     #[serde(rename = "Status")]
     pub status: CertificateStatus,
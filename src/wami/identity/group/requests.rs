@@ -26,6 +26,9 @@ pub struct UpdateGroupRequest {
 pub struct ListGroupsRequest {
     pub path_prefix: Option<String>,
     pub pagination: Option<PaginationParams>,
+    /// Also list groups of the caller's descendant tenants
+    #[serde(default)]
+    pub include_descendants: bool,
 }
 
 /// Response for listing groups
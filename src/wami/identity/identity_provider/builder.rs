@@ -2,7 +2,7 @@
 //!
 //! Pure functions for building and manipulating identity provider resources.
 
-use super::model::{OidcProvider, SamlProvider};
+use super::model::{OidcProvider, SamlMetadata, SamlProvider};
 use crate::arn::{Service, WamiArn};
 use crate::context::WamiContext;
 use crate::error::Result;
@@ -44,6 +44,8 @@ pub fn build_saml_provider(
         providers: Vec::new(),
         tenant_id: None,
         usage_count: 0,
+        entity_id: None,
+        signing_certificates: Vec::new(),
     })
 }
 
@@ -99,6 +101,16 @@ pub fn update_saml_metadata(
     provider
 }
 
+/// Record the entity ID and signing certificates parsed from a SAML provider's metadata (pure function)
+pub fn set_saml_metadata_details(
+    mut provider: SamlProvider,
+    metadata: SamlMetadata,
+) -> SamlProvider {
+    provider.entity_id = Some(metadata.entity_id);
+    provider.signing_certificates = metadata.signing_certificates;
+    provider
+}
+
 /// Set the valid_until date for a SAML provider (pure function)
 pub fn set_saml_valid_until(
     mut provider: SamlProvider,
@@ -6,7 +6,7 @@ pub mod operations;
 pub mod requests;
 
 // Re-export types
-pub use model::{OidcProvider, SamlProvider};
+pub use model::{OidcProvider, SamlMetadata, SamlProvider};
 pub use requests::{
     AddClientIDToOpenIDConnectProviderRequest, CreateOpenIDConnectProviderRequest,
     CreateSAMLProviderRequest, DeleteOpenIDConnectProviderRequest, DeleteSAMLProviderRequest,
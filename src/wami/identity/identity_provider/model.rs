@@ -27,6 +27,8 @@ use serde::{Deserialize, Serialize};
 ///     providers: vec![],
 ///     tenant_id: None,
 ///     usage_count: 0,
+///     entity_id: Some("http://www.okta.com/exampleid".to_string()),
+///     signing_certificates: vec![],
 /// };
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -51,6 +53,21 @@ pub struct SamlProvider {
     pub tenant_id: Option<crate::wami::tenant::TenantId>,
     /// Number of principals using this provider (for audit/tracking)
     pub usage_count: u64,
+    /// The `entityID` declared by the metadata document
+    #[serde(default)]
+    pub entity_id: Option<String>,
+    /// Base64-encoded signing certificates declared by the metadata document
+    #[serde(default)]
+    pub signing_certificates: Vec<String>,
+}
+
+/// Identity provider details read from a SAML metadata document
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SamlMetadata {
+    /// The `entityID` of the `EntityDescriptor`
+    pub entity_id: String,
+    /// Base64-encoded X.509 certificates of the signing key descriptors
+    pub signing_certificates: Vec<String>,
 }
 
 /// Represents an OpenID Connect (OIDC) identity provider
@@ -4,33 +4,71 @@
 
 #![allow(clippy::result_large_err)]
 
+use super::model::SamlMetadata;
 use crate::error::{AmiError, Result};
 use chrono::{DateTime, Utc};
 
 /// Validate SAML metadata document (XML format)
 ///
-/// Performs basic XML validation and checks for required SAML elements.
+/// The document must be well-formed XML with an `EntityDescriptor` declaring
+/// an `entityID`; see [`parse_saml_metadata`].
 pub fn validate_saml_metadata(metadata: &str) -> Result<()> {
+    parse_saml_metadata(metadata).map(|_| ())
+}
+
+/// Parse a SAML metadata document
+///
+/// Reads the `entityID` of the first `EntityDescriptor` (which may be nested
+/// in an `EntitiesDescriptor`) and the X.509 certificates of its signing key
+/// descriptors. Key descriptors without a `use` attribute count as signing
+/// keys, as in the SAML specification.
+pub fn parse_saml_metadata(metadata: &str) -> Result<SamlMetadata> {
+    let invalid = |message: String| AmiError::InvalidParameter { message };
+
     if metadata.is_empty() {
-        return Err(AmiError::InvalidParameter {
-            message: "SAML metadata document cannot be empty".to_string(),
-        });
+        return Err(invalid(
+            "SAML metadata document cannot be empty".to_string(),
+        ));
     }
 
-    // Parse XML to ensure it's well-formed
-    roxmltree::Document::parse(metadata).map_err(|e| AmiError::InvalidParameter {
-        message: format!("Invalid SAML metadata XML: {}", e),
-    })?;
-
-    // Check for basic SAML structure
-    if !metadata.contains("EntityDescriptor") && !metadata.contains("EntitiesDescriptor") {
-        return Err(AmiError::InvalidParameter {
-            message: "SAML metadata must contain EntityDescriptor or EntitiesDescriptor"
-                .to_string(),
-        });
+    let doc = roxmltree::Document::parse(metadata)
+        .map_err(|e| invalid(format!("Invalid SAML metadata XML: {}", e)))?;
+
+    let entity = doc
+        .descendants()
+        .find(|node| node.tag_name().name() == "EntityDescriptor")
+        .ok_or_else(|| invalid("SAML metadata must contain an EntityDescriptor".to_string()))?;
+
+    let entity_id = entity
+        .attribute("entityID")
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .ok_or_else(|| invalid("SAML EntityDescriptor must have an entityID".to_string()))?
+        .to_string();
+
+    let mut signing_certificates: Vec<String> = Vec::new();
+    let signing_keys = entity.descendants().filter(|node| {
+        node.tag_name().name() == "KeyDescriptor"
+            && matches!(node.attribute("use"), None | Some("signing"))
+    });
+    for key in signing_keys {
+        let certificates = key
+            .descendants()
+            .filter(|node| node.tag_name().name() == "X509Certificate")
+            .filter_map(|node| node.text())
+            .map(|text| text.split_whitespace().collect::<String>())
+            .filter(|certificate| !certificate.is_empty());
+        for certificate in certificates {
+            if !signing_certificates.contains(&certificate) {
+                signing_certificates.push(certificate);
+            }
+        }
     }
 
-    Ok(())
+    Ok(SamlMetadata {
+        entity_id,
+        signing_certificates,
+    })
 }
 
 /// Extract validity period from SAML metadata
@@ -137,6 +175,25 @@ pub fn validate_thumbprint_list(thumbprints: &[String]) -> Result<()> {
     Ok(())
 }
 
+/// Validate and normalize a thumbprint list
+///
+/// Each thumbprint must be a SHA-1 hex string. Thumbprints are lowercased and
+/// duplicates removed, keeping the first occurrence, before checking the
+/// AWS limit of one to five thumbprints.
+pub fn normalize_thumbprint_list(thumbprints: Vec<String>) -> Result<Vec<String>> {
+    let mut normalized: Vec<String> = Vec::with_capacity(thumbprints.len());
+    for thumbprint in thumbprints {
+        validate_thumbprint(&thumbprint)?;
+        let thumbprint = thumbprint.to_ascii_lowercase();
+        if !normalized.contains(&thumbprint) {
+            normalized.push(thumbprint);
+        }
+    }
+
+    validate_thumbprint_list(&normalized)?;
+    Ok(normalized)
+}
+
 /// Validate client ID list
 ///
 /// Ensures client IDs are valid and the list isn't too large.
@@ -205,7 +262,7 @@ mod tests {
     #[test]
     fn test_validate_saml_metadata() {
         let valid_metadata = r#"<?xml version="1.0"?>
-            <EntityDescriptor xmlns="urn:oasis:names:tc:SAML:2.0:metadata">
+            <EntityDescriptor xmlns="urn:oasis:names:tc:SAML:2.0:metadata" entityID="https://idp.example.com">
                 <IDPSSODescriptor>
                     <SingleSignOnService Binding="urn:oasis:names:tc:SAML:2.0:bindings:HTTP-POST" />
                 </IDPSSODescriptor>
@@ -215,6 +272,40 @@ mod tests {
         assert!(validate_saml_metadata("").is_err());
         assert!(validate_saml_metadata("not xml").is_err());
         assert!(validate_saml_metadata("<xml>no saml elements</xml>").is_err());
+        assert!(
+            validate_saml_metadata("<EntityDescriptor><IDPSSODescriptor/></EntityDescriptor>")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_saml_metadata() {
+        let metadata = r#"<?xml version="1.0"?>
+            <md:EntitiesDescriptor xmlns:md="urn:oasis:names:tc:SAML:2.0:metadata" xmlns:ds="http://www.w3.org/2000/09/xmldsig#">
+                <md:EntityDescriptor entityID="https://idp.example.com/saml">
+                    <md:IDPSSODescriptor>
+                        <md:KeyDescriptor use="signing">
+                            <ds:KeyInfo><ds:X509Data><ds:X509Certificate>
+                                MIIC8DCCAdigAwIBAgIQ
+                                SIGNING1
+                            </ds:X509Certificate></ds:X509Data></ds:KeyInfo>
+                        </md:KeyDescriptor>
+                        <md:KeyDescriptor use="encryption">
+                            <ds:KeyInfo><ds:X509Data><ds:X509Certificate>ENCRYPTION</ds:X509Certificate></ds:X509Data></ds:KeyInfo>
+                        </md:KeyDescriptor>
+                        <md:KeyDescriptor>
+                            <ds:KeyInfo><ds:X509Data><ds:X509Certificate>SIGNING2</ds:X509Certificate></ds:X509Data></ds:KeyInfo>
+                        </md:KeyDescriptor>
+                    </md:IDPSSODescriptor>
+                </md:EntityDescriptor>
+            </md:EntitiesDescriptor>"#;
+
+        let parsed = parse_saml_metadata(metadata).unwrap();
+        assert_eq!(parsed.entity_id, "https://idp.example.com/saml");
+        assert_eq!(
+            parsed.signing_certificates,
+            ["MIIC8DCCAdigAwIBAgIQSIGNING1", "SIGNING2"]
+        );
     }
 
     #[test]
@@ -42,6 +42,9 @@ pub struct ListRolesRequest {
     pub path_prefix: Option<String>,
     /// Pagination parameters
     pub pagination: Option<PaginationParams>,
+    /// Also list roles of the caller's descendant tenants
+    #[serde(default)]
+    pub include_descendants: bool,
 }
 
 /// Response for listing roles
@@ -100,6 +100,7 @@ impl RootUser {
             tags: vec![],
             providers: vec![],
             tenant_id: None,
+            expires_at: None,
         };
 
         Self { user }
@@ -14,3 +14,24 @@ pub fn build_deletion_task(role_name: String) -> DeletionTaskInfo {
         create_date: chrono::Utc::now(),
     }
 }
+
+/// Mark a deletion task as succeeded
+pub fn succeed_deletion_task(mut task: DeletionTaskInfo) -> DeletionTaskInfo {
+    task.status = DeletionTaskStatus::Succeeded;
+    task.failure_reason = None;
+    task
+}
+
+/// Mark a deletion task as failed because the role is still in use
+pub fn fail_deletion_task(
+    mut task: DeletionTaskInfo,
+    reason: String,
+    role_usage_list: Vec<RoleUsageType>,
+) -> DeletionTaskInfo {
+    task.status = DeletionTaskStatus::Failed;
+    task.failure_reason = Some(DeletionTaskFailureReason {
+        reason,
+        role_usage_list,
+    });
+    task
+}
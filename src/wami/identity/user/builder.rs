@@ -5,7 +5,7 @@ use crate::arn::{Service, WamiArn};
 use crate::context::WamiContext;
 use crate::error::Result;
 use crate::provider::{CloudProvider, ResourceType};
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
 /// Build a new User with context-based identifiers
@@ -42,6 +42,7 @@ pub fn build_user(user_name: String, path: Option<String>, context: &WamiContext
         wami_arn,
         providers: Vec::new(),
         tenant_id: None,
+        expires_at: None,
     })
 }
 
@@ -84,6 +85,7 @@ pub fn build_user_legacy(
         wami_arn,
         providers: Vec::new(),
         tenant_id: None,
+        expires_at: None,
     }
 }
 
@@ -120,6 +122,12 @@ pub fn set_tenant_id(mut user: User, tenant_id: crate::wami::tenant::TenantId) -
     user
 }
 
+/// Set when a temporary user expires (pure transformation)
+pub fn set_expiration(mut user: User, expires_at: DateTime<Utc>) -> User {
+    user.expires_at = Some(expires_at);
+    user
+}
+
 /// Clear user's permissions boundary (pure transformation)
 pub fn clear_permissions_boundary(mut user: User) -> User {
     user.permissions_boundary = None;
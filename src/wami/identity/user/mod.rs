@@ -54,4 +54,7 @@ pub mod requests;
 pub use model::User;
 // Operations moved to service layer - pure functions remain here
 // pub use operations::UserOperations;
-pub use requests::{CreateUserRequest, ListUsersRequest, ListUsersResponse, UpdateUserRequest};
+pub use requests::{
+    BulkFailure, BulkResult, CreateUserRequest, ListUsersRequest, ListUsersResponse,
+    UpdateUserRequest,
+};
@@ -37,6 +37,7 @@ use serde::{Deserialize, Serialize};
 ///     wami_arn,
 ///     providers: vec![],
 ///     tenant_id: None,
+///     expires_at: None,
 /// };
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -63,6 +64,11 @@ pub struct User {
     pub providers: Vec<crate::provider::ProviderConfig>,
     /// Optional tenant ID for multi-tenant isolation
     pub tenant_id: Option<crate::wami::tenant::TenantId>,
+    /// When a temporary user expires (None = permanent)
+    ///
+    /// Once expired the user is treated as nonexistent until it is pruned.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
 }
 
 impl User {
@@ -90,6 +96,13 @@ impl User {
         }
         Ok(())
     }
+
+    /// Whether the user has expired as of `now`
+    ///
+    /// Permanent users (without `expires_at`) never expire.
+    pub fn is_expired_at(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
 }
 
 #[cfg(test)]
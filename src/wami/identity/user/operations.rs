@@ -99,6 +99,7 @@ mod tests {
             wami_arn: "arn:wami:iam::tenant-hash:user/alice".to_string(),
             providers: vec![],
             tenant_id: Some(tenant_id.clone()),
+            expires_at: None,
         };
 
         assert!(user_operations::belongs_to_tenant(&user, &tenant_id));
@@ -125,6 +126,7 @@ mod tests {
                 wami_arn: "arn:wami:1".to_string(),
                 providers: vec![],
                 tenant_id: Some(tenant1.clone()),
+                expires_at: None,
             },
             User {
                 user_name: "bob".to_string(),
@@ -138,6 +140,7 @@ mod tests {
                 wami_arn: "arn:wami:2".to_string(),
                 providers: vec![],
                 tenant_id: Some(tenant2.clone()),
+                expires_at: None,
             },
         ];
 
@@ -3,6 +3,7 @@
 use serde::{Deserialize, Serialize};
 
 use super::model::User;
+use crate::error::AmiError;
 use crate::types::{PaginationParams, Tag};
 
 /// Parameters for creating a user
@@ -27,6 +28,9 @@ pub struct UpdateUserRequest {
 pub struct ListUsersRequest {
     pub path_prefix: Option<String>,
     pub pagination: Option<PaginationParams>,
+    /// Also list users of the caller's descendant tenants
+    #[serde(default)]
+    pub include_descendants: bool,
 }
 
 /// Response for listing users
@@ -36,3 +40,32 @@ pub struct ListUsersResponse {
     pub is_truncated: bool,
     pub marker: Option<String>,
 }
+
+/// Outcome of a bulk user import
+///
+/// Every request is reported, either as a created user or as a failure.
+#[derive(Debug, Default)]
+pub struct BulkResult {
+    /// The users created, in request order
+    pub succeeded: Vec<User>,
+    /// The requests that failed, in request order
+    pub failed: Vec<BulkFailure>,
+}
+
+impl BulkResult {
+    /// Whether every request succeeded
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+/// A request of a bulk import that failed
+#[derive(Debug)]
+pub struct BulkFailure {
+    /// Position of the request in the batch
+    pub index: usize,
+    /// Name of the user the request was for
+    pub user_name: String,
+    /// Why the request failed
+    pub error: AmiError,
+}
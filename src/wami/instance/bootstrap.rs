@@ -69,7 +69,7 @@ pub struct RootCredentials {
     /// The secret access key (private, like a password)
     ///
     /// **SECURITY:** This is shown in plaintext ONLY during initialization.
-    /// It is stored as a bcrypt hash and cannot be retrieved later.
+    /// It is stored as an Argon2 hash and cannot be retrieved later.
     pub secret_access_key: String,
 
     /// The instance ID this root user belongs to
@@ -88,11 +88,11 @@ impl InstanceBootstrap {
     /// This creates:
     /// 1. A root user with ARN: `arn:wami:iam:0:wami:{instance_id}:user/root`
     /// 2. An access key pair for the root user
-    /// 3. Securely hashed secret (bcrypt)
+    /// 3. Securely hashed secret (Argon2id)
     ///
     /// # Security
     ///
-    /// - Access key secret is hashed with bcrypt before storage
+    /// - Access key secret is hashed with Argon2id before storage
     /// - Secret is returned in plaintext ONLY during this initialization
     /// - Secrets cannot be retrieved later (by design)
     /// - Root access requires these credentials (prevents brute force attacks)
@@ -169,6 +169,7 @@ impl InstanceBootstrap {
             tags: vec![],
             providers: vec![],
             tenant_id: None,
+            expires_at: None,
         };
 
         // Generate access key credentials
@@ -195,6 +196,7 @@ impl InstanceBootstrap {
             secret_access_key: Some(secret_hash), // Stored as hash!
             wami_arn: access_key_arn,
             providers: vec![],
+            last_used: None,
         };
 
         // Store root user
@@ -43,14 +43,17 @@ pub mod credentials {
     pub mod access_key;
     pub mod login_profile;
     pub mod mfa_device;
+    pub mod password_policy;
     pub mod server_certificate;
     pub mod service_credential;
     pub mod signing_certificate;
+    pub mod x509;
 
     // Re-export types for convenience
     pub use access_key::AccessKey;
     pub use login_profile::LoginProfile;
     pub use mfa_device::MfaDevice;
+    pub use password_policy::PasswordPolicy;
     pub use server_certificate::ServerCertificate;
     pub use service_credential::ServiceSpecificCredential;
     pub use signing_certificate::SigningCertificate;
@@ -74,9 +77,11 @@ pub mod policies {
 
 /// Report generation and auditing
 pub mod reports {
+    pub mod authorization_details;
     pub mod credential_report;
 
     // Re-export types for convenience
+    pub use authorization_details::AccountAuthorizationDetails;
     pub use credential_report::CredentialReport;
 }
 
@@ -0,0 +1,440 @@
+//! Policy Condition Evaluation
+//!
+//! Pure functions for evaluating the `Condition` block of a policy statement
+//! against the context entries supplied with a request.
+//!
+//! A condition block has the shape `{ "<Operator>": { "<key>": <value(s)> } }`.
+//! All operators and all keys must match (logical AND), while multiple values
+//! for a single key match if any of them does (logical OR).
+//!
+//! Any operator may carry the `IfExists` suffix (e.g. `StringLikeIfExists`).
+//! Such a condition passes when the key is absent from the request context and
+//! is evaluated with the base operator when the key is present.
+//!
+//! `IpAddress` and `NotIpAddress` compare IPv4 or IPv6 addresses against CIDR
+//! blocks. They fail closed: an unparsable block in the policy or address in
+//! the context makes the condition not match.
+
+use super::model::ContextEntry;
+use ipnet::IpNet;
+use serde_json::Value;
+use std::net::IpAddr;
+
+/// Suffix that makes a condition operator pass when its key is absent
+const IF_EXISTS_SUFFIX: &str = "IfExists";
+
+/// Split an operator name into its base operator and whether it carries `IfExists`
+///
+/// `StringLikeIfExists` becomes `("StringLike", true)`.
+pub fn parse_operator(operator: &str) -> (&str, bool) {
+    match operator.strip_suffix(IF_EXISTS_SUFFIX) {
+        Some(base) if !base.is_empty() => (base, true),
+        _ => (operator, false),
+    }
+}
+
+/// Look up the values of a context key (key names are case-insensitive)
+pub fn find_context_values<'a>(context: &'a [ContextEntry], key: &str) -> Option<&'a [String]> {
+    context
+        .iter()
+        .find(|entry| entry.context_key_name.eq_ignore_ascii_case(key))
+        .map(|entry| entry.context_key_values.as_slice())
+}
+
+/// Evaluate a statement's condition block against the request context
+///
+/// Returns `true` when there is no condition block or every condition matches.
+pub fn evaluate_conditions(condition: Option<&Value>, context: &[ContextEntry]) -> bool {
+    let Some(condition) = condition else {
+        return true;
+    };
+
+    let Some(operators) = condition.as_object() else {
+        return false;
+    };
+
+    operators.iter().all(|(operator, keys)| {
+        let Some(keys) = keys.as_object() else {
+            return false;
+        };
+        keys.iter().all(|(key, expected)| {
+            evaluate_condition(operator, find_context_values(context, key), expected)
+        })
+    })
+}
+
+/// Collect the condition keys a statement needs but the context does not provide
+///
+/// Keys guarded by an `IfExists` operator or the `Null` operator are never
+/// reported, since their absence is a valid input.
+pub fn missing_context_keys(condition: Option<&Value>, context: &[ContextEntry]) -> Vec<String> {
+    let Some(operators) = condition.and_then(Value::as_object) else {
+        return Vec::new();
+    };
+
+    let mut missing = Vec::new();
+    for (operator, keys) in operators {
+        let (base, if_exists) = parse_operator(operator);
+        if if_exists || base == "Null" {
+            continue;
+        }
+        if let Some(keys) = keys.as_object() {
+            for key in keys.keys() {
+                if find_context_values(context, key).is_none() && !missing.contains(key) {
+                    missing.push(key.clone());
+                }
+            }
+        }
+    }
+    missing
+}
+
+/// Evaluate a single operator/key pair
+///
+/// `actual` holds the context values for the key, or `None` if the key is absent.
+pub fn evaluate_condition(operator: &str, actual: Option<&[String]>, expected: &Value) -> bool {
+    let (base, if_exists) = parse_operator(operator);
+    let expected = expected_values(expected);
+
+    if base == "Null" {
+        // "Null": "true" means the key must be absent, "false" that it must be present
+        let is_absent = actual.is_none_or(|values| values.is_empty());
+        return expected
+            .iter()
+            .any(|value| value.eq_ignore_ascii_case(&is_absent.to_string()));
+    }
+
+    let actual = match actual {
+        Some(values) if !values.is_empty() => values,
+        // Absent keys satisfy IfExists and negated operators, and fail everything else
+        _ => return if_exists || is_negated(base),
+    };
+
+    if matches!(base, "IpAddress" | "NotIpAddress") {
+        return evaluate_ip_condition(base == "NotIpAddress", actual, &expected);
+    }
+
+    if is_negated(base) {
+        let positive = positive_operator(base);
+        return !actual
+            .iter()
+            .any(|value| matches_any(positive, value, &expected));
+    }
+
+    actual
+        .iter()
+        .any(|value| matches_any(base, value, &expected))
+}
+
+/// Whether an operator is the negation of another one
+fn is_negated(base: &str) -> bool {
+    matches!(
+        base,
+        "StringNotEquals"
+            | "StringNotEqualsIgnoreCase"
+            | "StringNotLike"
+            | "NumericNotEquals"
+            | "DateNotEquals"
+            | "ArnNotEquals"
+            | "ArnNotLike"
+            | "NotIpAddress"
+    )
+}
+
+/// Map a negated operator to its positive counterpart
+fn positive_operator(base: &str) -> &str {
+    match base {
+        "StringNotEquals" => "StringEquals",
+        "StringNotEqualsIgnoreCase" => "StringEqualsIgnoreCase",
+        "StringNotLike" => "StringLike",
+        "NumericNotEquals" => "NumericEquals",
+        "DateNotEquals" => "DateEquals",
+        "ArnNotEquals" => "ArnEquals",
+        "ArnNotLike" => "ArnLike",
+        other => other,
+    }
+}
+
+/// Check a single context value against any of the expected values
+fn matches_any(base: &str, actual: &str, expected: &[String]) -> bool {
+    expected
+        .iter()
+        .any(|expected| matches_value(base, actual, expected))
+}
+
+/// Apply a positive base operator to a single pair of values
+///
+/// Unknown operators never match, so a statement with an unsupported
+/// condition fails closed.
+fn matches_value(base: &str, actual: &str, expected: &str) -> bool {
+    match base {
+        "StringEquals" | "ArnEquals" => actual == expected,
+        "StringEqualsIgnoreCase" => actual.eq_ignore_ascii_case(expected),
+        "StringLike" | "ArnLike" => wildcard_match(expected, actual),
+        "Bool" => actual.eq_ignore_ascii_case(expected),
+        "NumericEquals" => compare_numeric(actual, expected, |a, e| a == e),
+        "NumericLessThan" => compare_numeric(actual, expected, |a, e| a < e),
+        "NumericLessThanEquals" => compare_numeric(actual, expected, |a, e| a <= e),
+        "NumericGreaterThan" => compare_numeric(actual, expected, |a, e| a > e),
+        "NumericGreaterThanEquals" => compare_numeric(actual, expected, |a, e| a >= e),
+        "DateEquals" => compare_date(actual, expected, |a, e| a == e),
+        "DateLessThan" => compare_date(actual, expected, |a, e| a < e),
+        "DateLessThanEquals" => compare_date(actual, expected, |a, e| a <= e),
+        "DateGreaterThan" => compare_date(actual, expected, |a, e| a > e),
+        "DateGreaterThanEquals" => compare_date(actual, expected, |a, e| a >= e),
+        _ => false,
+    }
+}
+
+/// Normalize the expected value(s) of a condition key into strings
+fn expected_values(expected: &Value) -> Vec<String> {
+    match expected {
+        Value::Array(values) => values.iter().filter_map(scalar_to_string).collect(),
+        other => scalar_to_string(other).into_iter().collect(),
+    }
+}
+
+fn scalar_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Bool(b) => Some(b.to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+fn compare_numeric(actual: &str, expected: &str, cmp: impl Fn(f64, f64) -> bool) -> bool {
+    match (actual.parse::<f64>(), expected.parse::<f64>()) {
+        (Ok(a), Ok(e)) => cmp(a, e),
+        _ => false,
+    }
+}
+
+fn compare_date(actual: &str, expected: &str, cmp: impl Fn(i64, i64) -> bool) -> bool {
+    match (parse_date(actual), parse_date(expected)) {
+        (Some(a), Some(e)) => cmp(a, e),
+        _ => false,
+    }
+}
+
+/// Parse an ISO 8601 date or an epoch timestamp into epoch seconds
+fn parse_date(value: &str) -> Option<i64> {
+    chrono::DateTime::parse_from_rfc3339(value)
+        .map(|date| date.timestamp())
+        .ok()
+        .or_else(|| value.parse::<i64>().ok())
+}
+
+/// Evaluate `IpAddress` (or `NotIpAddress` when `negated`) against CIDR blocks
+fn evaluate_ip_condition(negated: bool, actual: &[String], expected: &[String]) -> bool {
+    let Some(networks) = expected
+        .iter()
+        .map(|cidr| parse_ip_network(cidr))
+        .collect::<Option<Vec<_>>>()
+    else {
+        return false;
+    };
+    let Some(addresses) = actual
+        .iter()
+        .map(|ip| ip.trim().parse::<IpAddr>().ok())
+        .collect::<Option<Vec<_>>>()
+    else {
+        return false;
+    };
+
+    let in_range = addresses
+        .iter()
+        .any(|ip| networks.iter().any(|network| network.contains(ip)));
+    in_range != negated
+}
+
+/// Parse a CIDR block; a bare address is treated as a single-host network
+fn parse_ip_network(cidr: &str) -> Option<IpNet> {
+    let cidr = cidr.trim();
+    cidr.parse::<IpNet>()
+        .ok()
+        .or_else(|| cidr.parse::<IpAddr>().ok().map(IpNet::from))
+}
+
+/// Match a value against a pattern supporting `*` (any run) and `?` (any single char)
+pub fn wildcard_match(pattern: &str, value: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let value: Vec<char> = value.chars().collect();
+
+    let (mut p, mut v) = (0, 0);
+    let mut star: Option<usize> = None;
+    let mut star_v = 0;
+
+    while v < value.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == value[v]) {
+            p += 1;
+            v += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some(p);
+            star_v = v;
+            p += 1;
+        } else if let Some(star_p) = star {
+            p = star_p + 1;
+            star_v += 1;
+            v = star_v;
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn entry(key: &str, values: &[&str]) -> ContextEntry {
+        ContextEntry {
+            context_key_name: key.to_string(),
+            context_key_values: values.iter().map(|v| v.to_string()).collect(),
+            context_key_type: "string".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_operator() {
+        assert_eq!(parse_operator("StringLike"), ("StringLike", false));
+        assert_eq!(parse_operator("StringLikeIfExists"), ("StringLike", true));
+        assert_eq!(parse_operator("IfExists"), ("IfExists", false));
+    }
+
+    #[test]
+    fn test_if_exists_passes_when_key_absent() {
+        let condition = json!({
+            "StringLikeIfExists": { "aws:PrincipalTag/team": "eng-*" }
+        });
+
+        assert!(evaluate_conditions(Some(&condition), &[]));
+        assert!(missing_context_keys(Some(&condition), &[]).is_empty());
+    }
+
+    #[test]
+    fn test_if_exists_enforced_when_key_present() {
+        let condition = json!({
+            "StringLikeIfExists": { "aws:PrincipalTag/team": "eng-*" }
+        });
+
+        let matching = vec![entry("aws:PrincipalTag/team", &["eng-platform"])];
+        assert!(evaluate_conditions(Some(&condition), &matching));
+
+        let wrong = vec![entry("aws:PrincipalTag/team", &["sales"])];
+        assert!(!evaluate_conditions(Some(&condition), &wrong));
+    }
+
+    #[test]
+    fn test_plain_operator_fails_when_key_absent() {
+        let condition = json!({ "StringEquals": { "aws:username": "alice" } });
+
+        assert!(!evaluate_conditions(Some(&condition), &[]));
+        assert_eq!(
+            missing_context_keys(Some(&condition), &[]),
+            vec!["aws:username".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_numeric_if_exists() {
+        let condition = json!({
+            "NumericLessThanIfExists": { "aws:MultiFactorAuthAge": "3600" }
+        });
+
+        assert!(evaluate_conditions(Some(&condition), &[]));
+        let fresh = vec![entry("aws:MultiFactorAuthAge", &["60"])];
+        assert!(evaluate_conditions(Some(&condition), &fresh));
+        let stale = vec![entry("aws:MultiFactorAuthAge", &["7200"])];
+        assert!(!evaluate_conditions(Some(&condition), &stale));
+    }
+
+    #[test]
+    fn test_null_operator() {
+        let condition = json!({ "Null": { "aws:TokenIssueTime": "true" } });
+
+        assert!(evaluate_conditions(Some(&condition), &[]));
+        let present = vec![entry("aws:TokenIssueTime", &["2025-01-01T00:00:00Z"])];
+        assert!(!evaluate_conditions(Some(&condition), &present));
+    }
+
+    #[test]
+    fn test_negated_operator_with_absent_key() {
+        let condition = json!({ "StringNotEquals": { "aws:username": "mallory" } });
+
+        assert!(evaluate_conditions(Some(&condition), &[]));
+        let mallory = vec![entry("aws:username", &["mallory"])];
+        assert!(!evaluate_conditions(Some(&condition), &mallory));
+    }
+
+    #[test]
+    fn test_wildcard_match() {
+        assert!(wildcard_match("eng-*", "eng-platform"));
+        assert!(wildcard_match("a?c", "abc"));
+        assert!(wildcard_match("*", ""));
+        assert!(!wildcard_match("eng-*", "sales"));
+        assert!(!wildcard_match("a?c", "ac"));
+    }
+
+    #[test]
+    fn test_ip_address_ipv4_cidr() {
+        let condition = json!({ "IpAddress": { "aws:SourceIp": ["203.0.113.0/24", "10.0.0.1"] } });
+
+        let inside = vec![entry("aws:SourceIp", &["203.0.113.200"])];
+        assert!(evaluate_conditions(Some(&condition), &inside));
+
+        let exact_host = vec![entry("aws:SourceIp", &["10.0.0.1"])];
+        assert!(evaluate_conditions(Some(&condition), &exact_host));
+
+        let outside = vec![entry("aws:SourceIp", &["203.0.114.1"])];
+        assert!(!evaluate_conditions(Some(&condition), &outside));
+    }
+
+    #[test]
+    fn test_ip_address_ipv6_cidr() {
+        let condition = json!({ "IpAddress": { "aws:SourceIp": "2001:db8::/32" } });
+
+        let inside = vec![entry("aws:SourceIp", &["2001:db8:abcd::1"])];
+        assert!(evaluate_conditions(Some(&condition), &inside));
+
+        let outside = vec![entry("aws:SourceIp", &["2001:db9::1"])];
+        assert!(!evaluate_conditions(Some(&condition), &outside));
+
+        // An IPv4 address never falls within an IPv6 block
+        let ipv4 = vec![entry("aws:SourceIp", &["192.0.2.1"])];
+        assert!(!evaluate_conditions(Some(&condition), &ipv4));
+    }
+
+    #[test]
+    fn test_not_ip_address() {
+        let condition = json!({ "NotIpAddress": { "aws:SourceIp": "192.0.2.0/24" } });
+
+        let outside = vec![entry("aws:SourceIp", &["198.51.100.1"])];
+        assert!(evaluate_conditions(Some(&condition), &outside));
+
+        let inside = vec![entry("aws:SourceIp", &["192.0.2.10"])];
+        assert!(!evaluate_conditions(Some(&condition), &inside));
+    }
+
+    #[test]
+    fn test_ip_address_fails_closed() {
+        let ip = vec![entry("aws:SourceIp", &["192.0.2.10"])];
+
+        // An invalid CIDR in the policy matches nothing, negated or not
+        let invalid = json!({ "IpAddress": { "aws:SourceIp": "192.0.2.0/33" } });
+        assert!(!evaluate_conditions(Some(&invalid), &ip));
+        let invalid_not = json!({ "NotIpAddress": { "aws:SourceIp": "not-a-cidr" } });
+        assert!(!evaluate_conditions(Some(&invalid_not), &ip));
+
+        // A malformed source IP never matches
+        let condition = json!({ "IpAddress": { "aws:SourceIp": "0.0.0.0/0" } });
+        let garbage = vec![entry("aws:SourceIp", &["localhost"])];
+        assert!(!evaluate_conditions(Some(&condition), &garbage));
+
+        // A missing source IP does not match IpAddress
+        assert!(!evaluate_conditions(Some(&condition), &[]));
+    }
+}
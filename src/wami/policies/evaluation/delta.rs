@@ -0,0 +1,189 @@
+//! Permission Delta Between Principals
+//!
+//! Pure functions comparing the identity-based policies of two principals.
+//!
+//! Permissions are compared at the granularity of the policy statements:
+//! every `Allow` statement contributes one [`PermissionGrant`] per
+//! action/resource pair. Wildcard patterns such as `s3:*` are kept as-is and
+//! reported as broad grants instead of being expanded into individual actions.
+//! Conditions are not evaluated; conditional statements are compared as if
+//! their conditions held.
+
+use super::condition::wildcard_match;
+use crate::types::PolicyDocument;
+use serde::{Deserialize, Serialize};
+
+/// An action allowed on a resource pattern
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PermissionGrant {
+    /// The allowed action or action pattern (e.g., "s3:GetObject", "s3:*")
+    pub action: String,
+    /// The resource or resource pattern the action is allowed on
+    pub resource: String,
+}
+
+impl PermissionGrant {
+    /// Whether the grant uses wildcards and so covers more than one action or resource
+    pub fn is_broad(&self) -> bool {
+        [&self.action, &self.resource]
+            .iter()
+            .any(|pattern| pattern.contains(['*', '?']))
+    }
+}
+
+/// Difference between the effective permissions of two principals
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PrincipalPermissionDelta {
+    /// ARN of the first principal
+    pub principal_a: String,
+    /// ARN of the second principal
+    pub principal_b: String,
+    /// Grants the first principal has that the second lacks
+    pub only_in_a: Vec<PermissionGrant>,
+    /// Grants the second principal has that the first lacks
+    pub only_in_b: Vec<PermissionGrant>,
+}
+
+impl PrincipalPermissionDelta {
+    /// Whether both principals have the same effective permissions
+    pub fn is_equivalent(&self) -> bool {
+        self.only_in_a.is_empty() && self.only_in_b.is_empty()
+    }
+}
+
+/// Collect the grants of a set of policies, sorted and deduplicated
+///
+/// Grants that are entirely covered by a `Deny` statement of the same
+/// policies are dropped.
+pub fn effective_grants(policies: &[PolicyDocument]) -> Vec<PermissionGrant> {
+    let denies = statement_grants(policies, "Deny");
+    let mut grants: Vec<PermissionGrant> = statement_grants(policies, "Allow")
+        .into_iter()
+        .filter(|grant| !is_covered(grant, &denies))
+        .collect();
+    grants.sort();
+    grants.dedup();
+    grants
+}
+
+/// Grants from `grants` that `policies` do not allow
+///
+/// A grant is allowed when a single `Allow` statement covers its whole
+/// action and resource patterns and no `Deny` statement covers them.
+pub fn grants_missing_from(
+    grants: &[PermissionGrant],
+    policies: &[PolicyDocument],
+) -> Vec<PermissionGrant> {
+    let allows = statement_grants(policies, "Allow");
+    let denies = statement_grants(policies, "Deny");
+
+    grants
+        .iter()
+        .filter(|grant| !is_covered(grant, &allows) || is_covered(grant, &denies))
+        .cloned()
+        .collect()
+}
+
+/// Compare two sets of policies
+///
+/// Returns the grants only the first set allows, then the grants only the
+/// second set allows.
+pub fn permission_delta(
+    policies_a: &[PolicyDocument],
+    policies_b: &[PolicyDocument],
+) -> (Vec<PermissionGrant>, Vec<PermissionGrant>) {
+    let only_in_a = grants_missing_from(&effective_grants(policies_a), policies_b);
+    let only_in_b = grants_missing_from(&effective_grants(policies_b), policies_a);
+    (only_in_a, only_in_b)
+}
+
+/// Action/resource pairs of every statement with the given effect
+fn statement_grants(policies: &[PolicyDocument], effect: &str) -> Vec<PermissionGrant> {
+    policies
+        .iter()
+        .flat_map(|policy| &policy.statement)
+        .filter(|statement| statement.effect.eq_ignore_ascii_case(effect))
+        .flat_map(|statement| {
+            statement.action.iter().flat_map(|action| {
+                statement.resource.iter().map(|resource| PermissionGrant {
+                    action: action.clone(),
+                    resource: resource.clone(),
+                })
+            })
+        })
+        .collect()
+}
+
+/// Whether one of `patterns` covers both the action and the resource of `grant`
+///
+/// A pattern covers another when it matches it literally, e.g. `s3:*` covers
+/// `s3:Get*`, which covers `s3:GetObject`. Actions are case-insensitive.
+fn is_covered(grant: &PermissionGrant, patterns: &[PermissionGrant]) -> bool {
+    patterns.iter().any(|pattern| {
+        wildcard_match(
+            &pattern.action.to_ascii_lowercase(),
+            &grant.action.to_ascii_lowercase(),
+        ) && wildcard_match(&pattern.resource, &grant.resource)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(json: &str) -> PolicyDocument {
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn grant(action: &str, resource: &str) -> PermissionGrant {
+        PermissionGrant {
+            action: action.to_string(),
+            resource: resource.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_wildcard_reported_as_broad_grant() {
+        let a = policy(
+            r#"{"Version":"2012-10-17","Statement":[{"Effect":"Allow","Action":"s3:*","Resource":"*"}]}"#,
+        );
+        let b = policy(
+            r#"{"Version":"2012-10-17","Statement":[{"Effect":"Allow","Action":"s3:GetObject","Resource":"*"}]}"#,
+        );
+
+        let (only_in_a, only_in_b) = permission_delta(&[a], &[b]);
+        assert_eq!(only_in_a, vec![grant("s3:*", "*")]);
+        assert!(only_in_a[0].is_broad());
+        assert!(only_in_b.is_empty());
+    }
+
+    #[test]
+    fn test_deny_removes_grant() {
+        let a = policy(
+            r#"{"Version":"2012-10-17","Statement":[
+                {"Effect":"Allow","Action":["s3:GetObject","s3:PutObject"],"Resource":"arn:aws:s3:::bucket/*"}
+            ]}"#,
+        );
+        let b = policy(
+            r#"{"Version":"2012-10-17","Statement":[
+                {"Effect":"Allow","Action":"s3:*","Resource":"*"},
+                {"Effect":"Deny","Action":"s3:PutObject","Resource":"*"}
+            ]}"#,
+        );
+
+        let (only_in_a, only_in_b) = permission_delta(&[a], &[b]);
+        assert_eq!(
+            only_in_a,
+            vec![grant("s3:PutObject", "arn:aws:s3:::bucket/*")]
+        );
+        assert_eq!(only_in_b, vec![grant("s3:*", "*")]);
+    }
+
+    #[test]
+    fn test_identical_policies_are_equivalent() {
+        let doc = r#"{"Version":"2012-10-17","Statement":[{"Effect":"Allow","Action":"iam:GetUser","Resource":"*"}]}"#;
+        let (only_in_a, only_in_b) = permission_delta(&[policy(doc)], &[policy(doc)]);
+        assert!(only_in_a.is_empty());
+        assert!(only_in_b.is_empty());
+    }
+}
@@ -1,11 +1,15 @@
 //! Policy simulation and evaluation
 
+pub mod condition;
+pub mod delta;
 pub mod model;
 // pub mod operations; // TODO: Fix model ref
 pub mod requests;
+pub mod variables;
 
 // Re-export types
-pub use model::{ContextEntry, EvaluationResult, StatementMatch};
+pub use delta::{PermissionGrant, PrincipalPermissionDelta};
+pub use model::{ContextEntry, DecisionSource, EvaluationResult, StatementMatch};
 pub use requests::{
     SimulateCustomPolicyRequest, SimulatePolicyResponse, SimulatePrincipalPolicyRequest,
 };
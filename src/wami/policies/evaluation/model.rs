@@ -15,6 +15,20 @@ pub struct EvaluationResult {
     pub matched_statements: Vec<StatementMatch>,
     /// List of statements that didn't match
     pub missing_context_values: Vec<String>,
+    /// The policies that produced the decision, `None` for an implicit deny
+    #[serde(default)]
+    pub decision_source: Option<DecisionSource>,
+}
+
+/// Which policies produced a simulated decision
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum DecisionSource {
+    /// The identity-based policies alone
+    IdentityPolicy,
+    /// The resource-based policy alone
+    ResourcePolicy,
+    /// Both the identity-based and the resource-based policies
+    IdentityAndResourcePolicy,
 }
 
 /// Information about a policy statement that matched
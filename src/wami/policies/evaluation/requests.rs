@@ -15,6 +15,18 @@ pub struct SimulateCustomPolicyRequest {
     pub resource_arns: Option<Vec<String>>,
     /// Optional context entries for condition evaluation
     pub context_entries: Option<Vec<ContextEntry>>,
+    /// ARN of the simulated caller, whose account or tenant is compared to `resource_owner`
+    #[serde(default)]
+    pub caller_arn: Option<String>,
+    /// Account or tenant owning the simulated resources
+    ///
+    /// When it differs from the caller's, both the identity policies and
+    /// `resource_policy` must allow an action for it to be allowed.
+    #[serde(default)]
+    pub resource_owner: Option<String>,
+    /// Resource-based policy attached to the simulated resources (JSON string)
+    #[serde(default)]
+    pub resource_policy: Option<String>,
 }
 
 /// Request to simulate a principal's policy
@@ -30,6 +42,15 @@ pub struct SimulatePrincipalPolicyRequest {
     pub policy_input_list: Option<Vec<String>>,
     /// Optional context entries for condition evaluation
     pub context_entries: Option<Vec<ContextEntry>>,
+    /// Account or tenant owning the simulated resources
+    ///
+    /// When it differs from the principal's, both the identity policies and
+    /// `resource_policy` must allow an action for it to be allowed.
+    #[serde(default)]
+    pub resource_owner: Option<String>,
+    /// Resource-based policy attached to the simulated resources (JSON string)
+    #[serde(default)]
+    pub resource_policy: Option<String>,
 }
 
 /// Response from policy simulation
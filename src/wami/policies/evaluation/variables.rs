@@ -0,0 +1,131 @@
+//! Policy Variable Expansion
+//!
+//! Resolves policy variables such as `${aws:username}` in statement resources
+//! against the context entries supplied with a request.
+//!
+//! A variable may carry a default value, used when the key is absent from the
+//! context: `${aws:PrincipalTag/team, 'none'}`. A variable without a default
+//! whose key is absent cannot be resolved, so the pattern containing it never
+//! matches.
+
+use super::condition::find_context_values;
+use super::model::ContextEntry;
+
+/// Expand every policy variable in `pattern`
+///
+/// Returns `None` if a variable has no context value and no default.
+/// An unterminated `${` is kept as literal text.
+///
+/// # Example
+///
+/// ```rust
+/// use wami::wami::policies::evaluation::variables::expand_policy_variables;
+///
+/// let expanded = expand_policy_variables("arn:aws:s3:::home/${aws:username, 'guest'}/*", &[]);
+/// assert_eq!(expanded.as_deref(), Some("arn:aws:s3:::home/guest/*"));
+/// assert_eq!(expand_policy_variables("${aws:username}", &[]), None);
+/// ```
+pub fn expand_policy_variables(pattern: &str, context: &[ContextEntry]) -> Option<String> {
+    let mut expanded = String::with_capacity(pattern.len());
+    let mut rest = pattern;
+
+    while let Some(start) = rest.find("${") {
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        expanded.push_str(&rest[..start]);
+        expanded.push_str(&resolve_variable(&rest[start + 2..start + len], context)?);
+        rest = &rest[start + len + 1..];
+    }
+
+    expanded.push_str(rest);
+    Some(expanded)
+}
+
+/// Resolve the body of a single `${...}` variable
+fn resolve_variable(body: &str, context: &[ContextEntry]) -> Option<String> {
+    let (key, default) = parse_variable(body);
+
+    find_context_values(context, key)
+        .and_then(|values| values.first())
+        .cloned()
+        .or_else(|| default.map(str::to_string))
+}
+
+/// Split a variable body into its key and optional quoted default value
+///
+/// `aws:PrincipalTag/team, 'none'` becomes `("aws:PrincipalTag/team", Some("none"))`.
+fn parse_variable(body: &str) -> (&str, Option<&str>) {
+    match body.split_once(',') {
+        Some((key, default)) => {
+            let default = default.trim();
+            let default = default
+                .strip_prefix('\'')
+                .and_then(|d| d.strip_suffix('\''))
+                .unwrap_or(default);
+            (key.trim(), Some(default))
+        }
+        None => (body.trim(), None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(key: &str, value: &str) -> ContextEntry {
+        ContextEntry {
+            context_key_name: key.to_string(),
+            context_key_values: vec![value.to_string()],
+            context_key_type: "string".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_expand_without_variables() {
+        assert_eq!(
+            expand_policy_variables("arn:aws:s3:::bucket/*", &[]).as_deref(),
+            Some("arn:aws:s3:::bucket/*")
+        );
+    }
+
+    #[test]
+    fn test_expand_from_context() {
+        let context = vec![entry("aws:username", "alice")];
+        assert_eq!(
+            expand_policy_variables("arn:aws:s3:::home/${aws:username}/*", &context).as_deref(),
+            Some("arn:aws:s3:::home/alice/*")
+        );
+    }
+
+    #[test]
+    fn test_expand_missing_variable_without_default() {
+        assert_eq!(
+            expand_policy_variables("arn:aws:s3:::home/${aws:username}/*", &[]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_expand_default_value() {
+        let pattern = "arn:aws:s3:::teams/${aws:PrincipalTag/team, 'none'}/*";
+        assert_eq!(
+            expand_policy_variables(pattern, &[]).as_deref(),
+            Some("arn:aws:s3:::teams/none/*")
+        );
+
+        let context = vec![entry("aws:PrincipalTag/team", "platform")];
+        assert_eq!(
+            expand_policy_variables(pattern, &context).as_deref(),
+            Some("arn:aws:s3:::teams/platform/*")
+        );
+    }
+
+    #[test]
+    fn test_unterminated_variable_is_literal() {
+        assert_eq!(
+            expand_policy_variables("arn:aws:s3:::${aws:username", &[]).as_deref(),
+            Some("arn:aws:s3:::${aws:username")
+        );
+    }
+}
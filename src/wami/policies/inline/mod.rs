@@ -3,6 +3,7 @@
 //! This module provides types for managing inline policies on users, groups, and roles.
 
 pub mod model;
+pub mod operations;
 pub mod requests;
 pub mod responses;
 
@@ -0,0 +1,96 @@
+//! Inline Policy Operations
+//!
+//! Pure functions for inline policy document and size validation.
+
+#![allow(clippy::result_large_err)]
+
+use crate::error::{AmiError, Result};
+use crate::types::PolicyDocument;
+
+/// Maximum aggregate size of a user's inline policies (non-whitespace characters)
+pub const USER_INLINE_POLICY_SIZE_LIMIT: usize = 2048;
+
+/// Maximum aggregate size of a group's inline policies (non-whitespace characters)
+pub const GROUP_INLINE_POLICY_SIZE_LIMIT: usize = 5120;
+
+/// Maximum aggregate size of a role's inline policies (non-whitespace characters)
+pub const ROLE_INLINE_POLICY_SIZE_LIMIT: usize = 10240;
+
+/// Size of a policy document as counted against inline policy quotas
+///
+/// Whitespace does not count toward the limit.
+pub fn policy_size(policy_document: &str) -> usize {
+    policy_document
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .count()
+}
+
+/// Parse an inline policy document, rejecting anything that is not a policy
+///
+/// The document is stored verbatim, so this only checks that it has the
+/// shape of a [`PolicyDocument`] and can take part in authorization.
+pub fn validate_inline_policy_document(policy_document: &str) -> Result<PolicyDocument> {
+    serde_json::from_str(policy_document).map_err(|e| AmiError::InvalidParameter {
+        message: format!("Invalid policy document: {}", e),
+    })
+}
+
+/// Check that the inline policies of a principal stay within `limit`
+///
+/// `documents` holds every inline policy the principal would have after the
+/// change, including the one being put. Returns `ResourceLimitExceeded` when
+/// their combined size is over the limit.
+pub fn validate_inline_policy_size<'a>(
+    principal_type: &str,
+    documents: impl IntoIterator<Item = &'a str>,
+    limit: usize,
+) -> Result<()> {
+    let total: usize = documents.into_iter().map(policy_size).sum();
+    if total > limit {
+        return Err(AmiError::ResourceLimitExceeded {
+            resource_type: format!("{} inline policy size", principal_type),
+            limit,
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_policy_size_ignores_whitespace() {
+        assert_eq!(policy_size("{ \"a\" :\n\t1 }"), 7);
+    }
+
+    #[test]
+    fn test_validate_inline_policy_document() {
+        let document = validate_inline_policy_document(
+            r#"{"Version":"2012-10-17","Statement":[{"Effect":"Allow","Action":"s3:GetObject","Resource":"*"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(document.statement[0].action, vec!["s3:GetObject"]);
+
+        // Valid JSON that is not a policy document
+        for invalid in ["invalid json", r#"{"Version":"2012-10-17"}"#, r#"["s3:*"]"#] {
+            assert!(matches!(
+                validate_inline_policy_document(invalid),
+                Err(AmiError::InvalidParameter { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_validate_inline_policy_size() {
+        let small = "x".repeat(1000);
+        assert!(validate_inline_policy_size("User", [small.as_str(); 2], 2048).is_ok());
+
+        let result = validate_inline_policy_size("User", [small.as_str(); 3], 2048);
+        assert!(matches!(
+            result,
+            Err(AmiError::ResourceLimitExceeded { limit: 2048, .. })
+        ));
+    }
+}
@@ -211,6 +211,7 @@ mod tests {
             arn: "arn:aws:iam::123456789012:policy/boundary".to_string(),
             path: "/".to_string(),
             default_version_id: "v1".to_string(),
+            versions: vec![],
             attachment_count: 0,
             permissions_boundary_usage_count: 0,
             is_attachable: true,
@@ -237,6 +238,7 @@ mod tests {
             arn: "".to_string(), // No ARN = inline policy
             path: "/".to_string(),
             default_version_id: "v1".to_string(),
+            versions: vec![],
             attachment_count: 0,
             permissions_boundary_usage_count: 0,
             is_attachable: true,
@@ -263,6 +265,7 @@ mod tests {
             arn: "arn:aws:iam::123456789012:policy/empty".to_string(),
             path: "/".to_string(),
             default_version_id: "v1".to_string(),
+            versions: vec![],
             attachment_count: 0,
             permissions_boundary_usage_count: 0,
             is_attachable: true,
@@ -289,6 +292,7 @@ mod tests {
             arn: "arn:aws:iam::123456789012:policy/boundary".to_string(),
             path: "/".to_string(),
             default_version_id: "v1".to_string(),
+            versions: vec![],
             attachment_count: 0,
             permissions_boundary_usage_count: 0,
             is_attachable: true,
@@ -337,6 +341,7 @@ mod tests {
             arn: "arn:aws:iam::123456789012:policy/boundary".to_string(),
             path: "/".to_string(),
             default_version_id: "v1".to_string(),
+            versions: vec![],
             attachment_count: 0,
             permissions_boundary_usage_count: 0,
             is_attachable: true,
@@ -1,9 +1,9 @@
 //! Policy Builder
 
-use super::model::Policy;
+use super::model::{Policy, PolicyVersion, MAX_POLICY_VERSIONS};
 use crate::arn::{Service, WamiArn};
 use crate::context::WamiContext;
-use crate::error::Result;
+use crate::error::{AmiError, Result};
 use crate::provider::ProviderConfig;
 use crate::types::Tag;
 use uuid::Uuid;
@@ -37,19 +37,26 @@ pub fn build_policy(
         policy_name
     );
 
+    let now = chrono::Utc::now();
     Ok(Policy {
         policy_name,
         policy_id,
         arn,
         path,
         default_version_id: "v1".to_string(),
+        versions: vec![PolicyVersion {
+            version_id: "v1".to_string(),
+            document: policy_document.clone(),
+            is_default_version: true,
+            create_date: now,
+        }],
         policy_document,
         attachment_count: 0,
         permissions_boundary_usage_count: 0,
         is_attachable: true,
         description,
-        create_date: chrono::Utc::now(),
-        update_date: chrono::Utc::now(),
+        create_date: now,
+        update_date: now,
         tags: tags.unwrap_or_default(),
         wami_arn,
         providers: Vec::new(),
@@ -73,6 +80,118 @@ pub fn update_policy(
     policy
 }
 
+/// Add a new version to a Policy
+///
+/// The version gets the next unused `vN` identifier. When `set_as_default` is
+/// true it becomes the default version and the policy's document. Returns
+/// `ResourceLimitExceeded` if the policy already has [`MAX_POLICY_VERSIONS`].
+#[allow(clippy::result_large_err)]
+pub fn add_policy_version(
+    mut policy: Policy,
+    document: String,
+    set_as_default: bool,
+) -> Result<(Policy, PolicyVersion)> {
+    seed_initial_version(&mut policy);
+    if policy.versions.len() >= MAX_POLICY_VERSIONS {
+        return Err(AmiError::ResourceLimitExceeded {
+            resource_type: format!("Versions of policy {}", policy.arn),
+            limit: MAX_POLICY_VERSIONS,
+        });
+    }
+
+    let next = policy
+        .versions
+        .iter()
+        .filter_map(|version| version.version_id.strip_prefix('v')?.parse::<u32>().ok())
+        .max()
+        .unwrap_or(0)
+        + 1;
+    let version_id = format!("v{}", next);
+    policy.versions.push(PolicyVersion {
+        version_id: version_id.clone(),
+        document,
+        is_default_version: false,
+        create_date: chrono::Utc::now(),
+    });
+
+    if set_as_default {
+        policy = set_default_policy_version(policy, &version_id)?;
+    } else {
+        policy.update_date = chrono::Utc::now();
+    }
+    let version = policy.versions[policy.versions.len() - 1].clone();
+    Ok((policy, version))
+}
+
+/// Make an existing version the default version of a Policy
+#[allow(clippy::result_large_err)]
+pub fn set_default_policy_version(mut policy: Policy, version_id: &str) -> Result<Policy> {
+    seed_initial_version(&mut policy);
+    let document = policy
+        .versions
+        .iter()
+        .find(|version| version.version_id == version_id)
+        .map(|version| version.document.clone())
+        .ok_or_else(|| AmiError::ResourceNotFound {
+            resource: format!("Policy version: {} of {}", version_id, policy.arn),
+        })?;
+
+    for version in &mut policy.versions {
+        version.is_default_version = version.version_id == version_id;
+    }
+    policy.default_version_id = version_id.to_string();
+    policy.policy_document = document;
+    policy.update_date = chrono::Utc::now();
+    Ok(policy)
+}
+
+/// Remove a non-default version from a Policy
+///
+/// The default version cannot be deleted; make another version the default first.
+#[allow(clippy::result_large_err)]
+pub fn delete_policy_version(mut policy: Policy, version_id: &str) -> Result<Policy> {
+    seed_initial_version(&mut policy);
+    let index = policy
+        .versions
+        .iter()
+        .position(|version| version.version_id == version_id)
+        .ok_or_else(|| AmiError::ResourceNotFound {
+            resource: format!("Policy version: {} of {}", version_id, policy.arn),
+        })?;
+    if policy.versions[index].is_default_version {
+        return Err(AmiError::DeleteConflict {
+            message: format!(
+                "Cannot delete the default version {} of policy {}",
+                version_id, policy.arn
+            ),
+        });
+    }
+
+    policy.versions.remove(index);
+    policy.update_date = chrono::Utc::now();
+    Ok(policy)
+}
+
+/// The versions of a Policy, oldest first
+///
+/// Policies stored before versioning report their document as the default version.
+pub fn policy_versions(mut policy: Policy) -> Vec<PolicyVersion> {
+    seed_initial_version(&mut policy);
+    policy.versions
+}
+
+/// Record the current document as the default version of a policy stored without versions
+fn seed_initial_version(policy: &mut Policy) {
+    if policy.versions.is_empty() {
+        policy.versions.push(PolicyVersion {
+            version_id: policy.default_version_id.clone(),
+            document: policy.policy_document.clone(),
+            is_default_version: true,
+            create_date: policy.create_date,
+        });
+    }
+}
+
 /// Add a provider configuration to a Policy
 pub fn add_provider_to_policy(mut policy: Policy, config: ProviderConfig) -> Policy {
     policy.providers.push(config);
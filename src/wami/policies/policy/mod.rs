@@ -6,10 +6,12 @@ pub mod builder;
 pub mod model;
 // pub mod operations; // TODO: Fix field mismatches in tests
 pub mod requests;
+pub mod validation;
 
-pub use model::Policy;
+pub use model::{Policy, PolicyVersion, MAX_POLICY_VERSIONS};
 // Operations moved to pure functions
 // pub use operations::PolicyOperations;
 pub use requests::{
     CreatePolicyRequest, ListPoliciesRequest, ListPoliciesResponse, UpdatePolicyRequest,
 };
+pub use validation::{validate_managed_policy_document, MANAGED_POLICY_SIZE_LIMIT};
@@ -4,6 +4,9 @@ use crate::arn::WamiArn;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+/// Maximum number of versions a managed policy can have
+pub const MAX_POLICY_VERSIONS: usize = 5;
+
 /// Represents an IAM managed policy
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Policy {
@@ -17,8 +20,11 @@ pub struct Policy {
     pub path: String,
     /// The identifier for the default version of the policy
     pub default_version_id: String,
-    /// The policy document in JSON format
+    /// The policy document in JSON format, always that of the default version
     pub policy_document: String,
+    /// The stored versions of the policy, at most [`MAX_POLICY_VERSIONS`]
+    #[serde(default)]
+    pub versions: Vec<PolicyVersion>,
     /// The number of entities (users, groups, and roles) that the policy is attached to
     pub attachment_count: i32,
     /// The number of entities that have the policy set as a permissions boundary
@@ -40,3 +46,16 @@ pub struct Policy {
     /// Optional tenant ID for multi-tenant isolation
     pub tenant_id: Option<crate::wami::tenant::TenantId>,
 }
+
+/// A version of an IAM managed policy
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PolicyVersion {
+    /// The identifier of the version, such as `v2`
+    pub version_id: String,
+    /// The policy document of this version in JSON format
+    pub document: String,
+    /// Whether this version is the one used when the policy is evaluated
+    pub is_default_version: bool,
+    /// The date and time when the version was created
+    pub create_date: DateTime<Utc>,
+}
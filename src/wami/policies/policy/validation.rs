@@ -0,0 +1,101 @@
+//! Managed Policy Validation
+//!
+//! Pure functions checking managed policy documents before they are stored.
+
+#![allow(clippy::result_large_err)]
+
+use crate::error::{AmiError, Result};
+use crate::types::PolicyDocument;
+use crate::wami::policies::inline::operations::policy_size;
+use serde_json::Value;
+
+/// Maximum size of a managed policy document (non-whitespace characters)
+pub const MANAGED_POLICY_SIZE_LIMIT: usize = 6144;
+
+/// Parse a managed policy document, rejecting anything IAM would refuse
+///
+/// The document must be valid JSON within [`MANAGED_POLICY_SIZE_LIMIT`],
+/// carry a `Version`, and hold a non-empty `Statement` array of well-formed
+/// statements. Each failure is reported as `InvalidParameter` with its own
+/// message.
+pub fn validate_managed_policy_document(policy_document: &str) -> Result<PolicyDocument> {
+    let size = policy_size(policy_document);
+    if size > MANAGED_POLICY_SIZE_LIMIT {
+        return Err(invalid(format!(
+            "Policy document is {} characters, exceeding the limit of {}",
+            size, MANAGED_POLICY_SIZE_LIMIT
+        )));
+    }
+
+    let value: Value = serde_json::from_str(policy_document)
+        .map_err(|e| invalid(format!("Policy document is not valid JSON: {}", e)))?;
+    if value.get("Version").is_none() {
+        return Err(invalid("Policy document is missing a Version".to_string()));
+    }
+    match value.get("Statement") {
+        Some(Value::Array(statements)) if statements.is_empty() => {
+            return Err(invalid(
+                "Policy document must contain at least one statement".to_string(),
+            ));
+        }
+        Some(Value::Array(_)) => {}
+        _ => {
+            return Err(invalid(
+                "Policy document must contain a Statement array".to_string(),
+            ));
+        }
+    }
+
+    serde_json::from_value(value)
+        .map_err(|e| invalid(format!("Policy document has an invalid statement: {}", e)))
+}
+
+fn invalid(message: String) -> AmiError {
+    AmiError::InvalidParameter { message }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(policy_document: &str) -> String {
+        match validate_managed_policy_document(policy_document) {
+            Err(AmiError::InvalidParameter { message }) => message,
+            other => panic!("expected InvalidParameter, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_validate_managed_policy_document() {
+        let document = validate_managed_policy_document(
+            r#"{"Version":"2012-10-17","Statement":[{"Effect":"Allow","Action":"s3:GetObject","Resource":"*"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(document.version, "2012-10-17");
+        assert_eq!(document.statement.len(), 1);
+
+        assert!(message(r#"{"Statement":[]}"#).contains("missing a Version"));
+        assert!(message(r#"{"Version":"2012-10-17","Statement":[]}"#).contains("at least one"));
+        assert!(message(r#"{"Version":"2012-10-17"}"#).contains("Statement array"));
+        assert!(message("{not json").contains("not valid JSON"));
+        assert!(
+            message(r#"{"Version":"2012-10-17","Statement":[{"Effect":"Allow"}]}"#)
+                .contains("invalid statement")
+        );
+    }
+
+    #[test]
+    fn test_size_limit_ignores_whitespace() {
+        let padded = format!(
+            r#"{{"Version":"2012-10-17","Statement":[{{"Effect":"Allow","Action":"s3:GetObject","Resource":"*"}}]}}{}"#,
+            " ".repeat(MANAGED_POLICY_SIZE_LIMIT)
+        );
+        assert!(validate_managed_policy_document(&padded).is_ok());
+
+        let oversized = format!(
+            r#"{{"Version":"2012-10-17","Statement":[{{"Effect":"Allow","Action":"s3:GetObject","Resource":"{}"}}]}}"#,
+            "a".repeat(MANAGED_POLICY_SIZE_LIMIT)
+        );
+        assert!(message(&oversized).contains("exceeding the limit of 6144"));
+    }
+}
@@ -0,0 +1,8 @@
+//! Account Authorization Details Module
+//!
+//! This module provides the model for the `GetAccountAuthorizationDetails`
+//! export of every principal and managed policy in an account.
+
+pub mod model;
+
+pub use model::*;
@@ -0,0 +1,246 @@
+//! Account Authorization Details Models
+//!
+//! Field names serialize to the shape of the AWS
+//! `GetAccountAuthorizationDetails` response.
+
+use crate::wami::identity::{Group, Role, User};
+use crate::wami::policies::policy::{Policy, PolicyVersion};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Permissions boundary type reported for users and roles
+pub const PERMISSIONS_BOUNDARY_TYPE: &str = "PermissionsBoundaryPolicy";
+
+/// Every user, group, role and managed policy of an account, with their policies
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct AccountAuthorizationDetails {
+    /// Users with their group memberships and policies
+    pub user_detail_list: Vec<UserDetail>,
+    /// Groups with their policies
+    pub group_detail_list: Vec<GroupDetail>,
+    /// Roles with their trust policy and policies
+    pub role_detail_list: Vec<RoleDetail>,
+    /// Managed policies with all their versions
+    pub policies: Vec<ManagedPolicyDetail>,
+    /// Whether the export was truncated (always false: the export is complete)
+    pub is_truncated: bool,
+}
+
+/// A managed policy attached to a principal
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct AttachedPolicyDetail {
+    /// The friendly name of the policy
+    pub policy_name: String,
+    /// The ARN of the policy
+    pub policy_arn: String,
+}
+
+/// An inline policy embedded in a principal
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct InlinePolicyDetail {
+    /// The name of the inline policy
+    pub policy_name: String,
+    /// The policy document (JSON)
+    pub policy_document: String,
+}
+
+/// The permissions boundary set on a user or role
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct AttachedPermissionsBoundary {
+    /// Always [`PERMISSIONS_BOUNDARY_TYPE`]
+    pub permissions_boundary_type: String,
+    /// The ARN of the boundary policy
+    pub permissions_boundary_arn: String,
+}
+
+impl AttachedPermissionsBoundary {
+    fn from_arn(arn: &Option<String>) -> Option<Self> {
+        arn.as_ref().map(|arn| Self {
+            permissions_boundary_type: PERMISSIONS_BOUNDARY_TYPE.to_string(),
+            permissions_boundary_arn: arn.clone(),
+        })
+    }
+}
+
+/// A user with its group memberships and policies
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct UserDetail {
+    pub path: String,
+    pub user_name: String,
+    pub user_id: String,
+    pub arn: String,
+    pub create_date: DateTime<Utc>,
+    /// Names of the groups the user belongs to
+    pub group_list: Vec<String>,
+    pub attached_managed_policies: Vec<AttachedPolicyDetail>,
+    pub user_policy_list: Vec<InlinePolicyDetail>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub permissions_boundary: Option<AttachedPermissionsBoundary>,
+}
+
+impl UserDetail {
+    /// Describe a user given its groups and policies
+    pub fn new(
+        user: &User,
+        group_list: Vec<String>,
+        attached_managed_policies: Vec<AttachedPolicyDetail>,
+        user_policy_list: Vec<InlinePolicyDetail>,
+    ) -> Self {
+        Self {
+            path: user.path.clone(),
+            user_name: user.user_name.clone(),
+            user_id: user.user_id.clone(),
+            arn: user.arn.clone(),
+            create_date: user.create_date,
+            group_list,
+            attached_managed_policies,
+            user_policy_list,
+            permissions_boundary: AttachedPermissionsBoundary::from_arn(&user.permissions_boundary),
+        }
+    }
+}
+
+/// A group with its policies
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct GroupDetail {
+    pub path: String,
+    pub group_name: String,
+    pub group_id: String,
+    pub arn: String,
+    pub create_date: DateTime<Utc>,
+    pub attached_managed_policies: Vec<AttachedPolicyDetail>,
+    pub group_policy_list: Vec<InlinePolicyDetail>,
+}
+
+impl GroupDetail {
+    /// Describe a group given its policies
+    pub fn new(
+        group: &Group,
+        attached_managed_policies: Vec<AttachedPolicyDetail>,
+        group_policy_list: Vec<InlinePolicyDetail>,
+    ) -> Self {
+        Self {
+            path: group.path.clone(),
+            group_name: group.group_name.clone(),
+            group_id: group.group_id.clone(),
+            arn: group.arn.clone(),
+            create_date: group.create_date,
+            attached_managed_policies,
+            group_policy_list,
+        }
+    }
+}
+
+/// A role with its trust policy and policies
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct RoleDetail {
+    pub path: String,
+    pub role_name: String,
+    pub role_id: String,
+    pub arn: String,
+    pub create_date: DateTime<Utc>,
+    /// The trust policy (JSON)
+    pub assume_role_policy_document: String,
+    pub attached_managed_policies: Vec<AttachedPolicyDetail>,
+    pub role_policy_list: Vec<InlinePolicyDetail>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub permissions_boundary: Option<AttachedPermissionsBoundary>,
+}
+
+impl RoleDetail {
+    /// Describe a role given its policies
+    pub fn new(
+        role: &Role,
+        attached_managed_policies: Vec<AttachedPolicyDetail>,
+        role_policy_list: Vec<InlinePolicyDetail>,
+    ) -> Self {
+        Self {
+            path: role.path.clone(),
+            role_name: role.role_name.clone(),
+            role_id: role.role_id.clone(),
+            arn: role.arn.clone(),
+            create_date: role.create_date,
+            assume_role_policy_document: role.assume_role_policy_document.clone(),
+            attached_managed_policies,
+            role_policy_list,
+            permissions_boundary: AttachedPermissionsBoundary::from_arn(&role.permissions_boundary),
+        }
+    }
+}
+
+/// A version of a managed policy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct PolicyVersionDetail {
+    /// The policy document (JSON)
+    pub document: String,
+    pub version_id: String,
+    pub is_default_version: bool,
+    pub create_date: DateTime<Utc>,
+}
+
+impl From<PolicyVersion> for PolicyVersionDetail {
+    fn from(version: PolicyVersion) -> Self {
+        Self {
+            document: version.document,
+            version_id: version.version_id,
+            is_default_version: version.is_default_version,
+            create_date: version.create_date,
+        }
+    }
+}
+
+/// A managed policy with all its versions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ManagedPolicyDetail {
+    pub policy_name: String,
+    pub policy_id: String,
+    pub arn: String,
+    pub path: String,
+    pub default_version_id: String,
+    pub attachment_count: i32,
+    pub permissions_boundary_usage_count: i32,
+    pub is_attachable: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub create_date: DateTime<Utc>,
+    pub update_date: DateTime<Utc>,
+    /// The policy's versions, oldest first; the default one is flagged
+    pub policy_version_list: Vec<PolicyVersionDetail>,
+}
+
+impl ManagedPolicyDetail {
+    /// Describe a managed policy given its versions
+    pub fn new(policy: &Policy, versions: Vec<PolicyVersion>) -> Self {
+        Self {
+            policy_name: policy.policy_name.clone(),
+            policy_id: policy.policy_id.clone(),
+            arn: policy.arn.clone(),
+            path: policy.path.clone(),
+            default_version_id: policy.default_version_id.clone(),
+            attachment_count: policy.attachment_count,
+            permissions_boundary_usage_count: policy.permissions_boundary_usage_count,
+            is_attachable: policy.is_attachable,
+            description: policy.description.clone(),
+            create_date: policy.create_date,
+            update_date: policy.update_date,
+            policy_version_list: versions.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// The document of the default version
+    pub fn default_document(&self) -> Option<&str> {
+        self.policy_version_list
+            .iter()
+            .find(|version| version.is_default_version)
+            .map(|version| version.document.as_str())
+    }
+}
@@ -5,6 +5,102 @@ use super::model::*;
 /// Pure domain operations for credential reports
 pub mod credential_report_operations {
     use super::*;
+    use crate::wami::credentials::access_key::AccessKey;
+    use crate::wami::credentials::login_profile::LoginProfile;
+    use crate::wami::credentials::password_policy::PasswordPolicy;
+    use crate::wami::credentials::signing_certificate::{CertificateStatus, SigningCertificate};
+    use crate::wami::identity::user::User;
+    use chrono::{DateTime, Utc};
+
+    /// Header row of an AWS credential report, in AWS column order
+    pub const CREDENTIAL_REPORT_HEADER: &str = "user,arn,user_creation_time,password_enabled,\
+password_last_used,password_last_changed,password_next_rotation,mfa_active,\
+access_key_1_active,access_key_1_last_rotated,access_key_1_last_used_date,\
+access_key_1_last_used_region,access_key_1_last_used_service,\
+access_key_2_active,access_key_2_last_rotated,access_key_2_last_used_date,\
+access_key_2_last_used_region,access_key_2_last_used_service,\
+cert_1_active,cert_1_last_rotated,cert_2_active,cert_2_last_rotated";
+
+    fn format_date(date: Option<DateTime<Utc>>) -> String {
+        date.map_or_else(|| "N/A".to_string(), |d| d.to_rfc3339())
+    }
+
+    fn format_access_key(key: Option<&AccessKey>) -> [String; 5] {
+        let Some(key) = key else {
+            return [
+                "false".to_string(),
+                "N/A".to_string(),
+                "N/A".to_string(),
+                "N/A".to_string(),
+                "N/A".to_string(),
+            ];
+        };
+        let last_used = key.last_used.clone().unwrap_or_default();
+        [
+            (key.status == "Active").to_string(),
+            key.create_date.to_rfc3339(),
+            format_date(last_used.last_used_date),
+            last_used.region.unwrap_or_else(|| "N/A".to_string()),
+            last_used.service_name.unwrap_or_else(|| "N/A".to_string()),
+        ]
+    }
+
+    fn format_certificate(cert: Option<&SigningCertificate>) -> [String; 2] {
+        match cert {
+            Some(cert) => [
+                (cert.status == CertificateStatus::Active).to_string(),
+                cert.upload_date.to_rfc3339(),
+            ],
+            None => ["false".to_string(), "N/A".to_string()],
+        }
+    }
+
+    /// Format one user's credential report row (pure function)
+    ///
+    /// Access keys and signing certificates are reported oldest first; only the
+    /// first two of each are included, as in AWS. `password_next_rotation` is
+    /// the date the password expires under `password_policy`, so an expired
+    /// password shows a date in the past.
+    pub fn format_credential_report_row(
+        user: &User,
+        login_profile: Option<&LoginProfile>,
+        password_policy: Option<&PasswordPolicy>,
+        mfa_active: bool,
+        access_keys: &[AccessKey],
+        signing_certificates: &[SigningCertificate],
+    ) -> String {
+        let mut keys: Vec<&AccessKey> = access_keys.iter().collect();
+        keys.sort_by_key(|k| k.create_date);
+        let mut certs: Vec<&SigningCertificate> = signing_certificates.iter().collect();
+        certs.sort_by_key(|c| c.upload_date);
+
+        let (password_last_used, password_last_changed, password_next_rotation) =
+            match login_profile {
+                Some(profile) => (
+                    user.password_last_used
+                        .map_or_else(|| "no_information".to_string(), |d| d.to_rfc3339()),
+                    profile.password_changed_at().to_rfc3339(),
+                    format_date(password_policy.and_then(|p| profile.password_expires_at(p))),
+                ),
+                None => ("N/A".to_string(), "N/A".to_string(), "N/A".to_string()),
+            };
+
+        let mut fields = vec![
+            user.user_name.clone(),
+            user.arn.clone(),
+            user.create_date.to_rfc3339(),
+            login_profile.is_some().to_string(),
+            password_last_used,
+            password_last_changed,
+            password_next_rotation,
+            mfa_active.to_string(),
+        ];
+        fields.extend(format_access_key(keys.first().copied()));
+        fields.extend(format_access_key(keys.get(1).copied()));
+        fields.extend(format_certificate(certs.first().copied()));
+        fields.extend(format_certificate(certs.get(1).copied()));
+        fields.join(",")
+    }
 
     /// Generate CSV report from user data (pure function)
     pub fn generate_csv_report(users: Vec<String>) -> String {
@@ -23,6 +23,10 @@ pub struct GetCredentialReportResponse {
     /// When the report was generated
     #[serde(rename = "GeneratedTime")]
     pub generated_time: DateTime<Utc>,
+
+    /// State of the returned report
+    #[serde(rename = "State")]
+    pub state: ReportState,
 }
 
 /// Request to generate a new credential report
@@ -2,4 +2,4 @@
 
 pub mod model;
 
-pub use model::AccountAssignment;
+pub use model::{AccountAssignment, AssignmentStatus};
@@ -3,6 +3,20 @@
 use crate::arn::WamiArn;
 use serde::{Deserialize, Serialize};
 
+/// Provisioning status of an account assignment
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum AssignmentStatus {
+    /// The assignment is being provisioned in the target account
+    #[serde(rename = "IN_PROGRESS")]
+    InProgress,
+    /// The assignment has been provisioned
+    #[serde(rename = "SUCCEEDED")]
+    Succeeded,
+    /// Provisioning the assignment failed
+    #[serde(rename = "FAILED")]
+    Failed,
+}
+
 /// Represents an SSO account assignment
 ///
 /// Links a permission set to a principal (user or group) for a specific AWS account.
@@ -30,4 +44,41 @@ pub struct AccountAssignment {
     pub wami_arn: WamiArn,
     /// List of cloud providers where this resource exists
     pub providers: Vec<crate::provider::ProviderConfig>,
+    /// Provisioning status of the assignment
+    #[serde(default = "provisioned")]
+    pub status: AssignmentStatus,
+    /// ID of the creation request, used to track its provisioning status
+    #[serde(default)]
+    pub request_id: String,
+    /// Why provisioning failed, when the status is `FAILED`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure_reason: Option<String>,
+}
+
+/// Assignments stored before provisioning was tracked were already in effect
+fn provisioned() -> AssignmentStatus {
+    AssignmentStatus::Succeeded
+}
+
+impl AccountAssignment {
+    /// Build the deterministic key identifying an assignment tuple
+    ///
+    /// The same account, permission set, and principal always yield the same
+    /// key, so it doubles as the assignment ID.
+    pub fn assignment_key(
+        account_id: &str,
+        permission_set_arn: &str,
+        principal_id: &str,
+    ) -> String {
+        format!("{}-{}-{}", account_id, permission_set_arn, principal_id)
+    }
+
+    /// The deterministic key of this assignment
+    pub fn key(&self) -> String {
+        Self::assignment_key(
+            &self.account_id,
+            &self.permission_set_arn,
+            &self.principal_id,
+        )
+    }
 }
@@ -2,4 +2,4 @@
 
 pub mod model;
 
-pub use model::Application;
+pub use model::{Application, ApplicationAssignment};
@@ -27,3 +27,36 @@ pub struct Application {
     /// List of cloud providers where this resource exists
     pub providers: Vec<crate::provider::ProviderConfig>,
 }
+
+/// Assignment of an SSO application to a user or group
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApplicationAssignment {
+    /// The ARN of the assigned application
+    pub application_arn: String,
+    /// The principal type (USER or GROUP)
+    pub principal_type: String,
+    /// The principal ID
+    pub principal_id: String,
+    /// When this assignment was created
+    pub created_date: chrono::DateTime<chrono::Utc>,
+}
+
+impl ApplicationAssignment {
+    /// Build the key identifying the assignment of an application to a principal
+    pub fn assignment_key(
+        application_arn: &str,
+        principal_type: &str,
+        principal_id: &str,
+    ) -> String {
+        format!("{}-{}-{}", application_arn, principal_type, principal_id)
+    }
+
+    /// The key of this assignment
+    pub fn key(&self) -> String {
+        Self::assignment_key(
+            &self.application_arn,
+            &self.principal_type,
+            &self.principal_id,
+        )
+    }
+}
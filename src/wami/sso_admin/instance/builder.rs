@@ -0,0 +1,42 @@
+//! SSO Instance Builder Functions
+
+use super::model::SsoInstance;
+use crate::arn::{Service, WamiArn};
+use crate::context::WamiContext;
+use crate::error::Result;
+use chrono::Utc;
+use uuid::Uuid;
+
+/// Build a new active SSO instance owned by the given account
+#[allow(clippy::result_large_err)]
+pub fn build_instance(
+    identity_store_id: String,
+    owner_account_id: String,
+    context: &WamiContext,
+) -> Result<SsoInstance> {
+    let instance_id = format!("ssoins-{}", &Uuid::new_v4().simple().to_string()[..16]);
+
+    // Build WAMI ARN using context
+    let wami_arn = WamiArn::builder()
+        .service(Service::SsoAdmin)
+        .tenant_path(context.tenant_path().clone())
+        .wami_instance(context.instance_id())
+        .resource("instance", &instance_id)
+        .build()?;
+
+    Ok(SsoInstance {
+        instance_arn: format!("arn:aws:sso:::instance/{}", instance_id),
+        identity_store_id,
+        name: None,
+        status: "ACTIVE".to_string(),
+        created_date: Utc::now(),
+        wami_arn,
+        providers: vec![],
+        owner_account_id: Some(owner_account_id),
+    })
+}
+
+/// Generate a new identity store ID (e.g., `d-1234567890`)
+pub fn generate_identity_store_id() -> String {
+    format!("d-{}", &Uuid::new_v4().simple().to_string()[..10])
+}
@@ -1,5 +1,6 @@
 //! SSO Instance Module
 
+pub mod builder;
 pub mod model;
 
 pub use model::SsoInstance;
@@ -20,4 +20,7 @@ pub struct SsoInstance {
     pub wami_arn: WamiArn,
     /// List of cloud providers where this resource exists
     pub providers: Vec<crate::provider::ProviderConfig>,
+    /// The account that owns the SSO instance
+    #[serde(default)]
+    pub owner_account_id: Option<String>,
 }
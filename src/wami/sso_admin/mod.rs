@@ -12,8 +12,8 @@ pub mod trusted_token_issuer;
 // pub mod tests;  // Temporarily disabled - will rewrite with pure function tests
 
 // Re-export main types
-pub use account_assignment::AccountAssignment;
-pub use application::Application;
+pub use account_assignment::{AccountAssignment, AssignmentStatus};
+pub use application::{Application, ApplicationAssignment};
 pub use instance::SsoInstance;
 pub use permission_set::PermissionSet;
 pub use trusted_token_issuer::TrustedTokenIssuer;
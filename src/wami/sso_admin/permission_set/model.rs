@@ -1,8 +1,16 @@
 //! Permission Set Model
 
 use crate::arn::WamiArn;
+use crate::error::{AmiError, Result};
+use chrono::Duration;
 use serde::{Deserialize, Serialize};
 
+/// Minimum session duration for a permission set (1 hour)
+pub const MIN_SESSION_DURATION_SECONDS: i64 = 3600;
+
+/// Maximum session duration for a permission set (12 hours)
+pub const MAX_SESSION_DURATION_SECONDS: i64 = 43200;
+
 /// Represents an SSO permission set
 ///
 /// A permission set defines a collection of permissions that can be assigned to users and groups.
@@ -18,6 +26,9 @@ pub struct PermissionSet {
     pub session_duration: Option<String>,
     /// The relay state URL for the application
     pub relay_state: Option<String>,
+    /// Policy document granted, in the assigned account, to principals assigned this permission set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inline_policy: Option<String>,
     /// The SSO instance ARN this permission set belongs to
     pub instance_arn: String,
     /// The date and time when the permission set was created
@@ -27,3 +38,97 @@ pub struct PermissionSet {
     /// List of cloud providers where this resource exists
     pub providers: Vec<crate::provider::ProviderConfig>,
 }
+
+impl PermissionSet {
+    /// Parse an ISO-8601 session duration (e.g. `PT8H`, `PT1H30M`) and check it is within 1-12 hours
+    #[allow(clippy::result_large_err)]
+    pub fn validate_session_duration(session_duration: &str) -> Result<Duration> {
+        let duration =
+            parse_iso8601_duration(session_duration).ok_or_else(|| AmiError::InvalidParameter {
+                message: format!(
+                    "Invalid session duration '{}': expected an ISO-8601 duration such as PT8H",
+                    session_duration
+                ),
+            })?;
+
+        let seconds = duration.num_seconds();
+        if !(MIN_SESSION_DURATION_SECONDS..=MAX_SESSION_DURATION_SECONDS).contains(&seconds) {
+            return Err(AmiError::InvalidParameter {
+                message: format!(
+                    "Session duration '{}' must be between 1 and 12 hours",
+                    session_duration
+                ),
+            });
+        }
+
+        Ok(duration)
+    }
+}
+
+/// Parse an ISO-8601 time duration of the form `PT[nH][nM][nS]`
+///
+/// Returns `None` for malformed input, including date components (`P1D`).
+fn parse_iso8601_duration(value: &str) -> Option<Duration> {
+    let rest = value.strip_prefix("PT")?;
+    if rest.is_empty() {
+        return None;
+    }
+
+    let mut seconds: i64 = 0;
+    let mut number = String::new();
+    let mut last_unit = 0;
+    for c in rest.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+
+        // Units must appear at most once and in H, M, S order
+        let (rank, multiplier) = match c {
+            'H' => (1, 3600),
+            'M' => (2, 60),
+            'S' => (3, 1),
+            _ => return None,
+        };
+        if number.is_empty() || rank <= last_unit {
+            return None;
+        }
+        seconds = seconds.checked_add(number.parse::<i64>().ok()?.checked_mul(multiplier)?)?;
+        number.clear();
+        last_unit = rank;
+    }
+
+    if !number.is_empty() {
+        return None;
+    }
+
+    Some(Duration::seconds(seconds))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_iso8601_duration() {
+        assert_eq!(parse_iso8601_duration("PT8H"), Some(Duration::hours(8)));
+        assert_eq!(
+            parse_iso8601_duration("PT1H30M"),
+            Some(Duration::minutes(90))
+        );
+        assert_eq!(parse_iso8601_duration("PT90S"), Some(Duration::seconds(90)));
+        assert_eq!(parse_iso8601_duration("PT"), None);
+        assert_eq!(parse_iso8601_duration("PT8"), None);
+        assert_eq!(parse_iso8601_duration("PT30M1H"), None);
+        assert_eq!(parse_iso8601_duration("P1D"), None);
+        assert_eq!(parse_iso8601_duration("8h"), None);
+    }
+
+    #[test]
+    fn test_validate_session_duration_bounds() {
+        assert!(PermissionSet::validate_session_duration("PT1H").is_ok());
+        assert!(PermissionSet::validate_session_duration("PT12H").is_ok());
+        assert!(PermissionSet::validate_session_duration("PT59M").is_err());
+        assert!(PermissionSet::validate_session_duration("PT12H1S").is_err());
+    }
+}
@@ -20,6 +20,7 @@ mod sso_tests {
             description: Some("Developer access permissions".to_string()),
             session_duration: Some("PT8H".to_string()),
             relay_state: None,
+            inline_policy: None,
         };
 
         let response = client.create_permission_set(request).await.unwrap();
@@ -42,6 +43,7 @@ mod sso_tests {
             description: Some("Test description".to_string()),
             session_duration: None,
             relay_state: None,
+            inline_policy: None,
         };
         let create_response = client.create_permission_set(create_request).await.unwrap();
         let permission_set_arn = create_response.data.unwrap().permission_set_arn;
@@ -73,6 +75,7 @@ mod sso_tests {
             description: None,
             session_duration: None,
             relay_state: None,
+            inline_policy: None,
         };
         let create_response = client.create_permission_set(create_request).await.unwrap();
         let permission_set_arn = create_response.data.unwrap().permission_set_arn;
@@ -113,6 +116,7 @@ mod sso_tests {
                 description: None,
                 session_duration: None,
                 relay_state: None,
+                inline_policy: None,
             };
             client.create_permission_set(request).await.unwrap();
         }
@@ -5,6 +5,12 @@
 pub mod model;
 // pub mod operations; // TODO: Fix field/ResourceType issues
 pub mod requests;
+pub mod saml;
+pub mod trust;
+pub mod web_identity;
 
 pub use model::*;
 pub use requests::*;
+pub use saml::{decode_saml_assertion, SamlAssertion};
+pub use trust::{evaluate_trust_policy, TrustDecision, TrustedCaller};
+pub use web_identity::{decode_web_identity_token, WebIdentityClaims};
@@ -2,6 +2,12 @@
 
 use serde::{Deserialize, Serialize};
 
+/// Maximum duration in seconds of a session obtained through role chaining
+pub const ROLE_CHAINING_MAX_SESSION_DURATION: i32 = 3600;
+
+/// Maximum number of tags on a session, including inherited transitive tags
+pub const MAX_SESSION_TAGS: usize = 50;
+
 /// Information about an assumed role user
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AssumedRoleUser {
@@ -1,10 +1,11 @@
 //! Assume Role Request and Response Types
 
 use crate::error::{AmiError, Result};
+use crate::types::Tag;
 use crate::wami::sts::Credentials;
 use serde::{Deserialize, Serialize};
 
-use super::model::AssumedRoleUser;
+use super::model::{AssumedRoleUser, MAX_SESSION_TAGS};
 
 /// Request to assume an IAM role
 ///
@@ -19,6 +20,8 @@ use super::model::AssumedRoleUser;
 ///     duration_seconds: Some(3600),
 ///     external_id: Some("unique-external-id".to_string()),
 ///     policy: None,
+///     tags: vec![],
+///     transitive_tag_keys: vec![],
 /// };
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,12 +30,20 @@ pub struct AssumeRoleRequest {
     pub role_arn: String,
     /// An identifier for the assumed role session
     pub role_session_name: String,
-    /// The duration of the session in seconds (default: 3600, max: 43200)
+    /// The duration of the session in seconds (default: the provider's default, max: 43200)
     pub duration_seconds: Option<i32>,
     /// A unique identifier used by third parties for assuming a role
+    ///
+    /// 2 to 1224 characters among letters, digits and `_+=,.@:/-`.
     pub external_id: Option<String>,
     /// An IAM policy in JSON format to further restrict permissions
     pub policy: Option<String>,
+    /// Session tags, visible to policies as `aws:PrincipalTag/<key>` (max 50)
+    #[serde(default)]
+    pub tags: Vec<Tag>,
+    /// Keys of `tags` passed on to sessions chained from this one
+    #[serde(default)]
+    pub transitive_tag_keys: Vec<String>,
 }
 
 impl AssumeRoleRequest {
@@ -45,24 +56,203 @@ impl AssumeRoleRequest {
             });
         }
 
-        if self.role_session_name.is_empty() {
+        validate_session_name("Role session name", &self.role_session_name, 64)?;
+
+        // Validate duration if provided
+        if let Some(duration) = self.duration_seconds {
+            if !(900..=43200).contains(&duration) {
+                return Err(AmiError::InvalidParameter {
+                    message: "Duration must be between 900 and 43200 seconds".to_string(),
+                });
+            }
+        }
+
+        if let Some(external_id) = &self.external_id {
+            if !(2..=1224).contains(&external_id.len()) {
+                return Err(AmiError::InvalidParameter {
+                    message: "External ID must be between 2 and 1224 characters".to_string(),
+                });
+            }
+            if !external_id.chars().all(|c| {
+                c.is_ascii_alphanumeric()
+                    || matches!(c, '_' | '+' | '=' | ',' | '.' | '@' | ':' | '/' | '-')
+            }) {
+                return Err(AmiError::InvalidParameter {
+                    message: "External ID contains invalid characters".to_string(),
+                });
+            }
+        }
+
+        validate_session_tags(&self.tags, &self.transitive_tag_keys)
+    }
+}
+
+/// Check a session name against the AWS pattern `[\w+=,.@-]{2,max_len}`
+///
+/// Session names end up in assumed-role and federated-user ARNs, so anything
+/// outside this set is rejected. `label` names the field in error messages.
+#[allow(clippy::result_large_err)]
+pub(crate) fn validate_session_name(label: &str, name: &str, max_len: usize) -> Result<()> {
+    if !(2..=max_len).contains(&name.len()) {
+        return Err(AmiError::InvalidParameter {
+            message: format!("{} must be between 2 and {} characters", label, max_len),
+        });
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '+' | '=' | ',' | '.' | '@' | '-'))
+    {
+        return Err(AmiError::InvalidParameter {
+            message: format!("{} contains invalid characters", label),
+        });
+    }
+    Ok(())
+}
+
+/// Check session tags for duplicate keys (ignoring case), the tag limit, and
+/// transitive keys that name no tag
+#[allow(clippy::result_large_err)]
+pub(crate) fn validate_session_tags(tags: &[Tag], transitive_tag_keys: &[String]) -> Result<()> {
+    if tags.len() > MAX_SESSION_TAGS {
+        return Err(AmiError::InvalidParameter {
+            message: format!(
+                "A session can have at most {} tags, got {}",
+                MAX_SESSION_TAGS,
+                tags.len()
+            ),
+        });
+    }
+
+    for (i, tag) in tags.iter().enumerate() {
+        if tags[..i]
+            .iter()
+            .any(|other| other.key.eq_ignore_ascii_case(&tag.key))
+        {
             return Err(AmiError::InvalidParameter {
-                message: "Role session name cannot be empty".to_string(),
+                message: format!("Duplicate session tag key: {}", tag.key),
             });
         }
+    }
 
-        // Validate session name format (alphanumeric, underscore, dash, plus, equals, comma, period, at sign, hyphen)
-        if !self
-            .role_session_name
-            .chars()
-            .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '+' | '=' | ',' | '.' | '@'))
-        {
+    if let Some(key) = transitive_tag_keys
+        .iter()
+        .find(|key| !tags.iter().any(|tag| tag.key.eq_ignore_ascii_case(key)))
+    {
+        return Err(AmiError::InvalidParameter {
+            message: format!("Transitive tag key {} is not a session tag", key),
+        });
+    }
+
+    Ok(())
+}
+
+/// Request to assume an IAM role with an OIDC web identity token
+///
+/// # Example
+///
+/// ```rust
+/// use wami::sts::AssumeRoleWithWebIdentityRequest;
+///
+/// let request = AssumeRoleWithWebIdentityRequest {
+///     role_arn: "arn:aws:iam::123456789012:role/GitHubDeploy".to_string(),
+///     role_session_name: "deploy-session".to_string(),
+///     web_identity_token: "eyJhbGciOiJSUzI1NiJ9.eyJzdWIiOiJ1c2VyIn0.sig".to_string(),
+///     provider_id: Some("token.actions.githubusercontent.com".to_string()),
+///     duration_seconds: Some(3600),
+///     policy: None,
+/// };
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssumeRoleWithWebIdentityRequest {
+    /// The ARN of the role to assume
+    pub role_arn: String,
+    /// An identifier for the assumed role session
+    pub role_session_name: String,
+    /// The raw OIDC token (JWT) issued by the identity provider
+    pub web_identity_token: String,
+    /// The OIDC provider ARN or host (defaults to the token's `iss` claim)
+    pub provider_id: Option<String>,
+    /// The duration of the session in seconds (default: the provider's default, max: 43200)
+    pub duration_seconds: Option<i32>,
+    /// An IAM policy in JSON format to further restrict permissions
+    pub policy: Option<String>,
+}
+
+impl AssumeRoleWithWebIdentityRequest {
+    /// Validate the request
+    #[allow(clippy::result_large_err)]
+    pub fn validate(&self) -> Result<()> {
+        if self.web_identity_token.is_empty() {
             return Err(AmiError::InvalidParameter {
-                message: "Role session name contains invalid characters".to_string(),
+                message: "Web identity token cannot be empty".to_string(),
+            });
+        }
+
+        // Role ARN, session name, and duration follow the same rules as AssumeRole
+        AssumeRoleRequest {
+            role_arn: self.role_arn.clone(),
+            role_session_name: self.role_session_name.clone(),
+            duration_seconds: self.duration_seconds,
+            external_id: None,
+            policy: None,
+            tags: vec![],
+            transitive_tag_keys: vec![],
+        }
+        .validate()
+    }
+}
+
+/// Request to assume an IAM role with a SAML assertion
+///
+/// # Example
+///
+/// ```rust
+/// use wami::sts::AssumeRoleWithSamlRequest;
+///
+/// let request = AssumeRoleWithSamlRequest {
+///     role_arn: "arn:aws:iam::123456789012:role/CorpAdmins".to_string(),
+///     principal_arn: "arn:aws:iam::123456789012:saml-provider/Okta".to_string(),
+///     saml_assertion: "PHNhbWxwOlJlc3BvbnNlPi4uLjwvc2FtbHA6UmVzcG9uc2U+".to_string(),
+///     duration_seconds: None,
+///     policy: None,
+/// };
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssumeRoleWithSamlRequest {
+    /// The ARN of the role to assume
+    pub role_arn: String,
+    /// The ARN of the SAML provider that issued the assertion
+    pub principal_arn: String,
+    /// The base64-encoded SAML response from the identity provider
+    pub saml_assertion: String,
+    /// The duration of the session in seconds (default: the provider's default, max: 43200)
+    pub duration_seconds: Option<i32>,
+    /// An IAM policy in JSON format to further restrict permissions
+    pub policy: Option<String>,
+}
+
+impl AssumeRoleWithSamlRequest {
+    /// Validate the request
+    #[allow(clippy::result_large_err)]
+    pub fn validate(&self) -> Result<()> {
+        if self.role_arn.is_empty() {
+            return Err(AmiError::InvalidParameter {
+                message: "Role ARN cannot be empty".to_string(),
+            });
+        }
+
+        if self.principal_arn.is_empty() {
+            return Err(AmiError::InvalidParameter {
+                message: "Principal ARN cannot be empty".to_string(),
+            });
+        }
+
+        if self.saml_assertion.is_empty() {
+            return Err(AmiError::InvalidParameter {
+                message: "SAML assertion cannot be empty".to_string(),
             });
         }
 
-        // Validate duration if provided
         if let Some(duration) = self.duration_seconds {
             if !(900..=43200).contains(&duration) {
                 return Err(AmiError::InvalidParameter {
@@ -82,4 +272,7 @@ pub struct AssumeRoleResponse {
     pub credentials: Credentials,
     /// Information about the assumed role user
     pub assumed_role_user: AssumedRoleUser,
+    /// The subject asserted by the federated identity provider, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub federated_subject: Option<String>,
 }
@@ -0,0 +1,158 @@
+//! SAML Assertion Decoding
+//!
+//! Pure functions for reading the attributes of a base64-encoded SAML 2.0
+//! response. Signatures are NOT verified; only the `NameID` and the AWS
+//! session attributes are extracted.
+
+#![allow(clippy::result_large_err)]
+
+use crate::error::{AmiError, Result};
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+
+/// SAML attribute carrying the maximum session duration in seconds
+pub const SESSION_DURATION_ATTRIBUTE: &str =
+    "https://aws.amazon.com/SAML/Attributes/SessionDuration";
+
+/// Maximum length of a role session name
+const MAX_SESSION_NAME_LENGTH: usize = 64;
+
+/// Attributes extracted from a SAML assertion
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SamlAssertion {
+    /// The subject's `NameID`
+    pub name_id: String,
+    /// The session duration requested by the identity provider, if any
+    pub session_duration: Option<i32>,
+    /// The entity ID of the identity provider that issued the assertion, if given
+    #[serde(default)]
+    pub issuer: Option<String>,
+}
+
+impl SamlAssertion {
+    /// Derive a valid role session name from the `NameID`
+    ///
+    /// Characters not allowed in session names are replaced with `_` and the
+    /// result is truncated to 64 characters.
+    pub fn role_session_name(&self) -> String {
+        self.name_id
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '+' | '=' | ',' | '.' | '@')
+                {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .take(MAX_SESSION_NAME_LENGTH)
+            .collect()
+    }
+}
+
+/// Decode a base64-encoded SAML response and extract its `NameID`, issuer and session duration
+///
+/// # Errors
+///
+/// Returns `InvalidParameter` if the assertion is not base64, not well-formed
+/// XML, has no `NameID`, or carries a non-numeric session duration.
+pub fn decode_saml_assertion(saml_assertion: &str) -> Result<SamlAssertion> {
+    let invalid = |reason: String| AmiError::InvalidParameter {
+        message: format!("Invalid SAML assertion: {}", reason),
+    };
+
+    let compact: String = saml_assertion
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    let xml = base64::engine::general_purpose::STANDARD
+        .decode(compact)
+        .map_err(|_| invalid("not base64 encoded".to_string()))?;
+    let xml = String::from_utf8(xml).map_err(|_| invalid("not valid UTF-8".to_string()))?;
+
+    let doc = roxmltree::Document::parse(&xml).map_err(|e| invalid(e.to_string()))?;
+
+    let name_id = doc
+        .descendants()
+        .find(|node| node.has_tag_name("NameID"))
+        .and_then(|node| node.text())
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+        .ok_or_else(|| invalid("missing NameID".to_string()))?
+        .to_string();
+
+    let session_duration = doc
+        .descendants()
+        .filter(|node| node.has_tag_name("Attribute"))
+        .find(|node| node.attribute("Name") == Some(SESSION_DURATION_ATTRIBUTE))
+        .and_then(|node| {
+            node.children()
+                .find(|child| child.has_tag_name("AttributeValue"))
+                .and_then(|value| value.text())
+        })
+        .map(|text| {
+            text.trim()
+                .parse::<i32>()
+                .map_err(|_| invalid(format!("invalid session duration '{}'", text.trim())))
+        })
+        .transpose()?;
+
+    let issuer = doc
+        .descendants()
+        .find(|node| node.has_tag_name("Issuer"))
+        .and_then(|node| node.text())
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+        .map(str::to_string);
+
+    Ok(SamlAssertion {
+        name_id,
+        session_duration,
+        issuer,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(xml: &str) -> String {
+        base64::engine::general_purpose::STANDARD.encode(xml)
+    }
+
+    #[test]
+    fn test_decode_saml_assertion() {
+        let xml = r#"<samlp:Response xmlns:samlp="urn:oasis:names:tc:SAML:2.0:protocol" xmlns:saml="urn:oasis:names:tc:SAML:2.0:assertion">
+  <saml:Assertion>
+    <saml:Subject><saml:NameID>alice@example.com</saml:NameID></saml:Subject>
+    <saml:AttributeStatement>
+      <saml:Attribute Name="https://aws.amazon.com/SAML/Attributes/SessionDuration">
+        <saml:AttributeValue>1800</saml:AttributeValue>
+      </saml:Attribute>
+    </saml:AttributeStatement>
+  </saml:Assertion>
+</samlp:Response>"#;
+
+        let assertion = decode_saml_assertion(&encode(xml)).unwrap();
+        assert_eq!(assertion.name_id, "alice@example.com");
+        assert_eq!(assertion.session_duration, Some(1800));
+        assert_eq!(assertion.role_session_name(), "alice@example.com");
+    }
+
+    #[test]
+    fn test_role_session_name_is_sanitized() {
+        let assertion = SamlAssertion {
+            name_id: "CORP\\alice smith".to_string(),
+            session_duration: None,
+            issuer: None,
+        };
+        assert_eq!(assertion.role_session_name(), "CORP_alice_smith");
+    }
+
+    #[test]
+    fn test_decode_saml_assertion_rejects_malformed() {
+        assert!(decode_saml_assertion("%%%").is_err());
+        assert!(decode_saml_assertion(&encode("<Response>")).is_err());
+        assert!(decode_saml_assertion(&encode("<Response><Assertion/></Response>")).is_err());
+    }
+}
@@ -0,0 +1,370 @@
+//! Role Trust Policy Evaluation
+//!
+//! Pure functions deciding whether a caller may assume a role, based on the
+//! role's trust policy (`assume_role_policy_document`).
+
+#![allow(clippy::result_large_err)]
+
+use crate::error::{AmiError, Result};
+use crate::wami::policies::evaluation::condition::evaluate_conditions;
+use crate::wami::policies::evaluation::ContextEntry;
+use serde_json::Value;
+
+/// Context key carrying the external ID supplied with an `AssumeRole` request
+pub const EXTERNAL_ID_CONTEXT_KEY: &str = "sts:ExternalId";
+
+/// The caller a trust policy is evaluated for
+#[derive(Debug, Clone, Default)]
+pub struct TrustedCaller {
+    /// The ARNs identifying the caller, in any format it may be named by
+    pub principal_arns: Vec<String>,
+    /// The accounts the caller belongs to; trusting an account trusts its principals
+    pub account_ids: Vec<String>,
+    /// The service principals the caller acts as (e.g. `ec2.amazonaws.com`)
+    pub services: Vec<String>,
+    /// The identity providers the caller was federated by (e.g. a SAML provider ARN)
+    pub federated: Vec<String>,
+    /// Whether the caller must be named explicitly, so a `*` principal does not trust it
+    ///
+    /// Set for callers from another account than the role's.
+    pub explicit_principal_required: bool,
+}
+
+/// Outcome of evaluating a role trust policy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrustDecision {
+    /// A statement allows the caller and no statement denies it
+    Allowed,
+    /// No statement allows the caller, or a statement explicitly denies it
+    PrincipalNotTrusted,
+    /// A statement names the caller, but its conditions (such as `sts:ExternalId`) do not hold
+    ConditionsNotMet,
+}
+
+/// Evaluate a trust policy for `caller` performing `action` (e.g. `sts:AssumeRole`)
+///
+/// A statement applies when its `Action` covers `action` and its `Principal`
+/// names the caller, or its `NotPrincipal` does not. The caller is named by
+/// `*`, by one of its ARNs or accounts (as an ID or `arn:aws:iam::<account>:root`)
+/// under `AWS`, by one of its services under `Service`, or by one of its
+/// identity providers under `Federated`. An applicable `Deny` always wins.
+/// When the caller [requires an explicit principal](TrustedCaller::explicit_principal_required),
+/// `*` names nobody and a `NotPrincipal` never allows it.
+///
+/// # Errors
+///
+/// Returns `InvalidParameter` if the trust policy is not a JSON policy document.
+pub fn evaluate_trust_policy(
+    trust_policy: &str,
+    action: &str,
+    caller: &TrustedCaller,
+    context: &[ContextEntry],
+) -> Result<TrustDecision> {
+    let document: Value =
+        serde_json::from_str(trust_policy).map_err(|e| AmiError::InvalidParameter {
+            message: format!("Invalid trust policy: {}", e),
+        })?;
+    let statements = match document.get("Statement") {
+        Some(Value::Array(statements)) => statements.iter().collect(),
+        Some(statement @ Value::Object(_)) => vec![statement],
+        _ => Vec::new(),
+    };
+
+    let mut decision = TrustDecision::PrincipalNotTrusted;
+    for statement in statements {
+        let effect = statement.get("Effect").and_then(Value::as_str);
+        let denies = effect.is_some_and(|effect| effect.eq_ignore_ascii_case("Deny"));
+        let principal_applies = match (statement.get("Principal"), statement.get("NotPrincipal")) {
+            (Some(principal), _) => principal_matches(principal, caller),
+            (None, Some(not_principal)) => {
+                (denies || !caller.explicit_principal_required)
+                    && !principal_matches(not_principal, caller)
+            }
+            (None, None) => false,
+        };
+        if !principal_applies || !action_matches(statement.get("Action"), action) {
+            continue;
+        }
+
+        let conditions_hold = evaluate_conditions(statement.get("Condition"), context);
+        match effect {
+            Some(effect) if effect.eq_ignore_ascii_case("Deny") && conditions_hold => {
+                return Ok(TrustDecision::PrincipalNotTrusted);
+            }
+            Some(effect) if effect.eq_ignore_ascii_case("Allow") => {
+                if conditions_hold {
+                    decision = TrustDecision::Allowed;
+                } else if decision != TrustDecision::Allowed {
+                    decision = TrustDecision::ConditionsNotMet;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(decision)
+}
+
+/// Whether a `Principal` (or `NotPrincipal`) element names the caller
+fn principal_matches(principal: &Value, caller: &TrustedCaller) -> bool {
+    let wildcard_trusts = !caller.explicit_principal_required;
+    let Value::Object(principals) = principal else {
+        return wildcard_trusts && principal.as_str() == Some("*");
+    };
+    let named = |key: &str, matches: &dyn Fn(&str) -> bool| {
+        principals
+            .get(key)
+            .map(string_values)
+            .unwrap_or_default()
+            .into_iter()
+            .any(|principal| (wildcard_trusts && principal == "*") || matches(principal))
+    };
+
+    named("AWS", &|principal| {
+        caller.principal_arns.iter().any(|arn| arn == principal)
+            || caller.account_ids.iter().any(|account| {
+                principal == account || principal == format!("arn:aws:iam::{}:root", account)
+            })
+    }) || named("Service", &|principal| {
+        caller
+            .services
+            .iter()
+            .any(|service| service.eq_ignore_ascii_case(principal))
+    }) || named("Federated", &|principal| {
+        caller
+            .federated
+            .iter()
+            .any(|provider| provider == principal)
+    })
+}
+
+/// Whether a statement's `Action` element covers the action
+fn action_matches(actions: Option<&Value>, action: &str) -> bool {
+    actions
+        .map(string_values)
+        .unwrap_or_default()
+        .into_iter()
+        .any(|pattern| match pattern.strip_suffix('*') {
+            Some(prefix) => action
+                .get(..prefix.len())
+                .is_some_and(|head| head.eq_ignore_ascii_case(prefix)),
+            None => pattern.eq_ignore_ascii_case(action),
+        })
+}
+
+/// The strings of a policy element given either as a string or an array of strings
+fn string_values(value: &Value) -> Vec<&str> {
+    match value {
+        Value::String(value) => vec![value.as_str()],
+        Value::Array(values) => values.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn caller() -> TrustedCaller {
+        TrustedCaller {
+            principal_arns: vec!["arn:aws:iam::123456789012:user/alice".to_string()],
+            account_ids: vec!["123456789012".to_string()],
+            explicit_principal_required: false,
+            ..Default::default()
+        }
+    }
+
+    fn trust(statements: &str) -> String {
+        format!(r#"{{"Version":"2012-10-17","Statement":[{}]}}"#, statements)
+    }
+
+    fn external_id(value: &str) -> Vec<ContextEntry> {
+        vec![ContextEntry {
+            context_key_name: EXTERNAL_ID_CONTEXT_KEY.to_string(),
+            context_key_values: vec![value.to_string()],
+            context_key_type: "String".to_string(),
+        }]
+    }
+
+    fn evaluate(trust_policy: &str, context: &[ContextEntry]) -> TrustDecision {
+        evaluate_trust_policy(trust_policy, "sts:AssumeRole", &caller(), context).unwrap()
+    }
+
+    #[test]
+    fn test_principal_forms() {
+        for principal in [
+            r#""*""#,
+            r#"{"AWS":"*"}"#,
+            r#"{"AWS":"arn:aws:iam::123456789012:user/alice"}"#,
+            r#"{"AWS":["arn:aws:iam::999999999999:root","arn:aws:iam::123456789012:root"]}"#,
+            r#"{"AWS":"123456789012"}"#,
+        ] {
+            let policy = format!(
+                r#"{{"Version":"2012-10-17","Statement":{{"Effect":"Allow","Principal":{},"Action":"sts:AssumeRole"}}}}"#,
+                principal
+            );
+            assert_eq!(
+                evaluate(&policy, &[]),
+                TrustDecision::Allowed,
+                "{}",
+                principal
+            );
+        }
+
+        for principal in [
+            r#"{"AWS":"arn:aws:iam::123456789012:user/bob"}"#,
+            r#"{"AWS":"arn:aws:iam::999999999999:root"}"#,
+            r#"{"Service":"ec2.amazonaws.com"}"#,
+        ] {
+            let policy = format!(
+                r#"{{"Version":"2012-10-17","Statement":[{{"Effect":"Allow","Principal":{},"Action":"sts:AssumeRole"}}]}}"#,
+                principal
+            );
+            assert_eq!(
+                evaluate(&policy, &[]),
+                TrustDecision::PrincipalNotTrusted,
+                "{}",
+                principal
+            );
+        }
+    }
+
+    #[test]
+    fn test_service_and_federated_principals() {
+        let ec2 = TrustedCaller {
+            services: vec!["ec2.amazonaws.com".to_string()],
+            explicit_principal_required: true,
+            ..Default::default()
+        };
+        let evaluate_ec2 = |statements: &str| {
+            evaluate_trust_policy(&trust(statements), "sts:AssumeRole", &ec2, &[]).unwrap()
+        };
+
+        assert_eq!(
+            evaluate_ec2(
+                r#"{"Effect":"Allow","Principal":{"Service":["lambda.amazonaws.com","ec2.amazonaws.com"]},"Action":"sts:AssumeRole"}"#
+            ),
+            TrustDecision::Allowed
+        );
+        for principal in [
+            r#"{"Service":"lambda.amazonaws.com"}"#,
+            r#""*""#,
+            r#"{"AWS":"*"}"#,
+            r#"{"AWS":"ec2.amazonaws.com"}"#,
+        ] {
+            let statement = format!(
+                r#"{{"Effect":"Allow","Principal":{},"Action":"sts:AssumeRole"}}"#,
+                principal
+            );
+            assert_eq!(
+                evaluate_ec2(&statement),
+                TrustDecision::PrincipalNotTrusted,
+                "{}",
+                principal
+            );
+        }
+
+        let saml = TrustedCaller {
+            federated: vec!["arn:aws:iam::123456789012:saml-provider/Okta".to_string()],
+            ..Default::default()
+        };
+        let policy = trust(
+            r#"{"Effect":"Allow","Principal":{"Federated":"arn:aws:iam::123456789012:saml-provider/Okta"},"Action":"sts:AssumeRoleWithSAML"}"#,
+        );
+        assert_eq!(
+            evaluate_trust_policy(&policy, "sts:AssumeRoleWithSAML", &saml, &[]).unwrap(),
+            TrustDecision::Allowed
+        );
+        assert_eq!(
+            evaluate_trust_policy(&policy, "sts:AssumeRoleWithSAML", &caller(), &[]).unwrap(),
+            TrustDecision::PrincipalNotTrusted
+        );
+    }
+
+    #[test]
+    fn test_not_principal() {
+        // The account, except anyone but bob
+        let excludes_alice = trust(
+            r#"{"Effect":"Allow","Principal":{"AWS":"123456789012"},"Action":"sts:AssumeRole"},
+               {"Effect":"Deny","NotPrincipal":{"AWS":"arn:aws:iam::123456789012:user/bob"},"Action":"sts:AssumeRole"}"#,
+        );
+        assert_eq!(
+            evaluate(&excludes_alice, &[]),
+            TrustDecision::PrincipalNotTrusted
+        );
+
+        let bob = TrustedCaller {
+            principal_arns: vec!["arn:aws:iam::123456789012:user/bob".to_string()],
+            account_ids: vec!["123456789012".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            evaluate_trust_policy(&excludes_alice, "sts:AssumeRole", &bob, &[]).unwrap(),
+            TrustDecision::Allowed
+        );
+
+        // An allow for anyone but the named principal
+        let allows_others = trust(
+            r#"{"Effect":"Allow","NotPrincipal":{"AWS":"arn:aws:iam::123456789012:user/alice"},"Action":"sts:AssumeRole"}"#,
+        );
+        assert_eq!(
+            evaluate(&allows_others, &[]),
+            TrustDecision::PrincipalNotTrusted
+        );
+        assert_eq!(
+            evaluate_trust_policy(&allows_others, "sts:AssumeRole", &bob, &[]).unwrap(),
+            TrustDecision::Allowed
+        );
+
+        // ... which does not count as naming a caller that must be named explicitly
+        let external = TrustedCaller {
+            explicit_principal_required: true,
+            ..bob
+        };
+        assert_eq!(
+            evaluate_trust_policy(&allows_others, "sts:AssumeRole", &external, &[]).unwrap(),
+            TrustDecision::PrincipalNotTrusted
+        );
+    }
+
+    #[test]
+    fn test_action_and_deny() {
+        let other_action = r#"{"Version":"2012-10-17","Statement":[{"Effect":"Allow","Principal":{"AWS":"*"},"Action":"sts:TagSession"}]}"#;
+        assert_eq!(
+            evaluate(other_action, &[]),
+            TrustDecision::PrincipalNotTrusted
+        );
+
+        let wildcard = r#"{"Version":"2012-10-17","Statement":[{"Effect":"Allow","Principal":{"AWS":"*"},"Action":"sts:*"}]}"#;
+        assert_eq!(evaluate(wildcard, &[]), TrustDecision::Allowed);
+
+        let denied = r#"{"Version":"2012-10-17","Statement":[
+            {"Effect":"Allow","Principal":{"AWS":"*"},"Action":"sts:AssumeRole"},
+            {"Effect":"Deny","Principal":{"AWS":"arn:aws:iam::123456789012:user/alice"},"Action":"sts:AssumeRole"}
+        ]}"#;
+        assert_eq!(evaluate(denied, &[]), TrustDecision::PrincipalNotTrusted);
+    }
+
+    #[test]
+    fn test_external_id_condition() {
+        let policy = r#"{"Version":"2012-10-17","Statement":[{"Effect":"Allow","Principal":{"AWS":"123456789012"},"Action":"sts:AssumeRole","Condition":{"StringEquals":{"sts:ExternalId":"partner-42"}}}]}"#;
+
+        assert_eq!(
+            evaluate(policy, &external_id("partner-42")),
+            TrustDecision::Allowed
+        );
+        assert_eq!(
+            evaluate(policy, &external_id("wrong")),
+            TrustDecision::ConditionsNotMet
+        );
+        assert_eq!(evaluate(policy, &[]), TrustDecision::ConditionsNotMet);
+    }
+
+    #[test]
+    fn test_invalid_trust_policy() {
+        assert!(matches!(
+            evaluate_trust_policy("not json", "sts:AssumeRole", &caller(), &[]),
+            Err(AmiError::InvalidParameter { .. })
+        ));
+    }
+}
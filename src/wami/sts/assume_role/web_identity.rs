@@ -0,0 +1,130 @@
+//! Web Identity Token Decoding
+//!
+//! Pure functions for reading the claims of an OIDC web identity token (JWT).
+//! Signatures are NOT verified: the token is treated as opaque apart from its
+//! payload, which is decoded to surface the subject, audience, and issuer.
+
+#![allow(clippy::result_large_err)]
+
+use crate::error::{AmiError, Result};
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Claims extracted from a web identity token
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WebIdentityClaims {
+    /// The subject (`sub`) of the token
+    pub subject: String,
+    /// The audiences (`aud`) the token was issued for
+    pub audiences: Vec<String>,
+    /// The issuer (`iss`) of the token, if present
+    pub issuer: Option<String>,
+}
+
+impl WebIdentityClaims {
+    /// Returns the issuer without its `https://` scheme (e.g. `token.actions.githubusercontent.com`)
+    pub fn issuer_host(&self) -> Option<&str> {
+        self.issuer
+            .as_deref()
+            .map(|iss| iss.trim_start_matches("https://").trim_end_matches('/'))
+    }
+}
+
+/// Decode the payload of a JWT web identity token without verifying its signature
+///
+/// # Errors
+///
+/// Returns `InvalidParameter` if the token is not a three-part JWT, the payload
+/// is not base64url-encoded JSON, or the `sub`/`aud` claims are missing.
+pub fn decode_web_identity_token(token: &str) -> Result<WebIdentityClaims> {
+    let invalid = |reason: &str| AmiError::InvalidParameter {
+        message: format!("Invalid web identity token: {}", reason),
+    };
+
+    let parts: Vec<&str> = token.split('.').collect();
+    if parts.len() != 3 || parts[1].is_empty() {
+        return Err(invalid("expected a JWT with three segments"));
+    }
+
+    let payload = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(parts[1].trim_end_matches('='))
+        .map_err(|_| invalid("payload is not base64url encoded"))?;
+
+    let claims: Value =
+        serde_json::from_slice(&payload).map_err(|_| invalid("payload is not valid JSON"))?;
+
+    let subject = claims
+        .get("sub")
+        .and_then(Value::as_str)
+        .filter(|sub| !sub.is_empty())
+        .ok_or_else(|| invalid("missing 'sub' claim"))?
+        .to_string();
+
+    let audiences: Vec<String> = match claims.get("aud") {
+        Some(Value::String(aud)) => vec![aud.clone()],
+        Some(Value::Array(auds)) => auds
+            .iter()
+            .filter_map(Value::as_str)
+            .map(String::from)
+            .collect(),
+        _ => Vec::new(),
+    };
+    if audiences.is_empty() {
+        return Err(invalid("missing 'aud' claim"));
+    }
+
+    let issuer = claims.get("iss").and_then(Value::as_str).map(String::from);
+
+    Ok(WebIdentityClaims {
+        subject,
+        audiences,
+        issuer,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode_token(payload: &str) -> String {
+        let engine = base64::engine::general_purpose::URL_SAFE_NO_PAD;
+        format!(
+            "{}.{}.signature",
+            engine.encode(r#"{"alg":"RS256","typ":"JWT"}"#),
+            engine.encode(payload)
+        )
+    }
+
+    #[test]
+    fn test_decode_web_identity_token() {
+        let token = encode_token(
+            r#"{"iss":"https://token.actions.githubusercontent.com","sub":"repo:org/app:ref:refs/heads/main","aud":"sts.amazonaws.com"}"#,
+        );
+
+        let claims = decode_web_identity_token(&token).unwrap();
+        assert_eq!(claims.subject, "repo:org/app:ref:refs/heads/main");
+        assert_eq!(claims.audiences, vec!["sts.amazonaws.com"]);
+        assert_eq!(
+            claims.issuer_host(),
+            Some("token.actions.githubusercontent.com")
+        );
+    }
+
+    #[test]
+    fn test_decode_web_identity_token_audience_list() {
+        let token = encode_token(r#"{"sub":"user-1","aud":["client-a","client-b"]}"#);
+
+        let claims = decode_web_identity_token(&token).unwrap();
+        assert_eq!(claims.audiences, vec!["client-a", "client-b"]);
+        assert!(claims.issuer.is_none());
+    }
+
+    #[test]
+    fn test_decode_web_identity_token_rejects_malformed() {
+        assert!(decode_web_identity_token("not-a-jwt").is_err());
+        assert!(decode_web_identity_token("a.!!!.c").is_err());
+        assert!(decode_web_identity_token(&encode_token(r#"{"aud":"client"}"#)).is_err());
+        assert!(decode_web_identity_token(&encode_token(r#"{"sub":"user"}"#)).is_err());
+    }
+}
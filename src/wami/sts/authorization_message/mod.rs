@@ -0,0 +1,8 @@
+//! Authorization Message Module
+//!
+//! This module provides encoding and decoding of authorization failure messages,
+//! mirroring STS `DecodeAuthorizationMessage`.
+
+pub mod model;
+
+pub use model::*;
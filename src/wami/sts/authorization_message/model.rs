@@ -0,0 +1,86 @@
+//! Authorization Message Model
+
+use crate::error::{AmiError, Result};
+use crate::types::PolicyStatement;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+
+/// The decoded explanation of an authorization decision
+///
+/// Produced by the authorization service when it denies a request, and
+/// recovered from the encoded blob with [`decode_authorization_message`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecodedMessage {
+    /// Whether the request was allowed
+    pub allowed: bool,
+    /// Whether the request was denied by an explicit `Deny` statement
+    pub explicit_deny: bool,
+    /// The statements that determined the decision
+    pub matched_statements: Vec<PolicyStatement>,
+    /// The action that was evaluated
+    pub action: String,
+    /// The resource the action was evaluated against
+    pub resource: String,
+}
+
+impl DecodedMessage {
+    /// Encode the message as a base64 JSON blob
+    pub fn encode(&self) -> String {
+        let json = serde_json::to_vec(self).expect("DecodedMessage is always serializable");
+        base64::engine::general_purpose::STANDARD.encode(json)
+    }
+}
+
+/// Decode an authorization failure message produced on a deny
+///
+/// # Errors
+///
+/// Returns `InvalidParameter` if the message is not base64-encoded JSON of a
+/// [`DecodedMessage`].
+#[allow(clippy::result_large_err)]
+pub fn decode_authorization_message(encoded: &str) -> Result<DecodedMessage> {
+    let json = base64::engine::general_purpose::STANDARD
+        .decode(encoded.trim())
+        .map_err(|_| AmiError::InvalidParameter {
+            message: "Authorization message is not base64 encoded".to_string(),
+        })?;
+
+    serde_json::from_slice(&json).map_err(|e| AmiError::InvalidParameter {
+        message: format!("Invalid authorization message: {}", e),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_decode_round_trip() {
+        let message = DecodedMessage {
+            allowed: false,
+            explicit_deny: true,
+            matched_statements: vec![PolicyStatement {
+                effect: "Deny".to_string(),
+                action: vec!["iam:DeleteUser".to_string()],
+                resource: vec!["*".to_string()],
+                condition: None,
+            }],
+            action: "iam:DeleteUser".to_string(),
+            resource: "arn:wami:iam:12345678:wami:999:user/alice".to_string(),
+        };
+
+        let decoded = decode_authorization_message(&message.encode()).unwrap();
+        assert!(!decoded.allowed);
+        assert!(decoded.explicit_deny);
+        assert_eq!(decoded.matched_statements.len(), 1);
+        assert_eq!(decoded.matched_statements[0].action, vec!["iam:DeleteUser"]);
+        assert_eq!(decoded.action, "iam:DeleteUser");
+    }
+
+    #[test]
+    fn test_decode_rejects_garbage() {
+        assert!(decode_authorization_message("not base64!").is_err());
+        let not_a_message = base64::engine::general_purpose::STANDARD.encode("{}");
+        assert!(decode_authorization_message(&not_a_message).is_err());
+    }
+}
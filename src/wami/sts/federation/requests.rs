@@ -1,6 +1,7 @@
 //! Federation Request and Response Types
 
 use crate::error::{AmiError, Result};
+use crate::wami::sts::assume_role::requests::validate_session_name;
 use crate::wami::sts::Credentials;
 use serde::{Deserialize, Serialize};
 
@@ -33,22 +34,8 @@ impl GetFederationTokenRequest {
     /// Validate the request
     #[allow(clippy::result_large_err)]
     pub fn validate(&self) -> Result<()> {
-        if self.name.is_empty() {
-            return Err(AmiError::InvalidParameter {
-                message: "Name cannot be empty".to_string(),
-            });
-        }
-
-        // Validate name format
-        if !self
-            .name
-            .chars()
-            .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | '@'))
-        {
-            return Err(AmiError::InvalidParameter {
-                message: "Name contains invalid characters".to_string(),
-            });
-        }
+        // Federated user names follow the session name pattern, capped at 32 characters
+        validate_session_name("Name", &self.name, 32)?;
 
         // Validate duration if provided
         if let Some(duration) = self.duration_seconds {
@@ -1,6 +1,7 @@
 //! Security Token Service (STS) - temporary credentials and federation
 
 pub mod assume_role;
+pub mod authorization_message;
 pub mod credentials;
 pub mod federation;
 pub mod identity;
@@ -11,7 +12,11 @@ pub mod session_token;
 // pub mod tests;  // Temporarily disabled - will rewrite with pure function tests
 
 // Re-export main types
-pub use assume_role::{AssumeRoleRequest, AssumeRoleResponse};
+pub use assume_role::{
+    AssumeRoleRequest, AssumeRoleResponse, AssumeRoleWithSamlRequest,
+    AssumeRoleWithWebIdentityRequest, SamlAssertion, WebIdentityClaims,
+};
+pub use authorization_message::{decode_authorization_message, DecodedMessage};
 pub use credentials::Credentials;
 pub use identity::model::CallerIdentity; // Model types
 pub use session::StsSession;
@@ -1,7 +1,9 @@
 //! Session Domain Model
 
 use crate::arn::WamiArn;
+use crate::types::{Tag, REDACTED};
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 /// Represents an STS session with temporary credentials
 ///
@@ -26,9 +28,15 @@ use serde::{Deserialize, Serialize};
 ///     tenant_id: None,
 ///     created_at: Utc::now(),
 ///     last_used: None,
+///     policy: None,
+///     role_chain_length: 1,
+///     tags: vec![],
+///     transitive_tag_keys: vec![],
 /// };
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// The `Debug` output redacts the secret access key and session token.
+#[derive(Clone, Serialize, Deserialize)]
 pub struct StsSession {
     /// The session token for temporary credentials
     pub session_token: String,
@@ -58,6 +66,46 @@ pub struct StsSession {
     pub created_at: chrono::DateTime<chrono::Utc>,
     /// When the session was last used
     pub last_used: Option<chrono::DateTime<chrono::Utc>>,
+    /// Inline session policy (JSON) that further scopes the session's permissions
+    #[serde(default)]
+    pub policy: Option<String>,
+    /// Number of roles assumed in a row to obtain this session
+    ///
+    /// 0 when no role was assumed, 1 for a role assumed by a user, and more
+    /// when a role was assumed from an assumed-role session (role chaining).
+    #[serde(default)]
+    pub role_chain_length: u32,
+    /// Session tags, visible to policies as `aws:PrincipalTag/<key>`
+    #[serde(default)]
+    pub tags: Vec<Tag>,
+    /// Keys of the session tags passed on to sessions chained from this one
+    #[serde(default)]
+    pub transitive_tag_keys: Vec<String>,
+}
+
+impl fmt::Debug for StsSession {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StsSession")
+            .field("session_token", &REDACTED)
+            .field("access_key_id", &self.access_key_id)
+            .field("secret_access_key", &REDACTED)
+            .field("expiration", &self.expiration)
+            .field("status", &self.status)
+            .field("assumed_role_arn", &self.assumed_role_arn)
+            .field("federated_user_name", &self.federated_user_name)
+            .field("principal_arn", &self.principal_arn)
+            .field("arn", &self.arn)
+            .field("wami_arn", &self.wami_arn)
+            .field("providers", &self.providers)
+            .field("tenant_id", &self.tenant_id)
+            .field("created_at", &self.created_at)
+            .field("last_used", &self.last_used)
+            .field("policy", &self.policy)
+            .field("role_chain_length", &self.role_chain_length)
+            .field("tags", &self.tags)
+            .field("transitive_tag_keys", &self.transitive_tag_keys)
+            .finish()
+    }
 }
 
 /// Status of an STS session
@@ -127,6 +175,10 @@ mod tests {
             tenant_id: None,
             created_at: chrono::Utc::now(),
             last_used: None,
+            policy: None,
+            role_chain_length: 0,
+            tags: vec![],
+            transitive_tag_keys: vec![],
         }
     }
 
@@ -29,6 +29,8 @@ mod sts_tests {
             role_arn: "arn:aws:iam::123456789012:role/TestRole".to_string(),
             role_session_name: "test-session".to_string(),
             policy: None,
+            tags: vec![],
+            transitive_tag_keys: vec![],
             duration_seconds: Some(3600),
             external_id: None,
         };
@@ -153,6 +155,8 @@ mod sts_tests {
             role_arn: "arn:aws:iam::123456789012:role/TestRole".to_string(),
             role_session_name: "format-test".to_string(),
             policy: None,
+            tags: vec![],
+            transitive_tag_keys: vec![],
             duration_seconds: None,
             external_id: None,
         };
@@ -1,63 +1,101 @@
 //! Tag Domain Operations - Pure Functions
 
 use crate::error::{AmiError, Result};
+use crate::provider::ResourceLimits;
 use crate::types::Tag;
 
 /// Pure domain operations for tags
 pub mod tag_operations {
     use super::*;
 
-    /// Validate a single tag (pure function)
+    /// Validate a single tag against the default (AWS) limits (pure function)
     #[allow(clippy::result_large_err)]
     pub fn validate_tag(tag: &Tag) -> Result<()> {
-        validate_tag_key(&tag.key)?;
-        validate_tag_value(&tag.value)?;
+        validate_tag_with_limits(tag, &ResourceLimits::default())
+    }
+
+    /// Validate a single tag against a provider's limits (pure function)
+    #[allow(clippy::result_large_err)]
+    pub fn validate_tag_with_limits(tag: &Tag, limits: &ResourceLimits) -> Result<()> {
+        validate_tag_key_with_limits(&tag.key, limits)?;
+        validate_tag_value_with_limits(&tag.value, limits)?;
         Ok(())
     }
 
-    /// Validate tag key format (pure function)
+    /// Validate tag key format against the default (AWS) limits (pure function)
     #[allow(clippy::result_large_err)]
     pub fn validate_tag_key(key: &str) -> Result<()> {
+        validate_tag_key_with_limits(key, &ResourceLimits::default())
+    }
+
+    /// Validate tag key format against a provider's limits (pure function)
+    #[allow(clippy::result_large_err)]
+    pub fn validate_tag_key_with_limits(key: &str, limits: &ResourceLimits) -> Result<()> {
         if key.is_empty() {
             return Err(AmiError::InvalidParameter {
                 message: "Tag key cannot be empty".to_string(),
             });
         }
 
-        if key.len() > 128 {
+        if key.chars().count() > limits.max_tag_key_length {
             return Err(AmiError::InvalidParameter {
-                message: "Tag key cannot exceed 128 characters".to_string(),
+                message: format!(
+                    "Tag key cannot exceed {} characters",
+                    limits.max_tag_key_length
+                ),
             });
         }
 
-        // AWS tag key restrictions
-        if key.starts_with("aws:") {
+        let lowercase_key = key.to_lowercase();
+        if let Some(prefix) = limits
+            .reserved_tag_key_prefixes
+            .iter()
+            .find(|prefix| lowercase_key.starts_with(&prefix.to_lowercase()))
+        {
             return Err(AmiError::InvalidParameter {
-                message: "Tag keys cannot start with 'aws:' (reserved prefix)".to_string(),
+                message: format!("Tag keys cannot start with '{}' (reserved prefix)", prefix),
             });
         }
 
-        Ok(())
+        validate_tag_characters("key", key)
     }
 
-    /// Validate tag value format (pure function)
+    /// Validate tag value format against the default (AWS) limits (pure function)
     #[allow(clippy::result_large_err)]
     pub fn validate_tag_value(value: &str) -> Result<()> {
-        if value.len() > 256 {
+        validate_tag_value_with_limits(value, &ResourceLimits::default())
+    }
+
+    /// Validate tag value format against a provider's limits (pure function)
+    #[allow(clippy::result_large_err)]
+    pub fn validate_tag_value_with_limits(value: &str, limits: &ResourceLimits) -> Result<()> {
+        if value.chars().count() > limits.max_tag_value_length {
             return Err(AmiError::InvalidParameter {
-                message: "Tag value cannot exceed 256 characters".to_string(),
+                message: format!(
+                    "Tag value cannot exceed {} characters",
+                    limits.max_tag_value_length
+                ),
             });
         }
 
-        Ok(())
+        validate_tag_characters("value", value)
     }
 
-    /// Validate a list of tags (pure function)
+    /// Validate a list of tags against the default (AWS) limits (pure function)
     #[allow(clippy::result_large_err)]
     pub fn validate_tags(tags: &[Tag]) -> Result<()> {
-        if tags.len() > 50 {
+        validate_tags_with_limits(tags, &ResourceLimits::default())
+    }
+
+    /// Validate a list of tags against a provider's limits (pure function)
+    #[allow(clippy::result_large_err)]
+    pub fn validate_tags_with_limits(tags: &[Tag], limits: &ResourceLimits) -> Result<()> {
+        if tags.len() > limits.max_tags_per_resource {
             return Err(AmiError::InvalidParameter {
-                message: "Cannot have more than 50 tags per resource".to_string(),
+                message: format!(
+                    "Cannot have more than {} tags per resource",
+                    limits.max_tags_per_resource
+                ),
             });
         }
 
@@ -69,12 +107,25 @@ pub mod tag_operations {
                     message: format!("Duplicate tag key: {}", tag.key),
                 });
             }
-            validate_tag(tag)?;
+            validate_tag_with_limits(tag, limits)?;
         }
 
         Ok(())
     }
 
+    /// Tag keys and values may contain letters, digits, whitespace and `_ . : / = + - @`
+    #[allow(clippy::result_large_err)]
+    fn validate_tag_characters(kind: &str, text: &str) -> Result<()> {
+        let is_allowed =
+            |c: char| c.is_alphanumeric() || c.is_whitespace() || "_.:/=+-@".contains(c);
+        match text.chars().find(|c| !is_allowed(*c)) {
+            Some(c) => Err(AmiError::InvalidParameter {
+                message: format!("Tag {} contains invalid character '{}'", kind, c),
+            }),
+            None => Ok(()),
+        }
+    }
+
     /// Merge tags, with new tags overwriting existing ones (pure function)
     pub fn merge_tags(existing: Vec<Tag>, new_tags: Vec<Tag>) -> Vec<Tag> {
         let mut tag_map: std::collections::HashMap<String, String> =
@@ -168,6 +219,45 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_validate_tag_key_rejects_reserved_prefix_in_any_case() {
+        assert!(validate_tag_key("AWS:CreatedBy").is_err());
+        assert!(validate_tag_key("awsome").is_ok());
+    }
+
+    #[test]
+    fn test_validate_tag_characters() {
+        assert!(validate_tag(&make_tag("cost-center", "team@example.com/ops + 1")).is_ok());
+        assert!(validate_tag_key("env;prod").is_err());
+        assert!(validate_tag_value("<script>").is_err());
+    }
+
+    #[test]
+    fn test_validate_tags_with_provider_limits() {
+        use crate::provider::{AwsProvider, CloudProvider, GcpProvider};
+
+        let gcp = GcpProvider::new("my-project");
+        let limits = gcp.resource_limits();
+
+        // A valid tag passes
+        let tag = make_tag("environment", "production");
+        assert!(validate_tags_with_limits(std::slice::from_ref(&tag), limits).is_ok());
+        assert!(gcp.validate_tags(&[tag]).is_ok());
+
+        // A value over the provider's limit is rejected, though AWS would accept it
+        let long_value = make_tag("environment", &"v".repeat(64));
+        assert!(validate_tags_with_limits(std::slice::from_ref(&long_value), limits).is_err());
+        assert!(AwsProvider::default().validate_tags(&[long_value]).is_ok());
+
+        // Reserved prefixes come from the provider too
+        let reserved = make_tag("aws:cloudformation:stack-name", "stack");
+        assert!(AwsProvider::default()
+            .validate_tags(std::slice::from_ref(&reserved))
+            .is_err());
+        assert!(gcp.validate_tags(&[reserved]).is_ok());
+        assert!(gcp.validate_tags(&[make_tag("goog-managed", "x")]).is_err());
+    }
+
     #[test]
     fn test_validate_tags_empty() {
         assert!(validate_tags(&[]).is_ok());
@@ -11,6 +11,7 @@ pub mod operations; // Pure functions (was client.rs)
 // Re-export main types
 pub use authorization::{check_tenant_permission, TenantAction};
 pub use model::{
-    BillingInfo, QuotaMode, Tenant, TenantId, TenantQuotas, TenantStatus, TenantType, TenantUsage,
+    BillingInfo, QuotaMode, Tenant, TenantDeletionSummary, TenantId, TenantQuotas, TenantStatus,
+    TenantType, TenantUsage,
 };
 // TenantClient removed - use pure functions in operations module instead
@@ -118,6 +118,34 @@ impl TenantId {
         other.is_descendant_of(self)
     }
 
+    /// Re-root this ID from one ancestor onto another
+    ///
+    /// The segments of `from` (which must be this ID or one of its ancestors)
+    /// are replaced with those of `to`. Returns None if `from` is not a prefix
+    /// of this ID.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use wami::tenant::TenantId;
+    ///
+    /// let old_parent = TenantId::root();
+    /// let tenant = old_parent.child();
+    /// let new_parent = TenantId::root();
+    ///
+    /// let moved = tenant.rebase(&old_parent, &new_parent).unwrap();
+    /// assert_eq!(moved.parent(), Some(new_parent));
+    /// assert_eq!(moved.segments().last(), tenant.segments().last());
+    /// ```
+    pub fn rebase(&self, from: &TenantId, to: &TenantId) -> Option<TenantId> {
+        if self != from && !self.is_descendant_of(from) {
+            return None;
+        }
+        let mut segments = to.segments.clone();
+        segments.extend_from_slice(&self.segments[from.segments.len()..]);
+        Some(Self { segments })
+    }
+
     /// Get the tenant ID as a string (slash-separated numeric segments)
     /// Aligns with AWS ARN conventions where paths use `/` separator.
     pub fn as_str(&self) -> String {
@@ -376,6 +404,24 @@ pub struct TenantUsage {
     pub include_descendants: bool,
 }
 
+/// Resources removed (or that would be removed) by a recursive tenant deletion
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TenantDeletionSummary {
+    /// Whether this summary describes a dry run (nothing was deleted)
+    pub dry_run: bool,
+    /// Tenants in deletion order (deepest descendants first, the target last)
+    pub tenants: Vec<TenantId>,
+    /// ARNs of the identity and credential resources scoped under the tenants
+    pub resources: Vec<String>,
+}
+
+impl TenantDeletionSummary {
+    /// Total number of tenants and resources covered by the deletion
+    pub fn total(&self) -> usize {
+        self.tenants.len() + self.resources.len()
+    }
+}
+
 #[cfg(test)]
 mod tenant_id_tests {
     use super::*;
@@ -578,6 +624,23 @@ mod tenant_id_tests {
         assert!(!child.is_descendant_of(&other_root));
     }
 
+    #[test]
+    fn test_tenant_id_rebase() {
+        let old_parent = TenantId::from_string("1/2").unwrap();
+        let new_parent = TenantId::from_string("9").unwrap();
+        let grandchild = TenantId::from_string("1/2/3/4").unwrap();
+
+        assert_eq!(
+            grandchild.rebase(&old_parent, &new_parent),
+            Some(TenantId::from_string("9/3/4").unwrap())
+        );
+        assert_eq!(
+            old_parent.rebase(&old_parent, &new_parent),
+            Some(new_parent.clone())
+        );
+        assert_eq!(new_parent.rebase(&old_parent, &grandchild), None);
+    }
+
     #[test]
     fn test_tenant_id_segments_access() {
         let id = TenantId::from_string("100/200/300").unwrap();